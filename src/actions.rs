@@ -0,0 +1,1103 @@
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+use thiserror::Error;
+
+use crate::journal::{civil_from_days, links_to, remove_any, symlink, unix_seconds};
+use crate::{
+    Config, ConfigError, Journal, JournalError, JournalMove, RecoveryEntry, Severity,
+    close_journal, expand_home, extract_bundled, find_committed_journals_since,
+    find_incomplete_journals, find_last_committed_journal, format_utc, home_dir, is_bundled,
+    journal_started, partial_path, read_journal, recover_journal, timed_bar,
+};
+
+type Result<T, E = ActionError> = std::result::Result<T, E>;
+
+/// Errors from applying actions to files and directories
+///
+/// A single item that can't be moved, archived or removed is recoverable: it
+/// gets an error status and the rest of the run continues. Bad action specs and
+/// journal failures are fatal.
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Journal(#[from] JournalError),
+    #[error(
+        "Unknown action '{0}' (use delete, trash, move[:DIR], archive:DIR or compress[:tar.zst|tar.gz])"
+    )]
+    Unknown(String),
+    #[error("Unknown link mode '{0}' (use original or destination)")]
+    UnknownLinkMode(String),
+    #[error("The {0} action needs a directory, e.g. {0}:/Volumes/External")]
+    MissingDirectory(&'static str),
+    #[error("Destination already exists: {}", .0.display())]
+    DestinationExists(PathBuf),
+    #[error("Failed to {action}: {}", path.display())]
+    File {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("tar failed for {}: {message}", path.display())]
+    Tar { path: PathBuf, message: String },
+}
+
+impl ActionError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            ActionError::DestinationExists(_)
+            | ActionError::File { .. }
+            | ActionError::Tar { .. } => Severity::Recoverable,
+            ActionError::Config(e) => e.severity(),
+            ActionError::Journal(e) => e.severity(),
+            _ => Severity::Fatal,
+        }
+    }
+}
+
+fn file(action: &'static str, path: &Path) -> impl FnOnce(io::Error) -> ActionError {
+    let path = path.to_path_buf();
+    move |source| ActionError::File {
+        action,
+        path,
+        source,
+    }
+}
+
+/// Archive formats for the compress action, written and read with `tar`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    #[default]
+    TarZst,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "tar.zst" | "zst" | "zstd" => Some(ArchiveFormat::TarZst),
+            "tar.gz" | "tgz" | "gz" | "gzip" => Some(ArchiveFormat::TarGz),
+            _ => None,
+        }
+    }
+
+    /// Format of an archive written by the compress action
+    fn of_archive(path: &Path) -> Self {
+        if path.to_string_lossy().ends_with(".tar.gz") {
+            ArchiveFormat::TarGz
+        } else {
+            ArchiveFormat::TarZst
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::TarZst => "tar.zst",
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+
+    fn tar_flag(self) -> &'static str {
+        match self {
+            ArchiveFormat::TarZst => "--zstd",
+            ArchiveFormat::TarGz => "--gzip",
+        }
+    }
+}
+
+/// What a clean or organize run does with each selected item
+///
+/// Set with `--action`, or with the `action` key of the mode's config section:
+///
+/// ```toml
+/// [clean]
+/// action = "compress:tar.zst"   # keep old node_modules as archives
+///
+/// [organize]
+/// action = "archive:/Volumes/External/Downloads"
+/// ```
+///
+/// Every action runs through `ActionRunner`, so each gets dry runs,
+/// journaling, recovery and undo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Remove the item for good
+    Delete,
+    /// Move into the user's Trash, from where undo restores it
+    Trash,
+    /// Move into `to`, or to the mode's own destination (organize's category
+    /// folders) when no directory is given
+    Move { to: Option<PathBuf> },
+    /// Move under `to`, keeping the item's path relative to the scanned root
+    Archive { to: PathBuf },
+    /// Pack into an archive where the item would otherwise go, then remove it
+    Compress { format: ArchiveFormat },
+}
+
+impl Action {
+    /// Parse an action spec: `delete`, `trash`, `move[:DIR]`, `archive:DIR` or
+    /// `compress[:tar.zst|tar.gz]`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (name, arg) = match spec.split_once(':') {
+            Some((name, arg)) => (name, Some(arg.trim()).filter(|a| !a.is_empty())),
+            None => (spec, None),
+        };
+        let dir = arg.map(expand_home).transpose()?;
+
+        match (name.trim().to_lowercase().as_str(), dir) {
+            ("delete", None) => Ok(Action::Delete),
+            ("trash", None) => Ok(Action::Trash),
+            ("move", to) => Ok(Action::Move { to }),
+            ("archive", Some(to)) => Ok(Action::Archive { to }),
+            ("archive", None) => Err(ActionError::MissingDirectory("archive")),
+            ("compress", _) => {
+                let format = match arg {
+                    Some(format) => ArchiveFormat::parse(format)
+                        .ok_or_else(|| ActionError::Unknown(spec.to_string()))?,
+                    None => ArchiveFormat::default(),
+                };
+                Ok(Action::Compress { format })
+            }
+            _ => Err(ActionError::Unknown(spec.to_string())),
+        }
+    }
+
+    /// The action from `--action`, else `[<section>] action`, else `default`
+    pub fn load(flag: Option<&str>, section: &str, default: Action) -> Result<Self> {
+        if let Some(spec) = flag {
+            return Self::parse(spec);
+        }
+
+        match Config::load()?.get_str(&format!("{}.action", section)) {
+            Some(spec) => Self::parse(spec),
+            None => Ok(default),
+        }
+    }
+
+    /// Name recorded in journals
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Delete => "delete",
+            Action::Trash => "trash",
+            Action::Move { .. } => "move",
+            Action::Archive { .. } => "archive",
+            Action::Compress { .. } => "compress",
+        }
+    }
+
+    /// Capitalized verb for prompts, e.g. "Compress"
+    pub fn verb(&self) -> &'static str {
+        match self {
+            Action::Delete => "Delete",
+            Action::Trash => "Trash",
+            Action::Move { .. } => "Move",
+            Action::Archive { .. } => "Archive",
+            Action::Compress { .. } => "Compress",
+        }
+    }
+
+    /// Verb for finished items, e.g. "Compressed"
+    pub fn past_tense(&self) -> &'static str {
+        match self {
+            Action::Delete => "Deleted",
+            Action::Trash => "Trashed",
+            Action::Move { .. } => "Moved",
+            Action::Archive { .. } => "Archived",
+            Action::Compress { .. } => "Compressed",
+        }
+    }
+
+    /// Verb for items in progress, e.g. "Compressing"
+    pub fn progressive(&self) -> &'static str {
+        match self {
+            Action::Delete => "Deleting",
+            Action::Trash => "Trashing",
+            Action::Move { .. } => "Moving",
+            Action::Archive { .. } => "Archiving",
+            Action::Compress { .. } => "Compressing",
+        }
+    }
+
+    /// Whether the action only works when the mode supplies a destination
+    pub fn needs_default_destination(&self) -> bool {
+        matches!(self, Action::Move { to: None })
+    }
+
+    /// Where `source` ends up, or `None` when it's deleted
+    ///
+    /// `default` is where the mode itself would put the item (organize's
+    /// category folder); archives and compressed copies follow it so they keep
+    /// the same layout.
+    pub fn destination(
+        &self,
+        source: &Path,
+        root: &Path,
+        default: Option<&Path>,
+    ) -> Result<Option<PathBuf>> {
+        let name = PathBuf::from(source.file_name().unwrap_or_default());
+        let target = default.unwrap_or(source);
+
+        Ok(match self {
+            Action::Delete => None,
+            Action::Trash => Some(trash_destination(source)?),
+            Action::Move { to: Some(dir) } => Some(dir.join(name)),
+            Action::Move { to: None } => Some(
+                default
+                    .ok_or(ActionError::MissingDirectory("move"))?
+                    .to_path_buf(),
+            ),
+            Action::Archive { to } => Some(to.join(target.strip_prefix(root).unwrap_or(&name))),
+            Action::Compress { format } => {
+                let mut archive = target.as_os_str().to_os_string();
+                archive.push(".");
+                archive.push(format.extension());
+                Some(PathBuf::from(archive))
+            }
+        })
+    }
+}
+
+/// The user's Trash: `~/.Trash` on macOS, the freedesktop.org trash elsewhere
+fn trash_dir() -> Result<PathBuf> {
+    let home = home_dir()?;
+    if cfg!(target_os = "macos") {
+        return Ok(home.join(".Trash"));
+    }
+
+    let data = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .unwrap_or_else(|| home.join(".local/share"));
+    Ok(data.join("Trash/files"))
+}
+
+/// Where `source` goes in the Trash
+///
+/// Trashing the same path again, say a `node_modules` that was reinstalled,
+/// gets ` 2`, ` 3`… after `trash_name` instead of colliding with the earlier one.
+fn trash_destination(source: &Path) -> Result<PathBuf> {
+    let files = trash_dir()?;
+    let base = trash_name(source);
+    let taken = |path: &Path| {
+        path.exists()
+            || path.is_symlink()
+            || trash_info_path(path).is_some_and(|info| info.exists())
+    };
+
+    let mut destination = files.join(&base);
+    let mut n = 2;
+    while taken(&destination) {
+        destination = files.join(format!("{} {}", base, n));
+        n += 1;
+    }
+    Ok(destination)
+}
+
+/// Name for `source` in the Trash, unique per original path
+///
+/// Every project has its own `node_modules`, so the name carries the parent
+/// folder and a short hash of the full path, e.g. `node_modules (web 3fa2c1)`.
+fn trash_name(source: &Path) -> String {
+    let name = source.file_name().unwrap_or_default().to_string_lossy();
+    let parent = source
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|p| p.to_string_lossy())
+        .unwrap_or_default();
+    let hash = Sha256::digest(source.as_os_str().as_encoded_bytes());
+    let short: String = hash[..3].iter().map(|b| format!("{:02x}", b)).collect();
+
+    if parent.is_empty() {
+        format!("{} ({})", name, short)
+    } else {
+        format!("{} ({} {})", name, parent, short)
+    }
+}
+
+/// The `.trashinfo` file for an item in the freedesktop.org trash, `None` on macOS
+fn trash_info_path(destination: &Path) -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        return None;
+    }
+    let trash = destination.parent()?.parent()?;
+    let mut name = destination.file_name()?.to_os_string();
+    name.push(".trashinfo");
+    Some(trash.join("info").join(name))
+}
+
+/// Move an item into the Trash
+///
+/// Outside macOS the `.trashinfo` file is written first, as the freedesktop.org
+/// spec asks, so file managers list the item and can restore it themselves.
+fn trash(source: &Path, destination: &Path) -> Result<()> {
+    let Some(info) = trash_info_path(destination) else {
+        return move_path(source, destination);
+    };
+
+    if let Some(parent) = info.parent() {
+        fs::create_dir_all(parent).map_err(file("create directory", parent))?;
+    }
+    let original = std::path::absolute(source).map_err(file("resolve", source))?;
+    let contents = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        trash_info_escape(&original),
+        trash_info_date(SystemTime::now())
+    );
+    fs::File::create_new(&info)
+        .and_then(|mut f| f.write_all(contents.as_bytes()))
+        .map_err(file("write", &info))?;
+
+    move_path(source, destination).inspect_err(|_| {
+        fs::remove_file(&info).ok();
+    })
+}
+
+/// A path percent-encoded for the `Path=` key of a `.trashinfo` file
+fn trash_info_escape(path: &Path) -> String {
+    path.as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .map(|&byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// A time as `2024-05-03T14:02:09` for `DeletionDate=`
+///
+/// The spec asks for local time; without a timezone database this writes UTC,
+/// which only shifts the date file managers show.
+fn trash_info_date(time: SystemTime) -> String {
+    let secs = unix_seconds(time);
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let of_day = secs.rem_euclid(86_400);
+    format!(
+        "{}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        of_day / 3600,
+        of_day / 60 % 60,
+        of_day % 60
+    )
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Move { to: Some(dir) } | Action::Archive { to: dir } => {
+                write!(f, "{}:{}", self.name(), dir.display())
+            }
+            Action::Compress { format } => write!(f, "compress:{}", format.extension()),
+            _ => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+/// Where a linked run leaves a symlink, see `ActionRunner::with_links`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LinkMode {
+    /// Move the item and leave a symlink to it at its original path
+    #[default]
+    Original,
+    /// Leave the item where it is and put a symlink to it at its destination
+    Destination,
+}
+
+impl LinkMode {
+    /// Parse a link mode: `original` or `destination`
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "original" | "back" => Ok(LinkMode::Original),
+            "destination" | "reverse" => Ok(LinkMode::Destination),
+            _ => Err(ActionError::UnknownLinkMode(name.to_string())),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LinkMode::Original => "original",
+            LinkMode::Destination => "destination",
+        }
+    }
+
+    /// Name recorded in journals, in place of the action's
+    fn journal_name(self) -> &'static str {
+        match self {
+            LinkMode::Original => "link",
+            LinkMode::Destination => "symlink",
+        }
+    }
+}
+
+/// Applies one action to every item of a run
+///
+/// Each item is recorded in the mode's journal before it's touched, so
+/// interrupted runs can be recovered and finished ones undone. Dry runs only
+/// report what would happen. The runner can be shared across threads to
+/// process items in parallel.
+pub struct ActionRunner {
+    action: Action,
+    root: PathBuf,
+    journal: Option<Arc<Mutex<Journal>>>,
+    background: Option<BackgroundDeletes>,
+    link: Option<LinkMode>,
+}
+
+/// Deletes running on worker threads, after each item was renamed aside
+///
+/// A rename within a directory is instant however big the tree, so items
+/// leave their paths right away and the slow removal happens here. Each item
+/// is marked done in the journal once it's actually gone.
+struct BackgroundDeletes {
+    queue: Option<Sender<(u64, PathBuf, PathBuf)>>,
+    workers: Vec<JoinHandle<()>>,
+    queued: AtomicUsize,
+    deleted: Arc<AtomicUsize>,
+    failures: Arc<Mutex<Vec<(PathBuf, String)>>>,
+    started: Instant,
+}
+
+/// How the background deletes of a run went, see
+/// `ActionRunner::delete_in_background`
+#[derive(Debug, Default)]
+pub struct BackgroundReport {
+    /// Items removed
+    pub deleted: usize,
+    /// Original path and error of items that were renamed aside but couldn't
+    /// be removed; they're left at their `.deleting` path
+    pub failed: Vec<(PathBuf, String)>,
+    /// From the first rename until the last removal finished
+    pub elapsed: Duration,
+}
+
+impl BackgroundDeletes {
+    fn start(journal: Arc<Mutex<Journal>>, workers: usize) -> Self {
+        let (queue, items) = mpsc::channel::<(u64, PathBuf, PathBuf)>();
+        let items: Arc<Mutex<Receiver<_>>> = Arc::new(Mutex::new(items));
+        let deleted = Arc::new(AtomicUsize::new(0));
+        let failures = Arc::new(Mutex::new(Vec::new()));
+
+        let workers = (0..workers.max(1))
+            .map(|_| {
+                let items = Arc::clone(&items);
+                let journal = Arc::clone(&journal);
+                let deleted = Arc::clone(&deleted);
+                let failures = Arc::clone(&failures);
+                thread::spawn(move || {
+                    loop {
+                        // The lock is released before the removal starts
+                        let item = items.lock().unwrap_or_else(PoisonError::into_inner).recv();
+                        let Ok((id, source, staging)) = item else {
+                            break;
+                        };
+                        let result =
+                            remove_path(&staging).and_then(|_| Ok(lock(&journal).record_done(id)?));
+                        match result {
+                            Ok(()) => {
+                                deleted.fetch_add(1, Ordering::Relaxed);
+                            }
+                            Err(e) => failures
+                                .lock()
+                                .unwrap_or_else(PoisonError::into_inner)
+                                .push((source, error_chain(&e))),
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            queue: Some(queue),
+            workers,
+            queued: AtomicUsize::new(0),
+            deleted,
+            failures,
+            started: Instant::now(),
+        }
+    }
+
+    fn push(&self, id: u64, source: &Path, staging: PathBuf) {
+        if let Some(queue) = &self.queue
+            && queue.send((id, source.to_path_buf(), staging)).is_ok()
+        {
+            self.queued.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Wait for every queued item, counting them on a bar
+    fn finish(mut self) -> BackgroundReport {
+        drop(self.queue.take());
+        let queued = self.queued.load(Ordering::Relaxed);
+        let bar = timed_bar(queued as u64).with_message("deleted");
+        while self.workers.iter().any(|worker| !worker.is_finished()) {
+            let failed = self.failures.lock().map(|f| f.len()).unwrap_or(0);
+            bar.set_position((self.deleted.load(Ordering::Relaxed) + failed) as u64);
+            thread::sleep(Duration::from_millis(50));
+        }
+        for worker in self.workers.drain(..) {
+            worker.join().ok();
+        }
+        bar.finish_and_clear();
+
+        BackgroundReport {
+            deleted: self.deleted.load(Ordering::Relaxed),
+            failed: std::mem::take(
+                &mut *self.failures.lock().unwrap_or_else(PoisonError::into_inner),
+            ),
+            elapsed: self.started.elapsed(),
+        }
+    }
+}
+
+/// An error with its causes (permission denied, busy, ...), which are only in
+/// the source chain
+pub fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(&format!(": {}", cause));
+        source = cause.source();
+    }
+    message
+}
+
+/// Hidden sibling an item is renamed to before it's deleted in the background
+///
+/// It stays on the same filesystem, so the rename is atomic and instant.
+pub fn deleting_path(source: &Path) -> PathBuf {
+    let name = source.file_name().unwrap_or_default().to_string_lossy();
+    source.with_file_name(format!(".{}.deleting-{}", name, std::process::id()))
+}
+
+impl ActionRunner {
+    /// Start a run over the items found under `root`
+    pub fn begin(kind: &str, action: Action, root: &Path, dry_run: bool) -> Result<Self> {
+        let journal = if dry_run {
+            None
+        } else {
+            Some(Arc::new(Mutex::new(Journal::begin(kind)?)))
+        };
+
+        Ok(Self {
+            action,
+            root: root.to_path_buf(),
+            journal,
+            background: None,
+            link: None,
+        })
+    }
+
+    /// Leave a symlink for every moved or archived item, so apps that still
+    /// use the original path keep working; other actions are unaffected
+    ///
+    /// Linked items are journaled as `link` or `symlink` rather than the
+    /// action, and undo removes the symlink along with moving the item back.
+    pub fn with_links(mut self, mode: LinkMode) -> Self {
+        if matches!(self.action, Action::Move { .. } | Action::Archive { .. }) {
+            self.link = Some(mode);
+        }
+        self
+    }
+
+    /// Delete by renaming each item aside and removing it on `workers`
+    /// background threads, so `apply` returns as soon as the item is out of
+    /// the way
+    ///
+    /// Only the delete action of a real run changes; `finish_background`
+    /// waits for the removals.
+    pub fn delete_in_background(mut self, workers: usize) -> Self {
+        if self.action == Action::Delete
+            && let Some(journal) = &self.journal
+        {
+            self.background = Some(BackgroundDeletes::start(Arc::clone(journal), workers));
+        }
+        self
+    }
+
+    /// Whether deletes are finished on background threads
+    pub fn deletes_in_background(&self) -> bool {
+        self.background.is_some()
+    }
+
+    /// Wait for the background deletes, if there are any
+    pub fn finish_background(&mut self) -> Option<BackgroundReport> {
+        self.background.take().map(BackgroundDeletes::finish)
+    }
+
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
+
+    /// Where items are linked, see `with_links`
+    pub fn links(&self) -> Option<LinkMode> {
+        self.link
+    }
+
+    /// Where `source` ends up, see `Action::destination`
+    pub fn destination(&self, source: &Path, default: Option<&Path>) -> Result<Option<PathBuf>> {
+        self.action.destination(source, &self.root, default)
+    }
+
+    /// Apply the action to one item, returning its status
+    ///
+    /// `default` is where the mode would move the item, see
+    /// `Action::destination`. Existing destinations are never overwritten.
+    pub fn apply(&self, source: &Path, default: Option<&Path>) -> Result<String> {
+        let destination = self.destination(source, default)?;
+
+        let Some(journal) = &self.journal else {
+            return Ok(match self.link {
+                Some(LinkMode::Original) => format!("Would {} and link", self.action.name()),
+                Some(LinkMode::Destination) => "Would link".to_string(),
+                None => format!("Would {}", self.action.name()),
+            });
+        };
+
+        if let Some(destination) = &destination
+            && (destination.exists() || destination.is_symlink())
+        {
+            return Err(ActionError::DestinationExists(destination.clone()));
+        }
+
+        if let Some(background) = &self.background {
+            // Done is recorded by the worker, once the renamed item is gone
+            let staging = deleting_path(source);
+            if staging.exists() || staging.is_symlink() {
+                return Err(ActionError::DestinationExists(staging));
+            }
+            let id = lock(journal).record_action("delete", source, Some(&staging))?;
+            fs::rename(source, &staging).map_err(file("move aside", source))?;
+            background.push(id, source, staging);
+            return Ok("✓ Deleted".to_string());
+        }
+
+        let name = self.link.map_or(self.action.name(), LinkMode::journal_name);
+        let id = lock(journal).record_action(name, source, destination.as_deref())?;
+
+        match (&self.action, destination.as_deref(), self.link) {
+            (_, Some(destination), Some(LinkMode::Original)) => {
+                move_path(source, destination)?;
+                symlink(destination, source).map_err(file("link", source))?;
+            }
+            (_, Some(destination), Some(LinkMode::Destination)) => {
+                if let Some(parent) = destination.parent() {
+                    fs::create_dir_all(parent).map_err(file("create directory", parent))?;
+                }
+                symlink(source, destination).map_err(file("link", destination))?;
+            }
+            (Action::Compress { format }, Some(archive), _) => {
+                compress(source, archive, *format)?;
+                remove_path(source)?;
+            }
+            (Action::Trash, Some(destination), None) => trash(source, destination)?,
+            (_, Some(destination), None) => move_path(source, destination)?,
+            (_, None, _) => remove_path(source)?,
+        }
+
+        lock(journal).record_done(id)?;
+        Ok(match self.link {
+            Some(LinkMode::Original) => format!("✓ {} and linked", self.action.past_tense()),
+            Some(LinkMode::Destination) => "✓ Linked".to_string(),
+            None => format!("✓ {}", self.action.past_tense()),
+        })
+    }
+
+    /// Move a file that's about to be replaced into the Trash, returning where
+    /// it went
+    ///
+    /// The move is journaled as its own item, so undo puts the replaced file
+    /// back after moving its replacement away.
+    pub fn trash_replaced(&self, path: &Path) -> Result<PathBuf> {
+        let destination = trash_destination(path)?;
+        let Some(journal) = &self.journal else {
+            return Ok(destination);
+        };
+
+        let id = lock(journal).record_action(Action::Trash.name(), path, Some(&destination))?;
+        trash(path, &destination)?;
+        lock(journal).record_done(id)?;
+        Ok(destination)
+    }
+
+    /// Bring back a file `trash_replaced` moved away, when its replacement
+    /// couldn't be put in its place
+    pub fn restore_replaced(&self, trashed: &Path, path: &Path) -> Result<()> {
+        let Some(journal) = &self.journal else {
+            return Ok(());
+        };
+
+        let id = lock(journal).record_action("move", trashed, Some(path))?;
+        move_path(trashed, path)?;
+        if let Some(info) = trash_info_path(trashed) {
+            fs::remove_file(info).ok();
+        }
+        lock(journal).record_done(id)?;
+        Ok(())
+    }
+
+    /// Mark the run as finished cleanly
+    ///
+    /// Waits for background deletes that `finish_background` didn't.
+    pub fn commit(mut self) -> Result<()> {
+        self.finish_background();
+        if let Some(journal) = self.journal {
+            lock(&journal).commit()?;
+        }
+        Ok(())
+    }
+}
+
+fn lock(journal: &Mutex<Journal>) -> MutexGuard<'_, Journal> {
+    journal.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    remove_any(path).map_err(file("remove", path))
+}
+
+/// Move a file or directory, copying through a staging path across filesystems
+fn move_path(source: &Path, destination: &Path) -> Result<()> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(file("create directory", parent))?;
+    }
+
+    if fs::rename(source, destination).is_ok() {
+        return Ok(());
+    }
+
+    let partial = partial_path(destination);
+    if let Err(e) = copy_path(source, &partial) {
+        remove_any(&partial).ok();
+        return Err(e);
+    }
+    fs::rename(&partial, destination).map_err(file("move into place", destination))?;
+    remove_path(source)
+}
+
+/// Copy a file or directory tree, recreating symlinks rather than following them
+fn copy_path(source: &Path, destination: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(source).map_err(file("read", source))?;
+
+    if metadata.is_symlink() {
+        copy_symlink(source, destination).map_err(file("copy link to", destination))?;
+    } else if metadata.is_dir() {
+        fs::create_dir(destination).map_err(file("create directory", destination))?;
+        for entry in fs::read_dir(source).map_err(file("read directory", source))? {
+            let entry = entry.map_err(file("read directory", source))?;
+            copy_path(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(source, destination).map_err(file("copy to", destination))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(source: &Path, destination: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(source)?, destination)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(source: &Path, destination: &Path) -> io::Result<()> {
+    fs::copy(source, destination).map(|_| ())
+}
+
+/// Run tar, turning a failed exit into an error with its stderr
+fn run_tar(args: &[&std::ffi::OsStr], path: &Path) -> Result<()> {
+    let output = Command::new("tar")
+        .args(args)
+        .output()
+        .map_err(file("run tar for", path))?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    Err(ActionError::Tar {
+        path: path.to_path_buf(),
+        message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    })
+}
+
+/// Pack a file or directory into an archive holding just its name
+fn compress(source: &Path, archive: &Path, format: ArchiveFormat) -> Result<()> {
+    if let Some(parent) = archive.parent() {
+        fs::create_dir_all(parent).map_err(file("create directory", parent))?;
+    }
+
+    let parent = source
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let partial = partial_path(archive);
+
+    let result = run_tar(
+        &[
+            "-c".as_ref(),
+            format.tar_flag().as_ref(),
+            "-f".as_ref(),
+            partial.as_os_str(),
+            "-C".as_ref(),
+            parent.as_os_str(),
+            source.file_name().unwrap_or_default(),
+        ],
+        source,
+    );
+    if let Err(e) = result {
+        fs::remove_file(&partial).ok();
+        return Err(e);
+    }
+
+    fs::rename(&partial, archive).map_err(file("move into place", archive))
+}
+
+/// Extract an archive written by `compress` back to `target`
+fn extract(archive: &Path, target: &Path) -> Result<()> {
+    let staging = partial_path(target);
+    fs::create_dir_all(&staging).map_err(file("create directory", &staging))?;
+
+    let result = run_tar(
+        &[
+            "-x".as_ref(),
+            ArchiveFormat::of_archive(archive).tar_flag().as_ref(),
+            "-f".as_ref(),
+            archive.as_os_str(),
+            "-C".as_ref(),
+            staging.as_os_str(),
+        ],
+        archive,
+    )
+    .and_then(|_| {
+        fs::rename(staging.join(target.file_name().unwrap_or_default()), target)
+            .map_err(file("restore", target))
+    });
+
+    remove_any(&staging).ok();
+    result
+}
+
+/// Warn about runs of a kind that were interrupted before finishing
+pub fn warn_incomplete_runs(kind: &str) {
+    let count = find_incomplete_journals(kind)
+        .map(|journals| journals.len())
+        .unwrap_or(0);
+
+    if count > 0 {
+        println!(
+            "{} Found {} interrupted {} run(s) with unfinished items.",
+            "⚠️".yellow(),
+            count.to_string().yellow(),
+            kind
+        );
+        println!(
+            "   {} Run with --subcommand recover to resolve them",
+            "💡".yellow()
+        );
+    }
+}
+
+/// Resolve unfinished items left behind by interrupted runs of a kind
+pub fn recover_runs(kind: &str, dry_run: bool) -> Result<Vec<RecoveryEntry>> {
+    let journals = find_incomplete_journals(kind)?;
+
+    if journals.is_empty() {
+        println!("{} No interrupted {} runs found.", "✓".green(), kind);
+        return Ok(Vec::new());
+    }
+
+    println!(
+        "{} Found {} interrupted {} run(s)",
+        "🔍".cyan(),
+        journals.len().to_string().yellow(),
+        kind
+    );
+
+    if dry_run {
+        println!("{} Dry run mode - no files will be changed\n", "🔍".cyan());
+    }
+
+    let mut results = Vec::new();
+    for journal in journals {
+        results.extend(recover_journal(&journal, dry_run)?);
+    }
+
+    Ok(results)
+}
+
+/// Turn a recoverable per-item failure into an error status; fatal errors abort the run
+fn recoverable_status(result: Result<String>) -> Result<String> {
+    match result {
+        Err(e) if e.severity() == Severity::Recoverable => Ok(format!("✗ Error: {}", e)),
+        result => result,
+    }
+}
+
+/// Put one item of an undone run back where it was
+fn undo_item(entry: &JournalMove) -> Result<String> {
+    if entry.action == "compress" {
+        extract(&entry.destination, &entry.source)?;
+        remove_path(&entry.destination)?;
+        Ok("✓ Extracted".to_string())
+    } else if entry.action == "trash" {
+        move_path(&entry.destination, &entry.source)?;
+        if let Some(info) = trash_info_path(&entry.destination) {
+            fs::remove_file(info).ok();
+        }
+        Ok("✓ Restored from Trash".to_string())
+    } else if entry.action == "symlink" {
+        remove_path(&entry.destination)?;
+        Ok("✓ Removed link".to_string())
+    } else if entry.action == "bundle" {
+        extract_bundled(&entry.destination, &entry.source)
+            .map_err(file("extract", &entry.source))?;
+        Ok("✓ Extracted".to_string())
+    } else {
+        if entry.action == "link" && links_to(&entry.source, &entry.destination) {
+            remove_path(&entry.source)?;
+        }
+        move_path(&entry.destination, &entry.source)?;
+        Ok("✓ Moved back".to_string())
+    }
+}
+
+/// Undo the most recent finished run of a kind, newest item first
+///
+/// Moved and archived items go back to their original paths and compressed
+/// ones are extracted again, removing the archive; symlinks left by linked runs
+/// are removed and files packed into zip bundles are extracted, leaving the
+/// bundle as it is. Deletes can't be undone and are only reported. The run is marked undone once every item is back, so a
+/// failed item can be retried by running undo again.
+pub fn undo_last_run(kind: &str, dry_run: bool) -> Result<Vec<RecoveryEntry>> {
+    let Some(journal) = find_last_committed_journal(kind)? else {
+        println!("{} No finished {} runs to undo.", "✓".green(), kind);
+        return Ok(Vec::new());
+    };
+
+    if dry_run {
+        println!("{} Dry run mode - no files will be changed\n", "🔍".cyan());
+    }
+    undo_journal(&journal, dry_run)
+}
+
+/// Undo every finished run of a kind started at or after `since`, newest
+/// first, like `undo_last_run`
+pub fn undo_runs_since(kind: &str, since: SystemTime, dry_run: bool) -> Result<Vec<RecoveryEntry>> {
+    let journals = find_committed_journals_since(kind, since)?;
+    if journals.is_empty() {
+        println!(
+            "{} No finished {} runs since {} to undo.",
+            "✓".green(),
+            kind,
+            format_utc(since)
+        );
+        return Ok(Vec::new());
+    }
+
+    println!(
+        "{} Undoing {} {} run(s) since {}",
+        "↩️".cyan(),
+        journals.len().to_string().yellow(),
+        kind,
+        format_utc(since)
+    );
+    if dry_run {
+        println!("{} Dry run mode - no files will be changed\n", "🔍".cyan());
+    }
+
+    let mut results = Vec::new();
+    for journal in journals {
+        results.extend(undo_journal(&journal, dry_run)?);
+    }
+    Ok(results)
+}
+
+/// Undo one finished run, newest item first, see `undo_last_run`
+fn undo_journal(journal: &Path, dry_run: bool) -> Result<Vec<RecoveryEntry>> {
+    let (moves, _) = read_journal(journal)?;
+    let completed: Vec<&JournalMove> = moves.iter().rev().filter(|m| m.completed).collect();
+    if let Some(started) = journal_started(journal) {
+        println!(
+            "{} Run from {} ({} items)",
+            "↩️".cyan(),
+            format_utc(started).bold(),
+            completed.len()
+        );
+    }
+
+    let mut results = Vec::new();
+    let mut failed = false;
+
+    for entry in completed {
+        // The symlink left at the original path doesn't count as taking it
+        let linked_back = entry.action == "link" && links_to(&entry.source, &entry.destination);
+        let action = if entry.action == "delete" {
+            "✗ Deleted, can't be undone".to_string()
+        } else if entry.action == "symlink" && !links_to(&entry.destination, &entry.source) {
+            "Already restored".to_string()
+        } else if entry.action == "symlink" && dry_run {
+            "Would remove link".to_string()
+        } else if entry.action == "symlink" {
+            let status = recoverable_status(undo_item(entry))?;
+            failed |= status.starts_with('✗');
+            status
+        } else if entry.action == "bundle" {
+            // The destination is the file's path inside its zip
+            if entry.source.exists() {
+                "✗ Original path is taken, left in place".to_string()
+            } else if !is_bundled(&entry.destination) {
+                "✗ Missing from the bundle, can't be restored".to_string()
+            } else if dry_run {
+                "Would extract".to_string()
+            } else {
+                let status = recoverable_status(undo_item(entry))?;
+                failed |= status.starts_with('✗');
+                status
+            }
+        } else if entry.source.exists() && !entry.destination.exists() {
+            "Already restored".to_string()
+        } else if entry.source.exists() && !linked_back {
+            "✗ Original path is taken, left in place".to_string()
+        } else if !entry.destination.exists() {
+            "✗ Missing, can't be restored".to_string()
+        } else if dry_run {
+            if entry.action == "compress" {
+                "Would extract".to_string()
+            } else {
+                "Would move back".to_string()
+            }
+        } else {
+            let status = recoverable_status(undo_item(entry))?;
+            failed |= status.starts_with('✗');
+            status
+        };
+
+        results.push(RecoveryEntry {
+            source: entry.source.display().to_string(),
+            destination: entry.destination.display().to_string(),
+            action,
+        });
+    }
+
+    if !dry_run && !failed {
+        close_journal(journal, "undone")?;
+    }
+
+    Ok(results)
+}
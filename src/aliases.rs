@@ -1,10 +1,38 @@
-use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::io;
 use std::process::Command;
+use std::string::FromUtf8Error;
 use tabled::Tabled;
+use thiserror::Error;
+
+use crate::{Config, ConfigError, Severity, home_dir};
+
+type Result<T, E = AliasesError> = std::result::Result<T, E>;
+
+/// Errors from collecting aliases
+///
+/// Failing to query the running shell is recoverable (config files are still
+/// read); a missing HOME is fatal.
+#[derive(Debug, Error)]
+pub enum AliasesError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("Failed to execute alias command")]
+    Command(#[source] io::Error),
+    #[error("Failed to parse alias output")]
+    Output(#[source] FromUtf8Error),
+}
+
+impl AliasesError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            AliasesError::Config(e) => e.severity(),
+            _ => Severity::Recoverable,
+        }
+    }
+}
 
 #[derive(Tabled)]
 pub struct AliasEntry {
@@ -50,21 +78,24 @@ pub fn get_all_aliases() -> Result<Vec<AliasEntry>> {
 }
 
 fn get_shell_aliases() -> Result<HashMap<String, String>> {
-    // Try to get current shell from environment
-    let shell = env::var("SHELL").unwrap_or_else(|_| "/bin/bash".to_string());
+    // `[aliases] shell` wins over the current shell from the environment
+    let shell = match Config::load()?.get_str("aliases.shell") {
+        Some(shell) => shell.to_string(),
+        None => env::var("SHELL").unwrap_or_else(|_| "/bin/bash".to_string()),
+    };
     
     let output = Command::new(&shell)
         .arg("-c")
         .arg("alias")
         .output()
-        .context("Failed to execute alias command")?;
+        .map_err(AliasesError::Command)?;
     
     if !output.status.success() {
         return Ok(HashMap::new());
     }
     
     let alias_output = String::from_utf8(output.stdout)
-        .context("Failed to parse alias output")?;
+        .map_err(AliasesError::Output)?;
     
     parse_alias_output(&alias_output)
 }
@@ -112,7 +143,7 @@ fn parse_alias_line(line: &str) -> Option<(String, String)> {
 
 fn get_config_file_aliases() -> Result<Vec<(String, String, String)>> {
     let mut aliases = Vec::new();
-    let home_dir = env::var("HOME").context("HOME environment variable not set")?;
+    let home_dir = home_dir()?;
     
     // Common shell configuration files
     let config_files = vec![
@@ -126,14 +157,14 @@ fn get_config_file_aliases() -> Result<Vec<(String, String, String)>> {
     ];
     
     for config_file in config_files {
-        let file_path = PathBuf::from(&home_dir).join(config_file);
+        let file_path = home_dir.join(config_file);
         
-        if file_path.exists() {
-            if let Ok(content) = fs::read_to_string(&file_path) {
-                let file_aliases = parse_config_file_aliases(&content);
-                for (alias, command) in file_aliases {
-                    aliases.push((alias, command, config_file.to_string()));
-                }
+        if file_path.exists()
+            && let Ok(content) = fs::read_to_string(&file_path)
+        {
+            let file_aliases = parse_config_file_aliases(&content);
+            for (alias, command) in file_aliases {
+                aliases.push((alias, command, config_file.to_string()));
             }
         }
     }
@@ -153,10 +184,10 @@ fn parse_config_file_aliases(content: &str) -> Vec<(String, String)> {
         }
         
         // Look for alias definitions
-        if line.starts_with("alias ") {
-            if let Some((alias, command)) = parse_alias_line(line) {
-                aliases.push((alias, command));
-            }
+        if line.starts_with("alias ")
+            && let Some((alias, command)) = parse_alias_line(line)
+        {
+            aliases.push((alias, command));
         }
     }
     
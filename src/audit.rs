@@ -0,0 +1,347 @@
+use rayon::prelude::*;
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use tabled::Tabled;
+
+use crate::{PackageEntry, PackagesError, Version, count_bar, error_chain};
+
+type Result<T, E = PackagesError> = std::result::Result<T, E>;
+
+const OSV_API: &str = "https://api.osv.dev/v1";
+
+/// Most queries OSV.dev takes in one batch
+const OSV_BATCH_SIZE: usize = 1000;
+
+/// The ecosystem OSV files a manifest's dependencies under, by package type
+pub fn osv_ecosystem(package_type: &str) -> Option<&'static str> {
+    match package_type {
+        "npm" => Some("npm"),
+        "cargo" => Some("crates.io"),
+        "pip" | "poetry" => Some("PyPI"),
+        "composer" => Some("Packagist"),
+        "go" => Some("Go"),
+        _ => None,
+    }
+}
+
+/// An advisory's severity, as GitHub's advisory database rates it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AdvisorySeverity {
+    Critical,
+    High,
+    Moderate,
+    Low,
+    /// Not a vulnerability but a notice, e.g. RustSec's unmaintained crates
+    Informational,
+    Unrated,
+}
+
+impl AdvisorySeverity {
+    pub fn label(self) -> &'static str {
+        match self {
+            AdvisorySeverity::Critical => "Critical",
+            AdvisorySeverity::High => "High",
+            AdvisorySeverity::Moderate => "Moderate",
+            AdvisorySeverity::Low => "Low",
+            AdvisorySeverity::Informational => "Informational",
+            AdvisorySeverity::Unrated => "Unrated",
+        }
+    }
+}
+
+/// What an advisory says, from its OSV record
+#[derive(Debug, Clone)]
+struct Advisory {
+    summary: String,
+    /// Other IDs it goes by, CVEs first
+    aliases: Vec<String>,
+    severity: AdvisorySeverity,
+    /// The first fixed version per affected package, by lowercased name
+    fixed: HashMap<String, String>,
+}
+
+impl Advisory {
+    fn from_json(record: &serde_json::Value) -> Self {
+        fn text(value: Option<&serde_json::Value>) -> Option<&str> {
+            value.and_then(|v| v.as_str())
+        }
+
+        let mut aliases: Vec<String> = record
+            .get("aliases")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect();
+        aliases.sort_by_key(|alias| !alias.starts_with("CVE-"));
+
+        let affected = record
+            .get("affected")
+            .and_then(|v| v.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let informational = affected.iter().any(|a| {
+            text(a.pointer("/database_specific/informational")).is_some()
+                || text(a.pointer("/ecosystem_specific/informational")).is_some()
+        });
+        let severity = match text(record.pointer("/database_specific/severity"))
+            .map(str::to_ascii_uppercase)
+            .as_deref()
+        {
+            Some("CRITICAL") => AdvisorySeverity::Critical,
+            Some("HIGH") => AdvisorySeverity::High,
+            Some("MODERATE" | "MEDIUM") => AdvisorySeverity::Moderate,
+            Some("LOW") => AdvisorySeverity::Low,
+            _ if informational => AdvisorySeverity::Informational,
+            _ => AdvisorySeverity::Unrated,
+        };
+
+        let mut fixed = HashMap::new();
+        for entry in affected {
+            let Some(name) = text(entry.pointer("/package/name")) else {
+                continue;
+            };
+            let first_fix = entry
+                .get("ranges")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|range| range.get("events")?.as_array())
+                .flatten()
+                .find_map(|event| text(event.get("fixed")));
+            if let Some(version) = first_fix {
+                fixed
+                    .entry(name.to_lowercase())
+                    .or_insert_with(|| version.to_string());
+            }
+        }
+
+        Self {
+            summary: text(record.get("summary"))
+                .or_else(|| text(record.get("details")))
+                .map(|s| s.lines().next().unwrap_or_default().to_string())
+                .unwrap_or_default(),
+            aliases,
+            severity,
+            fixed,
+        }
+    }
+}
+
+/// Row of the `packages audit` report
+#[derive(Tabled, Clone)]
+pub struct AuditEntry {
+    #[tabled(rename = "Package")]
+    pub name: String,
+    #[tabled(rename = "Version")]
+    pub version: String,
+    #[tabled(rename = "Advisory")]
+    pub advisory: String,
+    #[tabled(rename = "Severity")]
+    pub label: String,
+    #[tabled(rename = "Summary")]
+    pub summary: String,
+    #[tabled(rename = "Fixed In")]
+    pub fixed: String,
+    #[tabled(rename = "File")]
+    pub file_path: String,
+    #[tabled(skip)]
+    pub id: String,
+    #[tabled(skip)]
+    pub aliases: Vec<String>,
+    #[tabled(skip)]
+    pub severity: AdvisorySeverity,
+}
+
+impl AuditEntry {
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "name": self.name,
+            "version": self.version,
+            "id": self.id,
+            "aliases": self.aliases,
+            "severity": self.severity.label(),
+            "summary": self.summary,
+            "fixed": (self.fixed != "-").then_some(&self.fixed),
+            "file": self.file_path,
+        })
+    }
+}
+
+/// The version OSV is asked about for a manifest's requirement: the lowest
+/// it allows, so `^17.0.2` is checked as 17.0.2
+fn audited_version(package: &PackageEntry) -> Option<String> {
+    let version = Version::parse(&package.version).ok()?.to_string();
+    // Go module versions keep their `v`
+    Some(if package.package_type == "go" {
+        format!("v{}", version)
+    } else {
+        version
+    })
+}
+
+/// The IDs of the advisories affecting each `(ecosystem, name, version)`
+fn query_osv(
+    client: &Client,
+    queries: &[(&'static str, String, String)],
+) -> Result<Vec<Vec<String>>> {
+    let osv_error = |source| PackagesError::Registry {
+        registry: "OSV.dev",
+        package: "advisories".to_string(),
+        source,
+    };
+
+    let mut ids = Vec::with_capacity(queries.len());
+    for batch in queries.chunks(OSV_BATCH_SIZE) {
+        let body = json!({
+            "queries": batch
+                .iter()
+                .map(|(ecosystem, name, version)| json!({
+                    "package": { "name": name, "ecosystem": ecosystem },
+                    "version": version,
+                }))
+                .collect::<Vec<_>>(),
+        });
+        let response = client
+            .post(format!("{}/querybatch", OSV_API))
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.text())
+            .map_err(osv_error)?;
+        let response: serde_json::Value = serde_json::from_str(&response)?;
+
+        let results = response
+            .get("results")
+            .and_then(|v| v.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for index in 0..batch.len() {
+            ids.push(
+                results
+                    .get(index)
+                    .and_then(|result| result.get("vulns")?.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|vuln| vuln.get("id")?.as_str().map(str::to_string))
+                    .collect(),
+            );
+        }
+    }
+    Ok(ids)
+}
+
+fn fetch_advisory(client: &Client, id: &str) -> Result<Advisory> {
+    let body = client
+        .get(format!("{}/vulns/{}", OSV_API, id))
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .map_err(|source| PackagesError::Registry {
+            registry: "OSV.dev",
+            package: id.to_string(),
+            source,
+        })?;
+    Ok(Advisory::from_json(&serde_json::from_str(&body)?))
+}
+
+/// Check each dependency's version against the OSV.dev database, which
+/// includes the GitHub, PyPI, Go and RustSec advisories
+///
+/// Dependencies whose version doesn't parse (`*`, a git URL) or whose
+/// ecosystem OSV doesn't know are left out. Each advisory's details are
+/// fetched once, in parallel; one that can't be is still reported, unrated.
+/// Rows come most severe first.
+pub fn audit_packages(
+    packages: &[PackageEntry],
+    client: &Client,
+    verbose: bool,
+) -> Result<Vec<AuditEntry>> {
+    let audited: Vec<(&PackageEntry, &'static str, String)> = packages
+        .iter()
+        .filter_map(|p| Some((p, osv_ecosystem(&p.package_type)?, audited_version(p)?)))
+        .collect();
+    let queries: Vec<(&'static str, String, String)> = audited
+        .iter()
+        .map(|(p, ecosystem, version)| (*ecosystem, p.name.clone(), version.clone()))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    if verbose {
+        println!(
+            "🛡️  Asking OSV.dev about {} package versions",
+            queries.len()
+        );
+    }
+    let results: HashMap<(&'static str, String, String), Vec<String>> = queries
+        .iter()
+        .cloned()
+        .zip(query_osv(client, &queries)?)
+        .collect();
+
+    let ids: Vec<&String> = results
+        .values()
+        .flatten()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let bar = count_bar(ids.len() as u64);
+    let advisories: HashMap<&String, Advisory> = ids
+        .into_par_iter()
+        .map(|id| {
+            let advisory = fetch_advisory(client, id).unwrap_or_else(|e| {
+                if verbose {
+                    println!("  ✗ {}", error_chain(&e));
+                }
+                Advisory {
+                    summary: "Couldn't fetch the advisory".to_string(),
+                    aliases: Vec::new(),
+                    severity: AdvisorySeverity::Unrated,
+                    fixed: HashMap::new(),
+                }
+            });
+            bar.inc(1);
+            (id, advisory)
+        })
+        .collect();
+    bar.finish_and_clear();
+
+    let mut entries = Vec::new();
+    for (package, ecosystem, version) in &audited {
+        let key = (*ecosystem, package.name.clone(), version.clone());
+        for id in results.get(&key).into_iter().flatten() {
+            let advisory = &advisories[id];
+            entries.push(AuditEntry {
+                name: package.name.clone(),
+                version: package.version.clone(),
+                advisory: std::iter::once(id.as_str())
+                    .chain(advisory.aliases.iter().map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                label: advisory.severity.label().to_string(),
+                summary: advisory.summary.clone(),
+                fixed: advisory
+                    .fixed
+                    .get(&package.name.to_lowercase())
+                    .cloned()
+                    .unwrap_or_else(|| "-".to_string()),
+                file_path: package.file_path.clone(),
+                id: id.clone(),
+                aliases: advisory.aliases.clone(),
+                severity: advisory.severity,
+            });
+        }
+    }
+    entries.sort_by(|a, b| {
+        a.severity
+            .cmp(&b.severity)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.id.cmp(&b.id))
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+    Ok(entries)
+}
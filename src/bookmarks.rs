@@ -1,18 +1,132 @@
-use anyhow::{Context, Result};
 use colored::Colorize;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use indicatif::MultiProgress;
 use rayon::prelude::*;
-use std::collections::{HashMap, HashSet};
-use std::fs;
-use std::path::PathBuf;
-use std::sync::Arc;
+use reqwest::blocking::Client;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write as _};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::time::Duration;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use tabled::Tabled;
+use thiserror::Error;
+
+use crate::{
+    CONFIG_RULE_PRIORITY, CategoryRule, Config, ConfigError, Field, HttpError, RuleSet, RuleSource,
+    Severity, Term, TreeNode, builtin_rules, data_dir, expand_home, home_dir, status_line,
+    timed_bar, write_bookmarks_file,
+};
+
+type Result<T, E = BookmarksError> = std::result::Result<T, E>;
+
+/// Errors from reading, checking and rewriting Chrome bookmarks
+///
+/// Network failures for a single bookmark are recoverable: the bookmark gets
+/// an error status and the run goes on. Everything that touches the bookmarks
+/// file, the config or local state is fatal.
+#[derive(Debug, Error)]
+pub enum BookmarksError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Http(#[from] HttpError),
+    #[error("{0}")]
+    InvalidConfig(String),
+    #[error(
+        "Chrome bookmarks file not found at: {}\n\
+         Make sure Chrome is installed and you have bookmarks saved.",
+        .0.display()
+    )]
+    NotFound(PathBuf),
+    #[error("Failed to {action}: {}", path.display())]
+    File {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to parse {what}: {}", path.display())]
+    Parse {
+        what: &'static str,
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("No category snapshot found; run recategorize once to create one")]
+    NoSnapshot,
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    #[error("{0}")]
+    Unresolved(String),
+    #[error("Failed to open {url} with {program}")]
+    Open {
+        program: &'static str,
+        url: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl BookmarksError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            BookmarksError::Request(_)
+            | BookmarksError::Unresolved(_)
+            | BookmarksError::Open { .. } => Severity::Recoverable,
+            BookmarksError::Config(e) => e.severity(),
+            BookmarksError::Http(e) => e.severity(),
+            _ => Severity::Fatal,
+        }
+    }
+
+    fn file(action: &'static str, path: impl AsRef<Path>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.as_ref().to_path_buf();
+        move |source| BookmarksError::File {
+            action,
+            path,
+            source,
+        }
+    }
+
+    fn parse(what: &'static str, path: impl AsRef<Path>) -> impl FnOnce(serde_json::Error) -> Self {
+        let path = path.as_ref().to_path_buf();
+        move |source| BookmarksError::Parse { what, path, source }
+    }
+}
 
 /// Chrome bookmarks file location on macOS
 const CHROME_BOOKMARKS_PATH: &str = "Library/Application Support/Google/Chrome/Default/Bookmarks";
 
+/// Bookmark roots that hold bookmarks synced from mobile devices
+const MOBILE_ROOTS: &[&str] = &["synced"];
+
+/// Chrome's top-level bookmark roots, in the order they're reported
+pub const BOOKMARK_ROOTS: &[&str] = &["bookmark_bar", "other", "synced"];
+
+/// URL schemes of bookmarklets and browser-internal pages, which can't be
+/// fetched over HTTP and are kept out of dead-link checks and dedupe
+const INTERNAL_SCHEMES: &[&str] = &[
+    "javascript:",
+    "chrome://",
+    "chrome-extension://",
+    "about:",
+    "file://",
+];
+
 /// Bookmark categories for auto-organization
+///
+/// Built-in categories are fixed variants; user-defined categories from the
+/// config file are carried in `Custom` so every stats/export path handles them
+/// the same way.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum BookmarkCategory {
     // AI/ML Categories
@@ -71,7 +185,10 @@ pub enum BookmarkCategory {
     Gaming,
     Music,
     Video,
+    Bookmarklet,
     Other,
+    // User-defined
+    Custom(Arc<CustomCategory>),
 }
 
 impl std::fmt::Display for BookmarkCategory {
@@ -133,7 +250,9 @@ impl std::fmt::Display for BookmarkCategory {
             BookmarkCategory::Gaming => write!(f, "🎮 Gaming"),
             BookmarkCategory::Music => write!(f, "🎵 Music"),
             BookmarkCategory::Video => write!(f, "📹 Video"),
+            BookmarkCategory::Bookmarklet => write!(f, "🔖 Bookmarklets/Internal"),
             BookmarkCategory::Other => write!(f, "📁 Other"),
+            BookmarkCategory::Custom(custom) => write!(f, "{} {}", custom.emoji, custom.name),
         }
     }
 }
@@ -197,1124 +316,443 @@ impl BookmarkCategory {
             BookmarkCategory::Gaming => "Gaming",
             BookmarkCategory::Music => "Music",
             BookmarkCategory::Video => "Video",
+            BookmarkCategory::Bookmarklet => "Bookmarklets",
             BookmarkCategory::Other => "Other",
+            BookmarkCategory::Custom(custom) => &custom.folder,
         }
     }
 
-    /// Categorize a bookmark based on its URL and title
-    pub fn from_url_and_title(url: &str, title: &str) -> Self {
-        let url_lower = url.to_lowercase();
-        let title_lower = title.to_lowercase();
-        let combined = format!("{} {}", url_lower, title_lower);
-
-        // ============================================
-        // AI/ML Categories (check first for specificity)
-        // ============================================
-
-        // RAG (Retrieval Augmented Generation)
-        if combined.contains("retrieval augmented")
-            || combined.contains("rag ")
-            || combined.contains(" rag")
-            || combined.contains("langchain") && combined.contains("retriev")
-            || combined.contains("llamaindex")
-            || combined.contains("llama-index")
-            || combined.contains("llama_index")
-            || combined.contains("haystack") && combined.contains("ai")
-            || combined.contains("document retrieval")
-            || combined.contains("semantic search") && combined.contains("llm")
-            || combined.contains("knowledge base") && combined.contains("ai")
-            || combined.contains("chunking")
-                && (combined.contains("llm") || combined.contains("embedding"))
-        {
-            return BookmarkCategory::AIRAG;
-        }
-
-        // Context & Memory
-        if combined.contains("context window")
-            || combined.contains("context length")
-            || combined.contains("long context")
-            || combined.contains("memory")
-                && (combined.contains("llm")
-                    || combined.contains("agent")
-                    || combined.contains("ai"))
-            || combined.contains("conversation memory")
-            || combined.contains("chat history")
-            || combined.contains("mem0")
-            || combined.contains("memgpt")
-            || combined.contains("context management")
-            || combined.contains("token limit")
-            || combined.contains("context compression")
-            || combined.contains("sliding window") && combined.contains("context")
-        {
-            return BookmarkCategory::AIContext;
-        }
-
-        // AI Agents
-        if combined.contains("ai agent")
-            || combined.contains("autonomous agent")
-            || combined.contains("langchain agent")
-            || combined.contains("autogpt")
-            || combined.contains("auto-gpt")
-            || combined.contains("babyagi")
-            || combined.contains("crewai")
-            || combined.contains("crew ai")
-            || combined.contains("autogen")
-            || combined.contains("agent framework")
-            || combined.contains("multi-agent")
-            || combined.contains("multiagent")
-            || combined.contains("tool use") && combined.contains("llm")
-            || combined.contains("function calling") && combined.contains("ai")
-            || combined.contains("agentic")
-            || combined.contains("agent orchestration")
-            || combined.contains("smolagent")
-            || combined.contains("phidata")
-            || combined.contains("swarm") && combined.contains("agent")
-            || url_lower.contains("mcp")
-                && (combined.contains("protocol") || combined.contains("context"))
-            || combined.contains("model context protocol")
-        {
-            return BookmarkCategory::AIAgents;
-        }
-
-        // Prompt Engineering
-        if combined.contains("prompt engineering")
-            || combined.contains("prompt template")
-            || combined.contains("prompting")
-            || combined.contains("chain of thought")
-            || combined.contains("cot prompting")
-            || combined.contains("few-shot")
-            || combined.contains("zero-shot")
-            || combined.contains("in-context learning")
-            || combined.contains("prompt injection")
-            || combined.contains("jailbreak") && combined.contains("llm")
-            || combined.contains("system prompt")
-            || combined.contains("prompt optimization")
-            || combined.contains("dspy")
-            || combined.contains("promptfoo")
-            || combined.contains("prompt testing")
-        {
-            return BookmarkCategory::AIPromptEngineering;
-        }
-
-        // Vector Databases
-        if url_lower.contains("pinecone.io")
-            || url_lower.contains("weaviate.io")
-            || url_lower.contains("milvus.io")
-            || url_lower.contains("qdrant")
-            || url_lower.contains("chroma") && combined.contains("vector")
-            || url_lower.contains("chromadb")
-            || combined.contains("vector database")
-            || combined.contains("vector db")
-            || combined.contains("vectorstore")
-            || combined.contains("vector store")
-            || combined.contains("pgvector")
-            || combined.contains("faiss") && combined.contains("vector")
-            || combined.contains("annoy") && combined.contains("vector")
-            || combined.contains("similarity search") && combined.contains("vector")
-            || url_lower.contains("lancedb")
-            || url_lower.contains("vespa.ai")
-        {
-            return BookmarkCategory::AIVectorDB;
-        }
-
-        // Embeddings
-        if combined.contains("embedding")
-            || combined.contains("sentence transformer")
-            || combined.contains("text-embedding")
-            || combined.contains("ada-002")
-            || combined.contains("openai embedding")
-            || combined.contains("cohere embed")
-            || combined.contains("word2vec")
-            || combined.contains("doc2vec")
-            || combined.contains("semantic similarity")
-            || url_lower.contains("huggingface") && combined.contains("embed")
-            || combined.contains("voyage ai")
-            || combined.contains("jina embedding")
-        {
-            return BookmarkCategory::AIEmbeddings;
-        }
-
-        // Fine-Tuning
-        if combined.contains("fine-tun")
-            || combined.contains("finetun")
-            || combined.contains("lora")
-            || combined.contains("qlora")
-            || combined.contains("peft")
-            || combined.contains("adapter") && combined.contains("llm")
-            || combined.contains("instruction tuning")
-            || combined.contains("rlhf")
-            || combined.contains("dpo") && combined.contains("training")
-            || combined.contains("sft")
-                && (combined.contains("llm") || combined.contains("training"))
-            || combined.contains("training data") && combined.contains("llm")
-            || combined.contains("axolotl")
-            || combined.contains("unsloth")
-            || url_lower.contains("predibase")
-            || url_lower.contains("together.ai") && combined.contains("fine")
-        {
-            return BookmarkCategory::AIFineTuning;
-        }
-
-        // LLMs & Models
-        if url_lower.contains("openai.com")
-            || url_lower.contains("anthropic.com")
-            || url_lower.contains("claude.ai")
-            || url_lower.contains("chat.openai.com")
-            || url_lower.contains("gemini.google")
-            || url_lower.contains("bard.google")
-            || url_lower.contains("mistral.ai")
-            || url_lower.contains("cohere.com")
-            || url_lower.contains("huggingface.co")
-            || url_lower.contains("ollama")
-            || url_lower.contains("replicate.com")
-            || url_lower.contains("together.ai")
-            || url_lower.contains("groq.com")
-            || url_lower.contains("anyscale.com")
-            || url_lower.contains("perplexity.ai")
-            || url_lower.contains("deepseek")
-            || url_lower.contains("meta.ai")
-            || combined.contains("llama")
-                && (combined.contains("model")
-                    || combined.contains("meta")
-                    || combined.contains("ai"))
-            || combined.contains("gpt-4")
-            || combined.contains("gpt-3")
-            || combined.contains("chatgpt")
-            || combined.contains("claude") && combined.contains("anthropic")
-            || combined.contains("gemini") && combined.contains("google")
-            || combined.contains("mistral") && combined.contains("model")
-            || combined.contains("mixtral")
-            || combined.contains("phi-") && combined.contains("microsoft")
-            || combined.contains("falcon") && combined.contains("model")
-            || combined.contains("qwen")
-            || combined.contains("yi model")
-            || combined.contains("command-r")
-            || combined.contains("large language model")
-            || combined.contains("foundation model")
-        {
-            return BookmarkCategory::AILLMs;
-        }
-
-        // MLOps
-        if url_lower.contains("mlflow")
-            || url_lower.contains("wandb.ai")
-            || url_lower.contains("weights-and-biases")
-            || url_lower.contains("neptune.ai")
-            || url_lower.contains("comet.ml")
-            || url_lower.contains("dagshub")
-            || url_lower.contains("dvc.org")
-            || url_lower.contains("kubeflow")
-            || url_lower.contains("bentoml")
-            || url_lower.contains("seldon")
-            || url_lower.contains("ray.io")
-            || url_lower.contains("modal.com")
-            || combined.contains("mlops")
-            || combined.contains("ml ops")
-            || combined.contains("model deployment")
-            || combined.contains("model serving")
-            || combined.contains("model monitoring")
-            || combined.contains("experiment tracking")
-            || combined.contains("model registry")
-            || combined.contains("feature store")
-            || combined.contains("ml pipeline")
-        {
-            return BookmarkCategory::AIMLOps;
-        }
-
-        // Computer Vision
-        if combined.contains("computer vision")
-            || combined.contains("image recognition")
-            || combined.contains("object detection")
-            || combined.contains("image segmentation")
-            || combined.contains("yolo") && combined.contains("detection")
-            || combined.contains("opencv")
-            || combined.contains("stable diffusion")
-            || combined.contains("midjourney")
-            || combined.contains("dall-e")
-            || combined.contains("imagen")
-            || combined.contains("diffusion model")
-            || combined.contains("image generation")
-            || combined.contains("text-to-image")
-            || combined.contains("image-to-image")
-            || combined.contains("inpainting")
-            || combined.contains("controlnet")
-            || combined.contains("comfyui")
-            || url_lower.contains("civitai")
-            || url_lower.contains("stability.ai")
-            || url_lower.contains("runway")
-            || combined.contains("vision model")
-            || combined.contains("multimodal") && combined.contains("vision")
-        {
-            return BookmarkCategory::AIComputerVision;
-        }
-
-        // NLP
-        if combined.contains("natural language processing")
-            || combined.contains("nlp ")
-            || combined.contains(" nlp")
-            || combined.contains("text classification")
-            || combined.contains("named entity")
-            || combined.contains("ner ")
-            || combined.contains("sentiment analysis")
-            || combined.contains("text mining")
-            || combined.contains("spacy")
-            || combined.contains("nltk")
-            || combined.contains("tokeniz")
-            || combined.contains("part-of-speech")
-            || combined.contains("dependency parsing")
-            || combined.contains("text extraction")
-            || combined.contains("information extraction")
-        {
-            return BookmarkCategory::AINLP;
-        }
-
-        // AI Research
-        if url_lower.contains("arxiv.org") && combined.contains("ai")
-            || url_lower.contains("arxiv.org") && combined.contains("machine learning")
-            || url_lower.contains("arxiv.org") && combined.contains("llm")
-            || url_lower.contains("arxiv.org") && combined.contains("neural")
-            || url_lower.contains("arxiv.org") && combined.contains("transformer")
-            || url_lower.contains("paperswithcode.com")
-            || url_lower.contains("semanticscholar.org") && combined.contains("ai")
-            || url_lower.contains("connectedpapers.com")
-            || combined.contains("research paper") && combined.contains("ai")
-            || combined.contains("ai research")
-            || combined.contains("ml research")
-            || url_lower.contains("deepmind.com")
-            || url_lower.contains("research.google") && combined.contains("ai")
-            || url_lower.contains("ai.meta.com")
-            || url_lower.contains("research.microsoft.com") && combined.contains("ai")
-        {
-            return BookmarkCategory::AIResearch;
-        }
-
-        // General AI/ML (catch-all for AI content not fitting specific subcategories)
-        if combined.contains("artificial intelligence")
-            || combined.contains("machine learning")
-            || combined.contains("deep learning")
-            || combined.contains("neural network")
-            || combined.contains("transformer")
-                && (combined.contains("ai") || combined.contains("model"))
-            || combined.contains("tensorflow")
-            || combined.contains("pytorch")
-            || combined.contains("keras")
-            || combined.contains("scikit-learn")
-            || combined.contains("sklearn")
-            || url_lower.contains("kaggle.com")
-            || url_lower.contains("fast.ai")
-            || url_lower.contains("deeplearning.ai")
-            || combined.contains("ai tool")
-            || combined.contains("ml tool")
-            || combined.contains("generative ai")
-            || combined.contains("gen ai")
-            || combined.contains("langchain")
-            || combined.contains("llamaindex")
-            || combined.contains("inference")
-                && (combined.contains("model") || combined.contains("ai"))
-        {
-            return BookmarkCategory::AIGeneral;
-        }
-
-        // ============================================
-        // Finance Subcategories (check before Development)
-        // ============================================
-
-        // Crypto (check first - most specific)
-        if url_lower.contains("coinbase.com")
-            || url_lower.contains("binance.com")
-            || url_lower.contains("kraken.com")
-            || url_lower.contains("gemini.com")
-            || url_lower.contains("ftx.com")
-            || url_lower.contains("kucoin.com")
-            || url_lower.contains("huobi")
-            || url_lower.contains("okx.com")
-            || url_lower.contains("bybit.com")
-            || url_lower.contains("bitstamp")
-            || url_lower.contains("bitfinex")
-            || url_lower.contains("bitmex")
-            || url_lower.contains("coinmarketcap.com")
-            || url_lower.contains("coingecko.com")
-            || url_lower.contains("tradingview.com")
-            || url_lower.contains("dextools.io")
-            || url_lower.contains("etherscan.io")
-            || url_lower.contains("bscscan.com")
-            || url_lower.contains("polygonscan.com")
-            || url_lower.contains("uniswap")
-            || url_lower.contains("sushiswap")
-            || url_lower.contains("pancakeswap")
-            || url_lower.contains("metamask.io")
-            || url_lower.contains("opensea.io")
-            || url_lower.contains("rarible.com")
-            || url_lower.contains("looksrare")
-            || combined.contains("bitcoin")
-            || combined.contains("btc ")
-            || combined.contains("ethereum")
-            || combined.contains("eth ")
-            || combined.contains("crypto")
-            || combined.contains("blockchain")
-            || combined.contains("defi")
-            || combined.contains("nft")
-            || combined.contains("ico ")
-            || combined.contains("token sale")
-            || combined.contains("airdrop")
-            || combined.contains("staking")
-            || combined.contains("yield farming")
-            || combined.contains("liquidity pool")
-            || combined.contains("smart contract")
-            || combined.contains("wallet")
-                && (combined.contains("crypto")
-                    || combined.contains("bitcoin")
-                    || combined.contains("ethereum"))
-            || combined.contains("exchange")
-                && (combined.contains("crypto")
-                    || combined.contains("coin")
-                    || combined.contains("token"))
-            || combined.contains("altcoin")
-            || combined.contains("memecoin")
-            || combined.contains("chart pattern")
-            || combined.contains("candlestick")
-            || combined.contains("trading signal")
-            || combined.contains("technical analysis")
-                && (combined.contains("crypto") || combined.contains("coin"))
-            || combined.contains("solana")
-            || combined.contains("cardano")
-            || combined.contains("polkadot")
-            || combined.contains("avalanche")
-            || combined.contains("polygon") && !combined.contains("css")
-            || combined.contains("arbitrum")
-            || combined.contains("optimism")
-            || combined.contains("layer 2")
-            || combined.contains("web3")
-            || combined.contains("dapp")
-            || combined.contains("decentralized")
-        {
-            return BookmarkCategory::FinanceCrypto;
-        }
-
-        // Trading (stocks, forex, etc.)
-        if url_lower.contains("robinhood.com")
-            || url_lower.contains("etrade.com")
-            || url_lower.contains("tdameritrade.com")
-            || url_lower.contains("thinkorswim")
-            || url_lower.contains("interactivebrokers")
-            || url_lower.contains("stockcharts.com")
-            || url_lower.contains("finviz.com")
-            || url_lower.contains("yahoo.com/finance")
-            || url_lower.contains("finance.yahoo.com")
-            || url_lower.contains("marketwatch.com")
-            || url_lower.contains("seekingalpha.com")
-            || url_lower.contains("investopedia.com")
-            || url_lower.contains("morningstar.com")
-            || combined.contains("stock market")
-            || combined.contains("stock trading")
-            || combined.contains("forex")
-            || combined.contains("options trading")
-            || combined.contains("futures trading")
-            || combined.contains("dividend")
-            || combined.contains("portfolio") && combined.contains("invest")
-            || combined.contains("market analysis")
-            || combined.contains("bull market")
-            || combined.contains("bear market")
-            || combined.contains("earnings report")
-            || combined.contains("etf ")
-            || combined.contains("index fund")
-        {
-            return BookmarkCategory::FinanceTrading;
-        }
-
-        // Personal Finance
-        if url_lower.contains("mint.com")
-            || url_lower.contains("ynab.com")
-            || url_lower.contains("personalcapital.com")
-            || url_lower.contains("creditkarma.com")
-            || url_lower.contains("nerdwallet.com")
-            || url_lower.contains("bankrate.com")
-            || combined.contains("budget")
-            || combined.contains("saving money")
-            || combined.contains("retirement")
-            || combined.contains("401k")
-            || combined.contains("ira ")
-            || combined.contains("credit score")
-            || combined.contains("credit card") && !combined.contains("api")
-            || combined.contains("mortgage")
-            || combined.contains("debt")
-            || combined.contains("tax return")
-            || combined.contains("net worth")
-            || combined.contains("financial planning")
-            || combined.contains("emergency fund")
-        {
-            return BookmarkCategory::FinancePersonal;
-        }
-
-        // General Finance (catch-all)
-        if url_lower.contains("bank")
-            || url_lower.contains("paypal.com")
-            || url_lower.contains("venmo.com")
-            || url_lower.contains("fidelity.com")
-            || url_lower.contains("schwab.com")
-            || url_lower.contains("vanguard.com")
-            || url_lower.contains("finance.")
-            || combined.contains("invest") && !combined.contains("investigate")
-            || combined.contains("financial")
-        {
-            return BookmarkCategory::FinanceGeneral;
-        }
-
-        // ============================================
-        // Personal Development (check before Development)
-        // ============================================
-        if combined.contains("habit")
-            || combined.contains("productivity")
-                && !combined.contains("developer")
-                && !combined.contains("tool")
-            || combined.contains("self improvement")
-            || combined.contains("self-improvement")
-            || combined.contains("personal growth")
-            || combined.contains("motivation")
-            || combined.contains("mindset")
-            || combined.contains("goal setting")
-            || combined.contains("time management") && !combined.contains("project")
-            || combined.contains("life hack")
-            || combined.contains("morning routine")
-            || combined.contains("meditation")
-            || combined.contains("mindfulness")
-            || combined.contains("journaling")
-            || combined.contains("gratitude")
-            || combined.contains("stoicism")
-            || combined.contains("atomic habits")
-            || combined.contains("deep work")
-            || combined.contains("getting things done")
-            || combined.contains("gtd ")
-            || combined.contains("pomodoro")
-            || combined.contains("procrastination")
-            || combined.contains("discipline")
-            || combined.contains("self help")
-            || combined.contains("self-help")
-            || combined.contains("memory technique")
-            || combined.contains("speed reading")
-            || combined.contains("learning how to learn")
-            || combined.contains("career growth")
-            || combined.contains("public speaking")
-            || combined.contains("emotional intelligence")
-        {
-            return BookmarkCategory::PersonalDevelopment;
-        }
-
-        // ============================================
-        // Other General Categories (check before Development)
-        // ============================================
-
-        // Shopping (check early to avoid catching in Development)
-        if url_lower.contains("amazon.")
-            || url_lower.contains("ebay.")
-            || url_lower.contains("etsy.com")
-            || url_lower.contains("aliexpress.com")
-            || url_lower.contains("walmart.com")
-            || url_lower.contains("target.com")
-            || url_lower.contains("bestbuy.com")
-            || url_lower.contains("newegg.com")
-            || url_lower.contains("/cart")
-            || url_lower.contains("/checkout")
-            || combined.contains("buy now")
-            || combined.contains("add to cart")
-            || combined.contains("shopping")
-            || combined.contains("discount code")
-            || combined.contains("coupon")
-        {
-            return BookmarkCategory::Shopping;
+    /// All built-in categories, in display order
+    pub fn builtins() -> Vec<BookmarkCategory> {
+        vec![
+            BookmarkCategory::AIGeneral,
+            BookmarkCategory::AILLMs,
+            BookmarkCategory::AIPromptEngineering,
+            BookmarkCategory::AIAgents,
+            BookmarkCategory::AIRAG,
+            BookmarkCategory::AIContext,
+            BookmarkCategory::AIFineTuning,
+            BookmarkCategory::AIEmbeddings,
+            BookmarkCategory::AIVectorDB,
+            BookmarkCategory::AIMLOps,
+            BookmarkCategory::AIComputerVision,
+            BookmarkCategory::AINLP,
+            BookmarkCategory::AIResearch,
+            BookmarkCategory::DevGeneral,
+            BookmarkCategory::DevReact,
+            BookmarkCategory::DevPython,
+            BookmarkCategory::DevJava,
+            BookmarkCategory::DevRust,
+            BookmarkCategory::DevJavaScript,
+            BookmarkCategory::DevTypeScript,
+            BookmarkCategory::DevCSS,
+            BookmarkCategory::DevKubernetes,
+            BookmarkCategory::DevDocker,
+            BookmarkCategory::DevPostgres,
+            BookmarkCategory::DevDatabase,
+            BookmarkCategory::DevAWS,
+            BookmarkCategory::DevServerless,
+            BookmarkCategory::DevWebTech,
+            BookmarkCategory::DevMobile,
+            BookmarkCategory::DevGit,
+            BookmarkCategory::DevDevOps,
+            BookmarkCategory::DevAPI,
+            BookmarkCategory::FinanceGeneral,
+            BookmarkCategory::FinanceCrypto,
+            BookmarkCategory::FinanceTrading,
+            BookmarkCategory::FinancePersonal,
+            BookmarkCategory::PersonalDevelopment,
+            BookmarkCategory::Social,
+            BookmarkCategory::News,
+            BookmarkCategory::Shopping,
+            BookmarkCategory::Entertainment,
+            BookmarkCategory::Education,
+            BookmarkCategory::Reference,
+            BookmarkCategory::Tools,
+            BookmarkCategory::Health,
+            BookmarkCategory::Travel,
+            BookmarkCategory::Food,
+            BookmarkCategory::Sports,
+            BookmarkCategory::Gaming,
+            BookmarkCategory::Music,
+            BookmarkCategory::Video,
+            BookmarkCategory::Bookmarklet,
+            BookmarkCategory::Other,
+        ]
+    }
+
+    /// Categorize a bookmark and score how confident the match is (0.0 - 1.0)
+    ///
+    /// The score depends on which signals back the category on their own:
+    /// URL and title 1.0, URL only 0.8, title only 0.5, neither alone 0.3.
+    /// "Other" always scores 0.0.
+    pub fn from_url_and_title_scored(url: &str, title: &str) -> (Self, f64) {
+        categorize_scored_with(builtin_rules(), url, title)
+    }
+
+    /// Category name without the emoji, e.g. `Dev/Rust` for 🦀 Dev/Rust
+    pub fn label(&self) -> String {
+        let display = self.to_string();
+        match display.split_once(' ') {
+            Some((_, label)) => label.trim().to_string(),
+            None => display,
         }
+    }
 
-        // Video (check early)
-        if url_lower.contains("youtube.com")
-            || url_lower.contains("youtu.be")
-            || url_lower.contains("vimeo.com")
-            || url_lower.contains("dailymotion.com")
-            || url_lower.contains("twitch.tv")
-        {
-            return BookmarkCategory::Video;
-        }
-
-        // Social Media (check early)
-        if url_lower.contains("facebook.com")
-            || url_lower.contains("twitter.com")
-            || url_lower.contains("x.com")
-            || url_lower.contains("instagram.com")
-            || url_lower.contains("linkedin.com")
-            || url_lower.contains("reddit.com")
-            || url_lower.contains("discord.com")
-            || url_lower.contains("slack.com")
-            || url_lower.contains("telegram.org")
-            || url_lower.contains("whatsapp.com")
-            || url_lower.contains("snapchat.com")
-            || url_lower.contains("tiktok.com")
-            || url_lower.contains("pinterest.com")
-            || url_lower.contains("tumblr.com")
-            || url_lower.contains("mastodon")
-            || url_lower.contains("threads.net")
-            || url_lower.contains("bluesky")
-        {
-            return BookmarkCategory::Social;
-        }
-
-        // News
-        if url_lower.contains("news.")
-            || url_lower.contains("bbc.com")
-            || url_lower.contains("cnn.com")
-            || url_lower.contains("nytimes.com")
-            || url_lower.contains("washingtonpost.com")
-            || url_lower.contains("theguardian.com")
-            || url_lower.contains("reuters.com")
-            || url_lower.contains("apnews.com")
-            || url_lower.contains("bloomberg.com")
-            || url_lower.contains("techcrunch.com")
-            || url_lower.contains("theverge.com")
-            || url_lower.contains("wired.com")
-            || url_lower.contains("arstechnica.com")
-            || url_lower.contains("engadget.com")
-            || url_lower.contains("hackernews")
-            || url_lower.contains("news.ycombinator.com")
-            || combined.contains("breaking news")
-        {
-            return BookmarkCategory::News;
-        }
-
-        // Education
-        if url_lower.contains("coursera.org")
-            || url_lower.contains("udemy.com")
-            || url_lower.contains("edx.org")
-            || url_lower.contains("khanacademy.org")
-            || url_lower.contains("skillshare.com")
-            || url_lower.contains("pluralsight.com")
-            || url_lower.contains("lynda.com")
-            || url_lower.contains("codecademy.com")
-            || url_lower.contains("freecodecamp.org")
-            || url_lower.contains(".edu")
-            || url_lower.contains("learn.")
-            || combined.contains("online course")
-            || combined.contains("free course")
-        {
-            return BookmarkCategory::Education;
-        }
-
-        // ============================================
-        // Development Subcategories
-        // ============================================
-
-        // React / React Native
-        if url_lower.contains("reactjs.org")
-            || url_lower.contains("react.dev")
-            || url_lower.contains("reactnative.dev")
-            || combined.contains("react")
-                && (combined.contains("component")
-                    || combined.contains("hook")
-                    || combined.contains("redux")
-                    || combined.contains("nextjs")
-                    || combined.contains("next.js")
-                    || combined.contains("gatsby")
-                    || combined.contains("jsx")
-                    || combined.contains("state management"))
-            || combined.contains("react native")
-            || combined.contains("expo")
-            || url_lower.contains("nextjs.org")
-            || combined.contains("use effect")
-            || combined.contains("usestate")
-            || combined.contains("usememo")
-            || combined.contains("zustand")
-            || combined.contains("tanstack")
-            || combined.contains("react query")
-        {
-            return BookmarkCategory::DevReact;
-        }
-
-        // Python
-        if url_lower.contains("python.org")
-            || url_lower.contains("pypi.org")
-            || combined.contains("python")
-                && (combined.contains("pip")
-                    || combined.contains("django")
-                    || combined.contains("flask")
-                    || combined.contains("fastapi")
-                    || combined.contains("pandas")
-                    || combined.contains("numpy")
-                    || combined.contains("jupyter")
-                    || combined.contains("anaconda")
-                    || combined.contains("virtualenv")
-                    || combined.contains("poetry"))
-            || url_lower.contains("django")
-            || url_lower.contains("flask")
-            || url_lower.contains("fastapi")
-            || combined.contains("pydantic")
-            || combined.contains("pytest")
-        {
-            return BookmarkCategory::DevPython;
-        }
-
-        // Rust
-        if url_lower.contains("rust-lang.org")
-            || url_lower.contains("crates.io")
-            || combined.contains("rust")
-                && (combined.contains("cargo")
-                    || combined.contains("rustup")
-                    || combined.contains("tokio")
-                    || combined.contains("actix")
-                    || combined.contains("wasm")
-                    || combined.contains("serde"))
-            || combined.contains("rustacean")
-        {
-            return BookmarkCategory::DevRust;
-        }
-
-        // Java / Kotlin / JVM
-        if combined.contains("java")
-            && (combined.contains("spring")
-                || combined.contains("maven")
-                || combined.contains("gradle")
-                || combined.contains("jvm")
-                || combined.contains("hibernate")
-                || combined.contains("junit"))
-            || combined.contains("kotlin")
-            || url_lower.contains("spring.io")
-            || combined.contains("springboot")
-            || combined.contains("spring boot")
-        {
-            return BookmarkCategory::DevJava;
-        }
-
-        // TypeScript
-        if url_lower.contains("typescriptlang.org")
-            || combined.contains("typescript")
-                && (combined.contains("type")
-                    || combined.contains("interface")
-                    || combined.contains("generic")
-                    || combined.contains("tsc"))
-            || combined.contains(".ts ")
-            || combined.contains(".tsx")
-        {
-            return BookmarkCategory::DevTypeScript;
-        }
-
-        // JavaScript (general)
-        if url_lower.contains("nodejs.org")
-            || url_lower.contains("npmjs.com")
-            || combined.contains("javascript")
-            || combined.contains("node.js")
-            || combined.contains("nodejs")
-            || combined.contains("npm ")
-            || combined.contains("yarn ")
-            || combined.contains("pnpm")
-            || combined.contains("deno")
-            || combined.contains("bun ")
-            || combined.contains("express.js")
-            || combined.contains("expressjs")
-            || combined.contains("es6")
-            || combined.contains("ecmascript")
-            || combined.contains("async await")
-            || combined.contains("promise")
-        {
-            return BookmarkCategory::DevJavaScript;
-        }
-
-        // CSS / Styling
-        if combined.contains("css")
-            || combined.contains("tailwind")
-            || combined.contains("sass")
-            || combined.contains("scss")
-            || combined.contains("less ")
-            || combined.contains("styled-component")
-            || combined.contains("bootstrap")
-            || combined.contains("material ui")
-            || combined.contains("chakra ui")
-            || combined.contains("flexbox")
-            || combined.contains("grid layout")
-            || combined.contains("animation")
-            || combined.contains("responsive design")
-            || url_lower.contains("csswizardry")
-            || url_lower.contains("css-tricks")
-        {
-            return BookmarkCategory::DevCSS;
-        }
-
-        // Kubernetes
-        if url_lower.contains("kubernetes.io")
-            || combined.contains("kubernetes")
-            || combined.contains("k8s")
-            || combined.contains("kubectl")
-            || combined.contains("helm ")
-            || combined.contains("helm chart")
-            || combined.contains("minikube")
-            || combined.contains("kind cluster")
-            || combined.contains("pod ")
-            || combined.contains("deployment") && combined.contains("container")
-            || combined.contains("service mesh")
-            || combined.contains("istio")
-            || combined.contains("ingress")
-        {
-            return BookmarkCategory::DevKubernetes;
+    /// Categorize a bookmark based on its URL and title, using the built-in rules
+    pub fn from_url_and_title(url: &str, title: &str) -> Self {
+        rule_category(builtin_rules(), url, title)
+    }
+}
+
+/// A user-defined bookmark category loaded from the config file
+///
+/// ```toml
+/// [[bookmarks.categories]]
+/// name = "Photography"
+/// emoji = "📷"
+/// folder = "Hobbies/Photography"
+/// url_patterns = ["flickr.com", "500px.com"]
+/// title_patterns = ["photography", "lightroom"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CustomCategory {
+    pub name: String,
+    pub emoji: String,
+    pub folder: String,
+    pub url_patterns: Vec<String>,
+    pub title_patterns: Vec<String>,
+}
+
+/// The category the first matching rule assigns; bookmarklets and
+/// browser-internal pages get Bookmarklets whatever their title says
+fn rule_category(rules: &RuleSet, url: &str, title: &str) -> BookmarkCategory {
+    if is_internal_url(url) {
+        return BookmarkCategory::Bookmarklet;
+    }
+
+    rules
+        .evaluate(url, title)
+        .map(|m| m.rule.category.clone())
+        .unwrap_or(BookmarkCategory::Other)
+}
+
+/// Categorize with `rules` and score the match by whether the URL and the
+/// title each lead to the same category on their own
+fn categorize_scored_with(rules: &RuleSet, url: &str, title: &str) -> (BookmarkCategory, f64) {
+    let category = rule_category(rules, url, title);
+
+    let confidence = match category {
+        BookmarkCategory::Other => 0.0,
+        BookmarkCategory::Bookmarklet => 1.0,
+        _ => signal_confidence(
+            rule_category(rules, url, "") == category,
+            rule_category(rules, "", title) == category,
+        ),
+    };
+
+    (category, confidence)
+}
+
+/// Registry of all bookmark categories: built-ins plus user-defined entries,
+/// and the rules that assign them
+///
+/// With `[bookmarks] language_folders = true`, bookmarks no rule matches get a
+/// `Language/<language>` category when their title is reliably non-English.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryRegistry {
+    custom: Vec<Arc<CustomCategory>>,
+    rules: RuleSet,
+    language_folders: bool,
+}
+
+impl CategoryRegistry {
+    /// Load the registry from the global config file
+    pub fn load() -> Result<Self> {
+        Self::from_config(&Config::load()?)
+    }
+
+    /// Build the registry from `[[bookmarks.categories]]` and
+    /// `[[bookmarks.rules]]` config entries
+    ///
+    /// Each custom category becomes a rule matching its url/title patterns.
+    /// Extra rules can send bookmarks to any built-in or custom category:
+    ///
+    /// ```toml
+    /// [[bookmarks.rules]]
+    /// name = "work-wiki"
+    /// category = "Reference"        # category name or folder
+    /// priority = 500               # optional; built-ins run 10..510, default 1000
+    /// url_patterns = ["wiki.corp.example"]
+    /// title_patterns = ["runbook"]
+    /// patterns = ["confluence"]     # URL or title
+    /// exclude = ["jira"]            # never match when present
+    /// ```
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut custom = Vec::new();
+        let mut rules = Vec::new();
+
+        for entry in config.get_table_array("bookmarks.categories") {
+            let name = entry
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| {
+                    BookmarksError::InvalidConfig(
+                        "Custom bookmark category is missing a 'name'".to_string(),
+                    )
+                })?
+                .to_string();
+            let emoji = entry
+                .get("emoji")
+                .and_then(|v| v.as_str())
+                .unwrap_or("📁")
+                .to_string();
+            let folder = entry
+                .get("folder")
+                .and_then(|v| v.as_str())
+                .unwrap_or(&name)
+                .to_string();
+            let patterns = |key: &str| -> Vec<String> {
+                entry
+                    .get(key)
+                    .and_then(|v| v.as_array())
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|item| item.as_str())
+                            .map(|p| p.to_lowercase())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+            let url_patterns = patterns("url_patterns");
+            let title_patterns = patterns("title_patterns");
+
+            if url_patterns.is_empty() && title_patterns.is_empty() {
+                return Err(BookmarksError::InvalidConfig(format!(
+                    "Custom bookmark category '{}' needs url_patterns or title_patterns",
+                    name
+                )));
+            }
+
+            let category = Arc::new(CustomCategory {
+                name,
+                emoji,
+                folder,
+                url_patterns,
+                title_patterns,
+            });
+            rules.push(CategoryRule {
+                name: category.name.clone(),
+                priority: entry
+                    .get("priority")
+                    .and_then(|v| v.as_integer())
+                    .unwrap_or(CONFIG_RULE_PRIORITY),
+                category: BookmarkCategory::Custom(Arc::clone(&category)),
+                clauses: pattern_clauses(
+                    &category.url_patterns,
+                    &category.title_patterns,
+                    &[],
+                    &[],
+                ),
+                source: RuleSource::Config,
+            });
+            custom.push(category);
         }
 
-        // Docker
-        if url_lower.contains("docker.com")
-            || url_lower.contains("hub.docker.com")
-            || combined.contains("docker")
-            || combined.contains("dockerfile")
-            || combined.contains("container") && !combined.contains("kubernetes")
-            || combined.contains("docker-compose")
-            || combined.contains("podman")
-        {
-            return BookmarkCategory::DevDocker;
+        let categories: Vec<BookmarkCategory> = BookmarkCategory::builtins()
+            .into_iter()
+            .chain(custom.iter().cloned().map(BookmarkCategory::Custom))
+            .collect();
+
+        for entry in config.get_table_array("bookmarks.rules") {
+            let field = |key: &str| entry.get(key).and_then(|v| v.as_str());
+            let patterns = |key: &str| -> Vec<String> {
+                entry
+                    .get(key)
+                    .and_then(|v| v.as_array())
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|item| item.as_str())
+                            .map(|p| p.to_lowercase())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+
+            let name = field("name").ok_or_else(|| {
+                BookmarksError::InvalidConfig("Bookmark rule is missing a 'name'".to_string())
+            })?;
+            let category_name = field("category").ok_or_else(|| {
+                BookmarksError::InvalidConfig(format!(
+                    "Bookmark rule '{}' is missing a 'category'",
+                    name
+                ))
+            })?;
+            let category = categories
+                .iter()
+                .find(|c| {
+                    c.label().eq_ignore_ascii_case(category_name)
+                        || c.folder_name().eq_ignore_ascii_case(category_name)
+                })
+                .cloned()
+                .ok_or_else(|| {
+                    BookmarksError::InvalidConfig(format!(
+                        "Bookmark rule '{}' uses unknown category '{}' (see category-list)",
+                        name, category_name
+                    ))
+                })?;
+
+            let clauses = pattern_clauses(
+                &patterns("url_patterns"),
+                &patterns("title_patterns"),
+                &patterns("patterns"),
+                &patterns("exclude"),
+            );
+
+            if clauses.is_empty() {
+                return Err(BookmarksError::InvalidConfig(format!(
+                    "Bookmark rule '{}' needs url_patterns, title_patterns or patterns",
+                    name
+                )));
+            }
+
+            rules.push(CategoryRule {
+                name: name.to_string(),
+                priority: entry
+                    .get("priority")
+                    .and_then(|v| v.as_integer())
+                    .unwrap_or(CONFIG_RULE_PRIORITY),
+                category,
+                clauses,
+                source: RuleSource::Config,
+            });
         }
 
-        // PostgreSQL
-        if url_lower.contains("postgresql.org")
-            || combined.contains("postgresql")
-            || combined.contains("postgres")
-            || combined.contains("psql")
-            || combined.contains("pg_")
-        {
-            return BookmarkCategory::DevPostgres;
-        }
-
-        // Database (general)
-        if combined.contains("mysql")
-            || combined.contains("mongodb")
-            || combined.contains("redis")
-            || combined.contains("elasticsearch")
-            || combined.contains("sqlite")
-            || combined.contains("dynamodb")
-            || combined.contains("cassandra")
-            || combined.contains("sql ")
-            || combined.contains("nosql")
-            || combined.contains("database")
-            || combined.contains("query optimization")
-            || combined.contains("orm ")
-            || combined.contains("prisma")
-            || combined.contains("drizzle")
-        {
-            return BookmarkCategory::DevDatabase;
-        }
-
-        // AWS
-        if url_lower.contains("aws.amazon.com")
-            || combined.contains("aws ")
-            || combined.contains("amazon web services")
-            || combined.contains("lambda") && combined.contains("aws")
-            || combined.contains("ec2")
-            || combined.contains("s3 bucket")
-            || combined.contains("cloudformation")
-            || combined.contains("cloudwatch")
-            || combined.contains("dynamodb")
-            || combined.contains("sqs ")
-            || combined.contains("sns ")
-            || combined.contains("iam ") && combined.contains("aws")
-            || combined.contains("cdk") && combined.contains("aws")
-        {
-            return BookmarkCategory::DevAWS;
-        }
-
-        // Serverless
-        if combined.contains("serverless")
-            || combined.contains("lambda function")
-            || combined.contains("cloud function")
-            || combined.contains("azure function")
-            || combined.contains("vercel") && combined.contains("function")
-            || combined.contains("netlify function")
-            || combined.contains("edge function")
-            || combined.contains("faas")
-            || url_lower.contains("serverless.com")
-        {
-            return BookmarkCategory::DevServerless;
-        }
-
-        // Git
-        if url_lower.contains("github.com")
-            || url_lower.contains("gitlab.com")
-            || url_lower.contains("bitbucket.org")
-            || combined.contains("git ")
-            || combined.contains("gitflow")
-            || combined.contains("pull request")
-            || combined.contains("merge conflict")
-            || combined.contains("branch") && combined.contains("git")
-            || combined.contains("commit") && combined.contains("git")
-            || combined.contains("rebase")
-            || combined.contains("cherry-pick")
-        {
-            return BookmarkCategory::DevGit;
-        }
-
-        // DevOps / CI/CD
-        if combined.contains("devops")
-            || combined.contains("ci/cd")
-            || combined.contains("cicd")
-            || combined.contains("jenkins")
-            || combined.contains("github actions")
-            || combined.contains("gitlab ci")
-            || combined.contains("circleci")
-            || combined.contains("travis ci")
-            || combined.contains("argo")
-            || combined.contains("terraform")
-            || combined.contains("ansible")
-            || combined.contains("puppet")
-            || combined.contains("chef ")
-            || combined.contains("infrastructure as code")
-            || combined.contains("monitoring")
-            || combined.contains("prometheus")
-            || combined.contains("grafana")
-            || combined.contains("datadog")
-            || combined.contains("sonarqube")
-        {
-            return BookmarkCategory::DevDevOps;
-        }
-
-        // Mobile Development
-        if combined.contains("ios ")
-            || combined.contains("android ")
-            || combined.contains("swift")
-            || combined.contains("swiftui")
-            || combined.contains("xcode")
-            || combined.contains("flutter")
-            || combined.contains("dart ")
-            || combined.contains("mobile app")
-            || combined.contains("app store")
-            || combined.contains("play store")
-            || url_lower.contains("developer.apple.com")
-            || url_lower.contains("developer.android.com")
-        {
-            return BookmarkCategory::DevMobile;
-        }
-
-        // Web Tech (general web development)
-        if combined.contains("html")
-            || combined.contains("dom ")
-            || combined.contains("web component")
-            || combined.contains("pwa")
-            || combined.contains("progressive web")
-            || combined.contains("service worker")
-            || combined.contains("websocket")
-            || combined.contains("http")
-            || combined.contains("cors")
-            || combined.contains("oauth")
-            || combined.contains("jwt ")
-            || combined.contains("rest api")
-            || combined.contains("graphql")
-            || combined.contains("grpc")
-            || combined.contains("webpack")
-            || combined.contains("vite")
-            || combined.contains("esbuild")
-            || combined.contains("rollup")
-            || combined.contains("babel")
-            || url_lower.contains("vuejs.org")
-            || url_lower.contains("angular.io")
-            || url_lower.contains("svelte.dev")
-            || combined.contains("vue ")
-            || combined.contains("angular")
-            || combined.contains("svelte")
-        {
-            return BookmarkCategory::DevWebTech;
-        }
-
-        // API Development
-        if combined.contains("api ")
-            || combined.contains("rest ")
-            || combined.contains("openapi")
-            || combined.contains("swagger")
-            || combined.contains("postman")
-            || combined.contains("insomnia")
-            || combined.contains("endpoint")
-            || combined.contains("webhook")
-        {
-            return BookmarkCategory::DevAPI;
-        }
-
-        // General Development (catch-all)
-        if url_lower.contains("stackoverflow.com")
-            || url_lower.contains("stackexchange.com")
-            || url_lower.contains("developer.")
-            || url_lower.contains("docs.")
-            || url_lower.contains("vercel.com")
-            || url_lower.contains("netlify.com")
-            || url_lower.contains("heroku.com")
-            || url_lower.contains("cloud.google.com")
-            || url_lower.contains("azure.microsoft.com")
-            || url_lower.contains("codepen.io")
-            || url_lower.contains("codesandbox.io")
-            || url_lower.contains("replit.com")
-            || url_lower.contains("jsfiddle.net")
-            || url_lower.contains("medium.com") && combined.contains("programming")
-            || url_lower.contains("dev.to")
-            || url_lower.contains("hashnode.com")
-            || combined.contains("documentation")
-            || combined.contains("tutorial")
-            || combined.contains("programming")
-            || combined.contains("coding")
-            || combined.contains("developer")
-        {
-            return BookmarkCategory::DevGeneral;
-        }
-
-        // ============================================
-        // Remaining General Categories
-        // ============================================
-
-        // Music
-        if url_lower.contains("spotify.com")
-            || url_lower.contains("soundcloud.com")
-            || url_lower.contains("music.apple.com")
-            || url_lower.contains("bandcamp.com")
-            || url_lower.contains("last.fm")
-            || url_lower.contains("pandora.com")
-            || url_lower.contains("deezer.com")
-            || url_lower.contains("tidal.com")
-        {
-            return BookmarkCategory::Music;
-        }
-
-        // Gaming
-        if url_lower.contains("steam")
-            || url_lower.contains("epicgames.com")
-            || url_lower.contains("gog.com")
-            || url_lower.contains("playstation.com")
-            || url_lower.contains("xbox.com")
-            || url_lower.contains("nintendo.com")
-            || url_lower.contains("ign.com")
-            || url_lower.contains("gamespot.com")
-            || url_lower.contains("kotaku.com")
-            || url_lower.contains("polygon.com")
-        {
-            return BookmarkCategory::Gaming;
-        }
-
-        // Entertainment (Netflix, etc.)
-        if url_lower.contains("netflix.com")
-            || url_lower.contains("hulu.com")
-            || url_lower.contains("disneyplus.com")
-            || url_lower.contains("hbomax.com")
-            || url_lower.contains("primevideo.com")
-            || url_lower.contains("crunchyroll.com")
-            || url_lower.contains("imdb.com")
-            || url_lower.contains("rottentomatoes.com")
-            || url_lower.contains("letterboxd.com")
-        {
-            return BookmarkCategory::Entertainment;
-        }
-
-        // Reference (Wikipedia, dictionaries, etc.)
-        if url_lower.contains("wikipedia.org")
-            || url_lower.contains("wikimedia.org")
-            || url_lower.contains("wiktionary.org")
-            || url_lower.contains("britannica.com")
-            || url_lower.contains("merriam-webster.com")
-            || url_lower.contains("dictionary.com")
-            || url_lower.contains("thesaurus.com")
-            || url_lower.contains("translate.google")
-            || url_lower.contains("deepl.com")
-            || url_lower.contains("wolframalpha.com")
-        {
-            return BookmarkCategory::Reference;
-        }
-
-        // Tools & Utilities
-        if url_lower.contains("notion.so")
-            || url_lower.contains("trello.com")
-            || url_lower.contains("asana.com")
-            || url_lower.contains("monday.com")
-            || url_lower.contains("figma.com")
-            || url_lower.contains("canva.com")
-            || url_lower.contains("drive.google.com")
-            || url_lower.contains("dropbox.com")
-            || url_lower.contains("box.com")
-            || url_lower.contains("1password.com")
-            || url_lower.contains("lastpass.com")
-            || url_lower.contains("bitwarden.com")
-            || url_lower.contains("grammarly.com")
-            || url_lower.contains("calendly.com")
-            || url_lower.contains("zoom.us")
-            || url_lower.contains("meet.google.com")
-            || url_lower.contains("teams.microsoft.com")
-            || combined.contains("converter")
-            || combined.contains("generator")
-            || combined.contains("calculator")
-        {
-            return BookmarkCategory::Tools;
-        }
-
-        // Health
-        if url_lower.contains("webmd.com")
-            || url_lower.contains("mayoclinic.org")
-            || url_lower.contains("healthline.com")
-            || url_lower.contains("nih.gov")
-            || url_lower.contains("cdc.gov")
-            || url_lower.contains("who.int")
-            || url_lower.contains("myfitnesspal.com")
-            || url_lower.contains("strava.com")
-            || url_lower.contains("fitbit.com")
-            || combined.contains("health")
-            || combined.contains("fitness")
-            || combined.contains("workout")
-            || combined.contains("diet")
-        {
-            return BookmarkCategory::Health;
-        }
-
-        // Travel
-        if url_lower.contains("booking.com")
-            || url_lower.contains("airbnb.com")
-            || url_lower.contains("expedia.com")
-            || url_lower.contains("kayak.com")
-            || url_lower.contains("tripadvisor.com")
-            || url_lower.contains("skyscanner.com")
-            || url_lower.contains("google.com/flights")
-            || url_lower.contains("google.com/maps")
-            || url_lower.contains("maps.google")
-            || url_lower.contains("hotels.com")
-            || url_lower.contains("vrbo.com")
-            || combined.contains("travel")
-            || combined.contains("flight")
-            || combined.contains("hotel")
-            || combined.contains("vacation")
-        {
-            return BookmarkCategory::Travel;
-        }
-
-        // Food & Recipes
-        if url_lower.contains("allrecipes.com")
-            || url_lower.contains("foodnetwork.com")
-            || url_lower.contains("epicurious.com")
-            || url_lower.contains("bonappetit.com")
-            || url_lower.contains("seriouseats.com")
-            || url_lower.contains("tasty.co")
-            || url_lower.contains("doordash.com")
-            || url_lower.contains("ubereats.com")
-            || url_lower.contains("grubhub.com")
-            || url_lower.contains("postmates.com")
-            || url_lower.contains("yelp.com")
-            || combined.contains("recipe")
-            || combined.contains("cooking")
-            || combined.contains("restaurant")
-        {
-            return BookmarkCategory::Food;
-        }
-
-        // Sports
-        if url_lower.contains("espn.com")
-            || url_lower.contains("sports.")
-            || url_lower.contains("nfl.com")
-            || url_lower.contains("nba.com")
-            || url_lower.contains("mlb.com")
-            || url_lower.contains("nhl.com")
-            || url_lower.contains("fifa.com")
-            || url_lower.contains("uefa.com")
-            || url_lower.contains("olympics.com")
-            || combined.contains("score")
-            || combined.contains("league")
-            || combined.contains("team")
+        Ok(Self {
+            custom,
+            rules: RuleSet::with_rules(rules),
+            language_folders: config
+                .get_bool("bookmarks.language_folders")
+                .unwrap_or(false),
+        })
+    }
+
+    pub fn language_folders(&self) -> bool {
+        self.language_folders
+    }
+
+    /// Categorization rules in evaluation order
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// User-defined categories, in config order
+    pub fn custom_categories(&self) -> &[Arc<CustomCategory>] {
+        &self.custom
+    }
+
+    /// All known categories: built-ins followed by user-defined ones
+    pub fn all(&self) -> Vec<BookmarkCategory> {
+        let mut categories = BookmarkCategory::builtins();
+        categories.extend(self.custom.iter().cloned().map(BookmarkCategory::Custom));
+        categories
+    }
+
+    /// Categorize a bookmark, checking user-defined categories before the built-in rules
+    ///
+    /// Bookmarklets and internal pages always get the Bookmarklets/Internal category.
+    pub fn categorize(&self, url: &str, title: &str) -> BookmarkCategory {
+        self.categorize_scored(url, title).0
+    }
+
+    /// Categorize a bookmark and score the match, on the same scale as
+    /// `BookmarkCategory::from_url_and_title_scored`
+    ///
+    /// `Language/` fallbacks score 0.5.
+    pub fn categorize_scored(&self, url: &str, title: &str) -> (BookmarkCategory, f64) {
+        let (category, confidence) = categorize_scored_with(&self.rules, url, title);
+
+        if self.language_folders
+            && category == BookmarkCategory::Other
+            && let Some(lang) = detect_language(title).filter(|l| *l != whatlang::Lang::Eng)
         {
-            return BookmarkCategory::Sports;
+            return (language_category(lang), 0.5);
         }
 
-        BookmarkCategory::Other
+        (category, confidence)
+    }
+}
+
+/// One clause per URL, title or either-field pattern, each also requiring
+/// every `exclude` pattern to be absent
+fn pattern_clauses(
+    url_patterns: &[String],
+    title_patterns: &[String],
+    any_patterns: &[String],
+    exclude: &[String],
+) -> Vec<Vec<Term>> {
+    let terms = |patterns: &[String], field: Field| -> Vec<Term> {
+        patterns
+            .iter()
+            .map(|p| Term::new(field, p.as_str(), false))
+            .collect()
+    };
+
+    terms(url_patterns, Field::Url)
+        .into_iter()
+        .chain(terms(title_patterns, Field::Title))
+        .chain(terms(any_patterns, Field::Any))
+        .map(|term| {
+            let mut clause = vec![term];
+            clause.extend(
+                exclude
+                    .iter()
+                    .map(|p| Term::new(Field::Any, p.as_str(), true)),
+            );
+            clause
+        })
+        .collect()
+}
+
+/// Confidence for a category match, given which signals support it on their own
+fn signal_confidence(url_matches: bool, title_matches: bool) -> f64 {
+    match (url_matches, title_matches) {
+        (true, true) => 1.0,
+        (true, false) => 0.8,
+        (false, true) => 0.5,
+        (false, false) => 0.3,
     }
 }
 
+/// Default confidence below which organize suggestions go to "Needs Review"
+pub const DEFAULT_REVIEW_THRESHOLD: f64 = 0.6;
+
+/// Minimum whatlang confidence for a title's language to count
+///
+/// Titles are short, so whatlang rarely flags them as reliable; misdetected
+/// English titles stay well below this.
+const LANGUAGE_MIN_CONFIDENCE: f64 = 0.5;
+
+/// Detect the language of a bookmark title, when whatlang is confident about it
+pub fn detect_language(text: &str) -> Option<whatlang::Lang> {
+    whatlang::detect(text)
+        .filter(|info| info.is_reliable() || info.confidence() >= LANGUAGE_MIN_CONFIDENCE)
+        .map(|info| info.lang())
+}
+
+/// The `Language/<language>` category used for uncategorized non-English bookmarks
+fn language_category(lang: whatlang::Lang) -> BookmarkCategory {
+    BookmarkCategory::Custom(Arc::new(CustomCategory {
+        name: lang.eng_name().to_string(),
+        emoji: "🌍".to_string(),
+        folder: format!("Language/{}", lang.eng_name()),
+        url_patterns: Vec::new(),
+        title_patterns: Vec::new(),
+    }))
+}
+
 /// A Chrome bookmark entry
 #[derive(Debug, Clone)]
 pub struct Bookmark {
@@ -1324,6 +762,192 @@ pub struct Bookmark {
     pub date_added: Option<String>,
     pub folder_path: String,
     pub category: BookmarkCategory,
+    /// How confident the categorizer is about `category` (0.0 - 1.0)
+    pub confidence: f64,
+}
+
+impl Bookmark {
+    /// Root this bookmark lives under (`bookmark_bar`, `other`, `synced`)
+    pub fn root(&self) -> &str {
+        self.folder_path.split('/').next().unwrap_or("")
+    }
+
+    pub fn is_mobile(&self) -> bool {
+        is_mobile_root(&self.folder_path)
+    }
+
+    /// Whether this bookmark lives under one of `roots` (all roots when empty)
+    pub fn in_roots(&self, roots: &[String]) -> bool {
+        in_roots(&self.folder_path, roots)
+    }
+
+    /// Bookmarklet (`javascript:`) or browser-internal page (`chrome://`, `about:`, `file://`)
+    pub fn is_internal(&self) -> bool {
+        is_internal_url(&self.url)
+    }
+}
+
+/// Check whether a URL is a bookmarklet or browser-internal page
+pub fn is_internal_url(url: &str) -> bool {
+    let url = url.trim_start().to_lowercase();
+    INTERNAL_SCHEMES
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+}
+
+/// Check whether a bookmark or folder path lives under a mobile/synced root
+pub fn is_mobile_root(path: &str) -> bool {
+    let root = path.split('/').next().unwrap_or("");
+    MOBILE_ROOTS.contains(&root)
+}
+
+/// Query parameters that usually carry a secret or a session
+const SECRET_QUERY_PARAMS: &[&str] = &[
+    "token",
+    "access_token",
+    "id_token",
+    "refresh_token",
+    "api_key",
+    "apikey",
+    "key",
+    "secret",
+    "password",
+    "pwd",
+    "auth",
+    "sig",
+    "signature",
+    "session",
+    "sessionid",
+    "sid",
+    "code",
+    "x-amz-signature",
+];
+
+/// Host suffixes that only resolve on a private network
+const PRIVATE_HOST_SUFFIXES: &[&str] = &[
+    ".local",
+    ".internal",
+    ".corp",
+    ".lan",
+    ".intranet",
+    ".home.arpa",
+];
+
+/// Decides which bookmarks are too private to share (`--exclude-sensitive`)
+///
+/// Built in: URLs with credentials or secret-looking query parameters,
+/// `file://` links and private-network hosts. More can be added in config:
+///
+/// ```toml
+/// [bookmarks.sensitive]
+/// domains = ["mybank.com", "jira.example.com"]
+/// folders = ["Personal", "Finance"]
+/// keywords = ["salary", "medical"]
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SensitiveFilter {
+    domains: Vec<String>,
+    folders: Vec<String>,
+    keywords: Vec<String>,
+}
+
+impl SensitiveFilter {
+    /// Load the extra sensitive patterns from the global config file
+    pub fn load() -> Result<Self> {
+        Ok(Self::from_config(&Config::load()?))
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        let lowercase = |key: &str| {
+            config
+                .get_str_list(key)
+                .into_iter()
+                .map(|s| s.to_lowercase())
+                .collect()
+        };
+
+        Self {
+            domains: lowercase("bookmarks.sensitive.domains"),
+            folders: lowercase("bookmarks.sensitive.folders"),
+            keywords: lowercase("bookmarks.sensitive.keywords"),
+        }
+    }
+
+    /// Why a bookmark shouldn't be shared, if it shouldn't
+    pub fn reason(&self, bookmark: &Bookmark) -> Option<String> {
+        let url_lower = bookmark.url.to_lowercase();
+        if url_lower.starts_with("file:") {
+            return Some("local file".to_string());
+        }
+
+        if let Ok(url) = reqwest::Url::parse(&bookmark.url) {
+            if !url.username().is_empty() || url.password().is_some() {
+                return Some("credentials in URL".to_string());
+            }
+            if let Some((param, _)) = url
+                .query_pairs()
+                .find(|(name, _)| SECRET_QUERY_PARAMS.contains(&name.to_lowercase().as_str()))
+            {
+                return Some(format!("secret query parameter '{}'", param));
+            }
+            if let Some(host) = url.host_str() {
+                let host = host.trim_start_matches('[').trim_end_matches(']');
+                let private = match host.parse::<IpAddr>() {
+                    Ok(IpAddr::V4(ip)) => ip.is_private() || ip.is_loopback() || ip.is_link_local(),
+                    Ok(IpAddr::V6(ip)) => ip.is_loopback(),
+                    Err(_) => {
+                        host == "localhost"
+                            || PRIVATE_HOST_SUFFIXES.iter().any(|s| host.ends_with(s))
+                    }
+                };
+                if private {
+                    return Some("private network host".to_string());
+                }
+            }
+        }
+
+        let domain = extract_domain(&bookmark.url);
+        if let Some(d) = self
+            .domains
+            .iter()
+            .find(|d| domain == **d || domain.ends_with(&format!(".{}", d)))
+        {
+            return Some(format!("sensitive domain '{}'", d));
+        }
+
+        let folder_lower = bookmark.folder_path.to_lowercase();
+        if let Some(f) = self
+            .folders
+            .iter()
+            .find(|f| folder_lower.split('/').any(|part| part == f.as_str()))
+        {
+            return Some(format!("sensitive folder '{}'", f));
+        }
+
+        let title_lower = bookmark.name.to_lowercase();
+        self.keywords
+            .iter()
+            .find(|k| title_lower.contains(k.as_str()) || url_lower.contains(k.as_str()))
+            .map(|k| format!("sensitive keyword '{}'", k))
+    }
+}
+
+/// Check whether a bookmark or folder path lives under one of `roots`
+/// (all roots when empty)
+pub fn in_roots(path: &str, roots: &[String]) -> bool {
+    let root = path.split('/').next().unwrap_or("");
+    roots.is_empty() || roots.iter().any(|r| r == root)
+}
+
+/// Resolve a `--root` name to Chrome's root key; `bar` and `mobile` are
+/// accepted for `bookmark_bar` and `synced`
+pub fn parse_root(name: &str) -> Option<&'static str> {
+    match name.trim().to_lowercase().as_str() {
+        "bar" | "bookmark_bar" => Some("bookmark_bar"),
+        "other" => Some("other"),
+        "mobile" | "synced" => Some("synced"),
+        _ => None,
+    }
 }
 
 /// A bookmark folder
@@ -1345,6 +969,7 @@ pub struct BookmarkStats {
     pub by_category: HashMap<String, usize>,
     pub empty_folders: usize,
     pub deep_nesting_count: usize,
+    pub sync_orphans: usize,
 }
 
 /// Entry for duplicate bookmarks table
@@ -1369,6 +994,36 @@ pub struct DomainEntry {
     pub percentage: String,
 }
 
+/// Entry for the title language report
+#[derive(Tabled, Clone)]
+pub struct LanguageEntry {
+    #[tabled(rename = "Language")]
+    pub language: String,
+    #[tabled(rename = "Count")]
+    pub count: usize,
+    #[tabled(rename = "Percentage")]
+    pub percentage: String,
+    #[tabled(rename = "Uncategorized")]
+    pub uncategorized: usize,
+}
+
+/// Entry for the per-root statistics table
+#[derive(Tabled, Clone)]
+pub struct RootStatsEntry {
+    #[tabled(rename = "Root")]
+    pub root: String,
+    #[tabled(rename = "Bookmarks")]
+    pub bookmarks: usize,
+    #[tabled(rename = "Folders")]
+    pub folders: usize,
+    #[tabled(rename = "Categories")]
+    pub categories: usize,
+    #[tabled(rename = "Top Category")]
+    pub top_category: String,
+    #[tabled(rename = "Duplicates")]
+    pub duplicates: usize,
+}
+
 /// Entry for category statistics table
 #[derive(Tabled, Clone)]
 pub struct CategoryEntry {
@@ -1380,6 +1035,77 @@ pub struct CategoryEntry {
     pub percentage: String,
 }
 
+/// Entry for category definitions table
+#[derive(Tabled, Clone)]
+pub struct CategoryDefinitionEntry {
+    #[tabled(rename = "Category")]
+    pub category: String,
+    #[tabled(rename = "Folder")]
+    pub folder: String,
+    #[tabled(rename = "Source")]
+    pub source: String,
+    #[tabled(rename = "Patterns")]
+    pub patterns: String,
+}
+
+/// Entry for sync orphans table (mobile copies of desktop bookmarks)
+#[derive(Tabled, Clone)]
+pub struct SyncOrphanEntry {
+    #[tabled(rename = "Title")]
+    pub title: String,
+    #[tabled(rename = "URL")]
+    pub url: String,
+    #[tabled(rename = "Mobile Folder")]
+    pub mobile_folder: String,
+    #[tabled(rename = "Desktop Folder")]
+    pub desktop_folder: String,
+}
+
+/// Entry for the category migration table
+#[derive(Tabled, Clone)]
+pub struct RecategorizeEntry {
+    #[tabled(rename = "Title")]
+    pub title: String,
+    #[tabled(rename = "Old Category")]
+    pub old_category: String,
+    #[tabled(rename = "New Category")]
+    pub new_category: String,
+    #[tabled(rename = "Folder")]
+    pub folder: String,
+}
+
+/// Entry for the folder rename suggestions table
+#[derive(Tabled, Clone)]
+pub struct FolderRenameEntry {
+    #[tabled(rename = "Folder")]
+    pub folder: String,
+    #[tabled(rename = "Bookmarks")]
+    pub bookmarks: usize,
+    #[tabled(rename = "Dominant Category")]
+    pub dominant_category: String,
+    #[tabled(rename = "Share")]
+    pub share: String,
+    #[tabled(rename = "Suggested Name")]
+    pub suggested_name: String,
+}
+
+/// Entry for per-folder statistics table
+#[derive(Tabled, Clone)]
+pub struct FolderStatsEntry {
+    #[tabled(rename = "Folder")]
+    pub folder: String,
+    #[tabled(rename = "Bookmarks")]
+    pub bookmarks: usize,
+    #[tabled(rename = "Subfolders")]
+    pub subfolders: usize,
+    #[tabled(rename = "Dominant Category")]
+    pub dominant_category: String,
+    #[tabled(rename = "Duplicates")]
+    pub duplicates: usize,
+    #[tabled(rename = "Dead Links")]
+    pub dead_links: String,
+}
+
 /// Entry for bookmarks table
 #[derive(Tabled, Clone)]
 pub struct BookmarkTableEntry {
@@ -1404,19 +1130,21 @@ pub struct OrganizeSuggestion {
     pub suggested_folder: String,
     #[tabled(rename = "Category")]
     pub category: String,
+    #[tabled(rename = "Confidence")]
+    pub confidence: String,
 }
 
 /// Get the Chrome bookmarks file path
+///
+/// `[bookmarks] file` in the config picks another browser or profile.
 pub fn get_chrome_bookmarks_path() -> Result<PathBuf> {
-    let home = std::env::var("HOME").context("HOME environment variable not set")?;
-    let path = PathBuf::from(home).join(CHROME_BOOKMARKS_PATH);
+    let path = match Config::load()?.get_str("bookmarks.file") {
+        Some(file) => expand_home(file)?,
+        None => home_dir()?.join(CHROME_BOOKMARKS_PATH),
+    };
 
     if !path.exists() {
-        anyhow::bail!(
-            "Chrome bookmarks file not found at: {}\n\
-             Make sure Chrome is installed and you have bookmarks saved.",
-            path.display()
-        );
+        return Err(BookmarksError::NotFound(path));
     }
 
     Ok(path)
@@ -1424,12 +1152,13 @@ pub fn get_chrome_bookmarks_path() -> Result<PathBuf> {
 
 /// Parse the Chrome bookmarks JSON file
 pub fn parse_bookmarks() -> Result<(Vec<Bookmark>, Vec<BookmarkFolder>)> {
+    let registry = CategoryRegistry::load()?;
     let path = get_chrome_bookmarks_path()?;
-    let content = fs::read_to_string(&path)
-        .with_context(|| format!("Failed to read bookmarks file: {}", path.display()))?;
+    let content =
+        fs::read_to_string(&path).map_err(BookmarksError::file("read bookmarks file", &path))?;
 
     let json: serde_json::Value =
-        serde_json::from_str(&content).context("Failed to parse bookmarks JSON")?;
+        serde_json::from_str(&content).map_err(BookmarksError::parse("bookmarks file", &path))?;
 
     let mut bookmarks = Vec::new();
     let mut folders = Vec::new();
@@ -1440,7 +1169,13 @@ pub fn parse_bookmarks() -> Result<(Vec<Bookmark>, Vec<BookmarkFolder>)> {
             if root_name == "sync_transaction_version" {
                 continue;
             }
-            parse_bookmark_node(root_value, root_name, &mut bookmarks, &mut folders);
+            parse_bookmark_node(
+                root_value,
+                root_name,
+                &registry,
+                &mut bookmarks,
+                &mut folders,
+            );
         }
     }
 
@@ -1451,6 +1186,7 @@ pub fn parse_bookmarks() -> Result<(Vec<Bookmark>, Vec<BookmarkFolder>)> {
 fn parse_bookmark_node(
     node: &serde_json::Value,
     current_path: &str,
+    registry: &CategoryRegistry,
     bookmarks: &mut Vec<Bookmark>,
     folders: &mut Vec<BookmarkFolder>,
 ) {
@@ -1478,7 +1214,7 @@ fn parse_bookmark_node(
             .and_then(|d| d.as_str())
             .map(|s| s.to_string());
 
-        let category = BookmarkCategory::from_url_and_title(&url, &name);
+        let (category, confidence) = registry.categorize_scored(&url, &name);
 
         bookmarks.push(Bookmark {
             id,
@@ -1487,6 +1223,7 @@ fn parse_bookmark_node(
             date_added,
             folder_path: current_path.to_string(),
             category,
+            confidence,
         });
     } else if node_type == "folder" {
         // This is a folder
@@ -1511,7 +1248,7 @@ fn parse_bookmark_node(
         if let Some(children) = node.get("children").and_then(|c| c.as_array()) {
             children_count = children.len();
             for child in children {
-                parse_bookmark_node(child, &folder_path, bookmarks, folders);
+                parse_bookmark_node(child, &folder_path, registry, bookmarks, folders);
             }
         }
 
@@ -1545,6 +1282,59 @@ pub fn extract_domain(url: &str) -> String {
     domain.strip_prefix("www.").unwrap_or(domain).to_string()
 }
 
+/// ICANN section of the public suffix list (`data/public_suffix_list.dat`)
+///
+/// Privately registered suffixes (`github.io`, `blogspot.com`, ...) are left
+/// out on purpose, so sites hosted on a platform group under the platform.
+static PUBLIC_SUFFIXES: LazyLock<publicsuffix::IcannList> = LazyLock::new(|| {
+    include_str!("../data/public_suffix_list.dat")
+        .parse()
+        .expect("bundled public suffix list is valid")
+});
+
+/// Registrable domain of a URL: the public suffix plus one label
+///
+/// `maps.google.co.uk` -> `google.co.uk`, `foo.github.io` -> `github.io`.
+/// IP addresses and single-label hosts (`localhost`) are returned as-is.
+pub fn registrable_domain(url: &str) -> String {
+    use publicsuffix::Psl;
+
+    let host = extract_domain(url);
+    let host = match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name.to_string(),
+        _ => host,
+    };
+
+    if host.parse::<IpAddr>().is_ok() || !host.contains('.') {
+        return host;
+    }
+
+    PUBLIC_SUFFIXES
+        .domain(host.as_bytes())
+        .and_then(|domain| std::str::from_utf8(domain.as_bytes()).ok())
+        .map(|domain| domain.to_string())
+        .unwrap_or(host)
+}
+
+/// How domain statistics group bookmarks (`--group-by`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DomainGrouping {
+    /// Full host, minus `www.`
+    Subdomain,
+    /// Registrable domain, see `registrable_domain`
+    #[default]
+    Domain,
+}
+
+impl DomainGrouping {
+    pub fn key(self, url: &str) -> String {
+        match self {
+            DomainGrouping::Subdomain => extract_domain(url),
+            DomainGrouping::Domain => registrable_domain(url),
+        }
+    }
+}
+
 /// Find duplicate bookmarks
 pub fn find_duplicates(bookmarks: &[Bookmark]) -> Vec<DuplicateEntry> {
     let mut url_map: HashMap<String, Vec<&Bookmark>> = HashMap::new();
@@ -1570,23 +1360,287 @@ pub fn find_duplicates(bookmarks: &[Bookmark]) -> Vec<DuplicateEntry> {
         })
         .collect();
 
-    duplicates.sort_by(|a, b| b.count.cmp(&a.count));
+    duplicates.sort_by_key(|d| std::cmp::Reverse(d.count));
     duplicates
 }
 
-/// Get domain statistics
-pub fn get_domain_stats(bookmarks: &[Bookmark]) -> Vec<DomainEntry> {
-    let mut domain_counts: HashMap<String, usize> = HashMap::new();
-
-    for bookmark in bookmarks {
-        let domain = extract_domain(&bookmark.url);
-        *domain_counts.entry(domain).or_insert(0) += 1;
+/// Pair each mobile/synced bookmark with a desktop bookmark of the same URL
+fn sync_orphan_pairs(bookmarks: &[Bookmark]) -> Vec<(&Bookmark, &Bookmark)> {
+    let mut desktop_urls: HashMap<&str, &Bookmark> = HashMap::new();
+    for bookmark in bookmarks.iter().filter(|b| !b.is_mobile()) {
+        desktop_urls
+            .entry(bookmark.url.as_str())
+            .or_insert(bookmark);
     }
 
-    let total = bookmarks.len() as f64;
-    let mut entries: Vec<DomainEntry> = domain_counts
-        .into_iter()
-        .map(|(domain, count)| {
+    bookmarks
+        .iter()
+        .filter(|b| b.is_mobile())
+        .filter_map(|b| {
+            desktop_urls
+                .get(b.url.as_str())
+                .map(|desktop| (b, *desktop))
+        })
+        .collect()
+}
+
+/// Find bookmarks under mobile/synced roots that duplicate a desktop bookmark
+pub fn find_sync_orphans(bookmarks: &[Bookmark]) -> Vec<SyncOrphanEntry> {
+    sync_orphan_pairs(bookmarks)
+        .into_iter()
+        .map(|(mobile, desktop)| SyncOrphanEntry {
+            title: truncate_string(&mobile.name, 40),
+            url: truncate_string(&mobile.url, 50),
+            mobile_folder: mobile.folder_path.clone(),
+            desktop_folder: desktop.folder_path.clone(),
+        })
+        .collect()
+}
+
+/// Get per-folder statistics, messiest folders (most duplicates + dead links) first
+///
+/// Counts only cover bookmarks directly inside each folder. Dead links are
+/// shown as "-" unless the dead bookmarks from a link check are passed in.
+pub fn get_folder_stats(
+    bookmarks: &[Bookmark],
+    folders: &[BookmarkFolder],
+    dead: Option<&[(&Bookmark, String)]>,
+) -> Vec<FolderStatsEntry> {
+    let mut url_counts: HashMap<&str, usize> = HashMap::new();
+    for bookmark in bookmarks {
+        *url_counts.entry(bookmark.url.as_str()).or_insert(0) += 1;
+    }
+
+    let dead_ids: HashSet<&str> = dead
+        .unwrap_or_default()
+        .iter()
+        .map(|(b, _)| b.id.as_str())
+        .collect();
+
+    let mut entries: Vec<(usize, FolderStatsEntry)> = folders
+        .iter()
+        .map(|folder| {
+            let in_folder: Vec<&Bookmark> = bookmarks
+                .iter()
+                .filter(|b| b.folder_path == folder.path)
+                .collect();
+
+            let subfolders = folders
+                .iter()
+                .filter(|f| {
+                    f.path
+                        .strip_prefix(folder.path.as_str())
+                        .and_then(|rest| rest.strip_prefix('/'))
+                        .is_some_and(|rest| !rest.contains('/'))
+                })
+                .count();
+
+            let mut category_counts: HashMap<String, usize> = HashMap::new();
+            for bookmark in &in_folder {
+                *category_counts
+                    .entry(bookmark.category.to_string())
+                    .or_insert(0) += 1;
+            }
+            let dominant_category = category_counts
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+                .map(|(category, _)| category)
+                .unwrap_or_else(|| "-".to_string());
+
+            let duplicates = in_folder
+                .iter()
+                .filter(|b| url_counts.get(b.url.as_str()).copied().unwrap_or(0) > 1)
+                .count();
+
+            let dead_count = in_folder
+                .iter()
+                .filter(|b| dead_ids.contains(b.id.as_str()))
+                .count();
+
+            let entry = FolderStatsEntry {
+                folder: truncate_string(&folder.path, 50),
+                bookmarks: in_folder.len(),
+                subfolders,
+                dominant_category,
+                duplicates,
+                dead_links: if dead.is_some() {
+                    dead_count.to_string()
+                } else {
+                    "-".to_string()
+                },
+            };
+            (duplicates + dead_count, entry)
+        })
+        .collect();
+
+    entries.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| b.1.bookmarks.cmp(&a.1.bookmarks))
+            .then_with(|| a.1.folder.cmp(&b.1.folder))
+    });
+    entries.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Build the folder hierarchy as trees, one per root folder
+///
+/// Each node is labelled with the folder name and the number of bookmarks
+/// directly inside it.
+pub fn get_folder_tree(bookmarks: &[Bookmark], folders: &[BookmarkFolder]) -> Vec<TreeNode> {
+    let paths: HashSet<&str> = folders.iter().map(|f| f.path.as_str()).collect();
+    let parent_of = |path: &str| path.rsplit_once('/').map(|(parent, _)| parent.to_string());
+
+    let mut children: HashMap<String, Vec<&BookmarkFolder>> = HashMap::new();
+    let mut roots = Vec::new();
+    for folder in folders {
+        match parent_of(&folder.path) {
+            Some(parent) if paths.contains(parent.as_str()) => {
+                children.entry(parent).or_default().push(folder)
+            }
+            _ => roots.push(folder),
+        }
+    }
+
+    let mut bookmark_counts: HashMap<&str, usize> = HashMap::new();
+    for bookmark in bookmarks {
+        *bookmark_counts
+            .entry(bookmark.folder_path.as_str())
+            .or_insert(0) += 1;
+    }
+
+    fn build(
+        folder: &BookmarkFolder,
+        children: &HashMap<String, Vec<&BookmarkFolder>>,
+        bookmark_counts: &HashMap<&str, usize>,
+    ) -> TreeNode {
+        let count = bookmark_counts
+            .get(folder.path.as_str())
+            .copied()
+            .unwrap_or(0);
+        let mut node = TreeNode::new(format!("📁 {} ({})", folder.name, count));
+        if let Some(subfolders) = children.get(&folder.path) {
+            for subfolder in subfolders {
+                node.children
+                    .push(build(subfolder, children, bookmark_counts));
+            }
+        }
+        node
+    }
+
+    roots
+        .into_iter()
+        .map(|root| build(root, &children, &bookmark_counts))
+        .collect()
+}
+
+/// A suggested folder rename, keyed by the folder's ID in the bookmarks JSON
+#[derive(Clone)]
+pub struct FolderRename {
+    pub folder_id: String,
+    pub entry: FolderRenameEntry,
+}
+
+/// Suggest names for folders whose contents are dominated by one category
+///
+/// A folder qualifies when it holds at least `[bookmarks.rename] min_bookmarks`
+/// bookmarks (default 3), one category covers at least `min_share` percent of
+/// them (default 70), and the current name doesn't already mention it. The
+/// suggestion is the last part of the category's folder name ("Python" for
+/// Development/Python).
+pub fn suggest_folder_renames(
+    bookmarks: &[Bookmark],
+    folders: &[BookmarkFolder],
+    config: &Config,
+) -> Vec<FolderRename> {
+    let setting = |key: &str, default: i64| {
+        config
+            .get(key)
+            .and_then(|v| v.as_integer())
+            .unwrap_or(default)
+            .max(0) as usize
+    };
+    let min_bookmarks = setting("bookmarks.rename.min_bookmarks", 3).max(1);
+    let min_share = setting("bookmarks.rename.min_share", 70);
+
+    let paths: HashSet<&str> = folders.iter().map(|f| f.path.as_str()).collect();
+
+    let mut renames: Vec<(usize, FolderRename)> = folders
+        .iter()
+        // Root folders (Bookmarks bar, Other bookmarks, ...) can't be renamed
+        .filter(|folder| {
+            folder
+                .path
+                .rsplit_once('/')
+                .is_some_and(|(parent, _)| paths.contains(parent))
+        })
+        .filter_map(|folder| {
+            let mut counts: Vec<(&BookmarkCategory, usize)> = Vec::new();
+            let mut total = 0;
+            for bookmark in bookmarks.iter().filter(|b| b.folder_path == folder.path) {
+                total += 1;
+                match counts.iter_mut().find(|(c, _)| **c == bookmark.category) {
+                    Some((_, n)) => *n += 1,
+                    None => counts.push((&bookmark.category, 1)),
+                }
+            }
+            if total < min_bookmarks {
+                return None;
+            }
+
+            let (category, count) = counts.into_iter().max_by_key(|(_, n)| *n)?;
+            let share = count * 100 / total;
+            if *category == BookmarkCategory::Other || share < min_share {
+                return None;
+            }
+
+            let suggested = category
+                .folder_name()
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let current = folder.name.to_lowercase();
+            let wanted = suggested.to_lowercase();
+            if current.contains(&wanted) || wanted.contains(&current) {
+                return None;
+            }
+
+            Some((
+                share,
+                FolderRename {
+                    folder_id: folder.id.clone(),
+                    entry: FolderRenameEntry {
+                        folder: truncate_string(&folder.path, 50),
+                        bookmarks: total,
+                        dominant_category: category.to_string(),
+                        share: format!("{}%", share),
+                        suggested_name: suggested,
+                    },
+                },
+            ))
+        })
+        .collect();
+
+    // Most clear-cut suggestions first
+    renames.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| b.1.entry.bookmarks.cmp(&a.1.entry.bookmarks))
+    });
+    renames.into_iter().map(|(_, rename)| rename).collect()
+}
+
+/// Get domain statistics
+pub fn get_domain_stats(bookmarks: &[Bookmark], grouping: DomainGrouping) -> Vec<DomainEntry> {
+    let mut domain_counts: HashMap<String, usize> = HashMap::new();
+
+    for bookmark in bookmarks {
+        let domain = grouping.key(&bookmark.url);
+        *domain_counts.entry(domain).or_insert(0) += 1;
+    }
+
+    let total = bookmarks.len() as f64;
+    let mut entries: Vec<DomainEntry> = domain_counts
+        .into_iter()
+        .map(|(domain, count)| {
             let percentage = count as f64 / total * 100.0;
             DomainEntry {
                 domain: truncate_string(&domain, 40),
@@ -1596,7 +1650,49 @@ pub fn get_domain_stats(bookmarks: &[Bookmark]) -> Vec<DomainEntry> {
         })
         .collect();
 
-    entries.sort_by(|a, b| b.count.cmp(&a.count));
+    entries.sort_by_key(|e| std::cmp::Reverse(e.count));
+    entries
+}
+
+/// Count bookmarks per detected title language
+///
+/// Titles too short or mixed for a reliable guess are counted as "Unknown".
+/// `Uncategorized` counts bookmarks no category rule matched (Other or a
+/// `Language/` folder).
+pub fn get_language_stats(bookmarks: &[Bookmark]) -> Vec<LanguageEntry> {
+    let detected: Vec<(String, bool)> = bookmarks
+        .par_iter()
+        .map(|bookmark| {
+            let language = detect_language(&bookmark.name)
+                .map(|lang| lang.eng_name().to_string())
+                .unwrap_or_else(|| "Unknown".to_string());
+            let uncategorized = bookmark.category == BookmarkCategory::Other
+                || bookmark.category.folder_name().starts_with("Language/");
+            (language, uncategorized)
+        })
+        .collect();
+
+    let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+    for (language, uncategorized) in detected {
+        let entry = counts.entry(language).or_insert((0, 0));
+        entry.0 += 1;
+        if uncategorized {
+            entry.1 += 1;
+        }
+    }
+
+    let total = bookmarks.len() as f64;
+    let mut entries: Vec<LanguageEntry> = counts
+        .into_iter()
+        .map(|(language, (count, uncategorized))| LanguageEntry {
+            language,
+            count,
+            percentage: format!("{:.1}%", count as f64 / total * 100.0),
+            uncategorized,
+        })
+        .collect();
+
+    entries.sort_by(|a, b| b.count.cmp(&a.count).then(a.language.cmp(&b.language)));
     entries
 }
 
@@ -1622,13 +1718,48 @@ pub fn get_category_stats(bookmarks: &[Bookmark]) -> Vec<CategoryEntry> {
         })
         .collect();
 
-    entries.sort_by(|a, b| b.count.cmp(&a.count));
+    entries.sort_by_key(|e| std::cmp::Reverse(e.count));
     entries
 }
 
-/// Get organization suggestions
-pub fn get_organize_suggestions(bookmarks: &[Bookmark]) -> Vec<OrganizeSuggestion> {
+/// List every category known to the registry
+pub fn get_category_definitions(registry: &CategoryRegistry) -> Vec<CategoryDefinitionEntry> {
+    registry
+        .all()
+        .into_iter()
+        .map(|category| {
+            let (source, patterns) = match &category {
+                BookmarkCategory::Custom(custom) => {
+                    let all_patterns: Vec<&str> = custom
+                        .url_patterns
+                        .iter()
+                        .chain(custom.title_patterns.iter())
+                        .map(|p| p.as_str())
+                        .collect();
+                    ("Custom".to_string(), all_patterns.join(", "))
+                }
+                _ => ("Built-in".to_string(), "-".to_string()),
+            };
+            CategoryDefinitionEntry {
+                category: category.to_string(),
+                folder: category.folder_name().to_string(),
+                source,
+                patterns: truncate_string(&patterns, 40),
+            }
+        })
+        .collect()
+}
+
+/// Suggest category folders for bookmarks that aren't in one yet
+///
+/// Returns `(suggestions, needs_review)`: matches scoring below
+/// `min_confidence` are set aside for review instead of being suggested.
+pub fn get_organize_suggestions(
+    bookmarks: &[Bookmark],
+    min_confidence: f64,
+) -> (Vec<OrganizeSuggestion>, Vec<OrganizeSuggestion>) {
     let mut suggestions = Vec::new();
+    let mut needs_review = Vec::new();
 
     for bookmark in bookmarks {
         let suggested_folder = bookmark.category.folder_name();
@@ -1641,24 +1772,35 @@ pub fn get_organize_suggestions(bookmarks: &[Bookmark]) -> Vec<OrganizeSuggestio
                 .to_lowercase()
                 .contains(&suggested_folder.to_lowercase())
         {
-            suggestions.push(OrganizeSuggestion {
+            let suggestion = OrganizeSuggestion {
                 bookmark: truncate_string(&bookmark.name, 40),
                 current_folder: truncate_string(current_folder, 30),
                 suggested_folder: suggested_folder.to_string(),
                 category: bookmark.category.to_string(),
-            });
+                confidence: format!("{:.0}%", bookmark.confidence * 100.0),
+            };
+            if bookmark.confidence < min_confidence {
+                needs_review.push(suggestion);
+            } else {
+                suggestions.push(suggestion);
+            }
         }
     }
 
-    suggestions
+    (suggestions, needs_review)
 }
 
 /// Get bookmark statistics
-pub fn get_bookmark_stats(bookmarks: &[Bookmark], folders: &[BookmarkFolder]) -> BookmarkStats {
-    let mut stats = BookmarkStats::default();
-
-    stats.total_bookmarks = bookmarks.len();
-    stats.total_folders = folders.len();
+pub fn get_bookmark_stats(
+    bookmarks: &[Bookmark],
+    folders: &[BookmarkFolder],
+    grouping: DomainGrouping,
+) -> BookmarkStats {
+    let mut stats = BookmarkStats {
+        total_bookmarks: bookmarks.len(),
+        total_folders: folders.len(),
+        ..Default::default()
+    };
 
     // Count duplicates
     let mut url_counts: HashMap<String, usize> = HashMap::new();
@@ -1669,7 +1811,7 @@ pub fn get_bookmark_stats(bookmarks: &[Bookmark], folders: &[BookmarkFolder]) ->
 
     // Count by domain
     for bookmark in bookmarks {
-        let domain = extract_domain(&bookmark.url);
+        let domain = grouping.key(&bookmark.url);
         *stats.by_domain.entry(domain).or_insert(0) += 1;
     }
 
@@ -1679,6 +1821,8 @@ pub fn get_bookmark_stats(bookmarks: &[Bookmark], folders: &[BookmarkFolder]) ->
         *stats.by_category.entry(category).or_insert(0) += 1;
     }
 
+    stats.sync_orphans = sync_orphan_pairs(bookmarks).len();
+
     // Count empty folders
     stats.empty_folders = folders.iter().filter(|f| f.children_count == 0).count();
 
@@ -1691,8 +1835,72 @@ pub fn get_bookmark_stats(bookmarks: &[Bookmark], folders: &[BookmarkFolder]) ->
     stats
 }
 
+/// Break bookmark counts, categories and duplicates down per root
+///
+/// Duplicates count URLs that appear more than once within the same root, so
+/// a mobile copy of a desktop bookmark doesn't show up here (see `sync-orphans`).
+pub fn get_root_stats(bookmarks: &[Bookmark], folders: &[BookmarkFolder]) -> Vec<RootStatsEntry> {
+    let mut roots: Vec<String> = BOOKMARK_ROOTS.iter().map(|r| r.to_string()).collect();
+    let mut extra: Vec<String> = bookmarks
+        .iter()
+        .map(|b| b.root().to_string())
+        .filter(|root| !roots.contains(root))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    extra.sort();
+    roots.extend(extra);
+
+    roots
+        .into_iter()
+        .map(|root| {
+            let in_root: Vec<&Bookmark> = bookmarks.iter().filter(|b| b.root() == root).collect();
+
+            let mut url_counts: HashMap<&str, usize> = HashMap::new();
+            let mut category_counts: HashMap<String, usize> = HashMap::new();
+            for bookmark in &in_root {
+                *url_counts.entry(bookmark.url.as_str()).or_insert(0) += 1;
+                *category_counts
+                    .entry(bookmark.category.to_string())
+                    .or_insert(0) += 1;
+            }
+
+            let top_category = category_counts
+                .iter()
+                .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                .map(|(category, count)| format!("{} ({})", category, count))
+                .unwrap_or_else(|| "-".to_string());
+
+            RootStatsEntry {
+                folders: folders
+                    .iter()
+                    .filter(|f| f.path.split('/').next() == Some(root.as_str()))
+                    .count(),
+                bookmarks: in_root.len(),
+                categories: category_counts.len(),
+                top_category,
+                duplicates: url_counts.values().filter(|&&count| count > 1).count(),
+                root,
+            }
+        })
+        .collect()
+}
+
 /// Search bookmarks by query
 pub fn search_bookmarks(bookmarks: &[Bookmark], query: &str) -> Vec<BookmarkTableEntry> {
+    search_matches(bookmarks, query)
+        .into_iter()
+        .map(|b| BookmarkTableEntry {
+            title: truncate_string(&b.name, 40),
+            url: truncate_string(&b.url, 50),
+            category: b.category.to_string(),
+            folder: truncate_string(&b.folder_path, 30),
+        })
+        .collect()
+}
+
+/// Bookmarks whose title, URL or folder contains `query` (case-insensitive)
+pub fn search_matches<'a>(bookmarks: &'a [Bookmark], query: &str) -> Vec<&'a Bookmark> {
     let query_lower = query.to_lowercase();
 
     bookmarks
@@ -1702,15 +1910,246 @@ pub fn search_bookmarks(bookmarks: &[Bookmark], query: &str) -> Vec<BookmarkTabl
                 || b.url.to_lowercase().contains(&query_lower)
                 || b.folder_path.to_lowercase().contains(&query_lower)
         })
-        .map(|b| BookmarkTableEntry {
-            title: truncate_string(&b.name, 40),
-            url: truncate_string(&b.url, 50),
-            category: b.category.to_string(),
-            folder: truncate_string(&b.folder_path, 30),
+        .collect()
+}
+
+/// Open a URL in the default browser (`open` on macOS, `xdg-open` elsewhere)
+///
+/// The opener is started in the background; this doesn't wait for the browser.
+pub fn open_url(url: &str) -> Result<()> {
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+
+    Command::new(program)
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|source| BookmarksError::Open {
+            program,
+            url: url.to_string(),
+            source,
+        })?;
+
+    Ok(())
+}
+
+/// A bookmark matched by fuzzy search, with the matched character positions
+pub struct FuzzyMatch<'a> {
+    pub bookmark: &'a Bookmark,
+    pub score: i64,
+    pub title_indices: Vec<usize>,
+    pub url_indices: Vec<usize>,
+    pub folder_indices: Vec<usize>,
+}
+
+/// Rank bookmarks by skim-style fuzzy score across title, URL and folder
+///
+/// Title hits are weighted above URL hits, and URL hits above folder hits, so
+/// a query matching a title outranks one that only appears deep in a URL.
+pub fn fuzzy_rank_bookmarks<'a>(bookmarks: &'a [Bookmark], query: &str) -> Vec<FuzzyMatch<'a>> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+
+    let mut matches: Vec<FuzzyMatch> = bookmarks
+        .iter()
+        .filter_map(|b| {
+            let title = matcher.fuzzy_indices(&b.name, query);
+            let url = matcher.fuzzy_indices(&b.url, query);
+            let folder = matcher.fuzzy_indices(&b.folder_path, query);
+
+            let score = [
+                title.as_ref().map(|(score, _)| score * 3),
+                url.as_ref().map(|(score, _)| score * 2),
+                folder.as_ref().map(|(score, _)| *score),
+            ]
+            .into_iter()
+            .flatten()
+            .max()?;
+
+            Some(FuzzyMatch {
+                bookmark: b,
+                score,
+                title_indices: title.map(|(_, i)| i).unwrap_or_default(),
+                url_indices: url.map(|(_, i)| i).unwrap_or_default(),
+                folder_indices: folder.map(|(_, i)| i).unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.bookmark.name.cmp(&b.bookmark.name))
+    });
+    matches
+}
+
+/// Fuzzy search bookmarks, best matches first, optionally highlighting matched characters
+pub fn fuzzy_search_bookmarks(
+    bookmarks: &[Bookmark],
+    query: &str,
+    highlight: bool,
+) -> Vec<BookmarkTableEntry> {
+    let render = |text: &str, indices: &[usize], max_len: usize| {
+        if highlight {
+            highlight_matches(text, indices, max_len)
+        } else {
+            truncate_string(text, max_len)
+        }
+    };
+
+    fuzzy_rank_bookmarks(bookmarks, query)
+        .into_iter()
+        .map(|m| BookmarkTableEntry {
+            title: render(&m.bookmark.name, &m.title_indices, 40),
+            url: render(&m.bookmark.url, &m.url_indices, 50),
+            category: m.bookmark.category.to_string(),
+            folder: render(&m.bookmark.folder_path, &m.folder_indices, 30),
         })
         .collect()
 }
 
+/// Truncate like `truncate_string`, highlighting the characters at the given positions
+fn highlight_matches(s: &str, indices: &[usize], max_len: usize) -> String {
+    let count = s.chars().count();
+    let keep = if count <= max_len {
+        count
+    } else {
+        max_len.saturating_sub(3)
+    };
+
+    let mut result = String::new();
+    for (i, c) in s.chars().take(keep).enumerate() {
+        if indices.contains(&i) {
+            result.push_str(&c.to_string().bold().yellow().to_string());
+        } else {
+            result.push(c);
+        }
+    }
+
+    if keep < count {
+        result.push_str("...");
+    }
+    result
+}
+
+/// Interactive incremental fuzzy search; returns the bookmark picked with Enter
+pub fn interactive_search<'a>(
+    bookmarks: &'a [Bookmark],
+    initial_query: &str,
+) -> Result<Option<&'a Bookmark>> {
+    use crossterm::{
+        cursor,
+        event::{self, Event, KeyCode, KeyModifiers},
+        execute,
+        terminal::{self, ClearType},
+    };
+    use std::io::{Write, stdout};
+
+    let mut query = initial_query.to_string();
+    let mut selected_idx = 0;
+    let mut stdout = stdout();
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let picked = loop {
+        let matches = if query.is_empty() {
+            bookmarks
+                .iter()
+                .map(|b| FuzzyMatch {
+                    bookmark: b,
+                    score: 0,
+                    title_indices: Vec::new(),
+                    url_indices: Vec::new(),
+                    folder_indices: Vec::new(),
+                })
+                .collect()
+        } else {
+            fuzzy_rank_bookmarks(bookmarks, &query)
+        };
+        selected_idx = selected_idx.min(matches.len().saturating_sub(1));
+
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(ClearType::All)
+        )?;
+
+        write!(
+            stdout,
+            "{}\r\n",
+            "🔍 Bookmark Search - Interactive Mode".bold().cyan()
+        )?;
+        write!(stdout, "{}\r\n", "─".repeat(80).dimmed())?;
+        write!(stdout, "{} {}▏\r\n", "Query:".yellow(), query.bold())?;
+        write!(
+            stdout,
+            "Matches: {} | {}=Navigate {}=Select {}=Quit\r\n",
+            matches.len().to_string().green(),
+            "↑/↓".yellow(),
+            "Enter".yellow(),
+            "Esc".yellow()
+        )?;
+        write!(stdout, "{}\r\n", "─".repeat(80).dimmed())?;
+
+        // Calculate visible window
+        let term_height = terminal::size()?.1 as usize;
+        let list_height = term_height.saturating_sub(7);
+        let start_idx = if selected_idx >= list_height {
+            selected_idx - list_height + 1
+        } else {
+            0
+        };
+
+        for (idx, m) in matches.iter().enumerate().skip(start_idx).take(list_height) {
+            let title = highlight_matches(&m.bookmark.name, &m.title_indices, 40);
+            let url = truncate_string(&m.bookmark.url, 60);
+
+            if idx == selected_idx {
+                write!(
+                    stdout,
+                    "{} {}  {}\r\n",
+                    "▶".cyan(),
+                    title.on_bright_blue().white(),
+                    url.dimmed()
+                )?;
+            } else {
+                write!(stdout, "  {}  {}\r\n", title, url.dimmed())?;
+            }
+        }
+
+        stdout.flush()?;
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Esc => break None,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break None,
+                KeyCode::Enter => break matches.get(selected_idx).map(|m| m.bookmark),
+                KeyCode::Up => selected_idx = selected_idx.saturating_sub(1),
+                KeyCode::Down if selected_idx + 1 < matches.len() => selected_idx += 1,
+                KeyCode::Backspace => {
+                    query.pop();
+                    selected_idx = 0;
+                }
+                KeyCode::Char(c) => {
+                    query.push(c);
+                    selected_idx = 0;
+                }
+                _ => {}
+            }
+        }
+    };
+
+    execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
+    terminal::disable_raw_mode()?;
+
+    Ok(picked)
+}
+
 /// Filter bookmarks by category
 pub fn filter_by_category(bookmarks: &[Bookmark], category: &str) -> Vec<BookmarkTableEntry> {
     let category_lower = category.to_lowercase();
@@ -1748,105 +2187,442 @@ pub fn filter_by_domain(bookmarks: &[Bookmark], domain: &str) -> Vec<BookmarkTab
         .collect()
 }
 
-/// Export bookmarks to markdown
-pub fn export_to_markdown(bookmarks: &[Bookmark], output_path: Option<&str>) -> Result<String> {
-    let mut md = String::new();
+/// How exported bookmarks are grouped into sections/folders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportGrouping {
+    #[default]
+    Category,
+    Folder,
+    Domain,
+}
 
-    md.push_str("# Chrome Bookmarks Export\n\n");
-    md.push_str(&format!("*Exported on: {}*\n\n", chrono_lite_now()));
-    md.push_str(&format!("**Total bookmarks: {}**\n\n", bookmarks.len()));
+/// Order of the groups in an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportOrder {
+    /// Alphabetical by group name
+    #[default]
+    Alpha,
+    /// Largest groups first
+    Count,
+    /// Groups listed in `group_order` first, the rest alphabetically
+    Config,
+}
 
-    // Group by category
-    let mut by_category: HashMap<String, Vec<&Bookmark>> = HashMap::new();
-    for bookmark in bookmarks {
-        by_category
-            .entry(bookmark.category.folder_name().to_string())
-            .or_default()
-            .push(bookmark);
+/// A group of bookmarks in an export, e.g. one category folder
+pub struct ExportGroup<'a> {
+    pub name: String,
+    pub icon: Option<String>,
+    pub description: Option<String>,
+    pub bookmarks: Vec<&'a Bookmark>,
+}
+
+impl ExportGroup<'_> {
+    /// Group name with its icon, when folder icons are enabled
+    pub fn label(&self) -> String {
+        match &self.icon {
+            Some(icon) => format!("{} {}", icon, self.name),
+            None => self.name.clone(),
+        }
     }
+}
+
+/// Theme for the markdown and HTML exports, read from `[bookmarks.export]`
+///
+/// ```toml
+/// [bookmarks.export]
+/// title = "Team Links"
+/// header = "Curated links, regenerated weekly."
+/// group_by = "category"     # category | folder | domain
+/// order = "config"          # alpha | count | config
+/// group_order = ["Development/Rust", "AI-ML/LLMs & Models"]
+/// folder_icons = true
+///
+/// [bookmarks.export.descriptions]
+/// "Development/Rust" = "Crates, books and compiler internals"
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExportTheme {
+    pub title: Option<String>,
+    pub header: Option<String>,
+    pub group_by: ExportGrouping,
+    pub order: ExportOrder,
+    pub group_order: Vec<String>,
+    pub folder_icons: bool,
+    pub descriptions: HashMap<String, String>,
+}
 
-    // Sort categories
-    let mut categories: Vec<_> = by_category.keys().cloned().collect();
-    categories.sort();
+impl ExportTheme {
+    /// Load the export theme from the global config file
+    pub fn load() -> Result<Self> {
+        Self::from_config(&Config::load()?)
+    }
 
-    for category in categories {
-        if let Some(bms) = by_category.get(&category) {
-            md.push_str(&format!("## {}\n\n", category));
-            for bookmark in bms {
-                md.push_str(&format!("- [{}]({})\n", bookmark.name, bookmark.url));
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let group_by = match config.get_str("bookmarks.export.group_by") {
+            None | Some("category") => ExportGrouping::Category,
+            Some("folder") => ExportGrouping::Folder,
+            Some("domain") => ExportGrouping::Domain,
+            Some(other) => {
+                return Err(BookmarksError::InvalidConfig(format!(
+                    "Invalid bookmarks.export.group_by '{}'. Use: category, folder, domain",
+                    other
+                )));
             }
-            md.push('\n');
+        };
+
+        let order = match config.get_str("bookmarks.export.order") {
+            None | Some("alpha") => ExportOrder::Alpha,
+            Some("count") => ExportOrder::Count,
+            Some("config") => ExportOrder::Config,
+            Some(other) => {
+                return Err(BookmarksError::InvalidConfig(format!(
+                    "Invalid bookmarks.export.order '{}'. Use: alpha, count, config",
+                    other
+                )));
+            }
+        };
+
+        Ok(Self {
+            title: config
+                .get_str("bookmarks.export.title")
+                .map(|s| s.to_string()),
+            header: config
+                .get_str("bookmarks.export.header")
+                .map(|s| s.to_string()),
+            group_by,
+            order,
+            group_order: config.get_str_list("bookmarks.export.group_order"),
+            folder_icons: config
+                .get_bool("bookmarks.export.folder_icons")
+                .unwrap_or(false),
+            descriptions: config.get_str_map("bookmarks.export.descriptions"),
+        })
+    }
+
+    /// Group and order bookmarks according to the theme
+    ///
+    /// Bookmarks inside each group are sorted by name.
+    pub fn group<'a>(
+        &self,
+        bookmarks: impl IntoIterator<Item = &'a Bookmark>,
+    ) -> Vec<ExportGroup<'a>> {
+        let mut by_group: HashMap<String, ExportGroup<'a>> = HashMap::new();
+
+        for bookmark in bookmarks {
+            let (name, icon) = match self.group_by {
+                ExportGrouping::Category => (
+                    bookmark.category.folder_name().to_string(),
+                    bookmark
+                        .category
+                        .to_string()
+                        .split_whitespace()
+                        .next()
+                        .map(|s| s.to_string()),
+                ),
+                ExportGrouping::Folder => (bookmark.folder_path.clone(), None),
+                ExportGrouping::Domain => (extract_domain(&bookmark.url), None),
+            };
+
+            by_group
+                .entry(name.clone())
+                .or_insert_with(|| ExportGroup {
+                    description: self.descriptions.get(&name).cloned(),
+                    icon: icon.filter(|_| self.folder_icons),
+                    name,
+                    bookmarks: Vec::new(),
+                })
+                .bookmarks
+                .push(bookmark);
+        }
+
+        let mut groups: Vec<ExportGroup<'a>> = by_group.into_values().collect();
+        for group in &mut groups {
+            group.bookmarks.sort_by_key(|b| b.name.to_lowercase());
+        }
+
+        groups.sort_by(|a, b| a.name.cmp(&b.name));
+        match self.order {
+            ExportOrder::Alpha => {}
+            ExportOrder::Count => groups.sort_by_key(|g| std::cmp::Reverse(g.bookmarks.len())),
+            ExportOrder::Config => groups.sort_by_key(|g| {
+                self.group_order
+                    .iter()
+                    .position(|name| name == &g.name)
+                    .unwrap_or(usize::MAX)
+            }),
+        }
+
+        groups
+    }
+}
+
+/// Export bookmarks to markdown
+pub fn export_to_markdown(
+    bookmarks: &[Bookmark],
+    theme: &ExportTheme,
+    output_path: Option<&str>,
+) -> Result<String> {
+    let mut md = String::new();
+
+    md.push_str(&format!(
+        "# {}\n\n",
+        theme.title.as_deref().unwrap_or("Chrome Bookmarks Export")
+    ));
+    if let Some(header) = &theme.header {
+        md.push_str(&format!("{}\n\n", header));
+    }
+    md.push_str(&format!("*Exported on: {}*\n\n", chrono_lite_now()));
+    md.push_str(&format!("**Total bookmarks: {}**\n\n", bookmarks.len()));
+
+    for group in theme.group(bookmarks) {
+        md.push_str(&format!("## {}\n\n", group.label()));
+        if let Some(description) = &group.description {
+            md.push_str(&format!("*{}*\n\n", description));
         }
+        for bookmark in &group.bookmarks {
+            md.push_str(&format!("- [{}]({})\n", bookmark.name, bookmark.url));
+        }
+        md.push('\n');
     }
 
     if let Some(path) = output_path {
-        fs::write(path, &md).with_context(|| format!("Failed to write to {}", path))?;
+        fs::write(path, &md).map_err(BookmarksError::file("write", path))?;
         println!("{} Exported to: {}", "✅".green(), path.cyan());
     }
 
     Ok(md)
 }
 
-/// Export bookmarks to Chrome-compatible HTML format (Netscape Bookmark format)
-/// This creates an organized bookmark file that can be imported into Chrome
-pub fn export_to_chrome_html(bookmarks: &[Bookmark], output_path: Option<&str>) -> Result<String> {
-    use std::collections::BTreeMap;
-
-    // Group by category
-    let mut by_category: BTreeMap<String, Vec<&Bookmark>> = BTreeMap::new();
+/// Largest favicon embedded in an HTML export
+const FAVICON_MAX_BYTES: usize = 64 * 1024;
 
-    for bm in bookmarks {
-        let folder = bm.category.folder_name().to_string();
-        by_category.entry(folder).or_default().push(bm);
+/// Scheme, host and port of a URL, e.g. `https://example.com:8080`
+fn url_origin(url: &str) -> Option<String> {
+    let parsed = reqwest::Url::parse(url).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
     }
+    Some(parsed.origin().ascii_serialization())
+}
 
-    let mut html = String::new();
+/// Fetch `/favicon.ico` for every site in the bookmarks, as base64 data URIs keyed by origin
+///
+/// Sites without a usable favicon are left out, so their bookmarks are
+/// exported without an icon.
+pub fn fetch_favicons(bookmarks: &[Bookmark], client: &Client) -> HashMap<String, String> {
+    use base64::Engine;
 
-    // Netscape bookmark file header (required for Chrome import)
-    html.push_str("<!DOCTYPE NETSCAPE-Bookmark-file-1>\n");
-    html.push_str("<!-- This is an automatically generated file.\n");
-    html.push_str("     It will be read and overwritten.\n");
-    html.push_str("     DO NOT EDIT! -->\n");
-    html.push_str("<META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">\n");
-    html.push_str("<TITLE>Bookmarks</TITLE>\n");
-    html.push_str("<H1>Bookmarks</H1>\n");
-    html.push_str("<DL><p>\n");
+    let origins: HashSet<String> = bookmarks
+        .iter()
+        .filter_map(|b| url_origin(&b.url))
+        .collect();
 
-    // Bookmarks Bar folder (main import target)
-    html.push_str("    <DT><H3 ADD_DATE=\"1\" LAST_MODIFIED=\"1\" PERSONAL_TOOLBAR_FOLDER=\"true\">Bookmarks bar</H3>\n");
-    html.push_str("    <DL><p>\n");
+    println!(
+        "{} Fetching favicons for {} sites...",
+        "🖼️".cyan(),
+        origins.len().to_string().yellow()
+    );
 
-    // Sort categories for consistent output
-    let mut categories: Vec<_> = by_category.keys().collect();
-    categories.sort();
+    origins
+        .into_par_iter()
+        .filter_map(|origin| {
+            let response = client.get(format!("{}/favicon.ico", origin)).send().ok()?;
+            if !response.status().is_success() {
+                return None;
+            }
 
-    for category in categories {
-        let bms = &by_category[category];
+            let content_type = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.split(';').next().unwrap_or(v).trim().to_string())
+                .unwrap_or_else(|| "image/x-icon".to_string());
+            // Some sites answer with an HTML page instead of a 404
+            if !content_type.starts_with("image/") {
+                return None;
+            }
 
-        // Create a folder for each category
-        html.push_str(&format!(
-            "        <DT><H3 ADD_DATE=\"1\" LAST_MODIFIED=\"1\">{}</H3>\n",
-            html_escape(category)
+            let bytes = response.bytes().ok()?;
+            if bytes.is_empty() || bytes.len() > FAVICON_MAX_BYTES {
+                return None;
+            }
+
+            let data_uri = format!(
+                "data:{};base64,{}",
+                content_type,
+                base64::engine::general_purpose::STANDARD.encode(&bytes)
+            );
+            Some((origin, data_uri))
+        })
+        .collect()
+}
+
+/// A folder in the original bookmark tree, rebuilt for the HTML export
+#[derive(Default)]
+struct HtmlFolder<'a> {
+    name: String,
+    folders: Vec<HtmlFolder<'a>>,
+    bookmarks: Vec<&'a Bookmark>,
+}
+
+impl<'a> HtmlFolder<'a> {
+    /// Add a bookmark under `path`, creating intermediate folders as needed
+    fn insert(&mut self, path: &[&str], bookmark: &'a Bookmark) {
+        match path.split_first() {
+            None => self.bookmarks.push(bookmark),
+            Some((name, rest)) => {
+                let index = match self.folders.iter().position(|f| f.name == *name) {
+                    Some(index) => index,
+                    None => {
+                        self.folders.push(HtmlFolder {
+                            name: name.to_string(),
+                            ..Default::default()
+                        });
+                        self.folders.len() - 1
+                    }
+                };
+                self.folders[index].insert(rest, bookmark);
+            }
+        }
+    }
+}
+
+/// Rebuild the original folder tree for `bookmarks`
+///
+/// Bookmarks bar contents land directly in the exported Bookmarks bar; other
+/// roots keep their root folder ("Other bookmarks", "Mobile bookmarks").
+fn original_folder_tree<'a>(bookmarks: &[&'a Bookmark]) -> HtmlFolder<'a> {
+    let mut root = HtmlFolder::default();
+
+    for bookmark in bookmarks {
+        let skip = if bookmark.root() == "bookmark_bar" {
+            2
+        } else {
+            1
+        };
+        let path: Vec<&str> = bookmark
+            .folder_path
+            .split('/')
+            .skip(skip)
+            .filter(|part| !part.is_empty())
+            .collect();
+        root.insert(&path, bookmark);
+    }
+
+    root
+}
+
+/// Append one `<DT><A>` bookmark line
+fn push_html_bookmark(
+    html: &mut String,
+    bookmark: &Bookmark,
+    favicons: &HashMap<String, String>,
+    indent: &str,
+) {
+    let icon = url_origin(&bookmark.url)
+        .and_then(|origin| favicons.get(&origin))
+        .map(|data_uri| format!(" ICON=\"{}\"", data_uri))
+        .unwrap_or_default();
+
+    html.push_str(&format!(
+        "{}<DT><A HREF=\"{}\" ADD_DATE=\"1\"{}>{}</A>\n",
+        indent,
+        html_escape(&bookmark.url),
+        icon,
+        html_escape(&bookmark.name)
+    ));
+}
+
+/// Append the contents of an original folder, subfolders first
+fn push_html_folder_contents(
+    html: &mut String,
+    folder: &HtmlFolder,
+    favicons: &HashMap<String, String>,
+    indent: &str,
+) {
+    for subfolder in &folder.folders {
+        html.push_str(&format!(
+            "{}<DT><H3 ADD_DATE=\"1\" LAST_MODIFIED=\"1\">{}</H3>\n",
+            indent,
+            html_escape(&subfolder.name)
         ));
-        html.push_str("        <DL><p>\n");
+        html.push_str(&format!("{}<DL><p>\n", indent));
+        push_html_folder_contents(html, subfolder, favicons, &format!("{}    ", indent));
+        html.push_str(&format!("{}</DL><p>\n", indent));
+    }
 
-        // Sort bookmarks within category by name
-        let mut sorted_bms = bms.clone();
-        sorted_bms.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    for bookmark in &folder.bookmarks {
+        push_html_bookmark(html, bookmark, favicons, indent);
+    }
+}
 
-        for bm in sorted_bms {
-            let escaped_name = html_escape(&bm.name);
-            let escaped_url = html_escape(&bm.url);
+/// Export bookmarks to Chrome-compatible HTML format (Netscape Bookmark format)
+/// This creates an organized bookmark file that can be imported into Chrome
+///
+/// `favicons` maps site origins to data URIs (see `fetch_favicons`); matching
+/// bookmarks get an `ICON` attribute so they keep their icons on import.
+///
+/// With `preserve_unmatched`, bookmarks the categorizer files under "Other"
+/// stay in their original folders instead of being flattened into one folder.
+pub fn export_to_chrome_html(
+    bookmarks: &[Bookmark],
+    theme: &ExportTheme,
+    favicons: &HashMap<String, String>,
+    preserve_unmatched: bool,
+    output_path: Option<&str>,
+) -> Result<String> {
+    let (unmatched, matched): (Vec<&Bookmark>, Vec<&Bookmark>) = bookmarks
+        .iter()
+        .partition(|b| preserve_unmatched && b.category == BookmarkCategory::Other);
+    let groups = theme.group(matched);
+    let title = theme.title.as_deref().unwrap_or("Bookmarks");
 
-            html.push_str(&format!(
-                "            <DT><A HREF=\"{}\" ADD_DATE=\"1\">{}</A>\n",
-                escaped_url, escaped_name
-            ));
+    let mut html = String::new();
+
+    // Netscape bookmark file header (required for Chrome import)
+    html.push_str("<!DOCTYPE NETSCAPE-Bookmark-file-1>\n");
+    html.push_str("<!-- This is an automatically generated file.\n");
+    html.push_str("     It will be read and overwritten.\n");
+    html.push_str("     DO NOT EDIT! -->\n");
+    html.push_str("<META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">\n");
+    html.push_str(&format!("<TITLE>{}</TITLE>\n", html_escape(title)));
+    html.push_str(&format!("<H1>{}</H1>\n", html_escape(title)));
+    if let Some(header) = &theme.header {
+        html.push_str(&format!("<DD>{}\n", html_escape(header)));
+    }
+    html.push_str("<DL><p>\n");
+
+    // Bookmarks Bar folder (main import target)
+    html.push_str("    <DT><H3 ADD_DATE=\"1\" LAST_MODIFIED=\"1\" PERSONAL_TOOLBAR_FOLDER=\"true\">Bookmarks bar</H3>\n");
+    html.push_str("    <DL><p>\n");
+
+    for group in &groups {
+        // Create a folder for each group
+        html.push_str(&format!(
+            "        <DT><H3 ADD_DATE=\"1\" LAST_MODIFIED=\"1\">{}</H3>\n",
+            html_escape(&group.label())
+        ));
+        if let Some(description) = &group.description {
+            html.push_str(&format!("        <DD>{}\n", html_escape(description)));
+        }
+        html.push_str("        <DL><p>\n");
+
+        for bm in &group.bookmarks {
+            push_html_bookmark(&mut html, bm, favicons, "            ");
         }
 
         html.push_str("        </DL><p>\n");
     }
 
+    if !unmatched.is_empty() {
+        push_html_folder_contents(
+            &mut html,
+            &original_folder_tree(&unmatched),
+            favicons,
+            "        ",
+        );
+    }
+
     // Close Bookmarks bar folder
     html.push_str("    </DL><p>\n");
 
@@ -1854,15 +2630,21 @@ pub fn export_to_chrome_html(bookmarks: &[Bookmark], output_path: Option<&str>)
     html.push_str("</DL><p>\n");
 
     // Statistics comment
-    let total_bookmarks: usize = by_category.values().map(|v| v.len()).sum();
-    let total_categories = by_category.len();
+    let total_bookmarks: usize = groups.iter().map(|g| g.bookmarks.len()).sum();
+    let total_categories = groups.len();
     html.push_str(&format!(
         "<!-- Organized {} bookmarks into {} categories -->\n",
         total_bookmarks, total_categories
     ));
+    if !unmatched.is_empty() {
+        html.push_str(&format!(
+            "<!-- Kept {} uncategorized bookmarks in their original folders -->\n",
+            unmatched.len()
+        ));
+    }
 
     if let Some(path) = output_path {
-        fs::write(path, &html).with_context(|| format!("Failed to write to {}", path))?;
+        fs::write(path, &html).map_err(BookmarksError::file("write", path))?;
         println!(
             "\n{} Exported organized bookmarks to: {}",
             "✅".green(),
@@ -1885,13 +2667,259 @@ pub fn export_to_chrome_html(bookmarks: &[Bookmark], output_path: Option<&str>)
             "💡".yellow(),
             total_categories.to_string().green()
         );
+        if !unmatched.is_empty() {
+            println!(
+                "{} {} uncategorized bookmarks keep their original folders",
+                "💡".yellow(),
+                unmatched.len().to_string().green()
+            );
+        }
     }
 
     Ok(html)
 }
 
+/// One per-group page of a shared export
+#[derive(Tabled, Clone)]
+pub struct ShareBundleEntry {
+    #[tabled(rename = "Group")]
+    pub group: String,
+    #[tabled(rename = "File")]
+    pub file: String,
+    #[tabled(rename = "Bookmarks")]
+    pub count: usize,
+    #[tabled(rename = "Link")]
+    pub link: String,
+}
+
+/// File-name-safe slug for a group name ("AI-ML/LLMs & Models" -> "ai-ml-llms-models")
+fn share_slug(name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        "bookmarks".to_string()
+    } else {
+        slug
+    }
+}
+
+/// `<head>` for a shared page, with Open Graph tags so links unfurl in chat apps
+fn share_head(title: &str, description: &str, url: Option<&str>) -> String {
+    let mut head = String::new();
+    head.push_str("<head>\n<meta charset=\"utf-8\">\n");
+    head.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    head.push_str(&format!("<title>{}</title>\n", html_escape(title)));
+    head.push_str(&format!(
+        "<meta name=\"description\" content=\"{}\">\n",
+        html_escape(description)
+    ));
+    head.push_str(&format!(
+        "<meta property=\"og:title\" content=\"{}\">\n",
+        html_escape(title)
+    ));
+    head.push_str(&format!(
+        "<meta property=\"og:description\" content=\"{}\">\n",
+        html_escape(description)
+    ));
+    head.push_str("<meta property=\"og:type\" content=\"website\">\n");
+    if let Some(url) = url {
+        head.push_str(&format!(
+            "<meta property=\"og:url\" content=\"{}\">\n",
+            html_escape(url)
+        ));
+        head.push_str(&format!(
+            "<link rel=\"canonical\" href=\"{}\">\n",
+            html_escape(url)
+        ));
+    }
+    head.push_str("<meta name=\"twitter:card\" content=\"summary\">\n");
+    head.push_str(
+        "<style>body{font-family:system-ui,sans-serif;max-width:50rem;margin:2rem auto;padding:0 1rem;line-height:1.5}\
+         li{margin:.4rem 0}.domain,.meta{color:#666;font-size:.9em}a{text-decoration:none}a:hover{text-decoration:underline}</style>\n",
+    );
+    head.push_str("</head>\n");
+    head
+}
+
+/// Export bookmarks as a static site for sharing: one page per export group
+/// plus an `index.html` linking them and a `share.json` with each page's
+/// title, description, link and bookmark count
+///
+/// Groups follow the `[bookmarks.export]` theme. With `base_url` (where the
+/// directory will be hosted) the pages get absolute `og:url`/canonical links.
+/// Bookmarklets are left out, and pages listed in a previous `share.json`
+/// that this export no longer produces are removed.
+pub fn export_share_bundles(
+    bookmarks: &[Bookmark],
+    theme: &ExportTheme,
+    base_url: Option<&str>,
+    output_dir: &str,
+) -> Result<Vec<ShareBundleEntry>> {
+    fs::create_dir_all(output_dir).map_err(BookmarksError::file("create", output_dir))?;
+
+    let manifest_path = Path::new(output_dir).join("share.json");
+    let previous: Vec<String> = fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|manifest| {
+            manifest["bundles"].as_array().map(|bundles| {
+                bundles
+                    .iter()
+                    .filter_map(|b| b["file"].as_str().map(|f| f.to_string()))
+                    .collect()
+            })
+        })
+        .unwrap_or_default();
+
+    let groups = theme.group(bookmarks.iter().filter(|b| !is_internal_url(&b.url)));
+    let title = theme.title.as_deref().unwrap_or("Bookmarks");
+    let base_url = base_url.map(|url| url.trim_end_matches('/'));
+    let link = |file: &str| match base_url {
+        Some(base) => format!("{}/{}", base, file),
+        None => file.to_string(),
+    };
+
+    let mut used = HashSet::new();
+    let mut entries = Vec::new();
+    let mut manifest = Vec::new();
+
+    for group in &groups {
+        let slug = share_slug(&group.name);
+        let mut file = format!("{}.html", slug);
+        let mut n = 2;
+        while !used.insert(file.clone()) {
+            file = format!("{}-{}.html", slug, n);
+            n += 1;
+        }
+
+        let page_title = format!("{} · {}", group.name, title);
+        let description = group
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("{} links shared from {}", group.bookmarks.len(), title));
+
+        let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n");
+        html.push_str(&share_head(
+            &page_title,
+            &description,
+            base_url.map(|_| link(&file)).as_deref(),
+        ));
+        html.push_str("<body>\n");
+        html.push_str(&format!(
+            "<p class=\"meta\"><a href=\"index.html\">← {}</a></p>\n",
+            html_escape(title)
+        ));
+        html.push_str(&format!("<h1>{}</h1>\n", html_escape(&group.label())));
+        if let Some(description) = &group.description {
+            html.push_str(&format!("<p>{}</p>\n", html_escape(description)));
+        }
+        html.push_str("<ul>\n");
+        for bm in &group.bookmarks {
+            html.push_str(&format!(
+                "<li><a href=\"{}\">{}</a> <span class=\"domain\">{}</span></li>\n",
+                html_escape(&bm.url),
+                html_escape(&bm.name),
+                html_escape(&extract_domain(&bm.url))
+            ));
+        }
+        html.push_str("</ul>\n");
+        html.push_str(&format!(
+            "<p class=\"meta\">{} links · updated {}</p>\n",
+            group.bookmarks.len(),
+            chrono_lite_now()
+        ));
+        html.push_str("</body>\n</html>\n");
+
+        let path = Path::new(output_dir).join(&file);
+        fs::write(&path, html).map_err(BookmarksError::file("write", &path))?;
+
+        manifest.push(serde_json::json!({
+            "name": group.name,
+            "title": page_title,
+            "description": description,
+            "file": file,
+            "url": link(&file),
+            "count": group.bookmarks.len(),
+        }));
+        entries.push(ShareBundleEntry {
+            group: group.label(),
+            file: file.clone(),
+            count: group.bookmarks.len(),
+            link: link(&file),
+        });
+    }
+
+    let total: usize = groups.iter().map(|g| g.bookmarks.len()).sum();
+    let description = theme
+        .header
+        .clone()
+        .unwrap_or_else(|| format!("{} links in {} collections", total, groups.len()));
+
+    let mut index = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n");
+    index.push_str(&share_head(
+        title,
+        &description,
+        base_url.map(|_| link("index.html")).as_deref(),
+    ));
+    index.push_str("<body>\n");
+    index.push_str(&format!("<h1>{}</h1>\n", html_escape(title)));
+    index.push_str(&format!("<p>{}</p>\n", html_escape(&description)));
+    index.push_str("<ul>\n");
+    for (group, entry) in groups.iter().zip(&entries) {
+        index.push_str(&format!(
+            "<li><a href=\"{}\">{}</a> <span class=\"meta\">{} links</span>",
+            html_escape(&entry.file),
+            html_escape(&entry.group),
+            entry.count
+        ));
+        if let Some(description) = &group.description {
+            index.push_str(&format!(
+                "<br><span class=\"meta\">{}</span>",
+                html_escape(description)
+            ));
+        }
+        index.push_str("</li>\n");
+    }
+    index.push_str("</ul>\n");
+    index.push_str(&format!(
+        "<p class=\"meta\">Generated on {}</p>\n",
+        chrono_lite_now()
+    ));
+    index.push_str("</body>\n</html>\n");
+
+    let index_path = Path::new(output_dir).join("index.html");
+    fs::write(&index_path, index).map_err(BookmarksError::file("write", &index_path))?;
+
+    let manifest = serde_json::json!({
+        "title": title,
+        "description": description,
+        "url": link("index.html"),
+        "generated": chrono_lite_now(),
+        "bundles": manifest,
+    });
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)
+        .map_err(BookmarksError::file("write", &manifest_path))?;
+
+    for file in previous.iter().filter(|f| !used.contains(*f)) {
+        // Only plain file names we wrote ourselves; never follow paths out of the directory
+        if Path::new(file)
+            .file_name()
+            .is_some_and(|name| name == file.as_str())
+        {
+            let _ = fs::remove_file(Path::new(output_dir).join(file));
+        }
+    }
+
+    Ok(entries)
+}
+
 /// Escape HTML special characters
-fn html_escape(s: &str) -> String {
+pub(crate) fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -1900,7 +2928,7 @@ fn html_escape(s: &str) -> String {
 }
 
 /// Simple date/time function (avoiding chrono dependency)
-fn chrono_lite_now() -> String {
+pub(crate) fn chrono_lite_now() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
     let duration = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1918,7 +2946,7 @@ fn chrono_lite_now() -> String {
 }
 
 /// Truncate a string to a maximum length (handles UTF-8 properly)
-fn truncate_string(s: &str, max_len: usize) -> String {
+pub(crate) fn truncate_string(s: &str, max_len: usize) -> String {
     if s.chars().count() <= max_len {
         s.to_string()
     } else {
@@ -1929,7 +2957,7 @@ fn truncate_string(s: &str, max_len: usize) -> String {
 
 /// Interactive bookmark selector for organization
 pub fn interactive_organize(bookmarks: &[Bookmark]) -> Result<Vec<OrganizeSuggestion>> {
-    let suggestions = get_organize_suggestions(bookmarks);
+    let (suggestions, _) = get_organize_suggestions(bookmarks, DEFAULT_REVIEW_THRESHOLD);
 
     if suggestions.is_empty() {
         println!("{}", "All bookmarks are already well-organized!".green());
@@ -1957,24 +2985,18 @@ pub struct DeadLinkEntry {
     pub status: String,
     #[tabled(rename = "Folder")]
     pub folder: String,
+    /// Chrome node ID of the bookmark, used to remove exactly this entry
+    #[tabled(skip)]
+    pub id: String,
 }
 
 /// Check if a URL is dead (returns status code or error)
-pub fn check_url_status(url: &str) -> (bool, String) {
+pub fn check_url_status(client: &Client, url: &str) -> (bool, String) {
     // Skip non-http URLs
     if !url.starts_with("http://") && !url.starts_with("https://") {
         return (true, "skipped".to_string());
     }
 
-    let client = match reqwest::blocking::Client::builder()
-        .timeout(Duration::from_secs(10))
-        .redirect(reqwest::redirect::Policy::limited(5))
-        .build()
-    {
-        Ok(c) => c,
-        Err(e) => return (false, format!("client error: {}", e)),
-    };
-
     match client.head(url).send() {
         Ok(response) => {
             let status = response.status();
@@ -2008,177 +3030,1598 @@ pub fn check_url_status(url: &str) -> (bool, String) {
     }
 }
 
-/// Check for dead links in bookmarks (with parallel processing)
-pub fn find_dead_links(bookmarks: &[Bookmark], verbose: bool) -> Vec<DeadLinkEntry> {
-    let total = bookmarks.len();
-    let checked = Arc::new(AtomicUsize::new(0));
-    let dead_count = Arc::new(AtomicUsize::new(0));
-
-    println!(
-        "{} Checking {} bookmarks for dead links (this may take a while)...\n",
-        "🔍".cyan(),
-        total.to_string().yellow()
-    );
+/// Domain parking and domain marketplace services parked domains redirect to
+const PARKING_HOSTS: &[&str] = &[
+    "above.com",
+    "afternic.com",
+    "bodis.com",
+    "buydomains.com",
+    "dan.com",
+    "domainmarket.com",
+    "hugedomains.com",
+    "parkingcrew.net",
+    "parklogic.com",
+    "sedo.com",
+    "sedoparking.com",
+    "undeveloped.com",
+    "uniregistry.com",
+];
+
+/// Phrases found on parking and domain-for-sale pages
+const PARKED_BODY_PATTERNS: &[&str] = &[
+    "this domain is for sale",
+    "this domain may be for sale",
+    "the domain name is for sale",
+    "buy this domain",
+    "make an offer on this domain",
+    "inquire about this domain",
+    "this domain has expired",
+    "domain parking",
+    "parkingcrew",
+    "sedoparking",
+];
+
+/// How much of a page body to scan for parking patterns
+const PARKED_BODY_LIMIT: u64 = 64 * 1024;
+
+/// Check whether a live URL is a parking / domain-for-sale page, returning why
+///
+/// Fetches the page with GET: a final host belonging to a parking service or
+/// a for-sale phrase in the first part of an HTML body marks it as parked.
+pub fn detect_parked(client: &Client, url: &str) -> Option<String> {
+    use std::io::Read;
+
+    let response = client.get(url).send().ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
 
-    let dead_links: Vec<DeadLinkEntry> = bookmarks
-        .par_iter()
-        .filter_map(|bookmark| {
-            let current = checked.fetch_add(1, Ordering::SeqCst) + 1;
-
-            // Progress indicator every 50 bookmarks
-            if current % 50 == 0 || current == total {
-                print!(
-                    "\r{} Progress: {}/{} checked, {} dead found",
-                    "⏳".cyan(),
-                    current.to_string().yellow(),
-                    total.to_string().yellow(),
-                    dead_count.load(Ordering::SeqCst).to_string().red()
-                );
-                std::io::Write::flush(&mut std::io::stdout()).ok();
-            }
+    let final_host = response.url().host_str().unwrap_or("").to_lowercase();
+    if let Some(provider) = PARKING_HOSTS
+        .iter()
+        .find(|p| final_host == **p || final_host.ends_with(&format!(".{}", p)))
+    {
+        return Some(provider.to_string());
+    }
 
-            let (is_alive, status) = check_url_status(&bookmark.url);
+    let is_html = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("html"));
+    if !is_html {
+        return None;
+    }
 
-            if verbose && !is_alive {
-                println!(
-                    "\n  {} {} - {}",
-                    "❌".red(),
-                    truncate_string(&bookmark.name, 40),
-                    status.red()
-                );
-            }
+    let mut body = Vec::new();
+    response
+        .take(PARKED_BODY_LIMIT)
+        .read_to_end(&mut body)
+        .ok()?;
+    let body = String::from_utf8_lossy(&body).to_lowercase();
 
-            if !is_alive && status != "skipped" {
-                dead_count.fetch_add(1, Ordering::SeqCst);
-                Some(DeadLinkEntry {
-                    title: truncate_string(&bookmark.name, 40),
-                    url: truncate_string(&bookmark.url, 50),
-                    status,
-                    folder: truncate_string(&bookmark.folder_path, 25),
-                })
-            } else {
-                None
-            }
-        })
-        .collect();
+    PARKED_BODY_PATTERNS
+        .iter()
+        .any(|p| body.contains(p))
+        .then(|| "for sale".to_string())
+}
 
-    println!("\n"); // Clear the progress line
-    dead_links
+/// Whether a dead-link status marks a parked page rather than a dead one
+pub fn is_parked_status(status: &str) -> bool {
+    status.starts_with("parked")
 }
 
-/// Duplicate bookmark info for removal
-#[derive(Clone, Debug)]
-pub struct DuplicateGroup {
-    pub url: String,
-    pub bookmarks: Vec<BookmarkInfo>,
+/// Which HTTP status codes count as alive or dead in dead-link checks
+///
+/// Sites behind bot protection often answer HEAD requests with 403 or 429
+/// while the page itself is fine; listing those codes as alive keeps them out
+/// of dead-link reports. Dead codes do the opposite, e.g. to flag permanent
+/// redirects. Entries are single codes (`403`) or classes (`5xx`); when both
+/// lists match, the more specific entry wins, and a tie counts as dead.
+/// Configured under `[bookmarks.deadlinks]` and overridden by `--alive-status`
+/// and `--dead-status`:
+///
+/// ```toml
+/// [bookmarks.deadlinks]
+/// alive_status = [403, 405, 429]
+/// dead_status = ["301"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatusPolicy {
+    alive: Vec<StatusPattern>,
+    dead: Vec<StatusPattern>,
 }
 
-#[derive(Clone, Debug)]
-pub struct BookmarkInfo {
-    pub id: String,
-    pub name: String,
-    pub folder_path: String,
+/// A status code or a class of codes, as an inclusive range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StatusPattern {
+    low: u16,
+    high: u16,
 }
 
-/// Find duplicate bookmark groups with full info
-pub fn find_duplicate_groups(bookmarks: &[Bookmark]) -> Vec<DuplicateGroup> {
-    let mut url_map: HashMap<String, Vec<BookmarkInfo>> = HashMap::new();
+impl StatusPattern {
+    fn parse(pattern: &str) -> Result<Self> {
+        let pattern = pattern.trim().to_lowercase();
+        let invalid = || {
+            BookmarksError::InvalidConfig(format!(
+                "Invalid status code '{}' (use a code like 403 or a class like 5xx)",
+                pattern
+            ))
+        };
 
-    for bookmark in bookmarks {
-        url_map
-            .entry(bookmark.url.clone())
-            .or_default()
-            .push(BookmarkInfo {
-                id: bookmark.id.clone(),
-                name: bookmark.name.clone(),
-                folder_path: bookmark.folder_path.clone(),
-            });
+        let (low, high) = match pattern.strip_suffix("xx") {
+            Some(class) => {
+                let class: u16 = class.parse().map_err(|_| invalid())?;
+                (class * 100, class * 100 + 99)
+            }
+            None => {
+                let code = pattern.parse().map_err(|_| invalid())?;
+                (code, code)
+            }
+        };
+
+        if !(100..=599).contains(&low) {
+            return Err(invalid());
+        }
+        Ok(Self { low, high })
     }
 
-    url_map
-        .into_iter()
-        .filter(|(_, bms)| bms.len() > 1)
-        .map(|(url, bookmarks)| DuplicateGroup { url, bookmarks })
-        .collect()
+    fn matches(&self, code: u16) -> bool {
+        (self.low..=self.high).contains(&code)
+    }
 }
 
-/// Remove duplicates from the bookmarks file (keeps the first occurrence)
-pub fn remove_duplicates(dry_run: bool, interactive: bool) -> Result<usize> {
-    use crossterm::{
-        cursor,
-        event::{self, Event, KeyCode},
-        execute,
-        terminal::{self, ClearType},
-    };
-    use std::io::{Write, stdout};
+impl StatusPolicy {
+    /// Build a policy from code patterns, e.g. `["403", "5xx"]`
+    pub fn new(alive: &[String], dead: &[String]) -> Result<Self> {
+        let parse = |patterns: &[String]| {
+            patterns
+                .iter()
+                .filter(|p| !p.trim().is_empty())
+                .map(|p| StatusPattern::parse(p))
+                .collect::<Result<Vec<_>>>()
+        };
 
-    let path = get_chrome_bookmarks_path()?;
-    let content = fs::read_to_string(&path)?;
-    let mut json: serde_json::Value = serde_json::from_str(&content)?;
+        Ok(Self {
+            alive: parse(alive)?,
+            dead: parse(dead)?,
+        })
+    }
 
-    // Parse bookmarks to find duplicates
-    let (bookmarks, _) = parse_bookmarks()?;
-    let duplicate_groups = find_duplicate_groups(&bookmarks);
+    /// Read `alive_status` / `dead_status` from `[bookmarks.deadlinks]`
+    ///
+    /// Both integers and strings are accepted, so `[403, "5xx"]` works.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let patterns = |key: &str| -> Vec<String> {
+            config
+                .get(key)
+                .and_then(|v| v.as_array())
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| match item {
+                            toml::Value::Integer(code) => Some(code.to_string()),
+                            toml::Value::String(pattern) => Some(pattern.clone()),
+                            _ => None,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
 
-    if duplicate_groups.is_empty() {
-        println!("{}", "No duplicate bookmarks found!".green());
-        return Ok(0);
+        Self::new(
+            &patterns("bookmarks.deadlinks.alive_status"),
+            &patterns("bookmarks.deadlinks.dead_status"),
+        )
     }
 
-    println!(
-        "\n{} Found {} duplicate URL groups ({} total duplicate entries)\n",
-        "🔍".cyan(),
-        duplicate_groups.len().to_string().yellow(),
-        duplicate_groups
-            .iter()
-            .map(|g| g.bookmarks.len() - 1)
-            .sum::<usize>()
-            .to_string()
-            .yellow()
-    );
+    /// Replace the alive codes (`--alive-status`)
+    pub fn with_alive(mut self, alive: &[String]) -> Result<Self> {
+        self.alive = Self::new(alive, &[])?.alive;
+        Ok(self)
+    }
 
-    // Collect IDs to remove (keep first occurrence, remove rest)
-    let mut ids_to_remove: HashSet<String> = HashSet::new();
-    let mut removal_details: Vec<(String, String, String)> = Vec::new(); // (url, name, folder)
+    /// Replace the dead codes (`--dead-status`)
+    pub fn with_dead(mut self, dead: &[String]) -> Result<Self> {
+        self.dead = Self::new(&[], dead)?.dead;
+        Ok(self)
+    }
 
-    for group in &duplicate_groups {
-        // Skip the first one (keep it), remove the rest
-        for bookmark in group.bookmarks.iter().skip(1) {
-            ids_to_remove.insert(bookmark.id.clone());
-            removal_details.push((
-                truncate_string(&group.url, 50),
-                truncate_string(&bookmark.name, 30),
-                truncate_string(&bookmark.folder_path, 25),
-            ));
+    /// Decide whether a checked link is alive, given the default verdict and
+    /// its status text (e.g. "403 Forbidden")
+    ///
+    /// Statuses without a leading code (timeouts, DNS errors, parked pages)
+    /// keep the default verdict.
+    pub fn classify(&self, is_alive: bool, status: &str) -> bool {
+        let Some(code) = status
+            .split_whitespace()
+            .next()
+            .and_then(|c| c.parse::<u16>().ok())
+        else {
+            return is_alive;
+        };
+
+        let narrowest = |patterns: &[StatusPattern]| {
+            patterns
+                .iter()
+                .filter(|p| p.matches(code))
+                .map(|p| p.high - p.low)
+                .min()
+        };
+
+        match (narrowest(&self.alive), narrowest(&self.dead)) {
+            (Some(alive), Some(dead)) => alive < dead,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => is_alive,
         }
     }
+}
 
-    if interactive {
-        println!("{}", "Duplicates to be removed:".bold().cyan());
-        println!("{}", "─".repeat(80).dimmed());
+/// Per-host throttle for dead-link checks
+///
+/// Requests to the same host are spaced at least `interval` apart, while
+/// different hosts are still checked in parallel. Configured under
+/// `[bookmarks.deadlinks]` with `host_interval_ms` and `max_retries`;
+/// `detect_parked = false` turns off the extra GET used to spot parked domains.
+/// Results are classified with a `StatusPolicy`.
+pub struct HostRateLimiter {
+    interval: Duration,
+    max_retries: u32,
+    detect_parked: bool,
+    policy: StatusPolicy,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
 
-        for (i, (url, name, folder)) in removal_details.iter().enumerate().take(20) {
-            println!(
-                "  {}. {} - {} ({})",
-                (i + 1).to_string().yellow(),
-                name.cyan(),
-                url.dimmed(),
-                folder.magenta()
-            );
+impl HostRateLimiter {
+    pub fn new(interval: Duration, max_retries: u32) -> Self {
+        Self {
+            interval,
+            max_retries,
+            detect_parked: true,
+            policy: StatusPolicy::default(),
+            next_slot: Mutex::new(HashMap::new()),
         }
+    }
 
-        if removal_details.len() > 20 {
-            println!(
-                "  ... and {} more",
-                (removal_details.len() - 20).to_string().yellow()
+    /// Classify results with `policy` instead of the default verdicts
+    pub fn with_status_policy(mut self, policy: StatusPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Apply the status policy to a result
+    pub fn classify(&self, is_alive: bool, status: &str) -> bool {
+        self.policy.classify(is_alive, status)
+    }
+
+    /// Enable or disable parked-domain detection for live links
+    pub fn with_parked_detection(mut self, detect_parked: bool) -> Self {
+        self.detect_parked = detect_parked;
+        self
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        let setting = |key: &str, default: i64| {
+            config
+                .get(key)
+                .and_then(|v| v.as_integer())
+                .unwrap_or(default)
+                .max(0) as u64
+        };
+
+        Self::new(
+            Duration::from_millis(setting("bookmarks.deadlinks.host_interval_ms", 500)),
+            setting("bookmarks.deadlinks.max_retries", 3) as u32,
+        )
+        .with_parked_detection(
+            config
+                .get_bool("bookmarks.deadlinks.detect_parked")
+                .unwrap_or(true),
+        )
+    }
+
+    /// Block until `host` may be requested again, reserving the following slot
+    pub fn wait(&self, host: &str) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let slot = next_slot
+                .get(host)
+                .copied()
+                .filter(|t| *t > now)
+                .unwrap_or(now);
+            next_slot.insert(host.to_string(), slot + self.interval);
+            slot
+        };
+
+        let now = Instant::now();
+        if slot > now {
+            std::thread::sleep(slot - now);
+        }
+    }
+
+    /// Push back every future request to `host` after it answered 429
+    pub fn back_off(&self, host: &str, delay: Duration) {
+        let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+        let resume = Instant::now() + delay;
+        let slot = next_slot.entry(host.to_string()).or_insert(resume);
+        if *slot < resume {
+            *slot = resume;
+        }
+    }
+
+    /// Check a URL, waiting for its host's slot and backing off on 429 responses
+    ///
+    /// A host that keeps answering 429 is reported as alive ("rate limited"):
+    /// the server is up, it just refused to answer right now. Live pages that
+    /// turn out to be parking pages are reported as dead with a "parked" status.
+    /// Status codes the policy lists are classified as it says, without retries.
+    pub fn check(&self, client: &Client, url: &str) -> (bool, String) {
+        let host = extract_domain(url);
+
+        for attempt in 0..=self.max_retries {
+            self.wait(&host);
+            let (is_alive, status) = check_url_status(client, url);
+            let is_alive = self.classify(is_alive, &status);
+
+            if is_alive && self.detect_parked && status.starts_with('2') {
+                self.wait(&host);
+                if let Some(reason) = detect_parked(client, url) {
+                    return (false, format!("parked ({})", reason));
+                }
+            }
+
+            if !status.starts_with("429") || is_alive {
+                return (is_alive, status);
+            }
+            if attempt < self.max_retries {
+                let delay = self.interval.max(Duration::from_secs(1)) * 2u32.pow(attempt);
+                self.back_off(&host, delay);
+            }
+        }
+
+        let status = "429 rate limited".to_string();
+        (self.classify(true, &status), status)
+    }
+}
+
+/// Order bookmark indices round-robin by host, so parallel workers spread
+/// across hosts instead of all queueing on one dense domain
+fn interleave_by_host(bookmarks: &[Bookmark]) -> Vec<usize> {
+    let mut by_host: Vec<(String, Vec<usize>)> = Vec::new();
+    let mut host_index: HashMap<String, usize> = HashMap::new();
+
+    for (i, bookmark) in bookmarks.iter().enumerate() {
+        let host = extract_domain(&bookmark.url);
+        let slot = *host_index.entry(host.clone()).or_insert_with(|| {
+            by_host.push((host, Vec::new()));
+            by_host.len() - 1
+        });
+        by_host[slot].1.push(i);
+    }
+
+    let longest = by_host.iter().map(|(_, v)| v.len()).max().unwrap_or(0);
+    (0..longest)
+        .flat_map(|round| {
+            by_host
+                .iter()
+                .filter_map(move |(_, v)| v.get(round).copied())
+        })
+        .collect()
+}
+
+/// Check for dead links in bookmarks (with parallel processing)
+pub fn find_dead_links(
+    bookmarks: &[Bookmark],
+    client: &Client,
+    policy: &StatusPolicy,
+    verbose: bool,
+) -> Result<Vec<DeadLinkEntry>> {
+    Ok(dead_link_entries(&find_dead_bookmarks(
+        bookmarks, client, policy, verbose,
+    )?))
+}
+
+/// Convert dead bookmarks into table entries
+pub fn dead_link_entries(dead: &[(&Bookmark, String)]) -> Vec<DeadLinkEntry> {
+    dead.iter()
+        .map(|(bookmark, status)| DeadLinkEntry {
+            title: truncate_string(&bookmark.name, 40),
+            url: truncate_string(&bookmark.url, 50),
+            status: status.clone(),
+            folder: truncate_string(&bookmark.folder_path, 25),
+            id: bookmark.id.clone(),
+        })
+        .collect()
+}
+
+/// Scan state file for resumable dead-link scans, relative to the data directory
+const DEADLINK_SCAN_STATE: &str = "deadlinks-scan.jsonl";
+
+/// On-disk progress of a dead-link scan
+///
+/// Every checked bookmark is appended as a JSON line as soon as its result is
+/// known, so a scan that dies halfway can continue with `--resume`. The file
+/// is removed once a scan completes.
+pub struct ScanState {
+    path: PathBuf,
+    file: Mutex<File>,
+    results: HashMap<(String, String), (bool, String)>,
+}
+
+impl ScanState {
+    /// Location of the scan state file
+    pub fn path() -> Result<PathBuf> {
+        Ok(data_dir()?.join(DEADLINK_SCAN_STATE))
+    }
+
+    /// Open the scan state, keeping earlier results when resuming and
+    /// discarding them otherwise
+    pub fn open(resume: bool) -> Result<Self> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(BookmarksError::file("create directory", &parent))?;
+        }
+
+        let mut results = HashMap::new();
+        if let Ok(content) = fs::read_to_string(&path) {
+            // An interrupted scan can leave a torn last line, so bad lines are skipped
+            for record in content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            {
+                let field = |key: &str| record.get(key).and_then(|v| v.as_str()).unwrap_or("");
+                results.insert(
+                    (field("id").to_string(), field("url").to_string()),
+                    (
+                        record
+                            .get("alive")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(true),
+                        field("status").to_string(),
+                    ),
+                );
+            }
+        }
+
+        if !resume && !results.is_empty() {
+            println!(
+                "{} Discarding an unfinished scan ({} links checked); use --resume to continue it instead",
+                "💡".yellow(),
+                results.len().to_string().yellow()
+            );
+            results.clear();
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(BookmarksError::file("open scan state", &path))?;
+        if resume {
+            // Start on a fresh line in case the previous run left a torn record
+            writeln!(file)?;
+        } else {
+            file.set_len(0)?;
+        }
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+            results,
+        })
+    }
+
+    /// Number of bookmarks already checked by an earlier run
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    fn lookup(&self, bookmark: &Bookmark) -> Option<&(bool, String)> {
+        self.results
+            .get(&(bookmark.id.clone(), bookmark.url.clone()))
+    }
+
+    /// Append a result; failures only cost resumability, so they are ignored
+    fn record(&self, bookmark: &Bookmark, is_alive: bool, status: &str) {
+        let record = serde_json::json!({
+            "id": bookmark.id,
+            "url": bookmark.url,
+            "alive": is_alive,
+            "status": status,
+        });
+        if let Ok(mut file) = self.file.lock() {
+            writeln!(file, "{}", record).ok();
+        }
+    }
+
+    /// Remove the state file after a completed scan
+    pub fn finish(self) -> Result<()> {
+        fs::remove_file(&self.path).map_err(BookmarksError::file("remove scan state", &self.path))
+    }
+}
+
+/// Check every bookmark and return the dead ones along with their status
+pub fn find_dead_bookmarks<'a>(
+    bookmarks: &'a [Bookmark],
+    client: &Client,
+    policy: &StatusPolicy,
+    verbose: bool,
+) -> Result<Vec<(&'a Bookmark, String)>> {
+    scan_dead_bookmarks(bookmarks, client, policy, verbose, None)
+}
+
+/// Like `find_dead_bookmarks`, but persists progress so an interrupted scan
+/// can be resumed; the combined result is returned once every link is checked
+pub fn find_dead_bookmarks_resumable<'a>(
+    bookmarks: &'a [Bookmark],
+    client: &Client,
+    policy: &StatusPolicy,
+    verbose: bool,
+    resume: bool,
+) -> Result<Vec<(&'a Bookmark, String)>> {
+    let state = ScanState::open(resume)?;
+
+    if resume {
+        if state.is_empty() {
+            println!(
+                "{} No unfinished scan found, starting a new one",
+                "💡".yellow()
+            );
+        } else {
+            println!(
+                "{} Resuming scan: {} links already checked",
+                "⏩".cyan(),
+                state.len().to_string().yellow()
+            );
+        }
+    }
+
+    let dead = scan_dead_bookmarks(bookmarks, client, policy, verbose, Some(&state))?;
+    state.finish()?;
+    Ok(dead)
+}
+
+fn scan_dead_bookmarks<'a>(
+    bookmarks: &'a [Bookmark],
+    client: &Client,
+    policy: &StatusPolicy,
+    verbose: bool,
+    state: Option<&ScanState>,
+) -> Result<Vec<(&'a Bookmark, String)>> {
+    let limiter = HostRateLimiter::from_config(&Config::load()?).with_status_policy(policy.clone());
+
+    // Results carried over from an earlier run are not checked again, but the
+    // current status policy still decides whether they count as dead
+    let mut dead_links: Vec<(usize, &Bookmark, String)> = Vec::new();
+    let mut pending = Vec::new();
+    for idx in interleave_by_host(bookmarks) {
+        // Bookmarklets and internal pages can't be fetched
+        if bookmarks[idx].is_internal() {
+            continue;
+        }
+        match state.and_then(|s| s.lookup(&bookmarks[idx])) {
+            Some((is_alive, status))
+                if !limiter.classify(*is_alive, status) && status != "skipped" =>
+            {
+                dead_links.push((idx, &bookmarks[idx], status.clone()))
+            }
+            Some(_) => {}
+            None => pending.push(idx),
+        }
+    }
+
+    let total = bookmarks.iter().filter(|b| !b.is_internal()).count();
+    let dead_count = AtomicUsize::new(dead_links.len());
+
+    println!(
+        "{} Checking {} bookmarks for dead links (this may take a while)...\n",
+        "🔍".cyan(),
+        pending.len().to_string().yellow()
+    );
+
+    // Overall progress plus a live dead-link line; verbose output goes through
+    // the MultiProgress so it is printed above the bars instead of through them
+    let progress = MultiProgress::new();
+    let bar = progress.add(timed_bar(total as u64).with_position((total - pending.len()) as u64));
+    bar.reset_eta();
+    let dead_line = progress.add(
+        status_line().with_message(format!("{} dead found", dead_links.len().to_string().red())),
+    );
+
+    let newly_dead: Vec<(usize, &Bookmark, String)> = pending
+        .into_par_iter()
+        .filter_map(|idx| {
+            let bookmark = &bookmarks[idx];
+
+            let (is_alive, status) = limiter.check(client, &bookmark.url);
+            if let Some(state) = state {
+                state.record(bookmark, is_alive, &status);
+            }
+            bar.inc(1);
+
+            if verbose && !is_alive {
+                progress
+                    .println(format!(
+                        "  {} {} - {}",
+                        "❌".red(),
+                        truncate_string(&bookmark.name, 40),
+                        status.red()
+                    ))
+                    .ok();
+            }
+
+            if !is_alive && status != "skipped" {
+                let dead = dead_count.fetch_add(1, Ordering::SeqCst) + 1;
+                dead_line.set_message(format!(
+                    "{} dead found, latest: {} ({})",
+                    dead.to_string().red(),
+                    truncate_string(&bookmark.name, 40),
+                    status
+                ));
+                Some((idx, bookmark, status))
+            } else {
+                None
+            }
+        })
+        .collect();
+    dead_links.extend(newly_dead);
+
+    bar.finish_and_clear();
+    dead_line.finish_and_clear();
+
+    // Restore bookmark order after the host interleaving
+    dead_links.sort_by_key(|(idx, _, _)| *idx);
+
+    println!(
+        "{} Checked {} bookmarks, {} dead\n",
+        "✓".green(),
+        total.to_string().yellow(),
+        dead_links.len().to_string().red()
+    );
+    Ok(dead_links
+        .into_iter()
+        .map(|(_, bookmark, status)| (bookmark, status))
+        .collect())
+}
+
+/// Count dead links by a key, most common first
+fn count_dead_by(
+    dead: &[(&Bookmark, String)],
+    key: impl Fn(&Bookmark, &str) -> String,
+) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for (bookmark, status) in dead {
+        *counts.entry(key(bookmark, status)).or_insert(0) += 1;
+    }
+
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Wayback Machine link for a URL
+fn archive_url(url: &str) -> String {
+    format!("https://web.archive.org/web/*/{}", url)
+}
+
+/// Write a standalone link-rot report after a dead-link scan
+///
+/// The format follows the output extension: `.html`/`.htm` produce an HTML
+/// page, anything else markdown. The report covers counts by status code, by
+/// domain and by folder, plus the full dead list with archive links.
+pub fn export_link_rot_report(
+    dead: &[(&Bookmark, String)],
+    checked: usize,
+    output_path: &str,
+) -> Result<String> {
+    let by_status = count_dead_by(dead, |_, status| status.to_string());
+    let by_domain = count_dead_by(dead, |b, _| extract_domain(&b.url));
+    let by_folder = count_dead_by(dead, |b, _| b.folder_path.clone());
+
+    let rate = if checked > 0 {
+        dead.len() as f64 * 100.0 / checked as f64
+    } else {
+        0.0
+    };
+
+    let is_html = output_path.ends_with(".html") || output_path.ends_with(".htm");
+
+    let report = if is_html {
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"UTF-8\">\n");
+        html.push_str("<title>Link Rot Report</title>\n");
+        html.push_str("<style>body{font-family:sans-serif;margin:2em}table{border-collapse:collapse;margin-bottom:2em}th,td{border:1px solid #ccc;padding:4px 8px;text-align:left}th{background:#eee}</style>\n");
+        html.push_str("</head>\n<body>\n<h1>Link Rot Report</h1>\n");
+        html.push_str(&format!(
+            "<p><em>Generated on: {}</em></p>\n",
+            chrono_lite_now()
+        ));
+        html.push_str(&format!(
+            "<p><strong>Checked: {} bookmarks &mdash; Dead: {} ({:.1}%)</strong></p>\n",
+            checked,
+            dead.len(),
+            rate
+        ));
+
+        for (heading, column, counts) in [
+            ("By Status", "Status", &by_status),
+            ("By Domain", "Domain", &by_domain),
+            ("By Folder", "Folder", &by_folder),
+        ] {
+            html.push_str(&format!(
+                "<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>Count</th></tr>\n",
+                heading, column
+            ));
+            for (key, count) in counts {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td></tr>\n",
+                    html_escape(key),
+                    count
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("<h2>Dead Links</h2>\n<table>\n");
+        html.push_str(
+            "<tr><th>Title</th><th>URL</th><th>Status</th><th>Folder</th><th>Archive</th></tr>\n",
+        );
+        for (bookmark, status) in dead {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td><td><a href=\"{}\">archive</a></td></tr>\n",
+                html_escape(&bookmark.name),
+                html_escape(&bookmark.url),
+                html_escape(&bookmark.url),
+                html_escape(status),
+                html_escape(&bookmark.folder_path),
+                html_escape(&archive_url(&bookmark.url))
+            ));
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    } else {
+        // Pipes would break markdown table cells
+        let cell = |s: &str| s.replace('|', "\\|");
+
+        let mut md = String::new();
+        md.push_str("# Link Rot Report\n\n");
+        md.push_str(&format!("*Generated on: {}*\n\n", chrono_lite_now()));
+        md.push_str(&format!(
+            "**Checked: {} bookmarks — Dead: {} ({:.1}%)**\n\n",
+            checked,
+            dead.len(),
+            rate
+        ));
+
+        for (heading, column, counts) in [
+            ("By Status", "Status", &by_status),
+            ("By Domain", "Domain", &by_domain),
+            ("By Folder", "Folder", &by_folder),
+        ] {
+            md.push_str(&format!(
+                "## {}\n\n| {} | Count |\n|---|---|\n",
+                heading, column
+            ));
+            for (key, count) in counts {
+                md.push_str(&format!("| {} | {} |\n", cell(key), count));
+            }
+            md.push('\n');
+        }
+
+        md.push_str(
+            "## Dead Links\n\n| Title | URL | Status | Folder | Archive |\n|---|---|---|---|---|\n",
+        );
+        for (bookmark, status) in dead {
+            md.push_str(&format!(
+                "| {} | {} | {} | {} | [archive]({}) |\n",
+                cell(&bookmark.name),
+                cell(&bookmark.url),
+                cell(status),
+                cell(&bookmark.folder_path),
+                archive_url(&bookmark.url)
+            ));
+        }
+        md
+    };
+
+    fs::write(output_path, &report).map_err(BookmarksError::file("write", output_path))?;
+    println!(
+        "{} Link-rot report written to: {}",
+        "✅".green(),
+        output_path.cyan()
+    );
+
+    Ok(report)
+}
+
+/// Duplicate bookmark info for removal
+#[derive(Clone, Debug)]
+pub struct DuplicateGroup {
+    pub url: String,
+    pub bookmarks: Vec<BookmarkInfo>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BookmarkInfo {
+    pub id: String,
+    pub name: String,
+    pub folder_path: String,
+}
+
+/// Find duplicate bookmark groups with full info
+pub fn find_duplicate_groups(bookmarks: &[Bookmark]) -> Vec<DuplicateGroup> {
+    let mut url_map: HashMap<String, Vec<BookmarkInfo>> = HashMap::new();
+
+    for bookmark in bookmarks {
+        url_map
+            .entry(bookmark.url.clone())
+            .or_default()
+            .push(BookmarkInfo {
+                id: bookmark.id.clone(),
+                name: bookmark.name.clone(),
+                folder_path: bookmark.folder_path.clone(),
+            });
+    }
+
+    url_map
+        .into_iter()
+        .filter(|(_, bms)| bms.len() > 1)
+        .map(|(url, bookmarks)| DuplicateGroup { url, bookmarks })
+        .collect()
+}
+
+/// Remove duplicates from the bookmarks file (keeps the first occurrence)
+///
+/// Bookmarklets and internal pages are left alone unless `include_internal` is set.
+/// Only bookmarks under `roots` are considered (all roots when empty).
+pub fn remove_duplicates(
+    dry_run: bool,
+    interactive: bool,
+    include_internal: bool,
+    roots: &[String],
+) -> Result<usize> {
+    use crossterm::{
+        event::{self, Event, KeyCode},
+        terminal::{self},
+    };
+    use std::io::{Write, stdout};
+
+    let path = get_chrome_bookmarks_path()?;
+    let content = fs::read_to_string(&path)?;
+    let mut json: serde_json::Value = serde_json::from_str(&content)?;
+
+    // Parse bookmarks to find duplicates
+    let (mut bookmarks, _) = parse_bookmarks()?;
+    bookmarks.retain(|b| b.in_roots(roots));
+    if !include_internal {
+        bookmarks.retain(|b| !b.is_internal());
+    }
+    let duplicate_groups = find_duplicate_groups(&bookmarks);
+
+    if duplicate_groups.is_empty() {
+        println!("{}", "No duplicate bookmarks found!".green());
+        return Ok(0);
+    }
+
+    println!(
+        "\n{} Found {} duplicate URL groups ({} total duplicate entries)\n",
+        "🔍".cyan(),
+        duplicate_groups.len().to_string().yellow(),
+        duplicate_groups
+            .iter()
+            .map(|g| g.bookmarks.len() - 1)
+            .sum::<usize>()
+            .to_string()
+            .yellow()
+    );
+
+    // Collect IDs to remove (keep first occurrence, remove rest)
+    let mut ids_to_remove: HashSet<String> = HashSet::new();
+    let mut removal_details: Vec<(String, String, String)> = Vec::new(); // (url, name, folder)
+
+    for group in &duplicate_groups {
+        // Skip the first one (keep it), remove the rest
+        for bookmark in group.bookmarks.iter().skip(1) {
+            ids_to_remove.insert(bookmark.id.clone());
+            removal_details.push((
+                truncate_string(&group.url, 50),
+                truncate_string(&bookmark.name, 30),
+                truncate_string(&bookmark.folder_path, 25),
+            ));
+        }
+    }
+
+    if interactive {
+        println!("{}", "Duplicates to be removed:".bold().cyan());
+        println!("{}", "─".repeat(80).dimmed());
+
+        for (i, (url, name, folder)) in removal_details.iter().enumerate().take(20) {
+            println!(
+                "  {}. {} - {} ({})",
+                (i + 1).to_string().yellow(),
+                name.cyan(),
+                url.dimmed(),
+                folder.magenta()
+            );
+        }
+
+        if removal_details.len() > 20 {
+            println!(
+                "  ... and {} more",
+                (removal_details.len() - 20).to_string().yellow()
+            );
+        }
+
+        println!("\n{}", "─".repeat(80).dimmed());
+        println!(
+            "\n{} {} duplicates will be removed (keeping first occurrence of each URL)",
+            "⚠️".yellow(),
+            ids_to_remove.len().to_string().red()
+        );
+        print!(
+            "\n{} Are you sure you want to proceed? [y/N]: ",
+            "❓".cyan()
+        );
+        stdout().flush()?;
+
+        terminal::enable_raw_mode()?;
+        let confirmed = loop {
+            if let Event::Key(key_event) = event::read()? {
+                match key_event.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => break true,
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc | KeyCode::Enter => {
+                        break false;
+                    }
+                    _ => {}
+                }
+            }
+        };
+        terminal::disable_raw_mode()?;
+        println!();
+
+        if !confirmed {
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(0);
+        }
+    }
+
+    if dry_run {
+        println!("\n{} Dry run - no changes made", "📋".cyan());
+        println!("Would remove {} duplicate bookmarks:", ids_to_remove.len());
+        for (_url, name, folder) in removal_details.iter().take(10) {
+            println!("  {} {} ({})", "•".red(), name, folder);
+        }
+        if removal_details.len() > 10 {
+            println!("  ... and {} more", removal_details.len() - 10);
+        }
+        return Ok(ids_to_remove.len());
+    }
+
+    // Create backup
+    let backup_path = format!("{}.backup", path.display());
+    fs::copy(&path, &backup_path)?;
+    println!("{} Backup created: {}", "💾".green(), backup_path.cyan());
+
+    // Remove duplicates from JSON structure
+    let removed_count = remove_bookmarks_by_id(&mut json, &ids_to_remove);
+
+    write_bookmarks_file(&path, &mut json)
+        .map_err(BookmarksError::file("write bookmarks file", &path))?;
+
+    println!(
+        "\n{} Removed {} duplicate bookmarks",
+        "✅".green(),
+        removed_count.to_string().yellow()
+    );
+    println!("{} Restart Chrome to see the changes", "💡".yellow());
+
+    Ok(removed_count)
+}
+
+/// Remove mobile/synced copies of bookmarks that also exist on the desktop roots
+pub fn remove_sync_orphans(dry_run: bool, interactive: bool) -> Result<usize> {
+    use crossterm::{
+        event::{self, Event, KeyCode},
+        terminal,
+    };
+    use std::io::{Write, stdout};
+
+    let path = get_chrome_bookmarks_path()?;
+    let content = fs::read_to_string(&path)?;
+    let mut json: serde_json::Value = serde_json::from_str(&content)?;
+
+    let (bookmarks, _) = parse_bookmarks()?;
+    let pairs = sync_orphan_pairs(&bookmarks);
+
+    if pairs.is_empty() {
+        println!("{}", "No sync orphans found!".green());
+        return Ok(0);
+    }
+
+    let ids_to_remove: HashSet<String> = pairs.iter().map(|(m, _)| m.id.clone()).collect();
+
+    if interactive {
+        println!("{}", "Mobile copies to be removed:".bold().cyan());
+        println!("{}", "─".repeat(80).dimmed());
+
+        for (i, (mobile, desktop)) in pairs.iter().enumerate().take(20) {
+            println!(
+                "  {}. {} - {} (kept in {})",
+                (i + 1).to_string().yellow(),
+                truncate_string(&mobile.name, 30).cyan(),
+                truncate_string(&mobile.url, 50).dimmed(),
+                desktop.folder_path.magenta()
             );
         }
 
-        println!("\n{}", "─".repeat(80).dimmed());
+        if pairs.len() > 20 {
+            println!("  ... and {} more", (pairs.len() - 20).to_string().yellow());
+        }
+
+        println!("\n{}", "─".repeat(80).dimmed());
+        println!(
+            "\n{} {} mobile copies will be removed (desktop bookmarks are kept)",
+            "⚠️".yellow(),
+            ids_to_remove.len().to_string().red()
+        );
+        print!(
+            "\n{} Are you sure you want to proceed? [y/N]: ",
+            "❓".cyan()
+        );
+        stdout().flush()?;
+
+        terminal::enable_raw_mode()?;
+        let confirmed = loop {
+            if let Event::Key(key_event) = event::read()? {
+                match key_event.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => break true,
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc | KeyCode::Enter => {
+                        break false;
+                    }
+                    _ => {}
+                }
+            }
+        };
+        terminal::disable_raw_mode()?;
+        println!();
+
+        if !confirmed {
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(0);
+        }
+    }
+
+    if dry_run {
+        println!("\n{} Dry run - no changes made", "📋".cyan());
+        println!("Would remove {} mobile copies:", ids_to_remove.len());
+        for (mobile, _) in pairs.iter().take(10) {
+            println!("  {} {} ({})", "•".red(), mobile.name, mobile.folder_path);
+        }
+        if pairs.len() > 10 {
+            println!("  ... and {} more", pairs.len() - 10);
+        }
+        return Ok(ids_to_remove.len());
+    }
+
+    // Create backup
+    let backup_path = format!("{}.backup", path.display());
+    fs::copy(&path, &backup_path)?;
+    println!("{} Backup created: {}", "💾".green(), backup_path.cyan());
+
+    let removed_count = remove_bookmarks_by_id(&mut json, &ids_to_remove);
+
+    write_bookmarks_file(&path, &mut json)
+        .map_err(BookmarksError::file("write bookmarks file", &path))?;
+
+    println!(
+        "\n{} Removed {} mobile copies",
+        "✅".green(),
+        removed_count.to_string().yellow()
+    );
+    println!("{} Restart Chrome to see the changes", "💡".yellow());
+
+    Ok(removed_count)
+}
+
+/// Rename the folders suggested by `suggest_folder_renames` in the Chrome JSON
+///
+/// Only folders under `roots` are renamed (all roots when empty).
+pub fn apply_folder_renames(dry_run: bool, interactive: bool, roots: &[String]) -> Result<usize> {
+    use crossterm::{
+        event::{self, Event, KeyCode},
+        terminal,
+    };
+    use std::io::{Write, stdout};
+
+    let path = get_chrome_bookmarks_path()?;
+    let content = fs::read_to_string(&path)?;
+    let mut json: serde_json::Value = serde_json::from_str(&content)?;
+
+    let (mut bookmarks, mut folders) = parse_bookmarks()?;
+    bookmarks.retain(|b| b.in_roots(roots));
+    folders.retain(|f| in_roots(&f.path, roots));
+    let renames = suggest_folder_renames(&bookmarks, &folders, &Config::load()?);
+
+    if renames.is_empty() {
+        println!("{}", "No folder renames to apply!".green());
+        return Ok(0);
+    }
+
+    if interactive {
+        println!(
+            "\n{} {} folders will be renamed",
+            "⚠️".yellow(),
+            renames.len().to_string().yellow()
+        );
+        print!(
+            "\n{} Are you sure you want to proceed? [y/N]: ",
+            "❓".cyan()
+        );
+        stdout().flush()?;
+
+        terminal::enable_raw_mode()?;
+        let confirmed = loop {
+            if let Event::Key(key_event) = event::read()? {
+                match key_event.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => break true,
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc | KeyCode::Enter => {
+                        break false;
+                    }
+                    _ => {}
+                }
+            }
+        };
+        terminal::disable_raw_mode()?;
+        println!();
+
+        if !confirmed {
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(0);
+        }
+    }
+
+    if dry_run {
+        println!("\n{} Dry run - no changes made", "📋".cyan());
+        println!("Would rename {} folders", renames.len());
+        return Ok(renames.len());
+    }
+
+    // Create backup
+    let backup_path = format!("{}.backup", path.display());
+    fs::copy(&path, &backup_path)?;
+    println!("{} Backup created: {}", "💾".green(), backup_path.cyan());
+
+    let new_names: HashMap<String, String> = renames
+        .into_iter()
+        .map(|r| (r.folder_id, r.entry.suggested_name))
+        .collect();
+
+    let mut renamed = 0;
+    if let Some(roots) = json.get_mut("roots").and_then(|r| r.as_object_mut()) {
+        for root in roots.values_mut() {
+            renamed += rename_in_node(root, &new_names);
+        }
+    }
+
+    write_bookmarks_file(&path, &mut json)
+        .map_err(BookmarksError::file("write bookmarks file", &path))?;
+
+    println!(
+        "\n{} Renamed {} folders",
+        "✅".green(),
+        renamed.to_string().yellow()
+    );
+    println!("{} Restart Chrome to see the changes", "💡".yellow());
+
+    Ok(renamed)
+}
+
+fn rename_in_node(node: &mut serde_json::Value, new_names: &HashMap<String, String>) -> usize {
+    let mut renamed = 0;
+
+    if node.get("type").and_then(|t| t.as_str()) == Some("folder")
+        && let Some(new_name) = node
+            .get("id")
+            .and_then(|i| i.as_str())
+            .and_then(|id| new_names.get(id))
+            .cloned()
+    {
+        node["name"] = serde_json::Value::String(new_name);
+        renamed += 1;
+    }
+
+    if let Some(children) = node.get_mut("children").and_then(|c| c.as_array_mut()) {
+        for child in children.iter_mut() {
+            renamed += rename_in_node(child, new_names);
+        }
+    }
+
+    renamed
+}
+
+/// Recursively remove bookmarks by ID from JSON structure
+fn remove_bookmarks_by_id(json: &mut serde_json::Value, ids_to_remove: &HashSet<String>) -> usize {
+    let mut removed = 0;
+
+    if let Some(obj) = json.as_object_mut()
+        && let Some(roots) = obj.get_mut("roots")
+        && let Some(roots_obj) = roots.as_object_mut()
+    {
+        for (_key, value) in roots_obj.iter_mut() {
+            removed += remove_from_node(value, ids_to_remove);
+        }
+    }
+
+    removed
+}
+
+fn remove_from_node(node: &mut serde_json::Value, ids_to_remove: &HashSet<String>) -> usize {
+    let mut removed = 0;
+
+    if let Some(obj) = node.as_object_mut()
+        && let Some(children) = obj.get_mut("children")
+        && let Some(children_arr) = children.as_array_mut()
+    {
+        // First, recursively process children of folders
+        for child in children_arr.iter_mut() {
+            removed += remove_from_node(child, ids_to_remove);
+        }
+
+        // Then remove marked bookmarks
+        let original_len = children_arr.len();
+        children_arr.retain(|child| {
+            if let Some(id) = child.get("id").and_then(|i| i.as_str()) {
+                !ids_to_remove.contains(id)
+            } else {
+                true
+            }
+        });
+        removed += original_len - children_arr.len();
+    }
+
+    removed
+}
+
+/// Snapshot history file for `trends`, relative to the data directory
+const HISTORY_FILE: &str = "bookmark-history.jsonl";
+
+/// Counts recorded by `bookmarks snapshot`
+#[derive(Debug, Clone, Default)]
+pub struct HistorySnapshot {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub date: String,
+    pub total: usize,
+    pub folders: usize,
+    pub duplicates: usize,
+    /// Only known when the snapshot was taken with `--check-links`
+    pub dead: Option<usize>,
+    /// Bookmarks per category folder name
+    pub categories: BTreeMap<String, usize>,
+}
+
+impl HistorySnapshot {
+    /// Take a snapshot of the current collection
+    pub fn capture(
+        bookmarks: &[Bookmark],
+        folders: &[BookmarkFolder],
+        dead: Option<usize>,
+    ) -> Self {
+        let stats = get_bookmark_stats(bookmarks, folders, DomainGrouping::default());
+
+        let mut categories = BTreeMap::new();
+        for bookmark in bookmarks {
+            *categories
+                .entry(bookmark.category.folder_name().to_string())
+                .or_insert(0) += 1;
+        }
+
+        Self {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            date: chrono_lite_now(),
+            total: stats.total_bookmarks,
+            folders: stats.total_folders,
+            duplicates: stats.duplicates,
+            dead,
+            categories,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "timestamp": self.timestamp,
+            "date": self.date,
+            "total": self.total,
+            "folders": self.folders,
+            "duplicates": self.duplicates,
+            "dead": self.dead,
+            "categories": self.categories,
+        })
+    }
+
+    fn from_json(record: &serde_json::Value) -> Option<Self> {
+        let count = |key: &str| record.get(key).and_then(|v| v.as_u64()).map(|n| n as usize);
+
+        Some(Self {
+            timestamp: record.get("timestamp")?.as_u64()?,
+            date: record.get("date")?.as_str()?.to_string(),
+            total: count("total")?,
+            folders: count("folders").unwrap_or(0),
+            duplicates: count("duplicates").unwrap_or(0),
+            dead: count("dead"),
+            categories: record
+                .get("categories")
+                .and_then(|v| v.as_object())
+                .map(|categories| {
+                    categories
+                        .iter()
+                        .filter_map(|(name, n)| n.as_u64().map(|n| (name.clone(), n as usize)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Location of the snapshot history used by `trends`
+pub fn history_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join(HISTORY_FILE))
+}
+
+/// Append a snapshot to the history
+pub fn record_snapshot(snapshot: &HistorySnapshot) -> Result<()> {
+    let path = history_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(BookmarksError::file("create directory", &parent))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(BookmarksError::file("open snapshot history", &path))?;
+    writeln!(file, "{}", snapshot.to_json())
+        .map_err(BookmarksError::file("write snapshot history", &path))
+}
+
+/// All recorded snapshots, oldest first; unreadable lines are skipped
+pub fn load_history() -> Result<Vec<HistorySnapshot>> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(BookmarksError::file("read snapshot history", &path))?;
+    let mut history: Vec<HistorySnapshot> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|record| HistorySnapshot::from_json(&record))
+        .collect();
+    history.sort_by_key(|snapshot| snapshot.timestamp);
+
+    Ok(history)
+}
+
+/// Entry for the snapshot history table
+#[derive(Tabled, Clone)]
+pub struct TrendEntry {
+    #[tabled(rename = "Date")]
+    pub date: String,
+    #[tabled(rename = "Bookmarks")]
+    pub total: usize,
+    #[tabled(rename = "Change")]
+    pub change: String,
+    #[tabled(rename = "Folders")]
+    pub folders: usize,
+    #[tabled(rename = "Duplicates")]
+    pub duplicates: usize,
+    #[tabled(rename = "Dead")]
+    pub dead: String,
+}
+
+/// Entry for the per-category growth table
+#[derive(Tabled, Clone)]
+pub struct CategoryTrendEntry {
+    #[tabled(rename = "Category")]
+    pub category: String,
+    #[tabled(rename = "First")]
+    pub first: usize,
+    #[tabled(rename = "Latest")]
+    pub latest: usize,
+    #[tabled(rename = "Change")]
+    pub change: String,
+}
+
+/// Signed difference, formatted as `+3`, `-2` or `0`
+pub fn format_change(from: usize, to: usize) -> String {
+    match to.cmp(&from) {
+        std::cmp::Ordering::Greater => format!("+{}", to - from),
+        std::cmp::Ordering::Less => format!("-{}", from - to),
+        std::cmp::Ordering::Equal => "0".to_string(),
+    }
+}
+
+/// One row per snapshot, with the change in bookmark count since the previous one
+pub fn trend_entries(history: &[HistorySnapshot]) -> Vec<TrendEntry> {
+    history
+        .iter()
+        .enumerate()
+        .map(|(i, snapshot)| TrendEntry {
+            date: snapshot.date.clone(),
+            total: snapshot.total,
+            change: match i.checked_sub(1) {
+                Some(prev) => format_change(history[prev].total, snapshot.total),
+                None => "-".to_string(),
+            },
+            folders: snapshot.folders,
+            duplicates: snapshot.duplicates,
+            dead: snapshot
+                .dead
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        })
+        .collect()
+}
+
+/// Category counts in the first and latest snapshot, biggest movers first
+pub fn category_trend_entries(
+    first: &HistorySnapshot,
+    latest: &HistorySnapshot,
+) -> Vec<CategoryTrendEntry> {
+    let names: BTreeSet<&String> = first
+        .categories
+        .keys()
+        .chain(latest.categories.keys())
+        .collect();
+
+    let mut entries: Vec<(usize, CategoryTrendEntry)> = names
+        .into_iter()
+        .map(|name| {
+            let from = first.categories.get(name).copied().unwrap_or(0);
+            let to = latest.categories.get(name).copied().unwrap_or(0);
+            (
+                from.abs_diff(to),
+                CategoryTrendEntry {
+                    category: name.clone(),
+                    first: from,
+                    latest: to,
+                    change: format_change(from, to),
+                },
+            )
+        })
+        .collect();
+
+    entries
+        .sort_by_key(|(moved, entry)| (std::cmp::Reverse(*moved), std::cmp::Reverse(entry.latest)));
+    entries.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Category snapshot file, relative to the data directory
+const CATEGORY_SNAPSHOT: &str = "category-snapshot.json";
+
+/// Location of the stored category assignment used by `recategorize`
+pub fn category_snapshot_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join(CATEGORY_SNAPSHOT))
+}
+
+/// Load the stored bookmark id -> category assignment, if a snapshot exists
+pub fn load_category_snapshot() -> Result<Option<HashMap<String, String>>> {
+    let path = category_snapshot_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(BookmarksError::file("read category snapshot", &path))?;
+    let json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(BookmarksError::parse("category snapshot", &path))?;
+
+    Ok(Some(
+        json.as_object()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|(id, category)| {
+                        category.as_str().map(|c| (id.clone(), c.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    ))
+}
+
+/// Store the current category of every bookmark as the new snapshot
+pub fn save_category_snapshot(bookmarks: &[Bookmark]) -> Result<()> {
+    let path = category_snapshot_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(BookmarksError::file("create directory", &parent))?;
+    }
+
+    let snapshot: serde_json::Map<String, serde_json::Value> = bookmarks
+        .iter()
+        .map(|b| {
+            (
+                b.id.clone(),
+                serde_json::Value::String(b.category.to_string()),
+            )
+        })
+        .collect();
+
+    fs::write(&path, serde_json::to_string_pretty(&snapshot)?)
+        .map_err(BookmarksError::file("write category snapshot", &path))
+}
+
+/// Bookmarks whose category under the current rules differs from the snapshot,
+/// paired with their old category. Bookmarks added since the snapshot are skipped.
+pub fn category_changes<'a>(
+    bookmarks: &'a [Bookmark],
+    snapshot: &HashMap<String, String>,
+) -> Vec<(&'a Bookmark, String)> {
+    bookmarks
+        .iter()
+        .filter_map(|bookmark| {
+            let old = snapshot.get(&bookmark.id)?;
+            (*old != bookmark.category.to_string()).then(|| (bookmark, old.clone()))
+        })
+        .collect()
+}
+
+pub fn recategorize_entries(changes: &[(&Bookmark, String)]) -> Vec<RecategorizeEntry> {
+    changes
+        .iter()
+        .map(|(bookmark, old)| RecategorizeEntry {
+            title: truncate_string(&bookmark.name, 40),
+            old_category: old.clone(),
+            new_category: bookmark.category.to_string(),
+            folder: bookmark.folder_path.clone(),
+        })
+        .collect()
+}
+
+/// Move the bookmarks whose category changed since the snapshot into their
+/// new category folders, then refresh the snapshot
+///
+/// Category folders are created under the root each bookmark already lives
+/// in (bookmarks bar, other bookmarks, ...). Unchanged bookmarks are not touched,
+/// and neither are bookmarks outside `roots` (all roots when empty).
+pub fn apply_recategorize(dry_run: bool, interactive: bool, roots: &[String]) -> Result<usize> {
+    use crossterm::{
+        event::{self, Event, KeyCode},
+        terminal,
+    };
+    use std::io::{Write, stdout};
+
+    let snapshot = load_category_snapshot()?.ok_or(BookmarksError::NoSnapshot)?;
+
+    let path = get_chrome_bookmarks_path()?;
+    let content = fs::read_to_string(&path)?;
+    let mut json: serde_json::Value = serde_json::from_str(&content)?;
+
+    let (bookmarks, _) = parse_bookmarks()?;
+    let mut changes = category_changes(&bookmarks, &snapshot);
+    changes.retain(|(bookmark, _)| bookmark.in_roots(roots));
+
+    if changes.is_empty() {
+        println!("{}", "No category changes to apply!".green());
+        return Ok(0);
+    }
+
+    if interactive {
         println!(
-            "\n{} {} duplicates will be removed (keeping first occurrence of each URL)",
+            "\n{} {} bookmarks will be moved into their new category folders",
             "⚠️".yellow(),
-            ids_to_remove.len().to_string().red()
+            changes.len().to_string().yellow()
         );
         print!(
             "\n{} Are you sure you want to proceed? [y/N]: ",
@@ -2209,14 +4652,19 @@ pub fn remove_duplicates(dry_run: bool, interactive: bool) -> Result<usize> {
 
     if dry_run {
         println!("\n{} Dry run - no changes made", "📋".cyan());
-        println!("Would remove {} duplicate bookmarks:", ids_to_remove.len());
-        for (url, name, folder) in removal_details.iter().take(10) {
-            println!("  {} {} ({})", "•".red(), name, folder);
+        for (bookmark, _) in changes.iter().take(10) {
+            println!(
+                "  {} {} → {}/{}",
+                "•".cyan(),
+                bookmark.name,
+                bookmark.root(),
+                bookmark.category.folder_name()
+            );
         }
-        if removal_details.len() > 10 {
-            println!("  ... and {} more", removal_details.len() - 10);
+        if changes.len() > 10 {
+            println!("  ... and {} more", changes.len() - 10);
         }
-        return Ok(ids_to_remove.len());
+        return Ok(changes.len());
     }
 
     // Create backup
@@ -2224,134 +4672,590 @@ pub fn remove_duplicates(dry_run: bool, interactive: bool) -> Result<usize> {
     fs::copy(&path, &backup_path)?;
     println!("{} Backup created: {}", "💾".green(), backup_path.cyan());
 
-    // Remove duplicates from JSON structure
-    let removed_count = remove_bookmarks_by_id(&mut json, &ids_to_remove);
+    let ids: HashSet<String> = changes.iter().map(|(b, _)| b.id.clone()).collect();
+    let mut nodes = HashMap::new();
+    if let Some(roots) = json.get_mut("roots").and_then(|r| r.as_object_mut()) {
+        for root in roots.values_mut() {
+            take_from_node(root, &ids, &mut nodes);
+        }
+    }
+
+    let mut next_id = max_node_id(&json) + 1;
+    let mut moved = 0;
+    for (bookmark, _) in &changes {
+        let (Some(node), Some(root)) = (
+            nodes.remove(&bookmark.id),
+            json.get_mut("roots")
+                .and_then(|r| r.get_mut(bookmark.root())),
+        ) else {
+            continue;
+        };
+
+        let mut folder = root;
+        for name in bookmark.category.folder_name().split('/') {
+            folder = child_folder(folder, name, &mut next_id);
+        }
+        if let Some(children) = folder.get_mut("children").and_then(|c| c.as_array_mut()) {
+            children.push(node);
+            moved += 1;
+        }
+    }
 
-    // Write back to file
-    let new_content = serde_json::to_string_pretty(&json)?;
-    fs::write(&path, new_content)?;
+    write_bookmarks_file(&path, &mut json)
+        .map_err(BookmarksError::file("write bookmarks file", &path))?;
+    save_category_snapshot(&bookmarks)?;
 
     println!(
-        "\n{} Removed {} duplicate bookmarks",
+        "\n{} Moved {} bookmarks into their new category folders",
         "✅".green(),
-        removed_count.to_string().yellow()
+        moved.to_string().yellow()
     );
     println!("{} Restart Chrome to see the changes", "💡".yellow());
 
-    Ok(removed_count)
+    Ok(moved)
 }
 
-/// Recursively remove bookmarks by ID from JSON structure
-fn remove_bookmarks_by_id(json: &mut serde_json::Value, ids_to_remove: &HashSet<String>) -> usize {
-    let mut removed = 0;
+/// Recursively detach bookmarks by ID, keeping the removed nodes
+fn take_from_node(
+    node: &mut serde_json::Value,
+    ids: &HashSet<String>,
+    taken: &mut HashMap<String, serde_json::Value>,
+) {
+    if let Some(children) = node.get_mut("children").and_then(|c| c.as_array_mut()) {
+        for child in children.iter_mut() {
+            take_from_node(child, ids, taken);
+        }
 
-    if let Some(obj) = json.as_object_mut() {
-        if let Some(roots) = obj.get_mut("roots") {
-            if let Some(roots_obj) = roots.as_object_mut() {
-                for (_key, value) in roots_obj.iter_mut() {
-                    removed += remove_from_node(value, ids_to_remove);
-                }
+        let (removed, kept): (Vec<_>, Vec<_>) = children.drain(..).partition(|child| {
+            child
+                .get("id")
+                .and_then(|i| i.as_str())
+                .is_some_and(|id| ids.contains(id))
+        });
+        *children = kept;
+        for child in removed {
+            if let Some(id) = child.get("id").and_then(|i| i.as_str()).map(String::from) {
+                taken.insert(id, child);
             }
         }
     }
+}
 
-    removed
+/// Highest node ID in the bookmarks JSON, so new folders get unique IDs
+fn max_node_id(node: &serde_json::Value) -> u64 {
+    let own = node
+        .get("id")
+        .and_then(|i| i.as_str())
+        .and_then(|i| i.parse().ok())
+        .unwrap_or(0);
+
+    let children = match node {
+        serde_json::Value::Object(obj) => obj
+            .iter()
+            .filter(|(key, _)| *key == "roots" || *key == "children")
+            .flat_map(|(_, value)| match value {
+                serde_json::Value::Object(roots) => roots.values().collect::<Vec<_>>(),
+                serde_json::Value::Array(items) => items.iter().collect(),
+                _ => Vec::new(),
+            })
+            .map(max_node_id)
+            .max()
+            .unwrap_or(0),
+        _ => 0,
+    };
+
+    own.max(children)
 }
 
-fn remove_from_node(node: &mut serde_json::Value, ids_to_remove: &HashSet<String>) -> usize {
-    let mut removed = 0;
+/// Find a subfolder by name, creating it when missing
+fn child_folder<'a>(
+    folder: &'a mut serde_json::Value,
+    name: &str,
+    next_id: &mut u64,
+) -> &'a mut serde_json::Value {
+    let children = folder
+        .as_object_mut()
+        .expect("bookmark folder is a JSON object")
+        .entry("children")
+        .or_insert_with(|| serde_json::json!([]));
+    if !children.is_array() {
+        *children = serde_json::json!([]);
+    }
+    let children = children.as_array_mut().expect("children is an array");
+
+    let existing = children.iter().position(|child| {
+        child.get("type").and_then(|t| t.as_str()) == Some("folder")
+            && child.get("name").and_then(|n| n.as_str()) == Some(name)
+    });
+
+    let idx = match existing {
+        Some(idx) => idx,
+        None => {
+            // Chrome timestamps are microseconds since 1601-01-01
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_micros()
+                + 11_644_473_600_000_000;
+            children.push(serde_json::json!({
+                "children": [],
+                "date_added": now.to_string(),
+                "date_modified": now.to_string(),
+                "id": next_id.to_string(),
+                "name": name,
+                "type": "folder",
+            }));
+            *next_id += 1;
+            children.len() - 1
+        }
+    };
 
-    if let Some(obj) = node.as_object_mut() {
-        if let Some(children) = obj.get_mut("children") {
-            if let Some(children_arr) = children.as_array_mut() {
-                // First, recursively process children of folders
-                for child in children_arr.iter_mut() {
-                    removed += remove_from_node(child, ids_to_remove);
-                }
+    &mut children[idx]
+}
 
-                // Then remove marked bookmarks
-                let original_len = children_arr.len();
-                children_arr.retain(|child| {
-                    if let Some(id) = child.get("id").and_then(|i| i.as_str()) {
-                        !ids_to_remove.contains(id)
-                    } else {
-                        true
-                    }
-                });
-                removed += original_len - children_arr.len();
+/// Let the user pick which dead links to remove, all selected to start with
+///
+/// Returns `None` when the selection is cancelled.
+fn select_dead_links(dead_links: &[DeadLinkEntry]) -> Result<Option<Vec<&DeadLinkEntry>>> {
+    use crossterm::{
+        cursor,
+        event::{self, Event, KeyCode, KeyModifiers},
+        execute,
+        terminal::{self, ClearType},
+    };
+    use std::io::{Write, stdout};
+
+    let mut selected = vec![true; dead_links.len()];
+    let mut selected_idx: usize = 0;
+    let mut stdout = stdout();
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let confirmed = loop {
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(ClearType::All)
+        )?;
+
+        write!(
+            stdout,
+            "{}\r\n",
+            "🗑️  Remove Dead Links - Select Links".bold().cyan()
+        )?;
+        write!(stdout, "{}\r\n", "─".repeat(80).dimmed())?;
+        write!(
+            stdout,
+            "Selected: {}/{} | {}=Toggle {}=All {}=None {}=Remove {}=Quit\r\n",
+            selected.iter().filter(|s| **s).count().to_string().green(),
+            dead_links.len().to_string().cyan(),
+            "Space".yellow(),
+            "a".yellow(),
+            "n".yellow(),
+            "Enter".yellow(),
+            "q".yellow()
+        )?;
+        write!(stdout, "{}\r\n", "─".repeat(80).dimmed())?;
+
+        let term_height = terminal::size()?.1 as usize;
+        let list_height = term_height.saturating_sub(8).max(1);
+        let start_idx = (selected_idx + 1).saturating_sub(list_height);
+        let end_idx = (start_idx + list_height).min(dead_links.len());
+
+        for (idx, entry) in dead_links
+            .iter()
+            .enumerate()
+            .skip(start_idx)
+            .take(end_idx - start_idx)
+        {
+            let checkbox = if selected[idx] { "[✓]" } else { "[ ]" };
+            let line = format!(
+                " {} {} - {} [{}] {}",
+                checkbox, entry.title, entry.status, entry.folder, entry.url
+            );
+
+            if idx == selected_idx {
+                write!(stdout, "{}\r\n", line.on_bright_blue().white())?;
+            } else if selected[idx] {
+                write!(stdout, "{}\r\n", line.red())?;
+            } else {
+                write!(stdout, "{}\r\n", line.dimmed())?;
+            }
+        }
+
+        if dead_links.len() > list_height {
+            write!(
+                stdout,
+                "\r\n{} {}/{}\r\n",
+                "Showing:".dimmed(),
+                (selected_idx + 1).to_string().cyan(),
+                dead_links.len().to_string().cyan()
+            )?;
+        }
+
+        stdout.flush()?;
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    selected_idx = selected_idx.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') if selected_idx + 1 < dead_links.len() => {
+                    selected_idx += 1;
+                }
+                KeyCode::Char(' ') => selected[selected_idx] = !selected[selected_idx],
+                KeyCode::Char('a') => selected.iter_mut().for_each(|s| *s = true),
+                KeyCode::Char('n') => selected.iter_mut().for_each(|s| *s = false),
+                KeyCode::Enter => break true,
+                KeyCode::Char('q') | KeyCode::Esc => break false,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break false,
+                _ => {}
             }
         }
+    };
+
+    execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
+    terminal::disable_raw_mode()?;
+
+    if !confirmed {
+        return Ok(None);
     }
 
-    removed
+    Ok(Some(
+        dead_links
+            .iter()
+            .zip(&selected)
+            .filter(|(_, keep)| **keep)
+            .map(|(entry, _)| entry)
+            .collect(),
+    ))
 }
 
 /// Remove dead links from bookmarks
+///
+/// Entries are removed strictly by their bookmark ID. With `interactive`, the
+/// user picks which of the links to remove first.
 pub fn remove_dead_links(
     dead_links: &[DeadLinkEntry],
     dry_run: bool,
     interactive: bool,
 ) -> Result<usize> {
-    use crossterm::{
-        event::{self, Event, KeyCode},
-        terminal,
-    };
-    use std::io::{Write, stdout};
-
     if dead_links.is_empty() {
         println!("{}", "No dead links to remove!".green());
         return Ok(0);
     }
 
+    let to_remove: Vec<&DeadLinkEntry> = if interactive {
+        match select_dead_links(dead_links)? {
+            Some(selected) => selected,
+            None => {
+                println!("{}", "Operation cancelled.".yellow());
+                return Ok(0);
+            }
+        }
+    } else {
+        dead_links.iter().collect()
+    };
+
+    if to_remove.is_empty() {
+        println!("{}", "No dead links selected.".yellow());
+        return Ok(0);
+    }
+
+    let ids_to_remove: HashSet<String> = to_remove.iter().map(|d| d.id.clone()).collect();
+
+    if dry_run {
+        println!("\n{} Dry run - no changes made", "📋".cyan());
+        println!("Would remove {} dead links:", ids_to_remove.len());
+        for entry in to_remove.iter().take(10) {
+            println!("  {} {} ({})", "•".red(), entry.title, entry.folder);
+        }
+        if to_remove.len() > 10 {
+            println!("  ... and {} more", to_remove.len() - 10);
+        }
+        return Ok(ids_to_remove.len());
+    }
+
     let path = get_chrome_bookmarks_path()?;
     let content = fs::read_to_string(&path)?;
     let mut json: serde_json::Value = serde_json::from_str(&content)?;
 
-    // We need to find bookmark IDs by URL
-    let (bookmarks, _) = parse_bookmarks()?;
-    let dead_urls: HashSet<String> = dead_links.iter().map(|d| d.url.clone()).collect();
+    // Create backup
+    let backup_path = format!("{}.backup", path.display());
+    fs::copy(&path, &backup_path)?;
+    println!("{} Backup created: {}", "💾".green(), backup_path.cyan());
 
-    let ids_to_remove: HashSet<String> = bookmarks
-        .iter()
-        .filter(|b| {
-            dead_urls.iter().any(|dead_url| {
-                b.url.contains(dead_url.trim_end_matches("..."))
-                    || dead_url.contains(
-                        &truncate_string(&b.url, 50)
-                            .trim_end_matches("...")
-                            .to_string(),
-                    )
-            })
+    // Remove dead links from JSON structure
+    let removed_count = remove_bookmarks_by_id(&mut json, &ids_to_remove);
+
+    write_bookmarks_file(&path, &mut json)
+        .map_err(BookmarksError::file("write bookmarks file", &path))?;
+
+    println!(
+        "\n{} Removed {} dead links",
+        "✅".green(),
+        removed_count.to_string().yellow()
+    );
+    println!("{} Restart Chrome to see the changes", "💡".yellow());
+
+    Ok(removed_count)
+}
+
+/// Hosts of common URL shorteners
+const SHORTENER_HOSTS: &[&str] = &[
+    "bit.ly",
+    "t.co",
+    "goo.gl",
+    "tinyurl.com",
+    "ow.ly",
+    "buff.ly",
+    "is.gd",
+    "lnkd.in",
+    "rb.gy",
+    "tiny.cc",
+    "cutt.ly",
+];
+
+/// Cache of resolved short URLs (short -> final destination), in the data directory
+const SHORT_URL_CACHE: &str = "short-urls.json";
+
+/// Check whether a URL points at a known URL shortener
+pub fn is_short_url(url: &str) -> bool {
+    let domain = extract_domain(url);
+    let host = domain.split(':').next().unwrap_or(&domain);
+    SHORTENER_HOSTS.contains(&host)
+}
+
+#[derive(Tabled, Clone)]
+pub struct ShortUrlEntry {
+    #[tabled(rename = "Title")]
+    pub title: String,
+    #[tabled(rename = "Short URL")]
+    pub short_url: String,
+    #[tabled(rename = "Expanded URL")]
+    pub expanded_url: String,
+    #[tabled(rename = "Category")]
+    pub category: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+/// A shortened bookmark and where it resolves to, if it could be resolved
+pub struct ShortUrlExpansion {
+    pub bookmark_id: String,
+    pub expanded: Option<String>,
+    pub entry: ShortUrlEntry,
+}
+
+pub fn short_url_cache_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join(SHORT_URL_CACHE))
+}
+
+fn load_short_url_cache() -> Result<HashMap<String, String>> {
+    let path = short_url_cache_path()?;
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(BookmarksError::file("read short URL cache", &path))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&content).map_err(BookmarksError::parse("short URL cache", &path))?;
+
+    Ok(json
+        .as_object()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|(short, expanded)| {
+                    expanded.as_str().map(|e| (short.clone(), e.to_string()))
+                })
+                .collect()
         })
-        .map(|b| b.id.clone())
+        .unwrap_or_default())
+}
+
+fn save_short_url_cache(cache: &HashMap<String, String>) -> Result<()> {
+    let path = short_url_cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(BookmarksError::file("create directory", &parent))?;
+    }
+
+    let json: serde_json::Map<String, serde_json::Value> = cache
+        .iter()
+        .map(|(short, expanded)| (short.clone(), serde_json::Value::String(expanded.clone())))
         .collect();
 
-    if interactive {
-        println!("\n{}", "Dead links to be removed:".bold().cyan());
-        println!("{}", "─".repeat(80).dimmed());
+    fs::write(&path, serde_json::to_string_pretty(&json)?)
+        .map_err(BookmarksError::file("write short URL cache", &path))
+}
 
-        for (i, entry) in dead_links.iter().enumerate().take(20) {
-            println!(
-                "  {}. {} - {} [{}]",
-                (i + 1).to_string().yellow(),
-                entry.title.cyan(),
-                entry.status.red(),
-                entry.folder.dimmed()
-            );
+/// Follow a short URL's redirects and return the final destination
+///
+/// Tries HEAD first and falls back to GET for shorteners that reject HEAD.
+fn resolve_short_url(client: &Client, url: &str) -> Result<String> {
+    let response = match client.head(url).send() {
+        Ok(response) if response.status().is_success() || response.status().is_redirection() => {
+            response
         }
+        _ => client.get(url).send()?,
+    };
 
-        if dead_links.len() > 20 {
-            println!(
-                "  ... and {} more",
-                (dead_links.len() - 20).to_string().yellow()
-            );
+    if !response.status().is_success() {
+        return Err(BookmarksError::Unresolved(response.status().to_string()));
+    }
+
+    let final_url = response.url().to_string();
+    if final_url == url {
+        return Err(BookmarksError::Unresolved("no redirect".to_string()));
+    }
+
+    Ok(final_url)
+}
+
+/// Resolve every shortened bookmark, using and updating the on-disk cache
+///
+/// Each result carries the category the expanded URL would get and whether
+/// it duplicates a bookmark you already have.
+pub fn expand_short_urls(
+    bookmarks: &[Bookmark],
+    client: &Client,
+    verbose: bool,
+) -> Result<Vec<ShortUrlExpansion>> {
+    let mut cache = load_short_url_cache()?;
+    let registry = CategoryRegistry::load()?;
+
+    let short: Vec<&Bookmark> = bookmarks.iter().filter(|b| is_short_url(&b.url)).collect();
+
+    let to_resolve: HashSet<&str> = short
+        .iter()
+        .map(|b| b.url.as_str())
+        .filter(|url| !cache.contains_key(*url))
+        .collect();
+
+    println!(
+        "{} Found {} shortened URLs ({} cached, {} to resolve)",
+        "🔗".cyan(),
+        short.len().to_string().yellow(),
+        (short.len() - to_resolve.len()).to_string().green(),
+        to_resolve.len().to_string().yellow()
+    );
+
+    let resolved: Vec<(String, Result<String>)> = to_resolve
+        .into_par_iter()
+        .map(|url| {
+            let result = resolve_short_url(client, url);
+            if verbose {
+                match &result {
+                    Ok(expanded) => println!("  {} {} → {}", "✓".green(), url, expanded),
+                    Err(e) => println!("  {} {}: {}", "✗".red(), url, e),
+                }
+            }
+            (url.to_string(), result)
+        })
+        .collect();
+
+    let mut errors = HashMap::new();
+    let mut newly_resolved = HashSet::new();
+    for (url, result) in resolved {
+        match result {
+            Ok(expanded) => {
+                newly_resolved.insert(url.clone());
+                cache.insert(url, expanded);
+            }
+            Err(e) => {
+                errors.insert(url, e.to_string());
+            }
         }
+    }
 
-        println!("\n{}", "─".repeat(80).dimmed());
+    if !newly_resolved.is_empty() {
+        save_short_url_cache(&cache)?;
+    }
+
+    let existing_urls: HashSet<&str> = bookmarks.iter().map(|b| b.url.as_str()).collect();
+
+    Ok(short
+        .into_iter()
+        .map(|bookmark| {
+            let expanded = cache.get(&bookmark.url).cloned();
+            let (category, status) = match &expanded {
+                Some(url) => {
+                    let status = if existing_urls.contains(url.as_str()) {
+                        "Duplicate".to_string()
+                    } else if newly_resolved.contains(&bookmark.url) {
+                        "✓ Resolved".to_string()
+                    } else {
+                        "Cached".to_string()
+                    };
+                    (registry.categorize(url, &bookmark.name).to_string(), status)
+                }
+                None => (
+                    String::new(),
+                    format!(
+                        "✗ {}",
+                        errors
+                            .get(&bookmark.url)
+                            .map(|s| s.as_str())
+                            .unwrap_or("failed")
+                    ),
+                ),
+            };
+
+            ShortUrlExpansion {
+                bookmark_id: bookmark.id.clone(),
+                entry: ShortUrlEntry {
+                    title: truncate_string(&bookmark.name, 30),
+                    short_url: bookmark.url.clone(),
+                    expanded_url: expanded
+                        .as_deref()
+                        .map(|url| truncate_string(url, 50))
+                        .unwrap_or_default(),
+                    category,
+                    status,
+                },
+                expanded,
+            }
+        })
+        .collect())
+}
+
+/// Rewrite shortened bookmarks to their expanded URLs in the Chrome JSON
+pub fn rewrite_short_urls(
+    expansions: &[ShortUrlExpansion],
+    dry_run: bool,
+    interactive: bool,
+) -> Result<usize> {
+    use crossterm::{
+        event::{self, Event, KeyCode},
+        terminal,
+    };
+    use std::io::stdout;
+
+    let new_urls: HashMap<String, String> = expansions
+        .iter()
+        .filter_map(|e| {
+            e.expanded
+                .as_ref()
+                .map(|url| (e.bookmark_id.clone(), url.clone()))
+        })
+        .collect();
+
+    if new_urls.is_empty() {
+        println!("{}", "No resolved short URLs to rewrite!".green());
+        return Ok(0);
+    }
+
+    let path = get_chrome_bookmarks_path()?;
+    let content = fs::read_to_string(&path)?;
+    let mut json: serde_json::Value = serde_json::from_str(&content)?;
+
+    if interactive {
         println!(
-            "\n{} {} dead links will be removed",
+            "\n{} {} bookmarks will be rewritten to their expanded URLs",
             "⚠️".yellow(),
-            dead_links.len().to_string().red()
+            new_urls.len().to_string().yellow()
         );
         print!(
             "\n{} Are you sure you want to proceed? [y/N]: ",
@@ -2382,8 +5286,8 @@ pub fn remove_dead_links(
 
     if dry_run {
         println!("\n{} Dry run - no changes made", "📋".cyan());
-        println!("Would remove {} dead links", dead_links.len());
-        return Ok(dead_links.len());
+        println!("Would rewrite {} bookmarks", new_urls.len());
+        return Ok(new_urls.len());
     }
 
     // Create backup
@@ -2391,19 +5295,45 @@ pub fn remove_dead_links(
     fs::copy(&path, &backup_path)?;
     println!("{} Backup created: {}", "💾".green(), backup_path.cyan());
 
-    // Remove dead links from JSON structure
-    let removed_count = remove_bookmarks_by_id(&mut json, &ids_to_remove);
+    let mut rewritten = 0;
+    if let Some(roots) = json.get_mut("roots").and_then(|r| r.as_object_mut()) {
+        for root in roots.values_mut() {
+            rewritten += rewrite_urls_in_node(root, &new_urls);
+        }
+    }
 
-    // Write back to file
-    let new_content = serde_json::to_string_pretty(&json)?;
-    fs::write(&path, new_content)?;
+    write_bookmarks_file(&path, &mut json)
+        .map_err(BookmarksError::file("write bookmarks file", &path))?;
 
     println!(
-        "\n{} Removed {} dead links",
+        "\n{} Rewrote {} bookmarks",
         "✅".green(),
-        removed_count.to_string().yellow()
+        rewritten.to_string().yellow()
     );
     println!("{} Restart Chrome to see the changes", "💡".yellow());
 
-    Ok(removed_count)
+    Ok(rewritten)
+}
+
+fn rewrite_urls_in_node(node: &mut serde_json::Value, new_urls: &HashMap<String, String>) -> usize {
+    let mut rewritten = 0;
+
+    if node.get("type").and_then(|t| t.as_str()) == Some("url")
+        && let Some(new_url) = node
+            .get("id")
+            .and_then(|i| i.as_str())
+            .and_then(|id| new_urls.get(id))
+            .cloned()
+    {
+        node["url"] = serde_json::Value::String(new_url);
+        rewritten += 1;
+    }
+
+    if let Some(children) = node.get_mut("children").and_then(|c| c.as_array_mut()) {
+        for child in children.iter_mut() {
+            rewritten += rewrite_urls_in_node(child, new_urls);
+        }
+    }
+
+    rewritten
 }
@@ -0,0 +1,349 @@
+use colored::Colorize;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{self, ClearType},
+};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write, stdout};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::format_size;
+
+/// A directory with the total size of everything below it
+///
+/// Only directories are kept in the tree; a directory's files are listed
+/// from disk when it's opened, so a scan of a whole home directory stays small.
+#[derive(Debug, Clone)]
+pub struct DiskNode {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub children: Vec<DiskNode>,
+}
+
+/// Measure `path` and every directory below it, in parallel
+///
+/// Symlinks aren't followed; they count with their own size.
+pub fn scan_disk_usage(path: &Path) -> DiskNode {
+    let modified = fs::symlink_metadata(path).and_then(|m| m.modified()).ok();
+    let entries: Vec<fs::DirEntry> = fs::read_dir(path)
+        .map(|iter| iter.flatten().collect())
+        .unwrap_or_default();
+
+    let (dirs, files): (Vec<_>, Vec<_>) = entries
+        .into_iter()
+        .partition(|entry| entry.file_type().is_ok_and(|t| t.is_dir()));
+    let files_size: u64 = files
+        .iter()
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|m| m.len())
+        .sum();
+
+    let children: Vec<DiskNode> = dirs
+        .par_iter()
+        .map(|entry| scan_disk_usage(&entry.path()))
+        .collect();
+
+    DiskNode {
+        path: path.to_path_buf(),
+        size: files_size + children.iter().map(|c| c.size).sum::<u64>(),
+        modified,
+        children,
+    }
+}
+
+/// Column the browser sorts by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortBy {
+    Size,
+    Name,
+    Modified,
+}
+
+impl SortBy {
+    fn label(self) -> &'static str {
+        match self {
+            SortBy::Size => "size",
+            SortBy::Name => "name",
+            SortBy::Modified => "modified",
+        }
+    }
+}
+
+/// One line of the listing: a subdirectory (index into `children`) or a file
+struct Row {
+    name: String,
+    path: PathBuf,
+    size: u64,
+    modified: Option<SystemTime>,
+    child: Option<usize>,
+}
+
+fn rows(node: &DiskNode, sort: SortBy) -> Vec<Row> {
+    let mut rows: Vec<Row> = node
+        .children
+        .iter()
+        .enumerate()
+        .map(|(i, child)| Row {
+            name: format!("{}/", file_name(&child.path)),
+            path: child.path.clone(),
+            size: child.size,
+            modified: child.modified,
+            child: Some(i),
+        })
+        .collect();
+
+    // Files aren't in the tree; read them now
+    if let Ok(entries) = fs::read_dir(&node.path) {
+        rows.extend(
+            entries
+                .flatten()
+                .filter(|entry| !entry.file_type().is_ok_and(|t| t.is_dir()))
+                .filter_map(|entry| {
+                    let metadata = entry.metadata().ok()?;
+                    Some(Row {
+                        name: entry.file_name().to_string_lossy().to_string(),
+                        path: entry.path(),
+                        size: metadata.len(),
+                        modified: metadata.modified().ok(),
+                        child: None,
+                    })
+                }),
+        );
+    }
+
+    match sort {
+        SortBy::Size => rows.sort_by(|a, b| b.size.cmp(&a.size).then(a.name.cmp(&b.name))),
+        SortBy::Name => rows.sort_by_key(|row| row.name.to_lowercase()),
+        SortBy::Modified => rows.sort_by_key(|row| std::cmp::Reverse(row.modified)),
+    }
+    rows
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn age(modified: Option<SystemTime>) -> String {
+    match modified
+        .and_then(|m| SystemTime::now().duration_since(m).ok())
+        .map(|d| d.as_secs() / 86400)
+    {
+        Some(0) => "today".to_string(),
+        Some(days) => format!("{}d ago", days),
+        None => "-".to_string(),
+    }
+}
+
+/// Size bar relative to the largest entry in the listing
+fn bar(size: u64, largest: u64) -> String {
+    const WIDTH: usize = 10;
+    let filled = if largest == 0 {
+        0
+    } else {
+        ((size as f64 / largest as f64) * WIDTH as f64).round() as usize
+    };
+    format!("{}{}", "█".repeat(filled), "░".repeat(WIDTH - filled))
+}
+
+/// Browse `tree` ncdu-style and return the directories marked for cleaning
+///
+/// Directories can be opened to see what's using the space, sorted by size,
+/// name or modification time, and any of them marked. `can_mark` rejects
+/// directories that must not be cleaned (exclusions, kept projects); marking a directory
+/// unmarks anything marked inside it. Quitting returns nothing.
+pub fn browse_disk_usage(
+    tree: &DiskNode,
+    free_space: Option<u64>,
+    verb: &str,
+    can_mark: impl Fn(&Path) -> bool,
+) -> io::Result<Vec<(PathBuf, u64)>> {
+    // Child indices from the root to the open directory, and the cursor in each
+    let mut trail: Vec<usize> = Vec::new();
+    let mut cursors: Vec<usize> = vec![0];
+    let mut scroll_offset = 0;
+    let mut sort = SortBy::Size;
+    let mut marked: HashMap<PathBuf, u64> = HashMap::new();
+    let mut message = String::new();
+
+    terminal::enable_raw_mode()?;
+    let mut stdout = stdout();
+    execute!(stdout, terminal::Clear(ClearType::All), cursor::Hide)?;
+
+    let confirmed = loop {
+        let node = trail.iter().fold(tree, |node, &i| &node.children[i]);
+        let listing = rows(node, sort);
+        let cursor_pos = cursors.last_mut().expect("cursor for the open directory");
+        *cursor_pos = (*cursor_pos).min(listing.len().saturating_sub(1));
+
+        let (_, term_height) = terminal::size().unwrap_or((80, 24));
+        let visible_rows = (term_height as usize).saturating_sub(10);
+        if *cursor_pos < scroll_offset {
+            scroll_offset = *cursor_pos;
+        } else if *cursor_pos >= scroll_offset + visible_rows {
+            scroll_offset = *cursor_pos - visible_rows + 1;
+        }
+
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(ClearType::All)
+        )?;
+
+        let marked_size: u64 = marked.values().sum();
+        writeln!(
+            stdout,
+            "{}",
+            format!("🧹 Disk Usage - {}", node.path.display())
+                .bold()
+                .cyan()
+        )?;
+        writeln!(stdout, "{}", "─".repeat(80).dimmed())?;
+        writeln!(
+            stdout,
+            "Here: {}  |  Marked: {} ({})  |  Sorted by {}",
+            format_size(node.size).cyan(),
+            marked.len().to_string().green(),
+            format_size(marked_size).green(),
+            sort.label().yellow()
+        )?;
+        if let Some(free) = free_space {
+            writeln!(
+                stdout,
+                "Free space after cleaning: {} (currently {})",
+                format_size(free + marked_size).bold().green(),
+                format_size(free).cyan()
+            )?;
+        }
+        writeln!(stdout, "{}", "─".repeat(80).dimmed())?;
+
+        if listing.is_empty() {
+            writeln!(stdout, "{}", "  (empty)".dimmed())?;
+        }
+        let largest = listing.iter().map(|row| row.size).max().unwrap_or(0);
+        for (i, row) in listing
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(visible_rows)
+        {
+            let inside_marked = marked
+                .keys()
+                .any(|m| row.path.starts_with(m) && row.path != *m);
+            let checkbox = if marked.contains_key(&row.path) || inside_marked {
+                "[✓]"
+            } else {
+                "[ ]"
+            };
+            let name = if row.name.chars().count() > 45 {
+                format!("{}...", row.name.chars().take(42).collect::<String>())
+            } else {
+                row.name.clone()
+            };
+            let line = format!(
+                " {} {:>10} {} {:<45} {:>9}",
+                checkbox,
+                format_size(row.size),
+                bar(row.size, largest),
+                name,
+                age(row.modified)
+            );
+
+            if i == *cursor_pos {
+                writeln!(stdout, "{}", line.on_blue().white())?;
+            } else if checkbox == "[✓]" {
+                writeln!(stdout, "{}", line.green())?;
+            } else if row.child.is_none() {
+                writeln!(stdout, "{}", line.dimmed())?;
+            } else {
+                writeln!(stdout, "{}", line)?;
+            }
+        }
+
+        writeln!(stdout)?;
+        writeln!(stdout, "{}", message.yellow())?;
+        writeln!(stdout, "{}", "─".repeat(80).dimmed())?;
+        writeln!(
+            stdout,
+            "{}  {}  {}  {}  {}  {}  {}",
+            "↑↓:Navigate".dimmed(),
+            "→/Enter:Open ←:Up".dimmed(),
+            "Space:Mark".dimmed(),
+            "u:Unmark all".dimmed(),
+            "s/n/m:Sort size/name/modified".dimmed(),
+            format!("d:{} marked", verb).dimmed(),
+            "q:Quit".dimmed()
+        )?;
+        stdout.flush()?;
+        message.clear();
+
+        let Event::Key(key_event) = event::read()? else {
+            continue;
+        };
+        let current = listing.get(*cursor_pos);
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                *cursor_pos = cursor_pos.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if *cursor_pos + 1 < listing.len() => {
+                *cursor_pos += 1;
+            }
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char('l') => {
+                if let Some(i) = current.and_then(|row| row.child) {
+                    trail.push(i);
+                    cursors.push(0);
+                    scroll_offset = 0;
+                }
+            }
+            KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') if !trail.is_empty() => {
+                trail.pop();
+                cursors.pop();
+                scroll_offset = 0;
+            }
+            KeyCode::Char(' ') => match current {
+                Some(row) if row.child.is_none() => {
+                    message = "Only directories can be marked".to_string();
+                }
+                Some(row) if marked.remove(&row.path).is_some() => {}
+                Some(row) if marked.keys().any(|m| row.path.starts_with(m)) => {
+                    message = "A parent directory is already marked".to_string();
+                }
+                Some(row) if !can_mark(&row.path) => {
+                    message = format!("{} is excluded from cleaning", row.name);
+                }
+                Some(row) => {
+                    marked.retain(|m, _| !m.starts_with(&row.path));
+                    marked.insert(row.path.clone(), row.size);
+                }
+                None => {}
+            },
+            KeyCode::Char('u') => marked.clear(),
+            KeyCode::Char('s') => sort = SortBy::Size,
+            KeyCode::Char('n') => sort = SortBy::Name,
+            KeyCode::Char('m') => sort = SortBy::Modified,
+            KeyCode::Char('d') if !marked.is_empty() => break true,
+            KeyCode::Char('q') | KeyCode::Esc => break false,
+            KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                break false;
+            }
+            _ => {}
+        }
+    };
+
+    execute!(stdout, cursor::Show)?;
+    terminal::disable_raw_mode()?;
+
+    if !confirmed {
+        return Ok(Vec::new());
+    }
+    let mut marked: Vec<(PathBuf, u64)> = marked.into_iter().collect();
+    marked.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    Ok(marked)
+}
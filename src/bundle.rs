@@ -0,0 +1,410 @@
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tabled::Tabled;
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    FileCategory, Journal, ORGANIZE_JOURNAL_KIND, OrganizeError, Severity, civil_from_days,
+    file_sha256, format_size, is_dev_folder, load_exclusions, numbered_name, partial_path,
+    unix_seconds,
+};
+
+type Result<T, E = OrganizeError> = std::result::Result<T, E>;
+
+/// Folder under the organized root the bundles are written to
+pub const BUNDLES_FOLDER: &str = "Archives";
+
+/// Old files of one category and year, packed into one zip
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    /// e.g. `Archives/Documents-2023.zip` under the organized root
+    pub archive: PathBuf,
+    pub files: Vec<BundleFile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BundleFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// Row of the bundles table; the bundle's name is only on its first row
+#[derive(Tabled, Clone)]
+pub struct BundleEntry {
+    #[tabled(rename = "Bundle")]
+    pub bundle: String,
+    #[tabled(rename = "File")]
+    pub file: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Modified")]
+    pub modified: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+/// Find files under `root` last modified more than `older_than` ago, grouped
+/// into one bundle per category and year
+///
+/// Looks at the files in `root` and in its subfolders one level down (the
+/// category folders a run leaves), skipping hidden entries, development
+/// folders, symlinks, the bundles folder itself and files matching the
+/// `exclude` globs or `root`'s `.organizeignore`. The year is the
+/// modification time's, in UTC.
+pub fn find_bundles(root: &Path, older_than: Duration, exclude: &[String]) -> Result<Vec<Bundle>> {
+    let cutoff = SystemTime::now()
+        .checked_sub(older_than)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    let exclusions = load_exclusions(root, exclude)?;
+
+    let mut files = Vec::new();
+    collect_old_files(root, cutoff, &mut files)?;
+    for entry in fs::read_dir(root)
+        .map_err(|source| OrganizeError::ReadDir {
+            path: root.to_path_buf(),
+            source,
+        })?
+        .flatten()
+    {
+        let path = entry.path();
+        let name = entry.file_name();
+        if entry.file_type().is_ok_and(|t| t.is_dir())
+            && !name.to_string_lossy().starts_with('.')
+            && name != BUNDLES_FOLDER
+            && !is_dev_folder(&path)
+        {
+            // Unreadable subfolders are skipped rather than ending the scan
+            collect_old_files(&path, cutoff, &mut files).ok();
+        }
+    }
+
+    let mut bundles: BTreeMap<String, Vec<BundleFile>> = BTreeMap::new();
+    for file in files {
+        if exclusions.iter().any(|e| e.matches(&file.path)) {
+            continue;
+        }
+        let (category, _) = FileCategory::detect(&file.path, false);
+        let (year, _, _) = civil_from_days(unix_seconds(file.modified).div_euclid(86_400));
+        bundles
+            .entry(format!("{}-{}.zip", category.folder_name(), year))
+            .or_default()
+            .push(file);
+    }
+
+    Ok(bundles
+        .into_iter()
+        .map(|(name, mut files)| {
+            files.sort_by(|a, b| a.modified.cmp(&b.modified).then(a.path.cmp(&b.path)));
+            Bundle {
+                archive: root.join(BUNDLES_FOLDER).join(name),
+                files,
+            }
+        })
+        .collect())
+}
+
+/// Add the regular files directly in `dir` modified before `cutoff` to `files`
+fn collect_old_files(dir: &Path, cutoff: SystemTime, files: &mut Vec<BundleFile>) -> Result<()> {
+    let entries = fs::read_dir(dir).map_err(|source| OrganizeError::ReadDir {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || name.ends_with(".partial") {
+            continue;
+        }
+        // `file_type` doesn't follow symlinks, so links left by `--link` runs are skipped
+        if !entry.file_type().is_ok_and(|t| t.is_file()) {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if let Ok(modified) = metadata.modified()
+            && modified < cutoff
+        {
+            files.push(BundleFile {
+                path: entry.path(),
+                size: metadata.len(),
+                modified,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Rows of the bundles table, with `statuses` (one per file) once the bundles
+/// were written
+pub fn bundle_entries(bundles: &[Bundle], statuses: Option<&[Vec<String>]>) -> Vec<BundleEntry> {
+    bundles
+        .iter()
+        .enumerate()
+        .flat_map(|(index, bundle)| {
+            bundle.files.iter().enumerate().map(move |(i, file)| {
+                let (year, month, day) =
+                    civil_from_days(unix_seconds(file.modified).div_euclid(86_400));
+                BundleEntry {
+                    bundle: if i == 0 {
+                        let name = bundle.archive.file_name().unwrap_or_default();
+                        format!("{} ({} files)", name.to_string_lossy(), bundle.files.len())
+                    } else {
+                        String::new()
+                    },
+                    file: file.path.display().to_string(),
+                    size: format_size(file.size),
+                    modified: format!("{}-{:02}-{:02}", year, month, day),
+                    status: statuses
+                        .and_then(|s| s.get(index))
+                        .and_then(|s| s.get(i))
+                        .cloned()
+                        .unwrap_or_else(|| "Pending".to_string()),
+                }
+            })
+        })
+        .collect()
+}
+
+/// Pack each bundle's files into its zip, then remove the originals
+///
+/// A bundle that already exists from an earlier run is added to; names taken
+/// inside it get ` (1)`, ` (2)`, ... The zip is written at a staging path and
+/// every added file is read back and compared by SHA-256 before it's moved
+/// into place, so originals are only removed once their copy is verified. A
+/// bundle that fails is left out and its files stay where they are. Each
+/// removal is journaled under the organize kind as `bundle`, with the file's
+/// path inside the zip as its destination, so undo extracts it again.
+/// Returns each bundle's statuses, one per file.
+pub fn write_bundles(bundles: &[Bundle], dry_run: bool) -> Result<Vec<Vec<String>>> {
+    if dry_run {
+        return Ok(bundles
+            .iter()
+            .map(|bundle| vec!["Would bundle".to_string(); bundle.files.len()])
+            .collect());
+    }
+
+    let mut journal = Journal::begin(ORGANIZE_JOURNAL_KIND)?;
+    let mut statuses = Vec::new();
+
+    for bundle in bundles {
+        let names = match pack_verified(bundle) {
+            Ok(names) => names,
+            Err(e) if e.severity() == Severity::Recoverable => {
+                statuses.push(vec![format!("✗ Error: {}", e); bundle.files.len()]);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        println!(
+            "{} Wrote {}",
+            "🗜️".cyan(),
+            bundle.archive.display().to_string().green()
+        );
+
+        let mut bundle_statuses = Vec::new();
+        for (file, name) in bundle.files.iter().zip(&names) {
+            let id =
+                journal.record_action("bundle", &file.path, Some(&bundle.archive.join(name)))?;
+            match fs::remove_file(&file.path) {
+                Ok(()) => {
+                    journal.record_done(id)?;
+                    bundle_statuses.push("✓ Bundled".to_string());
+                }
+                Err(e) => bundle_statuses.push(format!("✗ Bundled, but not removed: {}", e)),
+            }
+        }
+        statuses.push(bundle_statuses);
+    }
+
+    journal.commit()?;
+    Ok(statuses)
+}
+
+/// Write a bundle's zip with its files added and verified, returning each
+/// file's name inside it
+fn pack_verified(bundle: &Bundle) -> Result<Vec<String>> {
+    let archive = &bundle.archive;
+    if let Some(parent) = archive.parent() {
+        fs::create_dir_all(parent).map_err(|source| OrganizeError::CreateDir {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+
+    let partial = partial_path(archive);
+    let result = pack(bundle, &partial).and_then(|added| {
+        verify(&partial, &added)?;
+        fs::rename(&partial, archive).map_err(|source| OrganizeError::File {
+            action: "move into place",
+            path: archive.clone(),
+            source,
+        })?;
+        Ok(added.into_iter().map(|(name, _)| name).collect())
+    });
+    if result.is_err() {
+        fs::remove_file(&partial).ok();
+    }
+    result
+}
+
+/// Write the bundle to `partial`, starting from a copy of its existing zip if
+/// there is one; returns each added file's name and SHA-256
+fn pack(bundle: &Bundle, partial: &Path) -> Result<Vec<(String, String)>> {
+    let zip_error = |source| OrganizeError::Bundle {
+        path: bundle.archive.clone(),
+        source,
+    };
+    let io_error = |action: &'static str, path: &Path| {
+        let path = path.to_path_buf();
+        move |source| OrganizeError::File {
+            action,
+            path,
+            source,
+        }
+    };
+
+    let existing = bundle.archive.exists();
+    if existing {
+        fs::copy(&bundle.archive, partial).map_err(io_error("copy", &bundle.archive))?;
+    }
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(!existing)
+        .open(partial)
+        .map_err(io_error("create", partial))?;
+    let mut zip = if existing {
+        ZipWriter::new_append(file).map_err(zip_error)?
+    } else {
+        ZipWriter::new(file)
+    };
+
+    let mut taken: HashSet<String> = if existing {
+        ZipArchive::new(File::open(&bundle.archive).map_err(io_error("read", &bundle.archive))?)
+            .map_err(zip_error)?
+            .file_names()
+            .map(str::to_string)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    let mut added = Vec::new();
+    for file in &bundle.files {
+        let original = file.path.file_name().unwrap_or_default().to_string_lossy();
+        let mut name = original.to_string();
+        for n in 1.. {
+            if !taken.contains(&name) {
+                break;
+            }
+            name = numbered_name(&original, n);
+        }
+        taken.insert(name.clone());
+
+        let mut options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(file.size >= u32::MAX as u64);
+        if let Some(time) = zip_time(file.modified) {
+            options = options.last_modified_time(time);
+        }
+        zip.start_file(name.as_str(), options).map_err(zip_error)?;
+        let mut source = File::open(&file.path).map_err(io_error("read", &file.path))?;
+        io::copy(&mut source, &mut zip).map_err(io_error("compress", &file.path))?;
+
+        added.push((name, file_sha256(&file.path)?));
+    }
+
+    zip.finish().map_err(zip_error)?;
+    Ok(added)
+}
+
+/// Read every added file back from the zip at `partial` and compare its SHA-256
+fn verify(partial: &Path, added: &[(String, String)]) -> Result<()> {
+    let zip_error = |source| OrganizeError::Bundle {
+        path: partial.to_path_buf(),
+        source,
+    };
+    let file = File::open(partial).map_err(|source| OrganizeError::File {
+        action: "read",
+        path: partial.to_path_buf(),
+        source,
+    })?;
+    let mut zip = ZipArchive::new(file).map_err(zip_error)?;
+
+    for (name, expected) in added {
+        let mut entry = zip.by_name(name).map_err(zip_error)?;
+        let mut hasher = Sha256::new();
+        io::copy(&mut entry, &mut hasher).map_err(|source| OrganizeError::File {
+            action: "verify",
+            path: partial.join(name),
+            source,
+        })?;
+        if format!("{:x}", hasher.finalize()) != *expected {
+            return Err(OrganizeError::BundleMismatch(partial.join(name)));
+        }
+    }
+    Ok(())
+}
+
+/// A time as a zip timestamp (UTC); zips can't hold times before 1980
+fn zip_time(time: SystemTime) -> Option<zip::DateTime> {
+    let secs = unix_seconds(time);
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let seconds_of_day = secs.rem_euclid(86_400);
+    zip::DateTime::from_date_and_time(
+        u16::try_from(year).ok()?,
+        month as u8,
+        day as u8,
+        (seconds_of_day / 3600) as u8,
+        (seconds_of_day / 60 % 60) as u8,
+        (seconds_of_day % 60) as u8,
+    )
+    .ok()
+}
+
+/// Extract a file `write_bundles` packed back to `target`
+///
+/// `bundled` is the journaled destination: the zip's path joined with the
+/// file's name inside it. The zip itself is left as it is.
+pub(crate) fn extract_bundled(bundled: &Path, target: &Path) -> io::Result<()> {
+    let (Some(archive), Some(name)) = (bundled.parent(), bundled.file_name()) else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "not a bundled path",
+        ));
+    };
+    let mut zip = ZipArchive::new(File::open(archive)?).map_err(io::Error::other)?;
+    let mut entry = zip
+        .by_name(&name.to_string_lossy())
+        .map_err(io::Error::other)?;
+
+    let partial = partial_path(target);
+    let result = File::create(&partial)
+        .and_then(|mut file| io::copy(&mut entry, &mut file))
+        .and_then(|_| fs::rename(&partial, target));
+    if result.is_err() {
+        fs::remove_file(&partial).ok();
+    }
+    result
+}
+
+/// Whether a file `write_bundles` packed is still in its zip
+pub(crate) fn is_bundled(bundled: &Path) -> bool {
+    let (Some(archive), Some(name)) = (bundled.parent(), bundled.file_name()) else {
+        return false;
+    };
+    File::open(archive)
+        .ok()
+        .and_then(|file| ZipArchive::new(file).ok())
+        .is_some_and(|zip| zip.index_for_name(&name.to_string_lossy()).is_some())
+}
@@ -0,0 +1,232 @@
+use colored::Colorize;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{self, ClearType},
+};
+use rayon::prelude::*;
+use std::env;
+use std::io::{self, Write, stdout};
+use std::path::{Path, PathBuf};
+use tabled::Tabled;
+
+use crate::{ConfigError, calculate_dir_size, format_size, home_dir};
+
+/// Package-manager caches `clean --subcommand caches` knows, by name
+pub const PACKAGE_CACHE_NAMES: &[&str] = &["npm", "yarn", "pnpm", "pip", "cargo"];
+
+/// A package manager's download cache, found on disk
+#[derive(Debug, Clone)]
+pub struct PackageCache {
+    /// One of `PACKAGE_CACHE_NAMES`
+    pub name: &'static str,
+    /// The cache directories that exist (yarn classic and berry, cargo's
+    /// registry cache and sources)
+    pub paths: Vec<PathBuf>,
+    pub size: u64,
+}
+
+/// Row of the package cache report
+#[derive(Tabled, Clone)]
+pub struct PackageCacheEntry {
+    #[tabled(rename = "Cache")]
+    pub name: String,
+    #[tabled(rename = "Location")]
+    pub location: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+}
+
+/// An absolute directory from an environment variable
+fn env_dir(key: &str) -> Option<PathBuf> {
+    env::var_os(key)
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+}
+
+/// Where a cache lives: its override variable, then the platform default
+fn cache_locations(name: &str, home: &Path) -> Vec<PathBuf> {
+    let macos = cfg!(target_os = "macos");
+    let xdg_cache = env_dir("XDG_CACHE_HOME").unwrap_or_else(|| home.join(".cache"));
+    let xdg_data = env_dir("XDG_DATA_HOME").unwrap_or_else(|| home.join(".local/share"));
+
+    match name {
+        "npm" => vec![
+            env_dir("npm_config_cache")
+                .unwrap_or_else(|| home.join(".npm"))
+                .join("_cacache"),
+        ],
+        "yarn" => vec![
+            env_dir("YARN_CACHE_FOLDER").unwrap_or_else(|| {
+                if macos {
+                    home.join("Library/Caches/Yarn")
+                } else {
+                    xdg_cache.join("yarn")
+                }
+            }),
+            home.join(".yarn/berry/cache"),
+        ],
+        "pnpm" => vec![if macos && env::var_os("XDG_DATA_HOME").is_none() {
+            home.join("Library/pnpm/store")
+        } else {
+            xdg_data.join("pnpm/store")
+        }],
+        "pip" => vec![env_dir("PIP_CACHE_DIR").unwrap_or_else(|| {
+            if macos {
+                home.join("Library/Caches/pip")
+            } else {
+                xdg_cache.join("pip")
+            }
+        })],
+        "cargo" => {
+            let cargo_home = env_dir("CARGO_HOME").unwrap_or_else(|| home.join(".cargo"));
+            vec![
+                cargo_home.join("registry/cache"),
+                cargo_home.join("registry/src"),
+            ]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Find the package-manager caches on this machine and measure them
+///
+/// Caches that don't exist are left out. For cargo only the downloaded
+/// crates and their extracted sources count; the registry index and git
+/// checkouts are kept.
+pub fn find_package_caches() -> Result<Vec<PackageCache>, ConfigError> {
+    let home = home_dir()?;
+    Ok(PACKAGE_CACHE_NAMES
+        .par_iter()
+        .filter_map(|&name| {
+            let paths: Vec<PathBuf> = cache_locations(name, &home)
+                .into_iter()
+                .filter(|path| path.is_dir())
+                .collect();
+            if paths.is_empty() {
+                return None;
+            }
+            let size = paths.iter().map(|path| calculate_dir_size(path)).sum();
+            Some(PackageCache { name, paths, size })
+        })
+        .collect())
+}
+
+/// Table row for a cache
+pub fn package_cache_entry(cache: &PackageCache) -> PackageCacheEntry {
+    PackageCacheEntry {
+        name: cache.name.to_string(),
+        location: cache
+            .paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        size: format_size(cache.size),
+    }
+}
+
+/// Let the user opt in to clearing caches, none selected to start with
+///
+/// Returns `None` when the selection is cancelled.
+pub fn select_package_caches(caches: &[PackageCache]) -> io::Result<Option<Vec<PackageCache>>> {
+    let mut selected = vec![false; caches.len()];
+    let mut selected_idx: usize = 0;
+    let mut stdout = stdout();
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let confirmed = loop {
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(ClearType::All)
+        )?;
+
+        let selected_size: u64 = caches
+            .iter()
+            .zip(&selected)
+            .filter(|(_, s)| **s)
+            .map(|(cache, _)| cache.size)
+            .sum();
+        write!(
+            stdout,
+            "{}\r\n",
+            "🧹 Clear Package Caches - Select Caches".bold().cyan()
+        )?;
+        write!(stdout, "{}\r\n", "─".repeat(80).dimmed())?;
+        write!(
+            stdout,
+            "Selected: {} ({}) | {}=Toggle {}=All {}=None {}=Clear {}=Quit\r\n",
+            selected.iter().filter(|s| **s).count().to_string().green(),
+            format_size(selected_size).green(),
+            "Space".yellow(),
+            "a".yellow(),
+            "n".yellow(),
+            "Enter".yellow(),
+            "q".yellow()
+        )?;
+        write!(stdout, "{}\r\n", "─".repeat(80).dimmed())?;
+
+        for (idx, cache) in caches.iter().enumerate() {
+            let checkbox = if selected[idx] { "[✓]" } else { "[ ]" };
+            let line = format!(
+                " {} {:<6} {:>10}  {}",
+                checkbox,
+                cache.name,
+                format_size(cache.size),
+                cache
+                    .paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+
+            if idx == selected_idx {
+                write!(stdout, "{}\r\n", line.on_bright_blue().white())?;
+            } else if selected[idx] {
+                write!(stdout, "{}\r\n", line.red())?;
+            } else {
+                write!(stdout, "{}\r\n", line.dimmed())?;
+            }
+        }
+        stdout.flush()?;
+
+        let Event::Key(key_event) = event::read()? else {
+            continue;
+        };
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => selected_idx = selected_idx.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if selected_idx + 1 < caches.len() => {
+                selected_idx += 1;
+            }
+            KeyCode::Char(' ') => selected[selected_idx] = !selected[selected_idx],
+            KeyCode::Char('a') => selected.fill(true),
+            KeyCode::Char('n') => selected.fill(false),
+            KeyCode::Enter => break true,
+            KeyCode::Char('q') | KeyCode::Esc => break false,
+            KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                break false;
+            }
+            _ => {}
+        }
+    };
+
+    execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
+    terminal::disable_raw_mode()?;
+
+    if !confirmed {
+        return Ok(None);
+    }
+    Ok(Some(
+        caches
+            .iter()
+            .zip(selected)
+            .filter(|(_, s)| *s)
+            .map(|(cache, _)| cache.clone())
+            .collect(),
+    ))
+}
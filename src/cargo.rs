@@ -0,0 +1,262 @@
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+use tabled::Tabled;
+
+use crate::format_size;
+
+/// Subdirectories of a profile directory that hold per-crate build output,
+/// the ones an age-based sweep removes entries from
+const SWEPT_DIRS: &[&str] = &["deps", "build", ".fingerprint", "incremental", "examples"];
+
+/// What `cargo metadata` says about the project a `target` directory sits in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetOwner {
+    /// Cargo confirmed this is the workspace's target directory
+    Workspace(PathBuf),
+    /// Cargo puts this project's builds elsewhere (a workspace member, or
+    /// `CARGO_TARGET_DIR` / `build.target-dir` pointing away)
+    Elsewhere(PathBuf),
+    /// `cargo metadata` isn't available or failed; only `Cargo.toml` next to it vouches for it
+    Unverified,
+}
+
+/// A `target` directory with its size per build profile
+#[derive(Debug, Clone)]
+pub struct CargoTarget {
+    pub path: PathBuf,
+    pub owner: TargetOwner,
+    /// `debug`, `release`, custom profiles, `<triple>/<profile>` for
+    /// cross builds, and other top-level entries (`doc`, `package`, ...)
+    pub profiles: Vec<(String, u64)>,
+    pub size: u64,
+    /// Most recent access or modification of a profile's fingerprints
+    pub last_used: Option<SystemTime>,
+}
+
+/// Row of the cargo target report
+#[derive(Tabled, Clone)]
+pub struct CargoTargetEntry {
+    #[tabled(rename = "Target")]
+    pub path: String,
+    #[tabled(rename = "Workspace")]
+    pub workspace: String,
+    #[tabled(rename = "Profiles")]
+    pub profiles: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Last used")]
+    pub last_used: String,
+}
+
+/// Build output in a target directory not used for a while
+#[derive(Debug, Clone)]
+pub struct StaleArtifact {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Ask cargo which target directory the project next to `target` uses
+fn target_owner(target: &Path) -> TargetOwner {
+    let Some(project) = target.parent() else {
+        return TargetOwner::Unverified;
+    };
+
+    let output = Command::new("cargo")
+        .args([
+            "metadata",
+            "--no-deps",
+            "--format-version",
+            "1",
+            "--offline",
+        ])
+        .current_dir(project)
+        .output();
+    let Some(metadata) = output
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| serde_json::from_slice::<serde_json::Value>(&output.stdout).ok())
+    else {
+        return TargetOwner::Unverified;
+    };
+
+    let field = |key: &str| {
+        metadata
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from)
+    };
+    let (Some(target_directory), Some(workspace_root)) =
+        (field("target_directory"), field("workspace_root"))
+    else {
+        return TargetOwner::Unverified;
+    };
+
+    let same = |a: &Path, b: &Path| match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    };
+    if same(&target_directory, target) {
+        TargetOwner::Workspace(workspace_root)
+    } else {
+        TargetOwner::Elsewhere(target_directory)
+    }
+}
+
+/// Whether `dir` is a profile directory (it has cargo's per-crate output)
+fn is_profile_dir(dir: &Path) -> bool {
+    dir.join(".fingerprint").is_dir() || dir.join("deps").is_dir()
+}
+
+/// Profile directories under `target`: `debug`, `release`, custom profiles
+/// and `<triple>/<profile>` for cross builds
+fn profile_dirs(target: &Path) -> Vec<(String, PathBuf)> {
+    let mut profiles = Vec::new();
+    for entry in fs::read_dir(target).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        if is_profile_dir(&path) {
+            profiles.push((name, path));
+            continue;
+        }
+        for inner in fs::read_dir(&path).into_iter().flatten().flatten() {
+            if is_profile_dir(&inner.path()) {
+                let inner_name = inner.file_name().to_string_lossy().to_string();
+                profiles.push((format!("{}/{}", name, inner_name), inner.path()));
+            }
+        }
+    }
+    profiles
+}
+
+/// When an entry was last used: the later of a file's access and
+/// modification times, a directory's modification time (listing a
+/// directory, as this scan does, bumps its access time)
+fn last_used(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    let modified = metadata.modified().ok();
+    if metadata.is_dir() {
+        return modified;
+    }
+    modified.max(metadata.accessed().ok())
+}
+
+fn tree_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| tree_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Verify `target` with `cargo metadata` and measure it per profile
+pub fn inspect_cargo_target(target: &Path) -> CargoTarget {
+    let owner = target_owner(target);
+    let profiles = profile_dirs(target);
+
+    let mut sizes: Vec<(String, u64)> = Vec::new();
+    for entry in fs::read_dir(target).into_iter().flatten().flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let nested: Vec<&(String, PathBuf)> = profiles
+            .iter()
+            .filter(|(label, _)| label.starts_with(&format!("{}/", name)))
+            .collect();
+        if nested.is_empty() {
+            sizes.push((name, tree_size(&entry.path())));
+        } else {
+            sizes.extend(
+                nested
+                    .into_iter()
+                    .map(|(label, path)| (label.clone(), tree_size(path))),
+            );
+        }
+    }
+    sizes.retain(|(_, size)| *size > 0);
+    sizes.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+    let last_used = profiles
+        .iter()
+        .filter_map(|(_, dir)| {
+            fs::read_dir(dir.join(".fingerprint"))
+                .ok()?
+                .flatten()
+                .filter_map(|entry| last_used(&entry.path()))
+                .max()
+        })
+        .max();
+
+    CargoTarget {
+        path: target.to_path_buf(),
+        owner,
+        size: sizes.iter().map(|(_, size)| size).sum(),
+        profiles: sizes,
+        last_used,
+    }
+}
+
+/// Entries of the target's profiles (crate outputs, fingerprints, build
+/// script output, incremental caches) not used in `days` days, like
+/// `cargo sweep --time`
+pub fn stale_cargo_artifacts(target: &Path, days: u64) -> Vec<StaleArtifact> {
+    let cutoff = SystemTime::now()
+        .checked_sub(Duration::from_secs(days * 86400))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let candidates: Vec<PathBuf> = profile_dirs(target)
+        .into_iter()
+        .flat_map(|(_, profile)| SWEPT_DIRS.iter().map(move |dir| profile.join(dir)))
+        .flat_map(|dir| {
+            fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+        })
+        .collect();
+
+    candidates
+        .into_par_iter()
+        .filter(|path| last_used(path).is_some_and(|used| used < cutoff))
+        .map(|path| StaleArtifact {
+            size: tree_size(&path),
+            path,
+        })
+        .collect()
+}
+
+/// Table row for a target
+pub fn cargo_target_entry(target: &CargoTarget) -> CargoTargetEntry {
+    CargoTargetEntry {
+        path: target.path.display().to_string(),
+        workspace: match &target.owner {
+            TargetOwner::Workspace(root) => root.display().to_string(),
+            TargetOwner::Elsewhere(_) => "not used by cargo".to_string(),
+            TargetOwner::Unverified => "unverified".to_string(),
+        },
+        profiles: target
+            .profiles
+            .iter()
+            .map(|(name, size)| format!("{} {}", name, format_size(*size)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        size: format_size(target.size),
+        last_used: match target
+            .last_used
+            .and_then(|used| SystemTime::now().duration_since(used).ok())
+            .map(|age| age.as_secs() / 86400)
+        {
+            Some(0) => "today".to_string(),
+            Some(days) => format!("{}d ago", days),
+            None => "-".to_string(),
+        },
+    }
+}
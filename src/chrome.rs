@@ -0,0 +1,74 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use md5::{Digest, Md5};
+
+/// Roots covered by Chrome's bookmarks checksum, in the order Chrome hashes them
+const CHECKSUM_ROOTS: &[&str] = &["bookmark_bar", "other", "synced"];
+
+/// Compute the `checksum` Chrome stores in its Bookmarks file
+///
+/// Chrome hashes every node of the three roots depth-first with MD5: the ID,
+/// the title as UTF-16LE, then `url` and the URL for bookmarks or `folder` for
+/// folders. A file whose stored checksum doesn't match gets its IDs reassigned
+/// on the next start, which breaks sync, so every rewrite must update it.
+pub fn bookmarks_checksum(json: &serde_json::Value) -> String {
+    let mut md5 = Md5::new();
+
+    for root in CHECKSUM_ROOTS {
+        if let Some(node) = json.get("roots").and_then(|r| r.get(root)) {
+            hash_node(&mut md5, node);
+        }
+    }
+
+    format!("{:x}", md5.finalize())
+}
+
+fn hash_node(md5: &mut Md5, node: &serde_json::Value) {
+    let field = |key: &str| node.get(key).and_then(|v| v.as_str()).unwrap_or("");
+
+    md5.update(field("id").as_bytes());
+    let title: Vec<u8> = field("name")
+        .encode_utf16()
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect();
+    md5.update(title);
+
+    if field("type") == "url" {
+        md5.update(b"url");
+        md5.update(field("url").as_bytes());
+        return;
+    }
+
+    md5.update(b"folder");
+    for child in node
+        .get("children")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+    {
+        hash_node(md5, child);
+    }
+}
+
+/// Write a modified Bookmarks file with an up-to-date checksum
+///
+/// The file is written to a temporary sibling and renamed over the original,
+/// so Chrome never sees a half-written file.
+pub fn write_bookmarks_file(path: &Path, json: &mut serde_json::Value) -> io::Result<()> {
+    let checksum = bookmarks_checksum(json);
+    if let Some(obj) = json.as_object_mut() {
+        obj.insert("checksum".to_string(), serde_json::Value::String(checksum));
+    }
+
+    let content = serde_json::to_string_pretty(json)?;
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
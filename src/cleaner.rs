@@ -1,4 +1,3 @@
-use anyhow::{Context, Result};
 use colored::Colorize;
 use crossterm::{
     cursor,
@@ -6,29 +5,686 @@ use crossterm::{
     execute,
     terminal::{self, ClearType},
 };
+use ignore::overrides::OverrideBuilder;
+use ignore::{WalkBuilder, WalkState};
+use indicatif::MultiProgress;
 use rayon::prelude::*;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::{Write, stdout};
+use std::io::{self, Write, stdout};
+use std::num::ParseFloatError;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tabled::Tabled;
+use thiserror::Error;
+
+use crate::{
+    Action, ActionError, ActionRunner, CargoTarget, Config, ConfigError, PACKAGE_CACHE_NAMES,
+    PackageCache, RestoreError, Severity, XcodeItem, XcodeKind, browse_disk_usage, count_bar,
+    deleting_path, error_chain, expand_home, home_dir, inspect_cargo_target, item_spinner,
+    locked_restore_command, manifest_entry, scan_disk_usage, stale_cargo_artifacts,
+    warn_incomplete_runs, write_clean_manifest,
+};
+
+type Result<T, E = CleanError> = std::result::Result<T, E>;
+
+/// Errors from finding and cleaning build-artifact directories
+///
+/// A directory the clean action fails on is recoverable: it's reported in the
+/// results and the others are still cleaned, and the run then ends with
+/// `Failed` so scripts see the exit code. Bad input, journal and terminal
+/// failures are fatal.
+#[derive(Debug, Error)]
+pub enum CleanError {
+    #[error("Invalid size: {input}")]
+    InvalidSize {
+        input: String,
+        #[source]
+        source: ParseFloatError,
+    },
+    #[error("Invalid size unit in '{0}' (use B, K, M, G or T)")]
+    InvalidSizeUnit(String),
+    #[error("Invalid clean pattern '{0}': use a directory name or node, js, rust, python, all")]
+    InvalidPattern(String),
+    #[error("Unknown clean profile '{name}' (defined: {available})")]
+    UnknownProfile { name: String, available: String },
+    #[error("Unknown package cache '{name}' (use {available} or all)")]
+    UnknownCache { name: String, available: String },
+    #[error("{failed} of {total} directories couldn't be cleaned")]
+    Failed { failed: usize, total: usize },
+    #[error("Invalid exclusion '{0}'")]
+    InvalidExclude(String),
+    #[error("Invalid [clean] config: {0}")]
+    InvalidConfig(String),
+    #[error("Failed to read {}", path.display())]
+    IgnoreFile {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Action(#[from] ActionError),
+    #[error(transparent)]
+    Restore(#[from] RestoreError),
+    #[error(transparent)]
+    Terminal(#[from] io::Error),
+}
+
+impl CleanError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            CleanError::Action(e) => e.severity(),
+            CleanError::Config(e) => e.severity(),
+            CleanError::Restore(e) => e.severity(),
+            CleanError::Failed { .. } => Severity::Recoverable,
+            _ => Severity::Fatal,
+        }
+    }
+}
+
+/// Journal kind used for clean runs
+pub const CLEAN_JOURNAL_KIND: &str = "clean";
+
+/// What must hold for a directory with a known artifact name to be cleaned
+enum Guard {
+    /// Always an artifact
+    Always,
+    /// One of these files sits next to the directory (the project it belongs to)
+    Sibling(&'static [&'static str]),
+    /// This file is inside the directory
+    Contains(&'static str),
+}
+
+/// A build-artifact directory `clean` knows about
+struct KnownArtifact {
+    name: &'static str,
+    ecosystems: &'static [&'static str],
+    guard: Guard,
+}
+
+const JS_PROJECT: &[&str] = &["package.json"];
+const BUILD_PROJECT: &[&str] = &["package.json", "pyproject.toml", "setup.py"];
+
+/// Known artifact directories by ecosystem
+///
+/// Names like `target`, `dist` and `build` are common enough to hold source
+/// or data, so they only count next to their project's manifest.
+const KNOWN_ARTIFACTS: &[KnownArtifact] = &[
+    KnownArtifact {
+        name: "node_modules",
+        ecosystems: &["node"],
+        guard: Guard::Always,
+    },
+    KnownArtifact {
+        name: ".next",
+        ecosystems: &["node"],
+        guard: Guard::Sibling(JS_PROJECT),
+    },
+    KnownArtifact {
+        name: ".nuxt",
+        ecosystems: &["node"],
+        guard: Guard::Sibling(JS_PROJECT),
+    },
+    KnownArtifact {
+        name: ".turbo",
+        ecosystems: &["node"],
+        guard: Guard::Sibling(JS_PROJECT),
+    },
+    KnownArtifact {
+        name: ".parcel-cache",
+        ecosystems: &["node"],
+        guard: Guard::Sibling(JS_PROJECT),
+    },
+    KnownArtifact {
+        name: "target",
+        ecosystems: &["rust"],
+        guard: Guard::Sibling(&["Cargo.toml"]),
+    },
+    KnownArtifact {
+        name: ".venv",
+        ecosystems: &["python"],
+        guard: Guard::Contains("pyvenv.cfg"),
+    },
+    KnownArtifact {
+        name: "venv",
+        ecosystems: &["python"],
+        guard: Guard::Contains("pyvenv.cfg"),
+    },
+    KnownArtifact {
+        name: "__pycache__",
+        ecosystems: &["python"],
+        guard: Guard::Always,
+    },
+    KnownArtifact {
+        name: ".pytest_cache",
+        ecosystems: &["python"],
+        guard: Guard::Always,
+    },
+    KnownArtifact {
+        name: ".mypy_cache",
+        ecosystems: &["python"],
+        guard: Guard::Always,
+    },
+    KnownArtifact {
+        name: ".tox",
+        ecosystems: &["python"],
+        guard: Guard::Sibling(&["tox.ini", "pyproject.toml", "setup.py"]),
+    },
+    KnownArtifact {
+        name: "dist",
+        ecosystems: &["node", "python"],
+        guard: Guard::Sibling(BUILD_PROJECT),
+    },
+    KnownArtifact {
+        name: "build",
+        ecosystems: &["node", "python"],
+        guard: Guard::Sibling(BUILD_PROJECT),
+    },
+];
+
+/// Projects committed to within this many days count as active
+pub const DEFAULT_ACTIVE_DAYS: u64 = 14;
+
+/// File in a project that keeps its artifacts from being cleaned
+pub const DEFAULT_KEEP_MARKER: &str = ".keep-node-modules";
+
+/// Directories never searched for artifacts
+const SKIPPED_DIRS: &[&str] = &[".git", ".cache", ".Trash"];
+
+/// Which directory names `clean` targets
+///
+/// Each entry is a directory name, or an ecosystem (`node`/`js`, `rust`,
+/// `python`, `all`) that expands to its known artifacts. Known names are only
+/// cleaned where their guard holds; other names are taken as given.
+#[derive(Debug, Clone)]
+pub struct CleanPatterns {
+    names: Vec<String>,
+}
+
+impl Default for CleanPatterns {
+    fn default() -> Self {
+        Self {
+            names: vec!["node_modules".to_string()],
+        }
+    }
+}
+
+impl CleanPatterns {
+    pub fn new(specs: &[String]) -> Result<Self> {
+        let mut names: Vec<String> = Vec::new();
+        let mut add = |name: &str| {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        };
+
+        for spec in specs {
+            let spec = spec.trim();
+            let ecosystem = match spec.to_lowercase().as_str() {
+                "node" | "js" => Some("node"),
+                "rust" => Some("rust"),
+                "python" => Some("python"),
+                "all" => Some(""),
+                _ => None,
+            };
+
+            match ecosystem {
+                Some(ecosystem) => KNOWN_ARTIFACTS
+                    .iter()
+                    .filter(|a| ecosystem.is_empty() || a.ecosystems.contains(&ecosystem))
+                    .for_each(|a| add(a.name)),
+                None if spec.is_empty()
+                    || matches!(spec, "." | "..")
+                    || spec.contains(['/', '\\'])
+                    || SKIPPED_DIRS.contains(&spec) =>
+                {
+                    return Err(CleanError::InvalidPattern(spec.to_string()));
+                }
+                None => add(spec),
+            }
+        }
+
+        if names.is_empty() {
+            return Ok(Self::default());
+        }
+        Ok(Self { names })
+    }
+
+    /// The directory names, for messages
+    pub fn label(&self) -> String {
+        self.names.join(", ")
+    }
+
+    /// Whether `dir` is an artifact directory to clean
+    fn matches(&self, dir: &Path) -> bool {
+        let Some(name) = dir.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        if !self.names.iter().any(|n| n == name) {
+            return false;
+        }
+
+        match KNOWN_ARTIFACTS
+            .iter()
+            .find(|a| a.name == name)
+            .map(|a| &a.guard)
+        {
+            None | Some(Guard::Always) => true,
+            Some(Guard::Sibling(markers)) => dir
+                .parent()
+                .is_some_and(|parent| markers.iter().any(|m| parent.join(m).is_file())),
+            Some(Guard::Contains(file)) => dir.join(file).is_file(),
+        }
+    }
+}
+
+/// Where `clean` looks and what it leaves alone
+///
+/// Read from `[clean]` in the config; a profile (`[clean.profiles.<name>]`)
+/// overrides whichever of `roots`, `patterns`, `exclude`, `max_depth`, `prune`,
+/// `gitignore`, `follow_symlinks`, `active_days`, `keep_marker`,
+/// `remove_empty`, `safe`, `background_delete` and `action` it sets:
+///
+/// ```toml
+/// [clean.profiles.weekly]
+/// roots = ["~/code", "~/work"]
+/// patterns = ["node", "rust", "python"]
+/// exclude = ["~/code/keep-builds", "vendor"]
+/// action = "compress"
+/// ```
+///
+/// Exclusions are globs (`*`, `?`, `**`). Ones with a `/` (or `~`) match
+/// paths, and the whole subtree of a match is skipped; bare ones match the
+/// name of any directory. A `.cleanerignore` at a root adds one exclusion per
+/// line, with relative paths anchored at that root.
+#[derive(Debug, Clone)]
+pub struct CleanScope {
+    pub roots: Vec<PathBuf>,
+    pub patterns: CleanPatterns,
+    exclusions: Vec<Exclusion>,
+    /// How many levels below a root artifacts are looked for; unlimited if `None`
+    pub max_depth: Option<usize>,
+    /// Once a directory holds an artifact, skip searching its other subdirectories
+    pub prune: bool,
+    /// Skip directories ignored by git (`.gitignore`, `.git/info/exclude`, the global excludes)
+    pub gitignore: bool,
+    /// Walk into symlinked directories and clean what symlinked artifacts
+    /// point to; otherwise links are never followed and symlinked artifacts
+    /// are only reported
+    pub follow_symlinks: bool,
+    /// A project with a commit in this many days (or uncommitted changes) is active
+    pub active_days: u64,
+    /// Clean active projects too instead of skipping them
+    pub include_active: bool,
+    /// File that, in a project directory, protects its artifacts from cleaning
+    pub keep_marker: String,
+    /// After cleaning, remove directories the cleaned projects are left with
+    /// that are empty
+    pub remove_empty: bool,
+    /// Only clean dependency directories with a lockfile next to them, which
+    /// reinstalling brings back exactly (see `locked_restore_command`)
+    pub safe: bool,
+    /// Delete by renaming each directory aside and removing it on background
+    /// threads (see `ActionRunner::delete_in_background`)
+    pub background_delete: bool,
+    /// Clean action spec from the profile or `[clean]`, if either sets one
+    pub action: Option<String>,
+    /// Selected profile name
+    pub profile: Option<String>,
+}
+
+impl CleanScope {
+    /// The `[clean]` scope, overlaid with `profile` when one is given
+    pub fn load(profile: Option<&str>) -> Result<Self> {
+        Self::from_config(&Config::load()?, profile)
+    }
+
+    pub fn from_config(config: &Config, profile: Option<&str>) -> Result<Self> {
+        let base = config.get("clean").and_then(|v| v.as_table());
+        let overlay = match profile {
+            None => None,
+            Some(name) => Some(
+                config
+                    .get("clean.profiles")
+                    .and_then(|v| v.as_table())
+                    .and_then(|profiles| profiles.get(name))
+                    .and_then(|v| v.as_table())
+                    .ok_or_else(|| CleanError::UnknownProfile {
+                        name: name.to_string(),
+                        available: match clean_profile_names(config).join(", ") {
+                            names if names.is_empty() => "none".to_string(),
+                            names => names,
+                        },
+                    })?,
+            ),
+        };
+
+        // The profile's value when it sets the key, else the [clean] one
+        let lookup = |key: &str| {
+            overlay
+                .and_then(|t| t.get(key))
+                .or_else(|| base.and_then(|t| t.get(key)))
+        };
+        let list = |key: &str| -> Result<Vec<String>> {
+            match lookup(key) {
+                None => Ok(Vec::new()),
+                Some(value) => value
+                    .as_array()
+                    .and_then(|items| {
+                        items
+                            .iter()
+                            .map(|item| item.as_str().map(str::to_string))
+                            .collect::<Option<Vec<_>>>()
+                    })
+                    .ok_or_else(|| {
+                        CleanError::InvalidConfig(format!("{} must be a list of strings", key))
+                    }),
+            }
+        };
+
+        let flag = |key: &str| -> Result<bool> {
+            match lookup(key) {
+                None => Ok(false),
+                Some(value) => value.as_bool().ok_or_else(|| {
+                    CleanError::InvalidConfig(format!(
+                        "{} must be true or false, got {}",
+                        key, value
+                    ))
+                }),
+            }
+        };
+
+        let mut roots = list("roots")?
+            .iter()
+            .map(|root| expand_home(root))
+            .collect::<Result<Vec<_>, _>>()?;
+        if roots.is_empty()
+            && let Some(path) = lookup("path").and_then(|v| v.as_str())
+        {
+            roots.push(expand_home(path)?);
+        }
+
+        let mut scope = Self {
+            roots,
+            patterns: CleanPatterns::new(&list("patterns")?)?,
+            exclusions: Vec::new(),
+            max_depth: match lookup("max_depth") {
+                None => None,
+                Some(value) => Some(
+                    value
+                        .as_integer()
+                        .and_then(|depth| usize::try_from(depth).ok())
+                        .ok_or_else(|| {
+                            CleanError::InvalidConfig(format!(
+                                "max_depth must be a non-negative number, got {}",
+                                value
+                            ))
+                        })?,
+                ),
+            },
+            prune: flag("prune")?,
+            gitignore: flag("gitignore")?,
+            follow_symlinks: flag("follow_symlinks")?,
+            active_days: match lookup("active_days") {
+                None => DEFAULT_ACTIVE_DAYS,
+                Some(value) => value
+                    .as_integer()
+                    .and_then(|days| u64::try_from(days).ok())
+                    .ok_or_else(|| {
+                        CleanError::InvalidConfig(format!(
+                            "active_days must be a whole number of days, got {}",
+                            value
+                        ))
+                    })?,
+            },
+            include_active: false,
+            keep_marker: lookup("keep_marker")
+                .and_then(|v| v.as_str())
+                .unwrap_or(DEFAULT_KEEP_MARKER)
+                .to_string(),
+            remove_empty: flag("remove_empty")?,
+            safe: flag("safe")?,
+            background_delete: flag("background_delete")?,
+            action: lookup("action")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            profile: profile.map(str::to_string),
+        };
+        for exclusion in list("exclude")? {
+            scope.exclude(&exclusion)?;
+        }
+
+        Ok(scope)
+    }
+
+    /// Add an exclusion glob, as given to `--exclude` or in `exclude`
+    ///
+    /// Relative path globs match at any depth, e.g. `work/critical-app/**`
+    /// skips that project under every root.
+    pub fn exclude(&mut self, glob: &str) -> Result<()> {
+        self.exclusions.push(Exclusion::new(glob, None)?);
+        Ok(())
+    }
+
+    /// This scope plus the exclusions in `root`'s `.cleanerignore`, if it has one
+    fn with_ignore_file(&self, root: &Path) -> Result<Self> {
+        let path = root.join(CLEAN_IGNORE_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(self.clone()),
+            Err(source) => return Err(CleanError::IgnoreFile { path, source }),
+        };
+
+        let anchor = std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf());
+        let mut scope = self.clone();
+        for line in content.lines().map(str::trim) {
+            if !line.is_empty() && !line.starts_with('#') {
+                scope.exclusions.push(Exclusion::new(line, Some(&anchor))?);
+            }
+        }
+        Ok(scope)
+    }
+
+    /// Roots to search; the current directory when none are configured
+    pub fn search_roots(&self) -> Vec<PathBuf> {
+        if self.roots.is_empty() {
+            vec![std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))]
+        } else {
+            self.roots.clone()
+        }
+    }
+
+    /// Deepest directory containing every root, used as the run's root
+    ///
+    /// `archive` keeps paths relative to it, so artifacts from different
+    /// roots can't collide.
+    fn common_root(&self) -> PathBuf {
+        let roots = self.search_roots();
+        let mut common = roots[0].clone();
+        for root in &roots[1..] {
+            while !root.starts_with(&common) {
+                if !common.pop() {
+                    break;
+                }
+            }
+        }
+        common
+    }
+
+    /// Whether `dir`'s project (its parent) has the keep marker
+    pub fn is_kept(&self, dir: &Path) -> bool {
+        dir.parent()
+            .is_some_and(|project| project.join(&self.keep_marker).exists())
+    }
+
+    fn is_excluded(&self, dir: &Path) -> bool {
+        if self.exclusions.is_empty() {
+            return false;
+        }
+
+        self.exclusions
+            .iter()
+            .any(|exclusion| exclusion.matches(dir))
+    }
+
+    /// Count and total size of `dirs` under each root, in root order; a
+    /// directory under overlapping roots counts for the deepest one
+    fn root_subtotals<'a>(
+        &self,
+        dirs: impl IntoIterator<Item = (&'a Path, u64)>,
+    ) -> Vec<(PathBuf, usize, u64)> {
+        let mut subtotals: Vec<(PathBuf, usize, u64)> = self
+            .search_roots()
+            .into_iter()
+            .map(|root| (root, 0, 0))
+            .collect();
+        for (dir, size) in dirs {
+            if let Some((_, count, total)) = subtotals
+                .iter_mut()
+                .filter(|(root, _, _)| dir.starts_with(root))
+                .max_by_key(|(root, _, _)| root.components().count())
+            {
+                *count += 1;
+                *total += size;
+            }
+        }
+        subtotals
+    }
+
+    /// Roots for messages
+    fn roots_label(&self) -> String {
+        self.search_roots()
+            .iter()
+            .map(|r| r.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// File at a clean root listing exclusion globs, one per line
+pub const CLEAN_IGNORE_FILE: &str = ".cleanerignore";
+
+/// An exclusion glob, compiled
+#[derive(Debug, Clone)]
+pub(crate) struct Exclusion {
+    regex: Regex,
+    /// Matched against the absolute path rather than the directory name
+    full_path: bool,
+}
+
+impl Exclusion {
+    /// Compile `glob`; relative path globs are anchored at `anchor` when
+    /// given, else they match at any depth
+    pub(crate) fn new(glob: &str, anchor: Option<&Path>) -> Result<Self> {
+        let glob = glob.trim().trim_end_matches('/');
+        if glob.is_empty() {
+            return Err(CleanError::InvalidExclude(glob.to_string()));
+        }
+
+        if !glob.contains('/') && !glob.starts_with('~') {
+            return Ok(Self {
+                regex: glob_regex("^", glob)?,
+                full_path: false,
+            });
+        }
+
+        let regex = if glob.starts_with('~') || glob.starts_with('/') {
+            glob_regex("^", &expand_home(glob)?.to_string_lossy())?
+        } else if let Some(anchor) = anchor {
+            glob_regex("^", &anchor.join(glob).to_string_lossy())?
+        } else {
+            glob_regex("(?:^|/)", glob)?
+        };
+        Ok(Self {
+            regex,
+            full_path: true,
+        })
+    }
+
+    /// Whether `path` matches: its name for bare globs, else its absolute path
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        if !self.full_path {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            return self.regex.is_match(name);
+        }
+        let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        self.regex.is_match(&absolute.to_string_lossy())
+    }
+}
+
+/// Regex for a whole-string glob match: `*` and `?` stay within one path
+/// component, `**` spans any number, and a trailing `/**` also matches the
+/// directory itself
+fn glob_regex(prefix: &str, glob: &str) -> Result<Regex> {
+    let (body, subtree) = match glob.strip_suffix("/**") {
+        Some(body) => (body, true),
+        None => (glob, false),
+    };
+
+    let mut pattern = prefix.to_string();
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    pattern.push_str("(?:.*/)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            c => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    if subtree {
+        pattern.push_str("(?:/.*)?");
+    }
+    pattern.push('$');
+
+    Regex::new(&pattern).map_err(|_| CleanError::InvalidExclude(glob.to_string()))
+}
+
+/// Names of the profiles defined under `[clean.profiles]`, sorted
+pub fn clean_profile_names(config: &Config) -> Vec<String> {
+    let mut names: Vec<String> = config
+        .get("clean.profiles")
+        .and_then(|v| v.as_table())
+        .map(|profiles| profiles.keys().cloned().collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
 
 #[derive(Clone)]
-pub struct NodeModuleEntry {
+pub struct ArtifactEntry {
     pub path: PathBuf,
+    /// Bytes cleaning it frees (see `DiskUsage::on_disk`)
     pub size: u64,
+    /// Sum of its file lengths
+    pub apparent: u64,
     pub selected: bool,
+    /// Its project has the keep marker; never cleaned
+    pub kept: bool,
     pub status: CleanStatus,
 }
 
 #[derive(Clone, PartialEq)]
 pub enum CleanStatus {
     Found,
-    Deleting,
-    Deleted,
+    /// The action is running, e.g. "Compressing"
+    Working(&'static str),
+    /// Finished, with the action's status
+    Done(String),
     Error(String),
 }
 
@@ -36,8 +692,8 @@ impl std::fmt::Display for CleanStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CleanStatus::Found => write!(f, "Found"),
-            CleanStatus::Deleting => write!(f, "Deleting..."),
-            CleanStatus::Deleted => write!(f, "✓ Deleted"),
+            CleanStatus::Working(verb) => write!(f, "{}...", verb),
+            CleanStatus::Done(status) => write!(f, "{}", status),
             CleanStatus::Error(e) => write!(f, "✗ {}", e),
         }
     }
@@ -51,54 +707,1122 @@ pub struct CleanedEntry {
     pub size: String,
     #[tabled(rename = "Status")]
     pub status: String,
+    /// `size` in bytes
+    #[tabled(skip)]
+    pub bytes: u64,
+    /// Sum of file lengths, when it differs from the bytes on disk
+    #[tabled(skip)]
+    pub apparent: u64,
+    /// Why the directory couldn't be cleaned, if it couldn't
+    #[tabled(skip)]
+    pub error: Option<String>,
 }
 
-/// Recursively find all node_modules directories
-fn find_node_modules(root: &Path, verbose: bool) -> Vec<PathBuf> {
-    let mut results = Vec::new();
-    find_node_modules_recursive(root, &mut results, verbose);
+impl CleanedEntry {
+    pub fn new(path: String, size: u64, status: &CleanStatus) -> Self {
+        Self {
+            path,
+            size: format_size(size),
+            bytes: size,
+            apparent: size,
+            status: status.to_string(),
+            error: match status {
+                CleanStatus::Error(e) => Some(e.clone()),
+                _ => None,
+            },
+        }
+    }
+
+    /// Set the apparent size of a directory measured with `disk_usage`
+    pub fn with_apparent(mut self, apparent: u64) -> Self {
+        self.apparent = apparent;
+        self
+    }
+}
+
+/// Row of the failures table printed after a clean run
+#[derive(Tabled, Clone)]
+pub struct CleanFailureEntry {
+    #[tabled(rename = "Path")]
+    pub path: String,
+    #[tabled(rename = "Error")]
+    pub error: String,
+}
+
+/// The results that failed, for the failures table
+pub fn clean_failures(results: &[CleanedEntry]) -> Vec<CleanFailureEntry> {
     results
+        .iter()
+        .filter_map(|result| {
+            Some(CleanFailureEntry {
+                path: result.path.clone(),
+                error: result.error.clone()?,
+            })
+        })
+        .collect()
 }
 
-fn find_node_modules_recursive(dir: &Path, results: &mut Vec<PathBuf>, verbose: bool) {
-    if !dir.is_dir() {
-        return;
+/// Row of the clean results grouped by project; the project and its total
+/// are only on its first row
+#[derive(Tabled, Clone)]
+pub struct ProjectCleanedEntry {
+    #[tabled(rename = "Project")]
+    pub project: String,
+    #[tabled(rename = "Directory")]
+    pub directory: String,
+    #[tabled(rename = "Apparent")]
+    pub apparent: String,
+    #[tabled(rename = "On disk")]
+    pub size: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+/// Files that make a directory a project, for grouping its artifacts
+const PROJECT_MANIFESTS: &[&str] = &["package.json", "Cargo.toml", "pyproject.toml", "setup.py"];
+
+/// The project `dir` belongs to: its nearest ancestor with a manifest
+/// (`package.json`, `Cargo.toml`, ...), else its parent
+pub fn project_root(dir: &Path) -> PathBuf {
+    let parent = dir.parent().unwrap_or(dir);
+    parent
+        .ancestors()
+        .find(|ancestor| {
+            PROJECT_MANIFESTS
+                .iter()
+                .any(|manifest| ancestor.join(manifest).is_file())
+        })
+        .unwrap_or(parent)
+        .to_path_buf()
+}
+
+/// Indices of `paths` grouped by project, the largest project first and
+/// the largest directory first within each
+fn group_by_project(paths: &[(&Path, u64)]) -> Vec<(PathBuf, Vec<usize>, u64)> {
+    let mut groups: Vec<(PathBuf, Vec<usize>, u64)> = Vec::new();
+    for (i, (path, size)) in paths.iter().enumerate() {
+        let project = project_root(path);
+        match groups.iter_mut().find(|(p, _, _)| *p == project) {
+            Some((_, members, total)) => {
+                members.push(i);
+                *total += size;
+            }
+            None => groups.push((project, vec![i], *size)),
+        }
+    }
+    for (_, members, _) in &mut groups {
+        members.sort_by_key(|&i| std::cmp::Reverse(paths[i].1));
+    }
+    groups.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+    groups
+}
+
+/// Group clean results under their projects, with a total per project
+pub fn group_cleaned_by_project(results: &[CleanedEntry]) -> Vec<ProjectCleanedEntry> {
+    let paths: Vec<PathBuf> = results.iter().map(|r| PathBuf::from(&r.path)).collect();
+    let keyed: Vec<(&Path, u64)> = paths
+        .iter()
+        .map(PathBuf::as_path)
+        .zip(results.iter().map(|r| r.bytes))
+        .collect();
+
+    let mut rows = Vec::new();
+    for (project, members, total) in group_by_project(&keyed) {
+        for (n, &i) in members.iter().enumerate() {
+            let directory = paths[i]
+                .strip_prefix(&project)
+                .unwrap_or(&paths[i])
+                .display()
+                .to_string();
+            rows.push(ProjectCleanedEntry {
+                project: if n == 0 {
+                    format!(
+                        "{}\n{} in {}",
+                        project.display(),
+                        format_size(total),
+                        members.len()
+                    )
+                } else {
+                    String::new()
+                },
+                directory,
+                apparent: format_size(results[i].apparent),
+                size: results[i].size.clone(),
+                status: results[i].status.clone(),
+            });
+        }
+    }
+    rows
+}
+
+/// Status of a kept directory in dry-run results
+const KEPT_STATUS: &str = "🔒 Kept";
+
+/// The directories `results` cleaned (or would on a dry run)
+fn cleaned_dirs(results: &[CleanedEntry]) -> impl Iterator<Item = PathBuf> {
+    results
+        .iter()
+        .filter(|r| r.error.is_none() && r.status != KEPT_STATUS)
+        .map(|r| PathBuf::from(&r.path))
+}
+
+/// The command reinstalling each project's cleaned dependencies, as `cd
+/// <project> && <command>` lines, for the `--safe` summary
+pub fn reinstall_commands(results: &[CleanedEntry]) -> Vec<String> {
+    let mut commands: Vec<String> = cleaned_dirs(results)
+        .filter_map(|dir| {
+            let command = locked_restore_command(&dir)?;
+            let project = dir.parent()?.display().to_string();
+            let project =
+                if project.contains(|c: char| c.is_whitespace() || "'\"$&;|()".contains(c)) {
+                    format!("'{}'", project.replace('\'', "'\\''"))
+                } else {
+                    project
+                };
+            Some(format!("cd {} && {}", project, command))
+        })
+        .collect();
+    commands.sort();
+    commands.dedup();
+    commands
+}
+
+/// Remove the directories left empty in the projects of `results`
+///
+/// A directory counts as empty when all it holds are empty directories and
+/// cleaned artifacts, so a dry run lists what a real run would remove; only
+/// the outermost one of an empty subtree is listed. Project roots, `.git`,
+/// excluded directories and other artifacts are left alone, and since
+/// removal goes through `remove_dir` nothing that has gained a file is
+/// deleted. Empty directories aren't journaled.
+pub fn remove_empty_dirs(
+    scope: &CleanScope,
+    results: &[CleanedEntry],
+    dry_run: bool,
+) -> Vec<CleanedEntry> {
+    let cleaned: HashSet<PathBuf> = cleaned_dirs(results).collect();
+    let mut projects: Vec<PathBuf> = cleaned.iter().map(|dir| project_root(dir)).collect();
+    projects.sort();
+    // A project nested in another is searched with it
+    projects.dedup_by(|inner, outer| inner.starts_with(outer));
+
+    let mut empty = Vec::new();
+    for project in &projects {
+        for entry in fs::read_dir(project).into_iter().flatten().flatten() {
+            collect_empty_dirs(scope, &cleaned, &entry.path(), &mut empty);
+        }
+    }
+
+    empty
+        .into_iter()
+        .map(|dir| {
+            let status = if dry_run {
+                CleanStatus::Done("Would remove (empty)".to_string())
+            } else {
+                match remove_empty_tree(&dir) {
+                    Ok(()) => CleanStatus::Done("✓ Removed (empty)".to_string()),
+                    Err(e) => CleanStatus::Error(format!(
+                        "Failed to remove empty directory {}: {}",
+                        dir.display(),
+                        e
+                    )),
+                }
+            };
+            CleanedEntry::new(dir.display().to_string(), 0, &status)
+        })
+        .collect()
+}
+
+/// Whether `path` is empty once the cleaned artifacts are gone (a cleaned
+/// artifact is, but isn't listed); an empty directory replaces whatever of
+/// its subdirectories `empty` got
+fn collect_empty_dirs(
+    scope: &CleanScope,
+    cleaned: &HashSet<PathBuf>,
+    path: &Path,
+    empty: &mut Vec<PathBuf>,
+) -> bool {
+    if cleaned.contains(path) {
+        return true;
+    }
+    let is_dir = fs::symlink_metadata(path).is_ok_and(|m| m.is_dir());
+    if !is_dir
+        || path.file_name().is_some_and(|name| name == ".git")
+        || scope.is_excluded(path)
+        || scope.patterns.matches(path)
+    {
+        return false;
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return false;
+    };
+
+    let listed = empty.len();
+    let mut all_empty = true;
+    for entry in entries.flatten() {
+        all_empty &= collect_empty_dirs(scope, cleaned, &entry.path(), empty);
+    }
+    if all_empty {
+        empty.truncate(listed);
+        empty.push(path.to_path_buf());
+    }
+    all_empty
+}
+
+/// Remove an empty directory and the empty directories in it
+fn remove_empty_tree(dir: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            remove_empty_tree(&entry.path())?;
+        }
+    }
+    fs::remove_dir(dir)
+}
+
+/// Find the Rust `target` directories in `scope` and inspect each, largest first
+///
+/// Each is checked with `cargo metadata` (see `inspect_cargo_target`); the
+/// scope's patterns are ignored.
+pub fn find_cargo_targets(scope: &CleanScope, verbose: bool) -> Result<Vec<CargoTarget>> {
+    let mut scope = scope.clone();
+    scope.patterns = CleanPatterns::new(&["rust".to_string()])?;
+
+    println!(
+        "{} Searching for Cargo target directories in: {}",
+        "🔍".cyan(),
+        scope.roots_label().yellow()
+    );
+    let dirs = find_artifact_dirs(&scope, verbose)?;
+
+    let mut targets: Vec<CargoTarget> = dirs
+        .par_iter()
+        .map(|dir| inspect_cargo_target(dir))
+        .collect();
+    targets.sort_by_key(|t| std::cmp::Reverse(t.size));
+    Ok(targets)
+}
+
+/// Remove build output not used in `days` days from every target in `scope`
+///
+/// Like `cargo sweep --time`: only entries of the profiles' `deps`, `build`,
+/// `.fingerprint`, `incremental` and `examples` go, so recent builds stay
+/// warm. Removals are journaled deletes.
+pub fn sweep_cargo_targets(
+    scope: &CleanScope,
+    days: u64,
+    dry_run: bool,
+    verbose: bool,
+) -> Result<Vec<CleanedEntry>> {
+    let targets = find_cargo_targets(scope, verbose)?;
+    if targets.is_empty() {
+        println!("{}", "No Cargo target directories found.".yellow());
+        return Ok(Vec::new());
+    }
+
+    if !dry_run {
+        warn_incomplete_runs(CLEAN_JOURNAL_KIND);
+    }
+    let runner = ActionRunner::begin(
+        CLEAN_JOURNAL_KIND,
+        Action::Delete,
+        &scope.common_root(),
+        dry_run,
+    )?;
+
+    let mut results = Vec::new();
+    let mut total_freed = 0;
+    for target in &targets {
+        let stale = stale_cargo_artifacts(&target.path, days);
+        let statuses = stale
+            .par_iter()
+            .map(|artifact| apply_action(&runner, &artifact.path))
+            .collect::<Result<Vec<_>>>()?;
+
+        let failed = statuses
+            .iter()
+            .filter(|status| matches!(status, CleanStatus::Error(_)))
+            .count();
+        let freed: u64 = stale
+            .iter()
+            .zip(&statuses)
+            .filter(|(_, status)| !matches!(status, CleanStatus::Error(_)))
+            .map(|(artifact, _)| artifact.size)
+            .sum();
+        total_freed += freed;
+
+        let status = match (stale.len(), failed) {
+            (0, _) => "Nothing stale".to_string(),
+            (count, _) if dry_run => format!("Would sweep {}", count),
+            (count, 0) => format!("✓ Swept {}", count),
+            (count, failed) => format!("✓ Swept {}, {} errors", count - failed, failed),
+        };
+        let error = stale
+            .iter()
+            .zip(&statuses)
+            .find_map(|(artifact, status)| match status {
+                CleanStatus::Error(e) => Some(format!(
+                    "{} of {} entries failed, e.g. {}: {}",
+                    failed,
+                    stale.len(),
+                    artifact.path.display(),
+                    e
+                )),
+                _ => None,
+            });
+        results.push(CleanedEntry {
+            path: target.path.display().to_string(),
+            size: format_size(freed),
+            bytes: freed,
+            apparent: freed,
+            status,
+            error,
+        });
+    }
+
+    runner.commit()?;
+
+    println!(
+        "\n{} {} {} of build output unused for {} days (entries of deps, build, .fingerprint, incremental, examples)",
+        "💾".green(),
+        if dry_run { "Would sweep" } else { "Swept" },
+        format_size(total_freed).bold(),
+        days
+    );
+
+    Ok(results)
+}
+
+/// The caches named in `names` (`all` for every one), out of those found
+///
+/// A known cache that isn't on this machine is only mentioned.
+pub fn pick_package_caches(found: &[PackageCache], names: &[String]) -> Result<Vec<PackageCache>> {
+    let mut picked: Vec<PackageCache> = Vec::new();
+    for name in names {
+        let name = name.trim().to_lowercase();
+        if name == "all" {
+            return Ok(found.to_vec());
+        }
+        if !PACKAGE_CACHE_NAMES.contains(&name.as_str()) {
+            return Err(CleanError::UnknownCache {
+                name,
+                available: PACKAGE_CACHE_NAMES.join(", "),
+            });
+        }
+        match found.iter().find(|cache| cache.name == name) {
+            Some(cache) if !picked.iter().any(|p| p.name == cache.name) => {
+                picked.push(cache.clone())
+            }
+            Some(_) => {}
+            None => println!("{} No {} cache found, skipping", "ℹ️".cyan(), name),
+        }
+    }
+    Ok(picked)
+}
+
+/// Clear package-manager caches with `action` (journaled), one result row
+/// per cache directory
+pub fn clear_package_caches(
+    caches: &[PackageCache],
+    action: Action,
+    dry_run: bool,
+) -> Result<Vec<CleanedEntry>> {
+    if !dry_run {
+        warn_incomplete_runs(CLEAN_JOURNAL_KIND);
+    }
+    let runner = ActionRunner::begin(CLEAN_JOURNAL_KIND, action, &home_dir()?, dry_run)?;
+
+    let mut results = Vec::new();
+    let mut total_freed = 0;
+    for cache in caches {
+        for path in &cache.paths {
+            let size = calculate_dir_size(path);
+            let status = apply_action(&runner, path)?;
+            if !matches!(status, CleanStatus::Error(_)) {
+                total_freed += size;
+            }
+            results.push(CleanedEntry::new(path.display().to_string(), size, &status));
+        }
+    }
+
+    runner.commit()?;
+
+    println!(
+        "\n{} {} {} of package caches; they fill up again on the next install",
+        "💾".green(),
+        if dry_run { "Would free" } else { "Freed" },
+        format_size(total_freed).bold()
+    );
+
+    Ok(results)
+}
+
+/// Clean Xcode items: directories with `action` (journaled), unavailable
+/// simulators with `xcrun simctl delete` so Xcode forgets them too
+pub fn clean_xcode_items(
+    items: &[XcodeItem],
+    action: Action,
+    dry_run: bool,
+) -> Result<Vec<CleanedEntry>> {
+    if !dry_run {
+        warn_incomplete_runs(CLEAN_JOURNAL_KIND);
+    }
+    let runner = ActionRunner::begin(CLEAN_JOURNAL_KIND, action, &home_dir()?, dry_run)?;
+
+    let mut results = Vec::new();
+    let mut total_freed = 0;
+    for item in items {
+        let status = match &item.kind {
+            XcodeKind::Simulator { .. } if dry_run => {
+                CleanStatus::Done("Would delete (simctl)".to_string())
+            }
+            XcodeKind::Simulator { udid } => {
+                match Command::new("xcrun")
+                    .args(["simctl", "delete", udid])
+                    .output()
+                {
+                    Ok(output) if output.status.success() => {
+                        CleanStatus::Done("✓ Deleted (simctl)".to_string())
+                    }
+                    Ok(output) => CleanStatus::Error(
+                        String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                    ),
+                    Err(e) => CleanStatus::Error(format!("xcrun: {}", e)),
+                }
+            }
+            _ => apply_action(&runner, &item.path)?,
+        };
+        if !matches!(status, CleanStatus::Error(_)) {
+            total_freed += item.size;
+        }
+        results.push(CleanedEntry::new(
+            format!("{} {}", item.kind.label(), item.name),
+            item.size,
+            &status,
+        ));
+    }
+
+    runner.commit()?;
+
+    println!(
+        "\n{} {} {} of Xcode data",
+        "💾".green(),
+        if dry_run { "Would free" } else { "Freed" },
+        format_size(total_freed).bold()
+    );
+
+    Ok(results)
+}
+
+/// Row of the top-offenders report: one project and its artifact directories
+#[derive(Tabled, Clone)]
+pub struct ProjectReportEntry {
+    #[tabled(rename = "Project")]
+    pub project: String,
+    #[tabled(rename = "Ecosystem")]
+    pub ecosystem: String,
+    #[tabled(rename = "Artifacts")]
+    pub artifacts: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+}
+
+/// Row of the report's per-ecosystem totals
+#[derive(Tabled, Clone)]
+pub struct EcosystemReportEntry {
+    #[tabled(rename = "Ecosystem")]
+    pub ecosystem: String,
+    #[tabled(rename = "Projects")]
+    pub projects: usize,
+    #[tabled(rename = "Directories")]
+    pub directories: usize,
+    #[tabled(rename = "Size")]
+    pub size: String,
+}
+
+/// Ecosystem an artifact directory belongs to, `other` for custom names
+///
+/// `dist` and `build` are told apart by the project's manifest.
+fn artifact_ecosystem(path: &Path) -> &'static str {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let Some(known) = KNOWN_ARTIFACTS.iter().find(|a| a.name == name) else {
+        return "other";
+    };
+
+    let project = path.parent().unwrap_or(path);
+    known
+        .ecosystems
+        .iter()
+        .find(|ecosystem| match **ecosystem {
+            "node" => project.join("package.json").is_file(),
+            "python" => {
+                project.join("pyproject.toml").is_file() || project.join("setup.py").is_file()
+            }
+            _ => true,
+        })
+        .or(known.ecosystems.first())
+        .copied()
+        .unwrap_or("other")
+}
+
+/// Report the largest artifact directories without cleaning anything
+///
+/// Directories are grouped by project (their parent directory); returns the
+/// `top` largest projects and the totals per ecosystem over all of them.
+pub fn artifact_report(
+    scope: &CleanScope,
+    top: usize,
+    verbose: bool,
+) -> Result<(Vec<ProjectReportEntry>, Vec<EcosystemReportEntry>)> {
+    let entries = list_artifact_dirs(scope, verbose)?;
+
+    struct Project {
+        path: PathBuf,
+        ecosystems: Vec<&'static str>,
+        artifacts: Vec<(String, u64)>,
+        size: u64,
+    }
+    struct Ecosystem {
+        name: &'static str,
+        projects: Vec<PathBuf>,
+        directories: usize,
+        size: u64,
+    }
+
+    let mut projects: Vec<Project> = Vec::new();
+    let mut ecosystems: Vec<Ecosystem> = Vec::new();
+    for entry in &entries {
+        let path = entry.path.parent().unwrap_or(&entry.path).to_path_buf();
+        let name = entry
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let ecosystem = artifact_ecosystem(&entry.path);
+
+        let project = match projects.iter().position(|p| p.path == path) {
+            Some(i) => &mut projects[i],
+            None => {
+                projects.push(Project {
+                    path: path.clone(),
+                    ecosystems: Vec::new(),
+                    artifacts: Vec::new(),
+                    size: 0,
+                });
+                projects.last_mut().expect("project was just added")
+            }
+        };
+        if !project.ecosystems.contains(&ecosystem) {
+            project.ecosystems.push(ecosystem);
+        }
+        project.artifacts.push((name, entry.size));
+        project.size += entry.size;
+
+        let totals = match ecosystems.iter().position(|e| e.name == ecosystem) {
+            Some(i) => &mut ecosystems[i],
+            None => {
+                ecosystems.push(Ecosystem {
+                    name: ecosystem,
+                    projects: Vec::new(),
+                    directories: 0,
+                    size: 0,
+                });
+                ecosystems.last_mut().expect("ecosystem was just added")
+            }
+        };
+        if !totals.projects.contains(&path) {
+            totals.projects.push(path);
+        }
+        totals.directories += 1;
+        totals.size += entry.size;
+    }
+
+    projects.sort_by_key(|p| std::cmp::Reverse(p.size));
+    ecosystems.sort_by_key(|e| std::cmp::Reverse(e.size));
+
+    let project_rows = projects
+        .into_iter()
+        .take(top)
+        .map(|mut project| {
+            project
+                .artifacts
+                .sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+            ProjectReportEntry {
+                project: project.path.display().to_string(),
+                ecosystem: project.ecosystems.join(", "),
+                artifacts: project
+                    .artifacts
+                    .iter()
+                    .map(|(name, size)| format!("{} {}", name, format_size(*size)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                size: format_size(project.size),
+            }
+        })
+        .collect();
+
+    let ecosystem_rows = ecosystems
+        .into_iter()
+        .map(|e| EcosystemReportEntry {
+            ecosystem: e.name.to_string(),
+            projects: e.projects.len(),
+            directories: e.directories,
+            size: format_size(e.size),
+        })
+        .collect();
+
+    Ok((project_rows, ecosystem_rows))
+}
+
+/// Find all artifact directories in `scope`, walking each root in parallel
+///
+/// Matches aren't searched further, so nested artifacts (a `dist` inside
+/// `node_modules`) are cleaned along with their parent. Overlapping roots
+/// report each directory once. `max_depth` bounds how deep artifacts are
+/// looked for (1 is a root's direct children), and with `prune` a project
+/// whose artifact was found isn't searched any further, which skips the
+/// source trees of large monorepos. With `gitignore`, directories ignored by
+/// git aren't searched either; artifacts themselves are still found even
+/// though they're almost always ignored.
+///
+/// Symlinks aren't followed unless `follow_symlinks` is set: a symlinked
+/// artifact is then only reported. Following them, loops are detected and
+/// reported, and a symlinked artifact stands for its target (see
+/// `resolve_symlinked_artifacts`).
+fn find_artifact_dirs(scope: &CleanScope, verbose: bool) -> Result<Vec<PathBuf>> {
+    let walked = Mutex::new(Walked::default());
+
+    for root in scope.search_roots() {
+        if !root.is_dir() {
+            continue;
+        }
+        let scope = scope.with_ignore_file(&root)?;
+
+        // Artifact names are whitelisted so .gitignore can't hide them; a
+        // whitelist also drops every file from the walk, which is all dirs
+        let mut overrides = OverrideBuilder::new(&root);
+        for name in &scope.patterns.names {
+            overrides
+                .add(name)
+                .map_err(|_| CleanError::InvalidPattern(name.clone()))?;
+        }
+        let overrides = overrides
+            .build()
+            .map_err(|_| CleanError::InvalidPattern(scope.patterns.label()))?;
+
+        WalkBuilder::new(&root)
+            .standard_filters(false)
+            .git_ignore(scope.gitignore)
+            .git_global(scope.gitignore)
+            .git_exclude(scope.gitignore)
+            .parents(scope.gitignore)
+            .overrides(overrides)
+            .max_depth(scope.max_depth)
+            .follow_links(scope.follow_symlinks)
+            .threads(rayon::current_num_threads())
+            .build_parallel()
+            .run(|| {
+                let scope = &scope;
+                let walked = &walked;
+                Box::new(move |entry| {
+                    let entry = match entry {
+                        Ok(entry) => entry,
+                        Err(e) => {
+                            if let Some(path) = symlink_loop(&e)
+                                && let Ok(mut walked) = walked.lock()
+                            {
+                                walked.loops.push(path.to_path_buf());
+                            }
+                            return WalkState::Continue;
+                        }
+                    };
+                    let path = entry.path();
+                    // Unfollowed links come through as links; only artifacts matter
+                    if entry.path_is_symlink() && !scope.follow_symlinks {
+                        if entry.depth() > 0
+                            && path.is_dir()
+                            && scope.patterns.matches(path)
+                            && !scope.is_excluded(path)
+                        {
+                            record_artifact(walked, scope, path.to_path_buf());
+                        }
+                        return WalkState::Continue;
+                    }
+                    if !entry.file_type().is_some_and(|t| t.is_dir()) {
+                        return WalkState::Continue;
+                    }
+                    visit_dir(path, entry.depth(), scope, walked, verbose)
+                })
+            });
+    }
+
+    let walked = walked.into_inner().unwrap_or_default();
+    for path in &walked.loops {
+        println!(
+            "{} Symlink loop at {}, not followed",
+            "⚠️".yellow(),
+            path.display()
+        );
+    }
+    if !walked.skipped_links.is_empty() {
+        println!(
+            "{} Skipped {} symlinked directories (--follow-symlinks cleans what they point to):",
+            "⚠️".yellow(),
+            walked.skipped_links.len()
+        );
+        for link in &walked.skipped_links {
+            println!(
+                "   {} → {}",
+                link.display(),
+                fs::read_link(link)
+                    .map(|target| target.display().to_string())
+                    .unwrap_or_default()
+                    .dimmed()
+            );
+        }
+    }
+
+    let mut results = walked.dirs;
+    if scope.follow_symlinks {
+        results = resolve_symlinked_artifacts(scope, results);
+    }
+    results.sort();
+    results.dedup();
+    Ok(results)
+}
+
+/// What the artifact walk found
+#[derive(Default)]
+struct Walked {
+    dirs: Vec<PathBuf>,
+    /// Artifacts that are symlinks, left alone because links aren't followed
+    skipped_links: Vec<PathBuf>,
+    /// Where the walk found a link back to one of its own ancestors
+    loops: Vec<PathBuf>,
+}
+
+/// Record an artifact the walk found; when it's a symlink and links aren't
+/// followed, it's only reported
+fn record_artifact(walked: &Mutex<Walked>, scope: &CleanScope, path: PathBuf) {
+    if let Ok(mut walked) = walked.lock() {
+        if path.is_symlink() && !scope.follow_symlinks {
+            walked.skipped_links.push(path);
+        } else {
+            walked.dirs.push(path);
+        }
+    }
+}
+
+/// The path of a symlink loop the walker refused to follow, if that's what `err` is
+fn symlink_loop(err: &ignore::Error) -> Option<&Path> {
+    match err {
+        ignore::Error::Loop { child, .. } => Some(child),
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => symlink_loop(err),
+        ignore::Error::Partial(errs) => errs.iter().find_map(symlink_loop),
+        _ => None,
+    }
+}
+
+/// With links followed, clean what a symlinked artifact points to rather
+/// than the link, once however many links lead to it
+///
+/// A target outside every root gets a warning, since cleaning it reaches
+/// beyond what was asked to be scanned; an excluded target is left alone.
+fn resolve_symlinked_artifacts(scope: &CleanScope, dirs: Vec<PathBuf>) -> Vec<PathBuf> {
+    let roots: Vec<PathBuf> = scope
+        .search_roots()
+        .iter()
+        .filter_map(|root| fs::canonicalize(root).ok())
+        .collect();
+
+    // Directories reached without a link go first, so they're the ones kept
+    let mut dirs: Vec<(PathBuf, PathBuf)> = dirs
+        .into_iter()
+        .filter_map(|dir| Some((fs::canonicalize(&dir).ok()?, dir)))
+        .collect();
+    dirs.sort_by_key(|(real, dir)| (real != dir, dir.clone()));
+
+    let mut seen = HashSet::new();
+    let mut resolved = Vec::new();
+    for (real, dir) in dirs {
+        if !seen.insert(real.clone()) {
+            continue;
+        }
+        if !roots.iter().any(|root| real.starts_with(root)) {
+            println!(
+                "{} {} resolves to {}, outside the scan roots",
+                "⚠️".yellow(),
+                dir.display(),
+                real.display().to_string().yellow()
+            );
+        }
+        if !dir.is_symlink() {
+            resolved.push(dir);
+        } else if scope.is_excluded(&real) {
+            println!(
+                "{} {} points to excluded {}, skipping",
+                "⚠️".yellow(),
+                dir.display(),
+                real.display()
+            );
+        } else {
+            resolved.push(real);
+        }
     }
+    resolved
+}
+
+/// Decide whether the walk goes into `dir`, recording it if it's an artifact
+///
+/// `depth` is how far `dir` is below its root.
+fn visit_dir(
+    dir: &Path,
+    depth: usize,
+    scope: &CleanScope,
+    walked: &Mutex<Walked>,
+    verbose: bool,
+) -> WalkState {
+    let record = |path: PathBuf| record_artifact(walked, scope, path);
 
+    if scope.is_excluded(dir) {
+        return WalkState::Skip;
+    }
+    if depth > 0 && scope.patterns.matches(dir) {
+        record(dir.to_path_buf());
+        return WalkState::Skip;
+    }
+
+    // A `target` that isn't a Cargo build dir is still not worth searching
     let dir_name = dir.file_name().and_then(|n| n.to_str()).unwrap_or("");
-    if matches!(dir_name, ".git" | "target" | ".cache" | ".Trash") {
-        return;
+    if SKIPPED_DIRS.contains(&dir_name) || dir_name == "target" {
+        return WalkState::Skip;
     }
 
     if verbose {
         println!("{} Scanning: {}", "🔍".dimmed(), dir.display());
     }
 
-    let entries = match fs::read_dir(dir) {
-        Ok(entries) => entries,
-        Err(_) => return,
+    // Look for the project's own artifacts before walking into it
+    if scope.prune && scope.max_depth.is_none_or(|max| depth < max) {
+        let found: Vec<PathBuf> = scope
+            .patterns
+            .names
+            .iter()
+            .map(|name| dir.join(name))
+            .filter(|path| {
+                path.is_dir() && !scope.is_excluded(path) && scope.patterns.matches(path)
+            })
+            .collect();
+        if !found.is_empty() {
+            found.into_iter().for_each(record);
+            return WalkState::Skip;
+        }
+    }
+
+    WalkState::Continue
+}
+
+/// Why the project at `dir` counts as active, if it does
+///
+/// Asks git about the project's own subtree: changes to tracked files, or a
+/// commit within `active_days`. Directories outside a repository (or without
+/// git installed) are never active.
+fn project_activity(dir: &Path, active_days: u64) -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
     };
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.is_dir() {
-            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-            if name == "node_modules" {
-                results.push(path);
-            } else {
-                find_node_modules_recursive(&path, results, verbose);
+    let changes = git(&["status", "--porcelain", "--untracked-files=no", "--", "."])?;
+    if !changes.is_empty() {
+        return Some("uncommitted changes".to_string());
+    }
+
+    let committed: u64 = git(&["log", "-1", "--format=%ct", "--", "."])?
+        .parse()
+        .ok()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let days = now.saturating_sub(committed) / 86400;
+    (days < active_days).then(|| match days {
+        0 => "committed today".to_string(),
+        1 => "committed yesterday".to_string(),
+        days => format!("committed {} days ago", days),
+    })
+}
+
+/// Drop artifacts of active projects (see `project_activity`) unless
+/// `include_active` is set, listing the active ones either way
+fn skip_active_projects(scope: &CleanScope, dirs: Vec<PathBuf>) -> Vec<PathBuf> {
+    let activity: Vec<Option<String>> = dirs
+        .par_iter()
+        .map(|dir| project_activity(dir.parent().unwrap_or(dir), scope.active_days))
+        .collect();
+    let active = activity.iter().flatten().count();
+    if active == 0 {
+        return dirs;
+    }
+
+    if scope.include_active {
+        println!(
+            "{} Including {} directories of active projects (uncommitted changes or commits in the last {} days):",
+            "⚠️".yellow(),
+            active.to_string().yellow(),
+            scope.active_days
+        );
+    } else {
+        println!(
+            "{} Skipping {} directories of active projects (uncommitted changes or commits in the last {} days; --include-active cleans them too):",
+            "⏭️".yellow(),
+            active.to_string().yellow(),
+            scope.active_days
+        );
+    }
+
+    dirs.into_iter()
+        .zip(activity)
+        .filter_map(|(dir, reason)| match reason {
+            None => Some(dir),
+            Some(reason) => {
+                println!("   {} ({})", dir.display(), reason.dimmed());
+                scope.include_active.then_some(dir)
             }
+        })
+        .collect()
+}
+
+/// With `--safe`, drop artifacts there's no lockfile to reinstall from,
+/// listing them
+fn skip_unlocked_projects(scope: &CleanScope, dirs: Vec<PathBuf>) -> Vec<PathBuf> {
+    if !scope.safe {
+        return dirs;
+    }
+    let (dirs, unlocked): (Vec<PathBuf>, Vec<PathBuf>) = dirs
+        .into_iter()
+        .partition(|dir| locked_restore_command(dir).is_some());
+    if !unlocked.is_empty() {
+        println!(
+            "{} Skipping {} directories without a lockfile to reinstall from (--safe):",
+            "🛡️".cyan(),
+            unlocked.len().to_string().yellow()
+        );
+        for dir in &unlocked {
+            println!("   {}", dir.display());
+        }
+    }
+    dirs
+}
+
+/// Drop artifacts whose project has the keep marker, listing them
+fn skip_kept_projects(scope: &CleanScope, dirs: Vec<PathBuf>) -> Vec<PathBuf> {
+    let (kept, dirs): (Vec<PathBuf>, Vec<PathBuf>) =
+        dirs.into_iter().partition(|dir| scope.is_kept(dir));
+    if !kept.is_empty() {
+        println!(
+            "{} Keeping {} directories whose project has a {} file:",
+            "🔒".cyan(),
+            kept.len().to_string().yellow(),
+            scope.keep_marker.cyan()
+        );
+        for dir in &kept {
+            println!("   {}", dir.display());
+        }
+    }
+    dirs
+}
+
+/// Sizes of a directory tree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// Sum of file lengths, what `du --apparent-size` reports
+    pub apparent: u64,
+    /// Blocks allocated to the tree, each hard-linked file once and only if
+    /// all its links are inside (pnpm's store keeps the others alive): what
+    /// deleting the tree frees
+    pub on_disk: u64,
+}
+
+/// Running totals of a tree walk; files with more than one link wait in
+/// `linked` (by device and inode: links, links seen, bytes) until it's known
+/// whether all of them were seen
+#[derive(Default)]
+struct UsageWalk {
+    usage: DiskUsage,
+    linked: HashMap<(u64, u64), (u64, u64, u64)>,
+}
+
+impl UsageWalk {
+    #[cfg(unix)]
+    fn add(&mut self, metadata: &fs::Metadata) {
+        use std::os::unix::fs::MetadataExt;
+
+        if !metadata.is_dir() {
+            self.usage.apparent += metadata.len();
+        }
+        let bytes = metadata.blocks() * 512;
+        if metadata.is_dir() || metadata.nlink() <= 1 {
+            self.usage.on_disk += bytes;
+            return;
+        }
+        let (_, seen, _) = self
+            .linked
+            .entry((metadata.dev(), metadata.ino()))
+            .or_insert((metadata.nlink(), 0, bytes));
+        *seen += 1;
+    }
+
+    #[cfg(not(unix))]
+    fn add(&mut self, metadata: &fs::Metadata) {
+        if !metadata.is_dir() {
+            self.usage.apparent += metadata.len();
+            self.usage.on_disk += metadata.len();
+        }
+    }
+
+    fn merge(mut self, other: UsageWalk) -> Self {
+        self.usage.apparent += other.usage.apparent;
+        self.usage.on_disk += other.usage.on_disk;
+        for (key, (links, seen, bytes)) in other.linked {
+            self.linked.entry(key).or_insert((links, 0, bytes)).1 += seen;
+        }
+        self
+    }
+
+    fn finish(self) -> DiskUsage {
+        let shared: u64 = self
+            .linked
+            .values()
+            .filter(|(links, seen, _)| seen >= links)
+            .map(|(_, _, bytes)| bytes)
+            .sum();
+        DiskUsage {
+            apparent: self.usage.apparent,
+            on_disk: self.usage.on_disk + shared,
         }
     }
 }
 
-/// Calculate directory size recursively using parallel traversal
-fn calculate_dir_size(path: &Path) -> u64 {
-    if !path.is_dir() {
-        return path.metadata().map(|m| m.len()).unwrap_or(0);
+/// Measure a directory tree, in parallel over its top-level entries
+pub(crate) fn disk_usage(path: &Path) -> DiskUsage {
+    let mut walk = UsageWalk::default();
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return walk.usage;
+    };
+    walk.add(&metadata);
+    if !metadata.is_dir() {
+        return walk.finish();
     }
 
-    // Use parallel iteration for top-level entries
     let entries: Vec<_> = fs::read_dir(path)
         .map(|iter| iter.flatten().collect())
         .unwrap_or_default();
@@ -106,33 +1830,32 @@ fn calculate_dir_size(path: &Path) -> u64 {
     entries
         .par_iter()
         .map(|entry| {
-            let path = entry.path();
-            if path.is_dir() {
-                calculate_dir_size_recursive(&path)
-            } else {
-                path.metadata().map(|m| m.len()).unwrap_or(0)
-            }
+            let mut walk = UsageWalk::default();
+            add_entry_usage(&mut walk, entry);
+            walk
         })
-        .sum()
+        .reduce(UsageWalk::default, UsageWalk::merge)
+        .merge(walk)
+        .finish()
 }
 
-/// Non-parallel recursive helper (parallel at top level is enough)
-fn calculate_dir_size_recursive(path: &Path) -> u64 {
-    fs::read_dir(path)
-        .map(|entries| {
-            entries
-                .flatten()
-                .map(|entry| {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        calculate_dir_size_recursive(&path)
-                    } else {
-                        path.metadata().map(|m| m.len()).unwrap_or(0)
-                    }
-                })
-                .sum()
-        })
-        .unwrap_or(0)
+/// Non-parallel recursive helper (parallel at top level is enough); links
+/// inside count with their own size, so a link loop can't recurse forever
+fn add_entry_usage(walk: &mut UsageWalk, entry: &fs::DirEntry) {
+    let Ok(metadata) = entry.metadata() else {
+        return;
+    };
+    walk.add(&metadata);
+    if metadata.is_dir() {
+        for child in fs::read_dir(entry.path()).into_iter().flatten().flatten() {
+            add_entry_usage(walk, &child);
+        }
+    }
+}
+
+/// Bytes deleting `path` frees (see `DiskUsage::on_disk`)
+pub(crate) fn calculate_dir_size(path: &Path) -> u64 {
+    disk_usage(path).on_disk
 }
 
 /// Format bytes into human-readable string
@@ -152,128 +1875,270 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
-/// Remove a directory and all its contents
-fn remove_directory(path: &Path) -> Result<()> {
-    fs::remove_dir_all(path)
-        .with_context(|| format!("Failed to remove directory: {}", path.display()))
+/// Parse a human-readable size like `500M`, `2G`, `1.5GB` or `4096` into bytes
+///
+/// Units are binary (1K = 1024 bytes) to match `format_size`.
+pub fn parse_size(input: &str) -> Result<u64> {
+    let trimmed = input.trim();
+    let split = trimmed
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(split);
+
+    let value: f64 = number.parse().map_err(|source| CleanError::InvalidSize {
+        input: input.to_string(),
+        source,
+    })?;
+
+    let multiplier: u64 = match unit
+        .trim()
+        .to_uppercase()
+        .trim_end_matches("IB")
+        .trim_end_matches('B')
+    {
+        "" => 1,
+        "K" => 1024,
+        "M" => 1024 * 1024,
+        "G" => 1024 * 1024 * 1024,
+        "T" => 1024 * 1024 * 1024 * 1024,
+        _ => return Err(CleanError::InvalidSizeUnit(input.to_string())),
+    };
+
+    Ok((value * multiplier as f64) as u64)
+}
+
+/// Free space on the volume holding `path`, in bytes
+///
+/// Uses POSIX `df -Pk`, which is available on macOS and Linux alike.
+pub fn available_space(path: &Path) -> Option<u64> {
+    let output = Command::new("df").arg("-Pk").arg(path).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // Second line: Filesystem 1024-blocks Used Available Capacity Mounted-on
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let available_kb: u64 = stdout
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse()
+        .ok()?;
+    Some(available_kb * 1024)
+}
+
+/// Apply the clean action to one directory; recoverable failures become an
+/// error status, fatal ones abort the run
+fn apply_action(runner: &ActionRunner, path: &Path) -> Result<CleanStatus> {
+    match runner.apply(path, None) {
+        Ok(status) => Ok(CleanStatus::Done(status)),
+        Err(e) if e.severity() == Severity::Recoverable => Ok(CleanStatus::Error(error_chain(&e))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The clean runner for `scope`, deleting in the background if it asks to
+fn begin_clean(scope: &CleanScope, action: Action, dry_run: bool) -> Result<ActionRunner> {
+    let runner = ActionRunner::begin(CLEAN_JOURNAL_KIND, action, &scope.common_root(), dry_run)?;
+    Ok(if scope.background_delete {
+        runner.delete_in_background(rayon::current_num_threads())
+    } else {
+        runner
+    })
+}
+
+/// Wait for the directories `runner` is deleting in the background, turning
+/// the ones that couldn't be removed into failures in `results`, and report
+/// the throughput
+fn finish_background_deletes(runner: &mut ActionRunner, results: &mut [CleanedEntry]) {
+    if !runner.deletes_in_background() {
+        return;
+    }
+    println!(
+        "{} Directories are out of the way; removing them in the background...",
+        "🗑️".cyan()
+    );
+    let Some(report) = runner.finish_background() else {
+        return;
+    };
+
+    for (source, error) in &report.failed {
+        let source = source.display().to_string();
+        if let Some(result) = results.iter_mut().find(|r| r.path == source) {
+            let error = format!(
+                "{} (left at {})",
+                error,
+                deleting_path(Path::new(&source)).display()
+            );
+            result.status = CleanStatus::Error(error.clone()).to_string();
+            result.error = Some(error);
+        }
+    }
+
+    let freed: u64 = results
+        .iter()
+        .filter(|r| r.error.is_none())
+        .map(|r| r.bytes)
+        .sum();
+    let seconds = report.elapsed.as_secs_f64().max(0.001);
+    println!(
+        "{} Removed {} directories ({}) in {:.1}s, {}/s",
+        "⚡".green(),
+        report.deleted.to_string().bold(),
+        format_size(freed).bold(),
+        seconds,
+        format_size((freed as f64 / seconds) as u64).bold().yellow()
+    );
 }
 
-/// Scan and display node_modules without cleaning (list mode)
-pub fn list_node_modules(search_path: Option<&str>, verbose: bool) -> Result<Vec<NodeModuleEntry>> {
-    let root = search_path
-        .map(PathBuf::from)
-        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+/// Print how `dirs` split across the roots, when there are several
+fn print_root_subtotals<'a>(scope: &CleanScope, dirs: impl IntoIterator<Item = (&'a Path, u64)>) {
+    if scope.search_roots().len() < 2 {
+        return;
+    }
+    for (root, count, size) in scope.root_subtotals(dirs) {
+        println!(
+            "   {} in {} directories under {}",
+            format!("{:>10}", format_size(size)).yellow(),
+            count,
+            root.display().to_string().cyan()
+        );
+    }
+}
 
+/// Scan and display artifact directories without cleaning (list mode)
+pub fn list_artifact_dirs(scope: &CleanScope, verbose: bool) -> Result<Vec<ArtifactEntry>> {
     println!(
-        "{} Searching for node_modules in: {}",
+        "{} Searching for {} in: {}",
         "🔍".cyan(),
-        root.display().to_string().yellow()
+        scope.patterns.label().cyan(),
+        scope.roots_label().yellow()
     );
 
-    let node_modules_dirs = find_node_modules(&root, verbose);
+    let artifact_dirs = skip_unlocked_projects(
+        scope,
+        skip_active_projects(scope, find_artifact_dirs(scope, verbose)?),
+    );
 
-    if node_modules_dirs.is_empty() {
-        println!("{}", "No node_modules directories found.".yellow());
+    if artifact_dirs.is_empty() {
+        println!("{}", "No matching directories found.".yellow());
         return Ok(Vec::new());
     }
 
-    let total_dirs = node_modules_dirs.len();
+    let total_dirs = artifact_dirs.len();
     println!(
-        "{} Found {} node_modules directories. Calculating sizes in parallel...",
+        "{} Found {} directories. Calculating sizes in parallel...",
         "📦".cyan(),
         total_dirs.to_string().green()
     );
 
-    // Shared state for progress tracking
-    let completed = Arc::new(AtomicUsize::new(0));
-    let total_size_so_far = Arc::new(AtomicU64::new(0));
-    let current_path: Arc<Mutex<String>> = Arc::new(Mutex::new(String::new()));
-    let done = Arc::new(AtomicBool::new(false));
-
-    // Clones for progress thread
-    let completed_clone = Arc::clone(&completed);
-    let total_size_clone = Arc::clone(&total_size_so_far);
-    let current_path_clone = Arc::clone(&current_path);
-    let done_clone = Arc::clone(&done);
-
-    let progress_handle = thread::spawn(move || {
-        let mut stdout = stdout();
-        while !done_clone.load(Ordering::Relaxed) {
-            let count = completed_clone.load(Ordering::Relaxed);
-            let size = total_size_clone.load(Ordering::Relaxed);
-            let path = current_path_clone
-                .lock()
-                .map(|p| p.clone())
-                .unwrap_or_default();
-
-            // Truncate path for display
-            let display_path = if path.len() > 40 {
-                format!("...{}", &path[path.len() - 37..])
-            } else {
-                path
-            };
-
-            print!(
-                "\r{} [{}/{}] {} found | {}",
-                "⏳".cyan(),
-                count.to_string().green(),
-                total_dirs.to_string().cyan(),
-                format_size(size).yellow(),
-                display_path.dimmed()
-            );
-            // Clear rest of line
-            print!("{}", " ".repeat(20));
-            stdout.flush().ok();
-            thread::sleep(Duration::from_millis(50));
-        }
-        print!("\r{}\r", " ".repeat(100)); // Clear the line
-        stdout.flush().ok();
-    });
+    let bar = count_bar(total_dirs as u64);
+    let total_size_so_far = AtomicU64::new(0);
 
-    let entries: Vec<NodeModuleEntry> = node_modules_dirs
+    let entries: Vec<ArtifactEntry> = artifact_dirs
         .par_iter()
         .map(|path| {
-            // Update current path being processed
-            if let Ok(mut current) = current_path.lock() {
-                *current = path.display().to_string();
-            }
-
-            let size = calculate_dir_size(path);
-            completed.fetch_add(1, Ordering::Relaxed);
-            total_size_so_far.fetch_add(size, Ordering::Relaxed);
-
-            NodeModuleEntry {
+            let DiskUsage {
+                apparent,
+                on_disk: size,
+            } = disk_usage(path);
+            let found = total_size_so_far.fetch_add(size, Ordering::Relaxed) + size;
+            bar.set_message(format!(
+                "{} found | {}",
+                format_size(found).yellow(),
+                path.display().to_string().dimmed()
+            ));
+            bar.inc(1);
+
+            ArtifactEntry {
                 path: path.clone(),
                 size,
+                apparent,
                 selected: false,
+                kept: scope.is_kept(path),
                 status: CleanStatus::Found,
             }
         })
         .collect();
 
-    done.store(true, Ordering::Relaxed);
-    progress_handle.join().ok();
+    bar.finish_and_clear();
+
+    let kept: Vec<&ArtifactEntry> = entries.iter().filter(|e| e.kept).collect();
+    if !kept.is_empty() {
+        println!(
+            "{} Keeping {} directories whose project has a {} file:",
+            "🔒".cyan(),
+            kept.len().to_string().yellow(),
+            scope.keep_marker.cyan()
+        );
+        for entry in &kept {
+            println!(
+                "   {} ({})",
+                entry.path.display(),
+                format_size(entry.size).dimmed()
+            );
+        }
+    }
 
-    let total_size: u64 = entries.iter().map(|e| e.size).sum();
+    let total_size: u64 = entries.iter().filter(|e| !e.kept).map(|e| e.size).sum();
+    let total_apparent: u64 = entries.iter().filter(|e| !e.kept).map(|e| e.apparent).sum();
 
     println!(
-        "\n{} Total space that can be freed: {}",
+        "\n{} Total space that can be freed: {} ({} apparent size)",
         "💾".green(),
-        format_size(total_size).bold().yellow()
+        format_size(total_size).bold().yellow(),
+        format_size(total_apparent)
     );
 
+    // Per-pattern breakdown, largest first, when the run spans several kinds
+    let mut by_name: Vec<(String, usize, u64)> = Vec::new();
+    for entry in &entries {
+        let name = entry
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match by_name.iter_mut().find(|(n, _, _)| *n == name) {
+            Some((_, count, size)) => {
+                *count += 1;
+                *size += entry.size;
+            }
+            None => by_name.push((name, 1, entry.size)),
+        }
+    }
+    if by_name.len() > 1 {
+        by_name.sort_by_key(|(_, _, size)| std::cmp::Reverse(*size));
+        for (name, count, size) in by_name {
+            println!(
+                "   {} {} in {} directories",
+                format!("{:>10}", format_size(size)).yellow(),
+                name.cyan(),
+                count
+            );
+        }
+    }
+    print_root_subtotals(scope, entries.iter().map(|e| (e.path.as_path(), e.size)));
+
     Ok(entries)
 }
 
-/// Interactive mode - select and delete node_modules
-pub fn interactive_clean(search_path: Option<&str>, verbose: bool) -> Result<Vec<CleanedEntry>> {
-    let mut entries = list_node_modules(search_path, verbose)?;
+/// Interactive mode - select artifact directories and apply the clean action to them
+pub fn interactive_clean(
+    scope: &CleanScope,
+    runner: ActionRunner,
+    verbose: bool,
+) -> Result<Vec<CleanedEntry>> {
+    let mut entries = list_artifact_dirs(scope, verbose)?;
 
     if entries.is_empty() {
         return Ok(Vec::new());
     }
 
-    entries.sort_by(|a, b| b.size.cmp(&a.size));
+    entries.sort_by_key(|e| std::cmp::Reverse(e.size));
+
+    let free_space = available_space(&scope.search_roots()[0]);
+    let verb = runner.action().verb();
 
     println!("\n{}", "Interactive Mode".bold().cyan());
     println!("{}", "─".repeat(60).dimmed());
@@ -281,8 +2146,18 @@ pub fn interactive_clean(search_path: Option<&str>, verbose: bool) -> Result<Vec
     println!("  {}  Toggle selection", "Space".yellow());
     println!("  {}      Select all", "a".yellow());
     println!("  {}      Deselect all", "n".yellow());
-    println!("  {}  Delete selected", "Enter".yellow());
-    println!("  {}      Quit without deleting", "q".yellow());
+    println!("  {}      Invert selection", "i".yellow());
+    println!(
+        "  {}      Filter by path (a/n/i act on the matches)",
+        "/".yellow()
+    );
+    println!("  {}      Sort by size, path or age", "s".yellow());
+    println!("  {}  {} selected", "Enter".yellow(), verb);
+    println!(
+        "  {}      Quit without {}",
+        "q".yellow(),
+        runner.action().progressive().to_lowercase()
+    );
     println!("{}", "─".repeat(60).dimmed());
     println!("\nPress any key to continue...");
 
@@ -290,27 +2165,220 @@ pub fn interactive_clean(search_path: Option<&str>, verbose: bool) -> Result<Vec
     let _ = event::read();
     terminal::disable_raw_mode()?;
 
-    let selected_entries = run_interactive_selection(&mut entries)?;
+    let selected_entries = run_interactive_selection(scope, &mut entries, free_space, verb)?;
 
     if selected_entries.is_empty() {
-        println!("{}", "No directories selected for deletion.".yellow());
+        println!("{}", "No directories selected.".yellow());
+        return Ok(Vec::new());
+    }
+    print_root_subtotals(
+        scope,
+        selected_entries.iter().map(|e| (e.path.as_path(), e.size)),
+    );
+
+    record_manifest(
+        &runner,
+        selected_entries.iter().map(|e| (e.path.as_path(), e.size)),
+    )?;
+    apply_with_live_updates(selected_entries, runner)
+}
+
+/// Browse disk usage under the scope's root and clean the directories marked
+///
+/// Unlike `interactive_clean` this isn't limited to artifact patterns: any
+/// directory can be marked, except excluded ones. The run is journaled and
+/// gets a manifest like any other clean.
+pub fn browse_clean(
+    scope: &CleanScope,
+    action: Action,
+    dry_run: bool,
+) -> Result<Vec<CleanedEntry>> {
+    if action.needs_default_destination() {
+        return Err(ActionError::MissingDirectory("move").into());
+    }
+
+    let root = scope.common_root();
+    if !dry_run {
+        warn_incomplete_runs(CLEAN_JOURNAL_KIND);
+    }
+    let runner = begin_clean(scope, action, dry_run)?;
+
+    println!(
+        "{} Measuring disk usage in: {}",
+        "🔍".cyan(),
+        root.display().to_string().yellow()
+    );
+    let tree = scan_disk_usage(&root);
+    let free_space = available_space(&root);
+
+    let marked = browse_disk_usage(&tree, free_space, runner.action().verb(), |path| {
+        !scope.is_excluded(path)
+            && !scope.is_kept(path)
+            && (!scope.safe || locked_restore_command(path).is_some())
+    })?;
+    if marked.is_empty() {
+        println!("{}", "No directories marked.".yellow());
         return Ok(Vec::new());
     }
 
-    delete_with_live_updates(selected_entries)
+    if dry_run {
+        println!(
+            "{} Dry run mode - no directories will be changed",
+            "⚠️".yellow()
+        );
+        return marked
+            .iter()
+            .map(|(path, size)| {
+                Ok(CleanedEntry::new(
+                    path.display().to_string(),
+                    *size,
+                    &apply_action(&runner, path)?,
+                ))
+            })
+            .collect();
+    }
+
+    record_manifest(
+        &runner,
+        marked.iter().map(|(path, size)| (path.as_path(), *size)),
+    )?;
+    apply_with_live_updates(
+        marked
+            .into_iter()
+            .map(|(path, size)| ArtifactEntry {
+                path,
+                size,
+                apparent: size,
+                selected: true,
+                kept: false,
+                status: CleanStatus::Found,
+            })
+            .collect(),
+        runner,
+    )
+}
+
+/// Order of the interactive selector's projects and their directories
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SelectionSort {
+    Size,
+    Path,
+    /// Least recently modified first, the likeliest to be stale
+    Modified,
+}
+
+impl SelectionSort {
+    fn label(self) -> &'static str {
+        match self {
+            SelectionSort::Size => "size",
+            SelectionSort::Path => "path",
+            SelectionSort::Modified => "oldest",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            SelectionSort::Size => SelectionSort::Path,
+            SelectionSort::Path => SelectionSort::Modified,
+            SelectionSort::Modified => SelectionSort::Size,
+        }
+    }
+}
+
+/// The selector's rows: a project header (no entry) followed by its entries
+/// whose path contains `filter` (case-insensitive), in `sort` order
+///
+/// Projects without a matching entry are left out.
+fn selection_rows(
+    groups: &[(PathBuf, Vec<usize>, u64)],
+    entries: &[ArtifactEntry],
+    modified: &[Option<SystemTime>],
+    filter: &str,
+    sort: SelectionSort,
+) -> Vec<(usize, Option<usize>)> {
+    let filter = filter.to_lowercase();
+    let mut visible: Vec<(usize, Vec<usize>)> = groups
+        .iter()
+        .enumerate()
+        .map(|(g, (_, members, _))| {
+            let mut members: Vec<usize> = members
+                .iter()
+                .copied()
+                .filter(|&m| {
+                    entries[m]
+                        .path
+                        .to_string_lossy()
+                        .to_lowercase()
+                        .contains(&filter)
+                })
+                .collect();
+            match sort {
+                SelectionSort::Size => {}
+                SelectionSort::Path => {
+                    members.sort_by(|&a, &b| entries[a].path.cmp(&entries[b].path))
+                }
+                SelectionSort::Modified => members.sort_by_key(|&m| modified[m]),
+            }
+            (g, members)
+        })
+        .filter(|(_, members)| !members.is_empty())
+        .collect();
+    match sort {
+        SelectionSort::Size => {}
+        SelectionSort::Path => visible.sort_by(|a, b| groups[a.0].0.cmp(&groups[b.0].0)),
+        // A project is as recent as its most recently modified directory
+        SelectionSort::Modified => {
+            visible.sort_by_key(|(_, members)| members.iter().map(|&m| modified[m]).max().flatten())
+        }
+    }
+
+    visible
+        .into_iter()
+        .flat_map(|(g, members)| {
+            std::iter::once((g, None)).chain(members.into_iter().map(move |m| (g, Some(m))))
+        })
+        .collect()
 }
 
-fn run_interactive_selection(entries: &mut Vec<NodeModuleEntry>) -> Result<Vec<NodeModuleEntry>> {
+/// `free_space` is the volume's current free space, used to project the free
+/// space left after deleting the selected directories; `verb` names the clean
+/// action in the key help
+///
+/// Entries are listed under their projects (see `project_root`); toggling a
+/// project's row selects or deselects all of its directories. `/` filters
+/// the list by path as you type, `s` cycles the sort order and `a`, `n` and
+/// `i` (invert) act on the directories the filter leaves visible.
+fn run_interactive_selection(
+    scope: &CleanScope,
+    entries: &mut [ArtifactEntry],
+    free_space: Option<u64>,
+    verb: &str,
+) -> Result<Vec<ArtifactEntry>> {
     let mut cursor_pos = 0;
     let mut scroll_offset = 0;
 
+    let groups = group_by_project(
+        &entries
+            .iter()
+            .map(|e| (e.path.as_path(), e.size))
+            .collect::<Vec<_>>(),
+    );
+    let modified: Vec<Option<SystemTime>> = entries
+        .iter()
+        .map(|e| fs::metadata(&e.path).and_then(|m| m.modified()).ok())
+        .collect();
+    let mut filter = String::new();
+    let mut editing_filter = false;
+    let mut sort = SelectionSort::Size;
+    let mut rows = selection_rows(&groups, entries, &modified, &filter, sort);
+
     terminal::enable_raw_mode()?;
     let mut stdout = stdout();
     execute!(stdout, terminal::Clear(ClearType::All), cursor::Hide)?;
 
     loop {
         let (_, term_height) = terminal::size().unwrap_or((80, 24));
-        let visible_rows = (term_height as usize).saturating_sub(8);
+        let visible_rows = (term_height as usize).saturating_sub(10);
 
         if cursor_pos < scroll_offset {
             scroll_offset = cursor_pos;
@@ -331,7 +2399,7 @@ fn run_interactive_selection(entries: &mut Vec<NodeModuleEntry>) -> Result<Vec<N
         writeln!(
             stdout,
             "{}",
-            "🧹 Node Modules Cleaner - Interactive Mode".bold().cyan()
+            "🧹 Build Artifact Cleaner - Interactive Mode".bold().cyan()
         )?;
         writeln!(stdout, "{}", "─".repeat(80).dimmed())?;
         writeln!(
@@ -343,30 +2411,124 @@ fn run_interactive_selection(entries: &mut Vec<NodeModuleEntry>) -> Result<Vec<N
             format_size(selected_size).green(),
             format_size(selected_size).bold().yellow()
         )?;
+        if scope.search_roots().len() > 1 {
+            let selected_by_root = scope.root_subtotals(
+                entries
+                    .iter()
+                    .filter(|e| e.selected)
+                    .map(|e| (e.path.as_path(), e.size)),
+            );
+            let by_root: Vec<String> = scope
+                .root_subtotals(entries.iter().map(|e| (e.path.as_path(), e.size)))
+                .into_iter()
+                .zip(selected_by_root)
+                .map(|((root, _, total), (_, _, selected))| {
+                    format!(
+                        "{} {}/{}",
+                        root.display(),
+                        format_size(selected).green(),
+                        format_size(total).cyan()
+                    )
+                })
+                .collect();
+            writeln!(stdout, "Per root: {}", by_root.join("  |  "))?;
+        }
+        if let Some(free) = free_space {
+            writeln!(
+                stdout,
+                "Free space after deletion: {} (currently {})",
+                format_size(free + selected_size).bold().green(),
+                format_size(free).cyan()
+            )?;
+        }
+        let shown = rows.iter().filter(|(_, e)| e.is_some()).count();
+        if editing_filter {
+            writeln!(
+                stdout,
+                "{} {}▏  ({} shown, Enter:Done Esc:Clear)",
+                "Filter:".yellow(),
+                filter.bold(),
+                shown
+            )?;
+        } else if filter.is_empty() {
+            writeln!(stdout, "Sort: {}", sort.label().cyan())?;
+        } else {
+            writeln!(
+                stdout,
+                "Sort: {}  |  Filter: {} ({} shown)",
+                sort.label().cyan(),
+                filter.yellow(),
+                shown
+            )?;
+        }
         writeln!(stdout, "{}", "─".repeat(80).dimmed())?;
 
-        for (i, entry) in entries
+        for (i, &(g, entry)) in rows
             .iter()
             .enumerate()
             .skip(scroll_offset)
             .take(visible_rows)
         {
             let is_current = i == cursor_pos;
+            let (project, members, project_size) = &groups[g];
+
+            let Some(e) = entry else {
+                let selectable = members.iter().filter(|&&m| !entries[m].kept).count();
+                let selected = members.iter().filter(|&&m| entries[m].selected).count();
+                let checkbox = match selected {
+                    0 if selectable == 0 => "[-]",
+                    0 => "[ ]",
+                    n if n == selectable => "[✓]",
+                    _ => "[~]",
+                };
+                let path_str = project.display().to_string();
+                let display_path = if path_str.len() > 50 {
+                    format!("...{}", &path_str[path_str.len() - 47..])
+                } else {
+                    path_str
+                };
+                let line = format!(
+                    " {} {:>10} 📁 {} ({} dirs)",
+                    checkbox,
+                    format_size(*project_size),
+                    display_path,
+                    members.len()
+                );
+                if is_current {
+                    writeln!(stdout, "{}", line.on_blue().white())?;
+                } else {
+                    writeln!(stdout, "{}", line.bold())?;
+                }
+                continue;
+            };
+
+            let entry = &entries[e];
             let checkbox = if entry.selected { "[✓]" } else { "[ ]" };
             let size_str = format!("{:>10}", format_size(entry.size));
-            let path_str = entry.path.display().to_string();
-
-            let max_path_len = 55;
+            let path_str = entry
+                .path
+                .strip_prefix(project)
+                .unwrap_or(&entry.path)
+                .display()
+                .to_string();
+
+            let max_path_len = 50;
             let display_path = if path_str.len() > max_path_len {
                 format!("...{}", &path_str[path_str.len() - max_path_len + 3..])
             } else {
                 path_str
             };
 
-            let line = format!(" {} {} {}", checkbox, size_str, display_path);
+            let line = if entry.kept {
+                format!("     [-] {}   {} (kept)", size_str, display_path)
+            } else {
+                format!("     {} {}   {}", checkbox, size_str, display_path)
+            };
 
             if is_current {
                 writeln!(stdout, "{}", line.on_blue().white())?;
+            } else if entry.kept {
+                writeln!(stdout, "{}", line.dimmed())?;
             } else if entry.selected {
                 writeln!(stdout, "{}", line.green())?;
             } else {
@@ -378,70 +2540,124 @@ fn run_interactive_selection(entries: &mut Vec<NodeModuleEntry>) -> Result<Vec<N
         writeln!(stdout, "{}", "─".repeat(80).dimmed())?;
         writeln!(
             stdout,
-            "{}  {}  {}  {}  {}  {}",
+            "{}  {}  {}  {}  {}  {}  {}  {}  {}",
             "↑↓:Navigate".dimmed(),
-            "Space:Toggle".dimmed(),
+            "Space:Toggle (project: all)".dimmed(),
             "a:All".dimmed(),
             "n:None".dimmed(),
-            "Enter:Delete".dimmed(),
+            "i:Invert".dimmed(),
+            "/:Filter".dimmed(),
+            "s:Sort".dimmed(),
+            format!("Enter:{}", verb).dimmed(),
             "q:Quit".dimmed()
         )?;
 
         stdout.flush()?;
 
-        if let Event::Key(key_event) = event::read()? {
+        let Event::Key(key_event) = event::read()? else {
+            continue;
+        };
+        let visible: Vec<usize> = rows.iter().filter_map(|&(_, e)| e).collect();
+        if editing_filter {
             match key_event.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if cursor_pos > 0 {
-                        cursor_pos -= 1;
-                    }
-                }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if cursor_pos < entries.len() - 1 {
-                        cursor_pos += 1;
-                    }
-                }
-                KeyCode::Char(' ') => {
-                    entries[cursor_pos].selected = !entries[cursor_pos].selected;
+                KeyCode::Enter => editing_filter = false,
+                KeyCode::Esc => {
+                    filter.clear();
+                    editing_filter = false;
                 }
-                KeyCode::Char('a') => {
-                    for entry in entries.iter_mut() {
-                        entry.selected = true;
-                    }
-                }
-                KeyCode::Char('n') => {
+                KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
                     for entry in entries.iter_mut() {
                         entry.selected = false;
                     }
-                }
-                KeyCode::Enter => {
                     break;
                 }
-                KeyCode::Char('q') | KeyCode::Esc => {
-                    for entry in entries.iter_mut() {
-                        entry.selected = false;
-                    }
-                    break;
+                KeyCode::Backspace => {
+                    filter.pop();
                 }
-                KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
-                    for entry in entries.iter_mut() {
-                        entry.selected = false;
+                KeyCode::Char(c) => filter.push(c),
+                _ => continue,
+            }
+            rows = selection_rows(&groups, entries, &modified, &filter, sort);
+            cursor_pos = 0;
+            continue;
+        }
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                cursor_pos = cursor_pos.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if cursor_pos + 1 < rows.len() => {
+                cursor_pos += 1;
+            }
+            KeyCode::Char(' ') => match rows.get(cursor_pos) {
+                Some(&(_, Some(e))) if !entries[e].kept => {
+                    entries[e].selected = !entries[e].selected;
+                }
+                Some(&(g, None)) => {
+                    let members: Vec<usize> = rows
+                        .iter()
+                        .filter(|&&(group, _)| group == g)
+                        .filter_map(|&(_, e)| e)
+                        .filter(|&m| !entries[m].kept)
+                        .collect();
+                    let select = !members.iter().all(|&m| entries[m].selected);
+                    for m in members {
+                        entries[m].selected = select;
                     }
-                    break;
                 }
                 _ => {}
+            },
+            KeyCode::Char('a') => {
+                for m in visible {
+                    entries[m].selected = !entries[m].kept;
+                }
+            }
+            KeyCode::Char('n') => {
+                for m in visible {
+                    entries[m].selected = false;
+                }
+            }
+            KeyCode::Char('i') => {
+                for m in visible {
+                    entries[m].selected = !entries[m].selected && !entries[m].kept;
+                }
+            }
+            KeyCode::Char('/') => editing_filter = true,
+            KeyCode::Char('s') => {
+                sort = sort.next();
+                rows = selection_rows(&groups, entries, &modified, &filter, sort);
+                cursor_pos = 0;
+            }
+            KeyCode::Enter => {
+                break;
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                for entry in entries.iter_mut() {
+                    entry.selected = false;
+                }
+                break;
+            }
+            KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                for entry in entries.iter_mut() {
+                    entry.selected = false;
+                }
+                break;
             }
+            _ => {}
         }
     }
 
     execute!(stdout, cursor::Show)?;
     terminal::disable_raw_mode()?;
 
-    let selected: Vec<NodeModuleEntry> = entries.iter().filter(|e| e.selected).cloned().collect();
+    let selected: Vec<ArtifactEntry> = entries.iter().filter(|e| e.selected).cloned().collect();
     Ok(selected)
 }
 
-fn delete_with_live_updates(entries: Vec<NodeModuleEntry>) -> Result<Vec<CleanedEntry>> {
+fn apply_with_live_updates(
+    entries: Vec<ArtifactEntry>,
+    mut runner: ActionRunner,
+) -> Result<Vec<CleanedEntry>> {
+    let action = runner.action().clone();
     let entries_arc = Arc::new(Mutex::new(
         entries
             .into_iter()
@@ -450,14 +2666,15 @@ fn delete_with_live_updates(entries: Vec<NodeModuleEntry>) -> Result<Vec<Cleaned
     ));
 
     let total_count = entries_arc.lock().unwrap().len();
-    let deleted_count = Arc::new(AtomicUsize::new(0));
+    let done_count = Arc::new(AtomicUsize::new(0));
     let freed_bytes = Arc::new(AtomicU64::new(0));
     let done = Arc::new(AtomicBool::new(false));
 
     let entries_display = Arc::clone(&entries_arc);
-    let deleted_display = Arc::clone(&deleted_count);
+    let done_count_display = Arc::clone(&done_count);
     let freed_display = Arc::clone(&freed_bytes);
     let done_display = Arc::clone(&done);
+    let progressive = action.progressive();
 
     let display_handle = thread::spawn(move || {
         let mut stdout = stdout();
@@ -470,16 +2687,23 @@ fn delete_with_live_updates(entries: Vec<NodeModuleEntry>) -> Result<Vec<Cleaned
             )
             .ok();
 
-            writeln!(stdout, "{}", "🧹 Deleting node_modules...".bold().cyan()).ok();
+            writeln!(
+                stdout,
+                "{}",
+                format!("🧹 {} build artifacts...", progressive)
+                    .bold()
+                    .cyan()
+            )
+            .ok();
             writeln!(stdout, "{}", "─".repeat(80).dimmed()).ok();
 
-            let deleted = deleted_display.load(Ordering::Relaxed);
+            let processed = done_count_display.load(Ordering::Relaxed);
             let freed = freed_display.load(Ordering::Relaxed);
 
             writeln!(
                 stdout,
                 "Progress: {}/{}  |  Freed: {}",
-                deleted.to_string().green(),
+                processed.to_string().green(),
                 total_count.to_string().cyan(),
                 format_size(freed).bold().yellow()
             )
@@ -499,8 +2723,10 @@ fn delete_with_live_updates(entries: Vec<NodeModuleEntry>) -> Result<Vec<Cleaned
 
                     let status_str = match status {
                         CleanStatus::Found => "⏳ Pending".dimmed().to_string(),
-                        CleanStatus::Deleting => "🔄 Deleting...".yellow().to_string(),
-                        CleanStatus::Deleted => "✓ Deleted".green().to_string(),
+                        CleanStatus::Working(verb) => {
+                            format!("🔄 {}...", verb).yellow().to_string()
+                        }
+                        CleanStatus::Done(status) => status.green().to_string(),
                         CleanStatus::Error(e) => format!("✗ {}", e).red().to_string(),
                     };
 
@@ -513,34 +2739,30 @@ fn delete_with_live_updates(entries: Vec<NodeModuleEntry>) -> Result<Vec<Cleaned
         }
     });
 
-    let paths_to_delete: Vec<(PathBuf, u64)> = {
+    let paths_to_clean: Vec<(PathBuf, u64)> = {
         let entries = entries_arc.lock().unwrap();
         entries.iter().map(|(p, s, _)| (p.clone(), *s)).collect()
     };
 
-    paths_to_delete.par_iter().for_each(|(path, size)| {
-        if let Ok(mut entries) = entries_arc.lock() {
-            if let Some(entry) = entries.iter_mut().find(|(p, _, _)| p == path) {
-                entry.2 = CleanStatus::Deleting;
-            }
+    let outcome: Result<()> = paths_to_clean.par_iter().try_for_each(|(path, size)| {
+        if let Ok(mut entries) = entries_arc.lock()
+            && let Some(entry) = entries.iter_mut().find(|(p, _, _)| p == path)
+        {
+            entry.2 = CleanStatus::Working(progressive);
         }
 
-        let result = remove_directory(path);
+        let status = apply_action(&runner, path)?;
 
-        if let Ok(mut entries) = entries_arc.lock() {
-            if let Some(entry) = entries.iter_mut().find(|(p, _, _)| p == path) {
-                match result {
-                    Ok(_) => {
-                        entry.2 = CleanStatus::Deleted;
-                        deleted_count.fetch_add(1, Ordering::Relaxed);
-                        freed_bytes.fetch_add(*size, Ordering::Relaxed);
-                    }
-                    Err(e) => {
-                        entry.2 = CleanStatus::Error(e.to_string());
-                    }
-                }
+        if let Ok(mut entries) = entries_arc.lock()
+            && let Some(entry) = entries.iter_mut().find(|(p, _, _)| p == path)
+        {
+            if let CleanStatus::Done(_) = status {
+                done_count.fetch_add(1, Ordering::Relaxed);
+                freed_bytes.fetch_add(*size, Ordering::Relaxed);
             }
+            entry.2 = status;
         }
+        Ok(())
     });
 
     done.store(true, Ordering::Relaxed);
@@ -553,201 +2775,244 @@ fn delete_with_live_updates(entries: Vec<NodeModuleEntry>) -> Result<Vec<Cleaned
         cursor::MoveTo(0, 0)
     )?;
 
-    let final_entries: Vec<CleanedEntry> = {
+    outcome?;
+
+    let mut final_entries: Vec<CleanedEntry> = {
         let entries = entries_arc.lock().unwrap();
         entries
             .iter()
-            .map(|(path, size, status)| CleanedEntry {
-                path: path.display().to_string(),
-                size: format_size(*size),
-                status: status.to_string(),
+            .map(|(path, size, status)| {
+                CleanedEntry::new(path.display().to_string(), *size, status)
             })
             .collect()
     };
+    finish_background_deletes(&mut runner, &mut final_entries);
+    runner.commit()?;
 
     let total_freed = freed_bytes.load(Ordering::Relaxed);
-    let total_deleted = deleted_count.load(Ordering::Relaxed);
+    let total_done = done_count.load(Ordering::Relaxed);
 
     println!(
-        "\n{} Completed! Deleted {} directories, freed {}",
+        "\n{} Completed! {} {} directories, freed {}",
         "✨".green(),
-        total_deleted.to_string().bold(),
+        action.past_tense(),
+        total_done.to_string().bold(),
         format_size(total_freed).bold().yellow()
     );
 
     Ok(final_entries)
 }
 
-/// Find and remove all node_modules directories in parallel (non-interactive)
-pub fn clean_node_modules(
-    search_path: Option<&str>,
+/// Write the clean manifest for the directories about to be cleaned
+///
+/// Records sizes, lockfile hashes and reinstall commands so `clean
+/// restore-report` can tell what it takes to bring the projects back.
+fn record_manifest<'a>(
+    runner: &ActionRunner,
+    dirs: impl Iterator<Item = (&'a Path, u64)>,
+) -> Result<()> {
+    let entries: Vec<_> = dirs
+        .map(|(path, size)| manifest_entry(path, size))
+        .collect();
+    let path = write_clean_manifest(runner.action().name(), &entries)?;
+    println!(
+        "{} Clean manifest: {}",
+        "📝".cyan(),
+        path.display().to_string().dimmed()
+    );
+    Ok(())
+}
+
+/// Find all artifact directories in `scope` and apply the clean action to them
+///
+/// The action (delete by default, see `Action`) is journaled under
+/// `CLEAN_JOURNAL_KIND`, so interrupted runs can be recovered and finished
+/// ones undone.
+pub fn clean_artifacts(
+    scope: &CleanScope,
+    action: Action,
     dry_run: bool,
     verbose: bool,
     interactive: bool,
 ) -> Result<Vec<CleanedEntry>> {
-    // If interactive mode, use the interactive cleaner (needs sizes for selection)
-    if interactive {
-        return interactive_clean(search_path, verbose);
+    if action.needs_default_destination() {
+        return Err(ActionError::MissingDirectory("move").into());
     }
 
+    if !dry_run {
+        warn_incomplete_runs(CLEAN_JOURNAL_KIND);
+    }
+    let runner = begin_clean(scope, action, dry_run)?;
+
     // If dry-run, we need sizes to show what would be freed
     if dry_run {
-        let entries = list_node_modules(search_path, verbose)?;
+        let entries = list_artifact_dirs(scope, verbose)?;
 
         if entries.is_empty() {
             return Ok(Vec::new());
         }
 
         println!(
-            "{} Dry run mode - no directories will be removed",
+            "{} Dry run mode - no directories will be changed",
             "⚠️".yellow()
         );
 
-        let results: Vec<CleanedEntry> = entries
+        let results = entries
             .iter()
-            .map(|e| CleanedEntry {
-                path: e.path.display().to_string(),
-                size: format_size(e.size),
-                status: "Would remove".to_string(),
+            .map(|e| {
+                let status = if e.kept {
+                    CleanStatus::Done(KEPT_STATUS.to_string())
+                } else {
+                    apply_action(&runner, &e.path)?
+                };
+                Ok(
+                    CleanedEntry::new(e.path.display().to_string(), e.size, &status)
+                        .with_apparent(e.apparent),
+                )
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
 
-        let total_size: u64 = entries.iter().map(|e| e.size).sum();
+        let cleaned: Vec<&ArtifactEntry> = entries.iter().filter(|e| !e.kept).collect();
+        let total_size: u64 = cleaned.iter().map(|e| e.size).sum();
         println!(
-            "\n{} Would free {} from {} directories",
+            "\n{} Would {} {} from {} directories",
             "💾".green(),
+            runner.action().name(),
             format_size(total_size).bold(),
-            entries.len().to_string().bold()
+            cleaned.len().to_string().bold()
         );
 
         return Ok(results);
     }
 
-    // For clean-all mode, skip size calculation and delete immediately
-    delete_all_node_modules(search_path, verbose)
-}
+    // If interactive mode, use the interactive cleaner (needs sizes for selection)
+    if interactive {
+        return interactive_clean(scope, runner, verbose);
+    }
 
-/// Delete all node_modules without calculating sizes first (fast mode)
-fn delete_all_node_modules(search_path: Option<&str>, verbose: bool) -> Result<Vec<CleanedEntry>> {
-    let root = search_path
-        .map(PathBuf::from)
-        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    // Otherwise clean everything that was found
+    clean_all_artifacts(scope, runner, verbose)
+}
 
+/// Apply the clean action to all artifact directories in parallel, without a selection step
+fn clean_all_artifacts(
+    scope: &CleanScope,
+    mut runner: ActionRunner,
+    verbose: bool,
+) -> Result<Vec<CleanedEntry>> {
     println!(
-        "{} Searching for node_modules in: {}",
+        "{} Searching for {} in: {}",
         "🔍".cyan(),
-        root.display().to_string().yellow()
+        scope.patterns.label().cyan(),
+        scope.roots_label().yellow()
     );
 
-    let node_modules_dirs = find_node_modules(&root, verbose);
+    let artifact_dirs = skip_kept_projects(
+        scope,
+        skip_unlocked_projects(
+            scope,
+            skip_active_projects(scope, find_artifact_dirs(scope, verbose)?),
+        ),
+    );
 
-    if node_modules_dirs.is_empty() {
-        println!("{}", "No node_modules directories found.".yellow());
+    if artifact_dirs.is_empty() {
+        println!("{}", "No matching directories found.".yellow());
         return Ok(Vec::new());
     }
 
-    let total_count = node_modules_dirs.len();
+    // Sizes go into the manifest, so they're measured before anything is touched
+    let usages: Vec<DiskUsage> = artifact_dirs
+        .par_iter()
+        .map(|path| disk_usage(path))
+        .collect();
+    let sizes: Vec<u64> = usages.iter().map(|usage| usage.on_disk).collect();
+    record_manifest(
+        &runner,
+        artifact_dirs
+            .iter()
+            .map(PathBuf::as_path)
+            .zip(sizes.iter().copied()),
+    )?;
+
+    let action = runner.action().clone();
+    let total_count = artifact_dirs.len();
     println!(
-        "{} Found {} node_modules directories. Deleting in parallel...",
+        "{} Found {} directories. {} in parallel...",
         "📦".cyan(),
-        total_count.to_string().green()
+        total_count.to_string().green(),
+        action.progressive()
+    );
+    print_root_subtotals(
+        scope,
+        artifact_dirs
+            .iter()
+            .map(PathBuf::as_path)
+            .zip(sizes.iter().copied()),
     );
 
-    // Shared state for progress
-    let deleted_count = Arc::new(AtomicUsize::new(0));
-    let error_count = Arc::new(AtomicUsize::new(0));
-    let current_path: Arc<Mutex<String>> = Arc::new(Mutex::new(String::new()));
-    let done = Arc::new(AtomicBool::new(false));
-
-    // Clones for display thread
-    let deleted_clone = Arc::clone(&deleted_count);
-    let error_clone = Arc::clone(&error_count);
-    let current_path_clone = Arc::clone(&current_path);
-    let done_clone = Arc::clone(&done);
-
-    let display_handle = thread::spawn(move || {
-        let mut stdout = stdout();
-        while !done_clone.load(Ordering::Relaxed) {
-            let deleted = deleted_clone.load(Ordering::Relaxed);
-            let errors = error_clone.load(Ordering::Relaxed);
-            let path = current_path_clone
-                .lock()
-                .map(|p| p.clone())
-                .unwrap_or_default();
-
-            let display_path = if path.len() > 45 {
-                format!("...{}", &path[path.len() - 42..])
-            } else {
-                path
-            };
-
-            let error_str = if errors > 0 {
-                format!(" | {} errors", errors.to_string().red())
-            } else {
-                String::new()
-            };
-
-            print!(
-                "\r{} Deleted {}/{}{}  {}",
-                "🗑️".cyan(),
-                deleted.to_string().green(),
-                total_count.to_string().cyan(),
-                error_str,
-                display_path.dimmed()
-            );
-            print!("{}", " ".repeat(20));
-            stdout.flush().ok();
-            thread::sleep(Duration::from_millis(50));
-        }
-        print!("\r{}\r", " ".repeat(100));
-        stdout.flush().ok();
-    });
+    // The overall count, with a spinner under it per directory in progress
+    let done_count = AtomicUsize::new(0);
+    let error_count = AtomicUsize::new(0);
+    let past_tense = action.past_tense();
+    let progress = MultiProgress::new();
+    let bar = progress.add(count_bar(total_count as u64).with_message(past_tense));
 
-    // Delete in parallel
-    let results: Vec<CleanedEntry> = node_modules_dirs
+    // Clean in parallel
+    let results: Result<Vec<CleanedEntry>> = artifact_dirs
         .par_iter()
-        .map(|path| {
-            if let Ok(mut current) = current_path.lock() {
-                *current = path.display().to_string();
-            }
-
-            let status = match remove_directory(path) {
-                Ok(_) => {
-                    deleted_count.fetch_add(1, Ordering::Relaxed);
-                    "✓ Deleted".to_string()
+        .zip(usages.par_iter())
+        .map(|(path, usage)| {
+            let spinner = progress.add(item_spinner(path.display().to_string()));
+            let status = apply_action(&runner, path);
+            spinner.finish_and_clear();
+            progress.remove(&spinner);
+            let status = status?;
+
+            match status {
+                CleanStatus::Error(_) => {
+                    let errors = error_count.fetch_add(1, Ordering::Relaxed) + 1;
+                    bar.set_message(format!(
+                        "{} | {} errors",
+                        past_tense,
+                        errors.to_string().red()
+                    ));
                 }
-                Err(e) => {
-                    error_count.fetch_add(1, Ordering::Relaxed);
-                    format!("✗ {}", e)
+                _ => {
+                    done_count.fetch_add(1, Ordering::Relaxed);
                 }
             };
+            bar.inc(1);
 
-            CleanedEntry {
-                path: path.display().to_string(),
-                size: "-".to_string(), // Size not calculated in fast mode
-                status,
-            }
+            Ok(
+                CleanedEntry::new(path.display().to_string(), usage.on_disk, &status)
+                    .with_apparent(usage.apparent),
+            )
         })
         .collect();
 
-    done.store(true, Ordering::Relaxed);
-    display_handle.join().ok();
+    bar.finish_and_clear();
+
+    let mut results = results?;
+    finish_background_deletes(&mut runner, &mut results);
+    runner.commit()?;
 
-    let deleted = deleted_count.load(Ordering::Relaxed);
+    let processed = done_count.load(Ordering::Relaxed);
     let errors = error_count.load(Ordering::Relaxed);
 
     if errors > 0 {
         println!(
-            "\n{} Completed! Deleted {} directories ({} errors)",
+            "\n{} Completed! {} {} directories ({} errors)",
             "✨".green(),
-            deleted.to_string().bold(),
+            past_tense,
+            processed.to_string().bold(),
             errors.to_string().red()
         );
     } else {
         println!(
-            "\n{} Completed! Deleted {} directories",
+            "\n{} Completed! {} {} directories",
             "✨".green(),
-            deleted.to_string().bold()
+            past_tense,
+            processed.to_string().bold()
         );
     }
 
@@ -1,17 +1,65 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Arg, ArgMatches, Command as ClapCommand};
 use colored::Colorize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use crate::{
-    clean_node_modules, display_aliases_table, display_bookmarks_table,
-    display_category_stats_table, display_cleaned_table, display_dead_links_table,
-    display_domain_stats_table, display_duplicates_table, display_functions_table,
-    display_organize_suggestions_table, display_organize_table, display_packages_table,
-    export_to_chrome_html, export_to_markdown, filter_by_category, filter_by_domain,
-    find_dead_links, find_duplicates, find_packages_with_version_greater_than, get_all_aliases,
-    get_all_functions, get_bookmark_stats, get_category_stats, get_domain_stats,
-    get_organize_suggestions, organize_files, parse_bookmarks, remove_dead_links,
-    remove_duplicates, search_bookmarks,
+    Action, ArticleBlock, Bookmark, CLEAN_JOURNAL_KIND, CategoryDestinations, CategoryRegistry,
+    CleanError, CleanFailureEntry, CleanPatterns, CleanScope, DEFAULT_MIN_AGE,
+    DEFAULT_REVIEW_THRESHOLD, DedupeMode, DomainGrouping, ExportTheme, FunctionEntry,
+    HistorySnapshot, HttpSettings, LARGE_FILE_REPORT_COUNT, LicenseEntry, LicensePolicy, LinkMode,
+    Notifier, ORGANIZE_JOURNAL_KIND, OnConflict, OrganizeBy, OrganizeEntry, OrganizeMethod,
+    OrganizeOptions, OrganizePlan, OrganizePreset, OrganizeSchedule, OutdatedEntry, PackageFilter,
+    REGISTRY_USER_AGENT, REVIEW_FOLDER, ReadingItem, RenameRules, ScheduleInterval,
+    SensitiveFilter, Severity, Staleness, StatusPolicy, TREE_TARGET_MARKER, VersionCmp,
+    VersionQuery, XcodeKind, apply_folder_renames, apply_plan, apply_recategorize, artifact_report,
+    ask_yes, audit_packages, browse_clean, bundle_entries, cargo_target_entry, category_changes,
+    category_trend_entries, check_outdated, clean_artifacts, clean_failures, clean_xcode_items,
+    clear_package_caches, collect_licenses, commit_safe_copies, dead_link_entries,
+    diagnose_lockfiles, display_aliases_table, display_audit_table, display_bookmarks_table,
+    display_bundles_table, display_cargo_targets_table, display_category_definitions_table,
+    display_category_stats_table, display_category_trends_table, display_clean_failures_table,
+    display_cleaned_table, display_dead_links_table, display_doctor_table,
+    display_domain_stats_table, display_duplicate_files_table, display_duplicates_table,
+    display_ecosystem_report_table, display_file_moves_table, display_flatten_table,
+    display_folder_renames_table, display_folder_stats_table, display_functions_table,
+    display_language_stats_table, display_license_summary_table, display_licenses_table,
+    display_organize_categories_table, display_organize_months_table,
+    display_organize_suggestions_table, display_organize_table, display_outdated_table,
+    display_package_caches_table, display_packages_table, display_project_cleaned_table,
+    display_project_report_table, display_purge_table, display_reading_list_table,
+    display_recategorize_table, display_recovery_table, display_resolution_table,
+    display_restore_table, display_root_stats_table, display_rule_matches_table,
+    display_safari_reading_table, display_schedules_table, display_share_bundles_table,
+    display_short_urls_table, display_sync_orphans_table, display_trends_table,
+    display_xcode_table, duplicate_entries, expand_home, expand_short_urls, explain_rules,
+    export_link_rot_report, export_share_bundles, export_to_chrome_html, export_to_markdown,
+    fetch_favicons, fetch_reading_list, file_move_entries, filter_by_category, filter_by_domain,
+    find_bundles, find_cargo_targets, find_dead_bookmarks, find_dead_bookmarks_resumable,
+    find_dead_links, find_dependency_chains, find_dependency_trees, find_duplicate_files,
+    find_duplicates, find_file_origin, find_nested_folders, find_package_caches,
+    find_packages_matching, find_quarantined, find_reading_list, find_schedules, find_sync_orphans,
+    find_xcode_items, flatten_folders, format_change, format_size, format_utc,
+    fuzzy_rank_bookmarks, fuzzy_search_bookmarks, get_all_aliases, get_all_functions,
+    get_bookmark_stats, get_category_definitions, get_category_stats, get_domain_stats,
+    get_folder_stats, get_folder_tree, get_language_stats, get_organize_suggestions,
+    get_root_stats, get_safari_bookmarks_path, group_cleaned_by_project, history_path, in_roots,
+    install_schedule, interactive_search, is_dev_folder, is_internal_url, is_mobile_root,
+    is_parked_status, latest_clean_manifest, license_summary, load_category_snapshot, load_history,
+    load_organize_history, old_after_days, open_url, organize_category_entries, organize_files,
+    organize_history_path, organize_month_entries, package_cache_entry, parse_bookmarks,
+    parse_min_age, parse_root, parse_safari_reading_list, parse_since, parse_size,
+    pending_safe_copies, pick_function, pick_package_caches, prompt_function_args,
+    purge_quarantined, reading_list_entries, recategorize_entries, record_snapshot, recover_runs,
+    registry_cache_max_age, reinstall_commands, remove_dead_links, remove_duplicates,
+    remove_empty_dirs, remove_schedule, remove_sync_orphans, render_tree, resolve_command,
+    resolve_duplicates, restore_report, rewrite_short_urls, run_function, run_restore,
+    run_setup_wizard, safari_reading_entries, save_category_snapshot, schedule_entries,
+    search_bookmarks, search_matches, select_package_caches, select_xcode_items,
+    should_offer_setup, skip_setup, suggest_folder_renames, sweep_cargo_targets, trend_entries,
+    undo_last_run, undo_runs_since, unix_seconds, write_bundles, write_reading_list, xcode_entry,
 };
 
 pub fn build_cli() -> ClapCommand {
@@ -23,45 +71,146 @@ MODES:
   aliases   - Show shell aliases from config files and current session
   functions - Show shell functions with documentation from config files  
   packages  - Find package versions greater than a specified threshold
-  clean     - Remove all node_modules directories recursively (parallel)
+  clean     - Remove build artifacts (node_modules by default; --patterns for target, .venv, dist...) recursively (parallel)
   organize  - Organize files in non-development folders by type
   bookmarks - Organize and analyze Chrome bookmarks
 
+ALIAS SUBCOMMANDS:
+  aliases resolve           - Show how a name resolves: alias → function → builtin → PATH binary (--query NAME)
+
+PACKAGE SUBCOMMANDS:
+  packages doctor           - Lockfile hygiene: multiple lockfiles per project, lockfiles out of sync with their manifest
+  packages outdated         - Current vs latest version of each dependency on npm, crates.io and PyPI (cached)
+  packages audit            - Known vulnerabilities in each dependency's version, from OSV.dev (GitHub, RustSec, PyPI, Go advisories)
+  packages licenses         - Licenses of every dependency, per-license summary and violations of [packages] license_allowlist
+  packages tree             - Dependency tree from lockfiles; with --package, only the top-level dependencies that pull it in
+
 BOOKMARK SUBCOMMANDS:
   bookmarks stats           - Show bookmark statistics (domains, categories, duplicates)
   bookmarks duplicates      - Find duplicate bookmarks
   bookmarks remove-dupes    - Remove duplicate bookmarks (interactive)
-  bookmarks deadlinks       - Check for dead/broken links
-  bookmarks remove-dead     - Remove dead links (interactive)
+  bookmarks orphans         - Find mobile/synced bookmarks that duplicate desktop bookmarks
+  bookmarks remove-orphans  - Remove mobile/synced copies of desktop bookmarks (interactive)
+  bookmarks deadlinks       - Check for dead/broken links (--output report.md|.html for a link-rot report, --resume to continue an interrupted scan)
+  bookmarks remove-dead     - Remove dead links (pick which ones interactively, --yes removes all; --include-parked also removes parked domains)
   bookmarks domains         - Show bookmarks grouped by domain
+  bookmarks folders         - Per-folder stats: counts, dominant category, duplicates, dead links (--format tree for the hierarchy)
+  bookmarks renames         - Suggest folder names from each folder's dominant category
+  bookmarks apply-renames   - Rename folders as suggested (interactive)
   bookmarks categories      - Show bookmarks grouped by category
-  bookmarks search          - Search bookmarks by query
+  bookmarks category-list   - List built-in and custom categories (custom ones come from config)
+  bookmarks explain         - Show which categorization rules match a URL (--query URL, --title TEXT)
+  bookmarks snapshot        - Record counts (total, per category, duplicates; dead links with --check-links) in the history
+  bookmarks trends          - Show how the recorded counts changed over time (--limit N latest snapshots)
+  bookmarks recategorize    - Move bookmarks whose category changed since the last snapshot (--diff to preview)
+  bookmarks languages       - Detected title languages and how many are uncategorized
+  bookmarks expand-short-urls - Resolve bit.ly/t.co/goo.gl/tinyurl links (cached); --rewrite replaces them
+  bookmarks search          - Search bookmarks by query (--fuzzy for ranked matches, -i for incremental, --open [N] to open results)
   bookmarks organize        - Get organization suggestions
   bookmarks export          - Export bookmarks to markdown
-  bookmarks export-html     - Export organized bookmarks to Chrome-importable HTML
+  bookmarks export-html     - Export organized bookmarks to Chrome-importable HTML (--favicons embeds site icons, --preserve-unmatched keeps original folders for uncategorized ones)
+  bookmarks export-share    - Export one HTML page per category plus an index, for a static host (--output DIR)
+  bookmarks reading-list    - Collect article-like bookmarks into a digest (--fetch for offline text, --output list.md|list.epub)
+  bookmarks safari-reading-list - Triage Safari's Reading List: categories, unread and old items (--unread, --older-than DAYS, --output list.html|list.md|list.epub)
 
 EXAMPLES:
   shell-explorer                                    # Show all aliases (default)
+  shell-explorer --setup                            # Detect browsers, shell and folders; write the config
   shell-explorer --mode functions --filter git     # Show functions containing 'git'
+  shell-explorer --mode functions --interactive    # Pick a function, enter its arguments and run it
+  shell-explorer --subcommand resolve --query ls    # What actually runs for 'ls'
   shell-explorer --mode packages --package react --min-version 17.0.0
+  shell-explorer --mode packages --path ./app  # Every dependency, any ecosystem
+  shell-explorer --mode packages --package '@babel/*' --package react --package react-dom  # A family in one run
   shell-explorer --mode packages --package typescript --min-version 4.0.0 --path ./src
+  shell-explorer --mode packages --package lodash --min-version 4.0.0 --format tree  # Who requires it
+  shell-explorer --mode packages --package react --version-req '>=17, <19'  # Any semver range, ^ and ~ too
+  shell-explorer --mode packages --package react --cmp lt --min-version 18.0.0  # Projects still on an old version
+  shell-explorer --mode packages --package serde --min-version 1.0.0 --sections dependencies --format json
+  shell-explorer --mode packages --subcommand doctor --path ~/code  # Lockfile hygiene report
+  shell-explorer --mode packages --subcommand outdated --path ./app  # What has newer releases
+  shell-explorer --mode packages --subcommand audit --path ./app  # Security advisories via OSV.dev
+  shell-explorer --mode packages --subcommand licenses --path ./app  # License summary and allowlist check
+  shell-explorer --mode packages --subcommand tree --package react --path ./app  # What pulls react in
   shell-explorer --mode clean --path ./projects    # Remove all node_modules
+  shell-explorer --mode clean --path ~/work --path ~/personal --path /Volumes/dev  # One run, subtotals per root
+  shell-explorer --mode clean --patterns rust,python,js --dry-run  # Preview artifacts of every ecosystem
+  shell-explorer --mode clean --profile weekly      # Roots, patterns and exclusions from [clean.profiles.weekly]
+  shell-explorer --mode clean --path ~ --max-depth 4 --prune --gitignore --dry-run  # Quick scan of the whole home directory
+  shell-explorer --mode clean --path ~/code --safe      # Only lockfile-backed dependencies, with reinstall commands
+  shell-explorer --mode clean -i --background-delete   # Selection returns at once, removal continues in the background
+  shell-explorer --mode clean --path ~/work --exclude '~/work/critical-app/**'  # Never touch that project
   shell-explorer --mode clean --dry-run            # Preview what would be removed
   shell-explorer --mode clean --interactive        # Select which node_modules to delete
+  shell-explorer --mode clean --subcommand cargo --path ~/code  # Cargo target dirs, verified, sizes per profile
+  shell-explorer --mode clean --subcommand cargo --older-than 30  # Sweep build output unused for 30 days
+  shell-explorer --mode clean --subcommand caches   # Sizes of the npm, yarn, pnpm, pip and cargo caches
+  shell-explorer --mode clean --subcommand caches --caches npm,pip  # Clear those two (-i to pick)
+  shell-explorer --mode clean --subcommand xcode -i  # Pick DerivedData, device support and old simulators
+  shell-explorer --mode clean --subcommand xcode --older-than 60 --trash  # What Xcode hasn't used in 60 days
+  shell-explorer --mode clean --patterns all --report --top 20  # Weekly report: biggest projects, totals per ecosystem
+  shell-explorer --mode clean --subcommand browse --path ~  # ncdu-style: drill into sizes, mark any directory
+  shell-explorer --mode clean --action compress:tar.zst  # Keep node_modules as archives instead
+  shell-explorer --mode clean --subcommand undo     # Undo the last clean run (not deletes)
+  shell-explorer --mode clean --trash               # Move to the Trash, so undo can bring them back
+  shell-explorer --mode clean --subcommand restore-report  # What the last run removed and how to rebuild it
+  shell-explorer --mode clean --subcommand restore  # Re-run npm ci / cargo build for what's still missing
   shell-explorer --mode organize --path ~/Downloads # Organize files in Downloads
+  shell-explorer --mode organize --path ~/Desktop --path ~/Downloads --dest ~/Organized  # One tree for both
+  shell-explorer --mode organize --preset tidy --dry-run  # Downloads, Desktop and Documents in one go
+  shell-explorer --mode organize --subcommand schedule --daily --path ~/Downloads --older-than 2d  # launchd agent
+  shell-explorer --mode organize --subcommand schedule-status  # Installed schedules and their last runs
+  shell-explorer --mode organize --subcommand stats  # Files organized per month and category
+  shell-explorer --mode organize --quarantine 90  # Other and stale files go to _Review/<date>/
+  shell-explorer --mode organize --subcommand purge --quarantine 30  # Delete what waited in _Review over 30 days
   shell-explorer --mode organize --dry-run          # Preview organization
+  shell-explorer --mode organize --skip-over 2G --limit 20  # Report the 20 largest files, leave >2 GB in place
+  shell-explorer --mode organize --subcommand recover  # Resolve interrupted organize runs
+  shell-explorer --mode organize --safe --path ~/Photos  # Copy-only run; originals untouched
+  shell-explorer --mode organize --subcommand commit    # Remove originals of verified safe copies
+  shell-explorer --mode organize --action archive:/Volumes/External/Downloads  # Archive by category
+  shell-explorer --mode organize --subcommand undo      # Put the last organize run's files back
+  shell-explorer --mode organize --subcommand undo --since 2d  # Reverse every organize run of the last two days
+  shell-explorer --mode organize --by date --date created  # File into 2024/05/ by creation month
+  shell-explorer --mode organize --by size --dry-run  # Preview Huge/ (>1GB), Large/ (>100MB), ... folders
+  shell-explorer --mode organize --by source  # File downloads into Sources/<domain>/ (macOS)
+  shell-explorer --mode organize --on-conflict rename   # Keep both: report.pdf becomes report (1).pdf
+  shell-explorer --mode organize --prefer-content       # Trust magic bytes over misleading extensions
+  shell-explorer --mode organize --subcommand dedupe -i  # Review identical files, trash or hard-link the copies
+  shell-explorer --mode organize --link  # Move files but leave a symlink at each original path
+  shell-explorer --mode organize --exclude '*.dmg' --exclude '*.torrent'  # Never move these
+  shell-explorer --mode organize --rename '{category}-{date}-{counter:3}' --dry-run  # Normalize names while moving
+  shell-explorer --mode organize --subcommand flatten --dry-run  # Pull single files out of extracted-zip folders
+  shell-explorer --mode organize --plan plan.json  # Write the intended moves for review instead of moving
+  shell-explorer --mode organize --subcommand apply --plan plan.json  # Run a reviewed plan
+  shell-explorer --mode organize --subcommand archive --older-than 1y --dry-run  # Preview Archives/Documents-2023.zip, ...
+  shell-explorer --mode organize --subcommand where -q ~/Downloads/Images/photo.jpg  # Where was it before?
   shell-explorer --mode bookmarks --subcommand stats           # Show bookmark stats
   shell-explorer --mode bookmarks --subcommand duplicates      # Find duplicates
   shell-explorer --mode bookmarks --subcommand remove-dupes    # Remove duplicates (confirm)
   shell-explorer --mode bookmarks --subcommand deadlinks       # Check for dead links
-  shell-explorer --mode bookmarks --subcommand remove-dead     # Remove dead links (confirm)
+  shell-explorer --mode bookmarks --subcommand remove-dead     # Pick dead links to remove
+  shell-explorer --mode bookmarks --subcommand snapshot --check-links  # Record today's counts
+  shell-explorer --mode bookmarks --subcommand trends          # Growth and cleanup over time
   shell-explorer --mode bookmarks --subcommand search --query github  # Search bookmarks
-  shell-explorer --mode bookmarks --subcommand export --output bookmarks.md")
+  shell-explorer --mode bookmarks --subcommand search --query gthb --fuzzy  # Fuzzy search
+  shell-explorer --mode bookmarks --subcommand export --output bookmarks.md
+  shell-explorer --mode bookmarks --subcommand export-share --exclude-sensitive --output ./team-links
+  shell-explorer --mode bookmarks --subcommand reading-list --fetch --output reading.epub  # Offline reading
+  shell-explorer --mode bookmarks --subcommand safari-reading-list --unread --older-than 60 --output stale.html
+
+ENVIRONMENT:
+  Every flag can also be set with a SHELL_EXPLORER_<FLAG> variable (dashes become
+  underscores); command-line flags take precedence. Boolean flags are on for any value but
+  an empty one or 0/false/no/off.
+  SHELL_EXPLORER_PATH=~/Downloads shell-explorer --mode organize
+  SHELL_EXPLORER_PLAIN=true SHELL_EXPLORER_YES=true shell-explorer --mode bookmarks --subcommand remove-dupes")
         .version("1.0.0")
         .arg(
             Arg::new("mode")
                 .short('m')
                 .long("mode")
+                .env("SHELL_EXPLORER_MODE")
                 .value_name("MODE")
                 .help("Mode: 'aliases' (default), 'functions', 'packages', 'clean', 'organize', or 'bookmarks'")
                 .default_value("aliases")
@@ -69,20 +218,30 @@ EXAMPLES:
         .arg(
             Arg::new("subcommand")
                 .long("subcommand")
+                .env("SHELL_EXPLORER_SUBCOMMAND")
                 .value_name("SUBCOMMAND")
-                .help("Subcommand for bookmarks mode: 'stats', 'duplicates', 'remove-dupes', 'deadlinks', 'remove-dead', 'domains', 'categories', 'search', 'organize', 'export', 'export-html'")
+                .help("Subcommand for bookmarks mode: 'stats', 'duplicates', 'remove-dupes', 'orphans', 'remove-orphans', 'deadlinks', 'remove-dead', 'domains', 'folders', 'renames', 'apply-renames', 'categories', 'category-list', 'explain', 'snapshot', 'trends', 'recategorize', 'expand-short-urls', 'languages', 'search', 'organize', 'export', 'export-html', 'export-share', 'reading-list', 'safari-reading-list'; for clean mode: 'browse', 'caches', 'cargo', 'xcode', 'recover', 'undo', 'restore-report', 'restore'; for organize mode: 'recover', 'commit', 'undo', 'dedupe', 'where', 'archive', 'apply', 'flatten', 'schedule', 'schedule-status', 'schedule-remove', 'stats', 'purge'; for packages mode: 'doctor', 'outdated', 'audit', 'licenses', 'tree'; for aliases mode: 'resolve'")
         )
         .arg(
             Arg::new("query")
                 .short('q')
                 .long("query")
+                .env("SHELL_EXPLORER_QUERY")
                 .value_name("QUERY")
-                .help("Search query for bookmarks search mode")
+                .help("Search query for bookmarks search mode, the URL for bookmarks explain, the file for organize where, or the command name for aliases resolve")
+        )
+        .arg(
+            Arg::new("title")
+                .long("title")
+                .env("SHELL_EXPLORER_TITLE")
+                .value_name("TITLE")
+                .help("Bookmark title to categorize along with the URL (for bookmarks explain)")
         )
         .arg(
             Arg::new("category")
                 .short('c')
                 .long("category")
+                .env("SHELL_EXPLORER_CATEGORY")
                 .value_name("CATEGORY")
                 .help("Filter by category (for bookmarks mode)")
         )
@@ -90,6 +249,7 @@ EXAMPLES:
             Arg::new("domain")
                 .short('d')
                 .long("domain")
+                .env("SHELL_EXPLORER_DOMAIN")
                 .value_name("DOMAIN")
                 .help("Filter by domain (for bookmarks mode)")
         )
@@ -97,13 +257,15 @@ EXAMPLES:
             Arg::new("output")
                 .short('o')
                 .long("output")
+                .env("SHELL_EXPLORER_OUTPUT")
                 .value_name("OUTPUT_FILE")
-                .help("Output file path (for bookmarks export)")
+                .help("Output file path (for bookmarks export, a link-rot report for deadlinks, a .md/.epub digest for reading-list, or .html/.md/.epub for safari-reading-list); a directory for export-share")
         )
         .arg(
             Arg::new("limit")
                 .short('l')
                 .long("limit")
+                .env("SHELL_EXPLORER_LIMIT")
                 .value_name("LIMIT")
                 .help("Limit number of results")
         )
@@ -111,6 +273,7 @@ EXAMPLES:
             Arg::new("filter")
                 .short('f')
                 .long("filter")
+                .env("SHELL_EXPLORER_FILTER")
                 .value_name("PATTERN")
                 .help("Filter aliases/functions by name or command (case-insensitive, not used in packages mode)")
         )
@@ -118,6 +281,7 @@ EXAMPLES:
             Arg::new("source")
                 .short('s')
                 .long("source")
+                .env("SHELL_EXPLORER_SOURCE")
                 .value_name("SOURCE")
                 .help("Filter by source file (.zshrc, .bashrc, etc. - not used in packages mode)")
         )
@@ -125,55 +289,732 @@ EXAMPLES:
             Arg::new("plain")
                 .short('p')
                 .long("plain")
+                .env("SHELL_EXPLORER_PLAIN")
                 .help("Plain text output without colors")
                 .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
         )
         .arg(
             Arg::new("package")
                 .long("package")
+                .env("SHELL_EXPLORER_PACKAGE")
                 .value_name("PACKAGE_NAME")
-                .help("Package name to search for (required for packages mode)")
-                .long_help("Package name to search for across all discovered package files. Case-insensitive matching.")
-                .required_if_eq("mode", "packages")
+                .action(clap::ArgAction::Append)
+                .help("Package name, glob or /regex/ to search for in packages mode, repeatable; omit it to list every dependency")
+                .long_help("Package to search for across all discovered package files. Case-insensitive matching. Takes a name, a glob where * and ? match any characters ('react*', '@babel/*') or a regex between slashes ('/^react(-dom)?$/'); repeat it to search for several at once. Without it, packages mode lists every dependency of every package file found, by name.")
         )
         .arg(
             Arg::new("min_version")
                 .long("min-version")
+                .env("SHELL_EXPLORER_MIN_VERSION")
                 .value_name("VERSION")
-                .help("Minimum version threshold - show packages with versions greater than this (required for packages mode)")
-                .long_help("Minimum version threshold using semantic versioning. Only packages with versions greater than this will be shown. Supports formats like: 1.0.0, 2.1.3, 0.5.0-beta, etc.")
-                .required_if_eq("mode", "packages")
+                .help("Version threshold - show packages with versions greater than this, or as --cmp says (packages mode; every version without it)")
+                .long_help("Version threshold using semantic versioning. Only packages with versions greater than this will be shown, or compared as --cmp says. Supports formats like: 1.0.0, 2.1.3, 0.5.0-beta, etc.; manifest ranges like ^17.0.2 compare by the lowest version they allow. A partial threshold is padded with zeros and compared as that one version: 18 means 18.0.0, so '--cmp eq --min-version 18' doesn't match 18.2.1.")
+                .conflicts_with("version_req")
+        )
+        .arg(
+            Arg::new("cmp")
+                .long("cmp")
+                .env("SHELL_EXPLORER_CMP")
+                .value_name("OP")
+                .help("How versions compare to --min-version: 'gt' (default), 'ge', 'lt', 'le', 'eq' or 'ne' (for packages mode)")
+                .long_help("How versions compare to --min-version: 'gt' (greater than, the default), 'ge', 'lt', 'le', 'eq' or 'ne'. '--cmp lt --min-version 18.0.0' finds the projects still on something older than 18.")
+                .value_parser(["gt", "ge", "lt", "le", "eq", "ne"])
+                .requires("min_version")
+                .conflicts_with("version_req")
+        )
+        .arg(
+            Arg::new("version_req")
+                .long("version-req")
+                .env("SHELL_EXPLORER_VERSION_REQ")
+                .value_name("REQ")
+                .help("Show packages whose version satisfies a semver requirement, e.g. '>=1.2, <2', '^1.4' or '~0.3' (for packages mode, instead of --min-version)")
+                .long_help("Semver requirement the version must satisfy, as Cargo reads it: comparators (>=1.2.0, <2), caret (^1.4, the default for a bare 1.4), tilde (~0.3), wildcards (1.*), comma-separated for all of them. Pre-release versions only match a comparator with the same major.minor.patch and a pre-release, e.g. '>=2.0.0-beta.1'.")
         )
         .arg(
             Arg::new("path")
                 .long("path")
+                .env("SHELL_EXPLORER_PATH")
                 .value_name("SEARCH_PATH")
-                .help("Path to search for package files (defaults to current directory)")
+                .action(clap::ArgAction::Append)
+                .help("Path to search (defaults to 'path' under the mode's config section, e.g. [organize], else the current directory); clean mode takes it repeatedly to scan several roots in one run, organize mode to organize several folders into --dest")
                 .long_help("Directory path to search for package files. Recursively searches subdirectories but excludes common build/cache directories (node_modules, target, .git, etc.)")
         )
         .arg(
             Arg::new("verbose")
                 .short('v')
                 .long("verbose")
+                .env("SHELL_EXPLORER_VERBOSE")
                 .help("Show verbose output including directories and files being scanned")
                 .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
         )
         .arg(
             Arg::new("dry_run")
                 .long("dry-run")
+                .env("SHELL_EXPLORER_DRY_RUN")
                 .help("Preview what would be removed without actually deleting (for clean mode)")
                 .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("action")
+                .long("action")
+                .env("SHELL_EXPLORER_ACTION")
+                .value_name("ACTION")
+                .help("What clean/organize do with each item: 'delete', 'move[:DIR]', 'archive:DIR' (keeps relative paths) or 'compress[:tar.zst|tar.gz]' or 'trash' (clean only); defaults to [clean]/[organize] action in config, else delete for clean and move into category folders for organize")
+        )
+        .arg(
+            Arg::new("patterns")
+                .long("patterns")
+                .env("SHELL_EXPLORER_PATTERNS")
+                .value_name("PATTERNS")
+                .value_delimiter(',')
+                .help("Directories clean targets: names like target,.venv,__pycache__,dist,build,.next, or ecosystems node (js), rust, python, all; target/dist/build only count next to their project manifest (for clean mode, default [clean] patterns, else node_modules)")
+        )
+        .arg(
+            Arg::new("exclude")
+                .long("exclude")
+                .env("SHELL_EXPLORER_EXCLUDE")
+                .value_name("GLOB")
+                .action(clap::ArgAction::Append)
+                .help("Never list or clean matches of GLOB, e.g. '~/work/critical-app/**' or 'vendor' (repeatable; added to [clean] exclude and each root's .cleanerignore; for clean mode) or leave files matching GLOB in place, e.g. '*.dmg' or '*.part' (added to [organize] exclude and the folder's .organizeignore; for organize mode)")
+        )
+        .arg(
+            Arg::new("max_depth")
+                .long("max-depth")
+                .env("SHELL_EXPLORER_MAX_DEPTH")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("Only look for artifacts up to N levels below each root, 1 being its direct children (for clean mode, default [clean] max_depth, else unlimited); for packages tree, how many levels below the project to show")
+        )
+        .arg(
+            Arg::new("prune")
+                .long("prune")
+                .env("SHELL_EXPLORER_PRUNE")
+                .help("Stop searching a project once one of its artifacts is found, skipping its other subdirectories such as monorepo packages (for clean mode, default [clean] prune)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("gitignore")
+                .long("gitignore")
+                .env("SHELL_EXPLORER_GITIGNORE")
+                .help("Don't search directories git ignores (.gitignore, .git/info/exclude, global excludes); artifacts are still found (for clean mode, default [clean] gitignore)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("follow_symlinks")
+                .long("follow-symlinks")
+                .env("SHELL_EXPLORER_FOLLOW_SYMLINKS")
+                .help("Follow symlinked directories (loops are detected) and clean what a symlinked artifact points to, warning when that's outside the roots (for clean mode, default [clean] follow_symlinks)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+                .conflicts_with("no_follow")
+        )
+        .arg(
+            Arg::new("no_follow")
+                .long("no-follow")
+                .env("SHELL_EXPLORER_NO_FOLLOW")
+                .help("Never follow symlinks, even if [clean] follow_symlinks is set; symlinked artifacts are listed as skipped (for clean mode)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("include_active")
+                .long("include-active")
+                .env("SHELL_EXPLORER_INCLUDE_ACTIVE")
+                .help("Also clean projects git shows as active: uncommitted changes or a commit in the last [clean] active_days (default 14); they're skipped otherwise (for clean mode)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("background_delete")
+                .long("background-delete")
+                .env("SHELL_EXPLORER_BACKGROUND_DELETE")
+                .help("Delete by renaming each directory aside (instant) and removing the renamed directories on background threads, reporting throughput; interrupted removals finish with --subcommand recover (for clean mode with the delete action, default [clean] background_delete)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("remove_empty")
+                .long("remove-empty")
+                .env("SHELL_EXPLORER_REMOVE_EMPTY")
+                .help("After cleaning, remove directories left empty in the cleaned projects (.cache, .turbo, ...), listed with the results (for clean mode, default [clean] remove_empty)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("report")
+                .long("report")
+                .env("SHELL_EXPLORER_REPORT")
+                .help("Report the largest artifact directories grouped by project, with totals per ecosystem, without cleaning anything (for clean mode)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("top")
+                .long("top")
+                .env("SHELL_EXPLORER_TOP")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("20")
+                .help("Number of projects in the clean --report")
+        )
+        .arg(
+            Arg::new("caches")
+                .long("caches")
+                .env("SHELL_EXPLORER_CACHES")
+                .value_name("NAMES")
+                .value_delimiter(',')
+                .help("Package caches clean --subcommand caches clears: npm, yarn, pnpm, pip, cargo or all; without it (or -i to pick them) the caches are only reported")
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .env("SHELL_EXPLORER_PROFILE")
+                .value_name("NAME")
+                .help("Named [clean.profiles.NAME] set of roots, patterns, exclusions and action from the config (for clean mode); --path, --patterns and --action still override it")
+        )
+        .arg(
+            Arg::new("trash")
+                .long("trash")
+                .env("SHELL_EXPLORER_TRASH")
+                .help("Move cleaned directories to the Trash instead of deleting them, so undo can restore them (for clean mode; same as --action trash)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("safe")
+                .long("safe")
+                .env("SHELL_EXPLORER_SAFE")
+                .help("Organize: copy files into the organized folders instead of moving them, never overwriting; verify each copy by hash and leave originals until --subcommand commit. Clean: only clean dependency directories with a lockfile next to them (node_modules with package-lock.json/yarn.lock/pnpm-lock.yaml/bun.lockb, target with Cargo.lock, virtualenvs with uv/poetry/Pipfile locks) and print each project's reinstall command (default [clean] safe)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .env("SHELL_EXPLORER_FORMAT")
+                .value_name("FORMAT")
+                .help("Output format: 'table', 'tree' (dependency chains for packages, folder tree for bookmarks folders) or 'json' (packages)")
+                .long_help("Output format. 'tree' shows who requires each matched package version, read from package-lock.json (v2+) and Cargo.lock files, and the folder hierarchy for 'bookmarks folders'. 'json' prints matched packages as a JSON array.")
+                .value_parser(["table", "tree", "json"])
+                .default_value("table")
+        )
+        .arg(
+            Arg::new("group_by")
+                .long("group-by")
+                .env("SHELL_EXPLORER_GROUP_BY")
+                .value_name("GROUPING")
+                .help("Group domain statistics by registrable domain ('domain', e.g. google.co.uk) or full host ('subdomain') (for bookmarks stats and domains)")
+                .value_parser(["domain", "subdomain"])
+                .default_value("domain")
+        )
+        .arg(
+            Arg::new("fuzzy")
+                .long("fuzzy")
+                .env("SHELL_EXPLORER_FUZZY")
+                .help("Fuzzy ranked search across title, URL and folder (for bookmarks search)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("diff")
+                .long("diff")
+                .env("SHELL_EXPLORER_DIFF")
+                .help("Only show category changes since the snapshot, without moving anything (for bookmarks recategorize)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("resume")
+                .long("resume")
+                .env("SHELL_EXPLORER_RESUME")
+                .help("Resume an interrupted dead-link scan instead of starting over (for bookmarks deadlinks)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("alive_status")
+                .long("alive-status")
+                .env("SHELL_EXPLORER_ALIVE_STATUS")
+                .value_name("CODES")
+                .value_delimiter(',')
+                .help("HTTP status codes that count as alive in dead-link checks, e.g. 403,405,429 or 4xx; comma-separated, replaces [bookmarks.deadlinks] alive_status")
+        )
+        .arg(
+            Arg::new("dead_status")
+                .long("dead-status")
+                .env("SHELL_EXPLORER_DEAD_STATUS")
+                .value_name("CODES")
+                .value_delimiter(',')
+                .help("HTTP status codes that count as dead in dead-link checks, e.g. 301; comma-separated, replaces [bookmarks.deadlinks] dead_status")
+        )
+        .arg(
+            Arg::new("include_parked")
+                .long("include-parked")
+                .env("SHELL_EXPLORER_INCLUDE_PARKED")
+                .help("Also remove parked/domain-for-sale pages (for bookmarks remove-dead)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("favicons")
+                .long("favicons")
+                .env("SHELL_EXPLORER_FAVICONS")
+                .help("Fetch each site's favicon and embed it in the export (for bookmarks export-html)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("fetch")
+                .long("fetch")
+                .env("SHELL_EXPLORER_FETCH")
+                .help("Download each article's readable text into the digest (for bookmarks reading-list and safari-reading-list)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("unread")
+                .long("unread")
+                .env("SHELL_EXPLORER_UNREAD")
+                .help("Only keep items that were never opened (for bookmarks safari-reading-list)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("older_than")
+                .long("older-than")
+                .env("SHELL_EXPLORER_OLDER_THAN")
+                .value_name("DAYS")
+                .help("Only keep items added more than DAYS ago; also the age at which unread items count as old (for bookmarks safari-reading-list, default [bookmarks.safari] old_after_days or 30); for clean cargo, sweep build output unused for DAYS; for clean xcode, clean items unused for DAYS and unavailable simulators; for organize, only organize files unmodified for DAYS, and for organize archive, bundle them. Also takes 2w, 6m (months) or 1y")
+                .value_parser(parse_days)
+        )
+        .arg(
+            Arg::new("include_internal")
+                .long("include-internal")
+                .env("SHELL_EXPLORER_INCLUDE_INTERNAL")
+                .help("Include bookmarklets and javascript:/chrome:///about:/file:// bookmarks in duplicate detection")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("sections")
+                .long("sections")
+                .env("SHELL_EXPLORER_SECTIONS")
+                .value_name("SECTIONS")
+                .help("Only match these dependency sections, comma-separated (for packages mode)")
+                .long_help("Only match packages declared in these dependency sections, e.g. 'dependencies,devDependencies'. Names are compared ignoring case, '-' and '_', so 'devDependencies' also matches Cargo's [dev-dependencies] and Poetry's dev group. Other sections: peerDependencies, optionalDependencies, build-dependencies, require, require-dev, <group>-dependencies.")
+                .value_delimiter(',')
+        )
+        .arg(
+            Arg::new("quarantine")
+                .long("quarantine")
+                .env("SHELL_EXPLORER_QUARANTINE")
+                .value_name("DAYS")
+                .help("Send uncategorized files and files unmodified for DAYS to a dated _Review/ folder instead; for organize purge, delete what has waited there longer than DAYS. Also takes 2w, 6m or 1y (for organize mode, default [organize] quarantine)")
+                .value_parser(parse_days)
+        )
+        .arg(
+            Arg::new("min_age")
+                .long("min-age")
+                .env("SHELL_EXPLORER_MIN_AGE")
+                .value_name("AGE")
+                .help("Leave files modified within AGE in place, as they may still be written, e.g. 30s, 10m or 2h; 0 turns it off (for organize mode, default [organize] min_age, else 10m)")
+        )
+        .arg(
+            Arg::new("skip_over")
+                .long("skip-over")
+                .env("SHELL_EXPLORER_SKIP_OVER")
+                .value_name("SIZE")
+                .help("Leave files larger than this in place, e.g. 500M or 2G (for organize mode)")
+        )
+        .arg(
+            Arg::new("by")
+                .long("by")
+                .env("SHELL_EXPLORER_BY")
+                .value_name("SCHEME")
+                .help("Folders to file items into: 'type' (Images/), 'date' (2024/05/), 'type-date' (Documents/2024/), 'size' (Huge/, Large/, ...) or 'source' (Sources/github.com/, from macOS download metadata) (for organize mode, default [organize] by, else type)")
+                .value_parser(["type", "date", "type-date", "size", "source"])
+        )
+        .arg(
+            Arg::new("on_conflict")
+                .long("on-conflict")
+                .env("SHELL_EXPLORER_ON_CONFLICT")
+                .value_name("STRATEGY")
+                .help("When a file's destination exists: 'rename' (appends ' (1)'), 'skip', 'overwrite' (moves the existing file to the Trash; not with --safe) or 'prompt' (for organize mode, default [organize] on_conflict, else skip)")
+                .value_parser(["rename", "skip", "overwrite", "prompt"])
+        )
+        .arg(
+            Arg::new("link")
+                .long("link")
+                .env("SHELL_EXPLORER_LINK")
+                .value_name("WHERE")
+                .num_args(0..=1)
+                .default_missing_value("original")
+                .help("Leave a symlink for each organized file: at its 'original' path after moving it (the default), or at its 'destination' while the file stays put (for organize mode, default [organize] link)")
+                .value_parser(["original", "destination"])
+        )
+        .arg(
+            Arg::new("rename")
+                .long("rename")
+                .env("SHELL_EXPLORER_RENAME")
+                .value_name("TEMPLATE")
+                .help("Rename every organized file by TEMPLATE, with {date} (2024-05-03, by --date), {category}, {counter} ({counter:3} pads to 001) and {original} (the name without extension); the extension is kept unless TEMPLATE has one (for organize mode, default the [[organize.rename]] rules)")
+        )
+        .arg(
+            Arg::new("dest")
+                .long("dest")
+                .env("SHELL_EXPLORER_DEST")
+                .value_name("DIR")
+                .help("Create the category folders under DIR rather than inside the folder being organized; needed when --path is given more than once (for organize mode, default [organize] dest)")
+        )
+        .arg(
+            Arg::new("preset")
+                .long("preset")
+                .env("SHELL_EXPLORER_PRESET")
+                .value_name("NAME")
+                .help("Organize a built-in set of folders: 'tidy' is ~/Downloads, ~/Desktop and the top of ~/Documents, each in place and renaming on conflicts (unless --on-conflict or [organize] on_conflict), with one summary (for organize mode)")
+                .value_parser(["tidy"])
+        )
+        .arg(
+            Arg::new("hourly")
+                .long("hourly")
+                .env("SHELL_EXPLORER_HOURLY")
+                .help("Run the scheduled organize every hour, on the hour (for organize schedule)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+                .conflicts_with_all(["daily", "weekly"])
+        )
+        .arg(
+            Arg::new("daily")
+                .long("daily")
+                .env("SHELL_EXPLORER_DAILY")
+                .help("Run the scheduled organize every day at 09:00, the default (for organize schedule)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+                .conflicts_with("weekly")
+        )
+        .arg(
+            Arg::new("weekly")
+                .long("weekly")
+                .env("SHELL_EXPLORER_WEEKLY")
+                .help("Run the scheduled organize on Mondays at 09:00 (for organize schedule)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("plan")
+                .long("plan")
+                .env("SHELL_EXPLORER_PLAN")
+                .value_name("FILE")
+                .help("Write every intended move (source, destination, category, size) to FILE as JSON instead of organizing; --subcommand apply --plan FILE runs it later (for organize mode)")
+        )
+        .arg(
+            Arg::new("last")
+                .long("last")
+                .env("SHELL_EXPLORER_LAST")
+                .help("Undo only the most recent finished run (the default for --subcommand undo)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+                .conflicts_with("since")
+        )
+        .arg(
+            Arg::new("since")
+                .long("since")
+                .env("SHELL_EXPLORER_SINCE")
+                .value_name("TIME")
+                .help("Undo every finished run started since TIME: a UTC date (2024-05-03), date and time (2024-05-03T14:00) or an age (30m, 2h, 3d) (for --subcommand undo)")
+        )
+        .arg(
+            Arg::new("hardlink")
+                .long("hardlink")
+                .env("SHELL_EXPLORER_HARDLINK")
+                .help("Replace duplicates with hard links to the kept copy instead of removing them (for organize --subcommand dedupe)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("prefer_content")
+                .long("prefer-content")
+                .env("SHELL_EXPLORER_PREFER_CONTENT")
+                .help("Categorize files by their content (magic bytes) whenever it's recognized; otherwise content only decides for unknown or missing extensions (for organize mode, default [organize] prefer_content)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("date")
+                .long("date")
+                .env("SHELL_EXPLORER_DATE")
+                .value_name("TIME")
+                .help("Which time date schemes go by: 'modified' or 'created' (for organize --by date/type-date, default [organize] date, else modified)")
+                .value_parser(["modified", "created"])
+        )
+        .arg(
+            Arg::new("preserve_unmatched")
+                .long("preserve-unmatched")
+                .env("SHELL_EXPLORER_PRESERVE_UNMATCHED")
+                .help("Keep uncategorized ('Other') bookmarks in their original folders (for bookmarks export-html)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("rewrite")
+                .long("rewrite")
+                .env("SHELL_EXPLORER_REWRITE")
+                .help("Rewrite shortened bookmarks to their expanded URLs (for bookmarks expand-short-urls)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("min_confidence")
+                .long("min-confidence")
+                .env("SHELL_EXPLORER_MIN_CONFIDENCE")
+                .value_name("SCORE")
+                .help("Categorization confidence (0-1) needed to suggest a folder; lower goes to Needs Review (for bookmarks organize, default 0.6)")
+                .value_parser(clap::value_parser!(f64))
+        )
+        .arg(
+            Arg::new("check_links")
+                .long("check-links")
+                .env("SHELL_EXPLORER_CHECK_LINKS")
+                .help("Check links for dead bookmarks (for bookmarks folders and snapshot)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("exclude_mobile")
+                .long("exclude-mobile")
+                .env("SHELL_EXPLORER_EXCLUDE_MOBILE")
+                .help("Exclude bookmarks under the mobile/synced root (for bookmarks mode)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("exclude_sensitive")
+                .long("exclude-sensitive")
+                .env("SHELL_EXPLORER_EXCLUDE_SENSITIVE")
+                .help("Exclude bookmarks with credentials/tokens in the URL, private-network hosts and [bookmarks.sensitive] matches (for bookmarks mode)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("root")
+                .long("root")
+                .env("SHELL_EXPLORER_ROOT")
+                .value_name("ROOTS")
+                .value_delimiter(',')
+                .help("Only use bookmarks under these roots: bookmark_bar (bar), other, synced (mobile); comma-separated (for bookmarks mode)")
+        )
+        .arg(
+            Arg::new("open")
+                .long("open")
+                .env("SHELL_EXPLORER_OPEN")
+                .value_name("N")
+                .num_args(0..=1)
+                .default_missing_value("1")
+                .value_parser(clap::value_parser!(usize))
+                .help("Open the first N search results (default 1), or the bookmark picked with --interactive, in the default browser (for bookmarks search)")
         )
         .arg(
             Arg::new("interactive")
                 .short('i')
                 .long("interactive")
-                .help("Interactive mode: select which node_modules to delete (for clean mode)")
+                .env("SHELL_EXPLORER_INTERACTIVE")
+                .help("Interactive mode: select which artifact directories to clean (for clean mode), or pick a function and run it (for functions mode)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .env("SHELL_EXPLORER_TIMEOUT")
+                .value_name("SECONDS")
+                .help("HTTP request timeout for link checks (default 10)")
+                .value_parser(clap::value_parser!(u64).range(1..))
+        )
+        .arg(
+            Arg::new("user_agent")
+                .long("user-agent")
+                .env("SHELL_EXPLORER_USER_AGENT")
+                .value_name("USER_AGENT")
+                .help("User-Agent header for link checks")
+        )
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .env("SHELL_EXPLORER_PROXY")
+                .value_name("URL")
+                .help("Proxy for link checks (http://, https:// or socks5://)")
+        )
+        .arg(
+            Arg::new("insecure")
+                .long("insecure")
+                .env("SHELL_EXPLORER_INSECURE")
+                .help("Accept invalid TLS certificates for link checks")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("notify")
+                .long("notify")
+                .env("SHELL_EXPLORER_NOTIFY")
+                .help("Show a desktop notification when long operations (clean, dead-link scans) finish")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("yes")
+                .short('y')
+                .long("yes")
+                .env("SHELL_EXPLORER_YES")
+                .help("Skip confirmation prompts (for bookmark removal subcommands, recategorize, apply-renames and organize commit)")
+                .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
+        )
+        .arg(
+            Arg::new("jobs")
+                .short('j')
+                .long("jobs")
+                .env("SHELL_EXPLORER_JOBS")
+                .value_name("N")
+                .help("Number of worker threads for parallel scans (defaults to the number of CPUs)")
+                .value_parser(clap::value_parser!(usize))
+        )
+        .arg(
+            Arg::new("setup")
+                .long("setup")
+                .env("SHELL_EXPLORER_SETUP")
+                .help("Run the setup wizard: detect browser profiles, shell and common folders and write the config file")
                 .action(clap::ArgAction::SetTrue)
+                .value_parser(clap::builder::FalseyValueParser::new())
         )
 }
 
+/// Apply options shared by every mode before dispatching
+pub fn apply_global_options(matches: &ArgMatches) -> Result<()> {
+    if let Some(&jobs) = matches.get_one::<usize>("jobs") {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .context("Failed to configure worker threads")?;
+    }
+
+    Ok(())
+}
+
+/// Run the setup wizard (`--setup`)
+pub fn handle_setup() -> Result<()> {
+    if let Some(path) = run_setup_wizard()? {
+        println!(
+            "\n{} Wrote {}; edit it any time or rerun with --setup",
+            "✅".green(),
+            path.display().to_string().cyan()
+        );
+    }
+    Ok(())
+}
+
+/// Offer the setup wizard on the first interactive run without a config file
+///
+/// Declining is remembered, so the offer is made only once.
+pub fn offer_first_run_setup() -> Result<()> {
+    if !should_offer_setup()? {
+        return Ok(());
+    }
+
+    println!(
+        "{} No config file yet. The setup wizard can detect your browser profiles, shell and folders.",
+        "👋".cyan()
+    );
+    if ask_yes("Run it now?")? {
+        println!();
+        handle_setup()?;
+        println!();
+    } else {
+        skip_setup()?;
+        println!(
+            "{} Skipped; run with --setup whenever you like\n",
+            "💡".yellow()
+        );
+    }
+    Ok(())
+}
+
+/// `--path`, falling back to `[<mode>] path` from the config
+fn configured_search_path(matches: &ArgMatches, mode: &str) -> Result<Option<String>> {
+    if let Some(path) = matches.get_one::<String>("path") {
+        return Ok(Some(path.clone()));
+    }
+
+    Ok(
+        match crate::Config::load()?.get_str(&format!("{}.path", mode)) {
+            Some(path) => Some(crate::expand_home(path)?.display().to_string()),
+            None => None,
+        },
+    )
+}
+
+/// Build the HTTP client for bookmark network checks, applying CLI overrides to the config
+fn http_client(matches: &ArgMatches) -> Result<reqwest::blocking::Client> {
+    Ok(http_settings(matches)?.build_client()?)
+}
+
+/// The `[http]` settings with the `--timeout`, `--user-agent`, `--proxy` and
+/// `--insecure` overrides
+fn http_settings(matches: &ArgMatches) -> Result<HttpSettings> {
+    let mut http = HttpSettings::load()?;
+
+    if let Some(&timeout) = matches.get_one::<u64>("timeout") {
+        http.timeout = std::time::Duration::from_secs(timeout);
+    }
+    if let Some(user_agent) = matches.get_one::<String>("user_agent") {
+        http.user_agent = Some(user_agent.clone());
+    }
+    if let Some(proxy) = matches.get_one::<String>("proxy") {
+        http.proxy = Some(proxy.clone());
+    }
+    if matches.get_flag("insecure") {
+        http.insecure = true;
+    }
+
+    Ok(http)
+}
+
+/// Build the dead-link status policy, applying `--alive-status`/`--dead-status` to the config
+fn status_policy(matches: &ArgMatches) -> Result<StatusPolicy> {
+    let codes = |id: &str| {
+        matches
+            .get_many::<String>(id)
+            .map(|codes| codes.cloned().collect::<Vec<_>>())
+    };
+
+    let mut policy = StatusPolicy::from_config(&crate::Config::load()?)?;
+    if let Some(alive) = codes("alive_status") {
+        policy = policy.with_alive(&alive)?;
+    }
+    if let Some(dead) = codes("dead_status") {
+        policy = policy.with_dead(&dead)?;
+    }
+    Ok(policy)
+}
+
 pub fn handle_aliases_mode(matches: &ArgMatches) -> Result<()> {
+    if let Some(subcommand) = matches.get_one::<String>("subcommand") {
+        match subcommand.as_str() {
+            "resolve" => return handle_resolve(matches),
+            _ => {
+                println!(
+                    "{}",
+                    format!("Unknown subcommand: {}. Use: resolve", subcommand).yellow()
+                );
+                return Ok(());
+            }
+        }
+    }
+
     let mut aliases = get_all_aliases()?;
 
     // Apply filters
@@ -212,37 +1053,154 @@ pub fn handle_aliases_mode(matches: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
-pub fn handle_functions_mode(matches: &ArgMatches) -> Result<()> {
-    let mut functions = get_all_functions()?;
+/// Show how a command name resolves: alias, keyword, function, builtin, then PATH
+fn handle_resolve(matches: &ArgMatches) -> Result<()> {
+    let Some(name) = matches.get_one::<String>("query") else {
+        println!(
+            "{}",
+            "Please provide a command name with --query <NAME>".yellow()
+        );
+        return Ok(());
+    };
 
-    // Apply filters
-    if let Some(filter_pattern) = matches.get_one::<String>("filter") {
-        let pattern = filter_pattern.to_lowercase();
-        functions.retain(|func| {
-            func.name.to_lowercase().contains(&pattern)
-                || func.description.to_lowercase().contains(&pattern)
-                || func.usage.to_lowercase().contains(&pattern)
-        });
-        println!("{} Filtering by: {}", "🔍".cyan(), filter_pattern.yellow());
-    }
+    println!("{} Resolving: {}\n", "🔍".cyan(), name.yellow());
 
-    if let Some(source_filter) = matches.get_one::<String>("source") {
-        functions.retain(|func| func.source.contains(source_filter));
+    let entries = resolve_command(name)?;
+    let Some(first) = entries.first() else {
         println!(
-            "{} Filtering by source: {}",
-            "📁".cyan(),
-            source_filter.yellow()
+            "{}",
+            format!(
+                "'{}' is not an alias, function, builtin or command on PATH",
+                name
+            )
+            .yellow()
         );
-    }
-
-    if functions.is_empty() {
-        println!("{}", "No functions found matching your criteria.".yellow());
         return Ok(());
-    }
+    };
+
+    let summary = format!(
+        "{} '{}' runs the {} ({})",
+        "✨".green(),
+        name.bold(),
+        first.kind.to_lowercase(),
+        if first.kind == "Binary" {
+            &first.source
+        } else {
+            &first.definition
+        }
+    );
+    let shadowed = entries.len() - 1;
 
-    let function_count = functions.len();
     let use_colors = !matches.get_flag("plain");
-    display_functions_table(functions, use_colors)?;
+    display_resolution_table(entries, use_colors)?;
+
+    println!("\n{}", summary);
+    if shadowed > 0 {
+        println!(
+            "{} {} other definition(s) shadowed",
+            "📊".cyan(),
+            shadowed.to_string().yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Pick functions from a list, prompt for their arguments and run them
+fn run_functions_interactive(functions: &[FunctionEntry]) -> Result<()> {
+    while let Some(function) = pick_function(functions, "")? {
+        println!("\n{} {}", "⚡".cyan(), function.name.bold().cyan());
+        println!("{}", "─".repeat(50).dimmed());
+        if !function.usage.is_empty() {
+            println!("  {} {}", "Usage:".dimmed(), function.usage.yellow());
+        }
+        println!("  {} {}", "Source:".dimmed(), function.source.dimmed());
+
+        let args = prompt_function_args(function)?;
+        println!(
+            "\n{} Running {} {}",
+            "▶".cyan(),
+            function.name.bold(),
+            args.join(" ")
+        );
+        println!("{}", "─".repeat(50).dimmed());
+
+        match run_function(function, &args) {
+            Ok(run) => {
+                print!("{}", run.stdout);
+                if !run.stdout.is_empty() && !run.stdout.ends_with('\n') {
+                    println!();
+                }
+                for line in run.stderr.lines() {
+                    println!("{}", line.red());
+                }
+                println!("{}", "─".repeat(50).dimmed());
+
+                let elapsed = format!("{:.2}s", run.duration.as_secs_f64());
+                if run.status.success() {
+                    println!("{} Exited 0 in {}", "✓".green(), elapsed);
+                } else {
+                    let code = run
+                        .status
+                        .code()
+                        .map(|c| c.to_string())
+                        .unwrap_or_else(|| "by signal".to_string());
+                    println!("{} Exited {} in {}", "✗".red(), code.red(), elapsed);
+                }
+            }
+            // A missing shell only fails this run
+            Err(e) if e.severity() == Severity::Recoverable => {
+                println!("{} {:#}", "✗ Error:".red(), anyhow::Error::from(e));
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        print!("\n{} Run another function? [y/N]: ", "❓".cyan());
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn handle_functions_mode(matches: &ArgMatches) -> Result<()> {
+    let mut functions = get_all_functions()?;
+
+    // Apply filters
+    if let Some(filter_pattern) = matches.get_one::<String>("filter") {
+        let pattern = filter_pattern.to_lowercase();
+        functions.retain(|func| {
+            func.name.to_lowercase().contains(&pattern)
+                || func.description.to_lowercase().contains(&pattern)
+                || func.usage.to_lowercase().contains(&pattern)
+        });
+        println!("{} Filtering by: {}", "🔍".cyan(), filter_pattern.yellow());
+    }
+
+    if let Some(source_filter) = matches.get_one::<String>("source") {
+        functions.retain(|func| func.source.contains(source_filter));
+        println!(
+            "{} Filtering by source: {}",
+            "📁".cyan(),
+            source_filter.yellow()
+        );
+    }
+
+    if functions.is_empty() {
+        println!("{}", "No functions found matching your criteria.".yellow());
+        return Ok(());
+    }
+
+    if matches.get_flag("interactive") {
+        return run_functions_interactive(&functions);
+    }
+
+    let function_count = functions.len();
+    let use_colors = !matches.get_flag("plain");
+    display_functions_table(functions, use_colors)?;
 
     println!(
         "\n{} Found {} functions",
@@ -252,23 +1210,487 @@ pub fn handle_functions_mode(matches: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
-pub fn handle_packages_mode(matches: &ArgMatches) -> Result<()> {
-    let package_name = matches.get_one::<String>("package").unwrap();
-    let min_version = matches.get_one::<String>("min_version").unwrap();
-    let search_path = matches.get_one::<String>("path").map(|s| s.as_str());
-    let verbose = matches.get_flag("verbose");
-
+/// Report lockfile hygiene problems for every project under the search path
+fn handle_packages_doctor(
+    search_path: Option<&str>,
+    verbose: bool,
+    use_colors: bool,
+) -> Result<()> {
+    println!("{}", "🩺 Package Doctor".bold().cyan());
+    println!("{}", "─".repeat(50).dimmed());
     println!(
-        "{} Searching for package '{}' with version > {}",
+        "{} Checking lockfiles under {}",
         "🔍".cyan(),
-        package_name.yellow(),
-        min_version.green()
+        search_path.unwrap_or(".").yellow()
+    );
+
+    let entries = diagnose_lockfiles(search_path, verbose)?;
+    if entries.is_empty() {
+        println!("{}", "No lockfile problems found!".green());
+        return Ok(());
+    }
+
+    let projects: HashSet<&str> = entries.iter().map(|e| e.project.as_str()).collect();
+    let project_count = projects.len();
+    let count = entries.len();
+    display_doctor_table(entries, use_colors)?;
+    println!(
+        "\n{} Found {} lockfile problems in {} projects",
+        "⚠️".yellow(),
+        count.to_string().yellow(),
+        project_count.to_string().yellow()
+    );
+
+    Ok(())
+}
+
+/// `--min-version` (compared as `--cmp` says) or `--version-req`, if given
+fn version_query(matches: &ArgMatches) -> Result<Option<VersionQuery>> {
+    Ok(
+        match (
+            matches.get_one::<String>("min_version"),
+            matches.get_one::<String>("version_req"),
+        ) {
+            (Some(min_version), _) => {
+                let cmp = matches
+                    .get_one::<String>("cmp")
+                    .and_then(|cmp| VersionCmp::parse(cmp))
+                    .unwrap_or_default();
+                Some(VersionQuery::compared(cmp, min_version)?)
+            }
+            (None, Some(req)) => Some(VersionQuery::requirement(req)?),
+            (None, None) => None,
+        },
+    )
+}
+
+/// `--sections`, trimmed; empty for every section
+fn package_sections(matches: &ArgMatches) -> Vec<String> {
+    matches
+        .get_many::<String>("sections")
+        .map(|values| {
+            values
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `--package`, compiled; empty for every package
+fn package_filter(matches: &ArgMatches) -> Result<PackageFilter> {
+    let patterns: Vec<String> = matches
+        .get_many::<String>("package")
+        .map(|values| {
+            values
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(PackageFilter::new(&patterns)?)
+}
+
+/// `packages outdated`: each dependency (of `--package` only, if given)
+/// against the latest release in its registry
+fn handle_packages_outdated(
+    matches: &ArgMatches,
+    search_path: Option<&str>,
+    verbose: bool,
+) -> Result<()> {
+    let filter = package_filter(matches)?;
+    let sections = package_sections(matches);
+    let json = matches.get_one::<String>("format").map(|s| s.as_str()) == Some("json");
+
+    let mut http = http_settings(matches)?;
+    http.user_agent
+        .get_or_insert_with(|| REGISTRY_USER_AGENT.to_string());
+    let client = http.build_client()?;
+    let max_age = registry_cache_max_age(&crate::Config::load()?);
+
+    if !json {
+        println!("{}", "📦 Outdated Dependencies".bold().cyan());
+        println!("{}", "─".repeat(50).dimmed());
+        println!(
+            "{} Checking dependencies under {} against npm, crates.io and PyPI",
+            "🔍".cyan(),
+            search_path.unwrap_or(".").yellow()
+        );
+    }
+
+    let packages = find_packages_matching(&filter, None, search_path, &sections, verbose)?;
+    let entries = check_outdated(&packages, &client, max_age, verbose)?;
+
+    if json {
+        let json: Vec<serde_json::Value> = entries.iter().map(|e| e.to_json()).collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!(
+            "{}",
+            "No dependencies from npm, crates.io or PyPI found".yellow()
+        );
+        return Ok(());
+    }
+
+    let total = entries.len();
+    let outdated = entries.iter().filter(|e| e.staleness.is_outdated()).count();
+    let up_to_date = entries
+        .iter()
+        .filter(|e| e.staleness == Staleness::UpToDate)
+        .count();
+    let failed = entries
+        .iter()
+        .filter(|e| e.staleness == Staleness::Failed)
+        .count();
+
+    // Verbose shows every dependency checked, not only the outdated ones
+    let shown: Vec<OutdatedEntry> = entries
+        .into_iter()
+        .filter(|e| verbose || e.staleness.is_outdated())
+        .collect();
+    if shown.is_empty() {
+        println!("{}", "Everything is up to date!".green());
+    } else {
+        display_outdated_table(shown, !matches.get_flag("plain"))?;
+    }
+
+    println!(
+        "\n{} {} of {} dependencies are outdated ({} up to date, {} couldn't be compared)",
+        "✨".green(),
+        outdated.to_string().bold(),
+        total,
+        up_to_date.to_string().green(),
+        (total - outdated - up_to_date).to_string().yellow()
+    );
+    if failed > 0 {
+        println!(
+            "{} {} lookups failed; --verbose shows why",
+            "⚠️".yellow(),
+            failed
+        );
+    }
+    Ok(())
+}
+
+/// `packages audit`: advisories affecting each dependency's version (of
+/// `--package` only, if given), from OSV.dev
+fn handle_packages_audit(
+    matches: &ArgMatches,
+    search_path: Option<&str>,
+    verbose: bool,
+) -> Result<()> {
+    let filter = package_filter(matches)?;
+    let sections = package_sections(matches);
+    let json = matches.get_one::<String>("format").map(|s| s.as_str()) == Some("json");
+
+    let mut http = http_settings(matches)?;
+    http.user_agent
+        .get_or_insert_with(|| REGISTRY_USER_AGENT.to_string());
+    let client = http.build_client()?;
+
+    if !json {
+        println!("{}", "🛡️  Security Audit".bold().cyan());
+        println!("{}", "─".repeat(50).dimmed());
+        println!(
+            "{} Checking dependencies under {} against OSV.dev",
+            "🔍".cyan(),
+            search_path.unwrap_or(".").yellow()
+        );
+    }
+
+    let packages = find_packages_matching(&filter, None, search_path, &sections, verbose)?;
+    let entries = audit_packages(&packages, &client, verbose)?;
+
+    if json {
+        let json: Vec<serde_json::Value> = entries.iter().map(|e| e.to_json()).collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "No known vulnerabilities in {} dependencies!",
+                packages.len()
+            )
+            .green()
+        );
+        return Ok(());
+    }
+
+    let advisories: HashSet<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+    let affected: HashSet<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    let summary = format!(
+        "\n{} {} advisories affect {} packages",
+        "⚠️".yellow(),
+        advisories.len().to_string().red().bold(),
+        affected.len().to_string().yellow()
     );
+    display_audit_table(entries, !matches.get_flag("plain"))?;
+    println!("{}", summary);
+    println!(
+        "{} Ranges are checked at the lowest version they allow, e.g. ^17.0.2 as 17.0.2",
+        "💡".yellow()
+    );
+    Ok(())
+}
+
+/// `packages licenses`: every dependency's license (of `--package` only, if
+/// given), summarized per license and checked against the allowlist
+fn handle_packages_licenses(
+    matches: &ArgMatches,
+    search_path: Option<&str>,
+    verbose: bool,
+) -> Result<()> {
+    let filter = package_filter(matches)?;
+    let sections = package_sections(matches);
+    let json = matches.get_one::<String>("format").map(|s| s.as_str()) == Some("json");
+    let use_colors = !matches.get_flag("plain");
+
+    let mut http = http_settings(matches)?;
+    http.user_agent
+        .get_or_insert_with(|| REGISTRY_USER_AGENT.to_string());
+    let client = http.build_client()?;
+    let config = crate::Config::load()?;
+    let policy = LicensePolicy::from_config(&config);
+
+    if !json {
+        println!("{}", "⚖️  License Report".bold().cyan());
+        println!("{}", "─".repeat(50).dimmed());
+        println!(
+            "{} Collecting the licenses of dependencies under {}",
+            "🔍".cyan(),
+            search_path.unwrap_or(".").yellow()
+        );
+    }
+
+    let packages = find_packages_matching(&filter, None, search_path, &sections, verbose)?;
+    let entries = collect_licenses(
+        &packages,
+        &client,
+        registry_cache_max_age(&config),
+        &policy,
+        verbose,
+    )?;
+
+    if json {
+        let json: Vec<serde_json::Value> = entries.iter().map(|e| e.to_json()).collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("{}", "No dependencies found".yellow());
+        return Ok(());
+    }
+
+    if verbose {
+        display_licenses_table(entries.clone(), use_colors)?;
+    }
+    display_license_summary_table(license_summary(&entries, &policy), use_colors)?;
+
+    if policy.is_empty() {
+        println!(
+            "\n{} Set license_allowlist under [packages] in the config to check licenses",
+            "💡".yellow()
+        );
+        return Ok(());
+    }
+
+    let violations: Vec<LicenseEntry> = entries
+        .into_iter()
+        .filter(|e| e.allowed == Some(false))
+        .collect();
+    if violations.is_empty() {
+        println!(
+            "\n{}",
+            "✓ Every dependency's license is on the allowlist".green()
+        );
+        return Ok(());
+    }
+
+    let count = violations.len();
+    println!(
+        "\n{} Licenses not on the allowlist (unknown ones included):",
+        "✗".red()
+    );
+    display_licenses_table(violations, use_colors)?;
+    println!(
+        "\n{} {} dependencies violate the license allowlist",
+        "⚠️".yellow(),
+        count.to_string().red().bold()
+    );
+    Ok(())
+}
+
+/// `packages tree`: each lockfile's dependency tree, top-down; with
+/// `--package`, only the branches that lead to it
+fn handle_packages_tree(
+    matches: &ArgMatches,
+    search_path: Option<&str>,
+    verbose: bool,
+) -> Result<()> {
+    let filter = package_filter(matches)?;
+    let query = version_query(matches)?;
+    let max_depth = matches.get_one::<usize>("max_depth").copied();
+
+    if filter.is_empty() {
+        println!("{} Dependency trees", "🌳".cyan());
+    } else {
+        println!(
+            "{} Finding what pulls in {}{}",
+            "🌳".cyan(),
+            filter.to_string().yellow(),
+            query
+                .as_ref()
+                .map(|query| format!(" with version {}", query.to_string().green()))
+                .unwrap_or_default()
+        );
+    }
+    if let Some(path) = search_path {
+        println!("{} Search path: {}", "📁".cyan(), path.yellow());
+    }
+
+    let trees = find_dependency_trees(&filter, query.as_ref(), search_path, max_depth, verbose)?;
+    if trees.is_empty() {
+        let message = if filter.is_empty() {
+            "No lockfiles found".to_string()
+        } else {
+            format!("No lockfile entries for {} found", filter)
+        };
+        println!("{}", message.yellow());
+        println!(
+            "{} Dependency trees are read from package-lock.json (v2+) and Cargo.lock",
+            "💡".yellow()
+        );
+        return Ok(());
+    }
+
+    let mut current_file = None;
+    for (lock_file, tree) in &trees {
+        if current_file != Some(lock_file) {
+            println!(
+                "\n{} {}",
+                "📄".cyan(),
+                lock_file.display().to_string().bold()
+            );
+            current_file = Some(lock_file);
+        }
+        print!("{}", render_tree(tree));
+        if !filter.is_empty() {
+            let top_level: Vec<&str> = tree
+                .children
+                .iter()
+                .map(|child| {
+                    child
+                        .label
+                        .trim_end_matches(" (*)")
+                        .trim_end_matches(TREE_TARGET_MARKER)
+                        .trim_end()
+                })
+                .collect();
+            println!(
+                "{} Pulled in by {}: {}",
+                "🔗".cyan(),
+                if top_level.len() == 1 {
+                    "1 top-level dependency".to_string()
+                } else {
+                    format!("{} top-level dependencies", top_level.len())
+                },
+                top_level.join(", ").yellow()
+            );
+        }
+    }
+
+    let lock_files: HashSet<&PathBuf> = trees.iter().map(|(lock_file, _)| lock_file).collect();
+    println!(
+        "\n{} Found {} trees in {} lockfiles ({} = expanded above{})",
+        "✨".green(),
+        trees.len().to_string().bold(),
+        lock_files.len(),
+        "(*)".dimmed(),
+        if !filter.is_empty() {
+            format!(", {} = a match", TREE_TARGET_MARKER)
+        } else {
+            String::new()
+        }
+    );
+    Ok(())
+}
+
+pub fn handle_packages_mode(matches: &ArgMatches) -> Result<()> {
+    let search_path = configured_search_path(matches, "packages")?;
+    let search_path = search_path.as_deref();
+    let verbose = matches.get_flag("verbose");
+
+    if let Some(subcommand) = matches.get_one::<String>("subcommand") {
+        match subcommand.as_str() {
+            "doctor" => {
+                return handle_packages_doctor(search_path, verbose, !matches.get_flag("plain"));
+            }
+            "outdated" => return handle_packages_outdated(matches, search_path, verbose),
+            "audit" => return handle_packages_audit(matches, search_path, verbose),
+            "licenses" => return handle_packages_licenses(matches, search_path, verbose),
+            "tree" => return handle_packages_tree(matches, search_path, verbose),
+            _ => {
+                println!(
+                    "{}",
+                    format!(
+                        "Unknown subcommand: {}. Use: doctor, outdated, audit, licenses, tree",
+                        subcommand
+                    )
+                    .yellow()
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    let query = version_query(matches)?;
+    let query = query.as_ref();
+    let filter = package_filter(matches)?;
+    let format = matches.get_one::<String>("format").map(|s| s.as_str());
+    let sections = package_sections(matches);
+
+    if format == Some("json") {
+        let packages = find_packages_matching(&filter, query, search_path, &sections, verbose)?;
+        let json: Vec<serde_json::Value> = packages.iter().map(|p| p.to_json()).collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    match (filter.is_empty(), query) {
+        (false, Some(query)) => println!(
+            "{} Searching for {} with version {}",
+            "🔍".cyan(),
+            filter.to_string().yellow(),
+            query.to_string().green()
+        ),
+        (false, None) => println!(
+            "{} Searching for every version of {}",
+            "🔍".cyan(),
+            filter.to_string().yellow()
+        ),
+        (true, Some(query)) => println!(
+            "{} Listing every dependency with version {}",
+            "📦".cyan(),
+            query.to_string().green()
+        ),
+        (true, None) => println!("{} Listing every dependency", "📦".cyan()),
+    }
 
     if let Some(path) = search_path {
         println!("{} Search path: {}", "📁".cyan(), path.yellow());
     }
 
+    if !sections.is_empty() {
+        println!("{} Sections: {}", "📑".cyan(), sections.join(", ").yellow());
+    }
+
     if verbose {
         println!(
             "{} Verbose mode enabled - showing scan details",
@@ -276,60 +1698,1721 @@ pub fn handle_packages_mode(matches: &ArgMatches) -> Result<()> {
         );
     }
 
-    let packages =
-        find_packages_with_version_greater_than(package_name, min_version, search_path, verbose)?;
+    if format == Some("tree") {
+        if filter.is_empty() {
+            anyhow::bail!("--format tree needs --package <PACKAGE_NAME>");
+        }
+        if !sections.is_empty() {
+            println!(
+                "{}",
+                "Lockfiles don't record dependency sections; --sections is ignored in tree format"
+                    .yellow()
+            );
+        }
+        return display_dependency_chains(&filter, query, search_path, verbose);
+    }
+
+    let packages = find_packages_matching(&filter, query, search_path, &sections, verbose)?;
 
     if packages.is_empty() {
-        println!(
-            "{}",
-            format!(
-                "No packages named '{}' found with version greater than '{}'",
-                package_name, min_version
-            )
-            .yellow()
-        );
+        let message = match (filter.is_empty(), query) {
+            (false, Some(query)) => format!("No {} found with version {}", filter, query),
+            (false, None) => format!("No {} found", filter),
+            (true, Some(query)) => format!("No dependencies found with version {}", query),
+            (true, None) => "No dependencies found".to_string(),
+        };
+        println!("{}", message.yellow());
         return Ok(());
     }
 
     let package_count = packages.len();
+    let file_count = packages
+        .iter()
+        .map(|p| &p.file_path)
+        .collect::<HashSet<_>>()
+        .len();
     let use_colors = !matches.get_flag("plain");
     display_packages_table(packages, use_colors)?;
 
+    if !filter.is_empty() {
+        println!(
+            "\n{} Found {} package instances",
+            "✨".green(),
+            package_count.to_string().bold()
+        );
+    } else {
+        println!(
+            "\n{} Found {} dependencies in {} files",
+            "✨".green(),
+            package_count.to_string().bold(),
+            file_count.to_string().bold()
+        );
+    }
+    Ok(())
+}
+
+/// Print "who requires it" trees for matched package versions, grouped by lockfile
+fn display_dependency_chains(
+    filter: &PackageFilter,
+    query: Option<&VersionQuery>,
+    search_path: Option<&str>,
+    verbose: bool,
+) -> Result<()> {
+    let chains = find_dependency_chains(filter, query, search_path, verbose)?;
+
+    if chains.is_empty() {
+        let message = match query {
+            Some(query) => format!(
+                "No lockfile entries for {} found with version {}",
+                filter, query
+            ),
+            None => format!("No lockfile entries for {} found", filter),
+        };
+        println!("{}", message.yellow());
+        println!(
+            "{} Dependency chains are read from package-lock.json (v2+) and Cargo.lock",
+            "💡".yellow()
+        );
+        return Ok(());
+    }
+
+    let mut current_file = None;
+    for (lock_file, tree) in &chains {
+        if current_file != Some(lock_file) {
+            println!(
+                "\n{} {}",
+                "📄".cyan(),
+                lock_file.display().to_string().bold()
+            );
+            current_file = Some(lock_file);
+        }
+        print!("{}", render_tree(tree));
+    }
+
     println!(
-        "\n{} Found {} package instances",
+        "\n{} Found {} matching lockfile entries ({} = dependents shown above)",
         "✨".green(),
-        package_count.to_string().bold()
+        chains.len().to_string().bold(),
+        "(*)".dimmed()
     );
     Ok(())
 }
 
-pub fn handle_clean_mode(matches: &ArgMatches) -> Result<()> {
-    let search_path = matches.get_one::<String>("path").map(|s| s.as_str());
+/// Handle the journal subcommands shared by clean and organize: `recover` and
+/// `undo` (the last run, or with `--since` every run since then)
+fn handle_journal_subcommand(
+    subcommand: &str,
+    kind: &str,
+    matches: &ArgMatches,
+    use_colors: bool,
+) -> Result<()> {
     let dry_run = matches.get_flag("dry_run");
-    let verbose = matches.get_flag("verbose");
-    let interactive = matches.get_flag("interactive");
+    let since = matches.get_one::<String>("since");
+    if since.is_some() && subcommand != "undo" {
+        anyhow::bail!("--since only applies to --subcommand undo");
+    }
 
-    let results = clean_node_modules(search_path, dry_run, verbose, interactive)?;
+    let results = match (subcommand, since) {
+        ("recover", _) => recover_runs(kind, dry_run)?,
+        (_, Some(since)) => undo_runs_since(kind, parse_since(since)?, dry_run)?,
+        _ => undo_last_run(kind, dry_run)?,
+    };
+    let deleted = results.iter().any(|r| r.action.starts_with("✗ Deleted"));
 
-    if !results.is_empty() && !interactive {
-        let use_colors = !matches.get_flag("plain");
-        display_cleaned_table(results, use_colors)?;
+    if !results.is_empty() {
+        display_recovery_table(results, use_colors)?;
+    }
+    if deleted && kind == CLEAN_JOURNAL_KIND {
+        println!(
+            "\n{} Deleted directories can be rebuilt: --subcommand restore-report shows how",
+            "💡".yellow()
+        );
     }
-
     Ok(())
 }
 
-pub fn handle_organize_mode(matches: &ArgMatches) -> Result<()> {
-    let search_path = matches.get_one::<String>("path").map(|s| s.as_str());
-    let dry_run = matches.get_flag("dry_run");
-    let verbose = matches.get_flag("verbose");
-    let interactive = matches.get_flag("interactive");
+pub fn handle_clean_mode(matches: &ArgMatches) -> Result<()> {
+    let dry_run = matches.get_flag("dry_run");
+    let verbose = matches.get_flag("verbose");
+    let interactive = matches.get_flag("interactive");
+    let use_colors = !matches.get_flag("plain");
+    let mut browse = false;
+    let mut cargo = false;
+
+    if let Some(subcommand) = matches.get_one::<String>("subcommand") {
+        match subcommand.as_str() {
+            "browse" => browse = true,
+            "cargo" => cargo = true,
+            "caches" => {
+                return handle_clean_caches(matches, dry_run, use_colors);
+            }
+            "xcode" => {
+                return handle_clean_xcode(matches, dry_run, use_colors);
+            }
+            "recover" | "undo" => {
+                return handle_journal_subcommand(
+                    subcommand,
+                    CLEAN_JOURNAL_KIND,
+                    matches,
+                    use_colors,
+                );
+            }
+            "restore-report" | "restore" => {
+                return handle_clean_restore(
+                    subcommand,
+                    dry_run,
+                    matches.get_flag("yes"),
+                    use_colors,
+                );
+            }
+            _ => {
+                println!(
+                    "{}",
+                    format!(
+                        "Unknown subcommand: {}. Use: browse, caches, cargo, xcode, recover, undo, restore-report, restore",
+                        subcommand
+                    )
+                    .yellow()
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    // Flags override the profile, which overrides the rest of [clean]
+    let mut scope = CleanScope::load(matches.get_one::<String>("profile").map(|s| s.as_str()))?;
+    if let Some(paths) = matches.get_many::<String>("path") {
+        scope.roots = paths.map(PathBuf::from).collect();
+    }
+    if let Some(specs) = matches.get_many::<String>("patterns") {
+        scope.patterns = CleanPatterns::new(&specs.cloned().collect::<Vec<_>>())?;
+    }
+    for glob in matches.get_many::<String>("exclude").into_iter().flatten() {
+        scope.exclude(glob)?;
+    }
+    if let Some(depth) = matches.get_one::<usize>("max_depth") {
+        scope.max_depth = Some(*depth);
+    }
+    if matches.get_flag("prune") {
+        scope.prune = true;
+    }
+    if matches.get_flag("gitignore") {
+        scope.gitignore = true;
+    }
+    if matches.get_flag("follow_symlinks") {
+        scope.follow_symlinks = true;
+    } else if matches.get_flag("no_follow") {
+        scope.follow_symlinks = false;
+    }
+    scope.include_active = matches.get_flag("include_active");
+    if matches.get_flag("remove_empty") {
+        scope.remove_empty = true;
+    }
+    if matches.get_flag("safe") {
+        scope.safe = true;
+    }
+    if matches.get_flag("background_delete") {
+        scope.background_delete = true;
+    }
+
+    if cargo {
+        match matches.get_one::<u64>("older_than") {
+            Some(&days) => {
+                let results = sweep_cargo_targets(&scope, days, dry_run, verbose)?;
+                let (failures, total) = (clean_failures(&results), results.len());
+                if !results.is_empty() {
+                    display_cleaned_table(results, use_colors)?;
+                }
+                report_clean_failures(failures, total, use_colors)?;
+            }
+            None => {
+                let targets = find_cargo_targets(&scope, verbose)?;
+                if targets.is_empty() {
+                    println!("{}", "No Cargo target directories found.".yellow());
+                } else {
+                    let total: u64 = targets.iter().map(|t| t.size).sum();
+                    display_cargo_targets_table(
+                        targets.iter().map(cargo_target_entry).collect(),
+                        use_colors,
+                    )?;
+                    println!(
+                        "\n{} {} in {} target directories; --older-than DAYS sweeps what wasn't used since",
+                        "💾".green(),
+                        format_size(total).bold(),
+                        targets.len()
+                    );
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    if matches.get_flag("report") {
+        let top = matches.get_one::<usize>("top").copied().unwrap_or(20);
+        let (projects, ecosystems) = artifact_report(&scope, top, verbose)?;
+        if !projects.is_empty() {
+            println!(
+                "\n{} Top {} projects by artifact size",
+                "🏆".yellow(),
+                projects.len()
+            );
+            display_project_report_table(projects, use_colors)?;
+            display_ecosystem_report_table(ecosystems, use_colors)?;
+        }
+        return Ok(());
+    }
+    if let Some(profile) = &scope.profile {
+        println!(
+            "{} Using clean profile {}",
+            "🗂️".cyan(),
+            profile.bold().cyan()
+        );
+    }
+
+    let action_flag = matches.get_one::<String>("action").map(|s| s.as_str());
+    let action = if matches.get_flag("trash") {
+        if action_flag.is_some() {
+            anyhow::bail!("--trash can't be combined with --action");
+        }
+        Action::Trash
+    } else {
+        Action::load(
+            action_flag.or(scope.action.as_deref()),
+            "clean",
+            Action::Delete,
+        )?
+    };
+    let notifier = Notifier::load(matches.get_flag("notify"))?;
+
+    let mut results = if browse {
+        browse_clean(&scope, action, dry_run)?
+    } else {
+        clean_artifacts(&scope, action, dry_run, verbose, interactive)?
+    };
+    let processed = results.len();
+
+    // The interactive cleaners show results as they go, except on dry runs;
+    // artifacts are listed under their projects, browsed directories as marked
+    let live = (interactive || browse) && !dry_run;
+    if scope.remove_empty && !browse {
+        let empty = remove_empty_dirs(&scope, &results, dry_run);
+        if live && !empty.is_empty() {
+            display_project_cleaned_table(group_cleaned_by_project(&empty), use_colors)?;
+        }
+        results.extend(empty);
+    }
+    let failures = clean_failures(&results);
+    let total = results.len();
+    let reinstall = if scope.safe {
+        reinstall_commands(&results)
+    } else {
+        Vec::new()
+    };
+
+    if !results.is_empty() && !live {
+        if browse {
+            display_cleaned_table(results, use_colors)?;
+        } else {
+            display_project_cleaned_table(group_cleaned_by_project(&results), use_colors)?;
+        }
+    }
+    if !reinstall.is_empty() {
+        println!("\n{} Reinstall with:", "🔁".cyan());
+        for command in reinstall {
+            println!("   {}", command.green());
+        }
+    }
+
+    notifier.finish(
+        "Clean finished",
+        &format!(
+            "Processed {} directories ({})",
+            processed,
+            if browse {
+                "browsed".to_string()
+            } else {
+                scope.patterns.label()
+            }
+        ),
+    );
+
+    report_clean_failures(failures, total, use_colors)
+}
+
+/// Print the directories a clean run failed on with their errors, and end
+/// the run with an error so cron jobs and CI see it was incomplete
+fn report_clean_failures(
+    failures: Vec<CleanFailureEntry>,
+    total: usize,
+    use_colors: bool,
+) -> Result<()> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    let failed = failures.len();
+    println!(
+        "\n{} {} couldn't be cleaned:",
+        "✗".red(),
+        format!("{} of {} directories", failed, total).red()
+    );
+    display_clean_failures_table(failures, use_colors)?;
+    Err(CleanError::Failed { failed, total }.into())
+}
+
+/// Report the package-manager caches and clear the ones opted in to with
+/// `--caches` or picked with `--interactive`
+fn handle_clean_caches(matches: &ArgMatches, dry_run: bool, use_colors: bool) -> Result<()> {
+    println!("{} Measuring package-manager caches...", "🔍".cyan());
+    let caches = find_package_caches()?;
+    if caches.is_empty() {
+        println!("{}", "No package-manager caches found.".yellow());
+        return Ok(());
+    }
+
+    display_package_caches_table(caches.iter().map(package_cache_entry).collect(), use_colors)?;
+    let total: u64 = caches.iter().map(|cache| cache.size).sum();
+    println!(
+        "\n{} {} in package caches",
+        "💾".green(),
+        format_size(total).bold()
+    );
+
+    let picked = if matches.get_flag("interactive") {
+        match select_package_caches(&caches)? {
+            Some(picked) => picked,
+            None => {
+                println!("{}", "Cancelled.".yellow());
+                return Ok(());
+            }
+        }
+    } else if let Some(names) = matches.get_many::<String>("caches") {
+        pick_package_caches(&caches, &names.cloned().collect::<Vec<_>>())?
+    } else {
+        println!(
+            "{}",
+            "--caches npm,pip,... (or all) clears caches; -i picks them".dimmed()
+        );
+        return Ok(());
+    };
+    if picked.is_empty() {
+        println!("{}", "No caches selected.".yellow());
+        return Ok(());
+    }
+
+    let action = if matches.get_flag("trash") {
+        Action::Trash
+    } else {
+        Action::Delete
+    };
+    let results = clear_package_caches(&picked, action, dry_run)?;
+    let (failures, total) = (clean_failures(&results), results.len());
+    display_cleaned_table(results, use_colors)?;
+    report_clean_failures(failures, total, use_colors)
+}
+
+/// Report Xcode's DerivedData, device support and unavailable simulators, and
+/// clean the ones picked with `--interactive` or unused for `--older-than` days
+fn handle_clean_xcode(matches: &ArgMatches, dry_run: bool, use_colors: bool) -> Result<()> {
+    println!("{} Measuring Xcode data...", "🔍".cyan());
+    let items = find_xcode_items()?;
+    if items.is_empty() {
+        println!(
+            "{}",
+            "No Xcode DerivedData, device support or old simulators found.".yellow()
+        );
+        return Ok(());
+    }
+
+    display_xcode_table(items.iter().map(xcode_entry).collect(), use_colors)?;
+    let total: u64 = items.iter().map(|item| item.size).sum();
+    println!(
+        "\n{} {} of Xcode data",
+        "💾".green(),
+        format_size(total).bold()
+    );
+
+    let picked = if matches.get_flag("interactive") {
+        match select_xcode_items(&items)? {
+            Some(picked) => picked,
+            None => {
+                println!("{}", "Cancelled.".yellow());
+                return Ok(());
+            }
+        }
+    } else if let Some(&days) = matches.get_one::<u64>("older_than") {
+        let cutoff = SystemTime::now()
+            .checked_sub(Duration::from_secs(days * 86400))
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        items
+            .into_iter()
+            .filter(|item| {
+                matches!(item.kind, XcodeKind::Simulator { .. })
+                    || item.modified.is_some_and(|modified| modified < cutoff)
+            })
+            .collect()
+    } else {
+        println!(
+            "{}",
+            "-i picks items to clean; --older-than DAYS cleans what Xcode hasn't used since"
+                .dimmed()
+        );
+        return Ok(());
+    };
+    if picked.is_empty() {
+        println!("{}", "Nothing to clean.".yellow());
+        return Ok(());
+    }
+
+    let action = if matches.get_flag("trash") {
+        Action::Trash
+    } else {
+        Action::Delete
+    };
+    let results = clean_xcode_items(&picked, action, dry_run)?;
+    let (failures, total) = (clean_failures(&results), results.len());
+    display_cleaned_table(results, use_colors)?;
+    report_clean_failures(failures, total, use_colors)
+}
+
+/// Show what the last clean run removed and how to rebuild it (`restore-report`),
+/// or re-run the reinstall commands after confirmation (`restore`)
+fn handle_clean_restore(
+    subcommand: &str,
+    dry_run: bool,
+    yes: bool,
+    use_colors: bool,
+) -> Result<()> {
+    let Some(manifest) = latest_clean_manifest()? else {
+        println!("{} No clean manifests recorded yet.", "✓".green());
+        return Ok(());
+    };
+
+    println!(
+        "{} Last clean run: {} of {} directories ({})",
+        "📝".cyan(),
+        manifest.action.bold(),
+        manifest.entries.len().to_string().bold(),
+        manifest.path.display().to_string().dimmed()
+    );
+    if manifest.action == "trash" {
+        println!(
+            "{} That run moved them to the Trash: --subcommand undo puts them back as they were",
+            "💡".yellow()
+        );
+    }
+
+    if subcommand == "restore-report" {
+        let entries = restore_report(&manifest);
+        if !entries.is_empty() {
+            display_restore_table(entries, use_colors)?;
+        }
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("{} Dry run mode - no commands will be run\n", "🔍".cyan());
+    } else if !yes {
+        print!(
+            "\n{} Re-run the reinstall commands for the missing directories? [y/N]: ",
+            "❓".cyan()
+        );
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(());
+        }
+    }
+
+    let results = run_restore(&manifest, dry_run)?;
+    let restored = results.iter().filter(|r| r.status.starts_with('✓')).count();
+    display_restore_table(results, use_colors)?;
+
+    if !dry_run {
+        println!(
+            "\n{} Restored {} directories",
+            "✨".green(),
+            restored.to_string().bold()
+        );
+    }
+    Ok(())
+}
+
+/// Remove the originals of verified `--safe` copies, after confirmation
+fn handle_organize_commit(dry_run: bool, yes: bool, use_colors: bool) -> Result<()> {
+    let pending = pending_safe_copies()?;
+    if pending.is_empty() {
+        println!(
+            "{} No safe-mode copies waiting to be committed.",
+            "✓".green()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {} safe-mode copies waiting; each original is removed only if both files still match the verified hash",
+        "🛡️".cyan(),
+        pending.len().to_string().yellow()
+    );
+
+    if dry_run {
+        println!("{} Dry run mode - no files will be removed\n", "🔍".cyan());
+    } else if !yes {
+        print!("\n{} Remove the verified originals? [y/N]: ", "❓".cyan());
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(());
+        }
+    }
+
+    let results = commit_safe_copies(dry_run)?;
+    let removed = results.iter().filter(|r| r.action.starts_with('✓')).count();
+    display_recovery_table(results, use_colors)?;
+
+    if !dry_run {
+        println!(
+            "\n{} Removed {} originals",
+            "✨".green(),
+            removed.to_string().bold()
+        );
+    }
+
+    Ok(())
+}
+
+/// Parse an age in days: a plain number, or with a unit like `2w`, `6m`
+/// (30-day months) or `1y`
+fn parse_days(value: &str) -> std::result::Result<u64, String> {
+    let value = value.trim();
+    let invalid = || format!("invalid age '{}' (use days, or 2w, 6m, 1y)", value);
+    let (amount, days) = match value.char_indices().last() {
+        Some((i, unit)) if unit.is_ascii_alphabetic() => {
+            let days = match unit.to_ascii_lowercase() {
+                'd' => 1,
+                'w' => 7,
+                'm' => 30,
+                'y' => 365,
+                _ => return Err(invalid()),
+            };
+            (&value[..i], days)
+        }
+        _ => (value, 1),
+    };
+    amount
+        .parse::<u64>()
+        .ok()
+        .and_then(|amount| amount.checked_mul(days))
+        .ok_or_else(invalid)
+}
+
+/// Show where an organized file came from (`--subcommand where`)
+fn handle_organize_where(matches: &ArgMatches, use_colors: bool) -> Result<()> {
+    let Some(file) = matches.get_one::<String>("query") else {
+        anyhow::bail!("where needs a file: --query <FILE>");
+    };
+
+    let origin = find_file_origin(&expand_home(file)?)?;
+    println!(
+        "{} {}",
+        "📍".cyan(),
+        origin.path.display().to_string().bold()
+    );
+
+    let Some(original) = origin.original() else {
+        println!("\n{}", "No record of organize moving this file.".yellow());
+        return Ok(());
+    };
+    println!(
+        "  {} {}",
+        "Originally:".dimmed(),
+        original.display().to_string().green()
+    );
+    if let Some(recorded) = &origin.recorded
+        && recorded != original
+    {
+        println!(
+            "  {} {}",
+            "Recorded on the file:".dimmed(),
+            recorded.display().to_string().green()
+        );
+    }
+
+    if !origin.moves.is_empty() {
+        display_file_moves_table(file_move_entries(&origin), use_colors)?;
+        if let Some(started) = origin.first_run {
+            println!(
+                "\n{} --subcommand undo --since {} moves it back, along with the rest of those runs",
+                "💡".yellow(),
+                unix_seconds(started)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Find files with identical content under the organize path and remove or
+/// hard-link the duplicates (`--subcommand dedupe`)
+fn handle_organize_dedupe(
+    matches: &ArgMatches,
+    search_path: Option<&str>,
+    use_colors: bool,
+) -> Result<()> {
+    let dry_run = matches.get_flag("dry_run");
+    let interactive = matches.get_flag("interactive");
+    let root = match search_path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let mode = if matches.get_flag("hardlink") {
+        DedupeMode::Hardlink
+    } else {
+        DedupeMode::Remove
+    };
+    // Duplicates go to the Trash unless asked otherwise, so undo can bring them back
+    let action = match matches.get_one::<String>("action") {
+        Some(spec) => Action::parse(spec)?,
+        None => Action::Trash,
+    };
+    if !matches!(action, Action::Trash | Action::Delete) {
+        anyhow::bail!("dedupe removes duplicates with --action trash or delete");
+    }
+
+    println!(
+        "{} Hashing files in: {}",
+        "🔍".cyan(),
+        root.display().to_string().yellow()
+    );
+    let mut groups = find_duplicate_files(&root)?;
+    if groups.is_empty() {
+        println!("{} No duplicate files found.", "✓".green());
+        return Ok(());
+    }
+
+    let duplicates: usize = groups.iter().map(|g| g.files.len() - 1).sum();
+    let wasted: u64 = groups.iter().map(|g| g.wasted()).sum();
+    println!(
+        "{} {} duplicates in {} groups, {} reclaimable",
+        "📑".cyan(),
+        duplicates.to_string().yellow(),
+        groups.len(),
+        format_size(wasted).bold().yellow()
+    );
+
+    if !interactive {
+        display_duplicate_files_table(duplicate_entries(&groups, None), use_colors)?;
+        if !dry_run && !matches.get_flag("yes") {
+            let verb = match mode {
+                DedupeMode::Hardlink => "Hard-link".to_string(),
+                DedupeMode::Remove => action.verb().to_string(),
+            };
+            print!(
+                "\n{} {} the {} duplicates, keeping the first copy of each group? [y/N]: ",
+                "❓".cyan(),
+                verb,
+                duplicates
+            );
+            std::io::Write::flush(&mut std::io::stdout())?;
+            let mut answer = String::new();
+            std::io::stdin().read_line(&mut answer)?;
+            if !answer.trim().eq_ignore_ascii_case("y") {
+                println!("{}", "Operation cancelled.".yellow());
+                return Ok(());
+            }
+        }
+    }
+    if dry_run {
+        println!("{} Dry run mode - no files will be changed", "🔍".cyan());
+    }
+
+    let statuses = resolve_duplicates(&root, &mut groups, action, mode, dry_run, interactive)?;
+    display_duplicate_files_table(duplicate_entries(&groups, Some(&statuses)), use_colors)?;
+    Ok(())
+}
+
+/// Pack files older than `--older-than` into zip bundles per category and
+/// year, removing the originals once verified (`--subcommand archive`)
+fn handle_organize_archive(
+    matches: &ArgMatches,
+    search_path: Option<&str>,
+    use_colors: bool,
+) -> Result<()> {
+    let dry_run = matches.get_flag("dry_run");
+    let Some(&days) = matches.get_one::<u64>("older_than") else {
+        anyhow::bail!("archive needs an age: --older-than DAYS (or 6m, 1y)");
+    };
+    let root = match search_path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    let mut exclude = crate::Config::load()?.get_str_list("organize.exclude");
+    exclude.extend(
+        matches
+            .get_many::<String>("exclude")
+            .into_iter()
+            .flatten()
+            .cloned(),
+    );
+
+    println!(
+        "{} Looking for files unmodified for {} days in: {}",
+        "🔍".cyan(),
+        days,
+        root.display().to_string().yellow()
+    );
+    let bundles = find_bundles(&root, Duration::from_secs(days * 86_400), &exclude)?;
+    if bundles.is_empty() {
+        println!("{} No files old enough to archive.", "✓".green());
+        return Ok(());
+    }
+
+    let files: usize = bundles.iter().map(|b| b.files.len()).sum();
+    let size: u64 = bundles.iter().flat_map(|b| &b.files).map(|f| f.size).sum();
+    println!(
+        "{} {} files ({}) into {} bundles",
+        "🗜️".cyan(),
+        files.to_string().yellow(),
+        format_size(size).bold().yellow(),
+        bundles.len()
+    );
+    if dry_run {
+        let statuses = write_bundles(&bundles, true)?;
+        display_bundles_table(bundle_entries(&bundles, Some(&statuses)), use_colors)?;
+        println!("{} Dry run mode - no files were changed", "🔍".cyan());
+        return Ok(());
+    }
+
+    display_bundles_table(bundle_entries(&bundles, None), use_colors)?;
+    if !matches.get_flag("yes") {
+        print!(
+            "\n{} Bundle the {} files and remove the originals once verified? [y/N]: ",
+            "❓".cyan(),
+            files
+        );
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(());
+        }
+    }
+
+    let statuses = write_bundles(&bundles, false)?;
+    display_bundles_table(bundle_entries(&bundles, Some(&statuses)), use_colors)?;
+    Ok(())
+}
+
+/// `--quarantine` in days, else `[organize] quarantine`
+fn quarantine_days(matches: &ArgMatches) -> Result<Option<u64>> {
+    if let Some(&days) = matches.get_one::<u64>("quarantine") {
+        return Ok(Some(days));
+    }
+    Ok(crate::Config::load()?
+        .get("organize.quarantine")
+        .and_then(|v| v.as_integer())
+        .and_then(|days| u64::try_from(days).ok()))
+}
+
+/// Remove the files that have waited in the `_Review` folders longer than
+/// the `--quarantine` days (`--subcommand purge`)
+fn handle_organize_purge(
+    matches: &ArgMatches,
+    search_path: Option<&str>,
+    use_colors: bool,
+) -> Result<()> {
+    let dry_run = matches.get_flag("dry_run");
+    let Some(days) = quarantine_days(matches)? else {
+        anyhow::bail!("purge needs the retention: --quarantine DAYS (or [organize] quarantine)");
+    };
+    let action = match matches.get_one::<String>("action") {
+        Some(spec) => Action::parse(spec)?,
+        None => Action::Delete,
+    };
+    if !matches!(action, Action::Trash | Action::Delete) {
+        anyhow::bail!("purge removes files with --action delete or trash");
+    }
+    let root = match search_path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+
+    println!(
+        "{} Looking for files quarantined over {} days ago in: {}",
+        "🔍".cyan(),
+        days,
+        root.join(REVIEW_FOLDER).display().to_string().yellow()
+    );
+    let files = find_quarantined(&root, Duration::from_secs(days * 86_400))?;
+    if files.is_empty() {
+        println!("{} Nothing has waited for review that long.", "✓".green());
+        return Ok(());
+    }
+
+    let size: u64 = files.iter().map(|f| f.size).sum();
+    println!(
+        "{} {} files ({}) past the retention",
+        "🗃️".cyan(),
+        files.len().to_string().yellow(),
+        format_size(size).bold().yellow()
+    );
+    if dry_run {
+        let results = purge_quarantined(&root, &files, action, true)?;
+        display_purge_table(results, use_colors)?;
+        println!("{} Dry run mode - no files were changed", "🔍".cyan());
+        return Ok(());
+    }
+
+    if !matches.get_flag("yes") {
+        let results = purge_quarantined(&root, &files, action.clone(), true)?;
+        display_purge_table(results, use_colors)?;
+        print!(
+            "\n{} {} these {} files? [y/N]: ",
+            "❓".cyan(),
+            action.verb(),
+            files.len()
+        );
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(());
+        }
+    }
+
+    let results = purge_quarantined(&root, &files, action, false)?;
+    let failures = results.iter().filter(|r| r.status.starts_with('✗')).count();
+    display_purge_table(results, use_colors)?;
+    println!(
+        "\n{} Purged {} files ({}) from review",
+        "✨".green(),
+        (files.len() - failures).to_string().bold(),
+        format_size(size)
+    );
+    Ok(())
+}
+
+/// Pull the single file out of each one-off subfolder into the organize
+/// folder, so the next run can categorize it (`--subcommand flatten`)
+fn handle_organize_flatten(
+    matches: &ArgMatches,
+    search_path: Option<&str>,
+    use_colors: bool,
+) -> Result<()> {
+    let dry_run = matches.get_flag("dry_run");
+    let root = match search_path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    };
+    if is_dev_folder(&root) {
+        anyhow::bail!("{} is a development folder", root.display());
+    }
+    let mut exclude = crate::Config::load()?.get_str_list("organize.exclude");
+    exclude.extend(
+        matches
+            .get_many::<String>("exclude")
+            .into_iter()
+            .flatten()
+            .cloned(),
+    );
+
+    println!(
+        "{} Looking for folders holding a single file in: {}",
+        "🔍".cyan(),
+        root.display().to_string().yellow()
+    );
+    let nested = find_nested_folders(&root, &exclude)?;
+    if nested.is_empty() {
+        println!("{} No folders to flatten.", "✓".green());
+        return Ok(());
+    }
+
+    println!(
+        "{} {} folders to flatten into {}",
+        "📂".cyan(),
+        nested.len().to_string().yellow(),
+        root.display()
+    );
+    if dry_run {
+        display_flatten_table(flatten_folders(&root, &nested, true)?, use_colors)?;
+        println!("{} Dry run mode - no files were changed", "🔍".cyan());
+        return Ok(());
+    }
+
+    if !matches.get_flag("yes") {
+        display_flatten_table(flatten_folders(&root, &nested, true)?, use_colors)?;
+        print!(
+            "\n{} Move the {} files up and remove their folders? [y/N]: ",
+            "❓".cyan(),
+            nested.len()
+        );
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(());
+        }
+    }
+
+    display_flatten_table(flatten_folders(&root, &nested, false)?, use_colors)?;
+    println!(
+        "{} Run organize to file them into categories",
+        "💡".yellow()
+    );
+    Ok(())
+}
+
+/// Install a launch agent that organizes the folders on its own
+/// (`--subcommand schedule`)
+///
+/// The agent runs this executable with the folders and the organize flags
+/// given here; `--dry-run` prints the agent instead of installing it.
+fn handle_organize_schedule(matches: &ArgMatches, search_path: Option<&str>) -> Result<()> {
+    if matches.get_one::<String>("preset").is_some() {
+        anyhow::bail!("--preset can't be scheduled; schedule its folders with --path instead");
+    }
+    let paths: Vec<String> = match matches.get_many::<String>("path") {
+        Some(paths) => paths.cloned().collect(),
+        None => search_path.map(str::to_string).into_iter().collect(),
+    };
+    if paths.is_empty() {
+        anyhow::bail!("schedule needs the folder to organize: --path DIR");
+    }
+    if paths.len() > 1 && matches.get_one::<String>("dest").is_none() {
+        anyhow::bail!("scheduling several folders needs --dest, the folder to organize them into");
+    }
+    let paths: Vec<PathBuf> = paths
+        .iter()
+        .map(|path| Ok(std::path::absolute(crate::expand_home(path)?)?))
+        .collect::<Result<_>>()?;
+    if let Some(missing) = paths.iter().find(|path| !path.is_dir()) {
+        anyhow::bail!("{} isn't a folder", missing.display());
+    }
+
+    let interval = if matches.get_flag("hourly") {
+        ScheduleInterval::Hourly
+    } else if matches.get_flag("weekly") {
+        ScheduleInterval::Weekly
+    } else {
+        ScheduleInterval::Daily
+    };
+
+    let mut arguments = vec![
+        std::env::current_exe()?.display().to_string(),
+        "--mode".to_string(),
+        "organize".to_string(),
+    ];
+    for path in &paths {
+        arguments.extend(["--path".to_string(), path.display().to_string()]);
+    }
+    arguments.extend(["--plain", "--limit", "0"].map(str::to_string));
+    if let Some(&days) = matches.get_one::<u64>("older_than") {
+        arguments.extend(["--older-than".to_string(), format!("{}d", days)]);
+    }
+    if let Some(&days) = matches.get_one::<u64>("quarantine") {
+        arguments.extend(["--quarantine".to_string(), format!("{}d", days)]);
+    }
+    for (id, flag) in [
+        ("dest", "--dest"),
+        ("action", "--action"),
+        ("by", "--by"),
+        ("date", "--date"),
+        ("skip_over", "--skip-over"),
+        ("min_age", "--min-age"),
+        ("rename", "--rename"),
+        ("link", "--link"),
+    ] {
+        if let Some(value) = matches.get_one::<String>(id) {
+            let value = match id {
+                "dest" => std::path::absolute(crate::expand_home(value)?)?
+                    .display()
+                    .to_string(),
+                _ => value.clone(),
+            };
+            arguments.extend([flag.to_string(), value]);
+        }
+    }
+    for glob in matches.get_many::<String>("exclude").into_iter().flatten() {
+        arguments.extend(["--exclude".to_string(), glob.clone()]);
+    }
+    if matches.get_flag("prefer_content") {
+        arguments.push("--prefer-content".to_string());
+    }
+    // Nobody is there to answer a prompt
+    let on_conflict = match matches.get_one::<String>("on_conflict") {
+        Some(strategy) => Some(strategy.clone()),
+        None => crate::Config::load()?
+            .get_str("organize.on_conflict")
+            .map(str::to_string),
+    };
+    match on_conflict.as_deref() {
+        Some("prompt" | "ask") => arguments.extend(["--on-conflict", "skip"].map(str::to_string)),
+        Some(strategy) => arguments.extend(["--on-conflict".to_string(), strategy.to_string()]),
+        None => {}
+    }
+
+    let schedule = OrganizeSchedule::new(paths, interval, arguments)?;
+    if matches.get_flag("dry_run") {
+        println!(
+            "{} Would write {} and load it:\n",
+            "🔍".cyan(),
+            schedule.agent_path()?.display().to_string().cyan()
+        );
+        println!("{}", schedule.plist_xml()?);
+        return Ok(());
+    }
+
+    let agent = install_schedule(&schedule)?;
+    println!(
+        "{} Scheduled organizing {} {}",
+        "✓".green(),
+        schedule
+            .paths
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+            .cyan(),
+        schedule.interval.label().yellow()
+    );
+    println!("   Agent: {}", agent.display().to_string().dimmed());
+    println!("   Log:   {}", schedule.log.display().to_string().dimmed());
+    println!(
+        "   {} --subcommand schedule-status shows its runs, schedule-remove takes it out",
+        "💡".yellow()
+    );
+    Ok(())
+}
+
+/// Totals and monthly trends of the recorded organize runs
+/// (`--subcommand stats`); `--limit` months are shown, `--path` keeps the runs
+/// into those folders
+fn handle_organize_stats(matches: &ArgMatches, use_colors: bool) -> Result<()> {
+    println!("{}", "📈 Organize Stats".bold().cyan());
+    println!("{}", "─".repeat(50).dimmed());
+
+    let roots: Vec<PathBuf> = matches
+        .get_many::<String>("path")
+        .into_iter()
+        .flatten()
+        .map(|path| Ok(std::path::absolute(crate::expand_home(path)?)?))
+        .collect::<Result<_>>()?;
+    let runs: Vec<_> = load_organize_history()?
+        .into_iter()
+        .filter(|run| roots.is_empty() || roots.contains(&run.root))
+        .collect();
+    if runs.is_empty() {
+        println!(
+            "{}",
+            "No organize runs recorded yet; runs that move files are added as they finish."
+                .yellow()
+        );
+        return Ok(());
+    }
+
+    let months = organize_month_entries(&runs);
+    let limit = matches
+        .get_one::<String>("limit")
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(12);
+    let shown = months[months.len().saturating_sub(limit.max(1))..].to_vec();
+    display_organize_months_table(shown, use_colors)?;
+
+    println!("\n{}", "📂 Categories".bold().cyan());
+    println!("{}", "─".repeat(50).dimmed());
+    display_organize_categories_table(organize_category_entries(&runs), use_colors)?;
+
+    let files: usize = runs.iter().map(|run| run.files).sum();
+    let bytes: u64 = runs.iter().map(|run| run.bytes).sum();
+    println!(
+        "\n{} {} files ({}) organized in {} runs since {}, about {} per month with runs",
+        "📊".cyan(),
+        files.to_string().bold(),
+        format_size(bytes).yellow(),
+        runs.len().to_string().cyan(),
+        format_utc(std::time::UNIX_EPOCH + Duration::from_secs(runs[0].timestamp.max(0) as u64))
+            .cyan(),
+        (files / months.len()).to_string().bold()
+    );
+    println!(
+        "   {} History: {}",
+        "💡".yellow(),
+        organize_history_path()?.display().to_string().dimmed()
+    );
+    Ok(())
+}
+
+/// List the installed organize schedules (`--subcommand schedule-status`)
+fn handle_schedule_status(use_colors: bool) -> Result<()> {
+    let schedules = find_schedules()?;
+    if schedules.is_empty() {
+        println!("{} No organize schedules installed.", "✓".green());
+        return Ok(());
+    }
+    display_schedules_table(schedule_entries(&schedules), use_colors)?;
+    Ok(())
+}
+
+/// Remove the schedule of the `--path` folders, or the only one installed
+/// (`--subcommand schedule-remove`)
+fn handle_schedule_remove(matches: &ArgMatches, dry_run: bool) -> Result<()> {
+    let schedules = find_schedules()?;
+    let paths: Vec<PathBuf> = matches
+        .get_many::<String>("path")
+        .into_iter()
+        .flatten()
+        .map(|path| Ok(std::path::absolute(crate::expand_home(path)?)?))
+        .collect::<Result<_>>()?;
+
+    let schedule = match schedules.as_slice() {
+        [] => {
+            println!("{} No organize schedules installed.", "✓".green());
+            return Ok(());
+        }
+        [only] if paths.is_empty() => only,
+        _ if paths.is_empty() => {
+            anyhow::bail!(
+                "{} schedules are installed; pick one with --path (see --subcommand schedule-status)",
+                schedules.len()
+            );
+        }
+        _ => match schedules.iter().find(|s| s.paths == paths) {
+            Some(schedule) => schedule,
+            None => anyhow::bail!(
+                "No organize schedule for {}",
+                paths
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        },
+    };
+
+    let agent = schedule.agent_path()?;
+    if dry_run {
+        println!(
+            "{} Would unload and remove {}",
+            "🔍".cyan(),
+            agent.display().to_string().cyan()
+        );
+        return Ok(());
+    }
+    remove_schedule(schedule)?;
+    println!(
+        "{} Removed the schedule for {} ({})",
+        "✓".green(),
+        schedule
+            .paths
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+            .cyan(),
+        agent.display().to_string().dimmed()
+    );
+    Ok(())
+}
+
+/// Run a plan written by `--plan` (`--subcommand apply`)
+fn handle_organize_apply(matches: &ArgMatches, use_colors: bool) -> Result<()> {
+    let dry_run = matches.get_flag("dry_run");
+    let Some(path) = matches.get_one::<String>("plan") else {
+        anyhow::bail!("apply needs the plan to run: --plan FILE");
+    };
+    let plan = OrganizePlan::load(Path::new(path))?;
+    if plan.moves.is_empty() {
+        println!("{} The plan has no moves.", "✓".green());
+        return Ok(());
+    }
+
+    let size: u64 = plan.moves.iter().map(|m| m.size).sum();
+    println!(
+        "{} Plan for {}: {} {} files ({})",
+        "📝".cyan(),
+        plan.root.display().to_string().yellow(),
+        plan.action.to_string().bold(),
+        plan.moves.len().to_string().yellow(),
+        format_size(size).bold().yellow()
+    );
+    if !dry_run && !matches.get_flag("yes") {
+        print!("\n{} Apply the plan? [y/N]: ", "❓".cyan());
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            println!("{}", "Operation cancelled.".yellow());
+            return Ok(());
+        }
+    }
+
+    let results = apply_plan(plan, dry_run, matches.get_flag("verbose"))?;
+    display_organize_table(results, use_colors)?;
+    Ok(())
+}
+
+pub fn handle_organize_mode(matches: &ArgMatches) -> Result<()> {
+    let search_path = configured_search_path(matches, "organize")?;
+    let search_path = search_path.as_deref();
+    let dry_run = matches.get_flag("dry_run");
+    let verbose = matches.get_flag("verbose");
+    let interactive = matches.get_flag("interactive");
+    let use_colors = !matches.get_flag("plain");
+
+    if let Some(subcommand) = matches.get_one::<String>("subcommand") {
+        match subcommand.as_str() {
+            "recover" | "undo" => {
+                return handle_journal_subcommand(
+                    subcommand,
+                    ORGANIZE_JOURNAL_KIND,
+                    matches,
+                    use_colors,
+                );
+            }
+            "commit" => {
+                return handle_organize_commit(dry_run, matches.get_flag("yes"), use_colors);
+            }
+            "dedupe" => {
+                return handle_organize_dedupe(matches, search_path, use_colors);
+            }
+            "where" => {
+                return handle_organize_where(matches, use_colors);
+            }
+            "archive" => {
+                return handle_organize_archive(matches, search_path, use_colors);
+            }
+            "apply" => {
+                return handle_organize_apply(matches, use_colors);
+            }
+            "purge" => {
+                return handle_organize_purge(matches, search_path, use_colors);
+            }
+            "flatten" => {
+                return handle_organize_flatten(matches, search_path, use_colors);
+            }
+            "schedule" => {
+                return handle_organize_schedule(matches, search_path);
+            }
+            "stats" => {
+                return handle_organize_stats(matches, use_colors);
+            }
+            "schedule-status" => {
+                return handle_schedule_status(use_colors);
+            }
+            "schedule-remove" => {
+                return handle_schedule_remove(matches, dry_run);
+            }
+            _ => {
+                println!(
+                    "{}",
+                    format!(
+                        "Unknown subcommand: {}. Use: recover, commit, undo, dedupe, where, archive, apply, flatten, schedule, schedule-status, schedule-remove, stats, purge",
+                        subcommand
+                    )
+                    .yellow()
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    let report_count = matches
+        .get_one::<String>("limit")
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(LARGE_FILE_REPORT_COUNT);
+    let skip_over = matches
+        .get_one::<String>("skip_over")
+        .map(|s| parse_size(s))
+        .transpose()?;
+
+    let action = matches.get_one::<String>("action").map(|s| s.as_str());
+    let method = if matches.get_flag("safe") {
+        if action.is_some() {
+            anyhow::bail!("--safe copies files and can't be combined with --action");
+        }
+        OrganizeMethod::SafeCopy
+    } else {
+        OrganizeMethod::Action(Action::load(action, "organize", Action::Move { to: None })?)
+    };
+
+    let by = OrganizeBy::load(
+        matches.get_one::<String>("by").map(|s| s.as_str()),
+        matches.get_one::<String>("date").map(|s| s.as_str()),
+    )?;
+
+    let preset = matches
+        .get_one::<String>("preset")
+        .map(|name| OrganizePreset::parse(name))
+        .transpose()?;
+
+    let on_conflict_flag = matches.get_one::<String>("on_conflict").map(|s| s.as_str());
+    let on_conflict = match preset {
+        // The preset's default gives way to [organize] on_conflict
+        Some(preset)
+            if on_conflict_flag.is_none()
+                && crate::Config::load()?
+                    .get_str("organize.on_conflict")
+                    .is_none() =>
+        {
+            preset.on_conflict()
+        }
+        _ => OnConflict::load(on_conflict_flag)?,
+    };
+    if on_conflict == OnConflict::Overwrite && matches!(method, OrganizeMethod::SafeCopy) {
+        anyhow::bail!(
+            "--safe never overwrites files and can't be combined with on_conflict overwrite"
+        );
+    }
+
+    let prefer_content = matches.get_flag("prefer_content")
+        || crate::Config::load()?
+            .get_bool("organize.prefer_content")
+            .unwrap_or(false);
+
+    let link = match matches.get_one::<String>("link") {
+        Some(mode) => Some(LinkMode::parse(mode)?),
+        None => crate::Config::load()?
+            .get_str("organize.link")
+            .map(LinkMode::parse)
+            .transpose()?,
+    };
+    if link.is_some()
+        && !matches!(
+            method,
+            OrganizeMethod::Action(Action::Move { .. } | Action::Archive { .. })
+        )
+    {
+        anyhow::bail!("--link only works when files are moved or archived");
+    }
+
+    let mut exclude = crate::Config::load()?.get_str_list("organize.exclude");
+    exclude.extend(
+        matches
+            .get_many::<String>("exclude")
+            .into_iter()
+            .flatten()
+            .cloned(),
+    );
+
+    let plan = matches.get_one::<String>("plan").map(PathBuf::from);
+    if plan.is_some() && matches!(method, OrganizeMethod::SafeCopy) {
+        anyhow::bail!("--plan records moves and can't be combined with --safe");
+    }
+
+    let rename = RenameRules::load(
+        matches.get_one::<String>("rename").map(|s| s.as_str()),
+        matches.get_one::<String>("date").map(|s| s.as_str()),
+    )?;
+
+    let sources: Vec<PathBuf> = match matches.get_many::<String>("path") {
+        Some(paths) => paths.map(PathBuf::from).collect(),
+        None => search_path.map(PathBuf::from).into_iter().collect(),
+    };
+    let dest = match matches.get_one::<String>("dest") {
+        Some(dest) => Some(PathBuf::from(dest)),
+        None => crate::Config::load()?
+            .get_str("organize.dest")
+            .map(crate::expand_home)
+            .transpose()?,
+    };
+
+    let quarantine = quarantine_days(matches)?.map(|days| Duration::from_secs(days * 86_400));
+    let min_age = match matches.get_one::<String>("min_age") {
+        Some(age) => parse_min_age(age)?,
+        None => match crate::Config::load()?.get_str("organize.min_age") {
+            Some(age) => parse_min_age(age)?,
+            None => DEFAULT_MIN_AGE,
+        },
+    };
+
+    let options = OrganizeOptions {
+        method,
+        by,
+        on_conflict,
+        prefer_content,
+        dry_run,
+        verbose,
+        interactive,
+        report_count,
+        skip_over,
+        link,
+        exclude,
+        rename,
+        plan,
+        dest,
+        destinations: CategoryDestinations::load()?,
+        older_than: matches
+            .get_one::<u64>("older_than")
+            .map(|&days| Duration::from_secs(days * 86_400)),
+        min_age,
+        quarantine,
+    };
+
+    if let Some(preset) = preset {
+        if matches.get_many::<String>("path").is_some() {
+            anyhow::bail!("--preset picks its own folders and can't be combined with --path");
+        }
+        return run_organize_preset(preset, options, interactive, use_colors);
+    }
+
+    let results = organize_files(&sources, options)?;
+
+    if !results.is_empty() && !interactive {
+        display_organize_table(results, use_colors)?;
+    }
+
+    Ok(())
+}
+
+/// Organize each of a preset's folders in place (or all of them into
+/// `--dest`), then print one table and a summary per folder
+fn run_organize_preset(
+    preset: OrganizePreset,
+    options: OrganizeOptions,
+    interactive: bool,
+    use_colors: bool,
+) -> Result<()> {
+    let sources = preset.sources()?;
+    if sources.is_empty() {
+        println!("{} None of the preset's folders exist.", "✓".green());
+        return Ok(());
+    }
+    if options.plan.is_some() && options.dest.is_none() && sources.len() > 1 {
+        anyhow::bail!("--plan writes one plan, so a preset needs --dest to go with it");
+    }
+
+    let runs: Vec<(Vec<PathBuf>, Vec<OrganizeEntry>)> = if options.dest.is_some() {
+        vec![(sources.clone(), organize_files(&sources, options)?)]
+    } else {
+        let mut runs = Vec::new();
+        for source in sources {
+            let results = organize_files(std::slice::from_ref(&source), options.clone())?;
+            runs.push((vec![source], results));
+            println!();
+        }
+        runs
+    };
+
+    let organized = |results: &[OrganizeEntry]| {
+        results
+            .iter()
+            .filter(|r| r.status.starts_with('✓') || r.status.starts_with("Would"))
+            .count()
+    };
+    let all: Vec<OrganizeEntry> = runs.iter().flat_map(|(_, r)| r.iter().cloned()).collect();
+    if !all.is_empty() && !interactive {
+        display_organize_table(all.clone(), use_colors)?;
+    }
+
+    println!("\n{} Summary:", "📋".cyan());
+    for (sources, results) in &runs {
+        let names: Vec<String> = sources.iter().map(|s| s.display().to_string()).collect();
+        let done = organized(results);
+        let line = if results.is_empty() {
+            "nothing to organize".dimmed().to_string()
+        } else if done == results.len() {
+            format!("{} files", done.to_string().green())
+        } else {
+            format!(
+                "{} files, {} left in place",
+                done.to_string().green(),
+                (results.len() - done).to_string().yellow()
+            )
+        };
+        println!("  {} {}: {}", "•".dimmed(), names.join(", ").cyan(), line);
+    }
+    println!(
+        "  {} {} files",
+        "Total:".bold(),
+        organized(&all).to_string().bold().green()
+    );
+    Ok(())
+}
+
+/// Open bookmarks in the default browser, reporting each one
+///
+/// Bookmarklets and browser-internal pages can't be handed to the opener and
+/// are skipped; a failed open is reported and the rest still open.
+fn open_bookmarks(bookmarks: &[&Bookmark]) {
+    for bookmark in bookmarks {
+        if is_internal_url(&bookmark.url) {
+            println!(
+                "{} Skipping {} (bookmarklets and internal pages can't be opened)",
+                "⏭️".yellow(),
+                bookmark.name.dimmed()
+            );
+            continue;
+        }
+
+        match open_url(&bookmark.url) {
+            Ok(()) => println!("{} Opened {}", "🌐".cyan(), bookmark.url.blue()),
+            Err(e) => println!("{} {:#}", "✗ Error:".red(), anyhow::Error::from(e)),
+        }
+    }
+}
+
+/// Show which rules match a URL/title and which one decides the category
+/// Triage Safari's Reading List and optionally export it
+///
+/// `.html` exports are Chrome-importable and grouped by category like
+/// export-html; anything else goes through the reading-list digest writer.
+fn handle_safari_reading_list(
+    matches: &ArgMatches,
+    use_colors: bool,
+    limit: Option<usize>,
+) -> Result<()> {
+    println!("{}", "🧭 Safari Reading List".bold().cyan());
+    println!("{}", "─".repeat(50).dimmed());
+
+    let path = get_safari_bookmarks_path()?;
+    let registry = CategoryRegistry::load()?;
+    let mut items = parse_safari_reading_list(&path, &registry)?;
+    println!(
+        "{} Found {} items in {}",
+        "✅".green(),
+        items.len().to_string().yellow(),
+        path.display().to_string().cyan()
+    );
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let old_after = match matches.get_one::<u64>("older_than") {
+        Some(&days) => days,
+        None => old_after_days(&crate::Config::load()?)?,
+    };
+    let unread = items.iter().filter(|item| item.is_unread()).count();
+    let old = items.iter().filter(|item| item.is_old(old_after)).count();
+
+    if matches.get_flag("unread") {
+        items.retain(|item| item.is_unread());
+    }
+    if let Some(&days) = matches.get_one::<u64>("older_than") {
+        items.retain(|item| item.age_days().is_some_and(|age| age > days));
+    }
+    if let Some(lim) = limit {
+        items.truncate(lim);
+    }
+
+    if items.is_empty() {
+        println!("{}", "No items match the filters.".green());
+    } else {
+        display_safari_reading_table(safari_reading_entries(&items, old_after), use_colors)?;
+    }
+
+    println!(
+        "\n{} {} unread, {} unread for more than {} days",
+        "📊".cyan(),
+        unread.to_string().yellow(),
+        old.to_string().red(),
+        old_after
+    );
+
+    let Some(output) = matches.get_one::<String>("output") else {
+        if old > 0 {
+            println!(
+                "{} Use --unread --older-than {} --output stale.html to export the old ones",
+                "💡".yellow(),
+                old_after
+            );
+        }
+        return Ok(());
+    };
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let is_html = Path::new(output)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("html") || e.eq_ignore_ascii_case("htm"));
+    if is_html {
+        let bookmarks: Vec<Bookmark> = items.iter().map(|item| item.bookmark.clone()).collect();
+        export_to_chrome_html(
+            &bookmarks,
+            &ExportTheme::load()?,
+            &HashMap::new(),
+            false,
+            Some(output),
+        )?;
+    } else {
+        let mut digest: Vec<ReadingItem> = items
+            .iter()
+            .map(|item| ReadingItem {
+                bookmark: &item.bookmark,
+                reason: "safari reading list",
+                // Safari's saved excerpt stands in until --fetch gets the full text
+                content: item
+                    .preview
+                    .clone()
+                    .map(|preview| Ok(vec![ArticleBlock::Paragraph(preview)])),
+            })
+            .collect();
+        if matches.get_flag("fetch") {
+            fetch_reading_list(&mut digest, &http_client(matches)?);
+        }
+        write_reading_list(&digest, output)?;
+    }
+    println!(
+        "{} Wrote {} items to {}",
+        "✅".green(),
+        items.len().to_string().yellow(),
+        output.cyan()
+    );
+
+    Ok(())
+}
+
+fn handle_explain(matches: &ArgMatches, use_colors: bool) -> Result<()> {
+    let Some(url) = matches.get_one::<String>("query") else {
+        anyhow::bail!("explain needs a URL: --query <URL> [--title <TITLE>]");
+    };
+    let title = matches
+        .get_one::<String>("title")
+        .map(|s| s.as_str())
+        .unwrap_or("");
+
+    println!("{}", "🔎 Explain Categorization".bold().cyan());
+    println!("{}", "─".repeat(50).dimmed());
+    println!("  {} {}", "URL:".dimmed(), url.blue());
+    if !title.is_empty() {
+        println!("  {} {}", "Title:".dimmed(), title.bold());
+    }
+
+    let registry = CategoryRegistry::load()?;
+    let (category, confidence) = registry.categorize_scored(url, title);
 
-    let results = organize_files(search_path, dry_run, verbose, interactive)?;
+    if is_internal_url(url) {
+        println!(
+            "\n{} Bookmarklets and browser-internal pages skip the rules",
+            "💡".yellow()
+        );
+    } else {
+        let entries = explain_rules(registry.rules(), url, title);
+        if entries.is_empty() {
+            println!("\n{}", "No rule matched.".yellow());
+        } else {
+            display_rule_matches_table(entries, use_colors)?;
+        }
+    }
 
-    if !results.is_empty() && !interactive {
-        let use_colors = !matches.get_flag("plain");
-        display_organize_table(results, use_colors)?;
+    println!(
+        "\n{} Category: {} ({:.0}% confidence)",
+        "📂".cyan(),
+        category.to_string().green().bold(),
+        confidence * 100.0
+    );
+    if category.folder_name().starts_with("Language/") {
+        println!(
+            "{} No rule matched; the title's language picked the folder (language_folders)",
+            "💡".yellow()
+        );
     }
 
     Ok(())
@@ -343,13 +3426,80 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
     let use_colors = !matches.get_flag("plain");
     let verbose = matches.get_flag("verbose");
     let dry_run = matches.get_flag("dry_run");
+    let yes = matches.get_flag("yes");
     let limit = matches
         .get_one::<String>("limit")
         .and_then(|s| s.parse::<usize>().ok());
+    let domain_grouping = match matches.get_one::<String>("group_by").map(|s| s.as_str()) {
+        Some("subdomain") => DomainGrouping::Subdomain,
+        _ => DomainGrouping::Domain,
+    };
+
+    // Explaining a URL doesn't need the bookmarks file
+    if subcommand == "explain" {
+        return handle_explain(matches, use_colors);
+    }
+
+    // Safari's Reading List is read from Safari's own file
+    if subcommand == "safari-reading-list" {
+        return handle_safari_reading_list(matches, use_colors, limit);
+    }
 
     // Parse bookmarks
     println!("{} Loading Chrome bookmarks...", "📖".cyan());
-    let (bookmarks, folders) = parse_bookmarks()?;
+    let (mut bookmarks, mut folders) = parse_bookmarks()?;
+
+    let roots: Vec<String> = matches
+        .get_many::<String>("root")
+        .map(|names| {
+            names
+                .map(|name| {
+                    parse_root(name).map(|r| r.to_string()).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Invalid root '{}'. Use: bookmark_bar (bar), other, synced (mobile)",
+                            name
+                        )
+                    })
+                })
+                .collect::<Result<_>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    if !roots.is_empty() {
+        let before = bookmarks.len();
+        bookmarks.retain(|b| b.in_roots(&roots));
+        folders.retain(|f| in_roots(&f.path, &roots));
+        println!(
+            "{} Limiting to {} ({} bookmarks under other roots skipped)",
+            "🗂️".cyan(),
+            roots.join(", ").cyan(),
+            (before - bookmarks.len()).to_string().yellow()
+        );
+    }
+
+    if matches.get_flag("exclude_mobile") {
+        let before = bookmarks.len();
+        bookmarks.retain(|b| !b.is_mobile());
+        folders.retain(|f| !is_mobile_root(&f.path));
+        println!(
+            "{} Excluding {} bookmarks under mobile/synced roots",
+            "📱".cyan(),
+            (before - bookmarks.len()).to_string().yellow()
+        );
+    }
+
+    if matches.get_flag("exclude_sensitive") {
+        let filter = SensitiveFilter::load()?;
+        let before = bookmarks.len();
+        bookmarks.retain(|b| filter.reason(b).is_none());
+        println!(
+            "{} Excluding {} sensitive bookmarks",
+            "🔒".cyan(),
+            (before - bookmarks.len()).to_string().yellow()
+        );
+    }
+
     println!(
         "{} Found {} bookmarks in {} folders\n",
         "✅".green(),
@@ -359,7 +3509,7 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
 
     match subcommand {
         "stats" => {
-            let stats = get_bookmark_stats(&bookmarks, &folders);
+            let stats = get_bookmark_stats(&bookmarks, &folders, domain_grouping);
 
             println!("{}", "📊 Bookmark Statistics".bold().cyan());
             println!("{}", "─".repeat(50).dimmed());
@@ -378,6 +3528,11 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
                 "🔄".cyan(),
                 stats.duplicates.to_string().yellow()
             );
+            println!(
+                "  {} Mobile copies of desktop bookmarks: {}",
+                "📱".cyan(),
+                stats.sync_orphans.to_string().yellow()
+            );
             println!(
                 "  {} Empty folders: {}",
                 "📂".cyan(),
@@ -394,10 +3549,15 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
                 stats.by_domain.len().to_string().yellow()
             );
 
+            // Show per-root breakdown
+            println!("\n{}", "🗂️  By Root".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+            display_root_stats_table(get_root_stats(&bookmarks, &folders), use_colors)?;
+
             // Show top domains
             println!("\n{}", "🔝 Top 10 Domains".bold().cyan());
             println!("{}", "─".repeat(50).dimmed());
-            let domain_stats = get_domain_stats(&bookmarks);
+            let domain_stats = get_domain_stats(&bookmarks, domain_grouping);
             let top_domains: Vec<_> = domain_stats.into_iter().take(10).collect();
             display_domain_stats_table(top_domains, use_colors)?;
 
@@ -411,7 +3571,16 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
             println!("{}", "🔄 Duplicate Bookmarks".bold().cyan());
             println!("{}", "─".repeat(50).dimmed());
 
-            let duplicates = find_duplicates(&bookmarks);
+            let duplicates = if matches.get_flag("include_internal") {
+                find_duplicates(&bookmarks)
+            } else {
+                let web: Vec<Bookmark> = bookmarks
+                    .iter()
+                    .filter(|b| !b.is_internal())
+                    .cloned()
+                    .collect();
+                find_duplicates(&web)
+            };
             if duplicates.is_empty() {
                 println!("{}", "No duplicate bookmarks found!".green());
             } else {
@@ -451,7 +3620,7 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
                     );
                 }
             } else {
-                let domain_stats = get_domain_stats(&bookmarks);
+                let domain_stats = get_domain_stats(&bookmarks, domain_grouping);
                 let limited: Vec<_> = if let Some(lim) = limit {
                     domain_stats.into_iter().take(lim).collect()
                 } else {
@@ -460,6 +3629,164 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
                 display_domain_stats_table(limited, use_colors)?;
             }
         }
+        "folders" if matches.get_one::<String>("format").map(|s| s.as_str()) == Some("tree") => {
+            println!("{}", "📁 Folder Tree".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            for tree in get_folder_tree(&bookmarks, &folders) {
+                print!("{}", render_tree(&tree));
+            }
+        }
+        "folders" => {
+            println!("{}", "📁 Folder Statistics".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let check_links = matches.get_flag("check_links");
+            let dead = if check_links {
+                let notifier = Notifier::load(matches.get_flag("notify"))?;
+                let dead = find_dead_bookmarks(
+                    &bookmarks,
+                    &http_client(matches)?,
+                    &status_policy(matches)?,
+                    verbose,
+                )?;
+                notifier.finish(
+                    "Folder link check finished",
+                    &format!("{} dead links in {} bookmarks", dead.len(), bookmarks.len()),
+                );
+                Some(dead)
+            } else {
+                None
+            };
+
+            let folder_stats = get_folder_stats(&bookmarks, &folders, dead.as_deref());
+            let limited: Vec<_> = if let Some(lim) = limit {
+                folder_stats.into_iter().take(lim).collect()
+            } else {
+                folder_stats
+            };
+            let count = limited.len();
+            display_folder_stats_table(limited, use_colors)?;
+            println!(
+                "\n{} Showing {} folders (messiest first)",
+                "📊".cyan(),
+                count.to_string().yellow()
+            );
+            if !check_links {
+                println!(
+                    "{} Add --check-links to count dead links per folder",
+                    "💡".yellow()
+                );
+            }
+        }
+        "snapshot" => {
+            println!("{}", "📸 Bookmark Snapshot".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let dead = if matches.get_flag("check_links") {
+                let dead = find_dead_bookmarks(
+                    &bookmarks,
+                    &http_client(matches)?,
+                    &status_policy(matches)?,
+                    verbose,
+                )?;
+                Some(
+                    dead.iter()
+                        .filter(|(_, status)| !is_parked_status(status))
+                        .count(),
+                )
+            } else {
+                None
+            };
+
+            let snapshot = HistorySnapshot::capture(&bookmarks, &folders, dead);
+            record_snapshot(&snapshot)?;
+
+            // Show the new row, with its change against the previous snapshot
+            let history = load_history()?;
+            let recent = &history[history.len().saturating_sub(2)..];
+            let latest: Vec<_> = trend_entries(recent)
+                .into_iter()
+                .skip(recent.len().saturating_sub(1))
+                .collect();
+            display_trends_table(latest, use_colors)?;
+            println!(
+                "\n{} Recorded snapshot in {}",
+                "✅".green(),
+                history_path()?.display().to_string().cyan()
+            );
+            if dead.is_none() {
+                println!(
+                    "{} Add --check-links to also record the dead-link count",
+                    "💡".yellow()
+                );
+            }
+        }
+        "trends" => {
+            println!("{}", "📈 Bookmark Trends".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let history = load_history()?;
+            if history.is_empty() {
+                println!(
+                    "{}",
+                    "No snapshots recorded yet. Run --subcommand snapshot to start the history."
+                        .yellow()
+                );
+                return Ok(());
+            }
+
+            let window = &history[history.len().saturating_sub(limit.unwrap_or(10).max(1))..];
+            display_trends_table(trend_entries(window), use_colors)?;
+
+            let (first, latest) = (&window[0], &window[window.len() - 1]);
+            if window.len() < 2 {
+                println!(
+                    "\n{} Only one snapshot so far; take another later to see trends",
+                    "💡".yellow()
+                );
+                return Ok(());
+            }
+
+            println!("\n{}", "📂 Categories".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+            let categories: Vec<_> = category_trend_entries(first, latest)
+                .into_iter()
+                .filter(|e| e.first != e.latest)
+                .collect();
+            if categories.is_empty() {
+                println!("{}", "No category changed size.".green());
+            } else {
+                display_category_trends_table(categories, use_colors)?;
+            }
+
+            // The first snapshot may predate --check-links; compare from the earliest dead count
+            let first_dead = window.iter().find_map(|s| s.dead);
+            println!(
+                "\n{} Since {}: bookmarks {}, duplicates {}{}",
+                "📊".cyan(),
+                first.date.cyan(),
+                format_change(first.total, latest.total).yellow(),
+                format_change(first.duplicates, latest.duplicates).yellow(),
+                match first_dead.zip(latest.dead) {
+                    Some((from, to)) =>
+                        format!(", dead links {}", format_change(from, to).yellow()),
+                    None => String::new(),
+                }
+            );
+            if latest.duplicates < first.duplicates
+                || first_dead
+                    .zip(latest.dead)
+                    .is_some_and(|(from, to)| to < from)
+            {
+                println!("{} Cleanup is paying off", "✅".green());
+            } else if latest.duplicates > first.duplicates {
+                println!(
+                    "{} Duplicates are piling up; try --subcommand remove-dupes",
+                    "💡".yellow()
+                );
+            }
+        }
         "categories" => {
             println!("{}", "📂 Bookmarks by Category".bold().cyan());
             println!("{}", "─".repeat(50).dimmed());
@@ -486,12 +3813,46 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
                 display_category_stats_table(category_stats, use_colors)?;
             }
         }
+        "category-list" => {
+            println!("{}", "🗂️  Category Definitions".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let registry = CategoryRegistry::load()?;
+            let custom_count = registry.custom_categories().len();
+            display_category_definitions_table(get_category_definitions(&registry), use_colors)?;
+            println!(
+                "\n{} {} custom categories loaded from {}",
+                "📊".cyan(),
+                custom_count.to_string().yellow(),
+                crate::Config::path()?.display().to_string().cyan()
+            );
+        }
         "search" => {
-            if let Some(query) = matches.get_one::<String>("query") {
+            if matches.get_flag("interactive") {
+                let initial = matches
+                    .get_one::<String>("query")
+                    .map(|s| s.as_str())
+                    .unwrap_or("");
+                match interactive_search(&bookmarks, initial)? {
+                    Some(bookmark) => {
+                        println!("{} {}", "🔖".cyan(), bookmark.name.bold());
+                        println!("   {}", bookmark.url.blue());
+                        println!("   {}", bookmark.folder_path.dimmed());
+                        if matches.contains_id("open") {
+                            open_bookmarks(&[bookmark]);
+                        }
+                    }
+                    None => println!("{}", "Cancelled.".yellow()),
+                }
+            } else if let Some(query) = matches.get_one::<String>("query") {
                 println!("{} Searching for: {}", "🔍".cyan(), query.yellow());
                 println!("{}", "─".repeat(50).dimmed());
 
-                let results = search_bookmarks(&bookmarks, query);
+                let results = if matches.get_flag("fuzzy") {
+                    fuzzy_search_bookmarks(&bookmarks, query, use_colors)
+                } else {
+                    search_bookmarks(&bookmarks, query)
+                };
                 if results.is_empty() {
                     println!(
                         "{}",
@@ -511,10 +3872,28 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
                         count.to_string().yellow()
                     );
                 }
+
+                if let Some(&count) = matches.get_one::<usize>("open") {
+                    // Same order as the table: fuzzy results are ranked by score
+                    let to_open: Vec<&Bookmark> = if matches.get_flag("fuzzy") {
+                        fuzzy_rank_bookmarks(&bookmarks, query)
+                            .into_iter()
+                            .map(|m| m.bookmark)
+                            .take(count)
+                            .collect()
+                    } else {
+                        search_matches(&bookmarks, query)
+                            .into_iter()
+                            .take(count)
+                            .collect()
+                    };
+                    open_bookmarks(&to_open);
+                }
             } else {
                 println!(
                     "{}",
-                    "Please provide a search query with --query <QUERY>".yellow()
+                    "Please provide a search query with --query <QUERY> (or use --interactive)"
+                        .yellow()
                 );
             }
         }
@@ -522,7 +3901,14 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
             println!("{}", "📋 Organization Suggestions".bold().cyan());
             println!("{}", "─".repeat(50).dimmed());
 
-            let suggestions = get_organize_suggestions(&bookmarks);
+            let min_confidence = match matches.get_one::<f64>("min_confidence") {
+                Some(score) => *score,
+                None => crate::Config::load()?
+                    .get("bookmarks.review_threshold")
+                    .and_then(|v| v.as_float())
+                    .unwrap_or(DEFAULT_REVIEW_THRESHOLD),
+            };
+            let (suggestions, needs_review) = get_organize_suggestions(&bookmarks, min_confidence);
             if suggestions.is_empty() {
                 println!("{}", "All bookmarks are already well-organized!".green());
             } else {
@@ -543,6 +3929,31 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
                     "💡".yellow()
                 );
             }
+
+            if !needs_review.is_empty() {
+                println!(
+                    "\n{}",
+                    format!(
+                        "🔎 Needs Review (confidence below {:.0}%)",
+                        min_confidence * 100.0
+                    )
+                    .bold()
+                    .cyan()
+                );
+                println!("{}", "─".repeat(50).dimmed());
+                let count = needs_review.len();
+                let limited: Vec<_> = if let Some(lim) = limit {
+                    needs_review.into_iter().take(lim).collect()
+                } else {
+                    needs_review.into_iter().take(50).collect()
+                };
+                display_organize_suggestions_table(limited, use_colors)?;
+                println!(
+                    "\n{} {} bookmarks matched a category too weakly to file automatically",
+                    "📊".cyan(),
+                    count.to_string().yellow()
+                );
+            }
         }
         "export" => {
             let output_path = matches.get_one::<String>("output").map(|s| s.as_str());
@@ -550,7 +3961,7 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
             let path = output_path.unwrap_or(default_path);
 
             println!("{} Exporting bookmarks to markdown...", "📝".cyan());
-            export_to_markdown(&bookmarks, Some(path))?;
+            export_to_markdown(&bookmarks, &ExportTheme::load()?, Some(path))?;
             println!(
                 "\n{} Exported {} bookmarks to {}",
                 "✅".green(),
@@ -567,13 +3978,141 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
                 "{} Exporting organized bookmarks to Chrome HTML...",
                 "📝".cyan()
             );
-            export_to_chrome_html(&bookmarks, Some(path))?;
+            let favicons = if matches.get_flag("favicons") {
+                fetch_favicons(&bookmarks, &http_client(matches)?)
+            } else {
+                HashMap::new()
+            };
+            export_to_chrome_html(
+                &bookmarks,
+                &ExportTheme::load()?,
+                &favicons,
+                matches.get_flag("preserve_unmatched"),
+                Some(path),
+            )?;
+        }
+        "export-share" => {
+            println!("{}", "🌐 Shared Export".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let output_dir = matches
+                .get_one::<String>("output")
+                .map(|s| s.as_str())
+                .unwrap_or("bookmarks_share");
+            let base_url = crate::Config::load()?
+                .get_str("bookmarks.share.base_url")
+                .map(|s| s.to_string());
+
+            let bundles = export_share_bundles(
+                &bookmarks,
+                &ExportTheme::load()?,
+                base_url.as_deref(),
+                output_dir,
+            )?;
+            let count = bundles.len();
+            display_share_bundles_table(bundles, use_colors)?;
+            println!(
+                "\n{} Exported {} bookmarks into {} pages under {} (index.html, share.json)",
+                "✅".green(),
+                bookmarks
+                    .iter()
+                    .filter(|b| !is_internal_url(&b.url))
+                    .count()
+                    .to_string()
+                    .yellow(),
+                count.to_string().yellow(),
+                output_dir.cyan()
+            );
+
+            if !matches.get_flag("exclude_sensitive") {
+                let filter = SensitiveFilter::load()?;
+                let sensitive = bookmarks
+                    .iter()
+                    .filter(|b| filter.reason(b).is_some())
+                    .count();
+                if sensitive > 0 {
+                    println!(
+                        "{} {} exported bookmarks look private (tokens in URLs, intranet hosts); re-run with --exclude-sensitive before sharing",
+                        "⚠️".yellow(),
+                        sensitive.to_string().yellow()
+                    );
+                }
+            }
+            if base_url.is_none() {
+                println!(
+                    "{} Set [bookmarks.share] base_url to the hosting URL for absolute share links",
+                    "💡".yellow()
+                );
+            }
+        }
+        "reading-list" => {
+            println!("{}", "📚 Reading List".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let mut items = find_reading_list(&bookmarks);
+            if items.is_empty() {
+                println!("{}", "No article-like bookmarks found!".green());
+                return Ok(());
+            }
+            if let Some(lim) = limit {
+                items.truncate(lim);
+            }
+
+            if matches.get_flag("fetch") {
+                fetch_reading_list(&mut items, &http_client(matches)?);
+            }
+            display_reading_list_table(reading_list_entries(&items), use_colors)?;
+
+            let path = matches
+                .get_one::<String>("output")
+                .map(|s| s.as_str())
+                .unwrap_or("reading_list.md");
+            write_reading_list(&items, path)?;
+            println!(
+                "\n{} Wrote {} articles to {}",
+                "✅".green(),
+                items.len().to_string().yellow(),
+                path.cyan()
+            );
+
+            let failed = items
+                .iter()
+                .filter(|item| matches!(item.content, Some(Err(_))))
+                .count();
+            if failed > 0 {
+                println!(
+                    "{} {} articles couldn't be fetched; the digest links to them instead",
+                    "⚠️".yellow(),
+                    failed.to_string().yellow()
+                );
+            } else if !matches.get_flag("fetch") {
+                println!(
+                    "{} Use --fetch to include each article's text for offline reading",
+                    "💡".yellow()
+                );
+            }
         }
         "deadlinks" => {
             println!("{}", "🔗 Checking for Dead Links".bold().cyan());
             println!("{}", "─".repeat(50).dimmed());
 
-            let dead_links = find_dead_links(&bookmarks, verbose);
+            let notifier = Notifier::load(matches.get_flag("notify"))?;
+            let dead = find_dead_bookmarks_resumable(
+                &bookmarks,
+                &http_client(matches)?,
+                &status_policy(matches)?,
+                verbose,
+                matches.get_flag("resume"),
+            )?;
+            notifier.finish(
+                "Dead-link scan finished",
+                &format!("{} dead links in {} bookmarks", dead.len(), bookmarks.len()),
+            );
+            if let Some(path) = matches.get_one::<String>("output") {
+                export_link_rot_report(&dead, bookmarks.len(), path)?;
+            }
+
+            let dead_links = dead_link_entries(&dead);
             if dead_links.is_empty() {
                 println!(
                     "{}",
@@ -592,6 +4131,14 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
                     "📊".cyan(),
                     count.to_string().red()
                 );
+                let parked = dead.iter().filter(|(_, s)| is_parked_status(s)).count();
+                if parked > 0 {
+                    println!(
+                        "{} {} of them are parked/for-sale domains",
+                        "🅿️".yellow(),
+                        parked.to_string().yellow()
+                    );
+                }
                 println!(
                     "\n{} Use --subcommand remove-dead to remove these dead links",
                     "💡".yellow()
@@ -603,7 +4150,36 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
             println!("{}", "─".repeat(50).dimmed());
 
             // First find dead links
-            let dead_links = find_dead_links(&bookmarks, verbose);
+            let notifier = Notifier::load(matches.get_flag("notify"))?;
+            let mut dead_links = find_dead_links(
+                &bookmarks,
+                &http_client(matches)?,
+                &status_policy(matches)?,
+                verbose,
+            )?;
+            notifier.finish(
+                "Dead-link scan finished",
+                &format!(
+                    "{} dead links in {} bookmarks",
+                    dead_links.len(),
+                    bookmarks.len()
+                ),
+            );
+
+            // Parked domains still answer, so they are only removed on request
+            if !matches.get_flag("include_parked") {
+                let before = dead_links.len();
+                dead_links.retain(|d| !is_parked_status(&d.status));
+                let parked = before - dead_links.len();
+                if parked > 0 {
+                    println!(
+                        "{} Keeping {} parked/for-sale pages; add --include-parked to remove them too",
+                        "💡".yellow(),
+                        parked.to_string().yellow()
+                    );
+                }
+            }
+
             if dead_links.is_empty() {
                 println!(
                     "{}",
@@ -616,20 +4192,200 @@ pub fn handle_bookmarks_mode(matches: &ArgMatches) -> Result<()> {
                     "📊".cyan(),
                     count.to_string().red()
                 );
-                remove_dead_links(&dead_links, dry_run, true)?;
+                remove_dead_links(&dead_links, dry_run, !yes)?;
+            }
+        }
+        "orphans" => {
+            println!("{}", "📱 Sync Orphans".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let orphans = find_sync_orphans(&bookmarks);
+            if orphans.is_empty() {
+                println!(
+                    "{}",
+                    "No mobile/synced bookmarks duplicate your desktop bookmarks!".green()
+                );
+            } else {
+                let limited: Vec<_> = if let Some(lim) = limit {
+                    orphans.into_iter().take(lim).collect()
+                } else {
+                    orphans
+                };
+                let count = limited.len();
+                display_sync_orphans_table(limited, use_colors)?;
+                println!(
+                    "\n{} Found {} mobile copies of desktop bookmarks",
+                    "📊".cyan(),
+                    count.to_string().yellow()
+                );
+                println!(
+                    "\n{} Use --subcommand remove-orphans to dedupe across roots, or --exclude-mobile to leave them out of stats",
+                    "💡".yellow()
+                );
+            }
+        }
+        "renames" => {
+            println!("{}", "✏️  Folder Rename Suggestions".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let renames = suggest_folder_renames(&bookmarks, &folders, &crate::Config::load()?);
+            if renames.is_empty() {
+                println!(
+                    "{}",
+                    "No rename suggestions - folder names already match their contents!".green()
+                );
+            } else {
+                let entries: Vec<_> = renames.into_iter().map(|r| r.entry).collect();
+                let limited: Vec<_> = if let Some(lim) = limit {
+                    entries.into_iter().take(lim).collect()
+                } else {
+                    entries
+                };
+                let count = limited.len();
+                display_folder_renames_table(limited, use_colors)?;
+                println!(
+                    "\n{} Found {} folders that could be renamed",
+                    "📊".cyan(),
+                    count.to_string().yellow()
+                );
+                println!(
+                    "\n{} Use --subcommand apply-renames to rename them",
+                    "💡".yellow()
+                );
+            }
+        }
+        "apply-renames" => {
+            println!("{}", "✏️  Rename Folders".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            apply_folder_renames(dry_run, !yes, &roots)?;
+        }
+        "remove-orphans" => {
+            println!("{}", "🗑️  Remove Sync Orphans".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            remove_sync_orphans(dry_run, !yes)?;
+        }
+        "recategorize" => {
+            println!("{}", "🔀 Recategorize Bookmarks".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let Some(snapshot) = load_category_snapshot()? else {
+                save_category_snapshot(&bookmarks)?;
+                println!(
+                    "{} Saved the current categories of {} bookmarks as a snapshot",
+                    "💾".green(),
+                    bookmarks.len().to_string().yellow()
+                );
+                println!(
+                    "{} Edit your category rules, then run recategorize --diff to see what changes",
+                    "💡".yellow()
+                );
+                return Ok(());
+            };
+
+            let changes = category_changes(&bookmarks, &snapshot);
+            if changes.is_empty() {
+                println!(
+                    "{}",
+                    "No bookmarks changed category since the snapshot.".green()
+                );
+                return Ok(());
+            }
+
+            let entries = recategorize_entries(&changes);
+            let limited: Vec<_> = if let Some(lim) = limit {
+                entries.into_iter().take(lim).collect()
+            } else {
+                entries
+            };
+            display_recategorize_table(limited, use_colors)?;
+            println!(
+                "\n{} {} bookmarks would change category",
+                "📊".cyan(),
+                changes.len().to_string().yellow()
+            );
+
+            if matches.get_flag("diff") {
+                println!(
+                    "{} Run recategorize without --diff to move just these bookmarks into their new folders",
+                    "💡".yellow()
+                );
+            } else {
+                apply_recategorize(dry_run, !yes, &roots)?;
+            }
+        }
+        "languages" => {
+            println!("{}", "🌍 Bookmark Languages".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let stats = get_language_stats(&bookmarks);
+            let foreign_uncategorized: usize = stats
+                .iter()
+                .filter(|e| e.language != "English" && e.language != "Unknown")
+                .map(|e| e.uncategorized)
+                .sum();
+            let limited: Vec<_> = if let Some(lim) = limit {
+                stats.into_iter().take(lim).collect()
+            } else {
+                stats
+            };
+            display_language_stats_table(limited, use_colors)?;
+
+            if foreign_uncategorized > 0 && !CategoryRegistry::load()?.language_folders() {
+                println!(
+                    "\n{} {} non-English bookmarks are uncategorized; set [bookmarks] language_folders = true to file them under Language/<language>",
+                    "💡".yellow(),
+                    foreign_uncategorized.to_string().yellow()
+                );
+            }
+        }
+        "expand-short-urls" => {
+            println!("{}", "🔗 Expand Short URLs".bold().cyan());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let expansions = expand_short_urls(&bookmarks, &http_client(matches)?, verbose)?;
+            if expansions.is_empty() {
+                println!("{}", "No shortened URLs found!".green());
+                return Ok(());
+            }
+
+            let entries: Vec<_> = expansions.iter().map(|e| e.entry.clone()).collect();
+            let limited: Vec<_> = if let Some(lim) = limit {
+                entries.into_iter().take(lim).collect()
+            } else {
+                entries
+            };
+            display_short_urls_table(limited, use_colors)?;
+
+            let resolved = expansions.iter().filter(|e| e.expanded.is_some()).count();
+            println!(
+                "\n{} Resolved {} of {} shortened URLs",
+                "📊".cyan(),
+                resolved.to_string().yellow(),
+                expansions.len().to_string().yellow()
+            );
+
+            if matches.get_flag("rewrite") {
+                rewrite_short_urls(&expansions, dry_run, !yes)?;
+            } else if resolved > 0 {
+                println!(
+                    "{} Use --rewrite to replace them with the expanded URLs",
+                    "💡".yellow()
+                );
             }
         }
         "remove-dupes" => {
             println!("{}", "🗑️  Remove Duplicate Bookmarks".bold().cyan());
             println!("{}", "─".repeat(50).dimmed());
 
-            remove_duplicates(dry_run, true)?;
+            remove_duplicates(dry_run, !yes, matches.get_flag("include_internal"), &roots)?;
         }
         _ => {
             println!(
                 "{}",
                 format!(
-                    "Unknown subcommand: {}. Use: stats, duplicates, remove-dupes, deadlinks, remove-dead, domains, categories, search, organize, export",
+                    "Unknown subcommand: {}. Use: stats, duplicates, remove-dupes, orphans, remove-orphans, deadlinks, remove-dead, domains, folders, renames, apply-renames, categories, category-list, explain, snapshot, trends, recategorize, expand-short-urls, languages, search, organize, export, export-html, export-share, reading-list, safari-reading-list",
                     subcommand
                 )
                 .yellow()
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+use crate::Severity;
+
+type Result<T, E = ConfigError> = std::result::Result<T, E>;
+
+/// Global config file location, relative to the home directory
+const CONFIG_PATH: &str = ".config/shell-explorer/config.toml";
+
+/// Data directory for journals and other state, relative to the home directory
+const DATA_DIR: &str = ".local/share/shell-explorer";
+
+/// Errors from locating and loading the config file
+///
+/// All of them are fatal: every mode reads the config before doing any work.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("HOME environment variable not set")]
+    HomeNotSet,
+    #[error("Failed to read config file: {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to parse config file: {}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+    #[error(transparent)]
+    Syntax(#[from] toml::de::Error),
+}
+
+impl ConfigError {
+    pub fn severity(&self) -> Severity {
+        Severity::Fatal
+    }
+}
+
+/// Get the home directory from `$HOME`
+pub fn home_dir() -> Result<PathBuf> {
+    std::env::var("HOME")
+        .map(PathBuf::from)
+        .map_err(|_| ConfigError::HomeNotSet)
+}
+
+/// Expand a leading `~` to the home directory
+pub fn expand_home(path: &str) -> Result<PathBuf> {
+    match path.strip_prefix('~') {
+        Some("") => home_dir(),
+        Some(rest) if rest.starts_with('/') => Ok(home_dir()?.join(&rest[1..])),
+        _ => Ok(PathBuf::from(path)),
+    }
+}
+
+/// Get the data directory used for journals and other persistent state
+pub fn data_dir() -> Result<PathBuf> {
+    Ok(home_dir()?.join(DATA_DIR))
+}
+
+/// Global configuration loaded from `~/.config/shell-explorer/config.toml`
+///
+/// Each mode reads its own section (`[bookmarks]`, `[clean]`, ...), so the
+/// config is kept as a raw TOML table and looked up with dotted keys.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    table: toml::Table,
+}
+
+impl Config {
+    /// Get the config file path
+    pub fn path() -> Result<PathBuf> {
+        Ok(home_dir()?.join(CONFIG_PATH))
+    }
+
+    /// Load the config file, falling back to an empty config when it doesn't exist
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(source) => return Err(ConfigError::Read { path, source }),
+        };
+
+        match content.parse() {
+            Ok(table) => Ok(Self { table }),
+            Err(source) => Err(ConfigError::Parse { path, source }),
+        }
+    }
+
+    /// Parse config from TOML text
+    pub fn parse(content: &str) -> Result<Self> {
+        let table: toml::Table = content.parse()?;
+        Ok(Self { table })
+    }
+
+    /// Look up a value by dotted key, e.g. `bookmarks.categories`
+    pub fn get(&self, key: &str) -> Option<&toml::Value> {
+        let mut parts = key.split('.');
+        let mut value = self.table.get(parts.next()?)?;
+
+        for part in parts {
+            value = value.as_table()?.get(part)?;
+        }
+
+        Some(value)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|v| v.as_str())
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(|v| v.as_bool())
+    }
+
+    /// Get a table of string values, skipping any non-string entries
+    pub fn get_str_map(&self, key: &str) -> HashMap<String, String> {
+        self.get(key)
+            .and_then(|v| v.as_table())
+            .map(|table| {
+                table
+                    .iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get a list of strings, skipping any non-string entries
+    pub fn get_str_list(&self, key: &str) -> Vec<String> {
+        self.get(key)
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get an array of tables (`[[section.name]]` entries)
+    pub fn get_table_array(&self, key: &str) -> Vec<&toml::Table> {
+        self.get(key)
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|item| item.as_table()).collect())
+            .unwrap_or_default()
+    }
+}
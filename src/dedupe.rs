@@ -0,0 +1,334 @@
+use colored::Colorize;
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, Write, stdout};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::SystemTime;
+use tabled::Tabled;
+
+use crate::{
+    Action, ActionRunner, ORGANIZE_JOURNAL_KIND, OrganizeError, Severity, error_chain, format_size,
+    is_dev_folder, partial_path,
+};
+
+type Result<T, E = OrganizeError> = std::result::Result<T, E>;
+
+/// A ` (1)`-style copy suffix before the extension, as browsers and Finder add
+static COPY_SUFFIX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?: \(\d+\)| copy(?: \d+)?)(\.[^.]*)?$").unwrap());
+
+/// Files with identical content
+#[derive(Debug, Clone)]
+pub struct DuplicateFileGroup {
+    /// BLAKE3 of the content, hex
+    pub hash: String,
+    /// Size of each copy in bytes
+    pub size: u64,
+    /// The copy that's kept first, then the duplicates
+    pub files: Vec<PathBuf>,
+}
+
+impl DuplicateFileGroup {
+    /// Bytes removing every duplicate frees
+    pub fn wasted(&self) -> u64 {
+        self.size * (self.files.len() as u64 - 1)
+    }
+}
+
+/// Row of the duplicates table; the group's size is only on its first row
+#[derive(Tabled, Clone)]
+pub struct DuplicateFileEntry {
+    #[tabled(rename = "Group")]
+    pub group: String,
+    #[tabled(rename = "File")]
+    pub path: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+/// What happens to the duplicates of a group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DedupeMode {
+    /// Apply the run's action (trash or delete) to them
+    Remove,
+    /// Replace each with a hard link to the kept copy, so every path stays
+    Hardlink,
+}
+
+/// Find files under `root` with identical content
+///
+/// Hidden files and directories and development folders are skipped, as
+/// organizing skips them. Files are grouped by size first, so only same-size
+/// files are hashed (BLAKE3, in parallel). Empty files are never duplicates.
+/// Groups are largest waste first; each keeps the copy without a ` (1)` or
+/// ` copy` suffix, then the oldest.
+pub fn find_duplicate_files(root: &Path) -> Result<Vec<DuplicateFileGroup>> {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    collect_files(root, &mut by_size, &mut HashSet::new())?;
+
+    let candidates: Vec<(u64, PathBuf)> = by_size
+        .into_iter()
+        .filter(|(size, files)| *size > 0 && files.len() > 1)
+        .flat_map(|(size, files)| files.into_iter().map(move |file| (size, file)))
+        .collect();
+
+    let hashed: Vec<(String, u64, PathBuf)> = candidates
+        .into_par_iter()
+        .filter_map(|(size, path)| Some((file_blake3(&path).ok()?, size, path)))
+        .collect();
+
+    let mut by_hash: HashMap<(String, u64), Vec<PathBuf>> = HashMap::new();
+    for (hash, size, path) in hashed {
+        by_hash.entry((hash, size)).or_default().push(path);
+    }
+
+    let mut groups: Vec<DuplicateFileGroup> = by_hash
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|((hash, size), mut files)| {
+            files.sort_by_cached_key(|file| {
+                (
+                    is_numbered_copy(file),
+                    modified(file).unwrap_or(SystemTime::UNIX_EPOCH),
+                    file.clone(),
+                )
+            });
+            DuplicateFileGroup { hash, size, files }
+        })
+        .collect();
+    groups.sort_by(|a, b| b.wasted().cmp(&a.wasted()).then(a.files.cmp(&b.files)));
+
+    Ok(groups)
+}
+
+/// Add the regular files under `dir` to `by_size`
+///
+/// Hard links to a file already in `seen` (device and inode) are the same
+/// file rather than a duplicate, so only its first path is added.
+fn collect_files(
+    dir: &Path,
+    by_size: &mut HashMap<u64, Vec<PathBuf>>,
+    seen: &mut HashSet<(u64, u64)>,
+) -> Result<()> {
+    let entries = fs::read_dir(dir).map_err(|source| OrganizeError::ReadDir {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+
+        if file_type.is_dir() {
+            // Unreadable subdirectories are skipped rather than ending the scan
+            if !is_dev_folder(&path) {
+                collect_files(&path, by_size, seen).ok();
+            }
+        } else if file_type.is_file()
+            && let Ok(metadata) = entry.metadata()
+            && file_id(&metadata).is_none_or(|id| seen.insert(id))
+        {
+            by_size.entry(metadata.len()).or_default().push(path);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Hex BLAKE3 of a file's content
+fn file_blake3(path: &Path) -> io::Result<String> {
+    let mut hasher = blake3::Hasher::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Whether a file name looks like a copy, e.g. `file (1).pdf` or `file copy.pdf`
+fn is_numbered_copy(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| COPY_SUFFIX.is_match(&name.to_string_lossy()))
+}
+
+/// Rows of the duplicates table, with `statuses` (one per file, kept copy
+/// first) when the groups were processed
+pub fn duplicate_entries(
+    groups: &[DuplicateFileGroup],
+    statuses: Option<&[Vec<String>]>,
+) -> Vec<DuplicateFileEntry> {
+    groups
+        .iter()
+        .enumerate()
+        .flat_map(|(index, group)| {
+            group.files.iter().enumerate().map(move |(i, file)| {
+                let status = match statuses.and_then(|s| s.get(index)).and_then(|s| s.get(i)) {
+                    Some(status) => status.clone(),
+                    None if i == 0 => "Keep".to_string(),
+                    None => "Duplicate".to_string(),
+                };
+                DuplicateFileEntry {
+                    group: if i == 0 {
+                        format!("#{} ({} copies)", index + 1, group.files.len())
+                    } else {
+                        String::new()
+                    },
+                    path: file.display().to_string(),
+                    size: if i == 0 {
+                        format_size(group.size)
+                    } else {
+                        String::new()
+                    },
+                    status,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Remove or hard-link the duplicates of every group, keeping its first copy
+///
+/// Removal goes through `action` (trash or delete) and is journaled under the
+/// organize kind, so a trashed run can be undone. Hard links replace each
+/// duplicate atomically (linked at a staging path, then renamed over it) and
+/// aren't journaled, since no content is lost. With `interactive`, each group
+/// is shown and asked about first: remove, hard-link, skip, or keep a
+/// different copy. Returns each group's statuses, kept copy first.
+pub fn resolve_duplicates(
+    root: &Path,
+    groups: &mut [DuplicateFileGroup],
+    action: Action,
+    mode: DedupeMode,
+    dry_run: bool,
+    interactive: bool,
+) -> Result<Vec<Vec<String>>> {
+    let runner = ActionRunner::begin(ORGANIZE_JOURNAL_KIND, action, root, dry_run)?;
+    let mut statuses = Vec::new();
+
+    for (index, group) in groups.iter_mut().enumerate() {
+        let mode = if interactive {
+            match prompt_group(index, group, mode)? {
+                Some(mode) => mode,
+                None => {
+                    statuses.push(
+                        std::iter::once("Keep".to_string())
+                            .chain(group.files[1..].iter().map(|_| "Skipped".to_string()))
+                            .collect(),
+                    );
+                    continue;
+                }
+            }
+        } else {
+            mode
+        };
+
+        let keep = group.files[0].clone();
+        let mut group_statuses = vec!["Keep".to_string()];
+        for duplicate in &group.files[1..] {
+            let status = match mode {
+                DedupeMode::Hardlink if dry_run => "Would hard-link".to_string(),
+                DedupeMode::Hardlink => match hardlink(&keep, duplicate) {
+                    Ok(()) => "✓ Hard-linked".to_string(),
+                    Err(e) => format!("✗ Error: {}", e),
+                },
+                DedupeMode::Remove => match runner.apply(duplicate, None) {
+                    Ok(status) => status,
+                    Err(e) if e.severity() == Severity::Recoverable => {
+                        format!("✗ Error: {}", error_chain(&e))
+                    }
+                    Err(e) => return Err(e.into()),
+                },
+            };
+            group_statuses.push(status);
+        }
+        statuses.push(group_statuses);
+    }
+
+    runner.commit()?;
+    Ok(statuses)
+}
+
+/// Replace `duplicate` with a hard link to `keep`
+fn hardlink(keep: &Path, duplicate: &Path) -> io::Result<()> {
+    let staging = partial_path(duplicate);
+    fs::hard_link(keep, &staging)?;
+    fs::rename(&staging, duplicate).inspect_err(|_| {
+        fs::remove_file(&staging).ok();
+    })
+}
+
+/// Show a group and ask what to do with it; `None` skips it
+///
+/// A number makes that copy the kept one (and asks again).
+fn prompt_group(
+    index: usize,
+    group: &mut DuplicateFileGroup,
+    default: DedupeMode,
+) -> Result<Option<DedupeMode>> {
+    loop {
+        println!(
+            "\n{} Group {}: {} copies of {}",
+            "📑".cyan(),
+            (index + 1).to_string().bold(),
+            group.files.len(),
+            format_size(group.size).yellow()
+        );
+        for (i, file) in group.files.iter().enumerate() {
+            let marker = if i == 0 {
+                "keep".green().to_string()
+            } else {
+                "    ".to_string()
+            };
+            println!("  {:>2}) [{}] {}", i + 1, marker, file.display());
+        }
+
+        let default_label = match default {
+            DedupeMode::Remove => "r",
+            DedupeMode::Hardlink => "h",
+        };
+        print!(
+            "  {} [r]emove the others, [h]ard-link them, [s]kip, or a number to keep instead [{}]: ",
+            "❓".cyan(),
+            default_label
+        );
+        stdout().flush()?;
+
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        match answer.trim().to_lowercase().as_str() {
+            "" => return Ok(Some(default)),
+            "r" | "remove" => return Ok(Some(DedupeMode::Remove)),
+            "h" | "hardlink" => return Ok(Some(DedupeMode::Hardlink)),
+            "s" | "skip" => return Ok(None),
+            number => {
+                if let Ok(n) = number.parse::<usize>()
+                    && (1..=group.files.len()).contains(&n)
+                {
+                    group.files.swap(0, n - 1);
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use std::io::Result;
 use tabled::{
     Table,
     settings::{
@@ -8,8 +8,16 @@ use tabled::{
 };
 
 use crate::{
-    AliasEntry, BookmarkTableEntry, CategoryEntry, CleanedEntry, DeadLinkEntry, DomainEntry,
-    DuplicateEntry, FunctionEntry, OrganizeSuggestion, PackageEntry,
+    AliasEntry, AuditEntry, BookmarkTableEntry, BundleEntry, CargoTargetEntry,
+    CategoryDefinitionEntry, CategoryEntry, CategoryTrendEntry, CleanFailureEntry, CleanedEntry,
+    DeadLinkEntry, DoctorEntry, DomainEntry, DuplicateEntry, DuplicateFileEntry,
+    EcosystemReportEntry, FileMoveEntry, FlattenEntry, FolderRenameEntry, FolderStatsEntry,
+    FunctionEntry, LanguageEntry, LicenseEntry, LicenseSummaryEntry, OrganizeCategoryEntry,
+    OrganizeMonthEntry, OrganizeSuggestion, OutdatedEntry, PackageCacheEntry, PackageEntry,
+    ProjectCleanedEntry, ProjectReportEntry, PurgeEntry, ReadingListEntry, RecategorizeEntry,
+    RecoveryEntry, ResolutionEntry, RestoreEntry, RootStatsEntry, RuleMatchEntry,
+    SafariReadingEntry, ScheduleEntry, ShareBundleEntry, ShortUrlEntry, SyncOrphanEntry,
+    TrendEntry, XcodeEntry,
 };
 
 pub fn display_aliases_table(aliases: Vec<AliasEntry>, use_colors: bool) -> Result<()> {
@@ -132,14 +140,1336 @@ pub fn display_packages_table(packages: Vec<PackageEntry>, use_colors: bool) ->
                 Modify::new(Columns::new(3..4))
                     .with(Color::FG_MAGENTA)
                     .with(Width::wrap(10)),
+            )
+            .with(
+                Modify::new(Columns::new(4..5))
+                    .with(Color::FG_BLUE)
+                    .with(Width::wrap(20)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(15)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(40)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(10)))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(20)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_doctor_table(entries: Vec<DoctorEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(35)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(25)),
+            )
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(45)))
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(45)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(35)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(45)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(45)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_audit_table(entries: Vec<AuditEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(25)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(15)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(22)),
+            )
+            .with(Modify::new(Columns::new(3..4)).with(Color::FG_RED))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(40)))
+            .with(
+                Modify::new(Columns::new(5..6))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(15)),
+            )
+            .with(Modify::new(Columns::new(6..7)).with(Width::wrap(35)));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(15)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(22)))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(40)))
+            .with(Modify::new(Columns::new(5..6)).with(Width::wrap(15)))
+            .with(Modify::new(Columns::new(6..7)).with(Width::wrap(35)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_licenses_table(entries: Vec<LicenseEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(30)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(15)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(25)),
+            )
+            .with(Modify::new(Columns::new(3..4)).with(Color::FG_MAGENTA))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(45)));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(15)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(45)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_license_summary_table(
+    entries: Vec<LicenseSummaryEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(40)),
+            )
+            .with(Modify::new(Columns::new(1..2)).with(Color::FG_GREEN))
+            .with(Modify::new(Columns::new(2..3)).with(Color::FG_CYAN));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_outdated_table(entries: Vec<OutdatedEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(30)),
+            )
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(15)))
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(15)),
+            )
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(20)),
+            )
+            .with(Modify::new(Columns::new(4..5)).with(Color::FG_MAGENTA))
+            .with(Modify::new(Columns::new(5..6)).with(Width::wrap(45)));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(15)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(15)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(20)))
+            .with(Modify::new(Columns::new(5..6)).with(Width::wrap(45)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_share_bundles_table(entries: Vec<ShareBundleEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(35)),
+            )
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(35)))
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            )
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(50)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(35)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(35)))
+            .with(Modify::new(Columns::new(2..3)).with(Alignment::right()))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(50)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_trends_table(entries: Vec<TrendEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_CYAN))
+            .with(
+                Modify::new(Columns::new(1..6))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(1..6)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_organize_months_table(
+    entries: Vec<OrganizeMonthEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_CYAN))
+            .with(
+                Modify::new(Columns::new(1..5))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            )
+            .with(Modify::new(Columns::new(5..6)).with(Color::FG_GREEN));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(1..5)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_organize_categories_table(
+    entries: Vec<OrganizeCategoryEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_CYAN))
+            .with(Modify::new(Columns::new(1..3)).with(Alignment::right()))
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(1..4)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_category_trends_table(
+    entries: Vec<CategoryTrendEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(40)),
+            )
+            .with(Modify::new(Columns::new(1..3)).with(Alignment::right()))
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
+            .with(Modify::new(Columns::new(1..4)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_safari_reading_table(
+    entries: Vec<SafariReadingEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(45)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(30)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(30)),
+            )
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(12)))
+            .with(
+                Modify::new(Columns::new(4..5))
+                    .with(Color::FG_MAGENTA)
+                    .with(Width::wrap(15)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(45)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(12)))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(15)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_reading_list_table(entries: Vec<ReadingListEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(45)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(30)),
+            )
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(15)))
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(40)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(45)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(15)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(40)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_cargo_targets_table(entries: Vec<CargoTargetEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(40)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_MAGENTA)
+                    .with(Width::wrap(30)),
+            )
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(40).keep_words(true)))
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            )
+            .with(Modify::new(Columns::new(4..5)).with(Alignment::right()));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(40).keep_words(true)))
+            .with(Modify::new(Columns::new(3..5)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_package_caches_table(
+    entries: Vec<PackageCacheEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_CYAN))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(60)))
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(60)))
+            .with(Modify::new(Columns::new(2..3)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_duplicate_files_table(
+    entries: Vec<DuplicateFileEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_MAGENTA))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(70)))
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            )
+            .with(Modify::new(Columns::new(3..4)).with(Color::FG_GREEN));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(70)))
+            .with(Modify::new(Columns::new(2..3)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_bundles_table(entries: Vec<BundleEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_MAGENTA))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(60)))
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            )
+            .with(Modify::new(Columns::new(3..4)).with(Color::FG_CYAN))
+            .with(Modify::new(Columns::new(4..5)).with(Color::FG_GREEN));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(60)))
+            .with(Modify::new(Columns::new(2..3)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_flatten_table(entries: Vec<FlattenEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_MAGENTA))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(60)))
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            )
+            .with(Modify::new(Columns::new(3..4)).with(Color::FG_CYAN))
+            .with(Modify::new(Columns::new(4..5)).with(Color::FG_GREEN));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(60)))
+            .with(Modify::new(Columns::new(2..3)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_purge_table(entries: Vec<PurgeEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_MAGENTA))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(60)))
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            )
+            .with(Modify::new(Columns::new(3..4)).with(Color::FG_GREEN));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(60)))
+            .with(Modify::new(Columns::new(2..3)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_schedules_table(entries: Vec<ScheduleEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_CYAN))
+            .with(Modify::new(Columns::new(1..2)).with(Color::FG_YELLOW))
+            .with(Modify::new(Columns::new(2..4)).with(Color::FG_GREEN))
+            .with(
+                Modify::new(Columns::new(4..5))
+                    .with(Color::FG_BRIGHT_BLACK)
+                    .with(Width::wrap(50)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(50)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_xcode_table(entries: Vec<XcodeEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_MAGENTA))
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(50)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            )
+            .with(Modify::new(Columns::new(3..4)).with(Alignment::right()));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(50)))
+            .with(Modify::new(Columns::new(2..4)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_project_report_table(
+    entries: Vec<ProjectReportEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(50)),
+            )
+            .with(Modify::new(Columns::new(1..2)).with(Color::FG_MAGENTA))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(40).keep_words(true)))
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(50)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(40).keep_words(true)))
+            .with(Modify::new(Columns::new(3..4)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_ecosystem_report_table(
+    entries: Vec<EcosystemReportEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_MAGENTA))
+            .with(Modify::new(Columns::new(1..3)).with(Alignment::right()))
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(1..4)).with(Alignment::right()));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_cleaned_table(entries: Vec<CleanedEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(60)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(15)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(20)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(60)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(15)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(20)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_project_cleaned_table(
+    entries: Vec<ProjectCleanedEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_MAGENTA)
+                    .with(Width::wrap(40)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(30)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_WHITE)
+                    .with(Width::wrap(12)),
+            )
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(12)),
+            )
+            .with(
+                Modify::new(Columns::new(4..5))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(20)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(12)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(12)))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(20)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_clean_failures_table(
+    entries: Vec<CleanFailureEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_RED)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(60)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_RED)
+                    .with(Width::wrap(60).keep_words(true)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(60)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(60).keep_words(true)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_bookmarks_table(entries: Vec<BookmarkTableEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(40)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(50)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(20)),
+            )
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_MAGENTA)
+                    .with(Width::wrap(30)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(50)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(20)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(30)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_duplicates_table(entries: Vec<DuplicateEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(60)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(12)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(50)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(60)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(12)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(50)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_domain_stats_table(entries: Vec<DomainEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(40)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(10)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(12)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(10)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(12)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_language_stats_table(entries: Vec<LanguageEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(40)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(10)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(12)),
+            )
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_MAGENTA)
+                    .with(Width::wrap(14)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(10)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(12)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(14)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_category_stats_table(entries: Vec<CategoryEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(25)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(10)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(12)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(10)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(12)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_root_stats_table(entries: Vec<RootStatsEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_CYAN))
+            .with(Modify::new(Columns::new(1..4)).with(Color::FG_YELLOW))
+            .with(
+                Modify::new(Columns::new(4..5))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(30)),
+            )
+            .with(Modify::new(Columns::new(5..6)).with(Color::FG_RED));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(30)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_rule_matches_table(entries: Vec<RuleMatchEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_CYAN))
+            .with(Modify::new(Columns::new(1..2)).with(Color::FG_YELLOW))
+            .with(Modify::new(Columns::new(3..4)).with(Color::FG_GREEN))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(40)));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(40)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_category_definitions_table(
+    entries: Vec<CategoryDefinitionEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(30)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(30)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(10)),
+            )
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_MAGENTA)
+                    .with(Width::wrap(40)),
             );
     } else {
         table
             .with(Modify::new(Rows::first()).with(Alignment::center()))
-            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(25)))
-            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(15)))
-            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(40)))
-            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(10)));
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(10)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(40)));
     }
 
     println!("\n{}", table);
@@ -147,7 +1477,10 @@ pub fn display_packages_table(packages: Vec<PackageEntry>, use_colors: bool) ->
     Ok(())
 }
 
-pub fn display_cleaned_table(entries: Vec<CleanedEntry>, use_colors: bool) -> Result<()> {
+pub fn display_organize_suggestions_table(
+    entries: Vec<OrganizeSuggestion>,
+    use_colors: bool,
+) -> Result<()> {
     let mut table = Table::new(&entries);
 
     table.with(Style::rounded());
@@ -163,24 +1496,36 @@ pub fn display_cleaned_table(entries: Vec<CleanedEntry>, use_colors: bool) -> Re
             .with(
                 Modify::new(Columns::new(0..1))
                     .with(Color::FG_CYAN)
-                    .with(Width::wrap(60)),
+                    .with(Width::wrap(40)),
             )
             .with(
                 Modify::new(Columns::new(1..2))
-                    .with(Color::FG_YELLOW)
-                    .with(Width::wrap(15)),
+                    .with(Color::FG_RED)
+                    .with(Width::wrap(30)),
             )
             .with(
                 Modify::new(Columns::new(2..3))
                     .with(Color::FG_GREEN)
                     .with(Width::wrap(20)),
+            )
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(20)),
+            )
+            .with(
+                Modify::new(Columns::new(4..5))
+                    .with(Color::FG_MAGENTA)
+                    .with(Alignment::right()),
             );
     } else {
         table
             .with(Modify::new(Rows::first()).with(Alignment::center()))
-            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(60)))
-            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(15)))
-            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(20)));
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(20)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(20)))
+            .with(Modify::new(Columns::new(4..5)).with(Alignment::right()));
     }
 
     println!("\n{}", table);
@@ -188,7 +1533,7 @@ pub fn display_cleaned_table(entries: Vec<CleanedEntry>, use_colors: bool) -> Re
     Ok(())
 }
 
-pub fn display_bookmarks_table(entries: Vec<BookmarkTableEntry>, use_colors: bool) -> Result<()> {
+pub fn display_dead_links_table(entries: Vec<DeadLinkEntry>, use_colors: bool) -> Result<()> {
     let mut table = Table::new(&entries);
 
     table.with(Style::rounded());
@@ -213,13 +1558,13 @@ pub fn display_bookmarks_table(entries: Vec<BookmarkTableEntry>, use_colors: boo
             )
             .with(
                 Modify::new(Columns::new(2..3))
-                    .with(Color::FG_YELLOW)
+                    .with(Color::FG_RED)
                     .with(Width::wrap(20)),
             )
             .with(
                 Modify::new(Columns::new(3..4))
-                    .with(Color::FG_MAGENTA)
-                    .with(Width::wrap(30)),
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(25)),
             );
     } else {
         table
@@ -227,7 +1572,7 @@ pub fn display_bookmarks_table(entries: Vec<BookmarkTableEntry>, use_colors: boo
             .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
             .with(Modify::new(Columns::new(1..2)).with(Width::wrap(50)))
             .with(Modify::new(Columns::new(2..3)).with(Width::wrap(20)))
-            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(30)));
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(25)));
     }
 
     println!("\n{}", table);
@@ -235,7 +1580,7 @@ pub fn display_bookmarks_table(entries: Vec<BookmarkTableEntry>, use_colors: boo
     Ok(())
 }
 
-pub fn display_duplicates_table(entries: Vec<DuplicateEntry>, use_colors: bool) -> Result<()> {
+pub fn display_recovery_table(entries: Vec<RecoveryEntry>, use_colors: bool) -> Result<()> {
     let mut table = Table::new(&entries);
 
     table.with(Style::rounded());
@@ -251,24 +1596,101 @@ pub fn display_duplicates_table(entries: Vec<DuplicateEntry>, use_colors: bool)
             .with(
                 Modify::new(Columns::new(0..1))
                     .with(Color::FG_CYAN)
-                    .with(Width::wrap(60)),
+                    .with(Width::wrap(45)),
             )
             .with(
                 Modify::new(Columns::new(1..2))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(45)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
                     .with(Color::FG_YELLOW)
-                    .with(Width::wrap(12)),
+                    .with(Width::wrap(30)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(45)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(45)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(30)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_file_moves_table(entries: Vec<FileMoveEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(0..1)).with(Color::FG_CYAN))
+            .with(Modify::new(Columns::new(1..2)).with(Color::FG_YELLOW))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(45)))
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(45)),
+            );
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(45)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(45)));
+    }
+
+    println!("\n{}", table);
+
+    Ok(())
+}
+
+pub fn display_restore_table(entries: Vec<RestoreEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(40)),
             )
+            .with(Modify::new(Columns::new(1..2)).with(Color::FG_YELLOW))
             .with(
                 Modify::new(Columns::new(2..3))
                     .with(Color::FG_GREEN)
-                    .with(Width::wrap(50)),
+                    .with(Alignment::right()),
+            )
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(25)))
+            .with(
+                Modify::new(Columns::new(4..5))
+                    .with(Color::FG_MAGENTA)
+                    .with(Width::wrap(35)),
             );
     } else {
         table
             .with(Modify::new(Rows::first()).with(Alignment::center()))
-            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(60)))
-            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(12)))
-            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(50)));
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
+            .with(Modify::new(Columns::new(2..3)).with(Alignment::right()))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(35)));
     }
 
     println!("\n{}", table);
@@ -276,7 +1698,7 @@ pub fn display_duplicates_table(entries: Vec<DuplicateEntry>, use_colors: bool)
     Ok(())
 }
 
-pub fn display_domain_stats_table(entries: Vec<DomainEntry>, use_colors: bool) -> Result<()> {
+pub fn display_sync_orphans_table(entries: Vec<SyncOrphanEntry>, use_colors: bool) -> Result<()> {
     let mut table = Table::new(&entries);
 
     table.with(Style::rounded());
@@ -296,20 +1718,26 @@ pub fn display_domain_stats_table(entries: Vec<DomainEntry>, use_colors: bool) -
             )
             .with(
                 Modify::new(Columns::new(1..2))
-                    .with(Color::FG_YELLOW)
-                    .with(Width::wrap(10)),
+                    .with(Color::FG_BLUE)
+                    .with(Width::wrap(50)),
             )
             .with(
                 Modify::new(Columns::new(2..3))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(30)),
+            )
+            .with(
+                Modify::new(Columns::new(3..4))
                     .with(Color::FG_GREEN)
-                    .with(Width::wrap(12)),
+                    .with(Width::wrap(30)),
             );
     } else {
         table
             .with(Modify::new(Rows::first()).with(Alignment::center()))
             .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
-            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(10)))
-            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(12)));
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(50)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(30)));
     }
 
     println!("\n{}", table);
@@ -317,7 +1745,7 @@ pub fn display_domain_stats_table(entries: Vec<DomainEntry>, use_colors: bool) -
     Ok(())
 }
 
-pub fn display_category_stats_table(entries: Vec<CategoryEntry>, use_colors: bool) -> Result<()> {
+pub fn display_folder_stats_table(entries: Vec<FolderStatsEntry>, use_colors: bool) -> Result<()> {
     let mut table = Table::new(&entries);
 
     table.with(Style::rounded());
@@ -333,24 +1761,30 @@ pub fn display_category_stats_table(entries: Vec<CategoryEntry>, use_colors: boo
             .with(
                 Modify::new(Columns::new(0..1))
                     .with(Color::FG_CYAN)
-                    .with(Width::wrap(25)),
+                    .with(Width::wrap(50)),
             )
             .with(
-                Modify::new(Columns::new(1..2))
-                    .with(Color::FG_YELLOW)
-                    .with(Width::wrap(10)),
+                Modify::new(Columns::new(1..3))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(12)),
             )
             .with(
-                Modify::new(Columns::new(2..3))
-                    .with(Color::FG_GREEN)
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_MAGENTA)
+                    .with(Width::wrap(25)),
+            )
+            .with(
+                Modify::new(Columns::new(4..6))
+                    .with(Color::FG_YELLOW)
                     .with(Width::wrap(12)),
             );
     } else {
         table
             .with(Modify::new(Rows::first()).with(Alignment::center()))
-            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(25)))
-            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(10)))
-            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(12)));
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(50)))
+            .with(Modify::new(Columns::new(1..3)).with(Width::wrap(12)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(4..6)).with(Width::wrap(12)));
     }
 
     println!("\n{}", table);
@@ -358,10 +1792,7 @@ pub fn display_category_stats_table(entries: Vec<CategoryEntry>, use_colors: boo
     Ok(())
 }
 
-pub fn display_organize_suggestions_table(
-    entries: Vec<OrganizeSuggestion>,
-    use_colors: bool,
-) -> Result<()> {
+pub fn display_recategorize_table(entries: Vec<RecategorizeEntry>, use_colors: bool) -> Result<()> {
     let mut table = Table::new(&entries);
 
     table.with(Style::rounded());
@@ -382,33 +1813,71 @@ pub fn display_organize_suggestions_table(
             .with(
                 Modify::new(Columns::new(1..2))
                     .with(Color::FG_RED)
-                    .with(Width::wrap(30)),
+                    .with(Width::wrap(25)),
             )
             .with(
                 Modify::new(Columns::new(2..3))
                     .with(Color::FG_GREEN)
-                    .with(Width::wrap(20)),
+                    .with(Width::wrap(25)),
             )
             .with(
                 Modify::new(Columns::new(3..4))
                     .with(Color::FG_YELLOW)
-                    .with(Width::wrap(20)),
+                    .with(Width::wrap(35)),
             );
     } else {
         table
             .with(Modify::new(Rows::first()).with(Alignment::center()))
             .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
-            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(30)))
-            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(20)))
-            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(20)));
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(35)));
     }
 
-    println!("\n{}", table);
+    println!("{}", table);
+    Ok(())
+}
+
+pub fn display_resolution_table(entries: Vec<ResolutionEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(Modify::new(Columns::new(1..2)).with(Color::FG_CYAN))
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(50)),
+            )
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(40)),
+            )
+            .with(Modify::new(Columns::new(4..5)).with(Color::FG_MAGENTA));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(50)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(40)));
+    }
 
+    println!("{}", table);
     Ok(())
 }
 
-pub fn display_dead_links_table(entries: Vec<DeadLinkEntry>, use_colors: bool) -> Result<()> {
+pub fn display_folder_renames_table(
+    entries: Vec<FolderRenameEntry>,
+    use_colors: bool,
+) -> Result<()> {
     let mut table = Table::new(&entries);
 
     table.with(Style::rounded());
@@ -424,30 +1893,82 @@ pub fn display_dead_links_table(entries: Vec<DeadLinkEntry>, use_colors: bool) -
             .with(
                 Modify::new(Columns::new(0..1))
                     .with(Color::FG_CYAN)
-                    .with(Width::wrap(40)),
+                    .with(Width::wrap(50)),
             )
             .with(
                 Modify::new(Columns::new(1..2))
-                    .with(Color::FG_GREEN)
-                    .with(Width::wrap(50)),
+                    .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
             )
             .with(
                 Modify::new(Columns::new(2..3))
-                    .with(Color::FG_RED)
-                    .with(Width::wrap(20)),
+                    .with(Color::FG_MAGENTA)
+                    .with(Width::wrap(25)),
             )
             .with(
                 Modify::new(Columns::new(3..4))
                     .with(Color::FG_YELLOW)
+                    .with(Alignment::right()),
+            )
+            .with(
+                Modify::new(Columns::new(4..5))
+                    .with(Color::FG_GREEN)
                     .with(Width::wrap(25)),
             );
     } else {
         table
             .with(Modify::new(Rows::first()).with(Alignment::center()))
-            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(40)))
-            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(50)))
-            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(20)))
-            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(25)));
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(50)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(25)));
+    }
+
+    println!("{}", table);
+    Ok(())
+}
+
+pub fn display_short_urls_table(entries: Vec<ShortUrlEntry>, use_colors: bool) -> Result<()> {
+    let mut table = Table::new(&entries);
+
+    table.with(Style::rounded());
+
+    if use_colors {
+        table
+            .with(
+                Modify::new(Rows::first())
+                    .with(Color::BG_BLUE)
+                    .with(Color::FG_WHITE)
+                    .with(Alignment::center()),
+            )
+            .with(
+                Modify::new(Columns::new(0..1))
+                    .with(Color::FG_CYAN)
+                    .with(Width::wrap(30)),
+            )
+            .with(
+                Modify::new(Columns::new(1..2))
+                    .with(Color::FG_YELLOW)
+                    .with(Width::wrap(30)),
+            )
+            .with(
+                Modify::new(Columns::new(2..3))
+                    .with(Color::FG_GREEN)
+                    .with(Width::wrap(50)),
+            )
+            .with(
+                Modify::new(Columns::new(3..4))
+                    .with(Color::FG_MAGENTA)
+                    .with(Width::wrap(25)),
+            )
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(25)));
+    } else {
+        table
+            .with(Modify::new(Rows::first()).with(Alignment::center()))
+            .with(Modify::new(Columns::new(0..1)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(1..2)).with(Width::wrap(30)))
+            .with(Modify::new(Columns::new(2..3)).with(Width::wrap(50)))
+            .with(Modify::new(Columns::new(3..4)).with(Width::wrap(25)))
+            .with(Modify::new(Columns::new(4..5)).with(Width::wrap(25)));
     }
 
     println!("\n{}", table);
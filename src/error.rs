@@ -0,0 +1,73 @@
+use std::process::ExitCode;
+
+use crate::{
+    ActionError, AliasesError, BookmarksError, CleanError, ConfigError, FunctionsError, HttpError,
+    JournalError, NotifyError, OrganizeError, PackagesError, ReadingListError, ResolveError,
+    RestoreError, SafariError, ScheduleError, SetupError,
+};
+
+/// Exit code for fatal errors: bad input, unreadable config, failed writes
+pub const EXIT_FATAL: u8 = 1;
+
+/// Exit code for recoverable errors (EX_TEMPFAIL): a retry may succeed
+pub const EXIT_RECOVERABLE: u8 = 75;
+
+/// How bad an error is for the operation that hit it
+///
+/// Recoverable errors belong to a single item (one file move, one network
+/// request, one manifest); callers can report them and carry on with the rest.
+/// Fatal errors mean the operation as a whole can't continue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Recoverable,
+    Fatal,
+}
+
+/// Classify an application error by the first crate error in its chain
+///
+/// Errors that don't come from this crate (argument parsing, terminal setup)
+/// are treated as fatal.
+pub fn severity_of(error: &anyhow::Error) -> Severity {
+    macro_rules! classify {
+        ($cause:expr, $($ty:ty),+) => {
+            $(
+                if let Some(e) = $cause.downcast_ref::<$ty>() {
+                    return e.severity();
+                }
+            )+
+        };
+    }
+
+    for cause in error.chain() {
+        classify!(
+            cause,
+            ActionError,
+            AliasesError,
+            BookmarksError,
+            CleanError,
+            ConfigError,
+            FunctionsError,
+            HttpError,
+            JournalError,
+            NotifyError,
+            OrganizeError,
+            PackagesError,
+            ReadingListError,
+            ResolveError,
+            RestoreError,
+            SafariError,
+            ScheduleError,
+            SetupError
+        );
+    }
+
+    Severity::Fatal
+}
+
+/// Process exit code for an error that ended the run
+pub fn exit_code(error: &anyhow::Error) -> ExitCode {
+    match severity_of(error) {
+        Severity::Recoverable => ExitCode::from(EXIT_RECOVERABLE),
+        Severity::Fatal => ExitCode::from(EXIT_FATAL),
+    }
+}
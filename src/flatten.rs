@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tabled::Tabled;
+
+use crate::{
+    Action, ActionRunner, BUNDLES_FOLDER, FileCategory, ORGANIZE_JOURNAL_KIND, OrganizeError,
+    REVIEW_FOLDER, SIZE_BUCKETS, SOURCES_FOLDER, format_size, is_dev_folder, load_exclusions,
+    numbered_name, recoverable_status,
+};
+
+type Result<T, E = OrganizeError> = std::result::Result<T, E>;
+
+/// Files macOS leaves in folders and zips that don't make a folder worth
+/// keeping: Finder's `.DS_Store` and the `._` resource forks of `__MACOSX`
+const JUNK_PREFIXES: &[&str] = &[".DS_Store", "._"];
+
+/// Folders that are single items to macOS (apps, libraries, bundles) and
+/// must stay whole
+const PACKAGE_EXTENSIONS: &[&str] = &[
+    "app",
+    "bundle",
+    "framework",
+    "kext",
+    "photoslibrary",
+    "plugin",
+    "pkg",
+    "rtfd",
+    "xcodeproj",
+    "xcworkspace",
+];
+
+/// A subfolder holding a single file, however deep, e.g. `report/report/report.pdf`
+/// left by extracting `report.zip`
+#[derive(Debug, Clone)]
+pub struct NestedFolder {
+    /// The subfolder directly under the organized root
+    pub folder: PathBuf,
+    pub file: PathBuf,
+    pub size: u64,
+}
+
+#[derive(Tabled, Clone)]
+pub struct FlattenEntry {
+    #[tabled(rename = "Folder")]
+    pub folder: String,
+    #[tabled(rename = "File")]
+    pub file: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Moved To")]
+    pub destination: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+/// Find the subfolders of `root` that hold nothing but one file, nested at
+/// any depth
+///
+/// Hidden folders, symlinks, packages like `.app`, the folders organize files
+/// into (`Documents`, `2024`, `Huge`, ...), folders matching the `exclude`
+/// globs or `root`'s `.organizeignore` and any folder with a development
+/// marker anywhere inside are left alone. `.DS_Store` and `._` files don't
+/// count.
+pub fn find_nested_folders(root: &Path, exclude: &[String]) -> Result<Vec<NestedFolder>> {
+    let exclusions = load_exclusions(root, exclude)?;
+    let entries = fs::read_dir(root).map_err(|source| OrganizeError::ReadDir {
+        path: root.to_path_buf(),
+        source,
+    })?;
+
+    let mut nested = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        // `file_type` doesn't follow symlinks
+        if !entry.file_type().is_ok_and(|t| t.is_dir())
+            || name.starts_with('.')
+            || is_organized_folder(&name)
+            || exclusions.iter().any(|e| e.matches(&path))
+        {
+            continue;
+        }
+
+        let mut files = Vec::new();
+        if collect_files(&path, &mut files) && files.len() == 1 {
+            let file = files.remove(0);
+            nested.push(NestedFolder {
+                size: fs::metadata(&file).map(|m| m.len()).unwrap_or(0),
+                folder: path,
+                file,
+            });
+        }
+    }
+
+    nested.sort_by(|a, b| a.folder.cmp(&b.folder));
+    Ok(nested)
+}
+
+/// Move each nested folder's file into `root` and remove the emptied folder
+///
+/// A file whose name is taken in `root` gets ` (1)` appended. Moves are
+/// journaled as an organize run, so undo puts the files back in their
+/// folders; the removed folders and junk files aren't journaled.
+pub fn flatten_folders(
+    root: &Path,
+    nested: &[NestedFolder],
+    dry_run: bool,
+) -> Result<Vec<FlattenEntry>> {
+    let runner = ActionRunner::begin(
+        ORGANIZE_JOURNAL_KIND,
+        Action::Move { to: None },
+        root,
+        dry_run,
+    )?;
+
+    let mut taken = HashSet::new();
+    let mut results = Vec::new();
+    for item in nested {
+        let original = item.file.file_name().unwrap_or_default().to_string_lossy();
+        let mut name = original.to_string();
+        for n in 1.. {
+            let path = root.join(&name);
+            if !taken.contains(&path) && !path.exists() && !path.is_symlink() {
+                break;
+            }
+            name = numbered_name(&original, n);
+        }
+        let destination = root.join(&name);
+        taken.insert(destination.clone());
+
+        let status = recoverable_status(
+            runner
+                .apply(&item.file, Some(&destination))
+                .map_err(OrganizeError::from),
+        )?;
+        if !dry_run && !status.starts_with('✗') {
+            remove_emptied(&item.folder);
+        }
+
+        results.push(FlattenEntry {
+            folder: item
+                .folder
+                .strip_prefix(root)
+                .unwrap_or(&item.folder)
+                .display()
+                .to_string(),
+            file: item
+                .file
+                .strip_prefix(&item.folder)
+                .unwrap_or(&item.file)
+                .display()
+                .to_string(),
+            size: format_size(item.size),
+            destination: name,
+            status,
+        });
+    }
+
+    runner.commit()?;
+    Ok(results)
+}
+
+/// Whether a folder under the root is one organize files into
+fn is_organized_folder(name: &str) -> bool {
+    FileCategory::ALL.iter().any(|c| c.folder_name() == name)
+        || SIZE_BUCKETS.iter().any(|(bucket, _)| *bucket == name)
+        || [BUNDLES_FOLDER, SOURCES_FOLDER, REVIEW_FOLDER, "Tiny"].contains(&name)
+        // Year folders of the date schemes
+        || (!name.is_empty() && name.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Add the files under `dir` to `files`, returning false when `dir` holds
+/// anything that makes it worth keeping: a symlink, a package, a hidden
+/// folder or file other than junk, or a development marker
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> bool {
+    if is_dev_folder(dir) {
+        return false;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Ok(file_type) = entry.file_type() else {
+            return false;
+        };
+
+        if file_type.is_file() && is_junk(&name) {
+            continue;
+        }
+        if file_type.is_symlink() || name.starts_with('.') {
+            return false;
+        }
+        if file_type.is_dir() {
+            let is_package = Path::new(&name)
+                .extension()
+                .is_some_and(|ext| PACKAGE_EXTENSIONS.contains(&&*ext.to_string_lossy()));
+            if is_package || !collect_files(&path, files) {
+                return false;
+            }
+        } else {
+            files.push(path);
+        }
+        // No need to look further once it holds more than one file
+        if files.len() > 1 {
+            return false;
+        }
+    }
+    true
+}
+
+fn is_junk(name: &str) -> bool {
+    JUNK_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Remove `dir` once its file has moved out, with any junk files and empty
+/// subfolders; anything else that turned up keeps it in place
+fn remove_emptied(dir: &Path) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        match entry.file_type() {
+            Ok(t) if t.is_dir() => remove_emptied(&entry.path()),
+            Ok(t) if t.is_file() && is_junk(&name) => {
+                fs::remove_file(entry.path()).ok();
+            }
+            _ => {}
+        }
+    }
+    fs::remove_dir(dir).ok();
+}
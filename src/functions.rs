@@ -1,8 +1,44 @@
-use anyhow::{Context, Result};
-use std::env;
+use colored::*;
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
 use tabled::Tabled;
+use thiserror::Error;
+
+use crate::{ConfigError, Severity, home_dir, truncate_string};
+
+type Result<T, E = FunctionsError> = std::result::Result<T, E>;
+
+/// Errors from collecting and running shell functions
+///
+/// A shell that can't be started only fails that one run; a missing HOME or
+/// a broken terminal is fatal.
+#[derive(Debug, Error)]
+pub enum FunctionsError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("Failed to start {shell} for function '{name}'")]
+    Run {
+        shell: String,
+        name: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Terminal error")]
+    Terminal(#[from] io::Error),
+}
+
+impl FunctionsError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            FunctionsError::Config(e) => e.severity(),
+            FunctionsError::Run { .. } => Severity::Recoverable,
+            FunctionsError::Terminal(_) => Severity::Fatal,
+        }
+    }
+}
 
 #[derive(Tabled)]
 pub struct FunctionEntry {
@@ -14,11 +50,14 @@ pub struct FunctionEntry {
     pub usage: String,
     #[tabled(rename = "Source")]
     pub source: String,
+    /// Config file the function is defined in
+    #[tabled(skip)]
+    pub path: PathBuf,
 }
 
 pub fn get_all_functions() -> Result<Vec<FunctionEntry>> {
     let mut functions = Vec::new();
-    let home_dir = env::var("HOME").context("HOME environment variable not set")?;
+    let home_dir = home_dir()?;
     
     // Common shell configuration files that might contain functions
     let config_files = vec![
@@ -31,19 +70,20 @@ pub fn get_all_functions() -> Result<Vec<FunctionEntry>> {
     ];
     
     for config_file in config_files {
-        let file_path = PathBuf::from(&home_dir).join(config_file);
+        let file_path = home_dir.join(config_file);
         
-        if file_path.exists() {
-            if let Ok(content) = fs::read_to_string(&file_path) {
-                let file_functions = parse_shell_functions(&content);
-                for (name, description, usage) in file_functions {
-                    functions.push(FunctionEntry {
-                        name,
-                        description,
-                        usage,
-                        source: config_file.to_string(),
-                    });
-                }
+        if file_path.exists()
+            && let Ok(content) = fs::read_to_string(&file_path)
+        {
+            let file_functions = parse_shell_functions(&content);
+            for (name, description, usage) in file_functions {
+                functions.push(FunctionEntry {
+                    name,
+                    description,
+                    usage,
+                    source: config_file.to_string(),
+                    path: file_path.clone(),
+                });
             }
         }
     }
@@ -185,10 +225,9 @@ fn extract_function_name(line: &str) -> Option<String> {
     // name() { ... }
     // function name { ... }
     
-    if line.starts_with("function ") {
+    if let Some(after_function) = line.strip_prefix("function ") {
         // function name() or function name
-        let after_function = &line[9..];
-        if let Some(space_or_paren) = after_function.find(|c| c == ' ' || c == '(' || c == '{') {
+        if let Some(space_or_paren) = after_function.find([' ', '(', '{']) {
             return Some(after_function[..space_or_paren].trim().to_string());
         }
     } else if line.contains("()") && (line.contains('{') || line.ends_with("()")) {
@@ -196,7 +235,7 @@ fn extract_function_name(line: &str) -> Option<String> {
         if let Some(paren_pos) = line.find("()") {
             let potential_name = line[..paren_pos].trim();
             // Make sure it's a valid function name (starts with letter or underscore)
-            if potential_name.chars().next().map_or(false, |c| c.is_alphabetic() || c == '_') &&
+            if potential_name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_') &&
                potential_name.chars().all(|c| c.is_alphanumeric() || c == '_') {
                 return Some(potential_name.to_string());
             }
@@ -214,19 +253,19 @@ fn extract_usage_from_body(body: &[&str], func_name: &str) -> String {
         let line = line.trim();
         
         // Look for echo statements that might show usage
-        if line.starts_with("echo") && (line.contains("Usage:") || line.contains("usage:")) {
-            if let Some(usage_start) = line.to_lowercase().find("usage:") {
-                let usage_part = &line[usage_start + 6..];
-                return usage_part.trim_matches('"').trim_matches('\'').trim().to_string();
-            }
+        if line.starts_with("echo") && (line.contains("Usage:") || line.contains("usage:"))
+            && let Some(usage_start) = line.to_lowercase().find("usage:")
+        {
+            let usage_part = &line[usage_start + 6..];
+            return usage_part.trim_matches('"').trim_matches('\'').trim().to_string();
         }
         
         // Look for printf statements with usage
-        if line.starts_with("printf") && (line.contains("Usage:") || line.contains("usage:")) {
-            if let Some(usage_start) = line.to_lowercase().find("usage:") {
-                let usage_part = &line[usage_start + 6..];
-                return usage_part.trim_matches('"').trim_matches('\'').trim().to_string();
-            }
+        if line.starts_with("printf") && (line.contains("Usage:") || line.contains("usage:"))
+            && let Some(usage_start) = line.to_lowercase().find("usage:")
+        {
+            let usage_part = &line[usage_start + 6..];
+            return usage_part.trim_matches('"').trim_matches('\'').trim().to_string();
         }
         
         // Look for variable assignments that indicate parameters
@@ -260,10 +299,10 @@ fn extract_usage_from_body(body: &[&str], func_name: &str) -> String {
         }
         
         // Look for shift commands (indicates parameter processing)
-        if line.trim() == "shift" || line.contains("shift ") {
-            if !params.contains(&"[args...]") {
-                params.push("[args...]");
-            }
+        if (line.trim() == "shift" || line.contains("shift "))
+            && !params.contains(&"[args...]")
+        {
+            params.push("[args...]");
         }
     }
     
@@ -274,3 +313,294 @@ fn extract_usage_from_body(body: &[&str], func_name: &str) -> String {
         func_name.to_string()
     }
 }
+
+/// One argument of a function, derived from its usage string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageArg {
+    pub name: String,
+    pub required: bool,
+    /// Takes any number of words (`[args...]`, `<files>...`, `[options]`)
+    pub variadic: bool,
+}
+
+/// Derive the arguments a function takes from its usage string
+///
+/// `<x>` and bare words are required, `[x]` and `-flags` are optional, and a
+/// trailing `...` (or `[options]`) takes several words. The function's own
+/// name at the start of the usage is skipped.
+pub fn parse_usage_args(name: &str, usage: &str) -> Vec<UsageArg> {
+    // Split on whitespace, keeping bracketed groups like "[-n count]" together
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in usage.chars() {
+        match c {
+            '[' | '<' => depth += 1,
+            ']' | '>' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if c.is_whitespace() && depth == 0 {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    if tokens.first().is_some_and(|t| t == name) {
+        tokens.remove(0);
+    }
+
+    tokens
+        .into_iter()
+        .filter_map(|token| {
+            let (token, dots) = match token.strip_suffix("...") {
+                Some(rest) => (rest.to_string(), true),
+                None => (token, false),
+            };
+
+            let (inner, required) = if let Some(inner) =
+                token.strip_prefix('[').and_then(|t| t.strip_suffix(']'))
+            {
+                (inner, false)
+            } else if let Some(inner) = token.strip_prefix('<').and_then(|t| t.strip_suffix('>')) {
+                (inner, true)
+            } else {
+                (token.as_str(), !token.starts_with('-'))
+            };
+
+            let (inner, inner_dots) = match inner.strip_suffix("...") {
+                Some(rest) => (rest, true),
+                None => (inner, false),
+            };
+            let inner = inner.trim_matches(|c| c == '<' || c == '>').trim();
+            if inner.is_empty() {
+                return None;
+            }
+
+            Some(UsageArg {
+                name: inner.to_string(),
+                required,
+                variadic: dots || inner_dots || inner == "options",
+            })
+        })
+        .collect()
+}
+
+/// Captured result of running a function
+pub struct FunctionRun {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+}
+
+/// The shell that understands a config file: zsh for zsh files, bash otherwise
+fn shell_for(path: &Path) -> &'static str {
+    let file = path.file_name().and_then(|f| f.to_str()).unwrap_or("");
+    if file.starts_with(".zsh") { "zsh" } else { "bash" }
+}
+
+/// Run a function in a subshell and capture its output
+///
+/// The function's config file is sourced first (with its own output
+/// discarded), so helpers and aliases it relies on are available. Stdin is
+/// closed; interactive functions will see end of input.
+pub fn run_function(function: &FunctionEntry, args: &[String]) -> Result<FunctionRun> {
+    let shell = shell_for(&function.path);
+    let script = concat!(
+        "source \"$SHELL_EXPLORER_FUNCTION_FILE\" >/dev/null 2>&1; ",
+        "if ! type \"$0\" >/dev/null 2>&1; then ",
+        "echo \"$0: not defined after sourcing $SHELL_EXPLORER_FUNCTION_FILE\" >&2; exit 127; ",
+        "fi; ",
+        "\"$0\" \"$@\""
+    );
+
+    let start = Instant::now();
+    let output = Command::new(shell)
+        .arg("-c")
+        .arg(script)
+        .arg(&function.name)
+        .args(args)
+        .env("SHELL_EXPLORER_FUNCTION_FILE", &function.path)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|source| FunctionsError::Run {
+            shell: shell.to_string(),
+            name: function.name.clone(),
+            source,
+        })?;
+
+    Ok(FunctionRun {
+        status: output.status,
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        duration: start.elapsed(),
+    })
+}
+
+/// Interactive function picker with type-to-filter; returns the function picked with Enter
+pub fn pick_function<'a>(
+    functions: &'a [FunctionEntry],
+    initial_query: &str,
+) -> Result<Option<&'a FunctionEntry>> {
+    use crossterm::{
+        cursor,
+        event::{self, Event, KeyCode, KeyModifiers},
+        execute,
+        terminal::{self, ClearType},
+    };
+    use std::io::{Write, stdout};
+
+    let mut query = initial_query.to_string();
+    let mut selected_idx = 0;
+    let mut stdout = stdout();
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let picked = loop {
+        let needle = query.to_lowercase();
+        let matches: Vec<&FunctionEntry> = functions
+            .iter()
+            .filter(|f| {
+                f.name.to_lowercase().contains(&needle)
+                    || f.description.to_lowercase().contains(&needle)
+            })
+            .collect();
+        selected_idx = selected_idx.min(matches.len().saturating_sub(1));
+
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(ClearType::All)
+        )?;
+
+        write!(
+            stdout,
+            "{}\r\n",
+            "⚡ Function Runner - Interactive Mode".bold().cyan()
+        )?;
+        write!(stdout, "{}\r\n", "─".repeat(80).dimmed())?;
+        write!(stdout, "{} {}▏\r\n", "Filter:".yellow(), query.bold())?;
+        write!(
+            stdout,
+            "Functions: {} | {}=Navigate {}=Run {}=Quit\r\n",
+            matches.len().to_string().green(),
+            "↑/↓".yellow(),
+            "Enter".yellow(),
+            "Esc".yellow()
+        )?;
+        write!(stdout, "{}\r\n", "─".repeat(80).dimmed())?;
+
+        // Calculate visible window
+        let term_height = terminal::size()?.1 as usize;
+        let list_height = term_height.saturating_sub(7);
+        let start_idx = if selected_idx >= list_height {
+            selected_idx - list_height + 1
+        } else {
+            0
+        };
+
+        for (idx, function) in matches.iter().enumerate().skip(start_idx).take(list_height) {
+            let name = format!("{:<24}", truncate_string(&function.name, 24));
+            let description = truncate_string(&function.description, 50);
+
+            if idx == selected_idx {
+                write!(
+                    stdout,
+                    "{} {}  {}\r\n",
+                    "▶".cyan(),
+                    name.on_bright_blue().white(),
+                    description.dimmed()
+                )?;
+            } else {
+                write!(stdout, "  {}  {}\r\n", name, description.dimmed())?;
+            }
+        }
+
+        stdout.flush()?;
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Esc => break None,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break None,
+                KeyCode::Enter => break matches.get(selected_idx).copied(),
+                KeyCode::Up => selected_idx = selected_idx.saturating_sub(1),
+                KeyCode::Down if selected_idx + 1 < matches.len() => selected_idx += 1,
+                KeyCode::Backspace => {
+                    query.pop();
+                    selected_idx = 0;
+                }
+                KeyCode::Char(c) => {
+                    query.push(c);
+                    selected_idx = 0;
+                }
+                _ => {}
+            }
+        }
+    };
+
+    execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
+    terminal::disable_raw_mode()?;
+
+    Ok(picked)
+}
+
+/// Prompt on stdin for each argument a function takes
+///
+/// Required arguments are asked for until given; leaving an optional one empty
+/// skips it and the rest. Variadic arguments are split on whitespace. Functions
+/// without a documented usage get one free-form prompt.
+pub fn prompt_function_args(function: &FunctionEntry) -> Result<Vec<String>> {
+    use std::io::{Write, stdout};
+
+    let mut usage_args = parse_usage_args(&function.name, &function.usage);
+    if usage_args.is_empty() {
+        usage_args.push(UsageArg {
+            name: "arguments".to_string(),
+            required: false,
+            variadic: true,
+        });
+    }
+
+    let mut args = Vec::new();
+    for arg in &usage_args {
+        let answer = loop {
+            let hint = match (arg.required, arg.variadic) {
+                (true, false) => "",
+                (true, true) => " (space-separated)",
+                (false, false) => " (optional)",
+                (false, true) => " (optional, space-separated)",
+            };
+            print!("  {}{}: ", arg.name.yellow(), hint.dimmed());
+            stdout().flush()?;
+
+            let mut line = String::new();
+            if io::stdin().read_line(&mut line)? == 0 {
+                // End of input: take what we have so far
+                return Ok(args);
+            }
+            let line = line.trim().to_string();
+            if !line.is_empty() || !arg.required {
+                break line;
+            }
+            println!("    {}", "This argument is required".red());
+        };
+
+        if answer.is_empty() {
+            break;
+        }
+        if arg.variadic {
+            args.extend(answer.split_whitespace().map(str::to_string));
+        } else {
+            args.push(answer);
+        }
+    }
+
+    Ok(args)
+}
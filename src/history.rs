@@ -0,0 +1,266 @@
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tabled::Tabled;
+
+use crate::{
+    OrganizeEntry, OrganizeError, civil_from_days, data_dir, format_change, format_size,
+    unix_seconds,
+};
+
+type Result<T, E = OrganizeError> = std::result::Result<T, E>;
+
+/// Organize run history for `stats`, relative to the data directory
+const ORGANIZE_HISTORY_FILE: &str = "organize-history.jsonl";
+
+/// Files and bytes organized into one category
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CategoryTally {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// A finished organize run, as recorded in the history
+#[derive(Debug, Clone)]
+pub struct OrganizeRun {
+    /// Seconds since the Unix epoch
+    pub timestamp: i64,
+    /// Folder the files were organized into
+    pub root: PathBuf,
+    /// `move`, `copy` (`--safe`), `archive`, ...
+    pub action: String,
+    pub files: usize,
+    pub bytes: u64,
+    /// Per category name, e.g. `Documents`
+    pub categories: BTreeMap<String, CategoryTally>,
+}
+
+impl OrganizeRun {
+    /// The run's files that were placed, from its results
+    pub fn from_results(root: &Path, action: &str, results: &[OrganizeEntry]) -> Self {
+        let mut run = Self {
+            timestamp: unix_seconds(SystemTime::now()),
+            root: root.to_path_buf(),
+            action: action.to_string(),
+            files: 0,
+            bytes: 0,
+            categories: BTreeMap::new(),
+        };
+        for entry in results.iter().filter(|r| r.status.starts_with('✓')) {
+            run.files += 1;
+            run.bytes += entry.bytes;
+            let category = entry
+                .category
+                .trim_end_matches(" (by content)")
+                .trim_end_matches(" (sidecar)");
+            let tally = run.categories.entry(category.to_string()).or_default();
+            tally.files += 1;
+            tally.bytes += entry.bytes;
+        }
+        run
+    }
+
+    /// `2024-05`, in UTC
+    pub fn month(&self) -> String {
+        let (year, month, _) = civil_from_days(self.timestamp.div_euclid(86_400));
+        format!("{}-{:02}", year, month)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let categories: serde_json::Map<String, serde_json::Value> = self
+            .categories
+            .iter()
+            .map(|(name, tally)| {
+                (
+                    name.clone(),
+                    json!({ "files": tally.files, "bytes": tally.bytes }),
+                )
+            })
+            .collect();
+        json!({
+            "timestamp": self.timestamp,
+            "root": self.root,
+            "action": self.action,
+            "files": self.files,
+            "bytes": self.bytes,
+            "categories": categories,
+        })
+    }
+
+    fn from_json(record: &serde_json::Value) -> Option<Self> {
+        let tally = |value: &serde_json::Value| CategoryTally {
+            files: value.get("files").and_then(|v| v.as_u64()).unwrap_or(0) as usize,
+            bytes: value.get("bytes").and_then(|v| v.as_u64()).unwrap_or(0),
+        };
+
+        Some(Self {
+            timestamp: record.get("timestamp")?.as_i64()?,
+            root: PathBuf::from(record.get("root")?.as_str()?),
+            action: record
+                .get("action")
+                .and_then(|v| v.as_str())
+                .unwrap_or("move")
+                .to_string(),
+            files: record.get("files")?.as_u64()? as usize,
+            bytes: record.get("bytes").and_then(|v| v.as_u64()).unwrap_or(0),
+            categories: record
+                .get("categories")
+                .and_then(|v| v.as_object())
+                .map(|categories| {
+                    categories
+                        .iter()
+                        .map(|(name, value)| (name.clone(), tally(value)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Location of the organize run history
+pub fn organize_history_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join(ORGANIZE_HISTORY_FILE))
+}
+
+/// Append a run to the history; runs that placed nothing aren't recorded
+pub fn record_organize_run(run: &OrganizeRun) -> Result<()> {
+    if run.files == 0 {
+        return Ok(());
+    }
+
+    let path = organize_history_path()?;
+    let history_error = |action| {
+        let path = path.clone();
+        move |source| OrganizeError::History {
+            action,
+            path,
+            source,
+        }
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(history_error("create the folder of"))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(history_error("open"))?;
+    writeln!(file, "{}", run.to_json()).map_err(history_error("write"))
+}
+
+/// All recorded runs, oldest first; unreadable lines are skipped
+pub fn load_organize_history() -> Result<Vec<OrganizeRun>> {
+    let path = organize_history_path()?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(OrganizeError::History {
+                action: "read",
+                path,
+                source,
+            });
+        }
+    };
+
+    let mut runs: Vec<OrganizeRun> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|record| OrganizeRun::from_json(&record))
+        .collect();
+    runs.sort_by_key(|run| run.timestamp);
+    Ok(runs)
+}
+
+/// Row of the per-month organize stats
+#[derive(Tabled, Clone)]
+pub struct OrganizeMonthEntry {
+    #[tabled(rename = "Month")]
+    pub month: String,
+    #[tabled(rename = "Runs")]
+    pub runs: usize,
+    #[tabled(rename = "Files")]
+    pub files: usize,
+    #[tabled(rename = "Change")]
+    pub change: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Top Category")]
+    pub top_category: String,
+}
+
+/// Row of the per-category organize totals
+#[derive(Tabled, Clone)]
+pub struct OrganizeCategoryEntry {
+    #[tabled(rename = "Category")]
+    pub category: String,
+    #[tabled(rename = "Files")]
+    pub files: usize,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Share")]
+    pub share: String,
+}
+
+/// Files and bytes per category over `runs`
+pub fn organize_category_totals(runs: &[OrganizeRun]) -> BTreeMap<String, CategoryTally> {
+    let mut totals: BTreeMap<String, CategoryTally> = BTreeMap::new();
+    for (name, tally) in runs.iter().flat_map(|run| &run.categories) {
+        let total = totals.entry(name.clone()).or_default();
+        total.files += tally.files;
+        total.bytes += tally.bytes;
+    }
+    totals
+}
+
+/// One row per month with a run, oldest first, with the change in files
+/// organized since the month before
+pub fn organize_month_entries(runs: &[OrganizeRun]) -> Vec<OrganizeMonthEntry> {
+    let mut months: BTreeMap<String, Vec<OrganizeRun>> = BTreeMap::new();
+    for run in runs {
+        months.entry(run.month()).or_default().push(run.clone());
+    }
+
+    let mut entries: Vec<OrganizeMonthEntry> = Vec::new();
+    for (month, runs) in months {
+        let files = runs.iter().map(|run| run.files).sum();
+        let top_category = organize_category_totals(&runs)
+            .into_iter()
+            .max_by_key(|(_, tally)| (tally.files, tally.bytes))
+            .map(|(name, tally)| format!("{} ({})", name, tally.files))
+            .unwrap_or_else(|| "-".to_string());
+        entries.push(OrganizeMonthEntry {
+            change: match entries.last() {
+                Some(previous) => format_change(previous.files, files),
+                None => "-".to_string(),
+            },
+            runs: runs.len(),
+            size: format_size(runs.iter().map(|run| run.bytes).sum()),
+            month,
+            files,
+            top_category,
+        });
+    }
+    entries
+}
+
+/// One row per category, most files first, with its share of all files
+pub fn organize_category_entries(runs: &[OrganizeRun]) -> Vec<OrganizeCategoryEntry> {
+    let total: usize = runs.iter().map(|run| run.files).sum();
+    let mut totals: Vec<(String, CategoryTally)> =
+        organize_category_totals(runs).into_iter().collect();
+    totals.sort_by_key(|(_, tally)| std::cmp::Reverse((tally.files, tally.bytes)));
+
+    totals
+        .into_iter()
+        .map(|(category, tally)| OrganizeCategoryEntry {
+            category,
+            files: tally.files,
+            size: format_size(tally.bytes),
+            share: format!("{:.0}%", tally.files as f64 * 100.0 / total.max(1) as f64),
+        })
+        .collect()
+}
@@ -0,0 +1,107 @@
+use reqwest::blocking::Client;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::{Config, ConfigError, Severity};
+
+/// Errors from setting up the HTTP client; all fatal, nothing can be checked without one
+#[derive(Debug, Error)]
+pub enum HttpError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("Invalid proxy: {proxy}")]
+    InvalidProxy {
+        proxy: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("Failed to build HTTP client")]
+    Build(#[source] reqwest::Error),
+}
+
+impl HttpError {
+    pub fn severity(&self) -> Severity {
+        Severity::Fatal
+    }
+}
+
+/// Default request timeout for network checks
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+/// Settings for the HTTP client used by bookmark network operations
+///
+/// Read from `[http]` in the config file; the `--timeout`, `--user-agent`,
+/// `--proxy` and `--insecure` flags override the config values.
+///
+/// ```toml
+/// [http]
+/// timeout = 20
+/// user_agent = "Mozilla/5.0 (Macintosh; Intel Mac OS X 14_0)"
+/// proxy = "http://proxy.corp.example:3128"
+/// insecure = false
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpSettings {
+    pub timeout: Duration,
+    pub user_agent: Option<String>,
+    pub proxy: Option<String>,
+    pub insecure: bool,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            user_agent: None,
+            proxy: None,
+            insecure: false,
+        }
+    }
+}
+
+impl HttpSettings {
+    /// Load HTTP settings from the global config file
+    pub fn load() -> Result<Self, ConfigError> {
+        Ok(Self::from_config(&Config::load()?))
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        let defaults = Self::default();
+
+        Self {
+            timeout: config
+                .get("http.timeout")
+                .and_then(|v| v.as_integer())
+                .map(|secs| Duration::from_secs(secs.max(1) as u64))
+                .unwrap_or(defaults.timeout),
+            user_agent: config.get_str("http.user_agent").map(|s| s.to_string()),
+            proxy: config.get_str("http.proxy").map(|s| s.to_string()),
+            insecure: config
+                .get_bool("http.insecure")
+                .unwrap_or(defaults.insecure),
+        }
+    }
+
+    /// Build a blocking client with these settings
+    pub fn build_client(&self) -> Result<Client, HttpError> {
+        let mut builder = Client::builder()
+            .timeout(self.timeout)
+            .redirect(reqwest::redirect::Policy::limited(5))
+            .danger_accept_invalid_certs(self.insecure);
+
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(|source| {
+                HttpError::InvalidProxy {
+                    proxy: proxy.clone(),
+                    source,
+                }
+            })?);
+        }
+
+        builder.build().map_err(HttpError::Build)
+    }
+}
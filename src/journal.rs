@@ -0,0 +1,668 @@
+use serde_json::json;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tabled::Tabled;
+use thiserror::Error;
+
+use crate::{ConfigError, Severity, data_dir};
+
+type Result<T, E = JournalError> = std::result::Result<T, E>;
+
+/// Errors from reading and writing move journals
+///
+/// All of them are fatal: moves must not run without a working journal.
+#[derive(Debug, Error)]
+pub enum JournalError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(
+        "Invalid time '{0}' (use a date like 2024-05-03, 2024-05-03T14:00, or an age like 30m, 2h, 3d)"
+    )]
+    InvalidTime(String),
+    #[error("Failed to {action}: {}", path.display())]
+    Io {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl JournalError {
+    pub fn severity(&self) -> Severity {
+        Severity::Fatal
+    }
+
+    fn io(action: &'static str, path: &Path) -> impl FnOnce(io::Error) -> Self {
+        let path = path.to_path_buf();
+        move |source| JournalError::Io {
+            action,
+            path,
+            source,
+        }
+    }
+}
+
+/// Write-ahead journal for file moves and other actions
+///
+/// Every run gets its own append-only JSON-lines file, so concurrent runs never
+/// write to the same journal. Each item is recorded as an `intent` (flushed to
+/// disk before the action starts) and a `done` record once it has completed; a
+/// final `commit` record marks the run as finished cleanly.
+///
+/// The run holds an exclusive lock on its journal for as long as it's going,
+/// so recovery can tell a live run from an interrupted one; the OS releases
+/// it when the process ends, crashes included.
+pub struct Journal {
+    path: PathBuf,
+    file: File,
+    next_id: u64,
+}
+
+/// A single move recorded in a journal
+#[derive(Debug, Clone)]
+pub struct JournalMove {
+    pub id: u64,
+    /// Action name (`move`, `archive`, `compress`, `delete`); journals written
+    /// before actions existed only hold moves
+    pub action: String,
+    pub source: PathBuf,
+    /// Empty for deletes, except background ones: where the item was renamed
+    /// before removal
+    pub destination: PathBuf,
+    /// Whether something was already at the destination when the action was
+    /// recorded, so it isn't the run's own; `None` in journals written before
+    /// this was recorded
+    pub destination_existed: Option<bool>,
+    /// When the action was recorded; `None` in journals written before
+    /// timestamps were
+    pub time: Option<SystemTime>,
+    pub completed: bool,
+}
+
+/// How a journaled run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalState {
+    /// Interrupted: no closing record yet
+    Open,
+    Committed,
+    Recovered,
+    Undone,
+}
+
+impl JournalState {
+    /// Whether the run needs no recovery
+    pub fn is_closed(self) -> bool {
+        self != JournalState::Open
+    }
+}
+
+/// Entry for the recovery results table
+#[derive(Tabled, Clone)]
+pub struct RecoveryEntry {
+    #[tabled(rename = "Source")]
+    pub source: String,
+    #[tabled(rename = "Destination")]
+    pub destination: String,
+    #[tabled(rename = "Action")]
+    pub action: String,
+}
+
+/// Directory holding the journals for one kind of operation (e.g. "organize")
+pub fn journal_dir(kind: &str) -> Result<PathBuf> {
+    let dir = data_dir()?.join("journals").join(kind);
+    fs::create_dir_all(&dir).map_err(JournalError::io("create journal directory", &dir))?;
+    Ok(dir)
+}
+
+/// `journal_dir` without creating it, `None` until a run of the kind has
+/// written a journal; for lookups that must not touch the disk, like dry runs
+pub fn existing_journal_dir(kind: &str) -> Result<Option<PathBuf>> {
+    let dir = data_dir()?.join("journals").join(kind);
+    Ok(dir.is_dir().then_some(dir))
+}
+
+impl Journal {
+    /// Start a new journal for this run
+    pub fn begin(kind: &str) -> Result<Self> {
+        let dir = journal_dir(kind)?;
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = dir.join(format!("{}-{}.jsonl", started, std::process::id()));
+
+        let file = OpenOptions::new()
+            .create_new(true)
+            .append(true)
+            .open(&path)
+            .map_err(JournalError::io("create journal", &path))?;
+        file.try_lock().map_err(|e| {
+            JournalError::io("lock journal", &path)(match e {
+                fs::TryLockError::Error(e) => e,
+                fs::TryLockError::WouldBlock => io::ErrorKind::WouldBlock.into(),
+            })
+        })?;
+
+        Ok(Self {
+            path,
+            file,
+            next_id: 0,
+        })
+    }
+
+    fn append(&mut self, record: serde_json::Value) -> Result<()> {
+        writeln!(self.file, "{}", record)
+            .and_then(|_| self.file.sync_data())
+            .map_err(JournalError::io("write journal", &self.path))
+    }
+
+    /// Record the intent to move a file; must be called before the move starts
+    pub fn record_intent(&mut self, source: &Path, destination: &Path) -> Result<u64> {
+        self.record_action("move", source, Some(destination))
+    }
+
+    /// Record the intent to apply an action to a path; must be called before
+    /// the action starts
+    pub fn record_action(
+        &mut self,
+        action: &str,
+        source: &Path,
+        destination: Option<&Path>,
+    ) -> Result<u64> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.append(json!({
+            "op": "intent",
+            "id": id,
+            "action": action,
+            "src": source.to_string_lossy(),
+            "dst": destination.map(|d| d.to_string_lossy()).unwrap_or_default(),
+            "dst_existed": destination.is_some_and(|d| d.exists() || d.is_symlink()),
+            "ts": SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        }))?;
+        Ok(id)
+    }
+
+    /// Mark a previously recorded move as completed
+    pub fn record_done(&mut self, id: u64) -> Result<()> {
+        self.append(json!({ "op": "done", "id": id }))
+    }
+
+    /// Mark the run as finished cleanly
+    pub fn commit(&mut self) -> Result<()> {
+        self.append(json!({ "op": "commit" }))
+    }
+}
+
+/// Read the moves recorded in a journal, and how the run ended
+pub fn read_journal(path: &Path) -> Result<(Vec<JournalMove>, JournalState)> {
+    let content = fs::read_to_string(path).map_err(JournalError::io("read journal", path))?;
+
+    let mut moves: Vec<JournalMove> = Vec::new();
+    let mut state = JournalState::Open;
+
+    // A crash can leave a torn last line, so unparseable lines are skipped
+    for record in content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+    {
+        let id = record.get("id").and_then(|i| i.as_u64());
+        match record.get("op").and_then(|o| o.as_str()) {
+            Some("intent") => {
+                let field = |key: &str| {
+                    record
+                        .get(key)
+                        .and_then(|v| v.as_str())
+                        .map(PathBuf::from)
+                        .unwrap_or_default()
+                };
+                moves.push(JournalMove {
+                    id: id.unwrap_or(0),
+                    action: record
+                        .get("action")
+                        .and_then(|a| a.as_str())
+                        .unwrap_or("move")
+                        .to_string(),
+                    source: field("src"),
+                    destination: field("dst"),
+                    destination_existed: record.get("dst_existed").and_then(|v| v.as_bool()),
+                    time: record
+                        .get("ts")
+                        .and_then(|t| t.as_u64())
+                        .map(|millis| UNIX_EPOCH + Duration::from_millis(millis)),
+                    completed: false,
+                });
+            }
+            Some("done") => {
+                if let Some(entry) = moves.iter_mut().find(|m| Some(m.id) == id) {
+                    entry.completed = true;
+                }
+            }
+            Some("commit") if state == JournalState::Open => state = JournalState::Committed,
+            Some("recovered") => state = JournalState::Recovered,
+            Some("undone") => state = JournalState::Undone,
+            _ => {}
+        }
+    }
+
+    Ok((moves, state))
+}
+
+/// List a kind's journals, oldest first
+fn list_journals(kind: &str) -> Result<Vec<PathBuf>> {
+    let Some(dir) = existing_journal_dir(kind)? else {
+        return Ok(Vec::new());
+    };
+    let mut journals: Vec<PathBuf> = fs::read_dir(&dir)
+        .map_err(JournalError::io("read journal directory", &dir))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("jsonl"))
+        .collect();
+    journals.sort();
+    Ok(journals)
+}
+
+/// Whether the run writing a journal is still going: it holds the lock
+/// `Journal::begin` takes
+fn is_live(path: &Path) -> bool {
+    File::open(path)
+        .is_ok_and(|file| matches!(file.try_lock_shared(), Err(fs::TryLockError::WouldBlock)))
+}
+
+/// List journals (oldest first) for runs that never committed and aren't
+/// still going, like a scheduled organize or a parallel clean
+pub fn find_incomplete_journals(kind: &str) -> Result<Vec<PathBuf>> {
+    let mut incomplete = Vec::new();
+    for path in list_journals(kind)? {
+        let (_, state) = read_journal(&path)?;
+        if !state.is_closed() && !is_live(&path) {
+            incomplete.push(path);
+        }
+    }
+
+    Ok(incomplete)
+}
+
+/// Finished items of every run of a kind that wasn't undone, oldest first,
+/// each with its journal
+pub fn completed_items(kind: &str) -> Result<Vec<(PathBuf, JournalMove)>> {
+    let mut items = Vec::new();
+    for path in list_journals(kind)? {
+        let (moves, state) = read_journal(&path)?;
+        if state != JournalState::Undone {
+            items.extend(
+                moves
+                    .into_iter()
+                    .filter(|m| m.completed)
+                    .map(|m| (path.clone(), m)),
+            );
+        }
+    }
+    Ok(items)
+}
+
+/// The most recent committed run that hasn't been undone yet
+pub fn find_last_committed_journal(kind: &str) -> Result<Option<PathBuf>> {
+    for path in list_journals(kind)?.into_iter().rev() {
+        if read_journal(&path)?.1 == JournalState::Committed {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+/// The committed runs started at or after `since` that haven't been undone,
+/// newest first
+pub fn find_committed_journals_since(kind: &str, since: SystemTime) -> Result<Vec<PathBuf>> {
+    let mut journals = Vec::new();
+    for path in list_journals(kind)?.into_iter().rev() {
+        if journal_started(&path).is_some_and(|started| started >= since)
+            && read_journal(&path)?.1 == JournalState::Committed
+        {
+            journals.push(path);
+        }
+    }
+    Ok(journals)
+}
+
+/// When a run started, from its journal's `<millis>-<pid>.jsonl` name
+pub fn journal_started(path: &Path) -> Option<SystemTime> {
+    let stem = path.file_stem()?.to_str()?;
+    let millis = stem.split('-').next()?.parse::<u64>().ok()?;
+    Some(UNIX_EPOCH + Duration::from_millis(millis))
+}
+
+/// Civil date (year, month, day) of a day count since the Unix epoch
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's algorithm, with eras of 400 years starting in March
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month as u32, day as u32)
+}
+
+/// Day count since the Unix epoch of a civil date
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = i64::from((month + 9) % 12);
+    let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Seconds since the Unix epoch, negative before it
+pub(crate) fn unix_seconds(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs() as i64,
+        Err(before) => -(before.duration().as_secs() as i64),
+    }
+}
+
+/// A time as `2024-05-03 14:02 UTC`
+pub fn format_utc(time: SystemTime) -> String {
+    let secs = unix_seconds(time);
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let minutes = secs.rem_euclid(86_400) / 60;
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/// Parse a `--since` time: a UTC date (`2024-05-03`), date and time
+/// (`2024-05-03T14:00` or `2024-05-03 14:00`), Unix seconds, or an age before
+/// now (`30m`, `2h`, `3d`, `1w`)
+pub fn parse_since(input: &str) -> Result<SystemTime> {
+    let invalid = || JournalError::InvalidTime(input.to_string());
+    let input = input.trim();
+
+    if let Some(unit) = input.chars().last().filter(char::is_ascii_alphabetic) {
+        let amount: u64 = input[..input.len() - 1].parse().map_err(|_| invalid())?;
+        let secs = match unit.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86_400,
+            'w' => 7 * 86_400,
+            _ => return Err(invalid()),
+        };
+        return SystemTime::now()
+            .checked_sub(Duration::from_secs(amount * secs))
+            .ok_or_else(invalid);
+    }
+    if let Ok(secs) = input.parse::<u64>() {
+        return Ok(UNIX_EPOCH + Duration::from_secs(secs));
+    }
+
+    let (date, time) = match input.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (input, None),
+    };
+    let mut date = date.split('-').map(|part| part.parse::<u32>().ok());
+    let (Some(Some(year)), Some(Some(month)), Some(Some(day)), None) =
+        (date.next(), date.next(), date.next(), date.next())
+    else {
+        return Err(invalid());
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(invalid());
+    }
+    let seconds_of_day = match time {
+        None => 0,
+        Some(time) => {
+            let mut parts = time.split(':').map(|part| part.parse::<u32>().ok());
+            match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                (Some(Some(hour)), Some(Some(minute)), second, None)
+                    if hour < 24 && minute < 60 =>
+                {
+                    let second = match second {
+                        None => 0,
+                        Some(Some(second)) if second < 60 => second,
+                        _ => return Err(invalid()),
+                    };
+                    i64::from(hour * 3600 + minute * 60 + second)
+                }
+                _ => return Err(invalid()),
+            }
+        }
+    };
+
+    let secs = days_from_civil(i64::from(year), month, day) * 86_400 + seconds_of_day;
+    u64::try_from(secs)
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+        .map_err(|_| invalid())
+}
+
+/// Append a closing record (`recovered`, `undone`) to a journal
+pub fn close_journal(path: &Path, op: &str) -> Result<()> {
+    OpenOptions::new()
+        .append(true)
+        .open(path)
+        .and_then(|mut file| {
+            // Start on a fresh line in case the crash left a torn record behind
+            writeln!(file, "\n{}", json!({ "op": op }))?;
+            file.sync_data()
+        })
+        .map_err(JournalError::io("close journal", path))
+}
+
+/// Sibling path a copy is staged at until it's complete
+///
+/// Copies are only renamed into place once they've finished, so an existing
+/// destination is always complete and a leftover staging path never is.
+pub fn partial_path(destination: &Path) -> PathBuf {
+    let mut name = destination.file_name().unwrap_or_default().to_os_string();
+    name.push(".partial");
+    destination.with_file_name(name)
+}
+
+/// Name of a journaled action for recovery reports
+fn action_noun(action: &str) -> &str {
+    match action {
+        "archive" => "Archive",
+        "compress" => "Compression",
+        "trash" => "Move to Trash",
+        "link" => "Move and link",
+        "symlink" => "Link",
+        _ => "Move",
+    }
+}
+
+/// Create a symlink at `link` pointing to `target`, made absolute so the link
+/// keeps working wherever it's read from
+pub(crate) fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    let target = std::path::absolute(target)?;
+    #[cfg(unix)]
+    return std::os::unix::fs::symlink(target, link);
+    #[cfg(windows)]
+    return if target.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    };
+}
+
+/// Whether `link` is a symlink pointing to `target`, as `symlink` creates them
+pub(crate) fn links_to(link: &Path, target: &Path) -> bool {
+    match (fs::read_link(link), std::path::absolute(target)) {
+        (Ok(points_to), Ok(target)) => points_to == target,
+        _ => false,
+    }
+}
+
+/// Remove a file or a whole directory tree
+pub(crate) fn remove_any(path: &Path) -> io::Result<()> {
+    if path.is_dir() && !path.is_symlink() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// Resolve the unfinished actions of an interrupted run and close its journal
+///
+/// For moves, archives and compressions:
+/// - source only: the action never happened, nothing to do
+/// - destination only: it finished but wasn't marked, mark it done
+/// - both: if the run put the destination there, the copy or archive
+///   finished (they're staged and renamed into place whole) but the source
+///   wasn't removed yet, so finish by removing it; a destination that was
+///   there before, or one an older journal doesn't say about, isn't the
+///   run's and both are left alone
+/// - neither: the file is missing and can't be recovered automatically
+///
+/// Only files the run itself created are ever removed. A journal whose run is
+/// still going is skipped.
+/// Leftover staging copies are removed. An interrupted delete is left as it
+/// is, unless it was renamed aside for a background delete: then the renamed
+/// item is removed. A move that should leave a symlink behind (`link`) but
+/// stopped after the move gets its symlink; a symlink placed instead of a move
+/// (`symlink`) is either there or not, nothing was moved. A file packed into a
+/// zip bundle (`bundle`) was only verified in it, so it's either removed or
+/// still in place.
+pub fn recover_journal(path: &Path, dry_run: bool) -> Result<Vec<RecoveryEntry>> {
+    // Held until recovery is done, so the run can't be live or recovered twice
+    let lock = File::open(path).map_err(JournalError::io("read journal", path))?;
+    match lock.try_lock() {
+        Ok(()) => {}
+        Err(fs::TryLockError::WouldBlock) => return Ok(Vec::new()),
+        Err(fs::TryLockError::Error(e)) => return Err(JournalError::io("lock journal", path)(e)),
+    }
+
+    let (moves, state) = read_journal(path)?;
+    if state.is_closed() {
+        return Ok(Vec::new());
+    }
+
+    let mut results = Vec::new();
+
+    for entry in moves.iter().filter(|m| !m.completed) {
+        let remove = |target: &Path| -> Result<()> {
+            if !dry_run {
+                remove_any(target).map_err(JournalError::io("remove", target))?;
+            }
+            Ok(())
+        };
+
+        if entry.action == "delete" {
+            // Background deletes rename the item aside first, see `deleting_path`
+            let staged = !entry.destination.as_os_str().is_empty()
+                && (entry.destination.exists() || entry.destination.is_symlink());
+            let action = if staged {
+                remove(&entry.destination)?;
+                "Finished background delete"
+            } else if entry.source.exists() {
+                "Delete interrupted (left in place)"
+            } else {
+                "Delete completed"
+            };
+            results.push(RecoveryEntry {
+                source: entry.source.display().to_string(),
+                destination: entry.destination.display().to_string(),
+                action: action.to_string(),
+            });
+            continue;
+        }
+
+        if entry.action == "symlink" {
+            let action = if links_to(&entry.destination, &entry.source) {
+                "Link completed"
+            } else {
+                "Not linked (left in place)"
+            };
+            results.push(RecoveryEntry {
+                source: entry.source.display().to_string(),
+                destination: entry.destination.display().to_string(),
+                action: action.to_string(),
+            });
+            continue;
+        }
+
+        if entry.action == "bundle" {
+            let action = if entry.source.exists() {
+                "Not bundled (left in place)"
+            } else {
+                "Bundle completed"
+            };
+            results.push(RecoveryEntry {
+                source: entry.source.display().to_string(),
+                destination: entry.destination.display().to_string(),
+                action: action.to_string(),
+            });
+            continue;
+        }
+
+        let partial = partial_path(&entry.destination);
+        if partial.exists() {
+            remove(&partial)?;
+        }
+
+        let src_exists = entry.source.exists();
+        let dst_exists = entry.destination.exists();
+
+        let action = match (src_exists, dst_exists) {
+            (true, true) if links_to(&entry.source, &entry.destination) => {
+                format!("{} completed", action_noun(&entry.action))
+            }
+            (true, false) => "Not moved (left in place)".to_string(),
+            (false, true) if entry.action == "link" => {
+                if !dry_run {
+                    // A dangling link from an earlier attempt is replaced
+                    if entry.source.is_symlink() {
+                        remove(&entry.source)?;
+                    }
+                    symlink(&entry.destination, &entry.source)
+                        .map_err(JournalError::io("link", &entry.source))?;
+                }
+                "Move completed, linked back".to_string()
+            }
+            (false, true) if entry.destination_existed == Some(true) => {
+                "✗ Source missing, destination was already there".to_string()
+            }
+            (false, true) => format!("{} completed", action_noun(&entry.action)),
+            (false, false) => "✗ Missing at both paths".to_string(),
+            (true, true) if entry.destination_existed == Some(false) => {
+                remove(&entry.source)?;
+                "Finished copy, removed source".to_string()
+            }
+            (true, true) => "✗ Destination isn't the run's (left both in place)".to_string(),
+        };
+
+        results.push(RecoveryEntry {
+            source: entry.source.display().to_string(),
+            destination: entry.destination.display().to_string(),
+            action,
+        });
+    }
+
+    if !dry_run {
+        close_journal(path, "recovered")?;
+    }
+
+    Ok(results)
+}
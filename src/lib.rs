@@ -1,17 +1,75 @@
+pub mod actions;
 pub mod aliases;
+pub mod audit;
 pub mod bookmarks;
+pub mod browse;
+pub mod bundle;
+pub mod caches;
+pub mod cargo;
+pub mod chrome;
 pub mod cleaner;
 pub mod cli;
+pub mod config;
+pub mod dedupe;
 pub mod display;
+pub mod error;
+pub mod flatten;
 pub mod functions;
+pub mod history;
+pub mod http;
+pub mod journal;
+pub mod licenses;
+pub mod notify;
 pub mod organizer;
 pub mod packages;
+pub mod progress;
+pub mod quarantine;
+pub mod reading;
+pub mod registry;
+pub mod rename;
+pub mod resolve;
+pub mod restore;
+pub mod rules;
+pub mod safari;
+pub mod schedule;
+pub mod setup;
+pub mod tree;
+pub mod xcode;
 
+pub use actions::*;
 pub use aliases::*;
+pub use audit::*;
 pub use bookmarks::*;
+pub use browse::*;
+pub use bundle::*;
+pub use caches::*;
+pub use cargo::*;
+pub use chrome::*;
 pub use cleaner::*;
 pub use cli::*;
+pub use config::*;
+pub use dedupe::*;
 pub use display::*;
+pub use error::*;
+pub use flatten::*;
 pub use functions::*;
+pub use history::*;
+pub use http::*;
+pub use journal::*;
+pub use licenses::*;
+pub use notify::*;
 pub use organizer::*;
 pub use packages::*;
+pub use progress::*;
+pub use quarantine::*;
+pub use reading::*;
+pub use registry::*;
+pub use rename::*;
+pub use resolve::*;
+pub use restore::*;
+pub use rules::*;
+pub use safari::*;
+pub use schedule::*;
+pub use setup::*;
+pub use tree::*;
+pub use xcode::*;
@@ -0,0 +1,215 @@
+use reqwest::blocking::Client;
+use serde_json::json;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use tabled::Tabled;
+
+use crate::{Config, PackageEntry, PackagesError, Registry, lookup_packages, npm_license};
+
+type Result<T, E = PackagesError> = std::result::Result<T, E>;
+
+/// The licenses dependencies may have, from `[packages] license_allowlist`
+///
+/// ```toml
+/// [packages]
+/// license_allowlist = ["MIT", "Apache-2.0", "BSD-3-Clause", "ISC"]
+/// ```
+///
+/// Without one, licenses are only summarized.
+#[derive(Debug, Clone, Default)]
+pub struct LicensePolicy {
+    allowed: Vec<String>,
+}
+
+impl LicensePolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            allowed: config.get_str_list("packages.license_allowlist"),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty()
+    }
+
+    /// Whether an SPDX expression is allowed: some `OR` alternative (`/` in
+    /// older crates) whose `AND` parts are all on the allowlist
+    pub fn allows(&self, expression: &str) -> bool {
+        let is_allowed = |license: &str| {
+            let license = license.trim().trim_matches(['(', ')']).trim();
+            self.allowed.iter().any(|a| a.eq_ignore_ascii_case(license))
+        };
+        expression
+            .replace('/', " OR ")
+            .split(" OR ")
+            .any(|alternative| alternative.split(" AND ").all(is_allowed))
+    }
+}
+
+/// A dependency's license and where it was found
+#[derive(Tabled, Clone)]
+pub struct LicenseEntry {
+    #[tabled(rename = "Package")]
+    pub name: String,
+    #[tabled(rename = "Version")]
+    pub version: String,
+    #[tabled(rename = "License")]
+    pub license: String,
+    #[tabled(rename = "Source")]
+    pub source: String,
+    #[tabled(rename = "File")]
+    pub file_path: String,
+    /// `None` without an allowlist
+    #[tabled(skip)]
+    pub allowed: Option<bool>,
+}
+
+impl LicenseEntry {
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "name": self.name,
+            "version": self.version,
+            "license": (self.license != UNKNOWN_LICENSE).then_some(&self.license),
+            "source": self.source,
+            "allowed": self.allowed,
+            "file": self.file_path,
+        })
+    }
+}
+
+/// Row of the per-license summary
+#[derive(Tabled, Clone)]
+pub struct LicenseSummaryEntry {
+    #[tabled(rename = "License")]
+    pub license: String,
+    #[tabled(rename = "Packages")]
+    pub packages: usize,
+    #[tabled(rename = "Share")]
+    pub share: String,
+    #[tabled(rename = "Allowed")]
+    pub allowed: String,
+}
+
+/// What dependencies without a declared license are listed under
+const UNKNOWN_LICENSE: &str = "Unknown";
+
+/// The license of an installed npm package, from the `node_modules` next to
+/// the manifest that declares it
+fn installed_npm_license(manifest: &Path, name: &str) -> Option<String> {
+    let installed = manifest
+        .parent()?
+        .join("node_modules")
+        .join(name)
+        .join("package.json");
+    let content = fs::read_to_string(installed).ok()?;
+    npm_license(&serde_json::from_str(&content).ok()?)
+}
+
+/// The declared license of every dependency
+///
+/// Installed npm packages are read from `node_modules`, which has the version
+/// in use; the rest are looked up in their registry (the latest release's
+/// license), cached like `packages outdated`'s lookups. Dependencies with
+/// neither, like composer and go ones, are `Unknown`, and with an allowlist
+/// they're violations too: nothing says they're allowed.
+pub fn collect_licenses(
+    packages: &[PackageEntry],
+    client: &Client,
+    max_age: Duration,
+    policy: &LicensePolicy,
+    verbose: bool,
+) -> Result<Vec<LicenseEntry>> {
+    let local: Vec<Option<String>> = packages
+        .iter()
+        .map(|p| {
+            (p.package_type == "npm")
+                .then(|| installed_npm_license(Path::new(&p.file_path), &p.name))
+                .flatten()
+        })
+        .collect();
+    let to_look_up: Vec<(Registry, &str)> = packages
+        .iter()
+        .zip(&local)
+        .filter(|(_, local)| local.is_none())
+        .filter_map(|(p, _)| {
+            Some((
+                Registry::for_package_type(&p.package_type)?,
+                p.name.as_str(),
+            ))
+        })
+        .collect();
+    let lookups = lookup_packages(&to_look_up, client, max_age, verbose)?;
+
+    let mut entries: Vec<LicenseEntry> = packages
+        .iter()
+        .zip(local)
+        .map(|(package, local)| {
+            let registry = Registry::for_package_type(&package.package_type);
+            let (license, source) = match local {
+                Some(license) => (Some(license), "node_modules".to_string()),
+                None => match registry {
+                    Some(registry) => (
+                        lookups
+                            .get(registry, &package.name)
+                            .and_then(|lookup| lookup.license.clone()),
+                        registry.name().to_string(),
+                    ),
+                    None => (None, "-".to_string()),
+                },
+            };
+            LicenseEntry {
+                name: package.name.clone(),
+                version: package.version.clone(),
+                allowed: (!policy.is_empty())
+                    .then(|| license.as_deref().is_some_and(|l| policy.allows(l))),
+                license: license.unwrap_or_else(|| UNKNOWN_LICENSE.to_string()),
+                source,
+                file_path: package.file_path.clone(),
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        a.name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+    Ok(entries)
+}
+
+/// One row per license, most packages first; a package declared in several
+/// manifests counts once per license
+pub fn license_summary(
+    entries: &[LicenseEntry],
+    policy: &LicensePolicy,
+) -> Vec<LicenseSummaryEntry> {
+    let mut packages: BTreeMap<&str, HashSet<String>> = BTreeMap::new();
+    for entry in entries {
+        packages
+            .entry(entry.license.as_str())
+            .or_default()
+            .insert(entry.name.to_lowercase());
+    }
+    let total: usize = packages.values().map(HashSet::len).sum();
+
+    let mut summary: Vec<LicenseSummaryEntry> = packages
+        .into_iter()
+        .map(|(license, names)| LicenseSummaryEntry {
+            license: license.to_string(),
+            packages: names.len(),
+            share: format!("{:.0}%", names.len() as f64 * 100.0 / total.max(1) as f64),
+            allowed: if policy.is_empty() {
+                "-"
+            } else if license != UNKNOWN_LICENSE && policy.allows(license) {
+                "✓ Yes"
+            } else {
+                "✗ No"
+            }
+            .to_string(),
+        })
+        .collect();
+    summary.sort_by_key(|entry| std::cmp::Reverse(entry.packages));
+    summary
+}
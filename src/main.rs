@@ -1,14 +1,34 @@
 use anyhow::Result;
 use colored::Colorize;
+use std::process::ExitCode;
 use utils::{
-    build_cli, handle_aliases_mode, handle_bookmarks_mode, handle_clean_mode,
-    handle_functions_mode, handle_organize_mode, handle_packages_mode,
+    apply_global_options, build_cli, exit_code, handle_aliases_mode, handle_bookmarks_mode,
+    handle_clean_mode, handle_functions_mode, handle_organize_mode, handle_packages_mode,
+    handle_setup, offer_first_run_setup,
 };
 
-fn main() -> Result<()> {
+/// Exits with 1 for fatal errors and 75 (EX_TEMPFAIL) when a retry may succeed
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{} {:#}", "Error:".red().bold(), e);
+            exit_code(&e)
+        }
+    }
+}
+
+fn run() -> Result<()> {
     let matches = build_cli().get_matches();
     let mode = matches.get_one::<String>("mode").unwrap();
 
+    apply_global_options(&matches)?;
+
+    if matches.get_flag("setup") {
+        return handle_setup();
+    }
+    offer_first_run_setup()?;
+
     match mode.as_str() {
         "functions" => {
             println!("{}", "🔧 Shell Function Explorer".bold().cyan());
@@ -16,12 +36,15 @@ fn main() -> Result<()> {
             handle_functions_mode(&matches)
         }
         "packages" => {
-            println!("{}", "📦 Package Version Explorer".bold().cyan());
-            println!("{}", "─".repeat(60).dimmed());
+            // Keep JSON output machine-readable
+            if matches.get_one::<String>("format").map(|s| s.as_str()) != Some("json") {
+                println!("{}", "📦 Package Version Explorer".bold().cyan());
+                println!("{}", "─".repeat(60).dimmed());
+            }
             handle_packages_mode(&matches)
         }
         "clean" => {
-            println!("{}", "🧹 Node Modules Cleaner".bold().cyan());
+            println!("{}", "🧹 Build Artifact Cleaner".bold().cyan());
             println!("{}", "─".repeat(60).dimmed());
             handle_clean_mode(&matches)
         }
@@ -35,7 +58,7 @@ fn main() -> Result<()> {
             println!("{}", "─".repeat(60).dimmed());
             handle_bookmarks_mode(&matches)
         }
-        "aliases" | _ => {
+        _ => {
             println!("{}", "🔍 Shell Alias Explorer".bold().cyan());
             println!("{}", "─".repeat(50).dimmed());
             handle_aliases_mode(&matches)
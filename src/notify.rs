@@ -0,0 +1,164 @@
+use colored::Colorize;
+use std::io;
+use std::process::{Command, ExitStatus};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+use crate::{Config, ConfigError, Severity};
+
+type Result<T, E = NotifyError> = std::result::Result<T, E>;
+
+/// Errors from sending completion notifications
+///
+/// Only a broken config is fatal; failed deliveries are recoverable and get
+/// reported without affecting the operation that just finished.
+#[derive(Debug, Error)]
+pub enum NotifyError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("Failed to run {program}")]
+    Command {
+        program: &'static str,
+        #[source]
+        source: io::Error,
+    },
+    #[error("notifier exited with {0}")]
+    Notifier(ExitStatus),
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    #[error("webhook returned {0}")]
+    Webhook(reqwest::StatusCode),
+}
+
+impl NotifyError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            NotifyError::Config(e) => e.severity(),
+            _ => Severity::Recoverable,
+        }
+    }
+}
+
+/// Completion notifications for long-running operations
+///
+/// Configured under `[notify]`; `--notify` turns on desktop notifications for
+/// a single run without touching the config.
+///
+/// ```toml
+/// [notify]
+/// desktop = true
+/// webhook = "https://hooks.slack.com/services/..."
+/// min_seconds = 30   # only notify when the operation took at least this long
+/// ```
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    desktop: bool,
+    webhook: Option<String>,
+    min_duration: Duration,
+    started: Instant,
+}
+
+impl Notifier {
+    /// Load notification settings from the global config, starting the timer now
+    pub fn load(desktop_flag: bool) -> Result<Self, ConfigError> {
+        Ok(Self::from_config(&Config::load()?, desktop_flag))
+    }
+
+    pub fn from_config(config: &Config, desktop_flag: bool) -> Self {
+        let min_seconds = config
+            .get("notify.min_seconds")
+            .and_then(|v| v.as_integer())
+            .unwrap_or(0)
+            .max(0) as u64;
+
+        Self {
+            desktop: desktop_flag || config.get_bool("notify.desktop").unwrap_or(false),
+            webhook: config.get_str("notify.webhook").map(|s| s.to_string()),
+            min_duration: Duration::from_secs(min_seconds),
+            started: Instant::now(),
+        }
+    }
+
+    /// Send the configured notifications if the operation ran long enough
+    ///
+    /// Notifications are best-effort: failures are reported but never abort
+    /// the operation that just finished.
+    pub fn finish(&self, title: &str, message: &str) {
+        let elapsed = self.started.elapsed();
+        if elapsed < self.min_duration {
+            return;
+        }
+
+        let message = format!("{} ({}s)", message, elapsed.as_secs());
+
+        if self.desktop
+            && let Err(e) = send_desktop_notification(title, &message)
+        {
+            println!("{} Desktop notification failed: {}", "⚠️".yellow(), e);
+        }
+
+        if let Some(url) = &self.webhook
+            && let Err(e) = send_webhook(url, title, &message)
+        {
+            println!("{} Webhook notification failed: {}", "⚠️".yellow(), e);
+        }
+    }
+}
+
+/// Show a desktop notification (osascript on macOS, notify-send elsewhere)
+fn send_desktop_notification(title: &str, message: &str) -> Result<()> {
+    let status = if cfg!(target_os = "macos") {
+        // AppleScript string literals only need quotes and backslashes escaped
+        let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+        Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                "display notification \"{}\" with title \"{}\"",
+                escape(message),
+                escape(title)
+            ))
+            .status()
+            .map_err(|source| NotifyError::Command {
+                program: "osascript",
+                source,
+            })?
+    } else {
+        Command::new("notify-send")
+            .arg(title)
+            .arg(message)
+            .status()
+            .map_err(|source| NotifyError::Command {
+                program: "notify-send",
+                source,
+            })?
+    };
+
+    if !status.success() {
+        return Err(NotifyError::Notifier(status));
+    }
+
+    Ok(())
+}
+
+/// POST a JSON payload to the webhook; `text` makes it work with Slack-style hooks
+fn send_webhook(url: &str, title: &str, message: &str) -> Result<()> {
+    let payload = serde_json::json!({
+        "title": title,
+        "message": message,
+        "text": format!("{}: {}", title, message),
+    });
+
+    let response = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(payload.to_string())
+        .send()?;
+
+    if !response.status().is_success() {
+        return Err(NotifyError::Webhook(response.status()));
+    }
+
+    Ok(())
+}
@@ -1,4 +1,3 @@
-use anyhow::{Context, Result};
 use colored::Colorize;
 use crossterm::{
     cursor,
@@ -6,11 +5,154 @@ use crossterm::{
     execute,
     terminal::{self, ClearType},
 };
-use std::collections::HashMap;
-use std::fs;
-use std::io::{Write, stdout};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Stdout, Write, stdout};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use tabled::Tabled;
+use thiserror::Error;
+
+use crate::{
+    Action, ActionError, ActionRunner, Config, ConfigError, Exclusion, JournalError, JournalMove,
+    LinkMode, OrganizeRun, REVIEW_FOLDER, RecoveryEntry, RenameRules, Severity, civil_from_days,
+    completed_items, data_dir, expand_home, format_size, format_utc, home_dir, journal_started,
+    quarantine_files, record_organize_run, registrable_domain, unix_seconds, warn_incomplete_runs,
+};
+
+type Result<T, E = OrganizeError> = std::result::Result<T, E>;
+
+/// Errors from organizing files
+///
+/// A category folder that can't be created or a file that can't be copied is
+/// recoverable: its files get an error status and the rest are still moved.
+/// So is a zip bundle that can't be written or verified.
+/// An unreadable source directory, journal or safe-copy manifest failures and
+/// terminal errors are fatal.
+#[derive(Debug, Error)]
+pub enum OrganizeError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Journal(#[from] JournalError),
+    #[error(transparent)]
+    Action(#[from] ActionError),
+    #[error("Failed to read directory: {}", path.display())]
+    ReadDir {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to create directory: {}", path.display())]
+    CreateDir {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to {action}: {}", path.display())]
+    File {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to {action} safe-copy manifest: {}", path.display())]
+    Manifest {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to parse safe-copy manifest")]
+    ManifestParse(#[from] serde_json::Error),
+    #[error("Unknown organize scheme '{0}' (use type, date, type-date, size or source)")]
+    UnknownScheme(String),
+    #[error("Unknown date '{0}' (use modified or created)")]
+    UnknownDateSource(String),
+    #[error("Unknown conflict strategy '{0}' (use rename, skip, overwrite or prompt)")]
+    UnknownConflict(String),
+    #[error("Unknown organize preset '{0}' (use tidy)")]
+    UnknownPreset(String),
+    #[error("Invalid exclusion '{0}'")]
+    InvalidExclude(String),
+    #[error("Invalid rename template '{template}': {reason}")]
+    InvalidTemplate { template: String, reason: String },
+    #[error("Invalid [organize] config: {0}")]
+    InvalidConfig(String),
+    #[error("Failed to {action} plan: {}", path.display())]
+    Plan {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to {action} organize history: {}", path.display())]
+    History {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Invalid minimum age '{0}' (use 30s, 10m, 2h or 1d; 0 turns it off)")]
+    InvalidMinAge(String),
+    #[error("Organizing several folders needs --dest, the folder to organize them into")]
+    NoDestination,
+    #[error("Invalid plan {}: {reason}", path.display())]
+    InvalidPlan { path: PathBuf, reason: String },
+    #[error("Failed to write bundle: {}", path.display())]
+    Bundle {
+        path: PathBuf,
+        #[source]
+        source: zip::result::ZipError,
+    },
+    #[error("Bundled copy doesn't match the original: {}", .0.display())]
+    BundleMismatch(PathBuf),
+    #[error(transparent)]
+    Terminal(#[from] io::Error),
+}
+
+impl OrganizeError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            OrganizeError::CreateDir { .. }
+            | OrganizeError::File { .. }
+            | OrganizeError::Bundle { .. }
+            | OrganizeError::BundleMismatch(_) => Severity::Recoverable,
+            OrganizeError::Config(e) => e.severity(),
+            OrganizeError::Journal(e) => e.severity(),
+            OrganizeError::Action(e) => e.severity(),
+            _ => Severity::Fatal,
+        }
+    }
+}
+
+/// Journal kind used for organize runs
+pub const ORGANIZE_JOURNAL_KIND: &str = "organize";
+
+/// Copies made by `--safe` runs that still await `organize commit`, in the data dir
+const SAFE_MANIFEST_FILE: &str = "organize-safe-copies.json";
+
+/// File at an organized root listing globs of files to leave in place, one per line
+pub const ORGANIZE_IGNORE_FILE: &str = ".organizeignore";
+
+/// iCloud Drive's local storage, relative to the home directory
+const ICLOUD_DRIVE_PATH: &str = "Library/Mobile Documents";
+
+/// Extensions browsers give downloads until they finish: Chrome's
+/// `.crdownload`, Firefox's `.part` and Safari's `.download`
+const PARTIAL_DOWNLOAD_EXTENSIONS: &[&str] = &["crdownload", "part", "download"];
+
+/// Files modified more recently than this are left alone by default, as they
+/// may still be being written
+pub const DEFAULT_MIN_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// Number of files shown in the pre-organize large-file report by default
+pub const LARGE_FILE_REPORT_COUNT: usize = 10;
+
+/// Home folders that iCloud "Desktop & Documents Folders" keeps in sync
+const ICLOUD_SYNCED_FOLDERS: &[&str] = &["Desktop", "Documents"];
 
 /// Markers that indicate a development/project folder that should be skipped
 const DEV_MARKERS: &[&str] = &[
@@ -89,6 +231,20 @@ impl std::fmt::Display for FileCategory {
 }
 
 impl FileCategory {
+    pub const ALL: &[FileCategory] = &[
+        FileCategory::Documents,
+        FileCategory::Images,
+        FileCategory::Videos,
+        FileCategory::Audio,
+        FileCategory::Archives,
+        FileCategory::Code,
+        FileCategory::Data,
+        FileCategory::Executables,
+        FileCategory::Fonts,
+        FileCategory::Ebooks,
+        FileCategory::Other,
+    ];
+
     pub fn folder_name(&self) -> &str {
         match self {
             FileCategory::Documents => "Documents",
@@ -153,26 +309,488 @@ impl FileCategory {
             _ => FileCategory::Other,
         }
     }
+
+    /// Category of a file's content, from its magic bytes; `None` when the
+    /// type isn't recognized or the file can't be read
+    pub fn from_content(path: &Path) -> Option<Self> {
+        let kind = infer::get_from_path(path).ok()??;
+        Some(match (kind.matcher_type(), kind.mime_type()) {
+            (_, "application/vnd.sqlite3") => FileCategory::Data,
+            (_, "text/x-shellscript") => FileCategory::Code,
+            (_, "text/xml") => FileCategory::Data,
+            (infer::MatcherType::App, _) => FileCategory::Executables,
+            (infer::MatcherType::Archive, _) => FileCategory::Archives,
+            (infer::MatcherType::Audio, _) => FileCategory::Audio,
+            (infer::MatcherType::Book, _) => FileCategory::Ebooks,
+            (infer::MatcherType::Doc | infer::MatcherType::Text, _) => FileCategory::Documents,
+            (infer::MatcherType::Font, _) => FileCategory::Fonts,
+            (infer::MatcherType::Image, _) => FileCategory::Images,
+            (infer::MatcherType::Video, _) => FileCategory::Videos,
+            (infer::MatcherType::Custom, _) => return None,
+        })
+    }
+
+    /// Category of a file by its extension, with its content as a fallback
+    /// for unknown or missing extensions; `prefer_content` lets the content
+    /// win whenever it's recognized
+    ///
+    /// Zip-based formats (`.pages`, `.docx`, `.epub`, ...) read as archives,
+    /// so archive content never overrides a known extension. Returns whether
+    /// the content decided.
+    pub fn detect(path: &Path, prefer_content: bool) -> (Self, bool) {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let by_extension = FileCategory::from_extension(extension);
+        if by_extension != FileCategory::Other && !prefer_content {
+            return (by_extension, false);
+        }
+
+        match FileCategory::from_content(path) {
+            Some(FileCategory::Archives) if by_extension != FileCategory::Other => {
+                (by_extension, false)
+            }
+            Some(by_content) => (by_content.clone(), by_content != by_extension),
+            None => (by_extension, false),
+        }
+    }
 }
 
 #[derive(Tabled, Clone)]
 pub struct OrganizeEntry {
     #[tabled(rename = "File")]
     pub file_name: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
     #[tabled(rename = "Category")]
     pub category: String,
     #[tabled(rename = "Destination")]
     pub destination: String,
     #[tabled(rename = "Status")]
     pub status: String,
+    /// Size in bytes, for the run history
+    #[tabled(skip)]
+    pub bytes: u64,
 }
 
 #[derive(Clone)]
 pub struct FileToOrganize {
     pub path: PathBuf,
     pub file_name: String,
+    /// Name in its folder: `file_name`, unless a rename rule gave it a new
+    /// one, see `RenameRules::apply`
+    pub target_name: String,
     pub category: FileCategory,
+    /// The category comes from the file's content rather than its extension
+    pub by_content: bool,
+    /// Folder under the root it's organized into, e.g. `Images` or `2024/05`
+    pub folder: PathBuf,
+    pub size: u64,
     pub selected: bool,
+    /// The file it's a sidecar of, whose folder, name and selection it
+    /// follows, see `attach_sidecars`
+    pub sidecar_of: Option<PathBuf>,
+}
+
+impl FileToOrganize {
+    /// Category for results tables, noting when the content decided it or
+    /// it goes along with another file
+    pub fn category_label(&self) -> String {
+        if self.sidecar_of.is_some() {
+            format!("{} (sidecar)", self.category)
+        } else if self.by_content {
+            format!("{} (by content)", self.category)
+        } else {
+            self.category.to_string()
+        }
+    }
+
+    /// File it under `category` instead of the detected one
+    pub fn set_category(&mut self, category: FileCategory, by: &OrganizeBy) {
+        let metadata = fs::metadata(&self.path).ok();
+        self.folder = by.folder(&self.path, &category, metadata.as_ref());
+        self.category = category;
+        self.by_content = false;
+    }
+}
+
+/// Which of a file's times date-based schemes go by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DateSource {
+    #[default]
+    Modified,
+    /// Birth time; files whose filesystem doesn't record it use the
+    /// modification time
+    Created,
+}
+
+impl DateSource {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "modified" | "mtime" => Ok(DateSource::Modified),
+            "created" | "birth" => Ok(DateSource::Created),
+            _ => Err(OrganizeError::UnknownDateSource(name.to_string())),
+        }
+    }
+
+    pub(crate) fn time(self, metadata: &fs::Metadata) -> Option<SystemTime> {
+        match self {
+            DateSource::Created => metadata.created().or_else(|_| metadata.modified()).ok(),
+            DateSource::Modified => metadata.modified().ok(),
+        }
+    }
+}
+
+/// The folders an organize run files items into
+///
+/// Set with `--by`, or `by` under `[organize]`; date-based schemes use the
+/// `--date` / `date` time (UTC):
+///
+/// ```toml
+/// [organize]
+/// by = "type-date"   # Documents/2024/
+/// date = "created"
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OrganizeBy {
+    /// Category folders, e.g. `Images/`
+    #[default]
+    Type,
+    /// Year and month folders, e.g. `2024/05/`
+    Date(DateSource),
+    /// Year folders inside category folders, e.g. `Documents/2024/`
+    TypeDate(DateSource),
+    /// Size buckets, e.g. `Huge/` for files over 1 GB, see `SIZE_BUCKETS`
+    Size,
+    /// Domain a file was downloaded from, e.g. `Sources/github.com/`, see
+    /// `download_source`
+    Source,
+}
+
+/// Folders of the size scheme with the size a file must exceed to go there,
+/// largest first; smaller files go to `Tiny`
+pub const SIZE_BUCKETS: &[(&str, u64)] = &[
+    ("Huge", 1 << 30),
+    ("Large", 100 << 20),
+    ("Medium", 10 << 20),
+    ("Small", 1 << 20),
+];
+
+impl OrganizeBy {
+    /// Parse a scheme name: `type`, `date`, `type-date`, `size` or `source`
+    pub fn parse(name: &str, date: DateSource) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "type" | "category" => Ok(OrganizeBy::Type),
+            "date" => Ok(OrganizeBy::Date(date)),
+            "type-date" | "type_date" => Ok(OrganizeBy::TypeDate(date)),
+            "size" => Ok(OrganizeBy::Size),
+            "source" | "where-from" => Ok(OrganizeBy::Source),
+            _ => Err(OrganizeError::UnknownScheme(name.to_string())),
+        }
+    }
+
+    /// The scheme from `--by` / `--date`, else `[organize] by` / `date`, else
+    /// by type
+    pub fn load(by: Option<&str>, date: Option<&str>) -> Result<Self> {
+        let config = Config::load()?;
+        let date = match date.or(config.get_str("organize.date")) {
+            Some(name) => DateSource::parse(name)?,
+            None => DateSource::default(),
+        };
+        match by.or(config.get_str("organize.by")) {
+            Some(name) => Self::parse(name, date),
+            None => Ok(OrganizeBy::Type),
+        }
+    }
+
+    /// Folder, relative to the organized root, a file goes into
+    pub fn folder(
+        &self,
+        path: &Path,
+        category: &FileCategory,
+        metadata: Option<&fs::Metadata>,
+    ) -> PathBuf {
+        let date = |source: DateSource| {
+            metadata
+                .and_then(|m| source.time(m))
+                .map(year_month)
+                .unwrap_or((1970, 1))
+        };
+
+        match *self {
+            OrganizeBy::Type => PathBuf::from(category.folder_name()),
+            OrganizeBy::Date(source) => {
+                let (year, month) = date(source);
+                PathBuf::from(year.to_string()).join(format!("{:02}", month))
+            }
+            OrganizeBy::TypeDate(source) => {
+                PathBuf::from(category.folder_name()).join(date(source).0.to_string())
+            }
+            OrganizeBy::Size => {
+                let size = metadata.map(|m| m.len()).unwrap_or(0);
+                PathBuf::from(
+                    SIZE_BUCKETS
+                        .iter()
+                        .find(|(_, over)| size > *over)
+                        .map(|(name, _)| *name)
+                        .unwrap_or("Tiny"),
+                )
+            }
+            OrganizeBy::Source => PathBuf::from(SOURCES_FOLDER)
+                .join(download_source(path).unwrap_or_else(|| "Unknown".to_string())),
+        }
+    }
+}
+
+/// Folders outside the organized root that some categories go to instead of
+/// their folder under it
+///
+/// Set by category name under `[organize.destinations]`; paths must be
+/// absolute or start with `~`:
+///
+/// ```toml
+/// [organize.destinations]
+/// Images = "~/Pictures/Inbox"
+/// Ebooks = "~/Books"
+/// ```
+///
+/// Only the category schemes use them: `type` files straight into the folder,
+/// `type-date` into year folders inside it. Moves to another volume copy and
+/// then remove the original, see `Action::Move`.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryDestinations(HashMap<FileCategory, PathBuf>);
+
+impl CategoryDestinations {
+    /// The `[organize.destinations]` table, rejecting unknown categories and
+    /// relative paths
+    pub fn load() -> Result<Self> {
+        let mut destinations = HashMap::new();
+        for (name, path) in Config::load()?.get_str_map("organize.destinations") {
+            let category = FileCategory::ALL
+                .iter()
+                .find(|c| c.folder_name().eq_ignore_ascii_case(&name))
+                .ok_or_else(|| {
+                    OrganizeError::InvalidConfig(format!(
+                        "Unknown category '{}' in destinations (use {})",
+                        name,
+                        FileCategory::ALL
+                            .iter()
+                            .map(|c| c.folder_name())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                })?;
+            let folder = expand_home(&path)?;
+            if !folder.is_absolute() {
+                return Err(OrganizeError::InvalidConfig(format!(
+                    "Destination for {} must be an absolute path or start with ~: {}",
+                    category, path
+                )));
+            }
+            destinations.insert(category.clone(), folder);
+        }
+        Ok(Self(destinations))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Point the files of overridden categories at their destination, when
+    /// `by` is a category scheme
+    pub fn apply(&self, files: &mut [FileToOrganize], by: &OrganizeBy) {
+        for file in files {
+            let Some(destination) = self.0.get(&file.category) else {
+                continue;
+            };
+            match by {
+                OrganizeBy::Type => file.folder = destination.clone(),
+                OrganizeBy::TypeDate(_) => {
+                    if let Ok(year) = file.folder.strip_prefix(file.category.folder_name()) {
+                        file.folder = destination.join(year);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Folder the source scheme files into, one subfolder per domain
+pub const SOURCES_FOLDER: &str = "Sources";
+
+/// Extended attribute where macOS records the URLs a file was downloaded from
+const WHERE_FROMS_XATTR: &str = "com.apple.metadata:kMDItemWhereFroms";
+
+/// URLs a file was downloaded from, as recorded by the browser
+///
+/// Browsers store the download URL first and the page it was linked from
+/// second. Files without the attribute (or on systems without it) have none.
+pub fn where_froms(path: &Path) -> Vec<String> {
+    let Ok(Some(data)) = xattr::get(path, WHERE_FROMS_XATTR) else {
+        return Vec::new();
+    };
+    match plist::Value::from_reader(io::Cursor::new(data)) {
+        Ok(plist::Value::Array(values)) => values
+            .into_iter()
+            .filter_map(|value| value.into_string())
+            .filter(|url| !url.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Registrable domain a file was downloaded from, e.g. `github.com`
+///
+/// The page the download was linked from is preferred over the download URL
+/// itself, since downloads are often served from CDNs
+/// (`objects.githubusercontent.com`). Only web URLs count.
+pub fn download_source(path: &Path) -> Option<String> {
+    where_froms(path)
+        .iter()
+        .rev()
+        .find(|url| url.starts_with("http://") || url.starts_with("https://"))
+        .map(|url| registrable_domain(url))
+        .filter(|domain| !domain.is_empty())
+}
+
+/// UTC year and month of a time
+fn year_month(time: SystemTime) -> (i64, u32) {
+    let (year, month, _) = civil_from_days(unix_seconds(time).div_euclid(86_400));
+    (year, month)
+}
+
+/// What to do with a file whose destination is already taken
+///
+/// Set with `--on-conflict`, or `on_conflict` under `[organize]`; skipping is
+/// the default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Put it next to the existing file with ` (1)`, ` (2)`, ... appended
+    Rename,
+    #[default]
+    Skip,
+    /// Replace the existing file, which goes to the Trash (never with `--safe`)
+    Overwrite,
+    /// Ask for each conflict
+    Prompt,
+}
+
+impl OnConflict {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "rename" => Ok(OnConflict::Rename),
+            "skip" => Ok(OnConflict::Skip),
+            "overwrite" => Ok(OnConflict::Overwrite),
+            "prompt" | "ask" => Ok(OnConflict::Prompt),
+            _ => Err(OrganizeError::UnknownConflict(name.to_string())),
+        }
+    }
+
+    /// The strategy from `--on-conflict`, else `[organize] on_conflict`, else skip
+    pub fn load(flag: Option<&str>) -> Result<Self> {
+        match flag.or(Config::load()?.get_str("organize.on_conflict")) {
+            Some(name) => Self::parse(name),
+            None => Ok(OnConflict::default()),
+        }
+    }
+}
+
+/// Built-in sets of folders organized by one command, see `--preset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrganizePreset {
+    /// The usual clutter: `~/Downloads`, `~/Desktop` and the files at the
+    /// top of `~/Documents`, each organized in place, renaming on conflicts
+    Tidy,
+}
+
+impl OrganizePreset {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "tidy" => Ok(OrganizePreset::Tidy),
+            _ => Err(OrganizeError::UnknownPreset(name.to_string())),
+        }
+    }
+
+    /// The preset's folders that exist
+    pub fn sources(self) -> Result<Vec<PathBuf>> {
+        let home = home_dir()?;
+        let folders = match self {
+            OrganizePreset::Tidy => ["Downloads", "Desktop", "Documents"],
+        };
+        Ok(folders
+            .iter()
+            .map(|folder| home.join(folder))
+            .filter(|path| path.is_dir())
+            .collect())
+    }
+
+    /// Conflict strategy when `--on-conflict` isn't given
+    pub fn on_conflict(self) -> OnConflict {
+        match self {
+            OrganizePreset::Tidy => OnConflict::Rename,
+        }
+    }
+}
+
+/// Options of an organize run
+#[derive(Debug, Clone)]
+pub struct OrganizeOptions {
+    pub method: OrganizeMethod,
+    pub by: OrganizeBy,
+    pub on_conflict: OnConflict,
+    /// Categorize by content whenever it's recognized, not only when the
+    /// extension is unknown
+    pub prefer_content: bool,
+    pub dry_run: bool,
+    pub verbose: bool,
+    pub interactive: bool,
+    /// How many of the largest files to list before organizing; 0 for none
+    pub report_count: usize,
+    /// Files larger than this many bytes are left in place
+    pub skip_over: Option<u64>,
+    /// Leave a symlink behind for each moved file, see `ActionRunner::with_links`
+    pub link: Option<LinkMode>,
+    /// Globs of files never to organize, from `--exclude` and `[organize]
+    /// exclude`; the root's `.organizeignore` adds more, see `load_exclusions`
+    pub exclude: Vec<String>,
+    /// New names for files as they're organized
+    pub rename: RenameRules,
+    /// Write what the run would do to this file instead of doing it, see
+    /// `OrganizePlan`
+    pub plan: Option<PathBuf>,
+    /// Root to create the category folders under, instead of the source
+    pub dest: Option<PathBuf>,
+    /// Folders elsewhere that some categories go to, see
+    /// `CategoryDestinations`
+    pub destinations: CategoryDestinations,
+    /// Only organize files last modified longer ago than this
+    pub older_than: Option<Duration>,
+    /// Files modified more recently are still being written and left alone,
+    /// `DEFAULT_MIN_AGE` unless set; zero organizes them too
+    pub min_age: Duration,
+    /// Send files without a category or unmodified for this long to the
+    /// review folder instead, see `quarantine_files`
+    pub quarantine: Option<Duration>,
+}
+
+/// How an organize run places files
+#[derive(Debug, Clone)]
+pub enum OrganizeMethod {
+    /// Apply an action; the default moves files into their category folders
+    Action(Action),
+    /// `--safe`: verified copies, see `Placement::Copy`
+    SafeCopy,
+}
+
+#[derive(Tabled, Clone)]
+pub struct LargeFileEntry {
+    #[tabled(rename = "File")]
+    pub file_name: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Category")]
+    pub category: String,
+    #[tabled(rename = "Action")]
+    pub action: String,
 }
 
 /// Check if a directory is a development/project folder
@@ -191,9 +809,8 @@ pub fn is_dev_folder(path: &Path) -> bool {
         let name_str = name.to_string_lossy();
 
         for marker in DEV_MARKERS {
-            if marker.starts_with('*') {
+            if let Some(suffix) = marker.strip_prefix('*') {
                 // Handle wildcard patterns like *.csproj
-                let suffix = &marker[1..];
                 if name_str.ends_with(suffix) {
                     return true;
                 }
@@ -206,12 +823,22 @@ pub fn is_dev_folder(path: &Path) -> bool {
     false
 }
 
-/// Get files to organize in a directory (non-recursive, top-level files only)
-pub fn get_files_to_organize(path: &Path) -> Result<Vec<FileToOrganize>> {
+/// Get files to organize in a directory (non-recursive, top-level files only),
+/// each with the folder `by` files it into
+///
+/// Categories go by extension, with the content as a fallback, see
+/// `FileCategory::detect`.
+pub fn get_files_to_organize(
+    path: &Path,
+    by: &OrganizeBy,
+    prefer_content: bool,
+) -> Result<Vec<FileToOrganize>> {
     let mut files = Vec::new();
 
-    let entries = fs::read_dir(path)
-        .with_context(|| format!("Failed to read directory: {}", path.display()))?;
+    let entries = fs::read_dir(path).map_err(|source| OrganizeError::ReadDir {
+        path: path.to_path_buf(),
+        source,
+    })?;
 
     for entry in entries.flatten() {
         let file_path = entry.path();
@@ -221,6 +848,13 @@ pub fn get_files_to_organize(path: &Path) -> Result<Vec<FileToOrganize>> {
             continue;
         }
 
+        // Symlinks left by `--link` runs point into the organized folders
+        if let Ok(target) = fs::read_link(&file_path)
+            && std::path::absolute(path).is_ok_and(|root| target.starts_with(root))
+        {
+            continue;
+        }
+
         let file_name = file_path
             .file_name()
             .and_then(|n| n.to_str())
@@ -232,73 +866,497 @@ pub fn get_files_to_organize(path: &Path) -> Result<Vec<FileToOrganize>> {
             continue;
         }
 
-        // Get extension and determine category
-        let extension = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-        let category = FileCategory::from_extension(extension);
+        let (category, by_content) = FileCategory::detect(&file_path, prefer_content);
+        let metadata = entry.metadata().ok();
+        let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
 
         files.push(FileToOrganize {
+            folder: by.folder(&file_path, &category, metadata.as_ref()),
             path: file_path,
+            target_name: file_name.clone(),
             file_name,
             category,
+            by_content,
+            size,
             selected: true, // Default to selected
+            sidecar_of: None,
         });
     }
 
-    // Sort by category then by name
-    files.sort_by(|a, b| {
-        a.category
-            .folder_name()
-            .cmp(b.category.folder_name())
-            .then(a.file_name.cmp(&b.file_name))
-    });
+    // Sort by folder then by name
+    files.sort_by(|a, b| a.folder.cmp(&b.folder).then(a.file_name.cmp(&b.file_name)));
 
     Ok(files)
 }
 
-/// Organize files in a directory
-pub fn organize_files(
-    search_path: Option<&str>,
-    dry_run: bool,
-    verbose: bool,
-    interactive: bool,
-) -> Result<Vec<OrganizeEntry>> {
-    let root = search_path
-        .map(PathBuf::from)
-        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+/// Whether `path` is a download a browser is still writing
+fn is_partial_download(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| {
+        PARTIAL_DOWNLOAD_EXTENSIONS
+            .iter()
+            .any(|partial| ext.eq_ignore_ascii_case(partial))
+    })
+}
 
-    println!(
-        "{} Checking directory: {}",
-        "🔍".cyan(),
-        root.display().to_string().yellow()
-    );
+/// Parse a `--min-age`: `30s`, `10m`, `2h` or `1d`, a bare number being
+/// minutes; `0` turns the guard off
+pub fn parse_min_age(input: &str) -> Result<Duration> {
+    let invalid = || OrganizeError::InvalidMinAge(input.to_string());
+    let input = input.trim();
+
+    let (amount, secs) = match input.chars().last() {
+        Some(unit) if unit.is_ascii_alphabetic() => {
+            let secs = match unit.to_ascii_lowercase() {
+                's' => 1,
+                'm' => 60,
+                'h' => 3600,
+                'd' => 86_400,
+                _ => return Err(invalid()),
+            };
+            (&input[..input.len() - 1], secs)
+        }
+        _ => (input, 60),
+    };
+    amount
+        .parse::<u64>()
+        .ok()
+        .and_then(|amount| amount.checked_mul(secs))
+        .map(Duration::from_secs)
+        .ok_or_else(invalid)
+}
+
+/// An age like `10m` or `2h`, in its largest whole unit
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        s if s % 86_400 == 0 => format!("{}d", s / 86_400),
+        s if s % 3600 == 0 => format!("{}h", s / 3600),
+        s if s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{}s", s),
+    }
+}
+
+/// Extensions of files that belong with another file, and the category that
+/// file must be in: subtitles go with videos, edit metadata with photos
+const SIDECAR_EXTENSIONS: &[(&str, FileCategory)] = &[
+    ("srt", FileCategory::Videos),
+    ("vtt", FileCategory::Videos),
+    ("ass", FileCategory::Videos),
+    ("ssa", FileCategory::Videos),
+    ("sub", FileCategory::Videos),
+    ("xmp", FileCategory::Images),
+    ("aae", FileCategory::Images),
+];
+
+/// Category of the file `path` would be a sidecar of, if it's a sidecar
+fn sidecar_category(path: &Path) -> Option<&'static FileCategory> {
+    let extension = path.extension()?.to_string_lossy().to_lowercase();
+    SIDECAR_EXTENSIONS
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, category)| category)
+}
+
+/// Pair each sidecar with the file in its folder it belongs to
+///
+/// `movie.srt`, `movie.en.srt` and `movie.en.forced.vtt` go with a video
+/// `movie.mp4`, `IMG_1234.xmp` and `IMG_1234.CR2.xmp` with a photo
+/// `IMG_1234.CR2`; names are compared case-insensitively and the longest
+/// matching name wins. Sidecars without one are organized on their own.
+pub fn attach_sidecars(files: &mut [FileToOrganize]) {
+    for i in 0..files.len() {
+        let Some(category) = sidecar_category(&files[i].path) else {
+            continue;
+        };
+        let sidecar = &files[i].path;
+        let stem = sidecar
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        let primary = files
+            .iter()
+            .filter(|f| {
+                f.category == *category
+                    && sidecar_category(&f.path).is_none()
+                    && f.path.parent() == sidecar.parent()
+            })
+            .filter_map(|f| {
+                let name = f.file_name.to_lowercase();
+                let own = f
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_lowercase())
+                    .unwrap_or_default();
+                (stem == name || stem == own || stem.starts_with(&format!("{}.", own)))
+                    .then_some((own.len(), &f.path))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, path)| path.clone());
+        files[i].sidecar_of = primary;
+    }
+}
+
+/// Give each sidecar its file's category, folder and selection, and the
+/// file's new name with the sidecar's own ending, e.g. `movie.en.srt` for a
+/// `movie.mkv` renamed `2024-05-03-movie.mkv` becomes `2024-05-03-movie.en.srt`
+pub fn follow_primaries(files: &mut [FileToOrganize]) {
+    let primaries: HashMap<PathBuf, usize> = files
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| f.sidecar_of.is_none())
+        .map(|(i, f)| (f.path.clone(), i))
+        .collect();
+
+    for i in 0..files.len() {
+        let Some(&p) = files[i]
+            .sidecar_of
+            .as_ref()
+            .and_then(|of| primaries.get(of))
+        else {
+            continue;
+        };
+        let primary = files[p].clone();
+        let sidecar = &mut files[i];
+
+        let target_name = if primary.target_name == primary.file_name {
+            sidecar.file_name.clone()
+        } else {
+            let name = &sidecar.file_name;
+            let primary_stem = primary
+                .path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            // `IMG_1234.CR2.xmp` carries the whole name, `movie.en.srt` the stem
+            if name.len() > primary.file_name.len()
+                && name[..primary.file_name.len()].eq_ignore_ascii_case(&primary.file_name)
+            {
+                format!(
+                    "{}{}",
+                    primary.target_name,
+                    &name[primary.file_name.len()..]
+                )
+            } else {
+                let target_stem = Path::new(&primary.target_name)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                format!(
+                    "{}{}",
+                    target_stem,
+                    name.get(primary_stem.len()..).unwrap_or_default()
+                )
+            }
+        };
+
+        sidecar.category = primary.category;
+        sidecar.folder = primary.folder;
+        sidecar.selected = primary.selected;
+        sidecar.by_content = false;
+        sidecar.target_name = target_name;
+    }
+}
+
+/// Compile the exclusion `globs` plus those in `root`'s `.organizeignore`
+///
+/// Globs without a `/` match file names, e.g. `*.dmg` or `*.part`; path
+/// globs in the ignore file are relative to `root`. Lines starting with `#`
+/// are comments.
+pub(crate) fn load_exclusions(root: &Path, globs: &[String]) -> Result<Vec<Exclusion>> {
+    let compile = |glob: &str, anchor: Option<&Path>| {
+        Exclusion::new(glob, anchor).map_err(|_| OrganizeError::InvalidExclude(glob.to_string()))
+    };
+    let mut exclusions = globs
+        .iter()
+        .map(|glob| compile(glob, None))
+        .collect::<Result<Vec<_>>>()?;
+
+    let path = root.join(ORGANIZE_IGNORE_FILE);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(exclusions),
+        Err(source) => {
+            return Err(OrganizeError::File {
+                action: "read",
+                path,
+                source,
+            });
+        }
+    };
+
+    let anchor = std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf());
+    for line in content.lines().map(str::trim) {
+        if !line.is_empty() && !line.starts_with('#') {
+            exclusions.push(compile(line, Some(&anchor))?);
+        }
+    }
+    Ok(exclusions)
+}
+
+/// Build the pre-organize report of the `count` largest files
+///
+/// Files over `skip_over` bytes are marked as skipped so big moves never
+/// happen unnoticed.
+pub fn large_file_report(
+    files: &[FileToOrganize],
+    count: usize,
+    skip_over: Option<u64>,
+) -> Vec<LargeFileEntry> {
+    let mut largest: Vec<&FileToOrganize> = files.iter().collect();
+    largest.sort_by(|a, b| b.size.cmp(&a.size).then(a.file_name.cmp(&b.file_name)));
+
+    largest
+        .into_iter()
+        .take(count)
+        .map(|file| LargeFileEntry {
+            file_name: file.file_name.clone(),
+            size: format_size(file.size),
+            category: file.category_label(),
+            action: match skip_over {
+                Some(limit) if file.size > limit => "Skip (too large)".to_string(),
+                _ => format!("→ {}/", file.folder.display()),
+            },
+        })
+        .collect()
+}
+
+/// Organize the files in one or more directories
+///
+/// Files from every source go into category folders under `dest`, or under
+/// the one source when there's no `dest`; sources that are development
+/// folders are skipped. Prints the `report_count` largest files first; files
+/// larger than `skip_over` bytes are left in place. Each file is handed to
+/// the `method`'s action with its folder under the `by` scheme as the default
+/// destination; with `OrganizeMethod::SafeCopy`, files are copied instead and
+/// `commit_safe_copies` removes the originals later.
+pub fn organize_files(sources: &[PathBuf], options: OrganizeOptions) -> Result<Vec<OrganizeEntry>> {
+    let OrganizeOptions {
+        method,
+        by,
+        mut on_conflict,
+        prefer_content,
+        dry_run,
+        verbose,
+        interactive,
+        report_count,
+        skip_over,
+        link,
+        exclude,
+        rename,
+        plan,
+        dest,
+        destinations,
+        older_than,
+        min_age,
+        quarantine,
+    } = options;
+    let dry_run = dry_run || plan.is_some();
+    let current = || std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let sources = match sources {
+        [] => vec![current()],
+        sources => sources.to_vec(),
+    };
+    let root = match (&dest, sources.as_slice()) {
+        (Some(dest), _) => dest.clone(),
+        (None, [source]) => source.clone(),
+        (None, _) => return Err(OrganizeError::NoDestination),
+    };
 
-    // Check if this is a dev folder
-    if is_dev_folder(&root) {
+    let mut files = Vec::new();
+    for source in &sources {
         println!(
-            "{} {} is a development folder. Skipping organization.",
-            "⚠️".yellow(),
-            root.display().to_string().cyan()
+            "{} Checking directory: {}",
+            "🔍".cyan(),
+            source.display().to_string().yellow()
         );
+
+        // Check if this is a dev folder
+        if is_dev_folder(source) {
+            println!(
+                "{} {} is a development folder. Skipping organization.",
+                "⚠️".yellow(),
+                source.display().to_string().cyan()
+            );
+            println!(
+                "{}",
+                "Development markers found (node_modules, package.json, Cargo.toml, etc.)".dimmed()
+            );
+            continue;
+        }
+
+        if let Some(sync_root) = icloud_sync_root(source)
+            && !confirm_icloud_organize(source, sync_root, dry_run)?
+        {
+            continue;
+        }
+
         println!(
-            "{}",
-            "Development markers found (node_modules, package.json, Cargo.toml, etc.)".dimmed()
+            "{} Not a development folder. Scanning for files to organize...",
+            "✓".green()
         );
-        return Ok(Vec::new());
+
+        let mut found = get_files_to_organize(source, &by, prefer_content)?;
+
+        let exclusions = load_exclusions(source, &exclude)?;
+        if !exclusions.is_empty() {
+            let (ignored, rest): (Vec<_>, Vec<_>) = found
+                .into_iter()
+                .partition(|f| exclusions.iter().any(|e| e.matches(&f.path)));
+            found = rest;
+
+            if !ignored.is_empty() {
+                println!(
+                    "{} Ignoring {} files matching exclusions",
+                    "⏭️".yellow(),
+                    ignored.len().to_string().yellow()
+                );
+                if verbose {
+                    for file in &ignored {
+                        println!("  {} {}", "•".dimmed(), file.file_name.dimmed());
+                    }
+                }
+            }
+        }
+        files.extend(found);
     }
 
-    println!(
-        "{} Not a development folder. Scanning for files to organize...",
-        "✓".green()
-    );
+    let before = files.len();
+    files.retain(|f| !is_partial_download(&f.path));
+    if files.len() < before {
+        println!(
+            "{} Leaving {} downloads still in progress",
+            "⏳".yellow(),
+            (before - files.len()).to_string().yellow()
+        );
+    }
+
+    // `--older-than` leaves at least as much in place
+    if !min_age.is_zero() && older_than.is_none_or(|age| age < min_age) {
+        let cutoff = SystemTime::now()
+            .checked_sub(min_age)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let before = files.len();
+        files.retain(|f| {
+            fs::metadata(&f.path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified < cutoff)
+        });
+        if files.len() < before {
+            println!(
+                "{} Leaving {} files modified in the last {}, they may still be written (--min-age 0 organizes them)",
+                "⏳".yellow(),
+                (before - files.len()).to_string().yellow(),
+                format_age(min_age)
+            );
+        }
+    }
+
+    if let Some(age) = older_than {
+        let cutoff = SystemTime::now()
+            .checked_sub(age)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let before = files.len();
+        files.retain(|f| {
+            fs::metadata(&f.path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified < cutoff)
+        });
+        if files.len() < before {
+            println!(
+                "{} Leaving {} files modified in the last {} days",
+                "⏭️".yellow(),
+                (before - files.len()).to_string().yellow(),
+                age.as_secs() / 86_400
+            );
+        }
+    }
 
-    let files = get_files_to_organize(&root)?;
+    if sources.len() > 1 {
+        files.sort_by(|a, b| a.folder.cmp(&b.folder).then(a.file_name.cmp(&b.file_name)));
+    }
+    if let Some(dest) = &dest {
+        println!(
+            "{} Organizing into: {}",
+            "📁".cyan(),
+            dest.display().to_string().yellow()
+        );
+    }
+    warn_incomplete_runs(ORGANIZE_JOURNAL_KIND);
 
     if files.is_empty() {
         println!("{}", "No files found to organize.".yellow());
         return Ok(Vec::new());
     }
 
+    destinations.apply(&mut files, &by);
+    rename.apply(&mut files);
+    attach_sidecars(&mut files);
+    if let Some(stale) = quarantine {
+        let quarantined = quarantine_files(&mut files, stale);
+        if quarantined > 0 {
+            println!(
+                "{} Sending {} uncategorized or stale files to {}/ for review",
+                "🗃️".yellow(),
+                quarantined.to_string().yellow(),
+                REVIEW_FOLDER
+            );
+        }
+    }
+    follow_primaries(&mut files);
+
+    if report_count > 0 {
+        println!("\n{} Largest files:", "📏".cyan());
+        display_large_files_table(large_file_report(&files, report_count, skip_over))?;
+    }
+
+    // Leave oversized files where they are
+    let mut skipped = Vec::new();
+    if let Some(limit) = skip_over {
+        // Sidecars stay with their file
+        let oversized: HashSet<PathBuf> = files
+            .iter()
+            .filter(|f| f.size > limit)
+            .map(|f| f.path.clone())
+            .collect();
+        let (too_large, rest): (Vec<_>, Vec<_>) = files.into_iter().partition(|f| {
+            oversized.contains(&f.path)
+                || f.sidecar_of
+                    .as_ref()
+                    .is_some_and(|of| oversized.contains(of))
+        });
+        files = rest;
+
+        if !too_large.is_empty() {
+            println!(
+                "{} Skipping {} files over {}",
+                "⏭️".yellow(),
+                too_large.len().to_string().yellow(),
+                format_size(limit)
+            );
+        }
+
+        skipped = too_large
+            .into_iter()
+            .map(|file| OrganizeEntry {
+                size: format_size(file.size),
+                bytes: file.size,
+                category: file.category_label(),
+                file_name: file.file_name,
+                destination: String::new(),
+                status: match file.sidecar_of {
+                    Some(_) if file.size <= limit => "Skipped (with its file)".to_string(),
+                    _ => format!("Skipped (over {})", format_size(limit)),
+                },
+            })
+            .collect();
+
+        if files.is_empty() {
+            println!("{}", "No files left to organize.".yellow());
+            return Ok(skipped);
+        }
+    }
+
     // Count files by category
     let mut category_counts: HashMap<&FileCategory, usize> = HashMap::new();
     for file in &files {
@@ -316,99 +1374,100 @@ pub fn organize_files(
     }
     println!();
 
-    if interactive {
-        return interactive_organize(&root, files, dry_run);
-    }
+    let mut placement = Placement::begin(&root, method, link, dry_run, plan)?;
 
-    if dry_run {
-        println!("{} Dry run mode - no files will be moved\n", "🔍".cyan());
+    if interactive {
+        return interactive_organize(
+            &root,
+            files,
+            placement,
+            on_conflict,
+            &by,
+            &rename,
+            &destinations,
+        );
     }
 
-    let mut results = Vec::new();
-
+    let mut results = skipped;
     for file in files {
-        let category_folder = root.join(file.category.folder_name());
-        let destination = category_folder.join(&file.file_name);
-
-        let status = if dry_run {
-            "Would move".to_string()
-        } else {
-            // Create category folder if it doesn't exist
-            if !category_folder.exists() {
-                fs::create_dir(&category_folder).with_context(|| {
-                    format!("Failed to create directory: {}", category_folder.display())
-                })?;
-                if verbose {
-                    println!(
-                        "{} Created folder: {}",
-                        "📁".green(),
-                        category_folder.display()
-                    );
-                }
-            }
-
-            // Move the file
-            match fs::rename(&file.path, &destination) {
-                Ok(_) => {
-                    if verbose {
-                        println!(
-                            "{} Moved: {} → {}",
-                            "✓".green(),
-                            file.file_name,
-                            destination.display()
-                        );
-                    }
-                    "✓ Moved".to_string()
-                }
-                Err(e) => {
-                    // Try copy + delete if rename fails (cross-device move)
-                    match fs::copy(&file.path, &destination) {
-                        Ok(_) => {
-                            fs::remove_file(&file.path).ok();
-                            if verbose {
-                                println!(
-                                    "{} Moved: {} → {}",
-                                    "✓".green(),
-                                    file.file_name,
-                                    destination.display()
-                                );
-                            }
-                            "✓ Moved".to_string()
-                        }
-                        Err(_) => format!("✗ Error: {}", e),
-                    }
-                }
-            }
-        };
+        let (destination, status) =
+            place_file(&mut placement, &root, &file, &mut on_conflict, verbose)?;
+        if verbose && status.starts_with('✓') {
+            println!(
+                "{} {}: {} → {}",
+                "✓".green(),
+                status.trim_start_matches("✓ "),
+                file.file_name,
+                destination.display()
+            );
+        }
 
         results.push(OrganizeEntry {
+            size: format_size(file.size),
+            bytes: file.size,
+            category: file.category_label(),
             file_name: file.file_name,
-            category: file.category.to_string(),
             destination: destination.display().to_string(),
             status,
         });
     }
 
-    if !dry_run {
-        let moved_count = results
-            .iter()
-            .filter(|r| r.status.contains("Moved"))
-            .count();
-        println!(
-            "\n{} Successfully organized {} files",
-            "✨".green(),
-            moved_count.to_string().bold()
-        );
-    }
+    placement.finish(&root, &results)?;
 
     Ok(results)
 }
 
+/// The interactive list's rows: a category header (no file) followed by its
+/// files whose name contains `filter` (case-insensitive), unless the category
+/// is `collapsed`
+///
+/// Categories without a matching file are left out.
+fn organize_rows(
+    files: &[FileToOrganize],
+    filter: &str,
+    collapsed: &HashSet<FileCategory>,
+) -> Vec<(FileCategory, Option<usize>)> {
+    let mut rows = Vec::new();
+    for category in FileCategory::ALL {
+        let members = group_members(files, category, filter);
+        if members.is_empty() {
+            continue;
+        }
+        rows.push((category.clone(), None));
+        if !collapsed.contains(category) {
+            rows.extend(members.into_iter().map(|m| (category.clone(), Some(m))));
+        }
+    }
+    rows
+}
+
+/// Files of `category` whose name contains `filter` (case-insensitive)
+fn group_members(files: &[FileToOrganize], category: &FileCategory, filter: &str) -> Vec<usize> {
+    let filter = filter.to_lowercase();
+    files
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| f.category == *category && f.file_name.to_lowercase().contains(&filter))
+        .map(|(i, _)| i)
+        .collect()
+}
+
 /// Interactive mode for organizing files
+///
+/// Files are listed under their categories; toggling a category's row
+/// selects or deselects all of its files, and `←`/`→` collapse and expand it.
+/// `/` filters the list by file name as you type, and `a` and `n` act on the
+/// files the filter leaves visible. Files can be given another category (`c`)
+/// before organizing, which moves them to that category's folder under `by`
+/// and renames them by `rename`'s rules again.
 fn interactive_organize(
     root: &Path,
     mut files: Vec<FileToOrganize>,
-    dry_run: bool,
+    mut placement: Placement,
+    mut on_conflict: OnConflict,
+    by: &OrganizeBy,
+    rename: &RenameRules,
+    destinations: &CategoryDestinations,
 ) -> Result<Vec<OrganizeEntry>> {
     if files.is_empty() {
         return Ok(Vec::new());
@@ -417,9 +1476,15 @@ fn interactive_organize(
     println!("\n{}", "Interactive Mode".bold().cyan());
     println!("{}", "─".repeat(60).dimmed());
     println!("  {}    Navigate up/down", "↑/↓".yellow());
-    println!("  {}  Toggle selection", "Space".yellow());
+    println!("  {}    Collapse/expand a category", "←/→".yellow());
+    println!(
+        "  {}  Toggle selection (on a category: all of its files)",
+        "Space".yellow()
+    );
     println!("  {}      Select all", "a".yellow());
     println!("  {}      Deselect all", "n".yellow());
+    println!("  {}      Filter by file name", "/".yellow());
+    println!("  {}      Change the file's category", "c".yellow());
     println!("  {}  Organize selected", "Enter".yellow());
     println!("  {}      Quit without organizing", "q".yellow());
     println!("{}", "─".repeat(60).dimmed());
@@ -429,12 +1494,19 @@ fn interactive_organize(
     let _ = event::read();
 
     let mut selected_idx = 0;
+    let mut filter = String::new();
+    let mut editing_filter = false;
+    let mut collapsed: HashSet<FileCategory> = HashSet::new();
+    let mut rows = organize_rows(&files, &filter, &collapsed);
     let mut stdout = stdout();
 
     // Enter alternate screen
     execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
 
     loop {
+        // Sidecars are (de)selected with their files
+        follow_primaries(&mut files);
+
         // Clear screen and render
         execute!(
             stdout,
@@ -453,42 +1525,97 @@ fn interactive_organize(
         let selected_count = files.iter().filter(|f| f.selected).count();
         writeln!(
             stdout,
-            "Selected: {}/{} | {}=Toggle {}=All {}=None {}=Organize {}=Quit",
+            "Selected: {}/{} | {}=Toggle {}=Fold {}=All {}=None {}=Filter {}=Category {}=Organize {}=Quit",
             selected_count.to_string().green(),
             files.len().to_string().cyan(),
             "Space".yellow(),
+            "←/→".yellow(),
             "a".yellow(),
             "n".yellow(),
+            "/".yellow(),
+            "c".yellow(),
             "Enter".yellow(),
             "q".yellow()
         )?;
+        let shown: usize = FileCategory::ALL
+            .iter()
+            .map(|category| group_members(&files, category, &filter).len())
+            .sum();
+        if editing_filter {
+            writeln!(
+                stdout,
+                "{} {}▏  ({} shown, Enter:Done Esc:Clear)",
+                "Filter:".yellow(),
+                filter.bold(),
+                shown
+            )?;
+        } else if !filter.is_empty() {
+            writeln!(stdout, "Filter: {} ({} shown)", filter.yellow(), shown)?;
+        }
         writeln!(stdout, "{}", "─".repeat(80).dimmed())?;
 
         // Calculate visible window
         let term_height = terminal::size()?.1 as usize;
-        let list_height = term_height.saturating_sub(8);
+        let list_height = term_height.saturating_sub(9);
         let start_idx = if selected_idx >= list_height {
             selected_idx - list_height + 1
         } else {
             0
         };
-        let end_idx = (start_idx + list_height).min(files.len());
+        let end_idx = (start_idx + list_height).min(rows.len());
 
-        // Render file list
-        for (idx, file) in files
+        // Render category headers and their files
+        for (idx, (category, file)) in rows
             .iter()
             .enumerate()
             .skip(start_idx)
             .take(end_idx - start_idx)
         {
             let is_current = idx == selected_idx;
+
+            let Some(file) = file else {
+                let members = group_members(&files, category, &filter);
+                let selected = members.iter().filter(|&&m| files[m].selected).count();
+                let checkbox = match selected {
+                    0 => "[ ]",
+                    n if n == members.len() => "[✓]",
+                    _ => "[~]",
+                };
+                let size: u64 = members.iter().map(|&m| files[m].size).sum();
+                let line = format!(
+                    " {} {:>10}  {} {} ({} files)",
+                    checkbox,
+                    format_size(size),
+                    if collapsed.contains(category) {
+                        "▸"
+                    } else {
+                        "▾"
+                    },
+                    category,
+                    members.len()
+                );
+                if is_current {
+                    writeln!(stdout, "{}", line.on_bright_blue().white())?;
+                } else {
+                    writeln!(stdout, "{}", line.bold())?;
+                }
+                continue;
+            };
+
+            let file = &files[*file];
             let checkbox = if file.selected { "[✓]" } else { "[ ]" };
 
+            let target = if file.target_name == file.file_name {
+                file.folder.clone()
+            } else {
+                file.folder.join(&file.target_name)
+            };
             let line = format!(
-                " {} {} → {}",
+                "     {} {:>10}  {} → {}",
                 checkbox,
+                format_size(file.size),
                 file.file_name,
-                file.category.folder_name()
+                target.display()
             );
 
             if is_current {
@@ -501,61 +1628,129 @@ fn interactive_organize(
         }
 
         // Show scroll indicator
-        if files.len() > list_height {
+        if rows.len() > list_height {
             writeln!(
                 stdout,
                 "\n{} {}/{}",
                 "Showing:".dimmed(),
                 (selected_idx + 1).to_string().cyan(),
-                files.len().to_string().cyan()
+                rows.len().to_string().cyan()
             )?;
         }
 
         stdout.flush()?;
 
         // Handle input
-        if let Event::Key(key) = event::read()? {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if editing_filter {
             match key.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if selected_idx > 0 {
-                        selected_idx -= 1;
-                    }
+                KeyCode::Enter => editing_filter = false,
+                KeyCode::Esc => {
+                    filter.clear();
+                    editing_filter = false;
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if selected_idx < files.len() - 1 {
-                        selected_idx += 1;
-                    }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
+                    terminal::disable_raw_mode()?;
+                    println!("{}", "Cancelled.".yellow());
+                    return Ok(Vec::new());
                 }
-                KeyCode::Char(' ') => {
-                    files[selected_idx].selected = !files[selected_idx].selected;
+                KeyCode::Backspace => {
+                    filter.pop();
                 }
-                KeyCode::Char('a') => {
-                    for file in &mut files {
-                        file.selected = true;
+                KeyCode::Char(c) => filter.push(c),
+                _ => continue,
+            }
+            rows = organize_rows(&files, &filter, &collapsed);
+            selected_idx = 0;
+            continue;
+        }
+
+        let visible: Vec<usize> = FileCategory::ALL
+            .iter()
+            .flat_map(|category| group_members(&files, category, &filter))
+            .collect();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                selected_idx = selected_idx.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if selected_idx + 1 < rows.len() => {
+                selected_idx += 1;
+            }
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Right | KeyCode::Char('l') => {
+                if let Some((category, _)) = rows.get(selected_idx).cloned() {
+                    let collapse = matches!(key.code, KeyCode::Left | KeyCode::Char('h'));
+                    if collapse {
+                        collapsed.insert(category.clone());
+                    } else {
+                        collapsed.remove(&category);
                     }
+                    rows = organize_rows(&files, &filter, &collapsed);
+                    // Stay on the category's header
+                    selected_idx = rows
+                        .iter()
+                        .position(|(c, f)| *c == category && f.is_none())
+                        .unwrap_or(0);
                 }
-                KeyCode::Char('n') => {
-                    for file in &mut files {
-                        file.selected = false;
+            }
+            KeyCode::Char(' ') => match rows.get(selected_idx) {
+                Some((_, Some(f))) => {
+                    files[*f].selected = !files[*f].selected;
+                }
+                Some((category, None)) => {
+                    let members = group_members(&files, category, &filter);
+                    let select = !members.iter().all(|&m| files[m].selected);
+                    for m in members {
+                        files[m].selected = select;
                     }
                 }
-                KeyCode::Enter => {
-                    break;
+                None => {}
+            },
+            KeyCode::Char('a') => {
+                for m in visible {
+                    files[m].selected = true;
                 }
-                KeyCode::Char('q') | KeyCode::Esc => {
-                    execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
-                    terminal::disable_raw_mode()?;
-                    println!("{}", "Cancelled.".yellow());
-                    return Ok(Vec::new());
+            }
+            KeyCode::Char('n') => {
+                for m in visible {
+                    files[m].selected = false;
                 }
-                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
-                    terminal::disable_raw_mode()?;
-                    println!("{}", "Cancelled.".yellow());
-                    return Ok(Vec::new());
+            }
+            KeyCode::Char('/') => editing_filter = true,
+            KeyCode::Char('c') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some(&(_, Some(f))) = rows.get(selected_idx)
+                    && let Some(category) = pick_category(&mut stdout, &files[f])?
+                {
+                    files[f].set_category(category, by);
+                    destinations.apply(std::slice::from_mut(&mut files[f]), by);
+                    rename.apply(&mut files);
+                    follow_primaries(&mut files);
+                    rows = organize_rows(&files, &filter, &collapsed);
+                    // Follow the file to its new category
+                    selected_idx = rows
+                        .iter()
+                        .position(|&(_, file)| file == Some(f))
+                        .unwrap_or(0);
                 }
-                _ => {}
             }
+            KeyCode::Enter => {
+                break;
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
+                terminal::disable_raw_mode()?;
+                println!("{}", "Cancelled.".yellow());
+                return Ok(Vec::new());
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
+                terminal::disable_raw_mode()?;
+                println!("{}", "Cancelled.".yellow());
+                return Ok(Vec::new());
+            }
+            _ => {}
         }
     }
 
@@ -577,79 +1772,1080 @@ fn interactive_organize(
         selected_files.len().to_string().green()
     );
 
-    if dry_run {
-        println!("{} Dry run mode - no files will be moved\n", "🔍".cyan());
-    }
-
     let mut results = Vec::new();
-
     for file in selected_files {
-        let category_folder = root.join(file.category.folder_name());
-        let destination = category_folder.join(&file.file_name);
-
-        let status = if dry_run {
-            "Would move".to_string()
-        } else {
-            // Create category folder if it doesn't exist
-            if !category_folder.exists() {
-                fs::create_dir(&category_folder).with_context(|| {
-                    format!("Failed to create directory: {}", category_folder.display())
-                })?;
-            }
-
-            // Move the file
-            match fs::rename(&file.path, &destination) {
-                Ok(_) => "✓ Moved".to_string(),
-                Err(e) => {
-                    // Try copy + delete if rename fails (cross-device move)
-                    match fs::copy(&file.path, &destination) {
-                        Ok(_) => {
-                            fs::remove_file(&file.path).ok();
-                            "✓ Moved".to_string()
-                        }
-                        Err(_) => format!("✗ Error: {}", e),
-                    }
-                }
-            }
-        };
+        let (destination, status) =
+            place_file(&mut placement, root, &file, &mut on_conflict, false)?;
 
         println!(
-            "  {} {} → {}/{}",
-            if status.contains("Moved") || status.contains("Would") {
+            "  {} {} → {}",
+            if status.starts_with('✓') || status.contains("Would") {
                 "✓".green()
             } else {
                 "✗".red()
             },
             file.file_name,
-            file.category.folder_name(),
-            file.file_name.dimmed()
+            destination
+                .strip_prefix(root)
+                .unwrap_or(&destination)
+                .display()
+                .to_string()
+                .dimmed()
         );
 
         results.push(OrganizeEntry {
+            size: format_size(file.size),
+            bytes: file.size,
+            category: file.category_label(),
             file_name: file.file_name,
-            category: file.category.to_string(),
             destination: destination.display().to_string(),
             status,
         });
     }
 
-    if !dry_run {
-        let moved_count = results
-            .iter()
-            .filter(|r| r.status.contains("Moved"))
-            .count();
-        println!(
-            "\n{} Successfully organized {} files",
-            "✨".green(),
-            moved_count.to_string().bold()
+    placement.finish(root, &results)?;
+
+    Ok(results)
+}
+
+/// Let the user pick another category for `file` in the interactive list;
+/// `None` when they back out with Esc
+fn pick_category(stdout: &mut Stdout, file: &FileToOrganize) -> Result<Option<FileCategory>> {
+    let mut selected_idx = FileCategory::ALL
+        .iter()
+        .position(|c| *c == file.category)
+        .unwrap_or(0);
+
+    loop {
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(ClearType::All)
+        )?;
+        writeln!(
+            stdout,
+            "{} {}",
+            "🏷️  Category for".bold().cyan(),
+            file.file_name.bold()
+        )?;
+        writeln!(stdout, "{}", "─".repeat(80).dimmed())?;
+        writeln!(
+            stdout,
+            "{}=Choose {}=Back",
+            "Enter".yellow(),
+            "Esc".yellow()
+        )?;
+        writeln!(stdout, "{}", "─".repeat(80).dimmed())?;
+
+        for (idx, category) in FileCategory::ALL.iter().enumerate() {
+            let marker = if *category == file.category {
+                "●"
+            } else {
+                " "
+            };
+            let line = format!(" {} {}", marker, category);
+            if idx == selected_idx {
+                writeln!(stdout, "{}", line.on_bright_blue().white())?;
+            } else {
+                writeln!(stdout, "{}", line)?;
+            }
+        }
+        stdout.flush()?;
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    selected_idx = selected_idx.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j')
+                    if selected_idx < FileCategory::ALL.len() - 1 =>
+                {
+                    selected_idx += 1;
+                }
+                KeyCode::Enter => return Ok(Some(FileCategory::ALL[selected_idx].clone())),
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Put one file into its folder under `root`, settling a taken destination
+/// with `on_conflict`; returns where it went and its status
+///
+/// A prompt answered for all remaining files replaces `on_conflict`. An
+/// overwritten file goes to the Trash, journaled, and comes back if its
+/// replacement can't be placed; safe copies never overwrite.
+fn place_file(
+    placement: &mut Placement,
+    root: &Path,
+    file: &FileToOrganize,
+    on_conflict: &mut OnConflict,
+    verbose: bool,
+) -> Result<(PathBuf, String)> {
+    let category_folder = root.join(&file.folder);
+    let mut category_path = category_folder.join(&file.target_name);
+    let mut destination = placement.destination(&file.path, &category_path)?;
+
+    let mut resolution = None;
+    let mut replaced = None;
+    if placement.conflicts(&file.path, &destination) {
+        let choice = match *on_conflict {
+            OnConflict::Prompt if placement.is_dry_run() => {
+                return Ok((destination, "Would ask (destination exists)".to_string()));
+            }
+            OnConflict::Prompt => prompt_conflict(&file.target_name, &destination, on_conflict)?,
+            choice => choice,
+        };
+
+        match choice {
+            OnConflict::Rename => {
+                for n in 1.. {
+                    category_path = category_folder.join(numbered_name(&file.target_name, n));
+                    destination = placement.destination(&file.path, &category_path)?;
+                    if !placement.conflicts(&file.path, &destination) {
+                        break;
+                    }
+                }
+                resolution = Some("renamed");
+            }
+            OnConflict::Overwrite => match placement {
+                Placement::Copy(_) | Placement::DryCopy => {
+                    return Ok((
+                        destination,
+                        "Skipped (safe mode never overwrites)".to_string(),
+                    ));
+                }
+                Placement::Apply {
+                    runner,
+                    dry_run: false,
+                } => {
+                    match runner.trash_replaced(&destination) {
+                        Ok(trashed) => replaced = Some(trashed),
+                        Err(e) => return Ok((destination, recoverable_status(Err(e.into()))?)),
+                    }
+                    resolution = Some("overwrote, old file in Trash");
+                }
+                _ => resolution = Some("overwrote"),
+            },
+            _ => return Ok((destination, "Skipped (destination exists)".to_string())),
+        }
+    }
+
+    let mut status = placement.place(&file.path, &category_folder, &category_path, verbose)?;
+    if let (Some(trashed), Placement::Apply { runner, .. }) = (&replaced, &*placement)
+        && status.starts_with('✗')
+        && let Err(e) = runner.restore_replaced(trashed, &destination)
+    {
+        status = recoverable_status(Err(e.into()))?;
+    }
+    let status = match resolution {
+        Some(resolution) if !status.starts_with('✗') => format!("{} ({})", status, resolution),
+        _ => status,
+    };
+    Ok((destination, status))
+}
+
+/// `name` with ` (n)` before its extension, e.g. `report (1).pdf`
+pub(crate) fn numbered_name(name: &str, n: usize) -> String {
+    let path = Path::new(name);
+    match (path.file_stem(), path.extension()) {
+        (Some(stem), Some(extension)) => format!(
+            "{} ({}).{}",
+            stem.to_string_lossy(),
+            n,
+            extension.to_string_lossy()
+        ),
+        _ => format!("{} ({})", name, n),
+    }
+}
+
+/// Ask what to do with a file whose destination is taken; an upper-case
+/// answer also replaces `on_conflict` for the rest of the run
+fn prompt_conflict(
+    name: &str,
+    destination: &Path,
+    on_conflict: &mut OnConflict,
+) -> Result<OnConflict> {
+    println!(
+        "\n{} {} already exists at {}",
+        "⚠️".yellow(),
+        name.bold(),
+        destination.display().to_string().cyan()
+    );
+
+    loop {
+        print!(
+            "   {} [r]ename, [s]kip or [o]verwrite? (R/S/O for all remaining): ",
+            "❓".cyan()
         );
+        stdout().flush()?;
+
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        let answer = answer.trim();
+        let choice = match answer.to_lowercase().as_str() {
+            "r" | "rename" => OnConflict::Rename,
+            "s" | "skip" | "" => OnConflict::Skip,
+            "o" | "overwrite" => OnConflict::Overwrite,
+            _ => continue,
+        };
+        if answer.chars().next().is_some_and(char::is_uppercase) {
+            *on_conflict = choice;
+        }
+        return Ok(choice);
+    }
+}
+
+/// How files get into their category folders
+enum Placement {
+    /// Hand each file to the run's action (journaled unless it's a dry run)
+    Apply { runner: ActionRunner, dry_run: bool },
+    /// `--safe`: copy-only, never overwriting; each copy is hash-verified and
+    /// recorded so `commit_safe_copies` can remove the original later
+    Copy(Vec<SafeCopy>),
+    /// `--safe --dry-run`: report the copies without making them
+    DryCopy,
+    /// `--plan`: a dry run that records each move and writes them to `path`
+    Plan {
+        runner: ActionRunner,
+        root: PathBuf,
+        path: PathBuf,
+        moves: Vec<PlannedMove>,
+    },
+}
+
+impl Placement {
+    fn begin(
+        root: &Path,
+        method: OrganizeMethod,
+        link: Option<LinkMode>,
+        dry_run: bool,
+        plan: Option<PathBuf>,
+    ) -> Result<Self> {
+        let verb = match &method {
+            OrganizeMethod::Action(action) => action.past_tense().to_lowercase(),
+            OrganizeMethod::SafeCopy => "copied".to_string(),
+        };
+
+        if dry_run {
+            println!("{} Dry run mode - no files will be {}\n", "🔍".cyan(), verb);
+        }
+
+        match method {
+            OrganizeMethod::Action(action) => {
+                let runner = ActionRunner::begin(ORGANIZE_JOURNAL_KIND, action, root, dry_run)?;
+                let runner = match link {
+                    Some(mode) => runner.with_links(mode),
+                    None => runner,
+                };
+                Ok(match plan {
+                    Some(path) => Placement::Plan {
+                        runner,
+                        root: root.to_path_buf(),
+                        path,
+                        moves: Vec::new(),
+                    },
+                    None => Placement::Apply { runner, dry_run },
+                })
+            }
+            OrganizeMethod::SafeCopy if dry_run => Ok(Placement::DryCopy),
+            OrganizeMethod::SafeCopy => {
+                println!(
+                    "{} Safe mode - files are copied and verified, originals stay in place\n",
+                    "🛡️".cyan()
+                );
+                Ok(Placement::Copy(Vec::new()))
+            }
+        }
+    }
+
+    /// Where a file ends up, given its path in its category folder
+    fn destination(&self, source: &Path, category_path: &Path) -> Result<PathBuf> {
+        match self {
+            Placement::Apply { runner, .. } | Placement::Plan { runner, .. } => Ok(runner
+                .destination(source, Some(category_path))?
+                .unwrap_or_default()),
+            _ => Ok(category_path.to_path_buf()),
+        }
+    }
+
+    fn is_dry_run(&self) -> bool {
+        matches!(
+            self,
+            Placement::Apply { dry_run: true, .. } | Placement::DryCopy | Placement::Plan { .. }
+        )
+    }
+
+    /// Whether `destination` is taken; for safe copies, a file with the same
+    /// content counts as an earlier copy rather than a conflict
+    fn conflicts(&self, source: &Path, destination: &Path) -> bool {
+        if !destination.exists() && !destination.is_symlink() {
+            return false;
+        }
+        match self {
+            Placement::Copy(_) | Placement::DryCopy => {
+                !matches!((file_sha256(source), file_sha256(destination)), (Ok(a), Ok(b)) if a == b)
+            }
+            Placement::Apply { .. } | Placement::Plan { .. } => true,
+        }
+    }
+
+    /// Put one file into its category folder (`category_path` inside
+    /// `category_folder`), returning its status
+    fn place(
+        &mut self,
+        source: &Path,
+        category_folder: &Path,
+        category_path: &Path,
+        verbose: bool,
+    ) -> Result<String> {
+        match self {
+            Placement::Apply {
+                runner,
+                dry_run: true,
+            } => Ok(runner.apply(source, Some(category_path))?),
+            Placement::Apply { runner, .. } => {
+                // Other actions create the directories they need themselves
+                let ready = if runner.action().needs_default_destination() {
+                    create_category_folder(category_folder, verbose)
+                } else {
+                    Ok(())
+                };
+                let status = recoverable_status(ready.and_then(|_| {
+                    runner
+                        .apply(source, Some(category_path))
+                        .map_err(OrganizeError::from)
+                }))?;
+
+                let moved = matches!(
+                    runner.action(),
+                    Action::Move { .. } | Action::Archive { .. }
+                ) && runner.links() != Some(LinkMode::Destination);
+                if moved
+                    && status.starts_with('✓')
+                    && let Some(destination) = runner.destination(source, Some(category_path))?
+                {
+                    record_origin(&destination, source);
+                }
+                Ok(status)
+            }
+            Placement::Plan {
+                runner,
+                root,
+                moves,
+                ..
+            } => {
+                moves.push(PlannedMove {
+                    source: source.to_path_buf(),
+                    target: category_path.to_path_buf(),
+                    destination: runner
+                        .destination(source, Some(category_path))?
+                        .unwrap_or_default(),
+                    folder: category_folder
+                        .strip_prefix(root)
+                        .unwrap_or(category_folder)
+                        .to_path_buf(),
+                    size: fs::metadata(source).map(|m| m.len()).unwrap_or(0),
+                });
+                Ok(runner.apply(source, Some(category_path))?)
+            }
+            Placement::DryCopy => Ok("Would copy".to_string()),
+            Placement::Copy(copies) => {
+                let status = recoverable_status(
+                    create_category_folder(category_folder, verbose)
+                        .and_then(|_| verified_copy(source, category_path, copies)),
+                )?;
+                if status.starts_with('✓') {
+                    record_origin(category_path, source);
+                }
+                Ok(status)
+            }
+        }
+    }
+
+    /// Close the run: commit the journal or record the copies, then summarize
+    /// and add the run to the history
+    fn finish(self, root: &Path, results: &[OrganizeEntry]) -> Result<()> {
+        let done = results.iter().filter(|r| r.status.starts_with('✓')).count();
+
+        match self {
+            Placement::Apply { dry_run: true, .. } | Placement::DryCopy => {}
+            Placement::Plan {
+                runner,
+                root,
+                path,
+                moves,
+            } => {
+                let plan = OrganizePlan {
+                    root,
+                    action: runner.action().clone(),
+                    link: runner.links(),
+                    moves,
+                };
+                plan.save(&path)?;
+                println!(
+                    "\n{} Wrote a plan of {} moves to {}",
+                    "📝".green(),
+                    plan.moves.len().to_string().bold(),
+                    path.display().to_string().cyan()
+                );
+                println!(
+                    "   {} Review it, then run --subcommand apply --plan {}",
+                    "💡".yellow(),
+                    path.display()
+                );
+            }
+            Placement::Apply { runner, .. } => {
+                let action = runner.action().name();
+                runner.commit()?;
+                record_run(&OrganizeRun::from_results(root, action, results));
+                println!(
+                    "\n{} Successfully organized {} files",
+                    "✨".green(),
+                    done.to_string().bold()
+                );
+            }
+            Placement::Copy(copies) => {
+                record_safe_copies(copies)?;
+                record_run(&OrganizeRun::from_results(root, "copy", results));
+                println!(
+                    "\n{} Copied and verified {} files; originals are untouched",
+                    "✨".green(),
+                    done.to_string().bold()
+                );
+                println!(
+                    "   {} Run --subcommand commit to remove the originals once you've checked the copies",
+                    "💡".yellow()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Add a run to the history; the files are organized either way, so a
+/// failure is only a warning
+fn record_run(run: &OrganizeRun) {
+    if let Err(e) = record_organize_run(run) {
+        eprintln!("{} Couldn't record the run for stats: {}", "⚠️".yellow(), e);
+    }
+}
+
+/// A file's move in an organize plan
+#[derive(Debug, Clone)]
+pub struct PlannedMove {
+    pub source: PathBuf,
+    /// Its path in its folder, the action's default destination
+    pub target: PathBuf,
+    /// Where the action puts it
+    pub destination: PathBuf,
+    /// Folder under the root it's organized into, e.g. `Images`
+    pub folder: PathBuf,
+    pub size: u64,
+}
+
+/// What an organize run would do, written by `--plan` so it can be reviewed
+/// (or edited) and run later with `apply_plan`
+///
+/// A JSON file with the root, the action spec, the link mode and the moves:
+///
+/// ```json
+/// {
+///   "root": "/Users/me/Downloads",
+///   "action": "move",
+///   "link": null,
+///   "moves": [
+///     {
+///       "source": "/Users/me/Downloads/report.pdf",
+///       "target": "/Users/me/Downloads/Documents/report.pdf",
+///       "destination": "/Users/me/Downloads/Documents/report.pdf",
+///       "folder": "Documents",
+///       "size": 52311
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrganizePlan {
+    pub root: PathBuf,
+    pub action: Action,
+    pub link: Option<LinkMode>,
+    pub moves: Vec<PlannedMove>,
+}
+
+impl OrganizePlan {
+    /// Read a plan written by `--plan`
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|source| OrganizeError::Plan {
+            action: "read",
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let invalid = |reason: String| OrganizeError::InvalidPlan {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+        let field = |key: &str| json.get(key).and_then(|v| v.as_str());
+
+        let root = field("root").ok_or_else(|| invalid("no 'root'".to_string()))?;
+        let action = field("action").ok_or_else(|| invalid("no 'action'".to_string()))?;
+        let moves = json
+            .get("moves")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| invalid("no 'moves'".to_string()))?
+            .iter()
+            .map(|entry| {
+                let path = |key: &str| {
+                    entry
+                        .get(key)
+                        .and_then(|v| v.as_str())
+                        .map(PathBuf::from)
+                        .ok_or_else(|| invalid(format!("a move has no '{}'", key)))
+                };
+                Ok(PlannedMove {
+                    source: path("source")?,
+                    target: path("target")?,
+                    destination: path("destination")?,
+                    folder: path("folder").unwrap_or_default(),
+                    size: entry.get("size").and_then(|v| v.as_u64()).unwrap_or(0),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            root: PathBuf::from(root),
+            action: Action::parse(action)?,
+            link: field("link").map(LinkMode::parse).transpose()?,
+            moves,
+        })
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        let moves: Vec<serde_json::Value> = self
+            .moves
+            .iter()
+            .map(|planned| {
+                json!({
+                    "source": planned.source.to_string_lossy(),
+                    "target": planned.target.to_string_lossy(),
+                    "destination": planned.destination.to_string_lossy(),
+                    "folder": planned.folder.to_string_lossy(),
+                    "size": planned.size,
+                })
+            })
+            .collect();
+        let plan = json!({
+            "root": std::path::absolute(&self.root)
+                .unwrap_or_else(|_| self.root.clone())
+                .to_string_lossy(),
+            "action": self.action.to_string(),
+            "link": self.link.map(LinkMode::name),
+            "moves": moves,
+        });
+
+        fs::write(path, serde_json::to_string_pretty(&plan)?).map_err(|source| {
+            OrganizeError::Plan {
+                action: "write",
+                path: path.to_path_buf(),
+                source,
+            }
+        })
+    }
+}
+
+/// Run a plan written by `--plan`
+///
+/// Each move goes through the plan's action and is journaled like any
+/// organize run. Moves whose file is gone or whose destination has been taken
+/// since the plan was written are skipped and reported.
+pub fn apply_plan(plan: OrganizePlan, dry_run: bool, verbose: bool) -> Result<Vec<OrganizeEntry>> {
+    warn_incomplete_runs(ORGANIZE_JOURNAL_KIND);
+
+    let mut placement = Placement::begin(
+        &plan.root,
+        OrganizeMethod::Action(plan.action),
+        plan.link,
+        dry_run,
+        None,
+    )?;
+
+    let mut results = Vec::new();
+    for planned in plan.moves {
+        let status = if !planned.source.exists() {
+            "✗ Missing, moved or removed since the plan".to_string()
+        } else if placement.conflicts(&planned.source, &planned.destination) {
+            "Skipped (destination exists)".to_string()
+        } else {
+            let folder = planned.target.parent().unwrap_or(&plan.root);
+            placement.place(&planned.source, folder, &planned.target, verbose)?
+        };
+
+        results.push(OrganizeEntry {
+            file_name: planned
+                .source
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            size: format_size(planned.size),
+            bytes: planned.size,
+            category: planned.folder.display().to_string(),
+            destination: planned.destination.display().to_string(),
+            status,
+        });
+    }
+
+    placement.finish(&plan.root, &results)?;
+    Ok(results)
+}
+
+/// Extended attribute holding the path a file had before it was first organized
+pub const ORIGIN_XATTR: &str = "user.shell-explorer.origin";
+
+/// Record `source` on the file now at `destination`, unless an earlier run
+/// already did: the first path is the original one
+///
+/// Filesystems without extended attributes just don't get it; the journals
+/// still have every move, see `find_file_origin`.
+fn record_origin(destination: &Path, source: &Path) {
+    if matches!(xattr::get(destination, ORIGIN_XATTR), Ok(Some(_))) {
+        return;
+    }
+    if let Ok(source) = std::path::absolute(source) {
+        xattr::set(
+            destination,
+            ORIGIN_XATTR,
+            source.as_os_str().as_encoded_bytes(),
+        )
+        .ok();
+    }
+}
+
+/// Where an organized file came from, see `find_file_origin`
+#[derive(Debug, Clone)]
+pub struct FileOrigin {
+    /// The file's absolute path
+    pub path: PathBuf,
+    /// Original path recorded on the file by the run that first moved it
+    pub recorded: Option<PathBuf>,
+    /// Journaled moves that brought it to `path`, oldest first
+    pub moves: Vec<JournalMove>,
+    /// When the run of the first of `moves` started; undoing every run since
+    /// puts the file back at its original path
+    pub first_run: Option<SystemTime>,
+}
+
+impl FileOrigin {
+    /// The earliest known path: where the first journaled move started, else
+    /// what's recorded on the file
+    pub fn original(&self) -> Option<&Path> {
+        self.moves
+            .first()
+            .map(|m| m.source.as_path())
+            .or(self.recorded.as_deref())
+    }
+}
+
+/// Row of the `organize where` table: one move of the file
+#[derive(Tabled, Clone)]
+pub struct FileMoveEntry {
+    #[tabled(rename = "When")]
+    pub when: String,
+    #[tabled(rename = "Action")]
+    pub action: String,
+    #[tabled(rename = "From")]
+    pub from: String,
+    #[tabled(rename = "To")]
+    pub to: String,
+}
+
+/// Find where a file was before organizing moved it
+///
+/// Follows the organize journals back from the file's path, newest move
+/// first, so a file reorganized by several runs is traced to where it
+/// started. Undone runs don't count. The original path is also read from the
+/// file itself (`ORIGIN_XATTR`), which survives journals being cleared and
+/// moves made outside this tool on the same filesystem.
+pub fn find_file_origin(path: &Path) -> Result<FileOrigin> {
+    let path = std::path::absolute(path).map_err(|source| OrganizeError::File {
+        action: "resolve",
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let recorded = match xattr::get(&path, ORIGIN_XATTR) {
+        Ok(Some(value)) => Some(PathBuf::from(String::from_utf8_lossy(&value).into_owned())),
+        _ => None,
+    };
+
+    let same = |a: &Path, b: &Path| std::path::absolute(a).is_ok_and(|a| a == b);
+    let mut moves = Vec::new();
+    let mut first_run = None;
+    let mut current = path.clone();
+    for (journal, item) in completed_items(ORGANIZE_JOURNAL_KIND)?.into_iter().rev() {
+        if item.action != "delete" && item.action != "symlink" && same(&item.destination, &current)
+        {
+            current = std::path::absolute(&item.source).unwrap_or_else(|_| item.source.clone());
+            first_run = journal_started(&journal);
+            moves.push(item);
+        }
+    }
+    moves.reverse();
+
+    Ok(FileOrigin {
+        path,
+        recorded,
+        moves,
+        first_run,
+    })
+}
+
+/// Rows of the `organize where` table, oldest move first
+pub fn file_move_entries(origin: &FileOrigin) -> Vec<FileMoveEntry> {
+    origin
+        .moves
+        .iter()
+        .map(|m| FileMoveEntry {
+            when: m.time.map(format_utc).unwrap_or_else(|| "-".to_string()),
+            action: m.action.clone(),
+            from: m.source.display().to_string(),
+            to: m.destination.display().to_string(),
+        })
+        .collect()
+}
+
+/// A `--safe` copy waiting for `organize commit`
+#[derive(Debug, Clone)]
+pub struct SafeCopy {
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    /// SHA-256 of the content both files had when the copy was verified
+    pub sha256: String,
+}
+
+/// Hex SHA-256 of a file's content
+pub(crate) fn file_sha256(path: &Path) -> Result<String> {
+    let mut file = File::open(path).map_err(|source| OrganizeError::File {
+        action: "read",
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher).map_err(|source| OrganizeError::File {
+        action: "hash",
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Copy a file without ever overwriting, then verify the copy by hash
+///
+/// An existing destination with the same content counts as already copied
+/// (so safe runs can be repeated); one with different content is left alone.
+fn verified_copy(source: &Path, destination: &Path, copies: &mut Vec<SafeCopy>) -> Result<String> {
+    let file_error = |action, path: &Path| {
+        let path = path.to_path_buf();
+        move |source| OrganizeError::File {
+            action,
+            path,
+            source,
+        }
+    };
+
+    let source_hash = file_sha256(source)?;
+
+    if destination.exists() {
+        if file_sha256(destination)? != source_hash {
+            return Ok("Skipped (destination exists)".to_string());
+        }
+        copies.push(SafeCopy {
+            source: source.to_path_buf(),
+            destination: destination.to_path_buf(),
+            sha256: source_hash,
+        });
+        return Ok("✓ Already copied".to_string());
+    }
+
+    let mut input = File::open(source).map_err(file_error("read", source))?;
+    // create_new refuses to clobber a file that appeared since the check above
+    let mut output = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(destination)
+        .map_err(file_error("create", destination))?;
+    let copied = io::copy(&mut input, &mut output)
+        .and_then(|_| output.sync_all())
+        .map_err(file_error("copy to", destination));
+    drop(output);
+    if let Ok(metadata) = fs::metadata(source) {
+        fs::set_permissions(destination, metadata.permissions()).ok();
+    }
+
+    // The destination is ours from here on, so a failed copy never leaves it behind
+    match copied.and_then(|_| file_sha256(destination)) {
+        Ok(hash) if hash == source_hash => {}
+        Ok(_) => {
+            fs::remove_file(destination).ok();
+            return Ok("✗ Verification failed (hash mismatch, copy removed)".to_string());
+        }
+        Err(e) => {
+            fs::remove_file(destination).ok();
+            return Err(e);
+        }
+    }
+
+    copies.push(SafeCopy {
+        source: source.to_path_buf(),
+        destination: destination.to_path_buf(),
+        sha256: source_hash,
+    });
+    Ok("✓ Copied".to_string())
+}
+
+fn safe_manifest_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join(SAFE_MANIFEST_FILE))
+}
+
+/// Copies made by `--safe` runs whose originals haven't been removed yet
+pub fn pending_safe_copies() -> Result<Vec<SafeCopy>> {
+    let path = safe_manifest_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|source| OrganizeError::Manifest {
+        action: "read",
+        path: path.clone(),
+        source,
+    })?;
+    let json: serde_json::Value = serde_json::from_str(&content)?;
+
+    Ok(json
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let field = |key: &str| entry.get(key).and_then(|v| v.as_str());
+                    Some(SafeCopy {
+                        source: PathBuf::from(field("source")?),
+                        destination: PathBuf::from(field("destination")?),
+                        sha256: field("sha256")?.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn save_safe_copies(copies: &[SafeCopy]) -> Result<()> {
+    let path = safe_manifest_path()?;
+    let manifest_error = |action| {
+        let path = path.clone();
+        move |source| OrganizeError::Manifest {
+            action,
+            path,
+            source,
+        }
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(manifest_error("create directory for"))?;
+    }
+
+    let entries: Vec<serde_json::Value> = copies
+        .iter()
+        .map(|copy| {
+            json!({
+                "source": copy.source.to_string_lossy(),
+                "destination": copy.destination.to_string_lossy(),
+                "sha256": copy.sha256,
+            })
+        })
+        .collect();
+    fs::write(&path, serde_json::to_string_pretty(&entries)?).map_err(manifest_error("write"))
+}
+
+/// Add new copies to the manifest; a repeated copy replaces its old entry
+fn record_safe_copies(copies: Vec<SafeCopy>) -> Result<()> {
+    if copies.is_empty() {
+        return Ok(());
+    }
+
+    let mut pending = pending_safe_copies()?;
+    pending.retain(|old| !copies.iter().any(|new| new.destination == old.destination));
+    pending.extend(copies);
+    save_safe_copies(&pending)
+}
+
+/// Remove the originals of `--safe` copies, each only after re-verifying it
+///
+/// The original is deleted only when both files still hash to the value
+/// recorded at copy time. Copies that went missing or changed are reported
+/// and dropped from the manifest with their originals kept; removals that
+/// fail stay pending for the next commit.
+pub fn commit_safe_copies(dry_run: bool) -> Result<Vec<RecoveryEntry>> {
+    let pending = pending_safe_copies()?;
+    let mut still_pending = Vec::new();
+    let mut results = Vec::new();
+
+    for copy in pending {
+        let hash_matches =
+            |path: &Path| path.exists() && file_sha256(path).is_ok_and(|h| h == copy.sha256);
+
+        let action = if !copy.source.exists() {
+            "Original already gone".to_string()
+        } else if !copy.destination.exists() {
+            "✗ Copy missing, original kept".to_string()
+        } else if !hash_matches(&copy.destination) {
+            "✗ Copy changed since verification, original kept".to_string()
+        } else if !hash_matches(&copy.source) {
+            "✗ Original changed since copy, original kept".to_string()
+        } else if dry_run {
+            still_pending.push(copy.clone());
+            "Would remove original".to_string()
+        } else {
+            match fs::remove_file(&copy.source) {
+                Ok(()) => "✓ Verified, original removed".to_string(),
+                Err(e) => {
+                    still_pending.push(copy.clone());
+                    format!("✗ Error: {}", e)
+                }
+            }
+        };
+
+        results.push(RecoveryEntry {
+            source: copy.source.display().to_string(),
+            destination: copy.destination.display().to_string(),
+            action,
+        });
+    }
+
+    if !dry_run {
+        save_safe_copies(&still_pending)?;
     }
 
     Ok(results)
 }
 
+/// Create a category folder (with its parents, for date folders) if it
+/// doesn't exist yet
+fn create_category_folder(folder: &Path, verbose: bool) -> Result<()> {
+    if folder.exists() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(folder).map_err(|source| OrganizeError::CreateDir {
+        path: folder.to_path_buf(),
+        source,
+    })?;
+    if verbose {
+        println!("{} Created folder: {}", "📁".green(), folder.display());
+    }
+
+    Ok(())
+}
+
+/// Turn a recoverable per-file failure into an error status; fatal errors abort the run
+pub(crate) fn recoverable_status(result: Result<String>) -> Result<String> {
+    match result {
+        Err(e) if e.severity() == Severity::Recoverable => Ok(format!("✗ Error: {}", e)),
+        result => result,
+    }
+}
+
+/// Get the iCloud-synced location a directory lives in, if any
+///
+/// Covers iCloud Drive itself and, when "Desktop & Documents Folders" sync is
+/// on (iCloud Drive then mirrors them), the Desktop and Documents folders.
+pub fn icloud_sync_root(path: &Path) -> Option<&'static str> {
+    let home = PathBuf::from(std::env::var("HOME").ok()?);
+    let home = home.canonicalize().unwrap_or(home);
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    let icloud_drive = home.join(ICLOUD_DRIVE_PATH);
+    if path.starts_with(&icloud_drive) {
+        return Some("iCloud Drive");
+    }
+
+    let cloud_docs = icloud_drive.join("com~apple~CloudDocs");
+    ICLOUD_SYNCED_FOLDERS
+        .iter()
+        .find(|folder| path.starts_with(home.join(folder)) && cloud_docs.join(folder).is_dir())
+        .copied()
+}
+
+/// Warn about organizing an iCloud-synced folder and ask for explicit confirmation
+///
+/// Every moved file is re-uploaded and can show up as a conflict on other
+/// devices. Set `allow_icloud = true` under `[organize]` to skip the prompt.
+fn confirm_icloud_organize(root: &Path, sync_root: &str, dry_run: bool) -> Result<bool> {
+    if Config::load()?
+        .get_bool("organize.allow_icloud")
+        .unwrap_or(false)
+    {
+        return Ok(true);
+    }
+
+    println!("\n{}", "═".repeat(60).red());
+    println!(
+        "{} {} is synced by iCloud ({})",
+        "⚠️".yellow(),
+        root.display().to_string().cyan(),
+        sync_root.yellow().bold()
+    );
+    println!("   Moving many files here makes iCloud re-upload every one of them and");
+    println!("   can leave duplicates or conflicts on your other devices.");
+    println!(
+        "   {} Set allow_icloud = true under [organize] in the config to skip this check",
+        "💡".yellow()
+    );
+    println!("{}", "═".repeat(60).red());
+
+    if dry_run {
+        println!("{} Dry run - nothing will be moved\n", "🔍".cyan());
+        return Ok(true);
+    }
+
+    print!(
+        "\n{} Type {} to organize this folder anyway: ",
+        "❓".cyan(),
+        "yes".bold()
+    );
+    stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    if answer.trim() != "yes" {
+        println!("{}", "Operation cancelled.".yellow());
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// Display the pre-organize large-file report
+pub fn display_large_files_table(entries: Vec<LargeFileEntry>) -> io::Result<()> {
+    use tabled::{
+        Table,
+        settings::{Alignment, Modify, Style, object::Columns},
+    };
+
+    let table = Table::new(&entries)
+        .with(Style::rounded())
+        .with(Modify::new(Columns::new(1..2)).with(Alignment::right()))
+        .to_string();
+    println!("{}", table);
+
+    Ok(())
+}
+
 /// Display table for organized files
-pub fn display_organize_table(entries: Vec<OrganizeEntry>, use_colors: bool) -> Result<()> {
+pub fn display_organize_table(entries: Vec<OrganizeEntry>, use_colors: bool) -> io::Result<()> {
     use tabled::{Table, settings::Style};
 
     if entries.is_empty() {
@@ -662,7 +2858,18 @@ pub fn display_organize_table(entries: Vec<OrganizeEntry>, use_colors: bool) ->
         // Add some color highlighting
         let colored_table = table
             .replace("✓ Moved", &"✓ Moved".green().to_string())
+            .replace("✓ Linked", &"✓ Linked".green().to_string())
+            .replace("✓ Archived", &"✓ Archived".green().to_string())
+            .replace("✓ Compressed", &"✓ Compressed".green().to_string())
+            .replace("✓ Deleted", &"✓ Deleted".green().to_string())
+            .replace("✓ Copied", &"✓ Copied".green().to_string())
+            .replace("✓ Already copied", &"✓ Already copied".green().to_string())
             .replace("Would move", &"Would move".yellow().to_string())
+            .replace("Would link", &"Would link".yellow().to_string())
+            .replace("Would archive", &"Would archive".yellow().to_string())
+            .replace("Would compress", &"Would compress".yellow().to_string())
+            .replace("Would delete", &"Would delete".yellow().to_string())
+            .replace("Would copy", &"Would copy".yellow().to_string())
             .replace("✗ Error", &"✗ Error".red().to_string());
         println!("{}", colored_table);
     } else {
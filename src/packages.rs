@@ -1,9 +1,84 @@
-use anyhow::{Context, Result};
 use rayon::prelude::*;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 use tabled::Tabled;
-use regex::Regex;
+use thiserror::Error;
+
+use crate::{ConfigError, Severity, TreeNode};
+
+type Result<T, E = PackagesError> = std::result::Result<T, E>;
+
+/// Errors from scanning manifests and lockfiles
+///
+/// A manifest or lockfile that can't be read or parsed is recoverable: it's
+/// skipped and the scan goes on, as is a failed registry lookup. A bad
+/// version or package pattern argument or an unreadable directory is fatal.
+#[derive(Debug, Error)]
+pub enum PackagesError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("Invalid version format: {0}")]
+    InvalidVersion(String),
+    #[error("Invalid version requirement '{req}' (e.g. '>=1.2, <2', '^1.4' or '~0.3')")]
+    InvalidVersionReq {
+        req: String,
+        #[source]
+        source: semver::Error,
+    },
+    #[error("Invalid package pattern '{pattern}'")]
+    InvalidPackagePattern {
+        pattern: String,
+        #[source]
+        source: regex::Error,
+    },
+    #[error("Failed to read directory: {}", path.display())]
+    ReadDir {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to read {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error("Failed to look up {package} on {registry}")]
+    Registry {
+        registry: &'static str,
+        package: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("Failed to {action} the registry cache: {}", path.display())]
+    RegistryCache {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl PackagesError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            PackagesError::Config(e) => e.severity(),
+            PackagesError::InvalidVersion(_)
+            | PackagesError::InvalidVersionReq { .. }
+            | PackagesError::InvalidPackagePattern { .. }
+            | PackagesError::ReadDir { .. } => Severity::Fatal,
+            _ => Severity::Recoverable,
+        }
+    }
+}
 
 #[derive(Tabled)]
 pub struct PackageEntry {
@@ -15,81 +90,285 @@ pub struct PackageEntry {
     pub file_path: String,
     #[tabled(rename = "Type")]
     pub package_type: String,
+    #[tabled(rename = "Section")]
+    pub section: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Version {
-    major: u32,
-    minor: u32,
-    patch: u32,
-    pre_release: String,
+impl PackageEntry {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.name,
+            "version": self.version,
+            "file": self.file_path,
+            "type": self.package_type,
+            "section": self.section,
+        })
+    }
+}
+
+/// Normalize a dependency section name so `devDependencies`, `dev-dependencies`
+/// and `dev_dependencies` all compare equal
+fn normalize_section(section: &str) -> String {
+    section.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Check whether `section` is one of the requested sections (all sections when empty)
+pub fn section_matches(section: &str, sections: &[String]) -> bool {
+    sections.is_empty()
+        || sections
+            .iter()
+            .any(|s| normalize_section(s) == normalize_section(section))
 }
 
+/// A package version, ordered by semver precedence
+///
+/// Manifests often hold requirements rather than versions (`^17.0.2`,
+/// `~4.17`, `>=1.2`), so parsing is lenient: the operator and a leading `v`
+/// are dropped and a missing minor or patch counts as 0.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version(semver::Version);
+
+/// Leading `major[.minor[.patch]][-pre][+build]` of a version string
+static VERSION_PREFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\d+)(?:\.(\d+))?(?:\.(\d+))?(-[0-9A-Za-z.-]+)?(\+[0-9A-Za-z.-]+)?").unwrap()
+});
+
 impl Version {
     pub fn parse(version_str: &str) -> Result<Self> {
-        let clean_version = version_str.trim_start_matches('v')
-            .trim_start_matches('^')
-            .trim_start_matches('~')
-            .trim_start_matches('=')
-            .trim_start_matches('>')
-            .trim_start_matches('<')
-            .trim_start_matches('*');
-
-        let re = Regex::new(r"^(\d+)(?:\.(\d+))?(?:\.(\d+))?(?:-(.+))?").unwrap();
-        
-        if let Some(captures) = re.captures(clean_version) {
-            let major = captures.get(1).unwrap().as_str().parse().unwrap_or(0);
-            let minor = captures.get(2).map_or(0, |m| m.as_str().parse().unwrap_or(0));
-            let patch = captures.get(3).map_or(0, |m| m.as_str().parse().unwrap_or(0));
-            let pre_release = captures.get(4).map_or(String::new(), |m| m.as_str().to_string());
-
-            Ok(Version {
-                major,
-                minor,
-                patch,
-                pre_release,
-            })
-        } else {
-            Err(anyhow::anyhow!("Invalid version format: {}", version_str))
+        let invalid = || PackagesError::InvalidVersion(version_str.to_string());
+        let clean_version = version_str
+            .trim()
+            .trim_start_matches(['^', '~', '=', '>', '<', ' '])
+            .trim_start_matches('v');
+
+        let captures = VERSION_PREFIX.captures(clean_version).ok_or_else(invalid)?;
+        let part = |i: usize| captures.get(i).map_or("0", |m| m.as_str());
+        let full = format!(
+            "{}.{}.{}{}{}",
+            part(1),
+            part(2),
+            part(3),
+            captures.get(4).map_or("", |m| m.as_str()),
+            captures.get(5).map_or("", |m| m.as_str())
+        );
+        semver::Version::parse(&full)
+            .map(Version)
+            .map_err(|_| invalid())
+    }
+
+    pub fn semver(&self) -> &semver::Version {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// How `--min-version` is compared, from `--cmp`: above it by default, or
+/// below it to find the projects still on an old version
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VersionCmp {
+    #[default]
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl VersionCmp {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "gt" | ">" => Some(VersionCmp::Gt),
+            "ge" | ">=" => Some(VersionCmp::Ge),
+            "lt" | "<" => Some(VersionCmp::Lt),
+            "le" | "<=" => Some(VersionCmp::Le),
+            "eq" | "=" | "==" => Some(VersionCmp::Eq),
+            "ne" | "!=" => Some(VersionCmp::Ne),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            VersionCmp::Gt => ">",
+            VersionCmp::Ge => ">=",
+            VersionCmp::Lt => "<",
+            VersionCmp::Le => "<=",
+            VersionCmp::Eq => "=",
+            VersionCmp::Ne => "!=",
         }
     }
 
-    pub fn is_greater_than(&self, other: &Version) -> bool {
-        if self.major != other.major {
-            return self.major > other.major;
+    /// Whether a version comparing `ordering` to the threshold passes
+    fn holds(self, ordering: std::cmp::Ordering) -> bool {
+        use std::cmp::Ordering::*;
+        match self {
+            VersionCmp::Gt => ordering == Greater,
+            VersionCmp::Ge => ordering != Less,
+            VersionCmp::Lt => ordering == Less,
+            VersionCmp::Le => ordering != Greater,
+            VersionCmp::Eq => ordering == Equal,
+            VersionCmp::Ne => ordering != Equal,
+        }
+    }
+}
+
+/// Which versions of a package a search shows
+#[derive(Debug, Clone)]
+pub enum VersionQuery {
+    /// `--min-version` with `--cmp`: any version above it (or below, equal,
+    /// ...), pre-releases included; manifest ranges compare by the lowest
+    /// version they allow
+    ///
+    /// The threshold is a single version, not a range: a partial one is padded
+    /// with zeros, so `--cmp lt --min-version 18` means below `18.0.0` and
+    /// `--cmp eq --min-version 18` only matches `18.0.0`, not `18.2.1`.
+    Compared(VersionCmp, Version),
+    /// `--version-req`: a Cargo-style requirement like `>=1.2, <2`, `^1.4` or
+    /// `~0.3`; pre-releases only match a comparator naming the same version
+    Matching(semver::VersionReq),
+}
+
+impl VersionQuery {
+    pub fn compared(cmp: VersionCmp, version: &str) -> Result<Self> {
+        Version::parse(version).map(|version| VersionQuery::Compared(cmp, version))
+    }
+
+    pub fn requirement(req: &str) -> Result<Self> {
+        semver::VersionReq::parse(req)
+            .map(VersionQuery::Matching)
+            .map_err(|source| PackagesError::InvalidVersionReq {
+                req: req.to_string(),
+                source,
+            })
+    }
+
+    /// Whether a manifest or lockfile version string satisfies the query;
+    /// unparseable versions never do
+    pub fn matches(&self, version: &str) -> bool {
+        let Ok(version) = Version::parse(version) else {
+            return false;
+        };
+        match self {
+            VersionQuery::Compared(cmp, threshold) => cmp.holds(version.cmp(threshold)),
+            VersionQuery::Matching(req) => req.matches(&version.0),
         }
-        if self.minor != other.minor {
-            return self.minor > other.minor;
+    }
+}
+
+impl std::fmt::Display for VersionQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionQuery::Compared(cmp, threshold) => write!(f, "{} {}", cmp.symbol(), threshold),
+            VersionQuery::Matching(req) => write!(f, "matching {}", req),
         }
-        if self.patch != other.patch {
-            return self.patch > other.patch;
+    }
+}
+
+/// Which packages a search looks at, from `--package`: names, globs like
+/// `react*` or `@babel/*` (`*` and `?` match any characters, `/` included)
+/// and `/regexes/`, any of them matching, ignoring case
+///
+/// Without any, a search looks at every package.
+#[derive(Debug, Clone, Default)]
+pub struct PackageFilter {
+    patterns: Vec<(String, Regex)>,
+}
+
+impl PackageFilter {
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                let regex = match pattern
+                    .strip_prefix('/')
+                    .and_then(|p| p.strip_suffix('/'))
+                    .filter(|p| !p.is_empty())
+                {
+                    Some(regex) => format!("(?i){}", regex),
+                    None => {
+                        let mut regex = "(?i)^".to_string();
+                        for c in pattern.chars() {
+                            match c {
+                                '*' => regex.push_str(".*"),
+                                '?' => regex.push('.'),
+                                c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+                            }
+                        }
+                        regex.push('$');
+                        regex
+                    }
+                };
+                Regex::new(&regex)
+                    .map(|regex| (pattern.clone(), regex))
+                    .map_err(|source| PackagesError::InvalidPackagePattern {
+                        pattern: pattern.clone(),
+                        source,
+                    })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Whether it's the every-package filter
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        self.is_empty() || self.patterns.iter().any(|(_, regex)| regex.is_match(name))
+    }
+
+    /// A single plain name, rather than a family of packages
+    fn is_single_name(&self) -> bool {
+        match self.patterns.as_slice() {
+            [(pattern, _)] => !pattern.contains(['*', '?']) && !pattern.starts_with('/'),
+            _ => false,
         }
-        
-        // Handle pre-release versions
-        match (&self.pre_release.is_empty(), &other.pre_release.is_empty()) {
-            (true, false) => true,   // 1.0.0 > 1.0.0-beta
-            (false, true) => false,  // 1.0.0-beta < 1.0.0
-            (true, true) => false,   // Equal versions
-            (false, false) => self.pre_release > other.pre_release, // Compare pre-release strings
+    }
+}
+
+impl std::fmt::Display for PackageFilter {
+    /// `package 'react'`, or `packages matching 'react*', '@babel/*'`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let patterns: Vec<String> = self
+            .patterns
+            .iter()
+            .map(|(pattern, _)| format!("'{}'", pattern))
+            .collect();
+        if self.is_single_name() {
+            write!(f, "package {}", patterns.join(", "))
+        } else {
+            write!(f, "packages matching {}", patterns.join(", "))
         }
     }
 }
 
-pub fn find_packages_with_version_greater_than(
-    package_name: &str,
-    min_version: &str,
+/// Find the manifest entries of the packages `filter` matches whose version
+/// satisfies `query`, by name and newest first; with an empty filter, every
+/// dependency of every manifest, by name and file, and without a query, every
+/// version
+pub fn find_packages_matching(
+    filter: &PackageFilter,
+    query: Option<&VersionQuery>,
     search_path: Option<&str>,
+    sections: &[String],
     verbose: bool,
 ) -> Result<Vec<PackageEntry>> {
-    let min_ver = Version::parse(min_version)
-        .with_context(|| format!("Invalid version format: {}", min_version))?;
-    
     let search_dir = search_path.unwrap_or(".");
     let mut packages = Vec::new();
-    
+
     // Find all package files
     let package_files = find_package_files(search_dir, verbose)?;
-    
+
     // Process files in parallel
     let matching_packages: Vec<PackageEntry> = package_files
         .par_iter()
@@ -101,21 +380,28 @@ pub fn find_packages_with_version_greater_than(
                         println!("✅ Parsed {} packages from {}", file_packages.len(), file_path.display());
                     }
                     let mut matches = Vec::new();
-                    for (name, version, pkg_type) in file_packages {
-                        if name.to_lowercase() == package_name.to_lowercase() {
-                            if let Ok(pkg_version) = Version::parse(&version) {
-                                if pkg_version.is_greater_than(&min_ver) {
-                                    if verbose {
-                                        println!("🎯 Found match: {} v{} in {} ({})", name, version, file_path.display(), pkg_type);
-                                    }
-                                    matches.push(PackageEntry {
-                                        name: name.clone(),
-                                        version: version.clone(),
-                                        file_path: file_path.to_string_lossy().to_string(),
-                                        package_type: pkg_type.clone(),
-                                    });
-                                }
+                    for (name, version, pkg_type, section) in file_packages {
+                        if filter.matches(&name)
+                            && section_matches(&section, sections)
+                            && query.is_none_or(|query| query.matches(&version))
+                        {
+                            if verbose {
+                                println!(
+                                    "🎯 Found match: {} v{} in {} ({}, {})",
+                                    name,
+                                    version,
+                                    file_path.display(),
+                                    pkg_type,
+                                    section
+                                );
                             }
+                            matches.push(PackageEntry {
+                                name: name.clone(),
+                                version: version.clone(),
+                                file_path: file_path.to_string_lossy().to_string(),
+                                package_type: pkg_type.clone(),
+                                section: section.clone(),
+                            });
                         }
                     }
                     if matches.is_empty() { None } else { Some(matches) }
@@ -130,69 +416,117 @@ pub fn find_packages_with_version_greater_than(
         })
         .flatten()
         .collect();
-    
+
     packages.extend(matching_packages);
-    
+
     if verbose {
-        println!("📊 Summary: Found {} package files, discovered {} matching packages", 
-                 package_files.len(), 
-                 packages.len());
+        println!(
+            "📊 Summary: Found {} package files, discovered {} matching packages",
+            package_files.len(),
+            packages.len()
+        );
     }
-    
-    // Sort by version (descending)
-    packages.sort_by(|a, b| {
-        let ver_a = Version::parse(&a.version).unwrap_or(Version { major: 0, minor: 0, patch: 0, pre_release: String::new() });
-        let ver_b = Version::parse(&b.version).unwrap_or(Version { major: 0, minor: 0, patch: 0, pre_release: String::new() });
-        ver_b.cmp(&ver_a)
-    });
-    
+
+    // A package's versions newest first, an inventory by name
+    if !filter.is_empty() {
+        packages.sort_by_cached_key(|p| {
+            (
+                p.name.to_lowercase(),
+                std::cmp::Reverse(Version::parse(&p.version).ok()),
+            )
+        });
+    } else {
+        packages.sort_by_cached_key(|p| {
+            (
+                p.name.to_lowercase(),
+                p.file_path.clone(),
+                p.section.clone(),
+            )
+        });
+    }
+
     Ok(packages)
 }
 
 fn find_package_files(search_dir: &str, verbose: bool) -> Result<Vec<PathBuf>> {
     let mut package_files = Vec::new();
     let search_path = PathBuf::from(search_dir);
-    
+
     if search_path.is_file() {
         if is_package_file(&search_path) {
             package_files.push(search_path);
         }
         return Ok(package_files);
     }
-    
+
     // Recursively search for package files
     if verbose {
         println!("📁 Scanning directory: {}", search_path.display());
     }
-    find_package_files_recursive(&search_path, &mut package_files, verbose)?;
-    
+    find_package_files_recursive(&search_path, &mut package_files, is_package_file, verbose)?;
+
     Ok(package_files)
 }
 
-fn find_package_files_recursive(dir: &PathBuf, package_files: &mut Vec<PathBuf>, verbose: bool) -> Result<()> {
+fn find_package_files_recursive(
+    dir: &PathBuf,
+    package_files: &mut Vec<PathBuf>,
+    is_wanted: fn(&Path) -> bool,
+    verbose: bool,
+) -> Result<()> {
     if !dir.is_dir() {
         return Ok(());
     }
-    
-    let entries = fs::read_dir(dir)
-        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
-    
+
+    let read_dir_error = |source| PackagesError::ReadDir {
+        path: dir.clone(),
+        source,
+    };
+    let entries = fs::read_dir(dir).map_err(read_dir_error)?;
+
     for entry in entries {
-        let entry = entry?;
+        let entry = entry.map_err(read_dir_error)?;
         let path = entry.path();
-        
+
         if path.is_dir() {
             // Skip common directories that are unlikely to contain package files we care about
             if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
-                if matches!(dir_name, 
-                    "node_modules" | "target" | ".git" | "build" | "dist" | 
-                    ".next" | ".nuxt" | ".cache" | "coverage" | ".nyc_output" |
-                    "__pycache__" | ".pytest_cache" | ".tox" | "venv" | ".venv" |
-                    "vendor" | ".bundle" | "tmp" | "temp" | ".tmp" |
-                    ".svn" | ".hg" | "CVS" | ".DS_Store" |
-                    "bin" | "obj" | "Debug" | "Release" | 
-                    ".idea" | ".vscode" | ".vs" | 
-                    "logs" | "log" | "*.log"
+                if matches!(
+                    dir_name,
+                    "node_modules"
+                        | "target"
+                        | ".git"
+                        | "build"
+                        | "dist"
+                        | ".next"
+                        | ".nuxt"
+                        | ".cache"
+                        | "coverage"
+                        | ".nyc_output"
+                        | "__pycache__"
+                        | ".pytest_cache"
+                        | ".tox"
+                        | "venv"
+                        | ".venv"
+                        | "vendor"
+                        | ".bundle"
+                        | "tmp"
+                        | "temp"
+                        | ".tmp"
+                        | ".svn"
+                        | ".hg"
+                        | "CVS"
+                        | ".DS_Store"
+                        | "bin"
+                        | "obj"
+                        | "Debug"
+                        | "Release"
+                        | ".idea"
+                        | ".vscode"
+                        | ".vs"
+                        | "logs"
+                        | "log"
+                        | "*.log"
                 ) {
                     if verbose {
                         println!("⏭️  Skipping directory: {}", path.display());
@@ -201,10 +535,10 @@ fn find_package_files_recursive(dir: &PathBuf, package_files: &mut Vec<PathBuf>,
                     if verbose {
                         println!("📂 Scanning subdirectory: {}", path.display());
                     }
-                    find_package_files_recursive(&path, package_files, verbose)?;
+                    find_package_files_recursive(&path, package_files, is_wanted, verbose)?;
                 }
             }
-        } else if is_package_file(&path) {
+        } else if is_wanted(&path) {
             if verbose {
                 println!("📄 Found package file: {}", path.display());
             }
@@ -215,7 +549,7 @@ fn find_package_files_recursive(dir: &PathBuf, package_files: &mut Vec<PathBuf>,
     Ok(())
 }
 
-fn is_package_file(path: &PathBuf) -> bool {
+fn is_package_file(path: &Path) -> bool {
     if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
         matches!(
             file_name,
@@ -229,7 +563,10 @@ fn is_package_file(path: &PathBuf) -> bool {
     }
 }
 
-fn parse_package_file(file_path: &PathBuf) -> Result<Vec<(String, String, String)>> {
+/// (name, version, package type, dependency section)
+type PackageDependency = (String, String, String, String);
+
+fn parse_package_file(file_path: &PathBuf) -> Result<Vec<PackageDependency>> {
     // Try to read as UTF-8, skip file if it's not valid UTF-8
     let content = match fs::read_to_string(file_path) {
         Ok(content) => content,
@@ -254,25 +591,25 @@ fn parse_package_file(file_path: &PathBuf) -> Result<Vec<(String, String, String
     }
 }
 
-fn parse_package_json(content: &str) -> Result<Vec<(String, String, String)>> {
+fn parse_package_json(content: &str) -> Result<Vec<PackageDependency>> {
     let mut packages = Vec::new();
     
     // Simple JSON parsing without serde to avoid dependency
-    let dependencies_sections = ["dependencies", "devDependencies", "peerDependencies"];
+    let dependencies_sections = ["dependencies", "devDependencies", "peerDependencies", "optionalDependencies"];
+    let re = Regex::new(r#""([^"]+)":\s*"([^"]+)""#).unwrap();
     
     for section in dependencies_sections {
-        if let Some(section_start) = content.find(&format!("\"{}\"", section)) {
-            if let Some(brace_start) = content[section_start..].find('{') {
-                let start_pos = section_start + brace_start + 1;
-                if let Some(brace_end) = find_matching_brace(&content[start_pos..]) {
-                    let deps_content = &content[start_pos..start_pos + brace_end];
-                    
-                    let re = Regex::new(r#""([^"]+)":\s*"([^"]+)""#).unwrap();
-                    for caps in re.captures_iter(deps_content) {
-                        let name = caps[1].to_string();
-                        let version = caps[2].to_string();
-                        packages.push((name, version, "npm".to_string()));
-                    }
+        if let Some(section_start) = content.find(&format!("\"{}\"", section))
+            && let Some(brace_start) = content[section_start..].find('{')
+        {
+            let start_pos = section_start + brace_start + 1;
+            if let Some(brace_end) = find_matching_brace(&content[start_pos..]) {
+                let deps_content = &content[start_pos..start_pos + brace_end];
+                
+                for caps in re.captures_iter(deps_content) {
+                    let name = caps[1].to_string();
+                    let version = caps[2].to_string();
+                    packages.push((name, version, "npm".to_string(), section.to_string()));
                 }
             }
         }
@@ -281,31 +618,31 @@ fn parse_package_json(content: &str) -> Result<Vec<(String, String, String)>> {
     Ok(packages)
 }
 
-fn parse_cargo_toml(content: &str) -> Result<Vec<(String, String, String)>> {
+fn parse_cargo_toml(content: &str) -> Result<Vec<PackageDependency>> {
     let mut packages = Vec::new();
     
     let sections = ["dependencies", "dev-dependencies", "build-dependencies"];
     
+    // Handle both formats: package = "version" and package = { version = "version" }
+    let simple_re = Regex::new(r#"([a-zA-Z0-9_-]+)\s*=\s*"([^"]+)""#).unwrap();
+    let complex_re = Regex::new(r#"([a-zA-Z0-9_-]+)\s*=\s*\{[^}]*version\s*=\s*"([^"]+)""#).unwrap();
+    
     for section in sections {
         if let Some(section_start) = content.find(&format!("[{}]", section)) {
             let section_content = &content[section_start..];
             let section_end = section_content.find("\n[").unwrap_or(section_content.len());
             let section_text = &section_content[..section_end];
             
-            // Handle both formats: package = "version" and package = { version = "version" }
-            let simple_re = Regex::new(r#"([a-zA-Z0-9_-]+)\s*=\s*"([^"]+)""#).unwrap();
-            let complex_re = Regex::new(r#"([a-zA-Z0-9_-]+)\s*=\s*\{[^}]*version\s*=\s*"([^"]+)""#).unwrap();
-            
             for caps in simple_re.captures_iter(section_text) {
                 let name = caps[1].to_string();
                 let version = caps[2].to_string();
-                packages.push((name, version, "cargo".to_string()));
+                packages.push((name, version, "cargo".to_string(), section.to_string()));
             }
             
             for caps in complex_re.captures_iter(section_text) {
                 let name = caps[1].to_string();
                 let version = caps[2].to_string();
-                packages.push((name, version, "cargo".to_string()));
+                packages.push((name, version, "cargo".to_string(), section.to_string()));
             }
         }
     }
@@ -313,7 +650,7 @@ fn parse_cargo_toml(content: &str) -> Result<Vec<(String, String, String)>> {
     Ok(packages)
 }
 
-fn parse_requirements_txt(content: &str) -> Result<Vec<(String, String, String)>> {
+fn parse_requirements_txt(content: &str) -> Result<Vec<PackageDependency>> {
     let mut packages = Vec::new();
     
     let re = Regex::new(r"^([a-zA-Z0-9_-]+)[>=<~!]*([0-9]+(?:\.[0-9]+)*(?:\.[0-9]+)?)").unwrap();
@@ -327,33 +664,39 @@ fn parse_requirements_txt(content: &str) -> Result<Vec<(String, String, String)>
         if let Some(caps) = re.captures(line) {
             let name = caps[1].to_string();
             let version = caps[2].to_string();
-            packages.push((name, version, "pip".to_string()));
+            packages.push((name, version, "pip".to_string(), "dependencies".to_string()));
         }
     }
     
     Ok(packages)
 }
 
-fn parse_pyproject_toml(content: &str) -> Result<Vec<(String, String, String)>> {
+fn parse_pyproject_toml(content: &str) -> Result<Vec<PackageDependency>> {
     let mut packages = Vec::new();
     
-    // Look for dependencies in [tool.poetry.dependencies] or [project.dependencies]
-    let sections = ["[tool.poetry.dependencies]", "[project.dependencies]"];
+    // Look for dependencies in [tool.poetry.dependencies], [tool.poetry.dev-dependencies],
+    // [tool.poetry.group.<name>.dependencies] or [project.dependencies]
+    let header_re = Regex::new(r"(?m)^\[(?:tool\.poetry\.(dependencies|dev-dependencies|group\.([a-zA-Z0-9_-]+)\.dependencies)|project\.(dependencies))\]").unwrap();
+    let re = Regex::new(r#"([a-zA-Z0-9_-]+)\s*=\s*"([^"]+)""#).unwrap();
     
-    for section in sections {
-        if let Some(section_start) = content.find(section) {
-            let section_content = &content[section_start..];
-            let section_end = section_content.find("\n[").unwrap_or(section_content.len());
-            let section_text = &section_content[..section_end];
-            
-            let re = Regex::new(r#"([a-zA-Z0-9_-]+)\s*=\s*"([^"]+)""#).unwrap();
-            
-            for caps in re.captures_iter(section_text) {
-                let name = caps[1].to_string();
-                let version = caps[2].to_string();
-                if name != "python" { // Skip python version specification
-                    packages.push((name, version, "poetry".to_string()));
-                }
+    for header in header_re.captures_iter(content) {
+        // Poetry groups are reported as `<group>-dependencies`, so the `dev`
+        // group lines up with the older dev-dependencies table
+        let section = match (header.get(2), header.get(1)) {
+            (Some(group), _) => format!("{}-dependencies", group.as_str()),
+            (None, Some(table)) => table.as_str().to_string(),
+            (None, None) => "dependencies".to_string(),
+        };
+        
+        let section_content = &content[header.get(0).unwrap().end()..];
+        let section_end = section_content.find("\n[").unwrap_or(section_content.len());
+        let section_text = &section_content[..section_end];
+        
+        for caps in re.captures_iter(section_text) {
+            let name = caps[1].to_string();
+            let version = caps[2].to_string();
+            if name != "python" { // Skip python version specification
+                packages.push((name, version, "poetry".to_string(), section.clone()));
             }
         }
     }
@@ -361,24 +704,24 @@ fn parse_pyproject_toml(content: &str) -> Result<Vec<(String, String, String)>>
     Ok(packages)
 }
 
-fn parse_composer_json(content: &str) -> Result<Vec<(String, String, String)>> {
+fn parse_composer_json(content: &str) -> Result<Vec<PackageDependency>> {
     let mut packages = Vec::new();
     
     let sections = ["require", "require-dev"];
+    let re = Regex::new(r#""([^"]+)":\s*"([^"]+)""#).unwrap();
     
     for section in sections {
-        if let Some(section_start) = content.find(&format!("\"{}\"", section)) {
-            if let Some(brace_start) = content[section_start..].find('{') {
-                let start_pos = section_start + brace_start + 1;
-                if let Some(brace_end) = find_matching_brace(&content[start_pos..]) {
-                    let deps_content = &content[start_pos..start_pos + brace_end];
-                    
-                    let re = Regex::new(r#""([^"]+)":\s*"([^"]+)""#).unwrap();
-                    for caps in re.captures_iter(deps_content) {
-                        let name = caps[1].to_string();
-                        let version = caps[2].to_string();
-                        packages.push((name, version, "composer".to_string()));
-                    }
+        if let Some(section_start) = content.find(&format!("\"{}\"", section))
+            && let Some(brace_start) = content[section_start..].find('{')
+        {
+            let start_pos = section_start + brace_start + 1;
+            if let Some(brace_end) = find_matching_brace(&content[start_pos..]) {
+                let deps_content = &content[start_pos..start_pos + brace_end];
+                
+                for caps in re.captures_iter(deps_content) {
+                    let name = caps[1].to_string();
+                    let version = caps[2].to_string();
+                    packages.push((name, version, "composer".to_string(), section.to_string()));
                 }
             }
         }
@@ -387,7 +730,7 @@ fn parse_composer_json(content: &str) -> Result<Vec<(String, String, String)>> {
     Ok(packages)
 }
 
-fn parse_go_mod(content: &str) -> Result<Vec<(String, String, String)>> {
+fn parse_go_mod(content: &str) -> Result<Vec<PackageDependency>> {
     let mut packages = Vec::new();
     
     let re = Regex::new(r"([a-zA-Z0-9./\-_]+)\s+v([0-9]+\.[0-9]+\.[0-9]+[^\s]*)").unwrap();
@@ -395,7 +738,7 @@ fn parse_go_mod(content: &str) -> Result<Vec<(String, String, String)>> {
     for caps in re.captures_iter(content) {
         let name = caps[1].to_string();
         let version = caps[2].to_string();
-        packages.push((name, version, "go".to_string()));
+        packages.push((name, version, "go".to_string(), "require".to_string()));
     }
     
     Ok(packages)
@@ -427,4 +770,708 @@ fn find_matching_brace(content: &str) -> Option<usize> {
     }
     
     None
-}
\ No newline at end of file
+}
+
+/// A package in a lockfile dependency graph
+#[derive(Debug, Clone)]
+pub struct LockNode {
+    pub name: String,
+    pub version: String,
+    /// Keys of the packages this one depends on
+    pub dependencies: Vec<String>,
+}
+
+/// Dependency graph parsed from a lockfile, keyed by a lockfile-specific id
+/// (install path for package-lock.json, "name version" for Cargo.lock)
+#[derive(Debug, Clone, Default)]
+pub struct LockGraph {
+    pub nodes: HashMap<String, LockNode>,
+}
+
+impl LockGraph {
+    /// Reverse edges: for each package, the packages that depend on it directly
+    fn dependents(&self) -> HashMap<&str, Vec<&str>> {
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+
+        for (key, node) in &self.nodes {
+            for dep in &node.dependencies {
+                dependents
+                    .entry(dep.as_str())
+                    .or_default()
+                    .push(key.as_str());
+            }
+        }
+
+        for parents in dependents.values_mut() {
+            parents.sort();
+            parents.dedup();
+        }
+
+        dependents
+    }
+
+    /// The packages nothing depends on: the project (or workspace members)
+    /// and anything extraneous
+    fn roots(&self) -> Vec<&str> {
+        let dependents = self.dependents();
+        let mut roots: Vec<&str> = self
+            .nodes
+            .keys()
+            .map(String::as_str)
+            .filter(|key| !dependents.contains_key(key))
+            .collect();
+        roots.sort();
+        roots
+    }
+
+    fn label(&self, key: &str) -> String {
+        let node = &self.nodes[key];
+        if node.version.is_empty() {
+            node.name.clone()
+        } else {
+            format!("{} {}", node.name, node.version)
+        }
+    }
+}
+
+/// The dependency graph of every lockfile under `search_path` (or of the
+/// lockfile it names), in path order
+fn find_lock_graphs(search_path: Option<&str>, verbose: bool) -> Result<Vec<(PathBuf, LockGraph)>> {
+    let search_dir = PathBuf::from(search_path.unwrap_or("."));
+    let mut lock_files = Vec::new();
+    if search_dir.is_file() {
+        if is_lock_file(&search_dir) {
+            lock_files.push(search_dir);
+        }
+    } else {
+        find_package_files_recursive(&search_dir, &mut lock_files, is_lock_file, verbose)?;
+    }
+    lock_files.sort();
+
+    let mut graphs = Vec::new();
+    for lock_file in lock_files {
+        match parse_lock_file(&lock_file) {
+            Ok(Some(graph)) => graphs.push((lock_file, graph)),
+            Ok(None) => {
+                if verbose {
+                    println!("⏭️  No dependency graph in {}", lock_file.display());
+                }
+            }
+            Err(e) => {
+                if verbose {
+                    println!("❌ Failed to parse {}: {}", lock_file.display(), e);
+                }
+            }
+        }
+    }
+    Ok(graphs)
+}
+
+/// Find "who requires it" trees for every lockfile entry of the packages `filter` matches whose
+/// version satisfies `query`, or for every version without one
+///
+/// Each tree starts at the matched package and branches out to its dependents
+/// up to the project root. Packages whose dependents were already shown are
+/// marked with (*) instead of being expanded again.
+pub fn find_dependency_chains(
+    filter: &PackageFilter,
+    query: Option<&VersionQuery>,
+    search_path: Option<&str>,
+    verbose: bool,
+) -> Result<Vec<(PathBuf, TreeNode)>> {
+    let mut chains = Vec::new();
+
+    for (lock_file, graph) in find_lock_graphs(search_path, verbose)? {
+        let dependents = graph.dependents();
+
+        let mut targets: Vec<&str> = graph
+            .nodes
+            .iter()
+            .filter(|(_, node)| {
+                filter.matches(&node.name) && query.is_none_or(|query| query.matches(&node.version))
+            })
+            .map(|(key, _)| key.as_str())
+            .collect();
+        targets.sort();
+        
+        for key in targets {
+            let mut expanded = HashSet::new();
+            let tree = build_dependents_tree(&graph, &dependents, key, &mut expanded);
+            chains.push((lock_file.clone(), tree));
+        }
+    }
+    
+    Ok(chains)
+}
+
+fn build_dependents_tree(
+    graph: &LockGraph,
+    dependents: &HashMap<&str, Vec<&str>>,
+    key: &str,
+    expanded: &mut HashSet<String>,
+) -> TreeNode {
+    let mut tree = TreeNode::new(graph.label(key));
+
+    // Already expanded elsewhere in this tree (this also breaks cycles)
+    if !expanded.insert(key.to_string()) {
+        tree.label.push_str(" (*)");
+        return tree;
+    }
+
+    if let Some(parents) = dependents.get(key) {
+        for parent in parents {
+            tree.children
+                .push(build_dependents_tree(graph, dependents, parent, expanded));
+        }
+    }
+
+    tree
+}
+
+/// Marks the searched-for package in a dependency tree
+pub const TREE_TARGET_MARKER: &str = "◀";
+
+/// Find the dependency tree of every lockfile's project, top-down
+///
+/// Each tree starts at a package nothing depends on (the project, or each
+/// workspace member) and branches out to its dependencies. With a package
+/// filter, only the branches leading to a matching package and version are
+/// kept, so the root's children are the top-level dependencies that pull it
+/// in; it's marked with ◀ and not expanded further. `max_depth` stops trees that many
+/// levels below the root. Packages already expanded elsewhere in a tree are
+/// marked with (*).
+pub fn find_dependency_trees(
+    filter: &PackageFilter,
+    query: Option<&VersionQuery>,
+    search_path: Option<&str>,
+    max_depth: Option<usize>,
+    verbose: bool,
+) -> Result<Vec<(PathBuf, TreeNode)>> {
+    let mut trees = Vec::new();
+
+    for (lock_file, graph) in find_lock_graphs(search_path, verbose)? {
+        let targets: HashSet<&str> = if filter.is_empty() {
+            HashSet::new()
+        } else {
+            graph
+                .nodes
+                .iter()
+                .filter(|(_, node)| {
+                    filter.matches(&node.name)
+                        && query.is_none_or(|query| query.matches(&node.version))
+                })
+                .map(|(key, _)| key.as_str())
+                .collect()
+        };
+        if !filter.is_empty() && targets.is_empty() {
+            continue;
+        }
+
+        // Everything a target can be reached from, walking dependents up
+        let leads_to = (!filter.is_empty()).then(|| {
+            let dependents = graph.dependents();
+            let mut seen: HashSet<&str> = targets.clone();
+            let mut stack: Vec<&str> = targets.iter().copied().collect();
+            while let Some(key) = stack.pop() {
+                for parent in dependents.get(key).into_iter().flatten() {
+                    if seen.insert(parent) {
+                        stack.push(parent);
+                    }
+                }
+            }
+            seen
+        });
+
+        for root in graph.roots() {
+            if leads_to.as_ref().is_some_and(|keys| !keys.contains(root)) {
+                continue;
+            }
+            let mut expanded = HashSet::new();
+            let tree = build_dependencies_tree(
+                &graph,
+                root,
+                &TreeFilter {
+                    targets: &targets,
+                    leads_to: leads_to.as_ref(),
+                    max_depth,
+                },
+                0,
+                &mut expanded,
+            );
+            trees.push((lock_file.clone(), tree));
+        }
+    }
+
+    Ok(trees)
+}
+
+/// Which branches of a top-down dependency tree are shown
+struct TreeFilter<'a> {
+    targets: &'a HashSet<&'a str>,
+    /// Only packages a target can be reached from, when one is searched for
+    leads_to: Option<&'a HashSet<&'a str>>,
+    max_depth: Option<usize>,
+}
+
+fn build_dependencies_tree(
+    graph: &LockGraph,
+    key: &str,
+    filter: &TreeFilter,
+    depth: usize,
+    expanded: &mut HashSet<String>,
+) -> TreeNode {
+    let mut tree = TreeNode::new(graph.label(key));
+
+    if filter.targets.contains(key) {
+        tree.label = format!("{} {}", tree.label, TREE_TARGET_MARKER);
+        return tree;
+    }
+    if filter.max_depth.is_some_and(|max| depth >= max) {
+        return tree;
+    }
+    // Already expanded elsewhere in this tree (this also breaks cycles)
+    if !expanded.insert(key.to_string()) {
+        tree.label.push_str(" (*)");
+        return tree;
+    }
+
+    let mut dependencies: Vec<&String> = graph.nodes[key]
+        .dependencies
+        .iter()
+        .filter(|dep| graph.nodes.contains_key(*dep))
+        .filter(|dep| {
+            filter
+                .leads_to
+                .is_none_or(|keys| keys.contains(dep.as_str()))
+        })
+        .collect();
+    dependencies.sort_by_key(|dep| graph.label(dep));
+    dependencies.dedup();
+    for dep in dependencies {
+        tree.children.push(build_dependencies_tree(
+            graph,
+            dep,
+            filter,
+            depth + 1,
+            expanded,
+        ));
+    }
+
+    tree
+}
+
+fn is_lock_file(path: &Path) -> bool {
+    matches!(
+        path.file_name().and_then(|n| n.to_str()),
+        Some("package-lock.json") | Some("Cargo.lock")
+    )
+}
+
+fn parse_lock_file(path: &Path) -> Result<Option<LockGraph>> {
+    let content = fs::read_to_string(path).map_err(|source| PackagesError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    
+    match path.file_name().and_then(|n| n.to_str()) {
+        Some("package-lock.json") => parse_package_lock(&content),
+        Some("Cargo.lock") => parse_cargo_lock(&content),
+        _ => Ok(None),
+    }
+}
+
+/// Parse a package-lock.json (v2/v3); v1 lockfiles have no flat package graph
+fn parse_package_lock(content: &str) -> Result<Option<LockGraph>> {
+    let json: serde_json::Value = serde_json::from_str(content)?;
+    
+    let packages = match json.get("packages").and_then(|p| p.as_object()) {
+        Some(packages) => packages,
+        None => return Ok(None),
+    };
+    
+    let mut graph = LockGraph::default();
+    
+    for (path, info) in packages {
+        let name = if path.is_empty() {
+            info.get("name").and_then(|n| n.as_str()).unwrap_or("(root)")
+        } else {
+            path.rsplit("node_modules/").next().unwrap_or(path)
+        };
+        let version = info.get("version").and_then(|v| v.as_str()).unwrap_or("");
+        
+        let mut dependencies = Vec::new();
+        for section in ["dependencies", "devDependencies", "optionalDependencies", "peerDependencies"] {
+            if let Some(deps) = info.get(section).and_then(|d| d.as_object()) {
+                dependencies.extend(deps.keys().filter_map(|dep| resolve_node_module(packages, path, dep)));
+            }
+        }
+        
+        graph.nodes.insert(path.clone(), LockNode {
+            name: name.to_string(),
+            version: version.to_string(),
+            dependencies,
+        });
+    }
+    
+    Ok(Some(graph))
+}
+
+/// Resolve a dependency like Node does: the nearest node_modules walking up from `from`
+fn resolve_node_module(packages: &serde_json::Map<String, serde_json::Value>, from: &str, dep: &str) -> Option<String> {
+    let mut base = from.to_string();
+    
+    loop {
+        let candidate = if base.is_empty() {
+            format!("node_modules/{}", dep)
+        } else {
+            format!("{}/node_modules/{}", base, dep)
+        };
+        if packages.contains_key(&candidate) {
+            return Some(candidate);
+        }
+        if base.is_empty() {
+            return None;
+        }
+        base = match base.rfind("/node_modules/") {
+            Some(i) => base[..i].to_string(),
+            None => String::new(),
+        };
+    }
+}
+
+/// Parse a Cargo.lock; dependencies are "name" or "name version [source]"
+fn parse_cargo_lock(content: &str) -> Result<Option<LockGraph>> {
+    let table: toml::Table = content.parse()?;
+    
+    let packages = match table.get("package").and_then(|p| p.as_array()) {
+        Some(packages) => packages,
+        None => return Ok(None),
+    };
+    
+    let entries: Vec<(String, String, Vec<String>)> = packages
+        .iter()
+        .filter_map(|p| p.as_table())
+        .map(|p| {
+            let field = |key: &str| p.get(key).and_then(|v| v.as_str()).unwrap_or("").to_string();
+            let deps = p.get("dependencies")
+                .and_then(|d| d.as_array())
+                .map(|deps| deps.iter().filter_map(|d| d.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default();
+            (field("name"), field("version"), deps)
+        })
+        .collect();
+    
+    let mut graph = LockGraph::default();
+    
+    for (name, version, deps) in &entries {
+        let dependencies = deps
+            .iter()
+            .filter_map(|dep| {
+                let mut parts = dep.split_whitespace();
+                let dep_name = parts.next()?;
+                match parts.next() {
+                    Some(dep_version) => Some(format!("{} {}", dep_name, dep_version)),
+                    // Only one version in the lockfile, so the name alone is unambiguous
+                    None => entries
+                        .iter()
+                        .find(|(n, _, _)| n == dep_name)
+                        .map(|(n, v, _)| format!("{} {}", n, v)),
+                }
+            })
+            .collect();
+        
+        graph.nodes.insert(format!("{} {}", name, version), LockNode {
+            name: name.clone(),
+            version: version.clone(),
+            dependencies,
+        });
+    }
+    
+    Ok(Some(graph))
+}
+
+/// A lockfile hygiene problem found by `packages doctor`
+#[derive(Tabled)]
+pub struct DoctorEntry {
+    #[tabled(rename = "Project")]
+    pub project: String,
+    #[tabled(rename = "Issue")]
+    pub issue: String,
+    #[tabled(rename = "Details")]
+    pub details: String,
+    #[tabled(rename = "Suggested Fix")]
+    pub fix: String,
+}
+
+/// JavaScript lockfiles and the package manager that writes each one
+const JS_LOCKFILES: &[(&str, &str)] = &[
+    ("package-lock.json", "npm"),
+    ("yarn.lock", "yarn"),
+    ("pnpm-lock.yaml", "pnpm"),
+    ("bun.lock", "bun"),
+    ("bun.lockb", "bun"),
+];
+
+/// (manifest, lockfile, command that brings the lockfile up to date)
+const LOCKFILE_PAIRS: &[(&str, &str, &str)] = &[
+    ("package.json", "package-lock.json", "npm install"),
+    ("package.json", "yarn.lock", "yarn install"),
+    ("package.json", "pnpm-lock.yaml", "pnpm install"),
+    ("package.json", "bun.lock", "bun install"),
+    ("package.json", "bun.lockb", "bun install"),
+    ("Cargo.toml", "Cargo.lock", "cargo update --workspace"),
+    ("Pipfile", "Pipfile.lock", "pipenv lock"),
+    ("pyproject.toml", "poetry.lock", "poetry lock"),
+    ("pyproject.toml", "uv.lock", "uv lock"),
+    ("composer.json", "composer.lock", "composer update --lock"),
+    ("Gemfile", "Gemfile.lock", "bundle install"),
+    ("go.mod", "go.sum", "go mod tidy"),
+];
+
+fn is_doctor_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| LOCKFILE_PAIRS.iter().any(|(manifest, lock, _)| name == *manifest || name == *lock))
+}
+
+/// Check every project under `search_path` for lockfile hygiene problems
+///
+/// Reports JavaScript projects with more than one lockfile and lockfiles that
+/// have drifted from their manifest. Drift is checked semantically where the
+/// lockfile format allows it (package-lock.json, yarn.lock, Cargo.lock: every
+/// manifest dependency must be locked, with the same version spec for
+/// package-lock.json); other lockfiles are stale when older than the manifest.
+pub fn diagnose_lockfiles(search_path: Option<&str>, verbose: bool) -> Result<Vec<DoctorEntry>> {
+    let search_dir = PathBuf::from(search_path.unwrap_or("."));
+    let mut files = Vec::new();
+    if verbose {
+        println!("📁 Scanning directory: {}", search_dir.display());
+    }
+    find_package_files_recursive(&search_dir, &mut files, is_doctor_file, verbose)?;
+    
+    // Group manifest and lockfiles by project directory
+    let mut projects: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+    for file in files {
+        if let (Some(dir), Some(name)) = (file.parent(), file.file_name().and_then(|n| n.to_str())) {
+            projects.entry(dir.to_path_buf()).or_default().insert(name.to_string());
+        }
+    }
+    
+    let mut project_dirs: Vec<PathBuf> = projects.keys().cloned().collect();
+    project_dirs.sort();
+    
+    let entries = project_dirs
+        .par_iter()
+        .flat_map(|dir| diagnose_project(dir, &projects[dir], verbose))
+        .collect();
+    
+    Ok(entries)
+}
+
+/// Hygiene checks for one project directory, given the file names it contains
+fn diagnose_project(dir: &Path, names: &HashSet<String>, verbose: bool) -> Vec<DoctorEntry> {
+    let project = dir.to_string_lossy().to_string();
+    let mut entries = Vec::new();
+    // Lockfiles we suggest deleting aren't worth checking for drift
+    let mut redundant: Vec<&str> = Vec::new();
+    
+    let js_locks: Vec<(&str, &str)> = JS_LOCKFILES
+        .iter()
+        .filter(|(lock, _)| names.contains(*lock))
+        .copied()
+        .collect();
+    
+    if names.contains("package.json") && js_locks.len() > 1 {
+        let lock_names: Vec<&str> = js_locks.iter().map(|(lock, _)| *lock).collect();
+        
+        // Prefer the manager pinned in package.json, then the newest lockfile
+        let pinned = fs::read_to_string(dir.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| json.get("packageManager").and_then(|m| m.as_str()).map(|m| m.to_string()));
+        let keep = pinned
+            .as_deref()
+            .and_then(|manager| js_locks.iter().find(|(_, m)| manager.starts_with(*m)))
+            .or_else(|| js_locks.iter().max_by_key(|(lock, _)| modified(&dir.join(lock))))
+            .map(|(lock, _)| *lock)
+            .unwrap_or(lock_names[0]);
+        let remove: Vec<&str> = lock_names.iter().copied().filter(|lock| *lock != keep).collect();
+        
+        entries.push(DoctorEntry {
+            project: project.clone(),
+            issue: "Multiple lockfiles".to_string(),
+            details: lock_names.join(", "),
+            fix: format!("Keep {} and delete {}", keep, remove.join(", ")),
+        });
+        redundant = remove;
+    }
+    
+    for (manifest, lock, command) in LOCKFILE_PAIRS {
+        if !names.contains(*manifest) || !names.contains(*lock) || redundant.contains(lock) {
+            continue;
+        }
+        let manifest_path = dir.join(manifest);
+        let lock_path = dir.join(lock);
+        
+        let drift = match *lock {
+            "package-lock.json" | "yarn.lock" | "Cargo.lock" => match lockfile_drift(&manifest_path, &lock_path) {
+                Ok(drift) => drift,
+                Err(e) => {
+                    if verbose {
+                        println!("❌ Failed to compare {} with {}: {}", manifest_path.display(), lock, e);
+                    }
+                    continue;
+                }
+            },
+            _ => match (modified(&manifest_path), modified(&lock_path)) {
+                (Some(manifest_time), Some(lock_time)) if lock_time < manifest_time => {
+                    vec![format!("{} is older than {}", lock, manifest)]
+                }
+                _ => Vec::new(),
+            },
+        };
+        
+        if !drift.is_empty() {
+            let shown: Vec<&str> = drift.iter().take(3).map(|d| d.as_str()).collect();
+            let more = if drift.len() > 3 { format!(" (+{} more)", drift.len() - 3) } else { String::new() };
+            entries.push(DoctorEntry {
+                project: project.clone(),
+                issue: format!("{} out of sync", lock),
+                details: format!("{}{}", shown.join("; "), more),
+                fix: format!("Run `{}`", command),
+            });
+        }
+    }
+    
+    entries
+}
+
+fn modified(path: &Path) -> Option<std::time::SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Manifest dependencies the lockfile doesn't agree with, one message each
+fn lockfile_drift(manifest: &Path, lock: &Path) -> Result<Vec<String>> {
+    let read = |path: &Path| fs::read_to_string(path).map_err(|source| PackagesError::Read {
+        path: path.to_path_buf(),
+        source,
+    });
+    let manifest_content = read(manifest)?;
+    let lock_content = read(lock)?;
+    
+    let mut drift = Vec::new();
+    
+    match lock.file_name().and_then(|n| n.to_str()) {
+        Some("package-lock.json") => {
+            let manifest_json: serde_json::Value = serde_json::from_str(&manifest_content)?;
+            let lock_json: serde_json::Value = serde_json::from_str(&lock_content)?;
+            
+            // v2/v3 copy the manifest's specs into packages[""]; v1 only lists names
+            let root = lock_json.get("packages").and_then(|p| p.get(""));
+            for (name, spec) in npm_manifest_deps(&manifest_json) {
+                match root {
+                    Some(root) => {
+                        let locked = ["dependencies", "devDependencies", "optionalDependencies", "peerDependencies"]
+                            .iter()
+                            .find_map(|section| root.get(section).and_then(|d| d.get(&name)).and_then(|s| s.as_str()));
+                        match locked {
+                            None => drift.push(format!("{} not in lockfile", name)),
+                            Some(locked) if locked != spec => {
+                                drift.push(format!("{} is {} in package.json, {} in lockfile", name, spec, locked));
+                            }
+                            Some(_) => {}
+                        }
+                    }
+                    None => {
+                        if lock_json.get("dependencies").and_then(|d| d.get(&name)).is_none() {
+                            drift.push(format!("{} not in lockfile", name));
+                        }
+                    }
+                }
+            }
+        }
+        Some("yarn.lock") => {
+            let manifest_json: serde_json::Value = serde_json::from_str(&manifest_content)?;
+            
+            // Entry headers look like `lodash@^4.17.0, lodash@^4.0.0:` (quoted in yarn 2+)
+            let locked: HashSet<String> = lock_content
+                .lines()
+                .filter(|line| !line.starts_with(' ') && line.ends_with(':'))
+                .flat_map(|line| line.trim_end_matches(':').split(", "))
+                .map(|entry| entry.trim_matches('"').to_string())
+                .collect();
+            for (name, spec) in npm_manifest_deps(&manifest_json) {
+                let entry = format!("{}@{}", name, spec);
+                let npm_entry = format!("{}@npm:{}", name, spec);
+                if !locked.contains(&entry) && !locked.contains(&npm_entry) {
+                    if locked.iter().any(|l| l.starts_with(&format!("{}@", name))) {
+                        drift.push(format!("{}@{} not in lockfile", name, spec));
+                    } else {
+                        drift.push(format!("{} not in lockfile", name));
+                    }
+                }
+            }
+        }
+        Some("Cargo.lock") => {
+            let manifest_table: toml::Table = manifest_content.parse()?;
+            let lock_table: toml::Table = lock_content.parse()?;
+            
+            let locked: HashSet<&str> = lock_table
+                .get("package")
+                .and_then(|p| p.as_array())
+                .map(|packages| packages.iter().filter_map(|p| p.get("name").and_then(|n| n.as_str())).collect())
+                .unwrap_or_default();
+            for name in cargo_manifest_deps(&manifest_table) {
+                if !locked.contains(name.as_str()) {
+                    drift.push(format!("{} not in lockfile", name));
+                }
+            }
+        }
+        _ => {}
+    }
+    
+    drift.sort();
+    Ok(drift)
+}
+
+/// (name, version spec) for every dependency declared in a package.json
+fn npm_manifest_deps(manifest: &serde_json::Value) -> Vec<(String, String)> {
+    let mut deps = Vec::new();
+    for section in ["dependencies", "devDependencies", "optionalDependencies"] {
+        if let Some(section_deps) = manifest.get(section).and_then(|d| d.as_object()) {
+            for (name, spec) in section_deps {
+                if let Some(spec) = spec.as_str() {
+                    deps.push((name.clone(), spec.to_string()));
+                }
+            }
+        }
+    }
+    deps
+}
+
+/// Package names of every dependency declared in a Cargo.toml (renames resolved)
+fn cargo_manifest_deps(manifest: &toml::Table) -> Vec<String> {
+    let mut tables = Vec::new();
+    for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        if let Some(deps) = manifest.get(section).and_then(|d| d.as_table()) {
+            tables.push(deps);
+        }
+    }
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        for target in targets.values() {
+            for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+                if let Some(deps) = target.get(section).and_then(|d| d.as_table()) {
+                    tables.push(deps);
+                }
+            }
+        }
+    }
+    
+    tables
+        .into_iter()
+        .flat_map(|deps| deps.iter())
+        .map(|(key, value)| {
+            value.get("package").and_then(|p| p.as_str()).unwrap_or(key).to_string()
+        })
+        .collect()
+}
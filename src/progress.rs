@@ -0,0 +1,54 @@
+use indicatif::{ProgressBar, ProgressStyle};
+use std::time::Duration;
+
+/// How often spinners advance while the work they show is running
+const TICK: Duration = Duration::from_millis(120);
+
+/// A bar counting `len` items, with the message (the item in progress, a
+/// running total, ...) filling the rest of the line
+///
+/// Like every bar here it draws to stderr, and not at all when that isn't a
+/// terminal; the message is cut to the terminal's width instead of wrapping.
+pub fn count_bar(len: u64) -> ProgressBar {
+    let bar = ProgressBar::new(len).with_style(
+        ProgressStyle::with_template("{spinner:.cyan} {bar:30.cyan/blue} {pos}/{len} {wide_msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_bar())
+            .progress_chars("█▓░"),
+    );
+    bar.enable_steady_tick(TICK);
+    bar
+}
+
+/// A bar counting `len` items with elapsed time, rate and ETA, for long runs
+pub fn timed_bar(len: u64) -> ProgressBar {
+    let bar = ProgressBar::new(len).with_style(
+        ProgressStyle::with_template(
+            "{spinner:.cyan} [{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} ({per_sec}, ETA {eta})",
+        )
+        .unwrap_or_else(|_| ProgressStyle::default_bar())
+        .progress_chars("█▓░"),
+    );
+    bar.enable_steady_tick(TICK);
+    bar
+}
+
+/// A line showing only its message, for a status under a bar
+pub fn status_line() -> ProgressBar {
+    ProgressBar::new_spinner().with_style(
+        ProgressStyle::with_template("  {wide_msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+    )
+}
+
+/// A spinner for one item being worked on, e.g. one directory of a parallel
+/// clean under its overall bar
+pub fn item_spinner(message: String) -> ProgressBar {
+    let spinner = ProgressBar::new_spinner()
+        .with_style(
+            ProgressStyle::with_template("  {spinner:.cyan} {wide_msg:.dim}")
+                .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+        )
+        .with_message(message);
+    spinner.enable_steady_tick(TICK);
+    spinner
+}
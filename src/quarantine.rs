@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tabled::Tabled;
+
+use crate::{
+    Action, ActionRunner, FileCategory, FileToOrganize, ORGANIZE_JOURNAL_KIND, OrganizeError,
+    civil_from_days, days_from_civil, format_size, recoverable_status, unix_seconds,
+};
+
+type Result<T, E = OrganizeError> = std::result::Result<T, E>;
+
+/// Folder under the organized root that quarantined files wait in, one
+/// `2024-05-03` subfolder per day they were put there
+pub const REVIEW_FOLDER: &str = "_Review";
+
+/// A file waiting in a review folder
+#[derive(Debug, Clone)]
+pub struct QuarantinedFile {
+    pub path: PathBuf,
+    /// Day it was quarantined, e.g. `2024-05-03`
+    pub date: String,
+    pub size: u64,
+}
+
+#[derive(Tabled, Clone)]
+pub struct PurgeEntry {
+    #[tabled(rename = "Quarantined")]
+    pub date: String,
+    #[tabled(rename = "File")]
+    pub file: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+/// Today's review folder, relative to the organized root
+fn todays_review_folder() -> PathBuf {
+    let (year, month, day) = civil_from_days(unix_seconds(SystemTime::now()).div_euclid(86_400));
+    PathBuf::from(REVIEW_FOLDER).join(format!("{}-{:02}-{:02}", year, month, day))
+}
+
+/// Send the files that have no category (`Other`) or weren't modified for
+/// `stale` to today's review folder, under their own names
+///
+/// Sidecars that go with another file are left to follow it. Returns how
+/// many files were quarantined.
+pub fn quarantine_files(files: &mut [FileToOrganize], stale: Duration) -> usize {
+    let folder = todays_review_folder();
+    let cutoff = SystemTime::now()
+        .checked_sub(stale)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let mut quarantined = 0;
+    for file in files.iter_mut().filter(|f| f.sidecar_of.is_none()) {
+        let is_stale = fs::metadata(&file.path)
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified < cutoff);
+        if file.category == FileCategory::Other || is_stale {
+            file.folder = folder.clone();
+            file.target_name = file.file_name.clone();
+            quarantined += 1;
+        }
+    }
+    quarantined
+}
+
+/// The files in `root`'s review folders that were quarantined more than
+/// `retention` ago, oldest first
+///
+/// Only the dated folders are looked at; anything else in `_Review` is left
+/// alone.
+pub fn find_quarantined(root: &Path, retention: Duration) -> Result<Vec<QuarantinedFile>> {
+    let review = root.join(REVIEW_FOLDER);
+    let Ok(entries) = fs::read_dir(&review) else {
+        return Ok(Vec::new());
+    };
+
+    let today = unix_seconds(SystemTime::now()).div_euclid(86_400);
+    let retention_days = (retention.as_secs() / 86_400) as i64;
+    let mut quarantined = Vec::new();
+    for entry in entries.flatten() {
+        let date = entry.file_name().to_string_lossy().into_owned();
+        let Some(day) = parse_day(&date) else {
+            continue;
+        };
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) || today - day <= retention_days {
+            continue;
+        }
+        collect_files(&entry.path(), &date, &mut quarantined);
+    }
+
+    quarantined.sort_by(|a, b| a.date.cmp(&b.date).then(a.path.cmp(&b.path)));
+    Ok(quarantined)
+}
+
+/// Remove quarantined files with `action` (delete or trash), then the
+/// review folders they leave empty
+///
+/// Journaled as an organize run, so trashed files can be brought back with
+/// undo.
+pub fn purge_quarantined(
+    root: &Path,
+    files: &[QuarantinedFile],
+    action: Action,
+    dry_run: bool,
+) -> Result<Vec<PurgeEntry>> {
+    let runner = ActionRunner::begin(ORGANIZE_JOURNAL_KIND, action, root, dry_run)?;
+
+    let mut results = Vec::new();
+    for file in files {
+        let status =
+            recoverable_status(runner.apply(&file.path, None).map_err(OrganizeError::from))?;
+        results.push(PurgeEntry {
+            date: file.date.clone(),
+            file: file
+                .path
+                .strip_prefix(root.join(REVIEW_FOLDER).join(&file.date))
+                .unwrap_or(&file.path)
+                .display()
+                .to_string(),
+            size: format_size(file.size),
+            status,
+        });
+    }
+    runner.commit()?;
+
+    if !dry_run {
+        let review = root.join(REVIEW_FOLDER);
+        let mut dates: Vec<&String> = files.iter().map(|f| &f.date).collect();
+        dates.dedup();
+        for date in dates {
+            remove_empty(&review.join(date));
+        }
+        fs::remove_dir(&review).ok();
+    }
+    Ok(results)
+}
+
+/// Day count of a `2024-05-03` folder name
+fn parse_day(name: &str) -> Option<i64> {
+    let mut parts = name.split('-');
+    let (Some(year), Some(month), Some(day), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    let (year, month, day) = (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+    ((1..=12).contains(&month) && (1..=31).contains(&day))
+        .then(|| days_from_civil(year, month, day))
+}
+
+fn collect_files(dir: &Path, date: &str, files: &mut Vec<QuarantinedFile>) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(t) if t.is_dir() => collect_files(&path, date, files),
+            Ok(_) => files.push(QuarantinedFile {
+                size: fs::symlink_metadata(&path).map(|m| m.len()).unwrap_or(0),
+                date: date.to_string(),
+                path,
+            }),
+            Err(_) => {}
+        }
+    }
+}
+
+/// Remove `dir` and its subfolders if nothing is left in them
+fn remove_empty(dir: &Path) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            remove_empty(&entry.path());
+        }
+    }
+    fs::remove_dir(dir).ok();
+}
@@ -0,0 +1,609 @@
+use colored::Colorize;
+use rayon::prelude::*;
+use regex::Regex;
+use reqwest::blocking::Client;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tabled::Tabled;
+use thiserror::Error;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    Bookmark, BookmarkCategory, Severity, chrono_lite_now, extract_domain, html_escape,
+    is_internal_url, truncate_string,
+};
+
+type Result<T, E = ReadingListError> = std::result::Result<T, E>;
+
+/// Errors from building a reading-list digest
+///
+/// Fetch failures only affect one article (it goes into the digest as a bare
+/// link) and are recoverable; failing to write the digest is fatal.
+#[derive(Debug, Error)]
+pub enum ReadingListError {
+    #[error("Failed to fetch {url}")]
+    Fetch {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("{url} returned {status}")]
+    Status {
+        url: String,
+        status: reqwest::StatusCode,
+    },
+    #[error("{url} is not an HTML page ({content_type})")]
+    NotHtml { url: String, content_type: String },
+    #[error("Failed to write {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to build EPUB")]
+    Epub(#[from] zip::result::ZipError),
+}
+
+impl ReadingListError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            ReadingListError::Fetch { .. }
+            | ReadingListError::Status { .. }
+            | ReadingListError::NotHtml { .. } => Severity::Recoverable,
+            ReadingListError::Write { .. } | ReadingListError::Epub(_) => Severity::Fatal,
+        }
+    }
+}
+
+/// Sites that mostly host articles: blogging platforms, newsletters, news and magazines
+const ARTICLE_DOMAINS: &[&str] = &[
+    // Blogging platforms and newsletters
+    "medium.com",
+    "substack.com",
+    "dev.to",
+    "hashnode.dev",
+    "hackernoon.com",
+    "towardsdatascience.com",
+    "blogspot.com",
+    "wordpress.com",
+    "ghost.io",
+    "mirror.xyz",
+    // News and magazines
+    "nytimes.com",
+    "theguardian.com",
+    "bbc.com",
+    "bbc.co.uk",
+    "washingtonpost.com",
+    "theatlantic.com",
+    "newyorker.com",
+    "economist.com",
+    "reuters.com",
+    "bloomberg.com",
+    "arstechnica.com",
+    "theverge.com",
+    "wired.com",
+    "techcrunch.com",
+    "quantamagazine.org",
+    "lwn.net",
+    // Long-form tech writing
+    "smashingmagazine.com",
+    "css-tricks.com",
+    "martinfowler.com",
+    "paulgraham.com",
+];
+
+/// Sites whose pages are apps, code or video rather than something to read
+const NON_ARTICLE_DOMAINS: &[&str] = &[
+    "github.com",
+    "gitlab.com",
+    "youtube.com",
+    "youtu.be",
+    "google.com",
+    "amazon.com",
+    "twitter.com",
+    "x.com",
+    "reddit.com",
+    "stackoverflow.com",
+    "linkedin.com",
+    "figma.com",
+    "notion.so",
+];
+
+/// Path segments that usually lead to a single post
+const ARTICLE_PATH_MARKERS: &[&str] = &[
+    "/blog/",
+    "/blogs/",
+    "/posts/",
+    "/post/",
+    "/article/",
+    "/articles/",
+    "/news/",
+    "/stories/",
+    "/story/",
+    "/essays/",
+    "/p/",
+];
+
+/// Slugs with at least this many words (`why-we-moved-to-rust`) look like article titles
+const ARTICLE_SLUG_WORDS: usize = 4;
+
+/// Shortest paragraph (in words) kept from a fetched page; drops buttons and captions
+const MIN_PARAGRAPH_WORDS: usize = 8;
+
+static DATED_PATH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"/(19|20)\d{2}/\d{1,2}/").unwrap());
+
+/// Page chrome that never holds article text
+static BOILERPLATE: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        "script", "style", "noscript", "svg", "nav", "header", "footer", "aside", "form", "iframe",
+    ]
+    .iter()
+    .map(|tag| Regex::new(&format!(r"(?is)<{0}\b[^>]*>.*?</{0}\s*>", tag)).unwrap())
+    .collect()
+});
+
+static CONTAINERS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    ["article", "main", "body"]
+        .iter()
+        .map(|tag| Regex::new(&format!(r"(?is)<{0}\b[^>]*>(.*)</{0}\s*>", tag)).unwrap())
+        .collect()
+});
+
+static BLOCKS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?is)<(p|h[1-4]|li|blockquote|pre)\b[^>]*>(.*?)</(?:p|h[1-4]|li|blockquote|pre)\s*>",
+    )
+    .unwrap()
+});
+
+static TAGS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<[^>]+>").unwrap());
+
+static ENTITIES: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"&(#[xX][0-9a-fA-F]+|#[0-9]+|[a-zA-Z]+);").unwrap());
+
+/// A bookmark picked for the reading list
+pub struct ReadingItem<'a> {
+    pub bookmark: &'a Bookmark,
+    /// Why the bookmark looks like an article
+    pub reason: &'static str,
+    /// Readable content, when fetched
+    pub content: Option<Result<Vec<ArticleBlock>>>,
+}
+
+/// A block of readable text extracted from an article page
+#[derive(Debug, Clone)]
+pub enum ArticleBlock {
+    Heading(String),
+    Paragraph(String),
+}
+
+/// Entry for the reading list table
+#[derive(Tabled, Clone)]
+pub struct ReadingListEntry {
+    #[tabled(rename = "Title")]
+    pub title: String,
+    #[tabled(rename = "Domain")]
+    pub domain: String,
+    #[tabled(rename = "Why")]
+    pub reason: String,
+    #[tabled(rename = "Content")]
+    pub content: String,
+}
+
+fn domain_in(domain: &str, list: &[&str]) -> bool {
+    list.iter()
+        .any(|d| domain == *d || domain.ends_with(&format!(".{}", d)))
+}
+
+/// Why a bookmark looks like an article worth reading offline, if it does
+///
+/// Needs a real path (home pages aren't articles) and then any of: a known
+/// article site, a blog/post path segment, a dated path, a long title-like
+/// slug, or the News category. Code hosts, video and app sites never qualify.
+pub fn article_reason(bookmark: &Bookmark) -> Option<&'static str> {
+    if is_internal_url(&bookmark.url) {
+        return None;
+    }
+    let parsed = reqwest::Url::parse(&bookmark.url).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
+    }
+
+    let domain = extract_domain(&bookmark.url);
+    if domain_in(&domain, NON_ARTICLE_DOMAINS) {
+        return None;
+    }
+
+    let path = parsed.path().to_lowercase();
+    let slug = path
+        .split('/')
+        .rfind(|segment| !segment.is_empty())?
+        .trim_end_matches(".html")
+        .trim_end_matches(".htm");
+
+    if domain_in(&domain, ARTICLE_DOMAINS) {
+        Some("article site")
+    } else if ARTICLE_PATH_MARKERS.iter().any(|m| path.contains(m)) {
+        Some("blog path")
+    } else if DATED_PATH.is_match(&path) {
+        Some("dated path")
+    } else if slug.split(['-', '_']).filter(|w| !w.is_empty()).count() >= ARTICLE_SLUG_WORDS {
+        Some("article slug")
+    } else if bookmark.category == BookmarkCategory::News {
+        Some("news")
+    } else {
+        None
+    }
+}
+
+/// Article-like bookmarks, newest first
+pub fn find_reading_list(bookmarks: &[Bookmark]) -> Vec<ReadingItem<'_>> {
+    let mut items: Vec<ReadingItem> = bookmarks
+        .iter()
+        .filter_map(|bookmark| {
+            article_reason(bookmark).map(|reason| ReadingItem {
+                bookmark,
+                reason,
+                content: None,
+            })
+        })
+        .collect();
+
+    let added = |item: &ReadingItem| {
+        item.bookmark
+            .date_added
+            .as_deref()
+            .and_then(|d| d.parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+    items.sort_by_key(|item| std::cmp::Reverse(added(item)));
+    items
+}
+
+/// Fetch the readable content of every item in parallel
+pub fn fetch_reading_list(items: &mut [ReadingItem], client: &Client) {
+    println!(
+        "{} Fetching {} articles...",
+        "📥".cyan(),
+        items.len().to_string().yellow()
+    );
+
+    items.par_iter_mut().for_each(|item| {
+        item.content = Some(fetch_article(client, &item.bookmark.url));
+    });
+}
+
+/// Download a page and pull out its readable text
+pub fn fetch_article(client: &Client, url: &str) -> Result<Vec<ArticleBlock>> {
+    let response = client
+        .get(url)
+        .send()
+        .map_err(|source| ReadingListError::Fetch {
+            url: url.to_string(),
+            source,
+        })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(ReadingListError::Status {
+            url: url.to_string(),
+            status,
+        });
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("text/html")
+        .to_string();
+    if !content_type.contains("html") {
+        return Err(ReadingListError::NotHtml {
+            url: url.to_string(),
+            content_type,
+        });
+    }
+
+    let html = response.text().map_err(|source| ReadingListError::Fetch {
+        url: url.to_string(),
+        source,
+    })?;
+    Ok(extract_readable(&html))
+}
+
+/// Readability-lite: drop page chrome, pick the article/main/body container and
+/// keep its headings and substantial paragraphs
+pub fn extract_readable(html: &str) -> Vec<ArticleBlock> {
+    let mut html = html.to_string();
+    for pattern in BOILERPLATE.iter() {
+        html = pattern.replace_all(&html, " ").into_owned();
+    }
+
+    let container = CONTAINERS
+        .iter()
+        .find_map(|pattern| pattern.captures(&html).map(|c| c[1].to_string()))
+        .unwrap_or(html);
+
+    BLOCKS
+        .captures_iter(&container)
+        .filter_map(|caps| {
+            let text = clean_text(&caps[2]);
+            if text.is_empty() {
+                return None;
+            }
+            if caps[1].to_lowercase().starts_with('h') {
+                Some(ArticleBlock::Heading(text))
+            } else if text.split_whitespace().count() >= MIN_PARAGRAPH_WORDS {
+                Some(ArticleBlock::Paragraph(text))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Strip tags, decode entities and collapse whitespace
+fn clean_text(fragment: &str) -> String {
+    let text = TAGS.replace_all(fragment, " ");
+    let text = ENTITIES.replace_all(&text, |caps: &regex::Captures| {
+        let entity = &caps[1];
+        let decoded = if let Some(hex) = entity
+            .strip_prefix("#x")
+            .or_else(|| entity.strip_prefix("#X"))
+        {
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        } else if let Some(dec) = entity.strip_prefix('#') {
+            dec.parse().ok().and_then(char::from_u32)
+        } else {
+            match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "mdash" => Some('—'),
+                "ndash" => Some('–'),
+                "hellip" => Some('…'),
+                "lsquo" => Some('‘'),
+                "rsquo" => Some('’'),
+                "ldquo" => Some('“'),
+                "rdquo" => Some('”'),
+                _ => None,
+            }
+        };
+        decoded
+            .map(String::from)
+            .unwrap_or_else(|| caps[0].to_string())
+    });
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn word_count(blocks: &[ArticleBlock]) -> usize {
+    blocks
+        .iter()
+        .map(|block| match block {
+            ArticleBlock::Heading(text) | ArticleBlock::Paragraph(text) => {
+                text.split_whitespace().count()
+            }
+        })
+        .sum()
+}
+
+/// Table rows for the reading list
+pub fn reading_list_entries(items: &[ReadingItem]) -> Vec<ReadingListEntry> {
+    items
+        .iter()
+        .map(|item| ReadingListEntry {
+            title: truncate_string(&item.bookmark.name, 45),
+            domain: extract_domain(&item.bookmark.url),
+            reason: item.reason.to_string(),
+            content: match &item.content {
+                None => "Not fetched".to_string(),
+                Some(Ok(blocks)) if blocks.is_empty() => "✗ No readable text".to_string(),
+                Some(Ok(blocks)) => format!("{} words", word_count(blocks)),
+                Some(Err(e)) => format!("✗ {}", e),
+            },
+        })
+        .collect()
+}
+
+/// Write the reading list as a markdown digest, or as an EPUB when the path ends in `.epub`
+pub fn write_reading_list(items: &[ReadingItem], path: &str) -> Result<()> {
+    let is_epub = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("epub"));
+
+    if is_epub {
+        write_reading_list_epub(items, path)
+    } else {
+        fs::write(path, reading_list_markdown(items)).map_err(|source| ReadingListError::Write {
+            path: PathBuf::from(path),
+            source,
+        })
+    }
+}
+
+/// The reading list as a markdown digest: contents, then one section per article
+pub fn reading_list_markdown(items: &[ReadingItem]) -> String {
+    let mut md = String::new();
+
+    md.push_str("# Reading List\n\n");
+    md.push_str(&format!("*Generated on: {}*\n\n", chrono_lite_now()));
+    md.push_str(&format!("**{} articles**\n\n", items.len()));
+
+    md.push_str("## Contents\n\n");
+    for (i, item) in items.iter().enumerate() {
+        md.push_str(&format!(
+            "{}. {} ({})\n",
+            i + 1,
+            item.bookmark.name,
+            extract_domain(&item.bookmark.url)
+        ));
+    }
+    md.push('\n');
+
+    for (i, item) in items.iter().enumerate() {
+        md.push_str("---\n\n");
+        md.push_str(&format!("## {}. {}\n\n", i + 1, item.bookmark.name));
+        md.push_str(&format!(
+            "[{}]({}) · {}\n\n",
+            extract_domain(&item.bookmark.url),
+            item.bookmark.url,
+            item.bookmark.folder_path
+        ));
+
+        match &item.content {
+            None => {}
+            Some(Ok(blocks)) => {
+                for block in blocks {
+                    match block {
+                        ArticleBlock::Heading(text) => md.push_str(&format!("### {}\n\n", text)),
+                        ArticleBlock::Paragraph(text) => md.push_str(&format!("{}\n\n", text)),
+                    }
+                }
+            }
+            Some(Err(e)) => md.push_str(&format!("*Could not fetch the article: {}*\n\n", e)),
+        }
+    }
+
+    md
+}
+
+/// XHTML page for one article in the EPUB
+fn epub_chapter(item: &ReadingItem) -> String {
+    let mut body = format!(
+        "<h1>{}</h1>\n<p><a href=\"{}\">{}</a></p>\n",
+        html_escape(&item.bookmark.name),
+        html_escape(&item.bookmark.url),
+        html_escape(&extract_domain(&item.bookmark.url))
+    );
+
+    match &item.content {
+        None => {}
+        Some(Ok(blocks)) => {
+            for block in blocks {
+                match block {
+                    ArticleBlock::Heading(text) => {
+                        body.push_str(&format!("<h2>{}</h2>\n", html_escape(text)))
+                    }
+                    ArticleBlock::Paragraph(text) => {
+                        body.push_str(&format!("<p>{}</p>\n", html_escape(text)))
+                    }
+                }
+            }
+        }
+        Some(Err(e)) => body.push_str(&format!(
+            "<p><em>Could not fetch the article: {}</em></p>\n",
+            html_escape(&e.to_string())
+        )),
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE html>\n\
+         <html xmlns=\"http://www.w3.org/1999/xhtml\">\n\
+         <head><title>{}</title></head>\n\
+         <body>\n{}</body>\n\
+         </html>\n",
+        html_escape(&item.bookmark.name),
+        body
+    )
+}
+
+/// Write the reading list as an EPUB 3 book, one chapter per article
+fn write_reading_list_epub(items: &[ReadingItem], path: &str) -> Result<()> {
+    let write_error = |source| ReadingListError::Write {
+        path: PathBuf::from(path),
+        source,
+    };
+
+    let file = File::create(path).map_err(write_error)?;
+    let mut zip = ZipWriter::new(file);
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let date = chrono_lite_now();
+    let chapters: Vec<String> = (1..=items.len())
+        .map(|i| format!("article-{}.xhtml", i))
+        .collect();
+
+    let container = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <container version=\"1.0\" xmlns=\"urn:oasis:names:tc:opendocument:xmlns:container\">\n\
+        <rootfiles><rootfile full-path=\"OEBPS/content.opf\" media-type=\"application/oebps-package+xml\"/></rootfiles>\n\
+        </container>\n";
+
+    let manifest: String = chapters
+        .iter()
+        .enumerate()
+        .map(|(i, chapter)| {
+            format!(
+                "<item id=\"a{}\" href=\"{}\" media-type=\"application/xhtml+xml\"/>\n",
+                i + 1,
+                chapter
+            )
+        })
+        .collect();
+    let spine: String = (1..=items.len())
+        .map(|i| format!("<itemref idref=\"a{}\"/>\n", i))
+        .collect();
+    let package = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" unique-identifier=\"id\">\n\
+         <metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n\
+         <dc:identifier id=\"id\">urn:shell-explorer:reading-list:{date}</dc:identifier>\n\
+         <dc:title>Reading List ({date})</dc:title>\n\
+         <dc:language>en</dc:language>\n\
+         <meta property=\"dcterms:modified\">{date}T00:00:00Z</meta>\n\
+         </metadata>\n\
+         <manifest>\n\
+         <item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n\
+         {manifest}</manifest>\n\
+         <spine>\n{spine}</spine>\n\
+         </package>\n"
+    );
+
+    let toc: String = items
+        .iter()
+        .zip(&chapters)
+        .map(|(item, chapter)| {
+            format!(
+                "<li><a href=\"{}\">{}</a></li>\n",
+                chapter,
+                html_escape(&item.bookmark.name)
+            )
+        })
+        .collect();
+    let nav = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE html>\n\
+         <html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\">\n\
+         <head><title>Reading List</title></head>\n\
+         <body>\n<nav epub:type=\"toc\"><h1>Reading List</h1>\n<ol>\n{toc}</ol></nav>\n</body>\n\
+         </html>\n"
+    );
+
+    // The mimetype entry must come first and be stored uncompressed
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(b"application/epub+zip")
+        .map_err(write_error)?;
+    zip.start_file("META-INF/container.xml", deflated)?;
+    zip.write_all(container.as_bytes()).map_err(write_error)?;
+    zip.start_file("OEBPS/content.opf", deflated)?;
+    zip.write_all(package.as_bytes()).map_err(write_error)?;
+    zip.start_file("OEBPS/nav.xhtml", deflated)?;
+    zip.write_all(nav.as_bytes()).map_err(write_error)?;
+    for (item, chapter) in items.iter().zip(&chapters) {
+        zip.start_file(format!("OEBPS/{}", chapter), deflated)?;
+        zip.write_all(epub_chapter(item).as_bytes())
+            .map_err(write_error)?;
+    }
+    zip.finish()?;
+
+    Ok(())
+}
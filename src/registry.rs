@@ -0,0 +1,506 @@
+use colored::*;
+use rayon::prelude::*;
+use reqwest::StatusCode;
+use reqwest::blocking::Client;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use tabled::Tabled;
+
+use crate::{
+    Config, PackageEntry, PackagesError, Version, count_bar, data_dir, error_chain, unix_seconds,
+};
+
+type Result<T, E = PackagesError> = std::result::Result<T, E>;
+
+/// Latest versions looked up in the registries, relative to the data directory
+const REGISTRY_CACHE_FILE: &str = "registry-cache.json";
+
+/// How long a looked-up version is trusted before the registry is asked again
+const DEFAULT_REGISTRY_CACHE_HOURS: u64 = 24;
+
+/// User agent for registry requests when none is configured; crates.io turns
+/// away requests without one
+pub const REGISTRY_USER_AGENT: &str = concat!("shell-explorer/", env!("CARGO_PKG_VERSION"));
+
+/// A public registry dependencies are published to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Registry {
+    Npm,
+    CratesIo,
+    PyPi,
+}
+
+impl Registry {
+    /// The registry a manifest's dependencies come from, by its package type;
+    /// `None` for the ecosystems without one here (composer, go)
+    pub fn for_package_type(package_type: &str) -> Option<Self> {
+        match package_type {
+            "npm" => Some(Registry::Npm),
+            "cargo" => Some(Registry::CratesIo),
+            "pip" | "poetry" => Some(Registry::PyPi),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Registry::Npm => "npm",
+            Registry::CratesIo => "crates.io",
+            Registry::PyPi => "PyPI",
+        }
+    }
+
+    fn latest_url(self, package: &str) -> String {
+        match self {
+            // Scoped packages keep their `/` encoded: `@babel%2Fcore`
+            Registry::Npm => format!(
+                "https://registry.npmjs.org/{}/latest",
+                package.replace('/', "%2F")
+            ),
+            Registry::CratesIo => format!("https://crates.io/api/v1/crates/{}", package),
+            Registry::PyPi => format!("https://pypi.org/pypi/{}/json", package),
+        }
+    }
+
+    /// The latest release in a registry's answer; crates.io's newest stable
+    /// one, unless it only has pre-releases
+    fn latest_version(self, body: &serde_json::Value) -> Option<String> {
+        let version = match self {
+            Registry::Npm => body.get("version"),
+            Registry::CratesIo => body.get("crate").and_then(|krate| {
+                krate
+                    .get("max_stable_version")
+                    .filter(|v| !v.is_null())
+                    .or_else(|| krate.get("max_version"))
+            }),
+            Registry::PyPi => body.get("info").and_then(|info| info.get("version")),
+        };
+        version?.as_str().map(str::to_string)
+    }
+
+    /// The license the registry has for `latest`, as an SPDX expression
+    /// where it gives one
+    fn license(self, body: &serde_json::Value, latest: Option<&str>) -> Option<String> {
+        match self {
+            Registry::Npm => npm_license(body),
+            Registry::CratesIo => {
+                let versions = body.get("versions")?.as_array()?;
+                versions
+                    .iter()
+                    .find(|v| v.get("num").and_then(|n| n.as_str()) == latest)
+                    .or_else(|| versions.first())?
+                    .get("license")?
+                    .as_str()
+                    .map(str::to_string)
+            }
+            Registry::PyPi => {
+                let info = body.get("info")?;
+                let field = |key: &str| {
+                    info.get(key)
+                        .and_then(|v| v.as_str())
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                };
+                // `license` is sometimes the whole license text
+                field("license_expression")
+                    .or_else(|| field("license").filter(|l| l.len() <= 50 && !l.contains('\n')))
+                    .map(str::to_string)
+                    .or_else(|| {
+                        info.get("classifiers")?
+                            .as_array()?
+                            .iter()
+                            .filter_map(|c| c.as_str()?.strip_prefix("License :: "))
+                            .find_map(|c| c.rsplit(" :: ").next().map(str::to_string))
+                    })
+            }
+        }
+    }
+
+    fn cache_key(self, package: &str) -> String {
+        format!("{}:{}", self.name(), package.to_lowercase())
+    }
+}
+
+/// The license in a `package.json`: `"MIT"`, `{ "type": "MIT" }` or the old
+/// `licenses` list
+pub fn npm_license(manifest: &serde_json::Value) -> Option<String> {
+    let license = match manifest.get("license") {
+        Some(license) => license,
+        None => manifest.get("licenses")?.as_array()?.first()?,
+    };
+    license
+        .as_str()
+        .or_else(|| license.get("type")?.as_str())
+        .map(str::to_string)
+}
+
+/// How far a dependency is behind its latest release
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Staleness {
+    UpToDate,
+    Patch,
+    /// Minor releases behind, on the same major
+    Minor(u64),
+    /// Major releases behind
+    Major(u64),
+    /// The manifest's version isn't one that compares, e.g. `*` or a git URL
+    Unknown,
+    /// The registry doesn't have the package, e.g. a private one
+    NotFound,
+    /// The registry couldn't be asked
+    Failed,
+}
+
+impl Staleness {
+    pub fn between(current: &Version, latest: &Version) -> Self {
+        let (current, latest) = (current.semver(), latest.semver());
+        if current >= latest {
+            Staleness::UpToDate
+        } else if latest.major > current.major {
+            Staleness::Major(latest.major - current.major)
+        } else if latest.minor > current.minor {
+            Staleness::Minor(latest.minor - current.minor)
+        } else {
+            Staleness::Patch
+        }
+    }
+
+    pub fn is_outdated(self) -> bool {
+        matches!(
+            self,
+            Staleness::Patch | Staleness::Minor(_) | Staleness::Major(_)
+        )
+    }
+
+    pub fn label(self) -> String {
+        let plural = |n: u64| if n == 1 { "" } else { "s" };
+        match self {
+            Staleness::UpToDate => "✓ Up to date".to_string(),
+            Staleness::Patch => "Patch behind".to_string(),
+            Staleness::Minor(n) => format!("{} minor{} behind", n, plural(n)),
+            Staleness::Major(n) => format!("⚠ {} major{} behind", n, plural(n)),
+            Staleness::Unknown => "? Not comparable".to_string(),
+            Staleness::NotFound => "? Not in registry".to_string(),
+            Staleness::Failed => "✗ Lookup failed".to_string(),
+        }
+    }
+
+    /// Most behind first: majors, minors, patches, then the rest
+    fn rank(self) -> (u8, std::cmp::Reverse<u64>) {
+        match self {
+            Staleness::Major(n) => (0, std::cmp::Reverse(n)),
+            Staleness::Minor(n) => (1, std::cmp::Reverse(n)),
+            Staleness::Patch => (2, std::cmp::Reverse(0)),
+            _ => (3, std::cmp::Reverse(0)),
+        }
+    }
+}
+
+/// Row of the `packages outdated` report
+#[derive(Tabled, Clone)]
+pub struct OutdatedEntry {
+    #[tabled(rename = "Package")]
+    pub name: String,
+    #[tabled(rename = "Current")]
+    pub current: String,
+    #[tabled(rename = "Latest")]
+    pub latest: String,
+    #[tabled(rename = "Staleness")]
+    pub label: String,
+    #[tabled(rename = "Registry")]
+    pub registry: String,
+    #[tabled(rename = "File")]
+    pub file_path: String,
+    #[tabled(skip)]
+    pub staleness: Staleness,
+}
+
+impl OutdatedEntry {
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "name": self.name,
+            "current": self.current,
+            "latest": (self.latest != "-").then_some(&self.latest),
+            "staleness": self.label.trim_start_matches(['✓', '⚠', '?', '✗', ' ']),
+            "outdated": self.staleness.is_outdated(),
+            "registry": self.registry,
+            "file": self.file_path,
+        })
+    }
+}
+
+/// What a registry knows about a package, as cached
+#[derive(Debug, Clone)]
+pub struct RegistryLookup {
+    /// The latest release, `None` when the registry doesn't have the package
+    pub latest: Option<String>,
+    /// The latest release's declared license
+    pub license: Option<String>,
+    checked: i64,
+}
+
+/// The registry lookups of one run
+#[derive(Debug, Default)]
+pub struct RegistryLookups {
+    lookups: HashMap<String, RegistryLookup>,
+}
+
+impl RegistryLookups {
+    /// `None` when the package couldn't be looked up
+    pub fn get(&self, registry: Registry, package: &str) -> Option<&RegistryLookup> {
+        self.lookups.get(&registry.cache_key(package))
+    }
+}
+
+pub fn registry_cache_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join(REGISTRY_CACHE_FILE))
+}
+
+/// `[packages] registry_cache_hours`, 24 by default; 0 asks every time
+pub fn registry_cache_max_age(config: &Config) -> Duration {
+    let hours = config
+        .get("packages.registry_cache_hours")
+        .and_then(|v| v.as_integer())
+        .map_or(DEFAULT_REGISTRY_CACHE_HOURS, |hours| hours.max(0) as u64);
+    Duration::from_secs(hours * 3600)
+}
+
+fn load_registry_cache() -> Result<HashMap<String, RegistryLookup>> {
+    let path = registry_cache_path()?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(source) => {
+            return Err(PackagesError::RegistryCache {
+                action: "read",
+                path,
+                source,
+            });
+        }
+    };
+
+    // A cache that doesn't parse is only a slower run
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap_or_default();
+    let text = |entry: &serde_json::Value, key: &str| {
+        entry.get(key).and_then(|v| v.as_str()).map(str::to_string)
+    };
+    Ok(json
+        .as_object()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|(key, entry)| {
+                    Some((
+                        key.clone(),
+                        RegistryLookup {
+                            latest: text(entry, "latest"),
+                            license: text(entry, "license"),
+                            // Entries from before licenses were cached are asked about again
+                            checked: match entry.get("license") {
+                                Some(_) => entry.get("checked")?.as_i64()?,
+                                None => 0,
+                            },
+                        },
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn save_registry_cache(cache: &HashMap<String, RegistryLookup>) -> Result<()> {
+    let path = registry_cache_path()?;
+    let cache_error = |action| {
+        let path = path.clone();
+        move |source| PackagesError::RegistryCache {
+            action,
+            path,
+            source,
+        }
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(cache_error("create the folder of"))?;
+    }
+
+    let json: serde_json::Map<String, serde_json::Value> = cache
+        .iter()
+        .map(|(key, lookup)| {
+            (
+                key.clone(),
+                json!({
+                    "latest": lookup.latest,
+                    "license": lookup.license,
+                    "checked": lookup.checked,
+                }),
+            )
+        })
+        .collect();
+    fs::write(&path, serde_json::to_string_pretty(&json)?).map_err(cache_error("write"))
+}
+
+/// Ask `registry` about `package`
+fn fetch_package(client: &Client, registry: Registry, package: &str) -> Result<RegistryLookup> {
+    let lookup_error = |source| PackagesError::Registry {
+        registry: registry.name(),
+        package: package.to_string(),
+        source,
+    };
+    let checked = unix_seconds(SystemTime::now());
+
+    let response = client
+        .get(registry.latest_url(package))
+        .send()
+        .map_err(lookup_error)?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(RegistryLookup {
+            latest: None,
+            license: None,
+            checked,
+        });
+    }
+    let body = response
+        .error_for_status()
+        .and_then(|response| response.text())
+        .map_err(lookup_error)?;
+    let body: serde_json::Value = serde_json::from_str(&body)?;
+    let latest = registry.latest_version(&body);
+    Ok(RegistryLookup {
+        license: registry.license(&body, latest.as_deref()),
+        latest,
+        checked,
+    })
+}
+
+/// Look up `packages` in their registries
+///
+/// Every package is asked about once, in parallel, and lookups younger than
+/// `max_age` come from the cache; packages the registry doesn't have are
+/// cached too, failed lookups aren't.
+pub fn lookup_packages(
+    packages: &[(Registry, &str)],
+    client: &Client,
+    max_age: Duration,
+    verbose: bool,
+) -> Result<RegistryLookups> {
+    let mut cache = load_registry_cache()?;
+    let now = unix_seconds(SystemTime::now());
+    let is_fresh = |lookup: &RegistryLookup| now - lookup.checked < max_age.as_secs() as i64;
+
+    let unique: HashSet<(Registry, &str)> = packages.iter().copied().collect();
+    let mut to_fetch: Vec<(Registry, &str)> = unique
+        .iter()
+        .filter(|(registry, name)| !cache.get(&registry.cache_key(name)).is_some_and(is_fresh))
+        .copied()
+        .collect();
+    to_fetch.sort();
+
+    if verbose {
+        println!(
+            "🌐 {} packages to look up, {} answered from the cache",
+            to_fetch.len(),
+            unique.len() - to_fetch.len()
+        );
+    }
+
+    let bar = count_bar(to_fetch.len() as u64);
+    let fetched: Vec<(Registry, &str, Result<RegistryLookup>)> = to_fetch
+        .into_par_iter()
+        .map(|(registry, name)| {
+            let result = fetch_package(client, registry, name);
+            bar.inc(1);
+            bar.set_message(name.to_string());
+            (registry, name, result)
+        })
+        .collect();
+    bar.finish_and_clear();
+
+    let mut failed = HashSet::new();
+    for (registry, name, result) in fetched {
+        match result {
+            Ok(lookup) => {
+                cache.insert(registry.cache_key(name), lookup);
+            }
+            Err(e) => {
+                if verbose {
+                    println!("  {} {}", "✗".red(), error_chain(&e));
+                }
+                failed.insert(registry.cache_key(name));
+            }
+        }
+    }
+    if let Err(e) = save_registry_cache(&cache) {
+        eprintln!(
+            "{} Couldn't save the registry cache: {}",
+            "⚠️".yellow(),
+            error_chain(&e)
+        );
+    }
+
+    // A stale answer isn't passed off as this run's
+    let keys: HashSet<String> = unique
+        .iter()
+        .map(|(registry, name)| registry.cache_key(name))
+        .filter(|key| !failed.contains(key))
+        .collect();
+    cache.retain(|key, _| keys.contains(key));
+    Ok(RegistryLookups { lookups: cache })
+}
+
+/// Compare each dependency with the latest release in its registry
+///
+/// Lookups go through `lookup_packages`, so each package is asked about once
+/// and recent answers come from the cache. Dependencies of ecosystems without
+/// a registry here are left out. Rows come most behind first.
+pub fn check_outdated(
+    packages: &[PackageEntry],
+    client: &Client,
+    max_age: Duration,
+    verbose: bool,
+) -> Result<Vec<OutdatedEntry>> {
+    let checked: Vec<(&PackageEntry, Registry)> = packages
+        .iter()
+        .filter_map(|p| Registry::for_package_type(&p.package_type).map(|r| (p, r)))
+        .collect();
+    let names: Vec<(Registry, &str)> = checked
+        .iter()
+        .map(|(p, registry)| (*registry, p.name.as_str()))
+        .collect();
+    let lookups = lookup_packages(&names, client, max_age, verbose)?;
+
+    let mut entries: Vec<OutdatedEntry> = checked
+        .into_iter()
+        .map(|(package, registry)| {
+            let lookup = lookups.get(registry, &package.name);
+            let latest = lookup.and_then(|lookup| lookup.latest.clone());
+            let staleness = match (lookup, &latest, Version::parse(&package.version)) {
+                (None, _, _) => Staleness::Failed,
+                (Some(_), None, _) => Staleness::NotFound,
+                (Some(_), Some(latest), Ok(current)) => match Version::parse(latest) {
+                    Ok(latest) => Staleness::between(&current, &latest),
+                    Err(_) => Staleness::Unknown,
+                },
+                (Some(_), Some(_), Err(_)) => Staleness::Unknown,
+            };
+            OutdatedEntry {
+                name: package.name.clone(),
+                current: package.version.clone(),
+                latest: latest.unwrap_or_else(|| "-".to_string()),
+                label: staleness.label(),
+                registry: registry.name().to_string(),
+                file_path: package.file_path.clone(),
+                staleness,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        a.staleness
+            .rank()
+            .cmp(&b.staleness.rank())
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+    Ok(entries)
+}
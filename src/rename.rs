@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::{
+    Config, DateSource, Exclusion, FileCategory, FileToOrganize, OrganizeError, civil_from_days,
+    unix_seconds,
+};
+
+type Result<T, E = OrganizeError> = std::result::Result<T, E>;
+
+/// One piece of a rename template
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Text(String),
+    /// `{date}`: the file's date as `2024-05-03` (UTC)
+    Date,
+    /// `{category}`: its category folder name, e.g. `Documents`
+    Category,
+    /// `{counter}` or `{counter:3}`: 1, 2, ... in the order files are
+    /// organized, zero-padded to the given width
+    Counter(usize),
+    /// `{original}`: its name without the extension
+    Original,
+}
+
+/// A file name pattern like `Invoice-{date}-{counter}.pdf`
+///
+/// Templates without an extension keep the file's own, so
+/// `{date}-{original}` turns `scan.pdf` into `2024-05-03-scan.pdf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameTemplate {
+    tokens: Vec<Token>,
+}
+
+impl RenameTemplate {
+    /// Parse a template, rejecting unknown `{tokens}` and unclosed braces
+    pub fn parse(template: &str) -> Result<Self> {
+        let invalid = |reason: String| OrganizeError::InvalidTemplate {
+            template: template.to_string(),
+            reason,
+        };
+
+        let mut tokens = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                tokens.push(Token::Text(rest[..start].to_string()));
+            }
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| invalid("unclosed '{'".to_string()))?;
+            let name = &rest[start + 1..start + end];
+            tokens.push(match name.split_once(':') {
+                Some(("counter", width)) => Token::Counter(
+                    width
+                        .parse()
+                        .map_err(|_| invalid(format!("bad counter width '{}'", width)))?,
+                ),
+                _ => match name {
+                    "date" => Token::Date,
+                    "category" => Token::Category,
+                    "counter" => Token::Counter(0),
+                    "original" => Token::Original,
+                    _ => {
+                        return Err(invalid(format!(
+                            "unknown token '{{{}}}' (use date, category, counter, original)",
+                            name
+                        )));
+                    }
+                },
+            });
+            rest = &rest[start + end + 1..];
+        }
+        if !rest.is_empty() {
+            tokens.push(Token::Text(rest.to_string()));
+        }
+
+        if tokens.is_empty() {
+            return Err(invalid("it's empty".to_string()));
+        }
+        if template.contains('/') {
+            return Err(invalid("names can't contain '/'".to_string()));
+        }
+        Ok(Self { tokens })
+    }
+
+    /// The new name of the file at `path`, the `counter`th file renamed by
+    /// this template
+    pub fn render(
+        &self,
+        path: &Path,
+        category: &FileCategory,
+        date: Option<(i64, u32, u32)>,
+        counter: usize,
+    ) -> String {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy())
+            .unwrap_or_default();
+        let mut name = String::new();
+        for token in &self.tokens {
+            match token {
+                Token::Text(text) => name.push_str(text),
+                Token::Date => {
+                    let (year, month, day) = date.unwrap_or((1970, 1, 1));
+                    name.push_str(&format!("{}-{:02}-{:02}", year, month, day));
+                }
+                Token::Category => name.push_str(category.folder_name()),
+                Token::Counter(width) => {
+                    name.push_str(&format!("{:0width$}", counter, width = *width))
+                }
+                Token::Original => name.push_str(&stem),
+            }
+        }
+
+        let has_extension = matches!(
+            self.tokens.last(),
+            Some(Token::Text(text)) if text.rsplit_once('.').is_some_and(|(_, ext)| !ext.is_empty())
+        );
+        if let Some(extension) = path.extension().filter(|_| !has_extension) {
+            name.push('.');
+            name.push_str(&extension.to_string_lossy());
+        }
+        name
+    }
+}
+
+/// A template and the files it renames
+#[derive(Debug, Clone)]
+pub struct RenameRule {
+    pub name: String,
+    /// Glob the file must match; bare globs match its name, e.g. `*invoice*`
+    pattern: Option<Exclusion>,
+    /// Category the file must be in
+    category: Option<String>,
+    pub template: RenameTemplate,
+}
+
+impl RenameRule {
+    fn matches(&self, path: &Path, category: &FileCategory) -> bool {
+        self.pattern.as_ref().is_none_or(|p| p.matches(path))
+            && self
+                .category
+                .as_ref()
+                .is_none_or(|c| c.eq_ignore_ascii_case(category.folder_name()))
+    }
+}
+
+/// How files are renamed as they're organized
+///
+/// Set with `--rename TEMPLATE` for every file, or per rule in the config;
+/// the first matching rule renames a file, files no rule matches keep their
+/// names:
+///
+/// ```toml
+/// [[organize.rename]]
+/// name = "invoices"
+/// match = "*invoice*.pdf"
+/// template = "Invoice-{date}-{counter:3}"
+///
+/// [[organize.rename]]
+/// category = "Images"
+/// template = "{date}-{original}"
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RenameRules {
+    pub rules: Vec<RenameRule>,
+    /// Which time `{date}` uses
+    pub date: DateSource,
+}
+
+impl RenameRules {
+    /// The `--rename` template for every file, else `[[organize.rename]]`;
+    /// `date` is `--date`, else `[organize] date`
+    pub fn load(template: Option<&str>, date: Option<&str>) -> Result<Self> {
+        let config = Config::load()?;
+        let date = match date.or(config.get_str("organize.date")) {
+            Some(name) => DateSource::parse(name)?,
+            None => DateSource::default(),
+        };
+
+        if let Some(template) = template {
+            return Ok(Self {
+                rules: vec![RenameRule {
+                    name: "--rename".to_string(),
+                    pattern: None,
+                    category: None,
+                    template: RenameTemplate::parse(template)?,
+                }],
+                date,
+            });
+        }
+
+        let mut rules = Vec::new();
+        for (index, entry) in config.get_table_array("organize.rename").iter().enumerate() {
+            let field = |key: &str| entry.get(key).and_then(|v| v.as_str());
+            let name = field("name")
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("#{}", index + 1));
+            let template = field("template").ok_or_else(|| {
+                OrganizeError::InvalidConfig(format!(
+                    "Rename rule {} is missing a 'template'",
+                    name
+                ))
+            })?;
+            let pattern = field("match")
+                .map(|glob| {
+                    Exclusion::new(glob, None)
+                        .map_err(|_| OrganizeError::InvalidExclude(glob.to_string()))
+                })
+                .transpose()?;
+
+            rules.push(RenameRule {
+                template: RenameTemplate::parse(template)?,
+                category: field("category").map(str::to_string),
+                pattern,
+                name,
+            });
+        }
+        Ok(Self { rules, date })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Give each file the name the first matching rule renders for it, and
+    /// files no rule matches their own
+    ///
+    /// Each rule counts its own files, in the order given.
+    pub fn apply(&self, files: &mut [FileToOrganize]) {
+        let mut counters: HashMap<usize, usize> = HashMap::new();
+        for file in files {
+            let Some(index) = self
+                .rules
+                .iter()
+                .position(|rule| rule.matches(&file.path, &file.category))
+            else {
+                file.target_name = file.file_name.clone();
+                continue;
+            };
+            let counter = counters.entry(index).or_insert(0);
+            *counter += 1;
+
+            let date = fs::metadata(&file.path)
+                .ok()
+                .and_then(|m| self.date.time(&m))
+                .map(|time| civil_from_days(unix_seconds(time).div_euclid(86_400)));
+            file.target_name =
+                self.rules[index]
+                    .template
+                    .render(&file.path, &file.category, date, *counter);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use tabled::Tabled;
+use thiserror::Error;
+
+use crate::{AliasesError, FunctionsError, Severity, get_all_aliases, get_all_functions};
+
+/// Errors from resolving a command name, all from reading the shell config
+#[derive(Debug, Error)]
+pub enum ResolveError {
+    #[error(transparent)]
+    Aliases(#[from] AliasesError),
+    #[error(transparent)]
+    Functions(#[from] FunctionsError),
+}
+
+impl ResolveError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            ResolveError::Aliases(e) => e.severity(),
+            ResolveError::Functions(e) => e.severity(),
+        }
+    }
+}
+
+/// Reserved words, checked right after aliases
+const SHELL_KEYWORDS: &[&str] = &[
+    "!", "[[", "]]", "{", "}", "case", "coproc", "do", "done", "elif", "else", "esac", "fi", "for",
+    "function", "if", "in", "select", "then", "time", "until", "while",
+];
+
+/// Builtins shared by bash and zsh, plus the common shell-specific ones
+const SHELL_BUILTINS: &[&str] = &[
+    ".",
+    ":",
+    "[",
+    "alias",
+    "autoload",
+    "bg",
+    "bind",
+    "bindkey",
+    "break",
+    "builtin",
+    "caller",
+    "cd",
+    "command",
+    "compgen",
+    "complete",
+    "continue",
+    "declare",
+    "dirs",
+    "disown",
+    "echo",
+    "emulate",
+    "enable",
+    "eval",
+    "exec",
+    "exit",
+    "export",
+    "false",
+    "fc",
+    "fg",
+    "getopts",
+    "hash",
+    "help",
+    "history",
+    "jobs",
+    "kill",
+    "let",
+    "local",
+    "logout",
+    "popd",
+    "print",
+    "printf",
+    "pushd",
+    "pwd",
+    "read",
+    "readonly",
+    "return",
+    "set",
+    "setopt",
+    "shift",
+    "shopt",
+    "source",
+    "suspend",
+    "test",
+    "times",
+    "trap",
+    "true",
+    "type",
+    "typeset",
+    "ulimit",
+    "umask",
+    "unalias",
+    "unfunction",
+    "unset",
+    "unsetopt",
+    "wait",
+    "whence",
+    "where",
+    "which",
+];
+
+/// One way a command name can resolve, in shell lookup order
+#[derive(Tabled, Clone)]
+pub struct ResolutionEntry {
+    #[tabled(rename = "#")]
+    pub order: usize,
+    #[tabled(rename = "Kind")]
+    pub kind: String,
+    #[tabled(rename = "Definition")]
+    pub definition: String,
+    #[tabled(rename = "Source")]
+    pub source: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+/// Report every way `name` resolves, in the order the shell looks it up:
+/// alias → keyword → function → builtin → PATH binaries
+///
+/// The first entry is what actually runs; everything after it is shadowed.
+pub fn resolve_command(name: &str) -> Result<Vec<ResolutionEntry>, ResolveError> {
+    let mut found: Vec<(&str, String, String)> = Vec::new();
+
+    for alias in get_all_aliases()?.into_iter().filter(|a| a.alias == name) {
+        found.push(("Alias", alias.command, alias.source));
+    }
+
+    if SHELL_KEYWORDS.contains(&name) {
+        found.push(("Keyword", name.to_string(), "shell".to_string()));
+    }
+
+    for function in get_all_functions()?.into_iter().filter(|f| f.name == name) {
+        let definition = if function.description.is_empty() {
+            function.usage
+        } else {
+            function.description
+        };
+        found.push(("Function", definition, function.source));
+    }
+
+    if SHELL_BUILTINS.contains(&name) {
+        found.push(("Builtin", name.to_string(), "shell".to_string()));
+    }
+
+    for path in find_in_path(name) {
+        let definition = match path.canonicalize() {
+            Ok(target) if target != path => format!("→ {}", target.display()),
+            _ => String::new(),
+        };
+        found.push(("Binary", definition, path.display().to_string()));
+    }
+
+    Ok(found
+        .into_iter()
+        .enumerate()
+        .map(|(i, (kind, definition, source))| ResolutionEntry {
+            order: i + 1,
+            kind: kind.to_string(),
+            definition,
+            source,
+            status: if i == 0 {
+                "✓ Runs".to_string()
+            } else {
+                "Shadowed".to_string()
+            },
+        })
+        .collect())
+}
+
+/// Find every executable named `name` on PATH, in PATH order
+pub fn find_in_path(name: &str) -> Vec<PathBuf> {
+    let Some(path_var) = env::var_os("PATH") else {
+        return Vec::new();
+    };
+
+    let mut seen = Vec::new();
+    for dir in env::split_paths(&path_var) {
+        let candidate = dir.join(name);
+        if is_executable(&candidate) && !seen.contains(&candidate) {
+            seen.push(candidate);
+        }
+    }
+    seen
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
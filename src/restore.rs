@@ -0,0 +1,482 @@
+use serde_json::json;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tabled::Tabled;
+use thiserror::Error;
+
+use crate::organizer::file_sha256;
+use crate::{ConfigError, Severity, data_dir, format_size};
+
+type Result<T, E = RestoreError> = std::result::Result<T, E>;
+
+/// Errors from clean manifests and restoring cleaned projects
+///
+/// A reinstall command that can't start or fails only affects its project and
+/// is recoverable; unreadable or unwritable manifests are fatal.
+#[derive(Debug, Error)]
+pub enum RestoreError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("Failed to {action}: {}", path.display())]
+    File {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to parse clean manifest: {}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Failed to run `{command}` in {}", dir.display())]
+    Spawn {
+        command: String,
+        dir: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("`{command}` exited with {status}")]
+    Failed { command: String, status: String },
+}
+
+impl RestoreError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            RestoreError::Spawn { .. } | RestoreError::Failed { .. } => Severity::Recoverable,
+            RestoreError::Config(e) => e.severity(),
+            _ => Severity::Fatal,
+        }
+    }
+
+    fn file(action: &'static str, path: &Path) -> impl FnOnce(io::Error) -> Self {
+        let path = path.to_path_buf();
+        move |source| RestoreError::File {
+            action,
+            path,
+            source,
+        }
+    }
+}
+
+/// Lockfiles and dependency lists hashed into the manifest, in the order
+/// they're looked for
+const LOCKFILES: &[&str] = &[
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+    "Cargo.lock",
+    "uv.lock",
+    "poetry.lock",
+    "Pipfile.lock",
+    "requirements.txt",
+];
+
+/// A lockfile as it was when its project was cleaned
+#[derive(Debug, Clone)]
+pub struct LockfileHash {
+    pub name: String,
+    pub sha256: String,
+}
+
+/// One cleaned directory in a manifest
+#[derive(Debug, Clone)]
+pub struct ManifestEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    /// Project the artifact belongs to (its parent directory)
+    pub project: PathBuf,
+    pub lockfiles: Vec<LockfileHash>,
+    /// Command that rebuilds the directory, run in `project`; `None` for
+    /// caches the tools recreate on their own
+    pub restore: Option<String>,
+}
+
+/// What a clean run removed, written before it touched anything
+#[derive(Debug, Clone)]
+pub struct CleanManifest {
+    pub path: PathBuf,
+    /// Unix time the run started
+    pub created: u64,
+    /// Clean action of the run (`delete`, `trash`, ...)
+    pub action: String,
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// Row of the restore report
+#[derive(Tabled, Clone)]
+pub struct RestoreEntry {
+    #[tabled(rename = "Project")]
+    pub project: String,
+    #[tabled(rename = "Artifact")]
+    pub artifact: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Lockfile")]
+    pub lockfile: String,
+    #[tabled(rename = "Restore")]
+    pub command: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+fn manifest_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("clean-manifests"))
+}
+
+/// The JS package manager a project uses, from its lockfile
+fn package_manager(project: &Path) -> &'static str {
+    if project.join("bun.lockb").is_file() {
+        "bun"
+    } else if project.join("pnpm-lock.yaml").is_file() {
+        "pnpm"
+    } else if project.join("yarn.lock").is_file() {
+        "yarn"
+    } else {
+        "npm"
+    }
+}
+
+/// Command that rebuilds the artifact directory `name` in `project`
+///
+/// Lockfiles are honored so the rebuilt dependencies match the cleaned ones.
+fn restore_command(name: &str, project: &Path) -> Option<String> {
+    let has = |file: &str| project.join(file).is_file();
+
+    match name {
+        "node_modules" => Some(
+            match package_manager(project) {
+                "bun" => "bun install --frozen-lockfile",
+                "pnpm" => "pnpm install --frozen-lockfile",
+                "yarn" => "yarn install --frozen-lockfile",
+                _ if has("package-lock.json") || has("npm-shrinkwrap.json") => "npm ci",
+                _ => "npm install",
+            }
+            .to_string(),
+        ),
+        "target" if has("Cargo.lock") => Some("cargo build --locked".to_string()),
+        "target" => Some("cargo build".to_string()),
+        ".venv" | "venv" => {
+            if name == ".venv" && has("uv.lock") {
+                Some("uv sync".to_string())
+            } else if has("poetry.lock") {
+                Some("poetry install".to_string())
+            } else if has("Pipfile.lock") {
+                Some("pipenv sync".to_string())
+            } else if has("requirements.txt") {
+                Some(format!(
+                    "python3 -m venv {0} && {0}/bin/pip install -r requirements.txt",
+                    name
+                ))
+            } else {
+                None
+            }
+        }
+        ".next" | ".nuxt" | "dist" | "build" if has("package.json") => {
+            Some(format!("{} run build", package_manager(project)))
+        }
+        "dist" | "build" if has("pyproject.toml") || has("setup.py") => {
+            Some("python3 -m build".to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Command that reinstalls the artifact directory `path` from its project's
+/// lockfile (`npm ci`, `pnpm install --frozen-lockfile`, `cargo build
+/// --locked`, `uv sync`, ...), so it comes back exactly as it was
+///
+/// `None` when there's no lockfile next to it, or it isn't a dependency
+/// directory (`node_modules`, `target`, a virtualenv).
+pub fn locked_restore_command(path: &Path) -> Option<String> {
+    let project = path.parent()?;
+    let name = path.file_name()?.to_str()?;
+    let has = |file: &str| project.join(file).is_file();
+
+    let locked = match name {
+        "node_modules" => [
+            "package-lock.json",
+            "npm-shrinkwrap.json",
+            "yarn.lock",
+            "pnpm-lock.yaml",
+            "bun.lockb",
+        ]
+        .iter()
+        .any(|lockfile| has(lockfile)),
+        "target" => has("Cargo.lock"),
+        ".venv" | "venv" => {
+            (name == ".venv" && has("uv.lock")) || has("poetry.lock") || has("Pipfile.lock")
+        }
+        _ => false,
+    };
+    locked.then(|| restore_command(name, project)).flatten()
+}
+
+/// Manifest entry for an artifact directory about to be cleaned
+pub fn manifest_entry(path: &Path, size: u64) -> ManifestEntry {
+    let project = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let lockfiles = LOCKFILES
+        .iter()
+        .filter_map(|lockfile| {
+            file_sha256(&project.join(lockfile))
+                .ok()
+                .map(|sha256| LockfileHash {
+                    name: lockfile.to_string(),
+                    sha256,
+                })
+        })
+        .collect();
+
+    ManifestEntry {
+        path: path.to_path_buf(),
+        size,
+        restore: restore_command(&name, &project),
+        project,
+        lockfiles,
+    }
+}
+
+/// Write the manifest for a clean run and return its path
+///
+/// Manifests go to `~/.local/share/shell-explorer/clean-manifests/`, one JSON
+/// file per run, and are written (and synced) before the first item is touched.
+pub fn write_clean_manifest(action: &str, entries: &[ManifestEntry]) -> Result<PathBuf> {
+    let dir = manifest_dir()?;
+    fs::create_dir_all(&dir).map_err(RestoreError::file("create directory", &dir))?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let path = dir.join(format!("{}-{}.json", now.as_millis(), std::process::id()));
+
+    let manifest = json!({
+        "created": now.as_secs(),
+        "action": action,
+        "entries": entries.iter().map(|entry| json!({
+            "path": entry.path,
+            "size": entry.size,
+            "project": entry.project,
+            "lockfiles": entry.lockfiles.iter().map(|lockfile| json!({
+                "name": lockfile.name,
+                "sha256": lockfile.sha256,
+            })).collect::<Vec<_>>(),
+            "restore": entry.restore,
+        })).collect::<Vec<_>>(),
+    });
+
+    let mut file = OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(&path)
+        .map_err(RestoreError::file("create manifest", &path))?;
+    serde_json::to_writer_pretty(&mut file, &manifest)
+        .map_err(io::Error::from)
+        .and_then(|_| file.sync_all())
+        .map_err(RestoreError::file("write manifest", &path))?;
+
+    Ok(path)
+}
+
+/// The manifest of the most recent clean run, if any
+pub fn latest_clean_manifest() -> Result<Option<CleanManifest>> {
+    let dir = manifest_dir()?;
+    if !dir.exists() {
+        return Ok(None);
+    }
+
+    let mut manifests: Vec<PathBuf> = fs::read_dir(&dir)
+        .map_err(RestoreError::file("read directory", &dir))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .collect();
+    // Names start with the run's start time in milliseconds
+    manifests.sort_by_key(|path| {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.split('-').next())
+            .and_then(|n| n.parse::<u128>().ok())
+            .unwrap_or(0)
+    });
+
+    match manifests.pop() {
+        Some(path) => read_clean_manifest(&path).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn read_clean_manifest(path: &Path) -> Result<CleanManifest> {
+    let content = fs::read_to_string(path).map_err(RestoreError::file("read manifest", path))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&content).map_err(|source| RestoreError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+    let str_of = |value: &serde_json::Value, key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+
+    let entries = json
+        .get("entries")
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .map(|entry| ManifestEntry {
+            path: PathBuf::from(str_of(entry, "path")),
+            size: entry.get("size").and_then(|s| s.as_u64()).unwrap_or(0),
+            project: PathBuf::from(str_of(entry, "project")),
+            lockfiles: entry
+                .get("lockfiles")
+                .and_then(|l| l.as_array())
+                .into_iter()
+                .flatten()
+                .map(|lockfile| LockfileHash {
+                    name: str_of(lockfile, "name"),
+                    sha256: str_of(lockfile, "sha256"),
+                })
+                .collect(),
+            restore: entry
+                .get("restore")
+                .and_then(|r| r.as_str())
+                .map(str::to_string),
+        })
+        .collect();
+
+    Ok(CleanManifest {
+        path: path.to_path_buf(),
+        created: json.get("created").and_then(|c| c.as_u64()).unwrap_or(0),
+        action: str_of(&json, "action"),
+        entries,
+    })
+}
+
+/// Whether the project's lockfiles still match the ones recorded at clean time
+fn lockfile_status(entry: &ManifestEntry) -> String {
+    if entry.lockfiles.is_empty() {
+        return "none".to_string();
+    }
+
+    let changed: Vec<&str> = entry
+        .lockfiles
+        .iter()
+        .filter(|lockfile| {
+            file_sha256(&entry.project.join(&lockfile.name))
+                .ok()
+                .as_deref()
+                != Some(lockfile.sha256.as_str())
+        })
+        .map(|lockfile| lockfile.name.as_str())
+        .collect();
+
+    if changed.is_empty() {
+        "unchanged".to_string()
+    } else {
+        format!("changed: {}", changed.join(", "))
+    }
+}
+
+fn report_entry(entry: &ManifestEntry, status: String) -> RestoreEntry {
+    RestoreEntry {
+        project: entry.project.display().to_string(),
+        artifact: entry
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        size: format_size(entry.size),
+        lockfile: lockfile_status(entry),
+        command: entry
+            .restore
+            .clone()
+            .unwrap_or_else(|| "(rebuilt automatically)".to_string()),
+        status,
+    }
+}
+
+/// What each cleaned directory needs to come back, and whether it already has
+pub fn restore_report(manifest: &CleanManifest) -> Vec<RestoreEntry> {
+    manifest
+        .entries
+        .iter()
+        .map(|entry| {
+            let status = if entry.path.exists() {
+                "Present"
+            } else if !entry.project.exists() {
+                "Project gone"
+            } else if entry.restore.is_some() {
+                "Missing"
+            } else {
+                "Not needed"
+            };
+            report_entry(entry, status.to_string())
+        })
+        .collect()
+}
+
+/// Re-run the reinstall command of every cleaned directory that's still missing
+///
+/// Commands run through `sh -c` in the project directory with the terminal
+/// attached, one project at a time. A failing command is reported and the
+/// others still run.
+pub fn run_restore(manifest: &CleanManifest, dry_run: bool) -> Result<Vec<RestoreEntry>> {
+    let mut results = Vec::new();
+
+    for entry in &manifest.entries {
+        let status = match &entry.restore {
+            _ if entry.path.exists() => "Already present".to_string(),
+            _ if !entry.project.exists() => "✗ Project gone".to_string(),
+            None => "Not needed".to_string(),
+            Some(_) if dry_run => "Would run".to_string(),
+            Some(command) => {
+                println!("\n$ cd {} && {}", entry.project.display(), command);
+                match run_in(command, &entry.project) {
+                    Ok(()) if entry.path.exists() => "✓ Restored".to_string(),
+                    Ok(()) => "✓ Ran, nothing to install".to_string(),
+                    Err(e) if e.severity() == Severity::Recoverable => format!("✗ {}", e),
+                    Err(e) => return Err(e),
+                }
+            }
+        };
+        results.push(report_entry(entry, status));
+    }
+
+    Ok(results)
+}
+
+fn run_in(command: &str, dir: &Path) -> Result<()> {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(dir)
+        .status()
+        .map_err(|source| RestoreError::Spawn {
+            command: command.to_string(),
+            dir: dir.to_path_buf(),
+            source,
+        })?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(RestoreError::Failed {
+            command: command.to_string(),
+            status: status.to_string(),
+        })
+    }
+}
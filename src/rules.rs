@@ -0,0 +1,1611 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::LazyLock;
+use tabled::Tabled;
+
+use crate::BookmarkCategory;
+
+/// Priority of rules from the config file unless they set their own; above
+/// every built-in rule, so user rules win
+pub const CONFIG_RULE_PRIORITY: i64 = 1000;
+
+/// Gap between the priorities of consecutive built-in rules, leaving room to
+/// slot config rules in between
+const BUILTIN_PRIORITY_STEP: i64 = 10;
+
+/// Which part of a bookmark a term looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Url,
+    Title,
+    /// URL and title joined with a space
+    Any,
+}
+
+/// A single substring check against the lowercased URL and/or title
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub field: Field,
+    pub pattern: Cow<'static, str>,
+    /// Match when the pattern is absent
+    pub negate: bool,
+}
+
+impl Term {
+    pub fn new(field: Field, pattern: impl Into<String>, negate: bool) -> Self {
+        Self {
+            field,
+            pattern: Cow::Owned(pattern.into().to_lowercase()),
+            negate,
+        }
+    }
+
+    fn matches(&self, text: &RuleInput) -> bool {
+        let haystack = match self.field {
+            Field::Url => &text.url,
+            Field::Title => &text.title,
+            Field::Any => &text.combined,
+        };
+        haystack.contains(self.pattern.as_ref()) != self.negate
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = match self.field {
+            Field::Url => "url",
+            Field::Title => "title",
+            Field::Any => "any",
+        };
+        let negate = if self.negate { "!" } else { "" };
+        write!(f, "{}{}:\"{}\"", negate, field, self.pattern)
+    }
+}
+
+const fn url(pattern: &'static str) -> Term {
+    Term {
+        field: Field::Url,
+        pattern: Cow::Borrowed(pattern),
+        negate: false,
+    }
+}
+
+const fn any(pattern: &'static str) -> Term {
+    Term {
+        field: Field::Any,
+        pattern: Cow::Borrowed(pattern),
+        negate: false,
+    }
+}
+
+const fn not(pattern: &'static str) -> Term {
+    Term {
+        field: Field::Any,
+        pattern: Cow::Borrowed(pattern),
+        negate: true,
+    }
+}
+
+/// Where a rule was defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSource {
+    Builtin,
+    Config,
+}
+
+impl fmt::Display for RuleSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleSource::Builtin => write!(f, "built-in"),
+            RuleSource::Config => write!(f, "config"),
+        }
+    }
+}
+
+/// A categorization rule: fires when every term of any one clause matches
+#[derive(Debug, Clone)]
+pub struct CategoryRule {
+    pub name: String,
+    pub priority: i64,
+    pub category: BookmarkCategory,
+    pub clauses: Vec<Vec<Term>>,
+    pub source: RuleSource,
+}
+
+impl CategoryRule {
+    /// The first clause that matches, if any
+    fn matching_clause(&self, text: &RuleInput) -> Option<&[Term]> {
+        self.clauses
+            .iter()
+            .find(|clause| clause.iter().all(|term| term.matches(text)))
+            .map(|clause| clause.as_slice())
+    }
+}
+
+/// A rule that matched a bookmark, and the clause that made it match
+#[derive(Debug, Clone, Copy)]
+pub struct RuleMatch<'a> {
+    pub rule: &'a CategoryRule,
+    pub clause: &'a [Term],
+}
+
+/// Lowercased bookmark text, prepared once per evaluation
+struct RuleInput {
+    url: String,
+    title: String,
+    combined: String,
+}
+
+impl RuleInput {
+    fn new(url: &str, title: &str) -> Self {
+        let url = url.to_lowercase();
+        let title = title.to_lowercase();
+        let combined = format!("{} {}", url, title);
+        Self {
+            url,
+            title,
+            combined,
+        }
+    }
+}
+
+/// Categorization rules, evaluated highest priority first
+///
+/// Rules with the same priority keep the order they were added in.
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    rules: Vec<CategoryRule>,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        BUILTIN.clone()
+    }
+}
+
+impl RuleSet {
+    /// Build a rule set from `rules` plus the built-ins
+    pub fn with_rules(rules: Vec<CategoryRule>) -> Self {
+        let mut all = rules;
+        all.extend(BUILTIN.rules.iter().cloned());
+        all.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        Self { rules: all }
+    }
+
+    /// Rules in evaluation order
+    pub fn rules(&self) -> &[CategoryRule] {
+        &self.rules
+    }
+
+    /// The rule that decides a bookmark's category
+    pub fn evaluate(&self, url: &str, title: &str) -> Option<RuleMatch<'_>> {
+        let text = RuleInput::new(url, title);
+        self.rules.iter().find_map(|rule| {
+            rule.matching_clause(&text)
+                .map(|clause| RuleMatch { rule, clause })
+        })
+    }
+
+    /// Every rule that matches a bookmark, in evaluation order; the first one fires
+    pub fn matches(&self, url: &str, title: &str) -> Vec<RuleMatch<'_>> {
+        let text = RuleInput::new(url, title);
+        self.rules
+            .iter()
+            .filter_map(|rule| {
+                rule.matching_clause(&text)
+                    .map(|clause| RuleMatch { rule, clause })
+            })
+            .collect()
+    }
+}
+
+/// Entry for the `explain` rule table
+#[derive(Tabled, Clone)]
+pub struct RuleMatchEntry {
+    #[tabled(rename = "Rule")]
+    pub rule: String,
+    #[tabled(rename = "Priority")]
+    pub priority: i64,
+    #[tabled(rename = "Source")]
+    pub source: String,
+    #[tabled(rename = "Category")]
+    pub category: String,
+    #[tabled(rename = "Matched")]
+    pub matched: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+/// Describe every rule matching a bookmark for `bookmarks explain`
+pub fn explain_rules(rules: &RuleSet, url: &str, title: &str) -> Vec<RuleMatchEntry> {
+    rules
+        .matches(url, title)
+        .into_iter()
+        .enumerate()
+        .map(|(i, m)| RuleMatchEntry {
+            rule: m.rule.name.clone(),
+            priority: m.rule.priority,
+            source: m.rule.source.to_string(),
+            category: m.rule.category.to_string(),
+            matched: m
+                .clause
+                .iter()
+                .map(|term| term.to_string())
+                .collect::<Vec<_>>()
+                .join(" & "),
+            status: if i == 0 {
+                "✓ Fired".to_string()
+            } else {
+                "Shadowed".to_string()
+            },
+        })
+        .collect()
+}
+
+/// The built-in rules, without any config rules
+pub fn builtin_rules() -> &'static RuleSet {
+    &BUILTIN
+}
+
+static BUILTIN: LazyLock<RuleSet> = LazyLock::new(|| {
+    let count = BUILTIN_RULES.len() as i64;
+    RuleSet {
+        rules: BUILTIN_RULES
+            .iter()
+            .enumerate()
+            .map(|(i, (name, category, clauses))| CategoryRule {
+                name: name.to_string(),
+                priority: (count - i as i64) * BUILTIN_PRIORITY_STEP,
+                category: category.clone(),
+                clauses: clauses.iter().map(|clause| clause.to_vec()).collect(),
+                source: RuleSource::Builtin,
+            })
+            .collect(),
+    }
+});
+
+/// A built-in rule: (name, category, clauses)
+type BuiltinRule = (&'static str, BookmarkCategory, &'static [&'static [Term]]);
+
+/// Built-in rules in evaluation order: specific categories before the
+/// catch-alls that would otherwise swallow them
+///
+/// A clause lists terms that must all match; a rule fires when any clause does.
+const BUILTIN_RULES: &[BuiltinRule] = &[
+    // AI/ML, checked first for specificity
+    (
+        "ai-rag",
+        BookmarkCategory::AIRAG,
+        &[
+            &[any("retrieval augmented")],
+            &[any("rag ")],
+            &[any(" rag")],
+            &[any("langchain"), any("retriev")],
+            &[any("llamaindex")],
+            &[any("llama-index")],
+            &[any("llama_index")],
+            &[any("haystack"), any("ai")],
+            &[any("document retrieval")],
+            &[any("semantic search"), any("llm")],
+            &[any("knowledge base"), any("ai")],
+            &[any("chunking"), any("llm")],
+            &[any("chunking"), any("embedding")],
+        ],
+    ),
+    (
+        "ai-context",
+        BookmarkCategory::AIContext,
+        &[
+            &[any("context window")],
+            &[any("context length")],
+            &[any("long context")],
+            &[any("memory"), any("llm")],
+            &[any("memory"), any("agent")],
+            &[any("memory"), any("ai")],
+            &[any("conversation memory")],
+            &[any("chat history")],
+            &[any("mem0")],
+            &[any("memgpt")],
+            &[any("context management")],
+            &[any("token limit")],
+            &[any("context compression")],
+            &[any("sliding window"), any("context")],
+        ],
+    ),
+    (
+        "ai-agents",
+        BookmarkCategory::AIAgents,
+        &[
+            &[any("ai agent")],
+            &[any("autonomous agent")],
+            &[any("langchain agent")],
+            &[any("autogpt")],
+            &[any("auto-gpt")],
+            &[any("babyagi")],
+            &[any("crewai")],
+            &[any("crew ai")],
+            &[any("autogen")],
+            &[any("agent framework")],
+            &[any("multi-agent")],
+            &[any("multiagent")],
+            &[any("tool use"), any("llm")],
+            &[any("function calling"), any("ai")],
+            &[any("agentic")],
+            &[any("agent orchestration")],
+            &[any("smolagent")],
+            &[any("phidata")],
+            &[any("swarm"), any("agent")],
+            &[url("mcp"), any("protocol")],
+            &[url("mcp"), any("context")],
+            &[any("model context protocol")],
+        ],
+    ),
+    (
+        "ai-prompt-engineering",
+        BookmarkCategory::AIPromptEngineering,
+        &[
+            &[any("prompt engineering")],
+            &[any("prompt template")],
+            &[any("prompting")],
+            &[any("chain of thought")],
+            &[any("cot prompting")],
+            &[any("few-shot")],
+            &[any("zero-shot")],
+            &[any("in-context learning")],
+            &[any("prompt injection")],
+            &[any("jailbreak"), any("llm")],
+            &[any("system prompt")],
+            &[any("prompt optimization")],
+            &[any("dspy")],
+            &[any("promptfoo")],
+            &[any("prompt testing")],
+        ],
+    ),
+    (
+        "ai-vector-db",
+        BookmarkCategory::AIVectorDB,
+        &[
+            &[url("pinecone.io")],
+            &[url("weaviate.io")],
+            &[url("milvus.io")],
+            &[url("qdrant")],
+            &[url("chroma"), any("vector")],
+            &[url("chromadb")],
+            &[any("vector database")],
+            &[any("vector db")],
+            &[any("vectorstore")],
+            &[any("vector store")],
+            &[any("pgvector")],
+            &[any("faiss"), any("vector")],
+            &[any("annoy"), any("vector")],
+            &[any("similarity search"), any("vector")],
+            &[url("lancedb")],
+            &[url("vespa.ai")],
+        ],
+    ),
+    (
+        "ai-embeddings",
+        BookmarkCategory::AIEmbeddings,
+        &[
+            &[any("embedding")],
+            &[any("sentence transformer")],
+            &[any("text-embedding")],
+            &[any("ada-002")],
+            &[any("openai embedding")],
+            &[any("cohere embed")],
+            &[any("word2vec")],
+            &[any("doc2vec")],
+            &[any("semantic similarity")],
+            &[url("huggingface"), any("embed")],
+            &[any("voyage ai")],
+            &[any("jina embedding")],
+        ],
+    ),
+    (
+        "ai-fine-tuning",
+        BookmarkCategory::AIFineTuning,
+        &[
+            &[any("fine-tun")],
+            &[any("finetun")],
+            &[any("lora")],
+            &[any("qlora")],
+            &[any("peft")],
+            &[any("adapter"), any("llm")],
+            &[any("instruction tuning")],
+            &[any("rlhf")],
+            &[any("dpo"), any("training")],
+            &[any("sft"), any("llm")],
+            &[any("sft"), any("training")],
+            &[any("training data"), any("llm")],
+            &[any("axolotl")],
+            &[any("unsloth")],
+            &[url("predibase")],
+            &[url("together.ai"), any("fine")],
+        ],
+    ),
+    (
+        "ai-llms",
+        BookmarkCategory::AILLMs,
+        &[
+            &[url("openai.com")],
+            &[url("anthropic.com")],
+            &[url("claude.ai")],
+            &[url("chat.openai.com")],
+            &[url("gemini.google")],
+            &[url("bard.google")],
+            &[url("mistral.ai")],
+            &[url("cohere.com")],
+            &[url("huggingface.co")],
+            &[url("ollama")],
+            &[url("replicate.com")],
+            &[url("together.ai")],
+            &[url("groq.com")],
+            &[url("anyscale.com")],
+            &[url("perplexity.ai")],
+            &[url("deepseek")],
+            &[url("meta.ai")],
+            &[any("llama"), any("model")],
+            &[any("llama"), any("meta")],
+            &[any("llama"), any("ai")],
+            &[any("gpt-4")],
+            &[any("gpt-3")],
+            &[any("chatgpt")],
+            &[any("claude"), any("anthropic")],
+            &[any("gemini"), any("google")],
+            &[any("mistral"), any("model")],
+            &[any("mixtral")],
+            &[any("phi-"), any("microsoft")],
+            &[any("falcon"), any("model")],
+            &[any("qwen")],
+            &[any("yi model")],
+            &[any("command-r")],
+            &[any("large language model")],
+            &[any("foundation model")],
+        ],
+    ),
+    (
+        "ai-mlops",
+        BookmarkCategory::AIMLOps,
+        &[
+            &[url("mlflow")],
+            &[url("wandb.ai")],
+            &[url("weights-and-biases")],
+            &[url("neptune.ai")],
+            &[url("comet.ml")],
+            &[url("dagshub")],
+            &[url("dvc.org")],
+            &[url("kubeflow")],
+            &[url("bentoml")],
+            &[url("seldon")],
+            &[url("ray.io")],
+            &[url("modal.com")],
+            &[any("mlops")],
+            &[any("ml ops")],
+            &[any("model deployment")],
+            &[any("model serving")],
+            &[any("model monitoring")],
+            &[any("experiment tracking")],
+            &[any("model registry")],
+            &[any("feature store")],
+            &[any("ml pipeline")],
+        ],
+    ),
+    (
+        "ai-computer-vision",
+        BookmarkCategory::AIComputerVision,
+        &[
+            &[any("computer vision")],
+            &[any("image recognition")],
+            &[any("object detection")],
+            &[any("image segmentation")],
+            &[any("yolo"), any("detection")],
+            &[any("opencv")],
+            &[any("stable diffusion")],
+            &[any("midjourney")],
+            &[any("dall-e")],
+            &[any("imagen")],
+            &[any("diffusion model")],
+            &[any("image generation")],
+            &[any("text-to-image")],
+            &[any("image-to-image")],
+            &[any("inpainting")],
+            &[any("controlnet")],
+            &[any("comfyui")],
+            &[url("civitai")],
+            &[url("stability.ai")],
+            &[url("runway")],
+            &[any("vision model")],
+            &[any("multimodal"), any("vision")],
+        ],
+    ),
+    (
+        "ai-nlp",
+        BookmarkCategory::AINLP,
+        &[
+            &[any("natural language processing")],
+            &[any("nlp ")],
+            &[any(" nlp")],
+            &[any("text classification")],
+            &[any("named entity")],
+            &[any("ner ")],
+            &[any("sentiment analysis")],
+            &[any("text mining")],
+            &[any("spacy")],
+            &[any("nltk")],
+            &[any("tokeniz")],
+            &[any("part-of-speech")],
+            &[any("dependency parsing")],
+            &[any("text extraction")],
+            &[any("information extraction")],
+        ],
+    ),
+    (
+        "ai-research",
+        BookmarkCategory::AIResearch,
+        &[
+            &[url("arxiv.org"), any("ai")],
+            &[url("arxiv.org"), any("machine learning")],
+            &[url("arxiv.org"), any("llm")],
+            &[url("arxiv.org"), any("neural")],
+            &[url("arxiv.org"), any("transformer")],
+            &[url("paperswithcode.com")],
+            &[url("semanticscholar.org"), any("ai")],
+            &[url("connectedpapers.com")],
+            &[any("research paper"), any("ai")],
+            &[any("ai research")],
+            &[any("ml research")],
+            &[url("deepmind.com")],
+            &[url("research.google"), any("ai")],
+            &[url("ai.meta.com")],
+            &[url("research.microsoft.com"), any("ai")],
+        ],
+    ),
+    (
+        "ai-general",
+        BookmarkCategory::AIGeneral,
+        &[
+            &[any("artificial intelligence")],
+            &[any("machine learning")],
+            &[any("deep learning")],
+            &[any("neural network")],
+            &[any("transformer"), any("ai")],
+            &[any("transformer"), any("model")],
+            &[any("tensorflow")],
+            &[any("pytorch")],
+            &[any("keras")],
+            &[any("scikit-learn")],
+            &[any("sklearn")],
+            &[url("kaggle.com")],
+            &[url("fast.ai")],
+            &[url("deeplearning.ai")],
+            &[any("ai tool")],
+            &[any("ml tool")],
+            &[any("generative ai")],
+            &[any("gen ai")],
+            &[any("langchain")],
+            &[any("llamaindex")],
+            &[any("inference"), any("model")],
+            &[any("inference"), any("ai")],
+        ],
+    ),
+    // Finance, checked before Development
+    (
+        "finance-crypto",
+        BookmarkCategory::FinanceCrypto,
+        &[
+            &[url("coinbase.com")],
+            &[url("binance.com")],
+            &[url("kraken.com")],
+            &[url("gemini.com")],
+            &[url("ftx.com")],
+            &[url("kucoin.com")],
+            &[url("huobi")],
+            &[url("okx.com")],
+            &[url("bybit.com")],
+            &[url("bitstamp")],
+            &[url("bitfinex")],
+            &[url("bitmex")],
+            &[url("coinmarketcap.com")],
+            &[url("coingecko.com")],
+            &[url("tradingview.com")],
+            &[url("dextools.io")],
+            &[url("etherscan.io")],
+            &[url("bscscan.com")],
+            &[url("polygonscan.com")],
+            &[url("uniswap")],
+            &[url("sushiswap")],
+            &[url("pancakeswap")],
+            &[url("metamask.io")],
+            &[url("opensea.io")],
+            &[url("rarible.com")],
+            &[url("looksrare")],
+            &[any("bitcoin")],
+            &[any("btc ")],
+            &[any("ethereum")],
+            &[any("eth ")],
+            &[any("crypto")],
+            &[any("blockchain")],
+            &[any("defi")],
+            &[any("nft")],
+            &[any("ico ")],
+            &[any("token sale")],
+            &[any("airdrop")],
+            &[any("staking")],
+            &[any("yield farming")],
+            &[any("liquidity pool")],
+            &[any("smart contract")],
+            &[any("wallet"), any("crypto")],
+            &[any("wallet"), any("bitcoin")],
+            &[any("wallet"), any("ethereum")],
+            &[any("exchange"), any("crypto")],
+            &[any("exchange"), any("coin")],
+            &[any("exchange"), any("token")],
+            &[any("altcoin")],
+            &[any("memecoin")],
+            &[any("chart pattern")],
+            &[any("candlestick")],
+            &[any("trading signal")],
+            &[any("technical analysis"), any("crypto")],
+            &[any("technical analysis"), any("coin")],
+            &[any("solana")],
+            &[any("cardano")],
+            &[any("polkadot")],
+            &[any("avalanche")],
+            &[any("polygon"), not("css")],
+            &[any("arbitrum")],
+            &[any("optimism")],
+            &[any("layer 2")],
+            &[any("web3")],
+            &[any("dapp")],
+            &[any("decentralized")],
+        ],
+    ),
+    (
+        "finance-trading",
+        BookmarkCategory::FinanceTrading,
+        &[
+            &[url("robinhood.com")],
+            &[url("etrade.com")],
+            &[url("tdameritrade.com")],
+            &[url("thinkorswim")],
+            &[url("interactivebrokers")],
+            &[url("stockcharts.com")],
+            &[url("finviz.com")],
+            &[url("yahoo.com/finance")],
+            &[url("finance.yahoo.com")],
+            &[url("marketwatch.com")],
+            &[url("seekingalpha.com")],
+            &[url("investopedia.com")],
+            &[url("morningstar.com")],
+            &[any("stock market")],
+            &[any("stock trading")],
+            &[any("forex")],
+            &[any("options trading")],
+            &[any("futures trading")],
+            &[any("dividend")],
+            &[any("portfolio"), any("invest")],
+            &[any("market analysis")],
+            &[any("bull market")],
+            &[any("bear market")],
+            &[any("earnings report")],
+            &[any("etf ")],
+            &[any("index fund")],
+        ],
+    ),
+    (
+        "finance-personal",
+        BookmarkCategory::FinancePersonal,
+        &[
+            &[url("mint.com")],
+            &[url("ynab.com")],
+            &[url("personalcapital.com")],
+            &[url("creditkarma.com")],
+            &[url("nerdwallet.com")],
+            &[url("bankrate.com")],
+            &[any("budget")],
+            &[any("saving money")],
+            &[any("retirement")],
+            &[any("401k")],
+            &[any("ira ")],
+            &[any("credit score")],
+            &[any("credit card"), not("api")],
+            &[any("mortgage")],
+            &[any("debt")],
+            &[any("tax return")],
+            &[any("net worth")],
+            &[any("financial planning")],
+            &[any("emergency fund")],
+        ],
+    ),
+    (
+        "finance-general",
+        BookmarkCategory::FinanceGeneral,
+        &[
+            &[url("bank")],
+            &[url("paypal.com")],
+            &[url("venmo.com")],
+            &[url("fidelity.com")],
+            &[url("schwab.com")],
+            &[url("vanguard.com")],
+            &[url("finance.")],
+            &[any("invest"), not("investigate")],
+            &[any("financial")],
+        ],
+    ),
+    (
+        "personal-development",
+        BookmarkCategory::PersonalDevelopment,
+        &[
+            &[any("habit")],
+            &[any("productivity"), not("developer"), not("tool")],
+            &[any("self improvement")],
+            &[any("self-improvement")],
+            &[any("personal growth")],
+            &[any("motivation")],
+            &[any("mindset")],
+            &[any("goal setting")],
+            &[any("time management"), not("project")],
+            &[any("life hack")],
+            &[any("morning routine")],
+            &[any("meditation")],
+            &[any("mindfulness")],
+            &[any("journaling")],
+            &[any("gratitude")],
+            &[any("stoicism")],
+            &[any("atomic habits")],
+            &[any("deep work")],
+            &[any("getting things done")],
+            &[any("gtd ")],
+            &[any("pomodoro")],
+            &[any("procrastination")],
+            &[any("discipline")],
+            &[any("self help")],
+            &[any("self-help")],
+            &[any("memory technique")],
+            &[any("speed reading")],
+            &[any("learning how to learn")],
+            &[any("career growth")],
+            &[any("public speaking")],
+            &[any("emotional intelligence")],
+        ],
+    ),
+    // General categories that would otherwise be caught by Development
+    (
+        "shopping",
+        BookmarkCategory::Shopping,
+        &[
+            &[url("amazon.")],
+            &[url("ebay.")],
+            &[url("etsy.com")],
+            &[url("aliexpress.com")],
+            &[url("walmart.com")],
+            &[url("target.com")],
+            &[url("bestbuy.com")],
+            &[url("newegg.com")],
+            &[url("/cart")],
+            &[url("/checkout")],
+            &[any("buy now")],
+            &[any("add to cart")],
+            &[any("shopping")],
+            &[any("discount code")],
+            &[any("coupon")],
+        ],
+    ),
+    (
+        "video",
+        BookmarkCategory::Video,
+        &[
+            &[url("youtube.com")],
+            &[url("youtu.be")],
+            &[url("vimeo.com")],
+            &[url("dailymotion.com")],
+            &[url("twitch.tv")],
+        ],
+    ),
+    (
+        "social",
+        BookmarkCategory::Social,
+        &[
+            &[url("facebook.com")],
+            &[url("twitter.com")],
+            &[url("x.com")],
+            &[url("instagram.com")],
+            &[url("linkedin.com")],
+            &[url("reddit.com")],
+            &[url("discord.com")],
+            &[url("slack.com")],
+            &[url("telegram.org")],
+            &[url("whatsapp.com")],
+            &[url("snapchat.com")],
+            &[url("tiktok.com")],
+            &[url("pinterest.com")],
+            &[url("tumblr.com")],
+            &[url("mastodon")],
+            &[url("threads.net")],
+            &[url("bluesky")],
+        ],
+    ),
+    (
+        "news",
+        BookmarkCategory::News,
+        &[
+            &[url("news.")],
+            &[url("bbc.com")],
+            &[url("cnn.com")],
+            &[url("nytimes.com")],
+            &[url("washingtonpost.com")],
+            &[url("theguardian.com")],
+            &[url("reuters.com")],
+            &[url("apnews.com")],
+            &[url("bloomberg.com")],
+            &[url("techcrunch.com")],
+            &[url("theverge.com")],
+            &[url("wired.com")],
+            &[url("arstechnica.com")],
+            &[url("engadget.com")],
+            &[url("hackernews")],
+            &[url("news.ycombinator.com")],
+            &[any("breaking news")],
+        ],
+    ),
+    (
+        "education",
+        BookmarkCategory::Education,
+        &[
+            &[url("coursera.org")],
+            &[url("udemy.com")],
+            &[url("edx.org")],
+            &[url("khanacademy.org")],
+            &[url("skillshare.com")],
+            &[url("pluralsight.com")],
+            &[url("lynda.com")],
+            &[url("codecademy.com")],
+            &[url("freecodecamp.org")],
+            &[url(".edu")],
+            &[url("learn.")],
+            &[any("online course")],
+            &[any("free course")],
+        ],
+    ),
+    // Development
+    (
+        "dev-react",
+        BookmarkCategory::DevReact,
+        &[
+            &[url("reactjs.org")],
+            &[url("react.dev")],
+            &[url("reactnative.dev")],
+            &[any("react"), any("component")],
+            &[any("react"), any("hook")],
+            &[any("react"), any("redux")],
+            &[any("react"), any("nextjs")],
+            &[any("react"), any("next.js")],
+            &[any("react"), any("gatsby")],
+            &[any("react"), any("jsx")],
+            &[any("react"), any("state management")],
+            &[any("react native")],
+            &[any("expo")],
+            &[url("nextjs.org")],
+            &[any("use effect")],
+            &[any("usestate")],
+            &[any("usememo")],
+            &[any("zustand")],
+            &[any("tanstack")],
+            &[any("react query")],
+        ],
+    ),
+    (
+        "dev-python",
+        BookmarkCategory::DevPython,
+        &[
+            &[url("python.org")],
+            &[url("pypi.org")],
+            &[any("python"), any("pip")],
+            &[any("python"), any("django")],
+            &[any("python"), any("flask")],
+            &[any("python"), any("fastapi")],
+            &[any("python"), any("pandas")],
+            &[any("python"), any("numpy")],
+            &[any("python"), any("jupyter")],
+            &[any("python"), any("anaconda")],
+            &[any("python"), any("virtualenv")],
+            &[any("python"), any("poetry")],
+            &[url("django")],
+            &[url("flask")],
+            &[url("fastapi")],
+            &[any("pydantic")],
+            &[any("pytest")],
+        ],
+    ),
+    (
+        "dev-rust",
+        BookmarkCategory::DevRust,
+        &[
+            &[url("rust-lang.org")],
+            &[url("crates.io")],
+            &[any("rust"), any("cargo")],
+            &[any("rust"), any("rustup")],
+            &[any("rust"), any("tokio")],
+            &[any("rust"), any("actix")],
+            &[any("rust"), any("wasm")],
+            &[any("rust"), any("serde")],
+            &[any("rustacean")],
+        ],
+    ),
+    (
+        "dev-java",
+        BookmarkCategory::DevJava,
+        &[
+            &[any("java"), any("spring")],
+            &[any("java"), any("maven")],
+            &[any("java"), any("gradle")],
+            &[any("java"), any("jvm")],
+            &[any("java"), any("hibernate")],
+            &[any("java"), any("junit")],
+            &[any("kotlin")],
+            &[url("spring.io")],
+            &[any("springboot")],
+            &[any("spring boot")],
+        ],
+    ),
+    (
+        "dev-typescript",
+        BookmarkCategory::DevTypeScript,
+        &[
+            &[url("typescriptlang.org")],
+            &[any("typescript"), any("type")],
+            &[any("typescript"), any("interface")],
+            &[any("typescript"), any("generic")],
+            &[any("typescript"), any("tsc")],
+            &[any(".ts ")],
+            &[any(".tsx")],
+        ],
+    ),
+    (
+        "dev-javascript",
+        BookmarkCategory::DevJavaScript,
+        &[
+            &[url("nodejs.org")],
+            &[url("npmjs.com")],
+            &[any("javascript")],
+            &[any("node.js")],
+            &[any("nodejs")],
+            &[any("npm ")],
+            &[any("yarn ")],
+            &[any("pnpm")],
+            &[any("deno")],
+            &[any("bun ")],
+            &[any("express.js")],
+            &[any("expressjs")],
+            &[any("es6")],
+            &[any("ecmascript")],
+            &[any("async await")],
+            &[any("promise")],
+        ],
+    ),
+    (
+        "dev-css",
+        BookmarkCategory::DevCSS,
+        &[
+            &[any("css")],
+            &[any("tailwind")],
+            &[any("sass")],
+            &[any("scss")],
+            &[any("less ")],
+            &[any("styled-component")],
+            &[any("bootstrap")],
+            &[any("material ui")],
+            &[any("chakra ui")],
+            &[any("flexbox")],
+            &[any("grid layout")],
+            &[any("animation")],
+            &[any("responsive design")],
+            &[url("csswizardry")],
+            &[url("css-tricks")],
+        ],
+    ),
+    (
+        "dev-kubernetes",
+        BookmarkCategory::DevKubernetes,
+        &[
+            &[url("kubernetes.io")],
+            &[any("kubernetes")],
+            &[any("k8s")],
+            &[any("kubectl")],
+            &[any("helm ")],
+            &[any("helm chart")],
+            &[any("minikube")],
+            &[any("kind cluster")],
+            &[any("pod ")],
+            &[any("deployment"), any("container")],
+            &[any("service mesh")],
+            &[any("istio")],
+            &[any("ingress")],
+        ],
+    ),
+    (
+        "dev-docker",
+        BookmarkCategory::DevDocker,
+        &[
+            &[url("docker.com")],
+            &[url("hub.docker.com")],
+            &[any("docker")],
+            &[any("dockerfile")],
+            &[any("container"), not("kubernetes")],
+            &[any("docker-compose")],
+            &[any("podman")],
+        ],
+    ),
+    (
+        "dev-postgres",
+        BookmarkCategory::DevPostgres,
+        &[
+            &[url("postgresql.org")],
+            &[any("postgresql")],
+            &[any("postgres")],
+            &[any("psql")],
+            &[any("pg_")],
+        ],
+    ),
+    (
+        "dev-database",
+        BookmarkCategory::DevDatabase,
+        &[
+            &[any("mysql")],
+            &[any("mongodb")],
+            &[any("redis")],
+            &[any("elasticsearch")],
+            &[any("sqlite")],
+            &[any("dynamodb")],
+            &[any("cassandra")],
+            &[any("sql ")],
+            &[any("nosql")],
+            &[any("database")],
+            &[any("query optimization")],
+            &[any("orm ")],
+            &[any("prisma")],
+            &[any("drizzle")],
+        ],
+    ),
+    (
+        "dev-aws",
+        BookmarkCategory::DevAWS,
+        &[
+            &[url("aws.amazon.com")],
+            &[any("aws ")],
+            &[any("amazon web services")],
+            &[any("lambda"), any("aws")],
+            &[any("ec2")],
+            &[any("s3 bucket")],
+            &[any("cloudformation")],
+            &[any("cloudwatch")],
+            &[any("dynamodb")],
+            &[any("sqs ")],
+            &[any("sns ")],
+            &[any("iam "), any("aws")],
+            &[any("cdk"), any("aws")],
+        ],
+    ),
+    (
+        "dev-serverless",
+        BookmarkCategory::DevServerless,
+        &[
+            &[any("serverless")],
+            &[any("lambda function")],
+            &[any("cloud function")],
+            &[any("azure function")],
+            &[any("vercel"), any("function")],
+            &[any("netlify function")],
+            &[any("edge function")],
+            &[any("faas")],
+            &[url("serverless.com")],
+        ],
+    ),
+    (
+        "dev-git",
+        BookmarkCategory::DevGit,
+        &[
+            &[url("github.com")],
+            &[url("gitlab.com")],
+            &[url("bitbucket.org")],
+            &[any("git ")],
+            &[any("gitflow")],
+            &[any("pull request")],
+            &[any("merge conflict")],
+            &[any("branch"), any("git")],
+            &[any("commit"), any("git")],
+            &[any("rebase")],
+            &[any("cherry-pick")],
+        ],
+    ),
+    (
+        "dev-devops",
+        BookmarkCategory::DevDevOps,
+        &[
+            &[any("devops")],
+            &[any("ci/cd")],
+            &[any("cicd")],
+            &[any("jenkins")],
+            &[any("github actions")],
+            &[any("gitlab ci")],
+            &[any("circleci")],
+            &[any("travis ci")],
+            &[any("argo")],
+            &[any("terraform")],
+            &[any("ansible")],
+            &[any("puppet")],
+            &[any("chef ")],
+            &[any("infrastructure as code")],
+            &[any("monitoring")],
+            &[any("prometheus")],
+            &[any("grafana")],
+            &[any("datadog")],
+            &[any("sonarqube")],
+        ],
+    ),
+    (
+        "dev-mobile",
+        BookmarkCategory::DevMobile,
+        &[
+            &[any("ios ")],
+            &[any("android ")],
+            &[any("swift")],
+            &[any("swiftui")],
+            &[any("xcode")],
+            &[any("flutter")],
+            &[any("dart ")],
+            &[any("mobile app")],
+            &[any("app store")],
+            &[any("play store")],
+            &[url("developer.apple.com")],
+            &[url("developer.android.com")],
+        ],
+    ),
+    (
+        "dev-webtech",
+        BookmarkCategory::DevWebTech,
+        &[
+            &[any("html")],
+            &[any("dom ")],
+            &[any("web component")],
+            &[any("pwa")],
+            &[any("progressive web")],
+            &[any("service worker")],
+            &[any("websocket")],
+            &[any("http")],
+            &[any("cors")],
+            &[any("oauth")],
+            &[any("jwt ")],
+            &[any("rest api")],
+            &[any("graphql")],
+            &[any("grpc")],
+            &[any("webpack")],
+            &[any("vite")],
+            &[any("esbuild")],
+            &[any("rollup")],
+            &[any("babel")],
+            &[url("vuejs.org")],
+            &[url("angular.io")],
+            &[url("svelte.dev")],
+            &[any("vue ")],
+            &[any("angular")],
+            &[any("svelte")],
+        ],
+    ),
+    (
+        "dev-api",
+        BookmarkCategory::DevAPI,
+        &[
+            &[any("api ")],
+            &[any("rest ")],
+            &[any("openapi")],
+            &[any("swagger")],
+            &[any("postman")],
+            &[any("insomnia")],
+            &[any("endpoint")],
+            &[any("webhook")],
+        ],
+    ),
+    (
+        "dev-general",
+        BookmarkCategory::DevGeneral,
+        &[
+            &[url("stackoverflow.com")],
+            &[url("stackexchange.com")],
+            &[url("developer.")],
+            &[url("docs.")],
+            &[url("vercel.com")],
+            &[url("netlify.com")],
+            &[url("heroku.com")],
+            &[url("cloud.google.com")],
+            &[url("azure.microsoft.com")],
+            &[url("codepen.io")],
+            &[url("codesandbox.io")],
+            &[url("replit.com")],
+            &[url("jsfiddle.net")],
+            &[url("medium.com"), any("programming")],
+            &[url("dev.to")],
+            &[url("hashnode.com")],
+            &[any("documentation")],
+            &[any("tutorial")],
+            &[any("programming")],
+            &[any("coding")],
+            &[any("developer")],
+        ],
+    ),
+    // Remaining general categories
+    (
+        "music",
+        BookmarkCategory::Music,
+        &[
+            &[url("spotify.com")],
+            &[url("soundcloud.com")],
+            &[url("music.apple.com")],
+            &[url("bandcamp.com")],
+            &[url("last.fm")],
+            &[url("pandora.com")],
+            &[url("deezer.com")],
+            &[url("tidal.com")],
+        ],
+    ),
+    (
+        "gaming",
+        BookmarkCategory::Gaming,
+        &[
+            &[url("steam")],
+            &[url("epicgames.com")],
+            &[url("gog.com")],
+            &[url("playstation.com")],
+            &[url("xbox.com")],
+            &[url("nintendo.com")],
+            &[url("ign.com")],
+            &[url("gamespot.com")],
+            &[url("kotaku.com")],
+            &[url("polygon.com")],
+        ],
+    ),
+    (
+        "entertainment",
+        BookmarkCategory::Entertainment,
+        &[
+            &[url("netflix.com")],
+            &[url("hulu.com")],
+            &[url("disneyplus.com")],
+            &[url("hbomax.com")],
+            &[url("primevideo.com")],
+            &[url("crunchyroll.com")],
+            &[url("imdb.com")],
+            &[url("rottentomatoes.com")],
+            &[url("letterboxd.com")],
+        ],
+    ),
+    (
+        "reference",
+        BookmarkCategory::Reference,
+        &[
+            &[url("wikipedia.org")],
+            &[url("wikimedia.org")],
+            &[url("wiktionary.org")],
+            &[url("britannica.com")],
+            &[url("merriam-webster.com")],
+            &[url("dictionary.com")],
+            &[url("thesaurus.com")],
+            &[url("translate.google")],
+            &[url("deepl.com")],
+            &[url("wolframalpha.com")],
+        ],
+    ),
+    (
+        "tools",
+        BookmarkCategory::Tools,
+        &[
+            &[url("notion.so")],
+            &[url("trello.com")],
+            &[url("asana.com")],
+            &[url("monday.com")],
+            &[url("figma.com")],
+            &[url("canva.com")],
+            &[url("drive.google.com")],
+            &[url("dropbox.com")],
+            &[url("box.com")],
+            &[url("1password.com")],
+            &[url("lastpass.com")],
+            &[url("bitwarden.com")],
+            &[url("grammarly.com")],
+            &[url("calendly.com")],
+            &[url("zoom.us")],
+            &[url("meet.google.com")],
+            &[url("teams.microsoft.com")],
+            &[any("converter")],
+            &[any("generator")],
+            &[any("calculator")],
+        ],
+    ),
+    (
+        "health",
+        BookmarkCategory::Health,
+        &[
+            &[url("webmd.com")],
+            &[url("mayoclinic.org")],
+            &[url("healthline.com")],
+            &[url("nih.gov")],
+            &[url("cdc.gov")],
+            &[url("who.int")],
+            &[url("myfitnesspal.com")],
+            &[url("strava.com")],
+            &[url("fitbit.com")],
+            &[any("health")],
+            &[any("fitness")],
+            &[any("workout")],
+            &[any("diet")],
+        ],
+    ),
+    (
+        "travel",
+        BookmarkCategory::Travel,
+        &[
+            &[url("booking.com")],
+            &[url("airbnb.com")],
+            &[url("expedia.com")],
+            &[url("kayak.com")],
+            &[url("tripadvisor.com")],
+            &[url("skyscanner.com")],
+            &[url("google.com/flights")],
+            &[url("google.com/maps")],
+            &[url("maps.google")],
+            &[url("hotels.com")],
+            &[url("vrbo.com")],
+            &[any("travel")],
+            &[any("flight")],
+            &[any("hotel")],
+            &[any("vacation")],
+        ],
+    ),
+    (
+        "food",
+        BookmarkCategory::Food,
+        &[
+            &[url("allrecipes.com")],
+            &[url("foodnetwork.com")],
+            &[url("epicurious.com")],
+            &[url("bonappetit.com")],
+            &[url("seriouseats.com")],
+            &[url("tasty.co")],
+            &[url("doordash.com")],
+            &[url("ubereats.com")],
+            &[url("grubhub.com")],
+            &[url("postmates.com")],
+            &[url("yelp.com")],
+            &[any("recipe")],
+            &[any("cooking")],
+            &[any("restaurant")],
+        ],
+    ),
+    (
+        "sports",
+        BookmarkCategory::Sports,
+        &[
+            &[url("espn.com")],
+            &[url("sports.")],
+            &[url("nfl.com")],
+            &[url("nba.com")],
+            &[url("mlb.com")],
+            &[url("nhl.com")],
+            &[url("fifa.com")],
+            &[url("uefa.com")],
+            &[url("olympics.com")],
+            &[any("score")],
+            &[any("league")],
+            &[any("team")],
+        ],
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CategoryRegistry, Config};
+
+    /// Name and category of the rule that fires for a bookmark
+    fn fired(rules: &RuleSet, url: &str, title: &str) -> Option<(String, BookmarkCategory)> {
+        rules
+            .evaluate(url, title)
+            .map(|m| (m.rule.name.clone(), m.rule.category.clone()))
+    }
+
+    fn rule(name: &str, priority: i64, category: BookmarkCategory, pattern: &str) -> CategoryRule {
+        CategoryRule {
+            name: name.to_string(),
+            priority,
+            category,
+            clauses: vec![vec![Term::new(Field::Any, pattern, false)]],
+            source: RuleSource::Config,
+        }
+    }
+
+    fn registry(toml: &str) -> CategoryRegistry {
+        CategoryRegistry::from_config(&Config::parse(toml).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn ai_rules_fire_before_development() {
+        assert_eq!(
+            fired(
+                builtin_rules(),
+                "https://python.langchain.com/docs/modules/data_connection/retrievers/",
+                "Retrievers | LangChain"
+            ),
+            Some(("ai-rag".to_string(), BookmarkCategory::AIRAG))
+        );
+    }
+
+    #[test]
+    fn finance_rules_fire_before_development() {
+        assert_eq!(
+            fired(
+                builtin_rules(),
+                "https://www.coinbase.com/developer-platform",
+                "Coinbase Developer Platform"
+            ),
+            Some((
+                "finance-crypto".to_string(),
+                BookmarkCategory::FinanceCrypto
+            ))
+        );
+    }
+
+    #[test]
+    fn general_rules_fire_before_development() {
+        assert_eq!(
+            fired(
+                builtin_rules(),
+                "https://www.youtube.com/watch?v=zF34dRivLOw",
+                "Rust programming tutorial"
+            ),
+            Some(("video".to_string(), BookmarkCategory::Video))
+        );
+    }
+
+    #[test]
+    fn development_rules_match() {
+        assert_eq!(
+            fired(
+                builtin_rules(),
+                "https://doc.rust-lang.org/book/",
+                "The Rust Programming Language"
+            ),
+            Some(("dev-rust".to_string(), BookmarkCategory::DevRust))
+        );
+        assert_eq!(
+            fired(builtin_rules(), "https://example.com/", "Example Domain"),
+            Some(("dev-webtech".to_string(), BookmarkCategory::DevWebTech))
+        );
+    }
+
+    #[test]
+    fn remaining_rules_match_when_nothing_earlier_does() {
+        assert_eq!(
+            fired(builtin_rules(), "open.spotify.com/album/1", "Blue Train"),
+            Some(("music".to_string(), BookmarkCategory::Music))
+        );
+        assert_eq!(fired(builtin_rules(), "", "Blue Train"), None);
+    }
+
+    #[test]
+    fn builtin_priorities_follow_table_order() {
+        let rules = builtin_rules().rules();
+        assert_eq!(rules.len(), BUILTIN_RULES.len());
+        assert!(rules.windows(2).all(|w| w[0].priority > w[1].priority));
+        assert!(rules.iter().all(|r| r.priority < CONFIG_RULE_PRIORITY));
+        assert_eq!(rules[0].name, BUILTIN_RULES[0].0);
+    }
+
+    #[test]
+    fn higher_priority_wins_and_ties_keep_insertion_order() {
+        let rules = RuleSet::with_rules(vec![
+            rule("first", 500, BookmarkCategory::Reference, "blue train"),
+            rule("second", 500, BookmarkCategory::Tools, "blue train"),
+            rule("highest", 600, BookmarkCategory::Music, "train"),
+        ]);
+        assert_eq!(
+            fired(&rules, "", "Blue Train"),
+            Some(("highest".to_string(), BookmarkCategory::Music))
+        );
+
+        let names: Vec<_> = rules
+            .matches("", "Blue Train")
+            .iter()
+            .map(|m| m.rule.name.as_str())
+            .collect();
+        assert_eq!(names, ["highest", "first", "second"]);
+    }
+
+    #[test]
+    fn config_rules_override_builtins() {
+        let registry = registry(
+            r#"
+[[bookmarks.rules]]
+name = "rust-reference"
+category = "Reference"
+url_patterns = ["rust-lang.org"]
+exclude = ["nightly"]
+"#,
+        );
+
+        let m = registry
+            .rules()
+            .evaluate(
+                "https://doc.rust-lang.org/book/",
+                "The Rust Programming Language",
+            )
+            .unwrap();
+        assert_eq!(m.rule.name, "rust-reference");
+        assert_eq!(m.rule.priority, CONFIG_RULE_PRIORITY);
+        assert_eq!(m.rule.source, RuleSource::Config);
+        assert_eq!(
+            registry.categorize(
+                "https://doc.rust-lang.org/book/",
+                "The Rust Programming Language"
+            ),
+            BookmarkCategory::Reference
+        );
+
+        // The excluded term stops the config rule, so the built-in fires again
+        assert_eq!(
+            fired(
+                registry.rules(),
+                "https://doc.rust-lang.org/nightly/std/",
+                "Rust standard library"
+            ),
+            Some(("dev-rust".to_string(), BookmarkCategory::DevRust))
+        );
+    }
+
+    #[test]
+    fn config_rules_can_rank_below_builtins() {
+        let registry = registry(
+            r#"
+[[bookmarks.rules]]
+name = "low"
+category = "Reference"
+priority = 1
+patterns = ["rust-lang.org"]
+"#,
+        );
+        assert_eq!(
+            fired(
+                registry.rules(),
+                "https://doc.rust-lang.org/book/",
+                "The Book"
+            ),
+            Some(("dev-rust".to_string(), BookmarkCategory::DevRust))
+        );
+        assert_eq!(registry.rules().rules().last().unwrap().name, "low");
+    }
+
+    #[test]
+    fn explain_rules_lists_fired_and_shadowed_rules() {
+        let entries = explain_rules(
+            builtin_rules(),
+            "https://doc.rust-lang.org/book/",
+            "The Rust Programming Language",
+        );
+        let rows: Vec<_> = entries
+            .iter()
+            .map(|e| (e.rule.as_str(), e.matched.as_str(), e.status.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("dev-rust", r#"url:"rust-lang.org""#, "✓ Fired"),
+                ("dev-webtech", r#"any:"http""#, "Shadowed"),
+                ("dev-general", r#"any:"programming""#, "Shadowed"),
+            ]
+        );
+
+        let first = &entries[0];
+        assert_eq!(first.source, "built-in");
+        assert_eq!(first.category, BookmarkCategory::DevRust.to_string());
+        assert!(first.priority > entries[1].priority);
+    }
+}
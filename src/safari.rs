@@ -0,0 +1,273 @@
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tabled::Tabled;
+use thiserror::Error;
+
+use crate::{
+    Bookmark, CategoryRegistry, Config, ConfigError, Severity, expand_home, extract_domain,
+    home_dir, truncate_string,
+};
+
+type Result<T, E = SafariError> = std::result::Result<T, E>;
+
+/// Errors from reading Safari's Reading List
+///
+/// All of them are fatal: without the plist there is nothing to triage.
+#[derive(Debug, Error)]
+pub enum SafariError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("{0}")]
+    InvalidConfig(String),
+    #[error("Safari bookmarks file not found at: {}", .0.display())]
+    NotFound(PathBuf),
+    #[error(
+        "Safari bookmarks at {} can't be read.\n\
+         Give your terminal Full Disk Access in System Settings > Privacy & Security.",
+        .0.display()
+    )]
+    PermissionDenied(PathBuf),
+    #[error("Failed to read {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to parse Safari bookmarks: {}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: plist::Error,
+    },
+}
+
+impl SafariError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            SafariError::Config(e) => e.severity(),
+            _ => Severity::Fatal,
+        }
+    }
+}
+
+/// Safari bookmarks file location on macOS
+const SAFARI_BOOKMARKS_PATH: &str = "Library/Safari/Bookmarks.plist";
+
+/// Title of the Reading List folder inside Bookmarks.plist
+const READING_LIST_TITLE: &str = "com.apple.ReadingList";
+
+/// Folder path given to Reading List items, so exports and digests can tell them apart
+pub const SAFARI_READING_LIST_FOLDER: &str = "Safari Reading List";
+
+/// Unread items added longer ago than this many days count as old
+pub const DEFAULT_OLD_AFTER_DAYS: u64 = 30;
+
+/// Seconds between the Chrome epoch (1601-01-01) and the Unix epoch
+const CHROME_EPOCH_OFFSET_SECS: u64 = 11_644_473_600;
+
+/// An item from Safari's Reading List, categorized like a Chrome bookmark
+pub struct SafariReadingItem {
+    pub bookmark: Bookmark,
+    /// When the item was added, in seconds since the Unix epoch
+    pub added: Option<u64>,
+    /// When the item was last opened; unread items have none
+    pub last_viewed: Option<u64>,
+    /// Article excerpt Safari saved with the item
+    pub preview: Option<String>,
+}
+
+impl SafariReadingItem {
+    pub fn is_unread(&self) -> bool {
+        self.last_viewed.is_none()
+    }
+
+    /// Days since the item was added, if Safari recorded when
+    pub fn age_days(&self) -> Option<u64> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.added.map(|added| now.saturating_sub(added) / 86400)
+    }
+
+    /// Unread and added more than `old_after_days` days ago
+    pub fn is_old(&self, old_after_days: u64) -> bool {
+        self.is_unread() && self.age_days().is_some_and(|age| age > old_after_days)
+    }
+}
+
+/// Entry for the Safari Reading List triage table
+#[derive(Tabled)]
+pub struct SafariReadingEntry {
+    #[tabled(rename = "Title")]
+    pub title: String,
+    #[tabled(rename = "Domain")]
+    pub domain: String,
+    #[tabled(rename = "Category")]
+    pub category: String,
+    #[tabled(rename = "Added")]
+    pub added: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+/// Path to Safari's Bookmarks.plist, honoring `bookmarks.safari_file` from the config
+pub fn get_safari_bookmarks_path() -> Result<PathBuf> {
+    let path = match Config::load()?.get_str("bookmarks.safari_file") {
+        Some(file) => expand_home(file)?,
+        None => home_dir()?.join(SAFARI_BOOKMARKS_PATH),
+    };
+
+    if !path.exists() {
+        return Err(SafariError::NotFound(path));
+    }
+
+    Ok(path)
+}
+
+/// Days after which unread items count as old: `[bookmarks.safari] old_after_days` or 30
+pub fn old_after_days(config: &Config) -> Result<u64> {
+    match config.get("bookmarks.safari.old_after_days") {
+        None => Ok(DEFAULT_OLD_AFTER_DAYS),
+        Some(value) => value
+            .as_integer()
+            .and_then(|days| u64::try_from(days).ok())
+            .ok_or_else(|| {
+                SafariError::InvalidConfig(format!(
+                    "bookmarks.safari.old_after_days must be a whole number of days, got {}",
+                    value
+                ))
+            }),
+    }
+}
+
+/// Parse the Reading List section of Safari's Bookmarks.plist, newest first
+///
+/// Every item is run through `registry` so it gets the same category a Chrome
+/// bookmark with that URL and title would. A plist without a Reading List
+/// (never used on this Mac) yields no items.
+pub fn parse_safari_reading_list(
+    path: &Path,
+    registry: &CategoryRegistry,
+) -> Result<Vec<SafariReadingItem>> {
+    let file = File::open(path).map_err(|source| match source.kind() {
+        io::ErrorKind::PermissionDenied => SafariError::PermissionDenied(path.to_path_buf()),
+        _ => SafariError::Read {
+            path: path.to_path_buf(),
+            source,
+        },
+    })?;
+    let root = plist::Value::from_reader(file).map_err(|source| SafariError::Parse {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let reading_list = root
+        .as_dictionary()
+        .and_then(|d| d.get("Children"))
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|child| child.as_dictionary())
+        .find(|child| child.get("Title").and_then(|t| t.as_string()) == Some(READING_LIST_TITLE));
+
+    let mut items: Vec<SafariReadingItem> = reading_list
+        .and_then(|list| list.get("Children"))
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|child| child.as_dictionary())
+        .filter_map(|entry| parse_reading_entry(entry, registry))
+        .collect();
+
+    items.sort_by_key(|item| std::cmp::Reverse(item.added.unwrap_or(0)));
+    Ok(items)
+}
+
+/// Turn one `WebBookmarkTypeLeaf` dictionary into a reading item
+fn parse_reading_entry(
+    entry: &plist::Dictionary,
+    registry: &CategoryRegistry,
+) -> Option<SafariReadingItem> {
+    let url = entry
+        .get("URLString")
+        .and_then(|u| u.as_string())?
+        .to_string();
+    let title = entry
+        .get("URIDictionary")
+        .and_then(|d| d.as_dictionary())
+        .and_then(|d| d.get("title"))
+        .and_then(|t| t.as_string())
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or(&url)
+        .to_string();
+    let id = entry
+        .get("WebBookmarkUUID")
+        .and_then(|u| u.as_string())
+        .unwrap_or("")
+        .to_string();
+
+    let details = entry.get("ReadingList").and_then(|r| r.as_dictionary());
+    let date = |key: &str| {
+        details
+            .and_then(|d| d.get(key))
+            .and_then(|v| v.as_date())
+            .and_then(|date| SystemTime::from(date).duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+    };
+    let added = date("DateAdded");
+    let last_viewed = date("DateLastViewed");
+    let preview = details
+        .and_then(|d| d.get("PreviewText"))
+        .and_then(|p| p.as_string())
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+
+    let (category, confidence) = registry.categorize_scored(&url, &title);
+
+    Some(SafariReadingItem {
+        bookmark: Bookmark {
+            id,
+            name: title,
+            url,
+            // Chrome's format, microseconds since 1601, so Chrome-side helpers sort it too
+            date_added: added
+                .map(|secs| ((secs + CHROME_EPOCH_OFFSET_SECS) * 1_000_000).to_string()),
+            folder_path: SAFARI_READING_LIST_FOLDER.to_string(),
+            category,
+            confidence,
+        },
+        added,
+        last_viewed,
+        preview,
+    })
+}
+
+/// Table rows for the Reading List triage report
+pub fn safari_reading_entries(
+    items: &[SafariReadingItem],
+    old_after_days: u64,
+) -> Vec<SafariReadingEntry> {
+    items
+        .iter()
+        .map(|item| SafariReadingEntry {
+            title: truncate_string(&item.bookmark.name, 45),
+            domain: extract_domain(&item.bookmark.url),
+            category: item.bookmark.category.to_string(),
+            added: match item.age_days() {
+                Some(0) => "today".to_string(),
+                Some(days) => format!("{}d ago", days),
+                None => "unknown".to_string(),
+            },
+            status: if item.is_old(old_after_days) {
+                "Unread (old)".to_string()
+            } else if item.is_unread() {
+                "Unread".to_string()
+            } else {
+                "Read".to_string()
+            },
+        })
+        .collect()
+}
@@ -0,0 +1,392 @@
+use plist::{Dictionary, Value};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tabled::Tabled;
+use thiserror::Error;
+
+use crate::{ConfigError, Severity, data_dir, home_dir};
+
+type Result<T, E = ScheduleError> = std::result::Result<T, E>;
+
+/// Errors from scheduling organize runs with launchd
+///
+/// All of them are fatal: a schedule that can't be written or loaded isn't
+/// worth keeping half-installed.
+#[derive(Debug, Error)]
+pub enum ScheduleError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("Scheduling uses launchd, which only macOS has")]
+    Unsupported,
+    #[error("Failed to {action}: {}", path.display())]
+    File {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to {action} launch agent {}", path.display())]
+    Plist {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: plist::Error,
+    },
+    #[error("Failed to run launchctl")]
+    Launchctl(#[source] io::Error),
+    #[error("launchctl {command} failed: {message}")]
+    LaunchctlFailed {
+        command: &'static str,
+        message: String,
+    },
+}
+
+impl ScheduleError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            ScheduleError::Config(e) => e.severity(),
+            _ => Severity::Fatal,
+        }
+    }
+}
+
+/// Start of the organize launch agents' labels; the rest names the folder
+pub const SCHEDULE_LABEL_PREFIX: &str = "com.shell-explorer.organize.";
+
+/// How often a scheduled run happens
+///
+/// Runs are calendar-based, so one missed while the Mac slept happens on wake.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScheduleInterval {
+    /// On the hour
+    Hourly,
+    /// At 09:00
+    #[default]
+    Daily,
+    /// Mondays at 09:00
+    Weekly,
+}
+
+impl ScheduleInterval {
+    pub fn label(self) -> &'static str {
+        match self {
+            ScheduleInterval::Hourly => "hourly, on the hour",
+            ScheduleInterval::Daily => "daily at 09:00",
+            ScheduleInterval::Weekly => "Mondays at 09:00",
+        }
+    }
+
+    /// launchd's `StartCalendarInterval` for it
+    fn calendar(self) -> Dictionary {
+        let mut calendar = Dictionary::new();
+        calendar.insert("Minute".to_string(), Value::from(0));
+        if self != ScheduleInterval::Hourly {
+            calendar.insert("Hour".to_string(), Value::from(9));
+        }
+        if self == ScheduleInterval::Weekly {
+            calendar.insert("Weekday".to_string(), Value::from(1));
+        }
+        calendar
+    }
+
+    fn from_calendar(calendar: &Dictionary) -> Self {
+        match (calendar.get("Weekday"), calendar.get("Hour")) {
+            (Some(_), _) => ScheduleInterval::Weekly,
+            (None, Some(_)) => ScheduleInterval::Daily,
+            (None, None) => ScheduleInterval::Hourly,
+        }
+    }
+}
+
+/// A launch agent running the organizer on its own
+#[derive(Debug, Clone)]
+pub struct OrganizeSchedule {
+    /// e.g. `com.shell-explorer.organize.downloads-3fa2c1`
+    pub label: String,
+    /// Folders it organizes
+    pub paths: Vec<PathBuf>,
+    pub interval: ScheduleInterval,
+    /// The program and its arguments
+    pub arguments: Vec<String>,
+    /// Where the runs' output goes
+    pub log: PathBuf,
+}
+
+/// Row of the schedules table
+#[derive(Tabled, Clone)]
+pub struct ScheduleEntry {
+    #[tabled(rename = "Folders")]
+    pub folders: String,
+    #[tabled(rename = "Runs")]
+    pub runs: String,
+    #[tabled(rename = "Loaded")]
+    pub loaded: String,
+    #[tabled(rename = "Last Exit")]
+    pub last_exit: String,
+    #[tabled(rename = "Log")]
+    pub log: String,
+}
+
+impl OrganizeSchedule {
+    /// A schedule for `paths`; `arguments` is the command line that
+    /// organizes them without asking anything
+    pub fn new(
+        paths: Vec<PathBuf>,
+        interval: ScheduleInterval,
+        arguments: Vec<String>,
+    ) -> Result<Self> {
+        let label = schedule_label(&paths);
+        Ok(Self {
+            log: data_dir()?.join("logs").join(format!("{}.log", label)),
+            label,
+            paths,
+            interval,
+            arguments,
+        })
+    }
+
+    /// Its launch agent under `~/Library/LaunchAgents`
+    pub fn agent_path(&self) -> Result<PathBuf> {
+        Ok(launch_agents_dir()?.join(format!("{}.plist", self.label)))
+    }
+
+    fn to_plist(&self) -> Value {
+        let mut agent = Dictionary::new();
+        agent.insert("Label".to_string(), Value::from(self.label.clone()));
+        agent.insert(
+            "ProgramArguments".to_string(),
+            Value::Array(self.arguments.iter().cloned().map(Value::from).collect()),
+        );
+        agent.insert(
+            "StartCalendarInterval".to_string(),
+            Value::Dictionary(self.interval.calendar()),
+        );
+        let log = Value::from(self.log.to_string_lossy().into_owned());
+        agent.insert("StandardOutPath".to_string(), log.clone());
+        agent.insert("StandardErrorPath".to_string(), log);
+        agent.insert("ProcessType".to_string(), Value::from("Background"));
+        Value::Dictionary(agent)
+    }
+
+    /// Read back a launch agent written by `install_schedule`
+    fn from_plist(agent: &Dictionary) -> Option<Self> {
+        let label = agent.get("Label")?.as_string()?.to_string();
+        let arguments: Vec<String> = agent
+            .get("ProgramArguments")?
+            .as_array()?
+            .iter()
+            .filter_map(|v| v.as_string().map(str::to_string))
+            .collect();
+        let paths = arguments
+            .windows(2)
+            .filter(|pair| pair[0] == "--path")
+            .map(|pair| PathBuf::from(&pair[1]))
+            .collect();
+
+        Some(Self {
+            interval: agent
+                .get("StartCalendarInterval")
+                .and_then(|v| v.as_dictionary())
+                .map(ScheduleInterval::from_calendar)
+                .unwrap_or_default(),
+            log: PathBuf::from(agent.get("StandardOutPath")?.as_string()?),
+            label,
+            paths,
+            arguments,
+        })
+    }
+
+    /// The launch agent's XML, as `install_schedule` writes it
+    pub fn plist_xml(&self) -> Result<String> {
+        let mut xml = Vec::new();
+        self.to_plist()
+            .to_writer_xml(&mut xml)
+            .map_err(|source| ScheduleError::Plist {
+                action: "write",
+                path: PathBuf::from(&self.label),
+                source,
+            })?;
+        Ok(String::from_utf8_lossy(&xml).into_owned())
+    }
+}
+
+/// Label for a schedule of `paths`: the first folder's name and a short hash
+/// of them all, so two `Downloads` folders get their own agents
+fn schedule_label(paths: &[PathBuf]) -> String {
+    let name: String = paths
+        .first()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let mut hasher = Sha256::new();
+    for path in paths {
+        hasher.update(path.as_os_str().as_encoded_bytes());
+        hasher.update(b"\n");
+    }
+    let short: String = hasher.finalize()[..3]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("{}{}-{}", SCHEDULE_LABEL_PREFIX, name, short)
+}
+
+fn launch_agents_dir() -> Result<PathBuf> {
+    Ok(home_dir()?.join("Library/LaunchAgents"))
+}
+
+/// Write the schedule's launch agent and load it, replacing one already
+/// installed for the same folders; returns the agent's path
+pub fn install_schedule(schedule: &OrganizeSchedule) -> Result<PathBuf> {
+    if !cfg!(target_os = "macos") {
+        return Err(ScheduleError::Unsupported);
+    }
+
+    let path = schedule.agent_path()?;
+    for dir in [path.parent(), schedule.log.parent()].into_iter().flatten() {
+        fs::create_dir_all(dir).map_err(|source| ScheduleError::File {
+            action: "create directory",
+            path: dir.to_path_buf(),
+            source,
+        })?;
+    }
+    if path.exists() {
+        // A stale agent may not be loaded; writing the new one is what matters
+        launchctl("unload", &path).ok();
+    }
+
+    schedule
+        .to_plist()
+        .to_file_xml(&path)
+        .map_err(|source| ScheduleError::Plist {
+            action: "write",
+            path: path.clone(),
+            source,
+        })?;
+    launchctl("load", &path)?;
+    Ok(path)
+}
+
+/// The organize launch agents installed under `~/Library/LaunchAgents`
+pub fn find_schedules() -> Result<Vec<OrganizeSchedule>> {
+    let dir = launch_agents_dir()?;
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut schedules = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !name.starts_with(SCHEDULE_LABEL_PREFIX) || !name.ends_with(".plist") {
+            continue;
+        }
+        let agent = Value::from_file(&path).map_err(|source| ScheduleError::Plist {
+            action: "read",
+            path: path.clone(),
+            source,
+        })?;
+        if let Some(schedule) = agent.as_dictionary().and_then(OrganizeSchedule::from_plist) {
+            schedules.push(schedule);
+        }
+    }
+
+    schedules.sort_by(|a, b| a.label.cmp(&b.label));
+    Ok(schedules)
+}
+
+/// Unload a schedule's launch agent and delete it; its log is kept
+pub fn remove_schedule(schedule: &OrganizeSchedule) -> Result<()> {
+    if !cfg!(target_os = "macos") {
+        return Err(ScheduleError::Unsupported);
+    }
+
+    let path = schedule.agent_path()?;
+    // An agent that was never loaded (or was unloaded by hand) is still removed
+    launchctl("unload", &path).ok();
+    fs::remove_file(&path).map_err(|source| ScheduleError::File {
+        action: "remove",
+        path,
+        source,
+    })
+}
+
+/// Table rows for `schedules`, with whether launchd has each loaded and how
+/// its last run exited
+pub fn schedule_entries(schedules: &[OrganizeSchedule]) -> Vec<ScheduleEntry> {
+    schedules
+        .iter()
+        .map(|schedule| {
+            let status = agent_status(&schedule.label);
+            ScheduleEntry {
+                folders: schedule
+                    .paths
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                runs: schedule.interval.label().to_string(),
+                loaded: if status.is_some() {
+                    "✓ Yes"
+                } else {
+                    "✗ No"
+                }
+                .to_string(),
+                last_exit: match status.flatten() {
+                    Some(0) => "✓ 0".to_string(),
+                    Some(code) => format!("✗ {}", code),
+                    None => "-".to_string(),
+                },
+                log: schedule.log.display().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// `launchctl load -w` or `unload -w` an agent
+fn launchctl(command: &'static str, agent: &Path) -> Result<()> {
+    let output = Command::new("launchctl")
+        .args([command, "-w"])
+        .arg(agent)
+        .output()
+        .map_err(ScheduleError::Launchctl)?;
+
+    // launchctl load reports some failures on stderr with a zero exit
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !output.status.success() || !stderr.is_empty() {
+        return Err(ScheduleError::LaunchctlFailed {
+            command,
+            message: if stderr.is_empty() {
+                output.status.to_string()
+            } else {
+                stderr
+            },
+        });
+    }
+    Ok(())
+}
+
+/// `None` when launchd doesn't have the agent loaded, else its last exit
+/// status, if it has run
+fn agent_status(label: &str) -> Option<Option<i64>> {
+    let output = Command::new("launchctl")
+        .args(["list", label])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    // `"LastExitStatus" = 0;`
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(stdout.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("\"LastExitStatus\" = ")?
+            .trim_end_matches(';')
+            .parse()
+            .ok()
+    }))
+}
@@ -0,0 +1,465 @@
+use colored::Colorize;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::{Config, ConfigError, Severity, data_dir, home_dir};
+
+type Result<T, E = SetupError> = std::result::Result<T, E>;
+
+/// Marker left in the data directory when the first-run offer is declined
+const SETUP_SKIPPED: &str = "setup-skipped";
+
+/// Chromium-based browser data directories, relative to the home directory
+const BROWSER_DIRS: &[(&str, &str)] = &[
+    // macOS
+    ("Google Chrome", "Library/Application Support/Google/Chrome"),
+    ("Chromium", "Library/Application Support/Chromium"),
+    (
+        "Brave",
+        "Library/Application Support/BraveSoftware/Brave-Browser",
+    ),
+    (
+        "Microsoft Edge",
+        "Library/Application Support/Microsoft Edge",
+    ),
+    ("Vivaldi", "Library/Application Support/Vivaldi"),
+    // Linux
+    ("Google Chrome", ".config/google-chrome"),
+    ("Chromium", ".config/chromium"),
+    ("Brave", ".config/BraveSoftware/Brave-Browser"),
+    ("Microsoft Edge", ".config/microsoft-edge"),
+    ("Vivaldi", ".config/vivaldi"),
+];
+
+/// Usual homes for source checkouts, relative to the home directory
+const CODE_ROOTS: &[&str] = &[
+    "code",
+    "Code",
+    "src",
+    "projects",
+    "Projects",
+    "dev",
+    "Developer",
+    "workspace",
+    "repos",
+    "git",
+];
+
+/// Errors from the setup wizard
+///
+/// All fatal: the wizard either writes a complete config or nothing.
+#[derive(Debug, Error)]
+pub enum SetupError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error("Failed to {action}: {}", path.display())]
+    File {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to read answer")]
+    Input(#[source] io::Error),
+}
+
+impl SetupError {
+    pub fn severity(&self) -> Severity {
+        match self {
+            SetupError::Config(e) => e.severity(),
+            _ => Severity::Fatal,
+        }
+    }
+
+    fn file(action: &'static str, path: impl AsRef<Path>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.as_ref().to_path_buf();
+        move |source| SetupError::File {
+            action,
+            path,
+            source,
+        }
+    }
+}
+
+/// A browser profile with a bookmarks file
+#[derive(Debug, Clone)]
+pub struct BrowserProfile {
+    pub browser: &'static str,
+    /// Profile name as shown in the browser, or its directory name
+    pub name: String,
+    pub bookmarks: PathBuf,
+}
+
+/// What the wizard found on this machine
+#[derive(Debug, Clone, Default)]
+pub struct SetupDetection {
+    pub profiles: Vec<BrowserProfile>,
+    /// Shell name from `$SHELL` (zsh, bash, ...)
+    pub shell: Option<String>,
+    pub downloads: Option<PathBuf>,
+    pub code_roots: Vec<PathBuf>,
+}
+
+/// The answers the config file is written from
+#[derive(Debug, Clone, Default)]
+pub struct SetupChoices {
+    pub bookmarks: Option<PathBuf>,
+    pub shell: Option<String>,
+    pub organize_path: Option<PathBuf>,
+    pub code_path: Option<PathBuf>,
+}
+
+/// Find bookmarks files of every Chromium-based browser profile under `home`
+pub fn detect_browser_profiles(home: &Path) -> Vec<BrowserProfile> {
+    let mut profiles = Vec::new();
+
+    for (browser, dir) in BROWSER_DIRS {
+        let dir = home.join(dir);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        // Display names live in "Local State" under profile.info_cache.<dir>.name
+        let local_state: serde_json::Value = fs::read_to_string(dir.join("Local State"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        let mut found: Vec<BrowserProfile> = entries
+            .flatten()
+            .filter(|entry| entry.path().join("Bookmarks").is_file())
+            .map(|entry| {
+                let dir_name = entry.file_name().to_string_lossy().to_string();
+                let name = local_state["profile"]["info_cache"][&dir_name]["name"]
+                    .as_str()
+                    .map(|name| format!("{} ({})", name, dir_name))
+                    .unwrap_or(dir_name);
+                BrowserProfile {
+                    browser,
+                    name,
+                    bookmarks: entry.path().join("Bookmarks"),
+                }
+            })
+            .collect();
+        found.sort_by_key(|p| (!p.name.starts_with("Default"), p.name.clone()));
+        profiles.extend(found);
+    }
+
+    profiles
+}
+
+/// Look at the machine for browsers, shell and common folders
+pub fn detect_setup(home: &Path) -> SetupDetection {
+    let shell = std::env::var("SHELL").ok().and_then(|shell| {
+        Path::new(&shell)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    });
+
+    let downloads = Some(home.join("Downloads")).filter(|path| path.is_dir());
+
+    // Case-insensitive filesystems report both "code" and "Code"
+    let mut code_roots: Vec<PathBuf> = Vec::new();
+    for root in CODE_ROOTS {
+        let path = home.join(root);
+        if let Ok(canonical) = path.canonicalize()
+            && path.is_dir()
+            && !code_roots
+                .iter()
+                .any(|known| known.canonicalize().ok().as_ref() == Some(&canonical))
+        {
+            code_roots.push(path);
+        }
+    }
+
+    SetupDetection {
+        profiles: detect_browser_profiles(home),
+        shell,
+        downloads,
+        code_roots,
+    }
+}
+
+/// TOML string literal for a value
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+/// Path with the home directory written as `~`
+fn display_path(path: &Path, home: &Path) -> String {
+    match path.strip_prefix(home) {
+        Ok(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Ok(rest) => format!("~/{}", rest.display()),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+/// Render the initial config file, with each mode's defaults written out
+/// (commented where the built-in default already applies)
+pub fn render_config(choices: &SetupChoices, home: &Path) -> String {
+    let path_line = |key: &str, path: &Option<PathBuf>, example: &str| match path {
+        Some(path) => format!("{} = {}\n", key, toml_string(&display_path(path, home))),
+        None => format!("# {} = {}\n", key, toml_string(example)),
+    };
+
+    let mut config = String::new();
+    config.push_str("# shell-explorer configuration\n");
+    config.push_str(&format!(
+        "# Generated by the setup wizard on {}; rerun with --setup to regenerate.\n\n",
+        crate::chrono_lite_now()
+    ));
+
+    config.push_str("[aliases]\n");
+    config.push_str("# Shell used to list the aliases of a live session\n");
+    match &choices.shell {
+        Some(shell) => config.push_str(&format!("shell = {}\n\n", toml_string(shell))),
+        None => config.push_str("# shell = \"zsh\"\n\n"),
+    }
+
+    config.push_str("[packages]\n");
+    config.push_str("# Default --path for package searches\n");
+    config.push_str(&path_line("path", &choices.code_path, "~/code"));
+    config.push('\n');
+
+    config.push_str("[clean]\n");
+    config.push_str("# Default --path for build-artifact cleanup\n");
+    config.push_str(&path_line("path", &choices.code_path, "~/code"));
+    config.push_str("# Directories to clean: names or ecosystems (node, rust, python, all)\n");
+    config.push_str("# patterns = [\"node\", \"rust\", \"python\"]\n");
+    config.push_str(
+        "# Directory names or path globs never searched (a root's .cleanerignore adds more)\n",
+    );
+    config.push_str("# exclude = [\"vendor\", \"~/work/critical-app/**\"]\n");
+    config.push_str(
+        "# Faster scans: look at most this deep, and stop in a project once an artifact is found\n",
+    );
+    config.push_str("# max_depth = 5\n");
+    config.push_str("# prune = true\n");
+    config.push_str("# Don't search directories git ignores (artifacts are still found)\n");
+    config.push_str("# gitignore = true\n");
+    config.push_str("# Follow symlinked directories and clean what symlinked artifacts point to\n");
+    config.push_str("# follow_symlinks = true\n");
+    config.push_str("# Projects with uncommitted changes or a commit this recent are skipped (--include-active)\n");
+    config.push_str("# active_days = 14\n");
+    config.push_str("# A project containing this file keeps its artifacts (greyed out in -i)\n");
+    config.push_str("# keep_marker = \".keep-node-modules\"\n");
+    config.push_str(
+        "# Remove directories cleaned projects are left with that are empty (--remove-empty)\n",
+    );
+    config.push_str("# remove_empty = true\n");
+    config.push_str("# Only clean dependencies a lockfile can reinstall exactly (--safe)\n");
+    config.push_str("# safe = true\n");
+    config.push_str(
+        "# Rename directories aside and delete them on background threads (--background-delete)\n",
+    );
+    config.push_str("# background_delete = true\n");
+    config.push_str("# Named sets for --profile, e.g. a weekly sweep over several roots:\n");
+    config.push_str("# [clean.profiles.weekly]\n");
+    config.push_str("# roots = [\"~/code\", \"~/work\"]\n");
+    config.push_str("# patterns = [\"all\"]\n");
+    config.push('\n');
+
+    config.push_str("[organize]\n");
+    config.push_str("# Default --path for file organization\n");
+    config.push_str(&path_line("path", &choices.organize_path, "~/Downloads"));
+    config.push_str("# Organize iCloud-synced folders without the typed confirmation\n");
+    config.push_str("allow_icloud = false\n");
+    config.push_str(
+        "# Folders to file into: type (Images/), date (2024/05/), type-date, size or source (--by)\n",
+    );
+    config.push_str("# by = \"type\"\n");
+    config.push_str("# Time date schemes go by: modified or created (--date)\n");
+    config.push_str("# date = \"modified\"\n");
+    config.push_str(
+        "# When a destination exists: rename, skip, overwrite or prompt (--on-conflict)\n",
+    );
+    config.push_str("# on_conflict = \"skip\"\n");
+    config.push_str("# Categorize by content (magic bytes) even when the extension is known\n");
+    config.push_str("# prefer_content = true\n");
+    config.push_str(
+        "# Leave a symlink at the original path (original) or the destination (destination) (--link)\n",
+    );
+    config.push_str("# link = \"original\"\n\n");
+
+    config.push_str("[bookmarks]\n");
+    config.push_str("# Bookmarks file of the browser profile to work on\n");
+    config.push_str(&path_line(
+        "file",
+        &choices.bookmarks,
+        "~/Library/Application Support/Google/Chrome/Default/Bookmarks",
+    ));
+    config.push_str("# Suggestions scoring below this confidence go to \"Needs Review\"\n");
+    config.push_str("review_threshold = 0.6\n");
+    config.push_str("# File uncategorized non-English bookmarks under Language/<language>\n");
+    config.push_str("language_folders = false\n\n");
+
+    config.push_str("[http]\n");
+    config.push_str("# Request timeout in seconds for link checks and fetches\n");
+    config.push_str("timeout = 10\n\n");
+
+    config.push_str("[notify]\n");
+    config.push_str("# Notify when long operations finish, even without --notify\n");
+    config.push_str("# desktop = true\n");
+    config.push_str("# min_seconds = 30\n");
+    config.push_str("# webhook = \"https://hooks.slack.com/services/...\"\n");
+
+    config
+}
+
+/// Print a question and read one line; an empty answer or EOF gives `default`
+fn ask(question: &str, default: &str) -> Result<String> {
+    if default.is_empty() {
+        print!("{} {}: ", "❓".cyan(), question);
+    } else {
+        print!("{} {} [{}]: ", "❓".cyan(), question, default.dimmed());
+    }
+    io::stdout().flush().map_err(SetupError::Input)?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .map_err(SetupError::Input)?;
+
+    let answer = answer.trim();
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
+
+/// Ask for a folder, offering `default`; "-" leaves the setting out
+fn ask_path(question: &str, default: Option<&Path>, home: &Path) -> Result<Option<PathBuf>> {
+    let default = default
+        .map(|path| display_path(path, home))
+        .unwrap_or_default();
+    let answer = ask(&format!("{} (- to skip)", question), &default)?;
+
+    if answer.is_empty() || answer == "-" {
+        return Ok(None);
+    }
+    Ok(Some(crate::expand_home(&answer)?))
+}
+
+/// Whether to offer the wizard before this run: no config yet, the offer
+/// wasn't declined before, and someone is at the terminal to answer
+pub fn should_offer_setup() -> Result<bool> {
+    Ok(!Config::path()?.exists()
+        && !data_dir()?.join(SETUP_SKIPPED).exists()
+        && io::stdin().is_terminal()
+        && io::stdout().is_terminal())
+}
+
+/// Remember that the first-run offer was declined
+pub fn skip_setup() -> Result<()> {
+    let dir = data_dir()?;
+    fs::create_dir_all(&dir).map_err(SetupError::file("create directory", &dir))?;
+    let marker = dir.join(SETUP_SKIPPED);
+    fs::write(&marker, "").map_err(SetupError::file("write", &marker))
+}
+
+/// Ask yes/no, defaulting to yes
+pub fn ask_yes(question: &str) -> Result<bool> {
+    let answer = ask(&format!("{} [Y/n]", question), "")?;
+    Ok(answer.is_empty() || answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+}
+
+/// Run the interactive wizard and write the config file
+///
+/// An existing config is kept as `config.toml.bak`. Returns the path written,
+/// or `None` when the user backed out.
+pub fn run_setup_wizard() -> Result<Option<PathBuf>> {
+    let home = home_dir()?;
+    let detected = detect_setup(&home);
+
+    println!("{}", "🧙 Setup Wizard".bold().cyan());
+    println!("{}", "─".repeat(50).dimmed());
+    println!("Press Enter to accept the suggestion in brackets.\n");
+
+    // Browser profile
+    let bookmarks = if detected.profiles.is_empty() {
+        println!("{} No Chromium-based browser profiles found", "🔖".cyan());
+        None
+    } else {
+        println!("{} Browser profiles with bookmarks:", "🔖".cyan());
+        for (i, profile) in detected.profiles.iter().enumerate() {
+            println!(
+                "  {}. {} — {}",
+                (i + 1).to_string().yellow(),
+                profile.browser.green(),
+                profile.name
+            );
+        }
+        let choice = loop {
+            let answer = ask("Profile to use (0 to skip)", "1")?;
+            match answer.parse::<usize>() {
+                Ok(n) if n <= detected.profiles.len() => break n,
+                _ => println!(
+                    "{}",
+                    format!("Enter a number from 0 to {}", detected.profiles.len()).yellow()
+                ),
+            }
+        };
+        choice
+            .checked_sub(1)
+            .map(|i| detected.profiles[i].bookmarks.clone())
+    };
+    println!();
+
+    // Shell
+    let shell = ask(
+        "Shell for live aliases",
+        detected.shell.as_deref().unwrap_or("bash"),
+    )?;
+    println!();
+
+    // Folders
+    let organize_path = ask_path(
+        "Folder to organize by default",
+        detected.downloads.as_deref(),
+        &home,
+    )?;
+    if detected.code_roots.len() > 1 {
+        let roots: Vec<String> = detected
+            .code_roots
+            .iter()
+            .map(|root| display_path(root, &home))
+            .collect();
+        println!("{} Code folders found: {}", "💡".yellow(), roots.join(", "));
+    }
+    let code_path = ask_path(
+        "Code root for packages and clean",
+        detected.code_roots.first().map(|p| p.as_path()),
+        &home,
+    )?;
+    println!();
+
+    let choices = SetupChoices {
+        bookmarks,
+        shell: Some(shell),
+        organize_path,
+        code_path,
+    };
+    let config = render_config(&choices, &home);
+
+    println!("{}", config.dimmed());
+    let path = Config::path()?;
+    if !ask_yes(&format!("Write this to {}?", display_path(&path, &home)))? {
+        println!("{}", "Setup cancelled; nothing was written.".yellow());
+        return Ok(None);
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(SetupError::file("create directory", parent))?;
+    }
+    if path.exists() {
+        let backup = path.with_extension("toml.bak");
+        fs::copy(&path, &backup).map_err(SetupError::file("back up", &path))?;
+        println!(
+            "{} Previous config kept as {}",
+            "💾".cyan(),
+            display_path(&backup, &home).cyan()
+        );
+    }
+    fs::write(&path, config).map_err(SetupError::file("write", &path))?;
+
+    Ok(Some(path))
+}
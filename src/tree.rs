@@ -0,0 +1,48 @@
+/// A node in a printable tree (dependency chains, bookmark folders, ...)
+#[derive(Debug, Clone, Default)]
+pub struct TreeNode {
+    pub label: String,
+    pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Sort children (recursively) by label
+    pub fn sort(&mut self) {
+        self.children.sort_by(|a, b| a.label.cmp(&b.label));
+        for child in &mut self.children {
+            child.sort();
+        }
+    }
+}
+
+/// Render a tree with box-drawing guides, one node per line
+pub fn render_tree(root: &TreeNode) -> String {
+    let mut output = format!("{}\n", root.label);
+    render_children(&root.children, "", &mut output);
+    output
+}
+
+fn render_children(children: &[TreeNode], prefix: &str, output: &mut String) {
+    for (i, child) in children.iter().enumerate() {
+        let is_last = i == children.len() - 1;
+        let (branch, continuation) = if is_last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+
+        output.push_str(&format!("{}{}{}\n", prefix, branch, child.label));
+        render_children(
+            &child.children,
+            &format!("{}{}", prefix, continuation),
+            output,
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use colored::Colorize;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{self, ClearType},
+};
+use rayon::prelude::*;
+use std::fs;
+use std::io::{self, Write, stdout};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+use tabled::Tabled;
+
+use crate::{ConfigError, calculate_dir_size, format_size, home_dir};
+
+/// Per-platform device support folders under `~/Library/Developer/Xcode`
+const DEVICE_SUPPORT_DIRS: &[&str] = &[
+    "iOS DeviceSupport",
+    "watchOS DeviceSupport",
+    "tvOS DeviceSupport",
+    "visionOS DeviceSupport",
+];
+
+/// What kind of Xcode data an item is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XcodeKind {
+    /// A project's build products and indexes in `DerivedData`
+    DerivedData,
+    /// Debug symbols copied from a device for one OS version
+    DeviceSupport,
+    /// A simulator whose runtime is no longer installed, removed with
+    /// `xcrun simctl delete`
+    Simulator { udid: String },
+}
+
+impl XcodeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            XcodeKind::DerivedData => "DerivedData",
+            XcodeKind::DeviceSupport => "Device support",
+            XcodeKind::Simulator { .. } => "Simulator",
+        }
+    }
+}
+
+/// Something Xcode keeps around that can be cleaned
+#[derive(Debug, Clone)]
+pub struct XcodeItem {
+    pub kind: XcodeKind,
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// Row of the Xcode report
+#[derive(Tabled, Clone)]
+pub struct XcodeEntry {
+    #[tabled(rename = "Kind")]
+    pub kind: String,
+    #[tabled(rename = "Item")]
+    pub name: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Last used")]
+    pub last_used: String,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Subdirectories of `dir` as items of `kind`
+fn dir_items(dir: &Path, kind: XcodeKind) -> Vec<XcodeItem> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| {
+            let path = entry.path();
+            // Xcode rewrites a project's info.plist whenever it opens it
+            let used = modified(&path).max(modified(&path.join("info.plist")));
+            XcodeItem {
+                kind: kind.clone(),
+                name: entry.file_name().to_string_lossy().to_string(),
+                path,
+                size: 0,
+                modified: used,
+            }
+        })
+        .collect()
+}
+
+/// "com.apple.CoreSimulator.SimRuntime.iOS-15-2" → "iOS 15.2"
+fn runtime_name(runtime: &str) -> String {
+    let short = runtime.rsplit('.').next().unwrap_or(runtime);
+    match short.split_once('-') {
+        Some((os, version)) => format!("{} {}", os, version.replace('-', ".")),
+        None => short.to_string(),
+    }
+}
+
+/// Simulators `simctl` reports as unavailable (their runtime was removed)
+///
+/// Without `xcrun` (not macOS, no Xcode) there are none.
+fn unavailable_simulators() -> Vec<XcodeItem> {
+    let Some(list) = Command::new("xcrun")
+        .args(["simctl", "list", "devices", "--json"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| serde_json::from_slice::<serde_json::Value>(&output.stdout).ok())
+    else {
+        return Vec::new();
+    };
+
+    let Some(runtimes) = list.get("devices").and_then(|d| d.as_object()) else {
+        return Vec::new();
+    };
+    runtimes
+        .iter()
+        .flat_map(|(runtime, devices)| {
+            devices
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|device| device.get("isAvailable").and_then(|a| a.as_bool()) == Some(false))
+                .filter_map(move |device| {
+                    let udid = device.get("udid")?.as_str()?.to_string();
+                    let name = device.get("name").and_then(|n| n.as_str()).unwrap_or("?");
+                    // dataPath is <device>/data; the device directory holds it all
+                    let path = device
+                        .get("dataPath")
+                        .and_then(|p| p.as_str())
+                        .map(PathBuf::from)
+                        .and_then(|data| data.parent().map(Path::to_path_buf))?;
+                    Some(XcodeItem {
+                        kind: XcodeKind::Simulator { udid },
+                        name: format!("{} ({})", name, runtime_name(runtime)),
+                        modified: modified(&path),
+                        path,
+                        size: 0,
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Find DerivedData projects, device support folders and unavailable
+/// simulators, measured in parallel, largest first
+pub fn find_xcode_items() -> Result<Vec<XcodeItem>, ConfigError> {
+    let xcode = home_dir()?.join("Library/Developer/Xcode");
+
+    let mut items = dir_items(&xcode.join("DerivedData"), XcodeKind::DerivedData);
+    for dir in DEVICE_SUPPORT_DIRS {
+        let mut support = dir_items(&xcode.join(dir), XcodeKind::DeviceSupport);
+        let platform = dir.trim_end_matches(" DeviceSupport");
+        for item in &mut support {
+            if !item.name.starts_with(platform) {
+                item.name = format!("{} {}", platform, item.name);
+            }
+        }
+        items.extend(support);
+    }
+    items.extend(unavailable_simulators());
+
+    items
+        .par_iter_mut()
+        .for_each(|item| item.size = calculate_dir_size(&item.path));
+    items.retain(|item| item.size > 0);
+    items.sort_by_key(|item| std::cmp::Reverse(item.size));
+    Ok(items)
+}
+
+fn age(modified: Option<SystemTime>) -> String {
+    match modified
+        .and_then(|m| SystemTime::now().duration_since(m).ok())
+        .map(|d| d.as_secs() / 86400)
+    {
+        Some(0) => "today".to_string(),
+        Some(days) => format!("{}d ago", days),
+        None => "-".to_string(),
+    }
+}
+
+/// Table row for an item
+pub fn xcode_entry(item: &XcodeItem) -> XcodeEntry {
+    XcodeEntry {
+        kind: item.kind.label().to_string(),
+        name: item.name.clone(),
+        size: format_size(item.size),
+        last_used: age(item.modified),
+    }
+}
+
+/// Let the user pick Xcode items to clean, none selected to start with
+///
+/// Returns `None` when the selection is cancelled.
+pub fn select_xcode_items(items: &[XcodeItem]) -> io::Result<Option<Vec<XcodeItem>>> {
+    let mut selected = vec![false; items.len()];
+    let mut selected_idx: usize = 0;
+    let mut stdout = stdout();
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let confirmed = loop {
+        execute!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(ClearType::All)
+        )?;
+
+        let selected_size: u64 = items
+            .iter()
+            .zip(&selected)
+            .filter(|(_, s)| **s)
+            .map(|(item, _)| item.size)
+            .sum();
+        write!(
+            stdout,
+            "{}\r\n",
+            "🧹 Xcode Cleanup - Select Items".bold().cyan()
+        )?;
+        write!(stdout, "{}\r\n", "─".repeat(80).dimmed())?;
+        write!(
+            stdout,
+            "Selected: {} ({}) | {}=Toggle {}=All {}=None {}=Clean {}=Quit\r\n",
+            selected.iter().filter(|s| **s).count().to_string().green(),
+            format_size(selected_size).green(),
+            "Space".yellow(),
+            "a".yellow(),
+            "n".yellow(),
+            "Enter".yellow(),
+            "q".yellow()
+        )?;
+        write!(stdout, "{}\r\n", "─".repeat(80).dimmed())?;
+
+        let term_height = terminal::size()?.1 as usize;
+        let list_height = term_height.saturating_sub(6).max(1);
+        let start_idx = (selected_idx + 1).saturating_sub(list_height);
+
+        for (idx, item) in items.iter().enumerate().skip(start_idx).take(list_height) {
+            let checkbox = if selected[idx] { "[✓]" } else { "[ ]" };
+            let name = if item.name.chars().count() > 40 {
+                format!("{}...", item.name.chars().take(37).collect::<String>())
+            } else {
+                item.name.clone()
+            };
+            let line = format!(
+                " {} {:<14} {:<40} {:>10} {:>9}",
+                checkbox,
+                item.kind.label(),
+                name,
+                format_size(item.size),
+                age(item.modified)
+            );
+
+            if idx == selected_idx {
+                write!(stdout, "{}\r\n", line.on_bright_blue().white())?;
+            } else if selected[idx] {
+                write!(stdout, "{}\r\n", line.red())?;
+            } else {
+                write!(stdout, "{}\r\n", line.dimmed())?;
+            }
+        }
+        stdout.flush()?;
+
+        let Event::Key(key_event) = event::read()? else {
+            continue;
+        };
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => selected_idx = selected_idx.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if selected_idx + 1 < items.len() => {
+                selected_idx += 1;
+            }
+            KeyCode::Char(' ') => selected[selected_idx] = !selected[selected_idx],
+            KeyCode::Char('a') => selected.fill(true),
+            KeyCode::Char('n') => selected.fill(false),
+            KeyCode::Enter => break true,
+            KeyCode::Char('q') | KeyCode::Esc => break false,
+            KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                break false;
+            }
+            _ => {}
+        }
+    };
+
+    execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
+    terminal::disable_raw_mode()?;
+
+    if !confirmed {
+        return Ok(None);
+    }
+    Ok(Some(
+        items
+            .iter()
+            .zip(selected)
+            .filter(|(_, s)| *s)
+            .map(|(item, _)| item.clone())
+            .collect(),
+    ))
+}
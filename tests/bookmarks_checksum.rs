@@ -0,0 +1,178 @@
+//! Chrome rejects a Bookmarks file whose `checksum` doesn't match its
+//! contents, so every subcommand that rewrites the file must update it.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use tempfile::TempDir;
+use utils::bookmarks_checksum;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/Bookmarks");
+const BOOKMARKS_PATH: &str = "Library/Application Support/Google/Chrome/Default/Bookmarks";
+
+/// A throwaway home directory holding a copy of the fixture Bookmarks file
+fn fixture_home() -> (TempDir, PathBuf) {
+    let home = TempDir::new().unwrap();
+    let path = home.path().join(BOOKMARKS_PATH);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::copy(FIXTURE, &path).unwrap();
+    (home, path)
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn urls(json: &serde_json::Value) -> Vec<String> {
+    fn walk(node: &serde_json::Value, out: &mut Vec<String>) {
+        if let Some(url) = node.get("url").and_then(|u| u.as_str()) {
+            out.push(url.to_string());
+        }
+        for child in node
+            .get("children")
+            .and_then(|c| c.as_array())
+            .into_iter()
+            .flatten()
+        {
+            walk(child, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    for root in json["roots"].as_object().unwrap().values() {
+        walk(root, &mut out);
+    }
+    out
+}
+
+fn run_bookmarks(home: &Path, subcommand: &str) {
+    let output = Command::new(env!("CARGO_BIN_EXE_shell-explorer"))
+        .args(["--mode", "bookmarks", "--subcommand", subcommand, "--yes"])
+        .args(["--timeout", "2"])
+        .env("HOME", home)
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{subcommand} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn assert_valid_checksum(path: &Path) {
+    let json = read_json(path);
+    assert_eq!(
+        json["checksum"].as_str(),
+        Some(bookmarks_checksum(&json).as_str()),
+        "stale checksum in {}",
+        path.display()
+    );
+}
+
+#[test]
+fn checksum_matches_chrome() {
+    let json = read_json(Path::new(FIXTURE));
+    assert_eq!(
+        bookmarks_checksum(&json),
+        "82069f09e88cb2a7edc5ae1b5bdeb76d"
+    );
+    assert_eq!(json["checksum"], "82069f09e88cb2a7edc5ae1b5bdeb76d");
+}
+
+#[test]
+fn checksum_hashes_titles_as_utf16() {
+    // Expected value from Python's hashlib over the same id/UTF-16LE title/url bytes
+    let json = serde_json::json!({
+        "roots": {
+            "bookmark_bar": {
+                "id": "1",
+                "name": "Bookmarks bar",
+                "type": "folder",
+                "children": [{
+                    "id": "4",
+                    "name": "Café – Über die Geschichte des Kaffees",
+                    "type": "url",
+                    "url": "https://de.wikipedia.org/wiki/Kaffee#Geschichte"
+                }]
+            },
+            "other": { "id": "2", "name": "Other bookmarks", "type": "folder", "children": [] },
+            "synced": { "id": "3", "name": "Mobile bookmarks", "type": "folder", "children": [] }
+        }
+    });
+    assert_eq!(
+        bookmarks_checksum(&json),
+        "673cbbd487339b4c4de717fefe4af415"
+    );
+}
+
+#[test]
+fn checksum_changes_with_contents() {
+    let mut json = read_json(Path::new(FIXTURE));
+    let before = bookmarks_checksum(&json);
+
+    json["roots"]["bookmark_bar"]["children"][0]["name"] = "Rust lang".into();
+    assert_ne!(bookmarks_checksum(&json), before);
+
+    // Metadata outside the hashed fields doesn't count
+    json["roots"]["bookmark_bar"]["children"][0]["name"] = "Rust".into();
+    json["roots"]["bookmark_bar"]["children"][0]["date_last_used"] = "1".into();
+    assert_eq!(bookmarks_checksum(&json), before);
+}
+
+#[test]
+fn remove_dupes_rewrites_checksum() {
+    let (home, path) = fixture_home();
+    run_bookmarks(home.path(), "remove-dupes");
+
+    let json = read_json(&path);
+    let urls = urls(&json);
+    assert_eq!(urls.len(), 5);
+    assert_eq!(
+        urls.iter()
+            .filter(|u| *u == "https://www.rust-lang.org/")
+            .count(),
+        1
+    );
+    assert_ne!(json["checksum"], "82069f09e88cb2a7edc5ae1b5bdeb76d");
+    assert_valid_checksum(&path);
+
+    // The backup is the untouched original
+    let backup = PathBuf::from(format!("{}.backup", path.display()));
+    assert_eq!(
+        fs::read_to_string(backup).unwrap(),
+        fs::read_to_string(FIXTURE).unwrap()
+    );
+}
+
+#[test]
+fn remove_dead_rewrites_checksum() {
+    let (home, path) = fixture_home();
+
+    // Serve every link from a local server; only 127.0.0.1:1 stays unreachable
+    let server = TcpListener::bind("127.0.0.1:0").unwrap();
+    let local = format!("http://{}/", server.local_addr().unwrap());
+    thread::spawn(move || {
+        for mut stream in server.incoming().flatten() {
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request);
+            let _ = stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok");
+        }
+    });
+    let content = fs::read_to_string(&path).unwrap();
+    fs::write(&path, content.replace("https://", &local)).unwrap();
+
+    run_bookmarks(home.path(), "remove-dead");
+
+    let json = read_json(&path);
+    let urls = urls(&json);
+    assert_eq!(urls.len(), 7);
+    assert!(!urls.iter().any(|u| u.starts_with("http://127.0.0.1:1/")));
+    assert_valid_checksum(&path);
+}
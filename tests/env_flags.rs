@@ -0,0 +1,49 @@
+//! Boolean flags read from `SHELL_EXPLORER_<FLAG>` are on for any value but
+//! an empty or false-like one, not only for `true`.
+
+use std::fs;
+use std::process::{Command, Stdio};
+
+use tempfile::TempDir;
+
+/// Organize a folder holding one file with `SHELL_EXPLORER_DRY_RUN` set to
+/// `value`; returns whether the run was a dry run
+fn dry_run_with(value: &str) -> bool {
+    let home = TempDir::new().unwrap();
+    let folder = home.path().join("inbox");
+    fs::create_dir_all(&folder).unwrap();
+    fs::write(folder.join("notes.txt"), "notes").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_shell-explorer"))
+        .args(["--mode", "organize", "--min-age", "0", "--yes", "--path"])
+        .arg(&folder)
+        .env("HOME", home.path())
+        .env("NO_COLOR", "1")
+        .env("SHELL_EXPLORER_DRY_RUN", value)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "SHELL_EXPLORER_DRY_RUN={value:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let dry_run = String::from_utf8_lossy(&output.stdout).contains("Dry run mode");
+    assert_eq!(dry_run, folder.join("notes.txt").exists());
+    dry_run
+}
+
+#[test]
+fn truthy_values_enable_the_flag() {
+    for value in ["1", "yes", "true", "on"] {
+        assert!(dry_run_with(value), "{value:?} should enable --dry-run");
+    }
+}
+
+#[test]
+fn empty_and_false_values_leave_the_flag_off() {
+    for value in ["", "0", "no", "false", "off"] {
+        assert!(!dry_run_with(value), "{value:?} should leave --dry-run off");
+    }
+}